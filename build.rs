@@ -0,0 +1,31 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "capi")]
+    write_header();
+}
+
+/// Writes the C header of the `capi` module to `chidog.h` in `OUT_DIR`, and
+/// also in the directory named by `CHIDOG_HEADER_DIR` if it is set, so that
+/// builds never write to the source tree unless asked to.
+#[cfg(feature = "capi")]
+fn write_header() {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-env-changed=CHIDOG_HEADER_DIR");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("CHIDOG_H".to_string()),
+        cpp_compat: true,
+        ..Default::default()
+    };
+    let bindings = cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{crate_dir}/src/capi.rs"))
+        .generate()
+        .expect("cannot generate the C header");
+    bindings.write_to_file(format!("{out_dir}/chidog.h"));
+    if let Ok(dir) = std::env::var("CHIDOG_HEADER_DIR") {
+        bindings.write_to_file(format!("{dir}/chidog.h"));
+    }
+}
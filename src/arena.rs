@@ -0,0 +1,85 @@
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use bumpalo::Bump;
+
+/// How many times the memory of its live terms an [`Arena`] may hold before
+/// [`Arena::is_mostly_garbage`] asks for it to be compacted.
+const GARBAGE_FACTOR: usize = 4;
+
+/// The smallest arena, in bytes, worth compacting.
+const MIN_COMPACTION: usize = 1 << 20;
+
+/// The bump arenas of a polynomial ring, lent out to computations which
+/// create many short-lived terms, such as the reductions of a Gröbner basis
+/// computation.
+///
+/// Allocating in a bump arena only advances a pointer, and nothing in it is
+/// freed until the whole arena is reset. An arena is returned to the pool
+/// when its loan ends, keeping its memory, so a later computation in the
+/// same ring allocates from memory it already has. The pool starts empty and
+/// only grows as computations ask for arenas, one per computation running
+/// at a time.
+#[derive(Default)]
+pub struct ArenaPool {
+    arenas: Mutex<Vec<Bump>>,
+}
+
+impl ArenaPool {
+    /// Lends out an arena of the pool, or a new one if all are lent.
+    pub fn lend(&self) -> Arena<'_> {
+        let bump = self.arenas.lock().unwrap().pop().unwrap_or_default();
+        Arena { bump, pool: self }
+    }
+
+    /// Frees the memory of the arenas not lent out.
+    pub fn shrink(&self) {
+        self.arenas.lock().unwrap().clear();
+    }
+}
+
+/// A bump arena lent out by an [`ArenaPool`], which allocates the buckets of
+/// a [`Geobucket`](crate::geobucket::Geobucket) through
+/// [`Geobucket::new_in`](crate::geobucket::Geobucket::new_in).
+///
+/// The buckets that merges leave behind stay in the arena until it is reset,
+/// so a long computation compacts it from time to time: it moves its live
+/// terms out with [`Geobucket::into_terms`](crate::geobucket::Geobucket::into_terms),
+/// resets the arena and continues with a new geobucket holding the
+/// survivors.
+pub struct Arena<'p> {
+    bump: Bump,
+    pool: &'p ArenaPool,
+}
+
+impl Arena<'_> {
+    /// Whether the arena holds so much more than the `live` bytes still in
+    /// use that it should be compacted.
+    pub fn is_mostly_garbage(&self, live: usize) -> bool {
+        let allocated = self.bump.allocated_bytes();
+        allocated >= MIN_COMPACTION && allocated > GARBAGE_FACTOR * live
+    }
+
+    /// Frees everything allocated in the arena, keeping its largest chunk of
+    /// memory for further allocations.
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+}
+
+impl Deref for Arena<'_> {
+    type Target = Bump;
+
+    fn deref(&self) -> &Bump {
+        &self.bump
+    }
+}
+
+/// Resets the arena and returns it to its pool.
+impl Drop for Arena<'_> {
+    fn drop(&mut self) {
+        let mut bump = std::mem::take(&mut self.bump);
+        bump.reset();
+        self.pool.arenas.lock().unwrap().push(bump);
+    }
+}
@@ -0,0 +1,456 @@
+use std::io::Read;
+use std::marker::PhantomData;
+use std::process::ExitCode;
+
+use chidog::cas::{CoefficientFormat, to_maple, to_wolfram};
+use chidog::groebner::groebner_basis;
+use chidog::macaulay2::to_m2;
+use chidog::openmath::to_openmath;
+use chidog::order::{GRevLex, GrLex, Lex};
+use chidog::sexpr::to_sexpr;
+use chidog::singular::to_singular;
+use chidog::univariate::DenseUnivariate;
+use chidog::{AlreadyRing, ChidogError, Monomial, Polynomial, PolynomialRing};
+use num::{BigRational, One, Zero};
+use smallvec::smallvec;
+
+#[cfg(feature = "repl")]
+mod repl;
+
+const USAGE: &str = "\
+usage: chidog <command> [options] [polynomial...]
+
+commands:
+  eval        substitute the values of --at into each polynomial
+  gcd         the monic greatest common divisor of the polynomials
+  factor      the factorization of a univariate polynomial into
+              irreducible polynomials over the rationals
+  groebner    the reduced Groebner basis of the polynomials
+  resultant   the resultant of two univariate polynomials
+  repl        an interactive session, if built with the `repl` feature
+
+options:
+  -f, --file <path>      read polynomials from a file, one per line, or
+                         from standard input if <path> is -
+  -v, --vars <x,y,...>   the variables, in decreasing order of significance;
+                         by default those of the input, sorted by name
+  -o, --format <format>  write results as infix (default), sympy, srepr,
+                         maple, wolfram, singular, m2, sexpr or openmath
+      --at <x=1,...>     the values for eval, which may be expressions
+      --order <order>    the monomial order for groebner: lex, grlex or
+                         grevlex (default)
+  -h, --help             print this message
+
+Polynomials have rational coefficients and are written as in
+`x^2*y - 3/4*z + 1`, with `^` or `**` for powers; those starting with `-`
+and a letter follow `--`. Standard input is read when no polynomial is
+given.";
+
+type Ring<'a> = PolynomialRing<'a, AlreadyRing<BigRational>, String>;
+type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+/// An error of the command line, reported with the usage if `usage`.
+struct CliError {
+    message: String,
+    usage: bool,
+}
+
+impl From<ChidogError> for CliError {
+    fn from(e: ChidogError) -> Self {
+        CliError {
+            message: e.to_string(),
+            usage: false,
+        }
+    }
+}
+
+fn usage_error(message: impl Into<String>) -> CliError {
+    CliError {
+        message: message.into(),
+        usage: true,
+    }
+}
+
+fn error(message: impl Into<String>) -> CliError {
+    CliError {
+        message: message.into(),
+        usage: false,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Infix,
+    Sympy,
+    Srepr,
+    Maple,
+    Wolfram,
+    Singular,
+    Macaulay2,
+    SExpr,
+    OpenMath,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Self, CliError> {
+        Ok(match name {
+            "infix" => Format::Infix,
+            "sympy" => Format::Sympy,
+            "srepr" => Format::Srepr,
+            "maple" => Format::Maple,
+            "wolfram" => Format::Wolfram,
+            "singular" => Format::Singular,
+            "m2" => Format::Macaulay2,
+            "sexpr" => Format::SExpr,
+            "openmath" => Format::OpenMath,
+            _ => return Err(usage_error(format!("unknown format `{name}`"))),
+        })
+    }
+
+    fn write(self, f: &Poly) -> String {
+        match self {
+            Format::Infix => f.to_sage(),
+            Format::Sympy => f.to_sympy(),
+            Format::Srepr => f.to_sympy_srepr(),
+            Format::Maple => to_maple(f, CoefficientFormat::Exact),
+            Format::Wolfram => to_wolfram(f, CoefficientFormat::Exact),
+            Format::Singular => to_singular(f),
+            Format::Macaulay2 => to_m2(f),
+            Format::SExpr => to_sexpr(f),
+            Format::OpenMath => to_openmath(f),
+        }
+    }
+}
+
+/// How results are computed and written, set by options or, in the REPL,
+/// by commands.
+struct Settings {
+    format: Format,
+    order: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            format: Format::Infix,
+            order: "grevlex".to_string(),
+        }
+    }
+}
+
+/// The parsed command line.
+struct Args {
+    command: String,
+    inputs: Vec<String>,
+    files: Vec<String>,
+    vars: Option<Vec<String>>,
+    at: Option<String>,
+    settings: Settings,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let command = args.next().ok_or_else(|| usage_error("missing command"))?;
+        let mut parsed = Args {
+            command,
+            inputs: Vec::new(),
+            files: Vec::new(),
+            vars: None,
+            at: None,
+            settings: Settings::default(),
+        };
+        let mut options = true;
+        while let Some(arg) = args.next() {
+            if !options {
+                parsed.inputs.push(arg);
+                continue;
+            }
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| usage_error(format!("missing value for `{arg}`")))
+            };
+            match arg.as_str() {
+                "-f" | "--file" => parsed.files.push(value()?),
+                "-v" | "--vars" => {
+                    let vars = value()?.split(',').map(|v| v.trim().to_string()).collect();
+                    parsed.vars = Some(vars);
+                }
+                "-o" | "--format" => parsed.settings.format = Format::parse(&value()?)?,
+                "--at" => parsed.at = Some(value()?),
+                "--order" => parsed.settings.order = value()?,
+                "--" => options = false,
+                _ if arg.starts_with('-') && arg.len() > 1 && !is_number(&arg) => {
+                    return Err(usage_error(format!("unknown option `{arg}`")));
+                }
+                _ => parsed.inputs.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Whether `arg` starts like a negated number or expression, such as `-3*x`
+/// or `-(x+1)`, rather than an option.
+fn is_number(arg: &str) -> bool {
+    arg[1..].starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '(')
+}
+
+/// The polynomials of the file at `path`, or of standard input for `-`: its
+/// nonempty lines, without comments starting with `#`.
+fn read_polynomials(path: &str) -> Result<Vec<String>, CliError> {
+    let mut text = String::new();
+    let result = match path {
+        "-" => std::io::stdin().read_to_string(&mut text).map(|_| ()),
+        _ => std::fs::read_to_string(path).map(|t| text = t),
+    };
+    result.map_err(|e| error(format!("cannot read `{path}`: {e}")))?;
+    Ok(text
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// The names in `inputs`, sorted and without duplicates.
+fn names(inputs: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    for input in inputs {
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if c.is_alphabetic() || c == '_' {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    end = i + c.len_utf8();
+                }
+                names.push(input[start..end].to_string());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn parse<'a>(ring: &'a Ring<'a>, input: &str) -> Result<Poly<'a>, CliError> {
+    Poly::from_sympy(ring, input).map_err(|e| error(format!("in `{input}`: {e}")))
+}
+
+/// The index of the variable `name` of `ring`.
+fn var_index(ring: &Ring, name: &str) -> Result<usize, CliError> {
+    ring.vars
+        .iter()
+        .position(|v| v == name)
+        .ok_or_else(|| error(format!("unknown variable `{name}`")))
+}
+
+/// The constant term of `f`, or an error if `f` is not constant.
+fn constant(f: &Poly, input: &str) -> Result<BigRational, CliError> {
+    let mut value = BigRational::zero();
+    for (m, c) in f.terms() {
+        if m.powers.iter().any(|p| *p > 0) {
+            return Err(error(format!("`{input}` is not a number")));
+        }
+        value = c.clone();
+    }
+    Ok(value)
+}
+
+/// `f` with the variables with index `i` replaced by `values[i]` where it is
+/// given.
+fn substitute<'a>(f: &Poly<'a>, values: &[Option<BigRational>]) -> Result<Poly<'a>, CliError> {
+    let terms = f.terms().map(|(m, c)| {
+        let mut c = c.clone();
+        let mut powers = m.powers.clone();
+        for (p, value) in powers.iter_mut().zip(values) {
+            if let Some(value) = value {
+                c *= num::pow(value.clone(), *p as usize);
+                *p = 0;
+            }
+        }
+        (Monomial { powers }, c)
+    });
+    Ok(Poly::from_terms(f.elem_of, terms)?)
+}
+
+/// The constant polynomial `c` of `ring`.
+fn constant_polynomial<'a>(ring: &'a Ring<'a>, c: BigRational) -> Result<Poly<'a>, CliError> {
+    let powers = smallvec![0; ring.vars.len()];
+    Ok(Poly::from_terms(ring, [(Monomial { powers }, c)])?)
+}
+
+/// `fs` as univariate polynomials in the only variable occurring in them,
+/// and the index of that variable, which is 0 if none occurs.
+type Univariate = (usize, Vec<DenseUnivariate<BigRational>>);
+
+fn univariate(ring: &Ring, fs: &[Poly]) -> Result<Univariate, CliError> {
+    let occurring: Vec<usize> = (0..ring.vars.len())
+        .filter(|&i| fs.iter().any(|f| f.terms().any(|(m, _)| m.powers[i] > 0)))
+        .collect();
+    let var = match occurring[..] {
+        [] => 0,
+        [i] => i,
+        _ => return Err(error("the polynomials are not univariate")),
+    };
+    let dense = fs
+        .iter()
+        .map(|f| match ring.vars.is_empty() {
+            // The constants of the ring without variables.
+            true => DenseUnivariate::new(f.terms().map(|(_, c)| c.clone()).collect()),
+            false => DenseUnivariate::from_polynomial(f, var).unwrap(),
+        })
+        .collect();
+    Ok((var, dense))
+}
+
+fn run(mut args: Args) -> Result<Vec<String>, CliError> {
+    if args.command == "repl" {
+        if !args.inputs.is_empty() || !args.files.is_empty() {
+            return Err(usage_error("repl takes no polynomials"));
+        }
+        #[cfg(feature = "repl")]
+        return repl::run(args.vars.unwrap_or_default(), args.settings).map(|()| Vec::new());
+        #[cfg(not(feature = "repl"))]
+        return Err(error("built without the `repl` feature"));
+    }
+    if args.inputs.is_empty() && args.files.is_empty() {
+        args.files.push("-".to_string());
+    }
+    for path in &args.files {
+        args.inputs.extend(read_polynomials(path)?);
+    }
+    let vars = args.vars.clone().unwrap_or_else(|| {
+        let mut inputs = args.inputs.clone();
+        inputs.extend(args.at.iter().cloned());
+        names(&inputs)
+    });
+    let base = AlreadyRing {
+        phantom: PhantomData::<BigRational>,
+    };
+    let ring = PolynomialRing::new(vars, &base);
+    let fs = args
+        .inputs
+        .iter()
+        .map(|input| parse(&ring, input))
+        .collect::<Result<Vec<_>, _>>()?;
+    execute(
+        &args.command,
+        &ring,
+        &fs,
+        args.at.as_deref(),
+        &args.settings,
+    )
+}
+
+/// The lines of the result of `command` on `fs`, with the assignments `at`
+/// of `eval`, such as `x=1, y=2/3`.
+fn execute<'a>(
+    command: &str,
+    ring: &'a Ring<'a>,
+    fs: &[Poly<'a>],
+    at: Option<&str>,
+    settings: &Settings,
+) -> Result<Vec<String>, CliError> {
+    let write = |f: &Poly| settings.format.write(f);
+    match command {
+        "eval" => {
+            let at = at.ok_or_else(|| usage_error("eval needs --at"))?;
+            let mut values = vec![None; ring.vars.len()];
+            for assignment in at.split(',') {
+                let (name, value) = assignment
+                    .split_once('=')
+                    .ok_or_else(|| usage_error(format!("invalid assignment `{assignment}`")))?;
+                let index = var_index(ring, name.trim())?;
+                values[index] = Some(constant(&parse(ring, value)?, value)?);
+            }
+            fs.iter()
+                .map(|f| Ok(write(&substitute(f, &values)?)))
+                .collect()
+        }
+        "gcd" => {
+            let (first, rest) = fs
+                .split_first()
+                .ok_or_else(|| usage_error("gcd needs a polynomial"))?;
+            let g = rest.iter().fold(first.monic(), |g, f| g.gcd(f));
+            Ok(vec![write(&g)])
+        }
+        "factor" => {
+            let [f] = fs else {
+                return Err(usage_error("factor needs one polynomial"));
+            };
+            let (var, dense) = univariate(ring, fs)?;
+            let (c, irreducible) = dense[0].factor();
+            let irreducible: Vec<_> = irreducible
+                .into_iter()
+                .map(|(g, e)| {
+                    let g = g.map(|a| BigRational::from_integer(a.clone()));
+                    (g.to_polynomial(ring, var), e)
+                })
+                .collect();
+            if let [(g, 1)] = &irreducible[..]
+                && c.is_one()
+            {
+                return Ok(vec![write(g)]);
+            }
+            if irreducible.is_empty() {
+                return Ok(vec![write(f)]);
+            }
+            let mut factors = Vec::new();
+            if !c.is_one() {
+                factors.push(write(&constant_polynomial(ring, c)?));
+            }
+            for (g, e) in &irreducible {
+                let g = match g.num_terms() {
+                    1 => write(g),
+                    _ => format!("({})", write(g)),
+                };
+                factors.push(match e {
+                    1 => g,
+                    _ => format!("{g}^{e}"),
+                });
+            }
+            Ok(vec![factors.join("*")])
+        }
+        "groebner" => {
+            let basis = match settings.order.as_str() {
+                "lex" => groebner_basis(fs, &Lex),
+                "grlex" => groebner_basis(fs, &GrLex),
+                "grevlex" => groebner_basis(fs, &GRevLex),
+                order => return Err(usage_error(format!("unknown order `{order}`"))),
+            };
+            Ok(basis.iter().map(write).collect())
+        }
+        "resultant" => {
+            if fs.len() != 2 {
+                return Err(usage_error("resultant needs two polynomials"));
+            }
+            let (_, dense) = univariate(ring, fs)?;
+            let res = dense[0].resultant(&dense[1]);
+            Ok(vec![write(&constant_polynomial(ring, res)?)])
+        }
+        command => Err(usage_error(format!("unknown command `{command}`"))),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().is_none_or(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    match Args::parse(args).and_then(run) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{line}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chidog: {}", e.message);
+            if e.usage {
+                eprintln!("{USAGE}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
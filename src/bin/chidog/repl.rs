@@ -0,0 +1,320 @@
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use chidog::{AlreadyRing, Monomial, PolynomialRing};
+use num::BigRational;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::{CliError, Format, Poly, Ring, Settings, constant_polynomial, error, execute, parse};
+
+const HELP: &str = "\
+  :ring x, y, z          declare the variables, in decreasing order of
+                         significance, keeping the bindings which remain valid
+  :ring                  show the variables
+  :bindings              show the bindings
+  name = <expr>          bind name to the expanded expression
+  <expr>                 print the expanded expression
+  :eval <expr>, x=1, ... substitute values into the expression
+  :gcd <expr>, ...       the monic greatest common divisor
+  :factor <expr>         the irreducible factors of a univariate expression
+  :groebner <expr>, ...  the reduced Groebner basis
+  :resultant <expr>, <expr>
+                         the resultant of two univariate expressions
+  :format <format>       write results as infix, sympy, srepr, maple, wolfram,
+                         singular, m2, sexpr or openmath
+  :order <order>         the monomial order of :groebner: lex, grlex or grevlex
+  :help                  print this message
+  :quit                  leave, as does Ctrl-D
+
+Expressions may use the variables and the bound names.";
+
+const COMMANDS: &[&str] = &[
+    ":bindings",
+    ":eval",
+    ":factor",
+    ":format",
+    ":gcd",
+    ":groebner",
+    ":help",
+    ":order",
+    ":quit",
+    ":resultant",
+    ":ring",
+];
+
+/// Completes commands and the names of the variables and bindings.
+struct Completion {
+    names: Vec<String>,
+}
+
+impl Completer for Completion {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == ':')
+            .last()
+            .map_or(pos, |(i, _)| i);
+        let word = &line[start..pos];
+        let candidates = match word.starts_with(':') {
+            true => COMMANDS.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+            false => self.names.clone(),
+        };
+        let candidates = candidates
+            .into_iter()
+            .filter(|c| c.starts_with(word))
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for Completion {
+    type Hint = String;
+}
+
+impl Highlighter for Completion {}
+
+impl Validator for Completion {}
+
+impl Helper for Completion {}
+
+/// Whether `name` can name a variable or a binding.
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// The variables, bindings and settings of a session.
+struct Session {
+    vars: Vec<String>,
+    /// The bound names and their values, written in the infix format.
+    bindings: Vec<(String, String)>,
+    settings: Settings,
+}
+
+impl Session {
+    /// The names to complete.
+    fn names(&self) -> Vec<String> {
+        let bound = self.bindings.iter().map(|(name, _)| name.clone());
+        self.vars.iter().cloned().chain(bound).collect()
+    }
+
+    /// The polynomial of `input`, in which the bound names stand for their
+    /// values.
+    fn parse<'a>(
+        &self,
+        ring: &'a Ring<'a>,
+        bindings: &[Poly<'a>],
+        input: &str,
+    ) -> Result<Poly<'a>, CliError> {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let extended = PolynomialRing::new(self.names(), &base);
+        let f = parse(&extended, input)?;
+        let n = ring.vars.len();
+        let mut sum = constant_polynomial(ring, BigRational::default())?;
+        for (m, c) in f.terms() {
+            let mut powers = m.powers.clone();
+            powers.truncate(n);
+            let mut term = Poly::from_terms(ring, [(Monomial { powers }, c.clone())])?;
+            for (b, p) in bindings.iter().zip(&m.powers[n..]) {
+                for _ in 0..*p {
+                    term *= b.clone();
+                }
+            }
+            sum += term;
+        }
+        Ok(sum)
+    }
+
+    /// Declares the variables `vars`, dropping the bindings which use other
+    /// variables or are named as one of them, and returns the messages.
+    fn declare(&mut self, vars: Vec<String>) -> Vec<String> {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(vars.clone(), &base);
+        let mut messages = Vec::new();
+        self.bindings.retain(|(name, value)| {
+            let keep = !vars.contains(name) && parse(&ring, value).is_ok();
+            if !keep {
+                messages.push(format!("dropped {name}"));
+            }
+            keep
+        });
+        self.vars = vars;
+        messages
+    }
+
+    /// The lines printed in response to `line`.
+    fn handle(&mut self, line: &str) -> Result<Vec<String>, CliError> {
+        if let Some(command) = line.strip_prefix(':') {
+            let (command, rest) = command
+                .split_once(char::is_whitespace)
+                .unwrap_or((command, ""));
+            let rest = rest.trim();
+            match command {
+                "ring" if rest.is_empty() && self.vars.is_empty() => {
+                    return Ok(vec!["no variables".to_string()]);
+                }
+                "ring" if rest.is_empty() => {
+                    return Ok(vec![format!("ring {}", self.vars.join(", "))]);
+                }
+                "ring" => {
+                    let vars: Vec<String> = rest.split(',').map(|v| v.trim().to_string()).collect();
+                    if let Some(v) = vars.iter().find(|v| !is_name(v)) {
+                        return Err(error(format!("invalid variable `{v}`")));
+                    }
+                    if let Some((_, v)) = vars
+                        .iter()
+                        .enumerate()
+                        .find(|(i, v)| vars[..*i].contains(v))
+                    {
+                        return Err(error(format!("duplicate variable `{v}`")));
+                    }
+                    return Ok(self.declare(vars));
+                }
+                "bindings" => {
+                    return Ok(self
+                        .bindings
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}"))
+                        .collect());
+                }
+                "format" => {
+                    self.settings.format = Format::parse(rest)?;
+                    return Ok(Vec::new());
+                }
+                "order" => {
+                    if !["lex", "grlex", "grevlex"].contains(&rest) {
+                        return Err(error(format!("unknown order `{rest}`")));
+                    }
+                    self.settings.order = rest.to_string();
+                    return Ok(Vec::new());
+                }
+                "help" => return Ok(vec![HELP.to_string()]),
+                _ => {}
+            }
+            let base = AlreadyRing {
+                phantom: PhantomData::<BigRational>,
+            };
+            let ring = PolynomialRing::new(self.vars.clone(), &base);
+            let bindings = self.bound(&ring)?;
+            let (assignments, inputs): (Vec<&str>, Vec<&str>) = rest
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .partition(|s| s.contains('='));
+            let fs = inputs
+                .iter()
+                .map(|input| self.parse(&ring, &bindings, input))
+                .collect::<Result<Vec<_>, _>>()?;
+            let at = (!assignments.is_empty()).then(|| assignments.join(","));
+            return execute(command, &ring, &fs, at.as_deref(), &self.settings);
+        }
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(self.vars.clone(), &base);
+        let bindings = self.bound(&ring)?;
+        if let Some((name, input)) = line.split_once('=')
+            && is_name(name.trim())
+        {
+            let name = name.trim();
+            if self.vars.iter().any(|v| v == name) {
+                return Err(error(format!("cannot bind the variable `{name}`")));
+            }
+            let f = self.parse(&ring, &bindings, input)?;
+            let line = format!("{name} = {}", self.settings.format.write(&f));
+            let value = f.to_sage();
+            match self.bindings.iter_mut().find(|(n, _)| n == name) {
+                Some(binding) => binding.1 = value,
+                None => self.bindings.push((name.to_string(), value)),
+            }
+            return Ok(vec![line]);
+        }
+        let f = self.parse(&ring, &bindings, line)?;
+        Ok(vec![self.settings.format.write(&f)])
+    }
+
+    /// The values of the bindings in `ring`.
+    fn bound<'a>(&self, ring: &'a Ring<'a>) -> Result<Vec<Poly<'a>>, CliError> {
+        self.bindings
+            .iter()
+            .map(|(_, value)| parse(ring, value))
+            .collect()
+    }
+}
+
+/// The file keeping the history between sessions.
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".chidog_history"))
+}
+
+/// Runs an interactive session in the variables `vars` until it is left.
+pub(crate) fn run(vars: Vec<String>, settings: Settings) -> Result<(), CliError> {
+    let mut session = Session {
+        vars,
+        bindings: Vec::new(),
+        settings,
+    };
+    let mut editor: Editor<Completion, DefaultHistory> =
+        Editor::new().map_err(|e| error(e.to_string()))?;
+    editor.set_helper(Some(Completion {
+        names: session.names(),
+    }));
+    let history = history_path();
+    if let Some(path) = &history {
+        // There is no history before the first session.
+        let _ = editor.load_history(path);
+    }
+    if session.vars.is_empty() {
+        println!("declare the variables with `:ring x, y, z`; `:help` lists the commands");
+    }
+    loop {
+        let line = match editor.readline("chidog> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(error(e.to_string())),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        if line == ":quit" {
+            break;
+        }
+        match session.handle(line) {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{line}");
+                }
+            }
+            Err(e) => eprintln!("error: {}", e.message),
+        }
+        editor.helper_mut().unwrap().names = session.names();
+    }
+    if let Some(path) = &history {
+        editor
+            .save_history(path)
+            .map_err(|e| error(format!("cannot save the history: {e}")))?;
+    }
+    Ok(())
+}
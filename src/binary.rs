@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::io::{self, Read, Write};
+
+use num::bigint::Sign;
+use num::{BigInt, BigRational, PrimInt, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::{Monomial, Polynomial, PolynomialRing, Semiring, SemiringElement};
+
+/// The bytes opening every file in the binary format.
+const MAGIC: &[u8; 4] = b"CDPL";
+
+/// The version of the binary format written by this crate.
+///
+/// Version 1 is laid out as follows, with every count and exponent an
+/// unsigned LEB128 varint:
+///
+/// - the header: [`MAGIC`], the version as one byte, and a tag byte, `P` for
+///   one polynomial or `S` for a system of polynomials;
+/// - for a system, the number of polynomials, followed by each of them;
+/// - for a polynomial, the number of variables and of terms, followed by
+///   the terms in increasing lexicographic order of their exponents;
+/// - for a term, its monomial and then its coefficient, as written by
+///   [`BinaryCoefficient::write_to`].
+///
+/// The monomial of the first term of a polynomial is written as all of its
+/// exponents. Each later monomial is delta-encoded against the previous
+/// one: the index `j` of the first exponent in which they differ, the
+/// positive difference of the exponents of `x_j`, and the exponents of the
+/// variables after `x_j`. Consecutive monomials in lexicographic order tend
+/// to share a long prefix, which this skips.
+pub const VERSION: u8 = 1;
+
+/// The `InvalidData` error wrapping `e`.
+fn invalid_data(e: ChidogError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// The error of data which does not follow the binary format.
+fn invalid(message: impl Into<String>) -> io::Error {
+    invalid_data(ChidogError::Parse(message.into()))
+}
+
+fn write_varint<W: Write + ?Sized>(w: &mut W, mut n: u64) -> io::Result<()> {
+    let mut bytes = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+    w.write_all(&bytes[..len])
+}
+
+fn read_varint<R: Read + ?Sized>(r: &mut R) -> io::Result<u64> {
+    let mut n = 0;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        r.read_exact(&mut byte)?;
+        let bits = (byte[0] & 0x7f) as u64;
+        if shift == 63 && bits > 1 {
+            break;
+        }
+        n |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(invalid("varint overflows 64 bits"))
+}
+
+/// A count read as a varint, which must fit in `usize`.
+fn read_len<R: Read + ?Sized>(r: &mut R) -> io::Result<usize> {
+    usize::try_from(read_varint(r)?).map_err(|_| invalid("length overflows usize"))
+}
+
+fn write_header<W: Write + ?Sized>(w: &mut W, tag: u8) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&[VERSION, tag])
+}
+
+fn read_header<R: Read + ?Sized>(r: &mut R, tag: u8) -> io::Result<()> {
+    let mut header = [0; 6];
+    r.read_exact(&mut header)?;
+    if header[..4] != MAGIC[..] {
+        return Err(invalid("not a chidog binary file"));
+    }
+    if header[4] != VERSION {
+        return Err(invalid(format!("unsupported version {}", header[4])));
+    }
+    if header[5] != tag {
+        return Err(invalid(format!(
+            "expected tag `{}`, found `{}`",
+            tag as char, header[5] as char
+        )));
+    }
+    Ok(())
+}
+
+/// Coefficients which can be written in the binary format.
+pub trait BinaryCoefficient: Sized {
+    fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()>;
+
+    fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self>;
+}
+
+/// Unsigned integers are written as varints.
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl BinaryCoefficient for $t {
+            fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+                write_varint(w, *self as u64)
+            }
+
+            fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self> {
+                <$t>::try_from(read_varint(r)?)
+                    .map_err(|_| invalid(concat!("coefficient overflows ", stringify!($t))))
+            }
+        }
+    )*};
+}
+
+/// Signed integers are written as the varints of their zigzag encodings,
+/// which map `0, -1, 1, -2, …` to `0, 1, 2, 3, …`.
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl BinaryCoefficient for $t {
+            fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+                let n = *self as i64;
+                write_varint(w, ((n << 1) ^ (n >> 63)) as u64)
+            }
+
+            fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self> {
+                let z = read_varint(r)?;
+                let n = (z >> 1) as i64 ^ -((z & 1) as i64);
+                <$t>::try_from(n)
+                    .map_err(|_| invalid(concat!("coefficient overflows ", stringify!($t))))
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64);
+impl_signed!(i8, i16, i32, i64);
+
+/// Written as its little-endian bytes.
+impl BinaryCoefficient for f64 {
+    fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.to_le_bytes())
+    }
+
+    fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self> {
+        let mut bytes = [0; 8];
+        r.read_exact(&mut bytes)?;
+        Ok(f64::from_le_bytes(bytes))
+    }
+}
+
+/// Written as a sign byte, `0` for zero, `1` for positive and `2` for
+/// negative, followed for nonzero values by the number of bytes of the
+/// magnitude and its little-endian bytes.
+impl BinaryCoefficient for BigInt {
+    fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        let (sign, bytes) = self.to_bytes_le();
+        match sign {
+            Sign::NoSign => return w.write_all(&[0]),
+            Sign::Plus => w.write_all(&[1])?,
+            Sign::Minus => w.write_all(&[2])?,
+        }
+        write_varint(w, bytes.len() as u64)?;
+        w.write_all(&bytes)
+    }
+
+    fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self> {
+        let mut sign = [0];
+        r.read_exact(&mut sign)?;
+        let sign = match sign[0] {
+            0 => return Ok(BigInt::zero()),
+            1 => Sign::Plus,
+            2 => Sign::Minus,
+            s => return Err(invalid(format!("invalid sign byte {s}"))),
+        };
+        let len = read_varint(r)?;
+        let mut bytes = Vec::new();
+        r.take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(BigInt::from_bytes_le(sign, &bytes))
+    }
+}
+
+/// Written as its numerator and then its denominator.
+impl BinaryCoefficient for BigRational {
+    fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        self.numer().write_to(w)?;
+        self.denom().write_to(w)
+    }
+
+    fn read_from<R: Read + ?Sized>(r: &mut R) -> io::Result<Self> {
+        let numer = BigInt::read_from(r)?;
+        let denom = BigInt::read_from(r)?;
+        if denom.is_zero() {
+            return Err(invalid("zero denominator"));
+        }
+        Ok(BigRational::new(numer, denom))
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: BinaryCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// Writes the terms of `self`, in the layout described at [`VERSION`].
+    fn write_body<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        let n = self.elem_of.vars.len();
+        write_varint(w, n as u64)?;
+        write_varint(w, self.terms.len() as u64)?;
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_unstable_by(|(a, _), (b, _)| a.powers.cmp(&b.powers));
+        let mut previous: Option<&Monomial<P>> = None;
+        for (m, c) in terms {
+            let start = match previous {
+                None => 0,
+                Some(p) => {
+                    let j = (0..n).find(|&j| m.powers[j] != p.powers[j]).unwrap();
+                    write_varint(w, j as u64)?;
+                    write_varint(w, (m.powers[j] - p.powers[j]).to_u64().unwrap())?;
+                    j + 1
+                }
+            };
+            for e in &m.powers[start..] {
+                write_varint(w, e.to_u64().unwrap())?;
+            }
+            c.write_to(w)?;
+            previous = Some(m);
+        }
+        Ok(())
+    }
+
+    /// Writes `self` to `w` in the versioned binary format of this crate,
+    /// described at [`VERSION`], without its ring.
+    ///
+    /// The terms are streamed to `w` one at a time, so large polynomials
+    /// should be written through a buffered writer.
+    pub fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        write_header(w, b'P')?;
+        self.write_body(w)
+    }
+}
+
+impl<'a, R, V, K, P, S> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + BinaryCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    /// Reads the terms of a polynomial in `ring`, in the layout described at
+    /// [`VERSION`].
+    fn read_body<Rd: Read + ?Sized>(
+        ring: &'a PolynomialRing<'a, R, V>,
+        r: &mut Rd,
+    ) -> io::Result<Self> {
+        let n = ring.vars.len();
+        let vars = read_len(r)?;
+        if vars != n {
+            return Err(invalid_data(ChidogError::InvalidInput(format!(
+                "polynomial in {vars} variables in a ring of {n}"
+            ))));
+        }
+        let num_terms = read_len(r)?;
+        let overflow = || invalid_data(ChidogError::ExponentOverflow);
+        let read_exponent = |r: &mut Rd| P::from(read_varint(r)?).ok_or_else(overflow);
+        // The count comes from the input, so it only bounds the allocation.
+        let mut terms = HashMap::with_capacity_and_hasher(num_terms.min(1 << 16), S::default());
+        let mut m = Monomial {
+            powers: smallvec![P::zero(); n],
+        };
+        for i in 0..num_terms {
+            let start = if i == 0 {
+                0
+            } else {
+                let j = read_len(r)?;
+                let delta = read_exponent(r)?;
+                if j >= n || delta.is_zero() {
+                    return Err(invalid("monomials out of order"));
+                }
+                m.powers[j] = m.powers[j].checked_add(&delta).ok_or_else(overflow)?;
+                j + 1
+            };
+            for e in &mut m.powers[start..] {
+                *e = read_exponent(r)?;
+            }
+            let c = K::read_from(r)?;
+            if !ring.base.is_zero(&c) {
+                terms.insert(m.clone(), c);
+            }
+        }
+        Ok(Polynomial {
+            elem_of: ring,
+            terms,
+        })
+    }
+
+    /// The polynomial in `ring` read from `r` in the binary format written
+    /// by [`Polynomial::write_to`].
+    ///
+    /// Fails with an `InvalidData` error, wrapping a [`ChidogError`], if the
+    /// data is not in the format, or is a polynomial in another number of
+    /// variables than `ring` has, or has exponents which do not fit in `P`.
+    pub fn read_from<Rd: Read + ?Sized>(
+        ring: &'a PolynomialRing<'a, R, V>,
+        r: &mut Rd,
+    ) -> io::Result<Self> {
+        read_header(r, b'P')?;
+        Self::read_body(ring, r)
+    }
+}
+
+type System<'a, R, V, K, P, S> = Vec<Polynomial<'a, R, V, K, P, S>>;
+
+/// Writes the polynomials of `system`, such as the generators of an ideal,
+/// to `w` in the binary format of [`Polynomial::write_to`].
+pub fn write_system_to<R, V, K, P, S, W>(
+    system: &[Polynomial<'_, R, V, K, P, S>],
+    w: &mut W,
+) -> io::Result<()>
+where
+    K: BinaryCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+    W: Write + ?Sized,
+{
+    write_header(w, b'S')?;
+    write_varint(w, system.len() as u64)?;
+    for f in system {
+        f.write_body(w)?;
+    }
+    Ok(())
+}
+
+/// The polynomials in `ring` read from `r` in the binary format written by
+/// [`write_system_to`], failing as [`Polynomial::read_from`] does.
+pub fn read_system_from<'a, R, V, K, P, S, Rd>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    r: &mut Rd,
+) -> io::Result<System<'a, R, V, K, P, S>>
+where
+    R: Semiring<K>,
+    K: SemiringElement + BinaryCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+    Rd: Read + ?Sized,
+{
+    read_header(r, b'S')?;
+    let len = read_len(r)?;
+    let mut system = Vec::with_capacity(len.min(1 << 16));
+    for _ in 0..len {
+        system.push(Polynomial::read_body(ring, r)?);
+    }
+    Ok(system)
+}
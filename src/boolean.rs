@@ -0,0 +1,236 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+pub mod cnf;
+pub mod zdd;
+
+/// A monomial in a Boolean polynomial ring, stored as the set of variables it
+/// contains. Since `x^2 = x`, every variable occurs with exponent 0 or 1, so
+/// the monomial is a bitset with one bit per variable.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BooleanMonomial {
+    bits: Vec<u64>,
+}
+
+impl BooleanMonomial {
+    /// The monomial `1` in a ring with `nvars` variables.
+    pub fn one(nvars: usize) -> Self {
+        Self {
+            bits: vec![0; nvars.div_ceil(64)],
+        }
+    }
+
+    /// The monomial containing exactly the variables with the given indices.
+    pub fn from_vars(nvars: usize, vars: impl IntoIterator<Item = usize>) -> Self {
+        let mut m = Self::one(nvars);
+        for i in vars {
+            assert!(i < nvars, "variable index out of range");
+            m.bits[i / 64] |= 1 << (i % 64);
+        }
+        m
+    }
+
+    pub fn contains(&self, var: usize) -> bool {
+        self.bits[var / 64] & (1 << (var % 64)) != 0
+    }
+
+    /// The indices of the variables in this monomial, in increasing order.
+    pub fn vars(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(|(w, &word)| {
+            (0..64)
+                .filter(move |b| word & (1 << b) != 0)
+                .map(move |b| 64 * w + b)
+        })
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.bits.iter().map(|w| w.count_ones()).sum()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.bits.iter().all(|&w| w == 0)
+    }
+
+    fn mul(&self, rhs: &Self) -> Self {
+        Self {
+            bits: self
+                .bits
+                .iter()
+                .zip(rhs.bits.iter())
+                .map(|(a, b)| a | b)
+                .collect(),
+        }
+    }
+}
+
+/// A variable `my_ring: BooleanPolynomialRing<V>` represents the Boolean
+/// polynomial ring `GF(2)[x_1, ..., x_n] / (x_1^2 + x_1, ..., x_n^2 + x_n)`,
+/// whose elements are of type `BooleanPolynomial<'_, V>`. The variable
+/// `my_ring` owns its variable names of type `V`.
+///
+/// Elements of this ring correspond exactly to Boolean functions in `n`
+/// variables, in algebraic normal form. Besides the explicit representation
+/// `BooleanPolynomial`, the ring owns the node table for its elements in the
+/// ZDD representation [`zdd::ZddPolynomial`].
+pub struct BooleanPolynomialRing<V> {
+    pub vars: Vec<V>,
+    zdd: RefCell<zdd::ZddTable>,
+}
+
+/// Boolean polynomials are stored as the set of monomials with coefficient 1.
+/// The field equations `x^2 = x` are applied automatically by the
+/// representation, and since `1 + 1 = 0`, addition is the symmetric
+/// difference of the sets of monomials.
+#[derive(Clone)]
+pub struct BooleanPolynomial<'a, V> {
+    pub elem_of: &'a BooleanPolynomialRing<V>,
+    pub terms: HashSet<BooleanMonomial>,
+}
+
+impl<V> BooleanPolynomialRing<V> {
+    pub fn new(vars: Vec<V>) -> Self {
+        Self {
+            vars,
+            zdd: RefCell::default(),
+        }
+    }
+
+    pub fn zero(&self) -> BooleanPolynomial<'_, V> {
+        BooleanPolynomial {
+            elem_of: self,
+            terms: HashSet::new(),
+        }
+    }
+
+    pub fn one(&self) -> BooleanPolynomial<'_, V> {
+        self.monomial(BooleanMonomial::one(self.vars.len()))
+    }
+
+    /// The `i`-th variable of the ring.
+    pub fn var(&self, i: usize) -> BooleanPolynomial<'_, V> {
+        self.monomial(BooleanMonomial::from_vars(self.vars.len(), [i]))
+    }
+
+    pub fn monomial(&self, m: BooleanMonomial) -> BooleanPolynomial<'_, V> {
+        BooleanPolynomial {
+            elem_of: self,
+            terms: HashSet::from([m]),
+        }
+    }
+}
+
+impl<V> BooleanPolynomial<'_, V> {
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.terms.len() == 1 && self.terms.iter().all(|m| m.is_one())
+    }
+
+    /// The largest degree of a monomial of `self`, or `None` for zero.
+    pub fn degree(&self) -> Option<u32> {
+        self.terms.iter().map(|m| m.degree()).max()
+    }
+
+    /// Evaluates `self` at the point assigning `point[i]` to the `i`-th
+    /// variable.
+    pub fn eval(&self, point: &[bool]) -> bool {
+        self.terms
+            .iter()
+            .filter(|m| m.vars().all(|i| point[i]))
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Adds the monomial `m` to `self`, cancelling it if already present.
+    fn toggle(&mut self, m: BooleanMonomial) {
+        if !self.terms.remove(&m) {
+            self.terms.insert(m);
+        }
+    }
+}
+
+impl<V> Add for BooleanPolynomial<'_, V> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<V> AddAssign for BooleanPolynomial<'_, V> {
+    fn add_assign(&mut self, rhs: Self) {
+        for m in rhs.terms {
+            self.toggle(m);
+        }
+    }
+}
+
+/// In characteristic 2, subtraction coincides with addition.
+impl<V> Sub for BooleanPolynomial<'_, V> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + rhs
+    }
+}
+
+impl<V> SubAssign for BooleanPolynomial<'_, V> {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self += rhs;
+    }
+}
+
+impl<V> Mul for BooleanPolynomial<'_, V> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut product = self.elem_of.zero();
+        for m1 in self.terms.iter() {
+            for m2 in rhs.terms.iter() {
+                product.toggle(m1.mul(m2));
+            }
+        }
+        product
+    }
+}
+
+impl<V> MulAssign for BooleanPolynomial<'_, V> {
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            terms: std::mem::take(&mut self.terms),
+        };
+        *self = lhs * rhs;
+    }
+}
+
+impl<V: Display> Display for BooleanPolynomial<'_, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, m) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, "+")?;
+            }
+            if m.is_one() {
+                write!(f, "1")?;
+            }
+            for (j, var) in m.vars().enumerate() {
+                if j > 0 {
+                    write!(f, "*")?;
+                }
+                write!(f, "{}", self.elem_of.vars[var])?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use super::{BooleanMonomial, BooleanPolynomial};
+use crate::error::ChidogError;
+
+/// A formula in conjunctive normal form equisatisfiable with a system of
+/// Boolean polynomial equations `f_1 = ... = f_k = 0`.
+///
+/// The encoding is the standard one from algebraic normal form: each
+/// nonlinear monomial is replaced by an auxiliary variable constrained to be
+/// the conjunction of its factors, and each equation then becomes a XOR
+/// constraint, which is cut into XORs of at most `xor_len` literals using
+/// further auxiliary variables, each expanded into clauses.
+///
+/// The variable `i` of the ring becomes DIMACS variable `i + 1`, and the
+/// auxiliary variables follow. `Display` writes the formula in DIMACS format.
+#[derive(Clone, Debug)]
+pub struct Cnf {
+    pub num_vars: usize,
+    pub num_ring_vars: usize,
+    pub clauses: Vec<Vec<i64>>,
+}
+
+impl Cnf {
+    /// The XOR cutting length used by [`Cnf::encode`].
+    pub const DEFAULT_XOR_LEN: usize = 5;
+
+    pub fn encode<V>(system: &[BooleanPolynomial<'_, V>]) -> Self {
+        Self::encode_with_xor_len(system, Self::DEFAULT_XOR_LEN)
+    }
+
+    /// Encodes `system`, cutting XOR constraints into pieces of at most
+    /// `xor_len` literals, each of which expands to `2^(xor_len - 1)`
+    /// clauses.
+    pub fn encode_with_xor_len<V>(system: &[BooleanPolynomial<'_, V>], xor_len: usize) -> Self {
+        assert!(xor_len >= 3, "XOR cutting length must be at least 3");
+        let num_ring_vars = system.first().map_or(0, |f| f.elem_of.vars.len());
+        let mut cnf = Self {
+            num_vars: num_ring_vars,
+            num_ring_vars,
+            clauses: Vec::new(),
+        };
+        let mut monomial_vars = HashMap::<BooleanMonomial, i64>::new();
+        for f in system {
+            let mut parity = false;
+            let mut literals = Vec::new();
+            for m in f.terms.iter() {
+                match m.degree() {
+                    0 => parity = !parity,
+                    1 => literals.push(m.vars().next().unwrap() as i64 + 1),
+                    _ => {
+                        let t = *monomial_vars
+                            .entry(m.clone())
+                            .or_insert_with(|| cnf.encode_conjunction(m));
+                        literals.push(t);
+                    }
+                }
+            }
+            // f = 0 exactly when the nonconstant terms sum to the constant.
+            cnf.encode_xor(literals, parity, xor_len);
+        }
+        cnf
+    }
+
+    fn fresh_var(&mut self) -> i64 {
+        self.num_vars += 1;
+        self.num_vars as i64
+    }
+
+    /// Introduces a variable equivalent to the conjunction of the variables
+    /// of `m`.
+    fn encode_conjunction(&mut self, m: &BooleanMonomial) -> i64 {
+        let t = self.fresh_var();
+        let mut long_clause = vec![t];
+        for i in m.vars() {
+            let x = i as i64 + 1;
+            self.clauses.push(vec![-t, x]);
+            long_clause.push(-x);
+        }
+        self.clauses.push(long_clause);
+        t
+    }
+
+    /// Constrains the XOR of `literals` to equal `parity`.
+    fn encode_xor(&mut self, mut literals: Vec<i64>, parity: bool, xor_len: usize) {
+        while literals.len() > xor_len {
+            let rest = literals.split_off(xor_len - 1);
+            let t = self.fresh_var();
+            literals.push(t);
+            self.encode_short_xor(&literals, false);
+            literals = std::iter::once(t).chain(rest).collect();
+        }
+        self.encode_short_xor(&literals, parity);
+    }
+
+    /// Expands a XOR constraint into the clauses forbidding each assignment
+    /// of the wrong parity.
+    fn encode_short_xor(&mut self, literals: &[i64], parity: bool) {
+        for assignment in 0u64..(1 << literals.len()) {
+            if (assignment.count_ones() % 2 == 1) == parity {
+                continue;
+            }
+            self.clauses.push(
+                literals
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &l)| if assignment & (1 << i) != 0 { -l } else { l })
+                    .collect(),
+            );
+        }
+    }
+
+    /// Interprets the output of a SAT solver run on this formula as an
+    /// assignment to the variables of the ring, or `None` if the solver
+    /// reported the formula unsatisfiable.
+    ///
+    /// Accepts both the competition format (`s SATISFIABLE` followed by `v`
+    /// lines) and the MiniSat format (`SAT` followed by a line of literals).
+    /// Variables the model leaves unassigned are taken to be false. Output
+    /// without a status line, or whose status is neither satisfiable nor
+    /// unsatisfiable, such as `s UNKNOWN` after a timeout, is a `Parse`
+    /// error.
+    pub fn parse_model(&self, output: &str) -> Result<Option<Vec<bool>>, ChidogError> {
+        let mut assignment = vec![false; self.num_ring_vars];
+        let mut satisfiable = false;
+        for line in output.lines().map(str::trim) {
+            let literals = match line.split_whitespace().next() {
+                None | Some("c") => continue,
+                Some("s") | Some("SAT") | Some("UNSAT") | Some("INDET") => {
+                    match line.trim_start_matches("s ").trim() {
+                        "SATISFIABLE" | "SAT" => satisfiable = true,
+                        "UNSATISFIABLE" | "UNSAT" => return Ok(None),
+                        status => {
+                            return Err(ChidogError::Parse(format!(
+                                "the solver gave no answer: `{status}`"
+                            )));
+                        }
+                    }
+                    continue;
+                }
+                Some("v") => &line[1..],
+                Some(_) => line,
+            };
+            for token in literals.split_whitespace() {
+                let literal: i64 = token
+                    .parse()
+                    .map_err(|_| ChidogError::Parse(format!("invalid literal `{token}`")))?;
+                let var = literal.unsigned_abs() as usize;
+                if (1..=self.num_ring_vars).contains(&var) {
+                    assignment[var - 1] = literal > 0;
+                }
+            }
+        }
+        if !satisfiable {
+            return Err(ChidogError::Parse(
+                "the solver output has no status line".to_string(),
+            ));
+        }
+        Ok(Some(assignment))
+    }
+}
+
+impl Display for Cnf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "p cnf {} {}", self.num_vars, self.clauses.len())?;
+        for clause in self.clauses.iter() {
+            for literal in clause {
+                write!(f, "{literal} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cnf() -> Cnf {
+        Cnf {
+            num_vars: 4,
+            num_ring_vars: 3,
+            clauses: vec![vec![1, -2], vec![2, 3, 4]],
+        }
+    }
+
+    #[test]
+    fn parse_model_reads_satisfying_assignments() {
+        let competition = "c a comment\ns SATISFIABLE\nv 1 -2\nv 3 -4 0\n";
+        assert_eq!(
+            cnf().parse_model(competition).unwrap(),
+            Some(vec![true, false, true])
+        );
+        let minisat = "SAT\n-1 2 -3 4 0\n";
+        assert_eq!(
+            cnf().parse_model(minisat).unwrap(),
+            Some(vec![false, true, false])
+        );
+    }
+
+    #[test]
+    fn parse_model_reads_unsatisfiable() {
+        assert_eq!(cnf().parse_model("s UNSATISFIABLE\n").unwrap(), None);
+        assert_eq!(cnf().parse_model("UNSAT\n").unwrap(), None);
+    }
+
+    #[test]
+    fn parse_model_rejects_unknown_status() {
+        for output in ["s UNKNOWN\n", "c timeout\ns UNKNOWN\nv 1 0\n", "INDET\n"] {
+            assert!(matches!(
+                cnf().parse_model(output),
+                Err(ChidogError::Parse(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_model_rejects_missing_status() {
+        for output in ["", "c interrupted\n", "v 1 2 3 0\n"] {
+            assert!(matches!(
+                cnf().parse_model(output),
+                Err(ChidogError::Parse(_))
+            ));
+        }
+    }
+}
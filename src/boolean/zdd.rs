@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Add, Mul};
+
+use super::{BooleanMonomial, BooleanPolynomial, BooleanPolynomialRing};
+
+/// A reference to a node of a zero-suppressed decision diagram.
+///
+/// The node `EMPTY` denotes the empty set of monomials (the polynomial 0),
+/// and `BASE` denotes the set containing only the monomial 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ZddNode(u32);
+
+const EMPTY: ZddNode = ZddNode(0);
+const BASE: ZddNode = ZddNode(1);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Op {
+    Union,
+    Xor,
+    Mul,
+}
+
+/// The shared node table of all ZDDs belonging to one Boolean polynomial
+/// ring.
+///
+/// A nonterminal node `(var, lo, hi)` denotes the set `lo ∪ {var * m : m ∈
+/// hi}`, where no monomial of `lo` or `hi` contains a variable with index at
+/// most `var`. Nodes are hash-consed, and nodes with `hi == EMPTY` are never
+/// created, so that equal sets of monomials are represented by the same node.
+#[derive(Default)]
+pub(crate) struct ZddTable {
+    nodes: Vec<(u32, ZddNode, ZddNode)>,
+    unique: HashMap<(u32, ZddNode, ZddNode), ZddNode>,
+    cache: HashMap<(Op, ZddNode, ZddNode), ZddNode>,
+    counts: HashMap<ZddNode, u64>,
+}
+
+impl ZddTable {
+    fn node(&self, n: ZddNode) -> (u32, ZddNode, ZddNode) {
+        self.nodes[n.0 as usize - 2]
+    }
+
+    /// The index of the top variable of `n`, or `u32::MAX` for terminals.
+    fn top(&self, n: ZddNode) -> u32 {
+        if n == EMPTY || n == BASE {
+            u32::MAX
+        } else {
+            self.node(n).0
+        }
+    }
+
+    fn mk(&mut self, var: u32, lo: ZddNode, hi: ZddNode) -> ZddNode {
+        if hi == EMPTY {
+            return lo;
+        }
+        if let Some(&n) = self.unique.get(&(var, lo, hi)) {
+            return n;
+        }
+        let n = ZddNode(self.nodes.len() as u32 + 2);
+        self.nodes.push((var, lo, hi));
+        self.unique.insert((var, lo, hi), n);
+        n
+    }
+
+    /// Splits `n` as `n0 + var * n1`, where neither part contains `var`.
+    fn cofactors(&self, n: ZddNode, var: u32) -> (ZddNode, ZddNode) {
+        if self.top(n) == var {
+            let (_, lo, hi) = self.node(n);
+            (lo, hi)
+        } else {
+            (n, EMPTY)
+        }
+    }
+
+    fn apply(&mut self, op: Op, a: ZddNode, b: ZddNode) -> ZddNode {
+        match op {
+            Op::Union if a == EMPTY || a == b => return b,
+            Op::Union if b == EMPTY => return a,
+            Op::Xor if a == b => return EMPTY,
+            Op::Xor if a == EMPTY => return b,
+            Op::Xor if b == EMPTY => return a,
+            Op::Mul if a == EMPTY || b == EMPTY => return EMPTY,
+            Op::Mul if a == BASE => return b,
+            Op::Mul if b == BASE => return a,
+            _ => {}
+        }
+        // All three operations are commutative.
+        let key = (op, a.min_by_id(b), a.max_by_id(b));
+        if let Some(&n) = self.cache.get(&key) {
+            return n;
+        }
+        let var = self.top(a).min(self.top(b));
+        let (a0, a1) = self.cofactors(a, var);
+        let (b0, b1) = self.cofactors(b, var);
+        let n = match op {
+            Op::Union | Op::Xor => {
+                let lo = self.apply(op, a0, b0);
+                let hi = self.apply(op, a1, b1);
+                self.mk(var, lo, hi)
+            }
+            Op::Mul => {
+                // (a0 + v*a1)(b0 + v*b1) = a0*b0 + v*(a0*b1 + a1*b0 + a1*b1),
+                // using v^2 = v.
+                let lo = self.apply(Op::Mul, a0, b0);
+                let hi01 = self.apply(Op::Mul, a0, b1);
+                let hi10 = self.apply(Op::Mul, a1, b0);
+                let hi11 = self.apply(Op::Mul, a1, b1);
+                let hi = self.apply(Op::Xor, hi01, hi10);
+                let hi = self.apply(Op::Xor, hi, hi11);
+                self.mk(var, lo, hi)
+            }
+        };
+        self.cache.insert(key, n);
+        n
+    }
+
+    fn count(&mut self, n: ZddNode) -> u64 {
+        if n == EMPTY {
+            return 0;
+        }
+        if n == BASE {
+            return 1;
+        }
+        if let Some(&c) = self.counts.get(&n) {
+            return c;
+        }
+        let (_, lo, hi) = self.node(n);
+        let c = self.count(lo) + self.count(hi);
+        self.counts.insert(n, c);
+        c
+    }
+
+    fn monomial_node(&mut self, m: &BooleanMonomial) -> ZddNode {
+        let vars: Vec<usize> = m.vars().collect();
+        vars.into_iter()
+            .rev()
+            .fold(BASE, |n, v| self.mk(v as u32, EMPTY, n))
+    }
+
+    fn collect_monomials(&self, n: ZddNode, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        if n == EMPTY {
+            return;
+        }
+        if n == BASE {
+            out.push(prefix.clone());
+            return;
+        }
+        let (var, lo, hi) = self.node(n);
+        self.collect_monomials(lo, prefix, out);
+        prefix.push(var as usize);
+        self.collect_monomials(hi, prefix, out);
+        prefix.pop();
+    }
+
+    fn reachable(&self, root: ZddNode) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack = vec![root];
+        while let Some(n) = stack.pop() {
+            if n == EMPTY || n == BASE || !seen.insert(n) {
+                continue;
+            }
+            let (_, lo, hi) = self.node(n);
+            stack.push(lo);
+            stack.push(hi);
+        }
+        seen.len()
+    }
+}
+
+impl ZddNode {
+    fn min_by_id(self, other: Self) -> Self {
+        if self.0 <= other.0 { self } else { other }
+    }
+
+    fn max_by_id(self, other: Self) -> Self {
+        if self.0 <= other.0 { other } else { self }
+    }
+}
+
+/// A Boolean polynomial stored as a zero-suppressed decision diagram over
+/// the node table of its ring.
+///
+/// Unlike [`BooleanPolynomial`], whose storage is proportional to its number
+/// of monomials, a ZDD shares common substructure between monomials and
+/// between polynomials, so that systems with millions of monomials can remain
+/// small. Since the representation is canonical, equality is a comparison of
+/// root nodes.
+pub struct ZddPolynomial<'a, V> {
+    pub elem_of: &'a BooleanPolynomialRing<V>,
+    root: ZddNode,
+}
+
+impl<V> Clone for ZddPolynomial<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for ZddPolynomial<'_, V> {}
+
+impl<V> PartialEq for ZddPolynomial<'_, V> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.elem_of, other.elem_of) && self.root == other.root
+    }
+}
+
+impl<V> Eq for ZddPolynomial<'_, V> {}
+
+impl<V> BooleanPolynomialRing<V> {
+    /// The number of ZDD nodes allocated in this ring's node table.
+    pub fn zdd_node_count(&self) -> usize {
+        self.zdd.borrow().nodes.len()
+    }
+
+    /// Discards the operation caches of this ring's node table. Nodes
+    /// themselves are kept, since live polynomials may refer to them.
+    pub fn clear_zdd_caches(&self) {
+        let mut table = self.zdd.borrow_mut();
+        table.cache.clear();
+        table.counts.clear();
+    }
+}
+
+impl<'a, V> BooleanPolynomial<'a, V> {
+    /// Converts `self` to the ZDD representation.
+    pub fn to_zdd(&self) -> ZddPolynomial<'a, V> {
+        let mut table = self.elem_of.zdd.borrow_mut();
+        let mut root = EMPTY;
+        for m in self.terms.iter() {
+            let n = table.monomial_node(m);
+            root = table.apply(Op::Union, root, n);
+        }
+        ZddPolynomial {
+            elem_of: self.elem_of,
+            root,
+        }
+    }
+}
+
+impl<'a, V> ZddPolynomial<'a, V> {
+    pub fn zero(ring: &'a BooleanPolynomialRing<V>) -> Self {
+        Self {
+            elem_of: ring,
+            root: EMPTY,
+        }
+    }
+
+    pub fn one(ring: &'a BooleanPolynomialRing<V>) -> Self {
+        Self {
+            elem_of: ring,
+            root: BASE,
+        }
+    }
+
+    /// The `i`-th variable of `ring`.
+    pub fn var(ring: &'a BooleanPolynomialRing<V>, i: usize) -> Self {
+        assert!(i < ring.vars.len(), "variable index out of range");
+        let root = ring.zdd.borrow_mut().mk(i as u32, EMPTY, BASE);
+        Self {
+            elem_of: ring,
+            root,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.root == EMPTY
+    }
+
+    /// The number of monomials of `self`.
+    pub fn num_terms(&self) -> u64 {
+        self.elem_of.zdd.borrow_mut().count(self.root)
+    }
+
+    /// The number of distinct nonterminal nodes reachable from `self`.
+    pub fn node_count(&self) -> usize {
+        self.elem_of.zdd.borrow().reachable(self.root)
+    }
+
+    /// Converts `self` to the explicit set-of-monomials representation.
+    pub fn to_polynomial(&self) -> BooleanPolynomial<'a, V> {
+        let nvars = self.elem_of.vars.len();
+        let mut monomials = Vec::new();
+        self.elem_of
+            .zdd
+            .borrow()
+            .collect_monomials(self.root, &mut Vec::new(), &mut monomials);
+        BooleanPolynomial {
+            elem_of: self.elem_of,
+            terms: monomials
+                .into_iter()
+                .map(|vars| BooleanMonomial::from_vars(nvars, vars))
+                .collect(),
+        }
+    }
+
+    /// Evaluates `self` at the point assigning `point[i]` to the `i`-th
+    /// variable.
+    pub fn eval(&self, point: &[bool]) -> bool {
+        let table = self.elem_of.zdd.borrow();
+        let mut memo = HashMap::new();
+        fn go(
+            table: &ZddTable,
+            n: ZddNode,
+            point: &[bool],
+            memo: &mut HashMap<ZddNode, bool>,
+        ) -> bool {
+            if n == EMPTY || n == BASE {
+                return n == BASE;
+            }
+            if let Some(&b) = memo.get(&n) {
+                return b;
+            }
+            let (var, lo, hi) = table.node(n);
+            let b =
+                go(table, lo, point, memo) ^ (point[var as usize] && go(table, hi, point, memo));
+            memo.insert(n, b);
+            b
+        }
+        go(&table, self.root, point, &mut memo)
+    }
+
+    fn apply(self, op: Op, rhs: Self) -> Self {
+        assert!(
+            std::ptr::eq(self.elem_of, rhs.elem_of),
+            "ZDD polynomials belong to different rings"
+        );
+        let root = self.elem_of.zdd.borrow_mut().apply(op, self.root, rhs.root);
+        Self {
+            elem_of: self.elem_of,
+            root,
+        }
+    }
+}
+
+impl<V> Add for ZddPolynomial<'_, V> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.apply(Op::Xor, rhs)
+    }
+}
+
+impl<V> Mul for ZddPolynomial<'_, V> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.apply(Op::Mul, rhs)
+    }
+}
+
+impl<V: Display> Display for ZddPolynomial<'_, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_polynomial())
+    }
+}
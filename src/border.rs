@@ -0,0 +1,394 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use num::{PrimInt, ToPrimitive, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::monomials::monomials_up_to_degree;
+use crate::order::{GrLex, MonomialOrder};
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing};
+
+/// A border basis of a zero-dimensional ideal, as computed by
+/// [`border_basis`].
+///
+/// The order ideal `O` is a set of monomials closed under division whose
+/// residue classes form a basis of the quotient by the ideal, and its
+/// border `∂O` is the set of the monomials `x_i*o` outside `O`, for `o` in
+/// `O`. The basis has one polynomial `g_b = b + ∑ c_(b,o)*o` for each border
+/// monomial `b`, which writes `b` modulo the ideal in the basis `O`.
+///
+/// Unlike a Gröbner basis, the order ideal need not come from a term order,
+/// which leaves it free to follow the coefficients; this is what makes
+/// border bases stable under small perturbations of the generators.
+#[derive(Clone)]
+pub struct BorderBasis<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    ring: &'a PolynomialRing<'a, R, V>,
+    order_ideal: Vec<Monomial<P>>,
+    border: Vec<Monomial<P>>,
+    polynomials: Vec<Polynomial<'a, R, V, K, P>>,
+}
+
+fn degree<P: PrimInt>(m: &Monomial<P>) -> usize {
+    m.powers.iter().map(|p| p.to_usize().unwrap()).sum()
+}
+
+/// The monomial `x_i*m`.
+fn times_var<P: PrimInt>(m: &Monomial<P>, i: usize) -> Monomial<P> {
+    let mut powers = m.powers.clone();
+    powers[i] = powers[i] + P::one();
+    Monomial { powers }
+}
+
+/// The rows spanning the same space as `rows`, reduced so that each has a
+/// one in a pivot column where the others vanish, with those columns.
+///
+/// If `stable`, the pivot of each step is the entry of largest magnitude in
+/// the columns of highest degree, as in complete pivoting; otherwise it is
+/// the entry in the first column, in the order of `degrees`, of the first
+/// row having one, which makes the pivots the leading monomials in that
+/// order.
+fn echelon<R, K>(
+    base: &R,
+    mut rows: Vec<Vec<K>>,
+    degrees: &[usize],
+    stable: bool,
+) -> (Vec<Vec<K>>, Vec<usize>)
+where
+    R: Field<K>,
+    K: FieldElement + Clone + ToPrimitive,
+{
+    let magnitude = |c: &K| c.to_f64().map_or(0.0, f64::abs);
+    let mut pivots = Vec::new();
+    for done in 0..rows.len() {
+        let entries = (done..rows.len()).flat_map(|r| (0..degrees.len()).map(move |c| (r, c)));
+        let pivot =
+            match stable {
+                true => entries.filter(|&(r, c)| !base.is_zero(&rows[r][c])).max_by(
+                    |&(r, c), &(s, d)| {
+                        degrees[c].cmp(&degrees[d]).then(
+                            magnitude(&rows[r][c])
+                                .partial_cmp(&magnitude(&rows[s][d]))
+                                .unwrap_or(Ordering::Equal),
+                        )
+                    },
+                ),
+                false => (0..degrees.len())
+                    .flat_map(|c| (done..rows.len()).map(move |r| (r, c)))
+                    .find(|&(r, c)| !base.is_zero(&rows[r][c])),
+            };
+        let Some((r, c)) = pivot else {
+            rows.truncate(done);
+            return (rows, pivots);
+        };
+        rows.swap(done, r);
+        let inverse = K::one() / rows[done][c].clone();
+        for a in &mut rows[done] {
+            *a = a.clone() * inverse.clone();
+        }
+        let pivot_row = rows[done].clone();
+        for (k, row) in rows.iter_mut().enumerate() {
+            if k == done || base.is_zero(&row[c]) {
+                continue;
+            }
+            let factor = row[c].clone();
+            for (a, p) in row.iter_mut().zip(&pivot_row) {
+                if base.sub_from(a, factor.clone() * p.clone()) {
+                    *a = K::zero();
+                }
+            }
+            row[c] = K::zero();
+        }
+        pivots.push(c);
+    }
+    (rows, pivots)
+}
+
+impl<'a, R, V, K, P> BorderBasis<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The order ideal, by increasing degree.
+    pub fn order_ideal(&self) -> &[Monomial<P>] {
+        &self.order_ideal
+    }
+
+    /// The border of the order ideal, in the order of
+    /// [`BorderBasis::polynomials`].
+    pub fn border(&self) -> &[Monomial<P>] {
+        &self.border
+    }
+
+    /// The polynomials of the basis, one for each border monomial, in which
+    /// it has coefficient one.
+    pub fn polynomials(&self) -> &[Polynomial<'a, R, V, K, P>] {
+        &self.polynomials
+    }
+
+    /// The index of `t`: zero if `t` is in the order ideal, and otherwise
+    /// the least degree of `t/o` for `o` in the order ideal dividing `t`,
+    /// with such an `o`. The order ideal must not be empty.
+    fn index<'o>(&'o self, t: &Monomial<P>) -> (usize, &'o Monomial<P>) {
+        self.order_ideal
+            .iter()
+            .filter(|o| o.divides(t))
+            .map(|o| (degree(t) - degree(o), o))
+            .min_by_key(|(i, _)| *i)
+            .expect("the order ideal contains 1")
+    }
+
+    /// The quotients `q_b` and the remainder `r` of the border division of
+    /// `f`, with `f = ∑ q_b*g_b + r`, where `r` is a combination of the
+    /// monomials of the order ideal.
+    ///
+    /// Each step takes the term `c*t` of largest index of the running
+    /// dividend, writes `t = t'*b` for a border monomial `b` with `t'` of
+    /// degree one less than the index, and subtracts `c*t'*g_b`, which only
+    /// leaves terms of smaller index in place of `t`.
+    #[allow(clippy::type_complexity)]
+    pub fn divide(
+        &self,
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> (Vec<Polynomial<'a, R, V, K, P>>, Polynomial<'a, R, V, K, P>) {
+        let zero = || Polynomial {
+            elem_of: self.ring,
+            terms: HashMap::default(),
+        };
+        let border: HashMap<&Monomial<P>, usize> = self.border.iter().zip(0..).collect();
+        let mut quotients: Vec<_> = self.border.iter().map(|_| zero()).collect();
+        if self.order_ideal.is_empty() {
+            // The unit ideal, whose border basis is `1`.
+            return (vec![f.clone()], zero());
+        }
+        let mut rest = f.clone();
+        loop {
+            let top = rest
+                .terms
+                .iter()
+                .map(|(t, c)| (self.index(t), t, c))
+                .filter(|((i, _), _, _)| *i > 0)
+                .max_by(|((i, _), s, _), ((j, _), t, _)| i.cmp(j).then(GrLex.cmp(s, t)));
+            let Some(((_, o), t, c)) = top else {
+                return (quotients, rest);
+            };
+            // A variable `x_i` of `t/o` gives the border monomial `x_i*o`.
+            let i = (0..t.powers.len())
+                .find(|&i| t.powers[i] > o.powers[i])
+                .unwrap();
+            let b = times_var(o, i);
+            let shift = t.checked_div(&b).unwrap();
+            let k = border[&b];
+            let term = Polynomial::from_terms(self.ring, [(shift, c.clone())])
+                .expect("the monomials have one exponent for each variable");
+            rest -= term.clone() * self.polynomials[k].clone();
+            quotients[k] += term;
+        }
+    }
+
+    /// The remainder of the border division of `f`, which is its normal form
+    /// in the basis of the order ideal.
+    pub fn normal_form(&self, f: &Polynomial<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P> {
+        self.divide(f).1
+    }
+
+    /// The matrix of multiplication by `x_i` on the quotient, in the basis of
+    /// the order ideal: its column `j` holds the coordinates of `x_i*o_j`.
+    pub fn multiplication_matrix(&self, i: usize) -> Matrix<K> {
+        let position: HashMap<&Monomial<P>, usize> = self.order_ideal.iter().zip(0..).collect();
+        let border: HashMap<&Monomial<P>, usize> = self.border.iter().zip(0..).collect();
+        let m = self.order_ideal.len();
+        let mut matrix = Matrix::zero(m, m);
+        for (j, o) in self.order_ideal.iter().enumerate() {
+            let t = times_var(o, i);
+            if let Some(&k) = position.get(&t) {
+                matrix[(k, j)] = K::one();
+                continue;
+            }
+            for (s, c) in &self.polynomials[border[&t]].terms {
+                if let Some(&k) = position.get(s) {
+                    matrix[(k, j)] = K::zero() - c.clone();
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Whether the polynomials form a border basis of the ideal they
+    /// generate, which by the criterion of Mourrain holds exactly when the
+    /// multiplication matrices commute.
+    pub fn is_border_basis(&self) -> bool {
+        let base = self.ring.base;
+        let n = self.ring.vars.len();
+        let matrices: Vec<_> = (0..n).map(|i| self.multiplication_matrix(i)).collect();
+        let m = self.order_ideal.len();
+        (0..n).all(|i| {
+            (0..i).all(|j| {
+                let a = matrices[i].clone() * matrices[j].clone();
+                let b = matrices[j].clone() * matrices[i].clone();
+                (0..m).all(|r| {
+                    (0..m).all(|c| {
+                        let mut x = a[(r, c)].clone();
+                        base.sub_from(&mut x, b[(r, c)].clone())
+                    })
+                })
+            })
+        })
+    }
+}
+
+/// The border basis of the zero-dimensional ideal generated by the nonempty
+/// `generators`, or an `InvalidInput` error if none is found with all
+/// monomials of degree up to `max_degree`, as happens if the ideal is not
+/// zero-dimensional.
+///
+/// This is the border basis algorithm of Kehrein and Kreuzer. The span of
+/// the generators within the monomials of degree up to `d` is closed under
+/// multiplication by the variables, as long as the products stay within
+/// that degree, and brought to reduced echelon form; the monomials which
+/// are not pivots form the order ideal, and the rows of the border
+/// monomials give the basis. The pivots are chosen by magnitude within the
+/// highest degree, which keeps the computation stable for floating-point
+/// coefficients, falling back to the graded lexicographic order if that
+/// does not give an order ideal. The degree `d` starts at that of the
+/// generators and grows until the border fits within it, the basis passes
+/// [`BorderBasis::is_border_basis`] and the generators reduce to zero.
+pub fn border_basis<'a, R, V, K, P>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    max_degree: usize,
+) -> Result<BorderBasis<'a, R, V, K, P>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone + ToPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    let Some(ring) = generators.first().map(|f| f.elem_of) else {
+        return Err(ChidogError::InvalidInput(
+            "a border basis needs at least one generator".to_string(),
+        ));
+    };
+    let n = ring.vars.len();
+    let start = generators
+        .iter()
+        .flat_map(|f| f.terms.keys().map(degree))
+        .max()
+        .unwrap_or(0);
+    for d in start.max(1)..=max_degree {
+        // The monomials of degree up to `d`, by decreasing degree.
+        let universe: Vec<Monomial<P>> = monomials_up_to_degree(n, d).collect();
+        let column: HashMap<&Monomial<P>, usize> = universe.iter().zip(0..).collect();
+        let degrees: Vec<usize> = universe.iter().map(degree).collect();
+        let to_row = |f: &Polynomial<'a, R, V, K, P>| {
+            let mut row = vec![K::zero(); universe.len()];
+            for (m, c) in &f.terms {
+                row[column[m]] = c.clone();
+            }
+            row
+        };
+        let rows: Vec<Vec<K>> = generators
+            .iter()
+            .filter(|f| f.terms.keys().all(|m| degree(m) <= d))
+            .map(to_row)
+            .collect();
+        for stable in [true, false] {
+            let (mut rows, _) = echelon(ring.base, rows.clone(), &degrees, stable);
+            let pivots = loop {
+                let rank = rows.len();
+                let mut extended = rows.clone();
+                for row in &rows {
+                    let support: Vec<usize> = (0..universe.len())
+                        .filter(|&c| !ring.base.is_zero(&row[c]))
+                        .collect();
+                    if support.iter().any(|&c| degrees[c] == d) {
+                        continue;
+                    }
+                    for i in 0..n {
+                        let mut shifted = vec![K::zero(); universe.len()];
+                        for &c in &support {
+                            shifted[column[&times_var(&universe[c], i)]] = row[c].clone();
+                        }
+                        extended.push(shifted);
+                    }
+                }
+                let (reduced, pivots) = echelon(ring.base, extended, &degrees, stable);
+                let grown = reduced.len() > rank;
+                rows = reduced;
+                if !grown {
+                    break pivots;
+                }
+            };
+            let pivot_of: HashMap<usize, usize> =
+                pivots.iter().zip(0..).map(|(&c, r)| (c, r)).collect();
+            let mut order_ideal: Vec<Monomial<P>> = (0..universe.len())
+                .rev()
+                .filter(|c| !pivot_of.contains_key(c))
+                .map(|c| universe[c].clone())
+                .collect();
+            let in_order_ideal: HashSet<&Monomial<P>> = order_ideal.iter().collect();
+            let closed = order_ideal.iter().all(|o| {
+                (0..n).all(|i| {
+                    o.powers[i].is_zero() || {
+                        let mut powers = o.powers.clone();
+                        powers[i] = powers[i] - P::one();
+                        in_order_ideal.contains(&Monomial { powers })
+                    }
+                })
+            });
+            if !closed || order_ideal.iter().any(|o| degree(o) >= d) {
+                continue;
+            }
+            let mut border: Vec<Monomial<P>> = Vec::new();
+            for o in &order_ideal {
+                for i in 0..n {
+                    let b = times_var(o, i);
+                    if !in_order_ideal.contains(&b) && !border.contains(&b) {
+                        border.push(b);
+                    }
+                }
+            }
+            if order_ideal.is_empty() {
+                border.push(Monomial {
+                    powers: smallvec![P::zero(); n],
+                });
+            }
+            border.sort_by(|a, b| GrLex.cmp(a, b));
+            let polynomials = border
+                .iter()
+                .map(|b| {
+                    let row = &rows[pivot_of[&column[b]]];
+                    let terms = (0..universe.len())
+                        .filter(|&c| !ring.base.is_zero(&row[c]))
+                        .map(|c| (universe[c].clone(), row[c].clone()));
+                    Polynomial::from_terms(ring, terms)
+                        .expect("the monomials have one exponent for each variable")
+                })
+                .collect();
+            order_ideal.sort_by(|a, b| GrLex.cmp(a, b));
+            let basis = BorderBasis {
+                ring,
+                order_ideal,
+                border,
+                polynomials,
+            };
+            if basis.is_border_basis()
+                && generators
+                    .iter()
+                    .all(|f| basis.normal_form(f).terms.is_empty())
+            {
+                return Ok(basis);
+            }
+        }
+    }
+    Err(ChidogError::InvalidInput(format!(
+        "no border basis with monomials of degree up to {max_degree}; \
+         the ideal may not be zero-dimensional"
+    )))
+}
@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+use crate::error::ChidogError;
+use crate::handle::{OwnedPolynomial, SharedRing, shared_ring};
+
+/// A polynomial ring over the rationals, behind a pointer for C.
+///
+/// Neither rings nor polynomials may be used from more than one thread.
+pub struct ChidogRing(SharedRing);
+
+/// A polynomial of a [`ChidogRing`], behind a pointer for C. It keeps its
+/// ring alive, so the ring may be freed first.
+pub struct ChidogPolynomial(OwnedPolynomial);
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Records `message` for [`chidog_last_error`] and returns null.
+fn fail<T>(message: impl ToString) -> *mut T {
+    // Interior NUL bytes cannot occur in C strings, so they are dropped.
+    let message = message.to_string().replace('\0', "");
+    LAST_ERROR.with(|e| *e.borrow_mut() = CString::new(message).unwrap());
+    ptr::null_mut()
+}
+
+fn new_polynomial(result: Result<OwnedPolynomial, ChidogError>) -> *mut ChidogPolynomial {
+    match result {
+        Ok(f) => Box::into_raw(Box::new(ChidogPolynomial(f))),
+        Err(e) => fail(e),
+    }
+}
+
+/// The message of the last failure on this thread, which is valid until the
+/// next failure, or the empty string if there was none.
+#[unsafe(no_mangle)]
+pub extern "C" fn chidog_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// A new ring in the `nvars` variables named by `vars`, in decreasing order
+/// of significance, or null if a name is not UTF-8.
+///
+/// # Safety
+///
+/// `vars` must point to `nvars` NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_ring_new(
+    vars: *const *const c_char,
+    nvars: usize,
+) -> *mut ChidogRing {
+    let mut names = Vec::with_capacity(nvars);
+    for i in 0..nvars {
+        // SAFETY: the caller guarantees `nvars` valid strings.
+        let name = unsafe { CStr::from_ptr(*vars.add(i)) };
+        match name.to_str() {
+            Ok(name) => names.push(name.to_string()),
+            Err(_) => return fail(format!("variable {i} is not UTF-8")),
+        }
+    }
+    Box::into_raw(Box::new(ChidogRing(shared_ring(names))))
+}
+
+/// Frees `ring`, which may be null.
+///
+/// # Safety
+///
+/// `ring` must be null or returned by [`chidog_ring_new`] and not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_ring_free(ring: *mut ChidogRing) {
+    if !ring.is_null() {
+        // SAFETY: the caller guarantees that `ring` is owned.
+        drop(unsafe { Box::from_raw(ring) });
+    }
+}
+
+/// The polynomial of `ring` written as `input`, such as
+/// `x^2*y - 3/4*z + 1`, or null if it cannot be read.
+///
+/// # Safety
+///
+/// `ring` must be a live ring and `input` a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_parse(
+    ring: *const ChidogRing,
+    input: *const c_char,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees both pointers.
+    let (ring, input) = unsafe { (&(*ring).0, CStr::from_ptr(input)) };
+    match input.to_str() {
+        Ok(input) => new_polynomial(OwnedPolynomial::parse(ring, input)),
+        Err(_) => fail("input is not UTF-8"),
+    }
+}
+
+/// The constant polynomial of `ring` written as `c`, an integer or a
+/// fraction such as `-3/4`, or null if it cannot be read.
+///
+/// # Safety
+///
+/// `ring` must be a live ring and `c` a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_constant(
+    ring: *const ChidogRing,
+    c: *const c_char,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees both pointers.
+    let (ring, c) = unsafe { (&(*ring).0, CStr::from_ptr(c)) };
+    match c.to_str().ok().and_then(|c| c.parse().ok()) {
+        Some(c) => new_polynomial(Ok(OwnedPolynomial::constant(ring, c))),
+        None => fail(format!("invalid number `{}`", c.to_string_lossy())),
+    }
+}
+
+/// The sum `f + g`, or null if they are of different rings.
+///
+/// # Safety
+///
+/// `f` and `g` must be live polynomials.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_add(
+    f: *const ChidogPolynomial,
+    g: *const ChidogPolynomial,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees both pointers.
+    let (f, g) = unsafe { (&(*f).0, &(*g).0) };
+    new_polynomial(f.add(g))
+}
+
+/// The difference `f - g`, or null if they are of different rings.
+///
+/// # Safety
+///
+/// `f` and `g` must be live polynomials.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_sub(
+    f: *const ChidogPolynomial,
+    g: *const ChidogPolynomial,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees both pointers.
+    let (f, g) = unsafe { (&(*f).0, &(*g).0) };
+    new_polynomial(f.sub(g))
+}
+
+/// The product `f * g`, or null if they are of different rings.
+///
+/// # Safety
+///
+/// `f` and `g` must be live polynomials.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_mul(
+    f: *const ChidogPolynomial,
+    g: *const ChidogPolynomial,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees both pointers.
+    let (f, g) = unsafe { (&(*f).0, &(*g).0) };
+    new_polynomial(f.mul(g))
+}
+
+/// The negation `-f`.
+///
+/// # Safety
+///
+/// `f` must be a live polynomial.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_neg(
+    f: *const ChidogPolynomial,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees the pointer.
+    let f = unsafe { &(*f).0 };
+    new_polynomial(Ok(f.neg()))
+}
+
+/// The power `f^n`.
+///
+/// # Safety
+///
+/// `f` must be a live polynomial.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_pow(
+    f: *const ChidogPolynomial,
+    n: u32,
+) -> *mut ChidogPolynomial {
+    // SAFETY: the caller guarantees the pointer.
+    let f = unsafe { &(*f).0 };
+    new_polynomial(Ok(f.pow(n)))
+}
+
+/// Whether `f` is zero.
+///
+/// # Safety
+///
+/// `f` must be a live polynomial.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_is_zero(f: *const ChidogPolynomial) -> bool {
+    // SAFETY: the caller guarantees the pointer.
+    unsafe { (*f).0.is_zero() }
+}
+
+/// Whether `f` and `g` are equal polynomials of the same ring.
+///
+/// # Safety
+///
+/// `f` and `g` must be live polynomials.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_equals(
+    f: *const ChidogPolynomial,
+    g: *const ChidogPolynomial,
+) -> bool {
+    // SAFETY: the caller guarantees both pointers.
+    unsafe { (*f).0.equals(&(*g).0) }
+}
+
+/// The total degree of `f`, or -1 if it is zero.
+///
+/// # Safety
+///
+/// `f` must be a live polynomial.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_degree(f: *const ChidogPolynomial) -> i64 {
+    // SAFETY: the caller guarantees the pointer.
+    unsafe { (*f).0.degree().map_or(-1, |d| d as i64) }
+}
+
+/// `f` written as read by [`chidog_polynomial_parse`], with its terms in
+/// decreasing lexicographic order, to be freed with [`chidog_string_free`].
+///
+/// # Safety
+///
+/// `f` must be a live polynomial.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_to_string(f: *const ChidogPolynomial) -> *mut c_char {
+    // SAFETY: the caller guarantees the pointer.
+    let f = unsafe { &(*f).0 };
+    // Parsed variable names contain no NUL bytes.
+    CString::new(f.to_infix()).unwrap().into_raw()
+}
+
+/// Frees `f`, which may be null.
+///
+/// # Safety
+///
+/// `f` must be null or a polynomial returned by this library and not yet
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_polynomial_free(f: *mut ChidogPolynomial) {
+    if !f.is_null() {
+        // SAFETY: the caller guarantees that `f` is owned.
+        drop(unsafe { Box::from_raw(f) });
+    }
+}
+
+/// Frees `s`, which may be null.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by [`chidog_polynomial_to_string`]
+/// and not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn chidog_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees that `s` is owned.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, Integer, PrimInt, Unsigned};
+
+use crate::Polynomial;
+use crate::infix::to_infix_with;
+
+/// How [`to_maple`] and [`to_wolfram`] write coefficients.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoefficientFormat {
+    /// Exact integers and fractions, such as `3/4`.
+    #[default]
+    Exact,
+    /// Floating-point numbers rounded to the given number of significant
+    /// digits, at least one, such as `0.75` or `1.5e-12`.
+    Float(usize),
+}
+
+/// The decimal digits and exponent of the positive `c` rounded to `digits`
+/// significant digits, half away from zero, so that `c` is about
+/// `0.d_1 d_2... * 10^(exponent + 1)`.
+fn round_significant(c: &BigRational, digits: usize) -> (String, i64) {
+    let ten = BigInt::from(10);
+    let scale = |e: i64| {
+        num::pow(
+            BigRational::from_integer(ten.clone()),
+            e.unsigned_abs() as usize,
+        )
+    };
+    // An estimate of floor(log10 c) from the lengths of the numerator and
+    // denominator, corrected below.
+    let mut exponent = c.numer().to_string().len() as i64 - c.denom().to_string().len() as i64;
+    loop {
+        let shift = digits as i64 - 1 - exponent;
+        let scaled = match shift >= 0 {
+            true => c * scale(shift),
+            false => c / scale(shift),
+        };
+        let (q, r) = scaled.numer().div_rem(scaled.denom());
+        let upper = num::pow(ten.clone(), digits);
+        if q < num::pow(ten.clone(), digits - 1) {
+            exponent -= 1;
+        } else if q >= upper {
+            exponent += 1;
+        } else if r * 2 < *scaled.denom() {
+            return (q.to_string(), exponent);
+        } else if &q + 1u32 < upper {
+            return ((q + 1u32).to_string(), exponent);
+        } else {
+            return (num::pow(ten, digits - 1).to_string(), exponent + 1);
+        }
+    }
+}
+
+/// The positive `c` written by `format`, with the exponent of floats in
+/// scientific notation written by `scientific`.
+fn coefficient(c: &BigRational, format: CoefficientFormat, scientific: &str) -> String {
+    let CoefficientFormat::Float(digits) = format else {
+        return c.to_string();
+    };
+    let (m, exponent) = round_significant(c, digits.max(1));
+    let m = m.trim_end_matches('0');
+    let m = if m.is_empty() { "0" } else { m };
+    if (-5..15).contains(&exponent) {
+        let point = exponent + 1;
+        if point <= 0 {
+            format!("0.{}{m}", "0".repeat(point.unsigned_abs() as usize))
+        } else if point as usize >= m.len() {
+            format!("{m}{}.", "0".repeat(point as usize - m.len()))
+        } else {
+            format!("{}.{}", &m[..point as usize], &m[point as usize..])
+        }
+    } else {
+        let (first, rest) = m.split_at(1);
+        format!("{first}.{rest}{scientific}{exponent}")
+    }
+}
+
+/// `f` as a Maple expression, such as `-1/2*x^2*y+3*z-1`, with its terms
+/// in decreasing lexicographic order and coefficients written by `format`,
+/// as `1.5e-12` for floats in scientific notation.
+pub fn to_maple<R, V, K, P, S>(
+    f: &Polynomial<'_, R, V, K, P, S>,
+    format: CoefficientFormat,
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    to_infix_with(f, "^", |c| coefficient(c, format, "e"))
+}
+
+/// The Maple statement assigning `f` to `name`, such as
+/// `poly := x^2+3/4*y;`, with coefficients written by `format`.
+pub fn maple_assignment<R, V, K, P, S>(
+    name: &str,
+    f: &Polynomial<'_, R, V, K, P, S>,
+    format: CoefficientFormat,
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    format!("{name} := {};", to_maple(f, format))
+}
+
+/// `f` in the `InputForm` of the Wolfram Language, such as
+/// `-1/2*x^2*y+3*z-1`, with its terms in decreasing lexicographic order and
+/// coefficients written by `format`, as `1.5*^-12` for floats in scientific
+/// notation.
+///
+/// The names of the variables must be Wolfram Language symbols, which
+/// cannot contain `_`.
+pub fn to_wolfram<R, V, K, P, S>(
+    f: &Polynomial<'_, R, V, K, P, S>,
+    format: CoefficientFormat,
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    to_infix_with(f, "^", |c| coefficient(c, format, "*^"))
+}
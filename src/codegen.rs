@@ -0,0 +1,145 @@
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hash};
+
+use num::{FromPrimitive, One, PrimInt, ToPrimitive, Unsigned};
+
+use crate::evaluator::{Instruction, compile_all};
+use crate::{Polynomial, Semiring, SemiringElement};
+
+/// The language of generated source code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+}
+
+impl Language {
+    /// A statement binding the temporary `t{i}` to `value`.
+    fn binding(self, i: usize, value: &str) -> String {
+        match self {
+            Language::Rust => format!("    let t{i} = {value};\n"),
+            Language::C => format!("    const double t{i} = {value};\n"),
+        }
+    }
+}
+
+/// The literal of `c` as a double, which reads the same in Rust and C.
+fn literal<K: ToPrimitive>(c: &K) -> String {
+    format!("{:?}", c.to_f64().unwrap())
+}
+
+/// The statements computing the temporaries of the straight-line program
+/// evaluating `polynomials` at the point `x`, and the expressions of their
+/// values.
+fn body<R, V, K, P, S>(
+    polynomials: &[Polynomial<'_, R, V, K, P, S>],
+    language: Language,
+) -> (String, Vec<String>)
+where
+    K: Clone + One + PartialEq + ToPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let (instructions, outputs) = compile_all(polynomials);
+    let mut body = String::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        let value = match instruction {
+            Instruction::Constant(c) => literal(c),
+            Instruction::Var(j) => format!("x[{j}]"),
+            Instruction::Add(a, b) => format!("t{a} + t{b}"),
+            Instruction::Mul(a, b) => format!("t{a} * t{b}"),
+        };
+        body.push_str(&language.binding(i, &value));
+    }
+    let values = outputs
+        .iter()
+        .map(|output| match output {
+            Some(i) => format!("t{i}"),
+            None => "0.0".to_string(),
+        })
+        .collect();
+    (body, values)
+}
+
+/// The source in `language` of a standalone function `name` returning the
+/// value of `f` at a point `x` of `f64`s, or `double`s in C.
+///
+/// The function evaluates the multivariate Horner scheme of
+/// [`Polynomial::compile_evaluator`], one temporary per operation, with the
+/// coefficients rounded to `f64`. Panics if a coefficient has no `f64`
+/// value.
+pub fn function<R, V, K, P, S>(
+    name: &str,
+    f: &Polynomial<'_, R, V, K, P, S>,
+    language: Language,
+) -> String
+where
+    K: Clone + One + PartialEq + ToPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let n = f.elem_of.vars.len();
+    let (body, values) = body(std::slice::from_ref(f), language);
+    let value = &values[0];
+    match language {
+        Language::Rust => format!(
+            "#[allow(unused_variables)]\npub fn {name}(x: &[f64; {n}]) -> f64 {{\n{body}    {value}\n}}\n"
+        ),
+        Language::C => {
+            format!("double {name}(const double x[{n}]) {{\n{body}    return {value};\n}}\n")
+        }
+    }
+}
+
+/// The source in `language` of a standalone function `name` writing the
+/// values of the polynomials of `system`, which must be in the same ring,
+/// at a point `x` to `values`, and, if `jacobian` is set, their partial
+/// derivatives to `jacobian`, with `jacobian[i][j]` the derivative of
+/// `system[i]` with respect to `x_j`.
+///
+/// All values are computed by one straight-line program, as in
+/// [`function`], in which subexpressions shared by several polynomials,
+/// such as the powers of the variables, are computed once.
+pub fn system_function<R, V, K, P, S>(
+    name: &str,
+    system: &[Polynomial<'_, R, V, K, P, S>],
+    jacobian: bool,
+    language: Language,
+) -> String
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + PartialEq + ToPrimitive + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Clone + Default,
+{
+    let m = system.len();
+    let n = system.first().map_or(0, |f| f.elem_of.vars.len());
+    let mut polynomials = system.to_vec();
+    if jacobian {
+        for f in system {
+            polynomials.extend((0..n).map(|j| f.derivative(j)));
+        }
+    }
+    let (mut source, values) = body(&polynomials, language);
+    for (i, value) in values[..m].iter().enumerate() {
+        writeln!(source, "    values[{i}] = {value};").unwrap();
+    }
+    for (k, value) in values[m..].iter().enumerate() {
+        writeln!(source, "    jacobian[{}][{}] = {value};", k / n, k % n).unwrap();
+    }
+    let signature = match (language, jacobian) {
+        (Language::Rust, false) => {
+            format!(
+                "#[allow(unused_variables)]\npub fn {name}(x: &[f64; {n}], values: &mut [f64; {m}])"
+            )
+        }
+        (Language::Rust, true) => format!(
+            "#[allow(unused_variables)]\npub fn {name}(x: &[f64; {n}], values: &mut [f64; {m}], jacobian: &mut [[f64; {n}]; {m}])"
+        ),
+        (Language::C, false) => format!("void {name}(const double x[{n}], double values[{m}])"),
+        (Language::C, true) => format!(
+            "void {name}(const double x[{n}], double values[{m}], double jacobian[{m}][{n}])"
+        ),
+    };
+    format!("{signature} {{\n{source}}}\n")
+}
@@ -0,0 +1,139 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::error::ChidogError;
+
+/// A report of how far a long-running computation has come, passed to the
+/// callback of a [`ComputationControl`] at each of its steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// The algorithm, such as `"buchberger"`.
+    pub algorithm: &'static str,
+    /// The number of steps taken, such as S-pairs reduced.
+    pub steps: usize,
+    /// The number of steps known to remain, which may still grow, if any
+    /// bound is known.
+    pub remaining: Option<usize>,
+    /// The number of terms or coefficients held by the intermediate
+    /// results, a measure of their memory.
+    pub terms: usize,
+}
+
+/// A flag which cancels the computations of a [`ComputationControl`] from
+/// another thread.
+#[derive(Clone, Debug, Default)]
+pub struct Canceller(Arc<AtomicBool>);
+
+impl Canceller {
+    /// Cancels the computations, which fail with a `Cancelled` error at
+    /// their next step.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+type Callback = Box<dyn Fn(&Progress) + Send + Sync>;
+
+/// Progress reporting, cancellation and resource budgets for long-running
+/// computations, such as [`groebner_basis_controlled`].
+///
+/// The computation consults the control at each step, calling the progress
+/// callback and then failing with a `Cancelled` error once cancelled, or
+/// with a `BudgetExceeded` error once over its time limit or once its
+/// intermediate results hold more terms than its term limit.
+///
+/// [`groebner_basis_controlled`]: crate::groebner::groebner_basis_controlled
+#[derive(Default)]
+pub struct ComputationControl {
+    cancelled: Canceller,
+    deadline: Option<(Instant, Duration)>,
+    term_limit: Option<usize>,
+    progress: Option<Callback>,
+}
+
+impl ComputationControl {
+    /// A control without callback or budgets, which lets computations run
+    /// until cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits computations to `limit` from now.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.deadline = Some((Instant::now() + limit, limit));
+        self
+    }
+
+    /// Limits the intermediate results of computations to `limit` terms.
+    pub fn with_term_limit(mut self, limit: usize) -> Self {
+        self.term_limit = Some(limit);
+        self
+    }
+
+    /// Calls `callback` at each step of the computations.
+    pub fn with_progress(mut self, callback: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// A flag cancelling the computations, which may be sent to another
+    /// thread.
+    pub fn canceller(&self) -> Canceller {
+        self.cancelled.clone()
+    }
+
+    /// Cancels the computations.
+    pub fn cancel(&self) {
+        self.cancelled.cancel();
+    }
+
+    /// Whether the computations have been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.0.load(Ordering::Relaxed)
+    }
+
+    /// Reports `progress` and checks whether the computation may go on.
+    pub fn check(&self, progress: &Progress) -> Result<(), ChidogError> {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+        self.check_interrupted()?;
+        if let Some(limit) = self.term_limit
+            && progress.terms > limit
+        {
+            return Err(ChidogError::BudgetExceeded(format!(
+                "term limit of {limit}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks whether the computation may go on, without reporting progress
+    /// or measuring terms, as the workers of a parallel computation do
+    /// during their steps: fails once cancelled or over the time limit.
+    pub(crate) fn check_interrupted(&self) -> Result<(), ChidogError> {
+        if self.is_cancelled() {
+            return Err(ChidogError::Cancelled);
+        }
+        if let Some((deadline, limit)) = self.deadline
+            && Instant::now() >= deadline
+        {
+            return Err(ChidogError::BudgetExceeded(format!(
+                "time limit of {limit:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Debug for ComputationControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComputationControl")
+            .field("cancelled", &self.is_cancelled())
+            .field("deadline", &self.deadline.map(|(deadline, _)| deadline))
+            .field("term_limit", &self.term_limit)
+            .finish_non_exhaustive()
+    }
+}
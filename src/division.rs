@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::zip;
+
+use bumpalo::Bump;
+use num::{PrimInt, Unsigned};
+
+use crate::arena::Arena;
+use crate::control::ComputationControl;
+use crate::error::ChidogError;
+use crate::geobucket::Geobucket;
+use crate::order::MonomialOrder;
+use crate::{Field, FieldElement, Monomial, Polynomial, Powers, Semiring, SemiringElement};
+
+/// The number of reduction steps between the checks of a
+/// [`ComputationControl`] by [`Polynomial::reduce_controlled`].
+const CHECK_INTERVAL: usize = 64;
+
+/// The leading term of a divisor, and its tail in decreasing order.
+type Split<'d, K, P> = ((&'d Monomial<P>, &'d K), Vec<(&'d Monomial<P>, &'d K)>);
+
+/// The divisors of a multivariate division, split into leading terms and
+/// tails once, so that they can be reused for many dividends.
+///
+/// For an involutive division, each divisor also has its multiplicative
+/// variables, and only divides the monomials that are its leading monomial
+/// times a product of them.
+pub(crate) struct Divisors<'d, K, P> {
+    split: Vec<Option<Split<'d, K, P>>>,
+    multiplicative: Option<Vec<Vec<bool>>>,
+}
+
+impl<'d, K: Clone, P: Hash + PrimInt + Unsigned> Divisors<'d, K, P> {
+    pub(crate) fn new<R, V, O>(divisors: &'d [Polynomial<'_, R, V, K, P>], order: &O) -> Self
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        O: MonomialOrder,
+    {
+        let split = divisors
+            .iter()
+            .map(|g| {
+                let mut terms = g.sorted_terms(order).into_iter();
+                terms.next().map(|lead| (lead, terms.collect()))
+            })
+            .collect();
+        Self {
+            split,
+            multiplicative: None,
+        }
+    }
+
+    /// The divisors of an involutive division, where `multiplicative[i][j]`
+    /// tells whether variable `j` is multiplicative for `divisors[i]`.
+    pub(crate) fn involutive<R, V, O>(
+        divisors: &'d [Polynomial<'_, R, V, K, P>],
+        order: &O,
+        multiplicative: Vec<Vec<bool>>,
+    ) -> Self
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        O: MonomialOrder,
+    {
+        Self {
+            multiplicative: Some(multiplicative),
+            ..Self::new(divisors, order)
+        }
+    }
+
+    /// Whether the leading monomial `lm` of divisor `i` divides `m`, in the
+    /// involutive sense if the division is involutive.
+    fn divides(&self, i: usize, lm: &Monomial<P>, m: &Monomial<P>) -> bool {
+        lm.divides(m)
+            && self.multiplicative.as_ref().is_none_or(|multiplicative| {
+                zip(&multiplicative[i], zip(&lm.powers, &m.powers))
+                    .all(|(&multiplicative, (p, q))| multiplicative || p == q)
+            })
+    }
+}
+
+impl<'a, R, V, K, P> Polynomial<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The remainder of `self` by `divisors`, adding the quotients to
+    /// `quotients` if given.
+    ///
+    /// The dividend is kept in a geobucket allocated in an arena of the
+    /// ring, which is compacted whenever it is mostly garbage.
+    pub(crate) fn reduce<O: MonomialOrder + Clone>(
+        &self,
+        divisors: &Divisors<'_, K, P>,
+        order: &O,
+        quotients: Option<&mut [Self]>,
+    ) -> Self {
+        self.reduce_checked(divisors, order, quotients, None)
+            .expect("uncontrolled reductions are not interrupted")
+    }
+
+    /// The remainder of [`Polynomial::reduce`], checking every
+    /// [`CHECK_INTERVAL`] steps whether `control` is cancelled or out of
+    /// time, and failing if so.
+    pub(crate) fn reduce_controlled<O: MonomialOrder + Clone>(
+        &self,
+        divisors: &Divisors<'_, K, P>,
+        order: &O,
+        control: &ComputationControl,
+    ) -> Result<Self, ChidogError> {
+        self.reduce_checked(divisors, order, None, Some(control))
+    }
+
+    /// The remainder of [`Polynomial::reduce`], checking `control` if given.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(terms = self.terms.len(), divisors = divisors.split.len())
+        )
+    )]
+    fn reduce_checked<O: MonomialOrder + Clone>(
+        &self,
+        divisors: &Divisors<'_, K, P>,
+        order: &O,
+        mut quotients: Option<&mut [Self]>,
+        control: Option<&ComputationControl>,
+    ) -> Result<Self, ChidogError> {
+        let mut remainder = Self {
+            elem_of: self.elem_of,
+            terms: HashMap::default(),
+        };
+        let mut arena = self.elem_of.arenas().lend();
+        let mut survivors: Vec<_> = self
+            .sorted_terms(order)
+            .into_iter()
+            .map(|(m, c)| (m.clone(), c.clone()))
+            .collect();
+        let mut steps: usize = 0;
+        let mut step = || {
+            steps += 1;
+            match control {
+                Some(control) if steps.is_multiple_of(CHECK_INTERVAL) => {
+                    control.check_interrupted()
+                }
+                _ => Ok(()),
+            }
+        };
+        while !survivors.is_empty() {
+            let mut dividend = Geobucket::new_in(self.elem_of, order.clone(), &*arena);
+            dividend.add_terms(survivors);
+            survivors = self.reduce_in(
+                dividend,
+                divisors,
+                &mut remainder,
+                quotients.as_deref_mut(),
+                &arena,
+                &mut step,
+            )?;
+            arena.reset();
+        }
+        self.elem_of.counters().record_reduction(steps);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(steps, remainder = remainder.terms.len(), "reduced");
+        Ok(remainder)
+    }
+
+    /// Reduces `dividend` by `divisors` until it is zero or `arena` should
+    /// be compacted, moving terms to `remainder` and `quotients`, and returns
+    /// the terms left in `dividend`. `step` is called after each reduction
+    /// step, and the reduction fails if it does.
+    fn reduce_in<O: MonomialOrder>(
+        &self,
+        mut dividend: Geobucket<'_, R, V, K, P, O, &Bump>,
+        divisors: &Divisors<'_, K, P>,
+        remainder: &mut Self,
+        mut quotients: Option<&mut [Self]>,
+        arena: &Arena<'_>,
+        step: &mut impl FnMut() -> Result<(), ChidogError>,
+    ) -> Result<Vec<(Monomial<P>, K)>, ChidogError> {
+        while let Some((m, c)) = dividend.pop_leading() {
+            let found = divisors.split.iter().enumerate().find_map(|(i, g)| {
+                g.as_ref()
+                    .filter(|((lm, _), _)| divisors.divides(i, lm, &m))
+                    .map(|(lead, tail)| (i, lead, tail))
+            });
+            let Some((i, (lm, lc), tail)) = found else {
+                remainder.terms.insert(m, c);
+                continue;
+            };
+            let shift: Powers<P> = zip(&m.powers, &lm.powers).map(|(p, q)| *p - *q).collect();
+            let t = c / (*lc).clone();
+            let product = tail
+                .iter()
+                .map(|(n, d)| {
+                    let powers = zip(&shift, &n.powers).map(|(p, q)| *p + *q).collect();
+                    (Monomial { powers }, t.clone() * (*d).clone())
+                })
+                .filter(|(_, d)| !self.elem_of.base.is_zero(d))
+                .collect();
+            dividend.sub_terms(product);
+            step()?;
+            if let Some(quotients) = quotients.as_deref_mut() {
+                quotients[i].terms.insert(Monomial { powers: shift }, t);
+            }
+            if arena.is_mostly_garbage(dividend.len() * size_of::<(Monomial<P>, K)>()) {
+                return Ok(dividend.into_terms());
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// The quotients `q_i` and the remainder `r` of the multivariate division
+    /// of `self` by `divisors` in `order`, with `self = ∑ q_i*divisors[i] +
+    /// r`, where no term of `r` is divisible by the leading monomial of any
+    /// nonzero divisor.
+    ///
+    /// Each step removes the leading term `t` of the running dividend. If the
+    /// leading monomial of some divisor `g_i` divides it, the first such
+    /// divisor is used: `t/lt(g_i)` is added to `q_i` and `t/lt(g_i)` times
+    /// the tail of `g_i` is subtracted from the dividend. Otherwise `t` moves
+    /// to the remainder. The dividend is kept in a [`Geobucket`], so the
+    /// leading term is cheap to find and the subtractions do not rebuild it.
+    pub fn div_rem<O: MonomialOrder + Clone>(
+        &self,
+        divisors: &[Self],
+        order: &O,
+    ) -> (Vec<Self>, Self) {
+        let mut quotients: Vec<_> = divisors
+            .iter()
+            .map(|_| Self {
+                elem_of: self.elem_of,
+                terms: HashMap::default(),
+            })
+            .collect();
+        let divisors = Divisors::new(divisors, order);
+        let remainder = self.reduce(&divisors, order, Some(&mut quotients));
+        (quotients, remainder)
+    }
+
+    /// The remainder of the multivariate division of `self` by `divisors` in
+    /// `order`, as in [`Polynomial::div_rem`].
+    pub fn rem<O: MonomialOrder + Clone>(&self, divisors: &[Self], order: &O) -> Self {
+        self.reduce(&Divisors::new(divisors, order), order, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use num::BigRational;
+
+    use super::*;
+    use crate::order::GRevLex;
+    use crate::{AlreadyRing, PolynomialRing};
+
+    type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+    #[test]
+    fn controlled_reduction_stops_when_cancelled() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(vec!["x".into()], &base);
+        let f = Poly::from_sympy(&ring, "x^200").unwrap();
+        let g = [Poly::from_sympy(&ring, "x - 1").unwrap()];
+        let divisors = Divisors::new(&g, &GRevLex);
+        let control = ComputationControl::new();
+        let remainder = f.reduce_controlled(&divisors, &GRevLex, &control).unwrap();
+        assert_eq!(remainder.terms, f.reduce(&divisors, &GRevLex, None).terms);
+        control.cancel();
+        let result = f.reduce_controlled(&divisors, &GRevLex, &control);
+        assert!(matches!(result, Err(ChidogError::Cancelled)));
+    }
+}
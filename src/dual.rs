@@ -0,0 +1,134 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{One, Zero};
+
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// A dual number `real + dual*ε`, where `ε^2 = 0`.
+///
+/// Evaluating a polynomial `f` at `x + ε` yields `f(x) + f'(x)*ε`, so the
+/// dual numbers give forward-mode automatic differentiation: to compute the
+/// partial derivative of a multivariate polynomial with respect to one
+/// variable, evaluate at a point whose coordinate in that variable is
+/// [`Dual::variable`] and whose other coordinates are [`Dual::constant`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dual<K> {
+    pub real: K,
+    pub dual: K,
+}
+
+/// The ring `K[ε]/(ε^2)` of dual numbers over the ring with elements in `K`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DualNumbers<K> {
+    phantom: PhantomData<K>,
+}
+
+impl<K: RingElement + Clone> Semiring<Dual<K>> for DualNumbers<K> {}
+impl<K: RingElement + Clone> Ring<Dual<K>> for DualNumbers<K> {}
+impl<K: RingElement + Clone> SemiringElement for Dual<K> {}
+impl<K: RingElement + Clone> RingElement for Dual<K> {}
+
+impl<K: RingElement> Dual<K> {
+    pub fn new(real: K, dual: K) -> Self {
+        Self { real, dual }
+    }
+
+    /// The value `x`, whose derivative vanishes.
+    pub fn constant(x: K) -> Self {
+        Self::new(x, K::zero())
+    }
+
+    /// The value `x + ε`, representing the variable being differentiated
+    /// with respect to.
+    pub fn variable(x: K) -> Self {
+        Self::new(x, K::one())
+    }
+}
+
+impl<K: RingElement> From<K> for Dual<K> {
+    fn from(x: K) -> Self {
+        Self::constant(x)
+    }
+}
+
+impl<K: RingElement> Add for Dual<K> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.real + rhs.real, self.dual + rhs.dual)
+    }
+}
+
+impl<K: RingElement> Sub for Dual<K> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.real - rhs.real, self.dual - rhs.dual)
+    }
+}
+
+impl<K: RingElement> Neg for Dual<K> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl<K: RingElement + Clone> Mul for Dual<K> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.real.clone() * rhs.real.clone(),
+            self.real * rhs.dual + self.dual * rhs.real,
+        )
+    }
+}
+
+impl<K: RingElement> AddAssign for Dual<K> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.real += rhs.real;
+        self.dual += rhs.dual;
+    }
+}
+
+impl<K: RingElement> SubAssign for Dual<K> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.real -= rhs.real;
+        self.dual -= rhs.dual;
+    }
+}
+
+impl<K: RingElement + Clone> MulAssign for Dual<K> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<K: RingElement> Zero for Dual<K> {
+    fn zero() -> Self {
+        Self::new(K::zero(), K::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.real.is_zero() && self.dual.is_zero()
+    }
+}
+
+impl<K: RingElement + Clone> One for Dual<K> {
+    fn one() -> Self {
+        Self::new(K::one(), K::zero())
+    }
+}
+
+impl<K: Display> Display for Dual<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        write!(f, "({}{:+}*ε)", self.real, self.dual)
+    }
+}
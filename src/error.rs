@@ -0,0 +1,53 @@
+use thiserror::Error;
+
+/// Errors reported by fallible operations throughout the crate.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ChidogError {
+    /// Division by an element which is zero, or which is not known to enough
+    /// precision to be distinguished from zero.
+    #[error("division by zero")]
+    DivisionByZero,
+
+    /// An operation needed more digits of an approximate value than are known.
+    #[error("precision loss: {required} digits of precision required, but only {available} known")]
+    PrecisionLoss { required: i64, available: i64 },
+
+    /// An operation defined only on integral elements was given a
+    /// non-integral one.
+    #[error("element is not integral")]
+    NotIntegral,
+
+    /// An operation was applied to an argument outside its domain, such as
+    /// the logarithm of a power series whose constant term is not 1.
+    #[error("argument outside the domain of {0}")]
+    Domain(String),
+
+    /// Input to a constructor does not satisfy the axioms of the structure
+    /// it describes, such as a multiplication table which is not a group.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+
+    /// Input in some textual or binary format could not be parsed.
+    #[error("parse error: {0}")]
+    Parse(String),
+
+    /// A multimodular computation found no verified reconstruction of its
+    /// result from the images modulo the given number of primes.
+    #[error("no verified reconstruction from {0} primes")]
+    ReconstructionFailed(usize),
+
+    /// An exponent of a monomial does not fit in its exponent type, or
+    /// exceeds the largest exponent allowed by its ring.
+    #[error("exponent overflow")]
+    ExponentOverflow,
+
+    /// A computation was cancelled through its
+    /// [`ComputationControl`](crate::control::ComputationControl).
+    #[error("computation cancelled")]
+    Cancelled,
+
+    /// A computation exceeded a budget of its
+    /// [`ComputationControl`](crate::control::ComputationControl).
+    #[error("budget exceeded: {0}")]
+    BudgetExceeded(String),
+}
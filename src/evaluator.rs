@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use num::{One, PrimInt, Unsigned};
+
+use crate::{Polynomial, SemiringElement};
+
+/// An instruction of an [`Evaluator`], which computes the next register
+/// from constants, coordinates of the point and earlier registers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction<K> {
+    /// The constant `c`.
+    Constant(K),
+    /// The coordinate of variable `i` of the point.
+    Var(usize),
+    /// The sum of two earlier registers.
+    Add(usize, usize),
+    /// The product of two earlier registers.
+    Mul(usize, usize),
+}
+
+/// A straight-line program evaluating a fixed polynomial, compiled once by
+/// [`Polynomial::compile_evaluator`] and then run at many points.
+///
+/// Register `i` holds the value of instruction `i`, and the value of the
+/// polynomial is in the output register, or is zero if there is none.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluator<K> {
+    vars: usize,
+    instructions: Vec<Instruction<K>>,
+    output: Option<usize>,
+}
+
+impl<K> Evaluator<K> {
+    /// The number of variables of the point.
+    pub fn vars(&self) -> usize {
+        self.vars
+    }
+
+    pub fn instructions(&self) -> &[Instruction<K>] {
+        &self.instructions
+    }
+
+    /// The register holding the value of the polynomial, or `None` for
+    /// zero.
+    pub fn output(&self) -> Option<usize> {
+        self.output
+    }
+
+    /// The number of multiplications of one evaluation.
+    pub fn multiplications(&self) -> usize {
+        self.instructions
+            .iter()
+            .filter(|i| matches!(i, Instruction::Mul(_, _)))
+            .count()
+    }
+
+    /// The value of the polynomial at `point`, in any ring `T` into which
+    /// the coefficients embed.
+    pub fn eval<T>(&self, point: &[T]) -> T
+    where
+        K: Clone,
+        T: SemiringElement + Clone + From<K>,
+    {
+        assert_eq!(point.len(), self.vars, "wrong number of variables");
+        let mut registers: Vec<T> = Vec::with_capacity(self.instructions.len());
+        for instruction in &self.instructions {
+            let value = match instruction {
+                Instruction::Constant(c) => T::from(c.clone()),
+                Instruction::Var(i) => point[*i].clone(),
+                Instruction::Add(a, b) => registers[*a].clone() + registers[*b].clone(),
+                Instruction::Mul(a, b) => registers[*a].clone() * registers[*b].clone(),
+            };
+            registers.push(value);
+        }
+        match self.output {
+            Some(output) => registers.swap_remove(output),
+            None => T::zero(),
+        }
+    }
+}
+
+/// Builds the instructions of an [`Evaluator`], reusing the register of any
+/// instruction on registers, such as a power of a variable, that was
+/// already emitted.
+struct Compiler<K> {
+    instructions: Vec<Instruction<K>>,
+    emitted: HashMap<(u8, usize, usize), usize>,
+}
+
+impl<K: Clone + One + PartialEq> Compiler<K> {
+    fn emit(&mut self, instruction: Instruction<K>) -> usize {
+        let key = match instruction {
+            Instruction::Constant(_) => None,
+            Instruction::Var(i) => Some((0, i, 0)),
+            Instruction::Add(a, b) => Some((1, a, b)),
+            Instruction::Mul(a, b) => Some((2, a, b)),
+        };
+        if let Some(key) = key
+            && let Some(&register) = self.emitted.get(&key)
+        {
+            return register;
+        }
+        self.instructions.push(instruction);
+        let register = self.instructions.len() - 1;
+        if let Some(key) = key {
+            self.emitted.insert(key, register);
+        }
+        register
+    }
+
+    /// The register holding `x_i^d`, for `d > 0`, by repeated squaring.
+    fn power(&mut self, i: usize, d: usize) -> usize {
+        if d == 1 {
+            return self.emit(Instruction::Var(i));
+        }
+        let half = self.power(i, d / 2);
+        let square = self.emit(Instruction::Mul(half, half));
+        if d.is_multiple_of(2) {
+            square
+        } else {
+            let x = self.emit(Instruction::Var(i));
+            self.emit(Instruction::Mul(square, x))
+        }
+    }
+
+    /// The register holding the sum of the nonzero `terms`, given by their
+    /// exponents and coefficients, or `None` if there are none, by the
+    /// multivariate Horner scheme of [`horner_step`].
+    fn compile(&mut self, terms: Vec<(Vec<usize>, K)>) -> Option<usize> {
+        let (i, d, g, h) = match horner_step(terms)? {
+            HornerStep::Constant(c) => return Some(self.emit(Instruction::Constant(c))),
+            HornerStep::Split {
+                var,
+                power,
+                rest,
+                cofactor,
+            } => (var, power, rest, cofactor),
+        };
+        let power = self.power(i, d);
+        let product = match &h[..] {
+            [(e, c)] if e.iter().all(|&e| e == 0) && c.is_one() => power,
+            _ => {
+                let h = self.compile(h)?;
+                self.emit(Instruction::Mul(power, h))
+            }
+        };
+        match self.compile(g) {
+            Some(g) => Some(self.emit(Instruction::Add(g, product))),
+            None => Some(product),
+        }
+    }
+}
+
+/// One step of the multivariate Horner scheme.
+pub(crate) enum HornerStep<K> {
+    /// A sum of one constant term.
+    Constant(K),
+    /// The split of a sum as `rest + x_var^power * cofactor`.
+    Split {
+        var: usize,
+        power: usize,
+        rest: Vec<(Vec<usize>, K)>,
+        cofactor: Vec<(Vec<usize>, K)>,
+    },
+}
+
+/// The first step of the multivariate Horner scheme for the sum of the
+/// nonzero `terms`, given by their exponents and coefficients, or `None` if
+/// there are none.
+///
+/// The sum is split as `g + x_i^d * h`, where `x_i` is the variable
+/// occurring in the most terms, `g` is the sum of the terms without it and
+/// `d` is its least positive exponent; `g` and `h` are then split the same
+/// way.
+pub(crate) fn horner_step<K>(mut terms: Vec<(Vec<usize>, K)>) -> Option<HornerStep<K>> {
+    let vars = terms.first()?.0.len();
+    let occurrences = |i: usize| terms.iter().filter(|(e, _)| e[i] > 0).count();
+    let Some(i) = (0..vars)
+        .filter(|&i| occurrences(i) > 0)
+        .max_by_key(|&i| (occurrences(i), std::cmp::Reverse(i)))
+    else {
+        let (_, c) = terms.pop().unwrap();
+        return Some(HornerStep::Constant(c));
+    };
+    let d = terms.iter().map(|(e, _)| e[i]).filter(|&e| e > 0).min()?;
+    let (mut h, g): (Vec<_>, Vec<_>) = terms.into_iter().partition(|(e, _)| e[i] > 0);
+    for (e, _) in &mut h {
+        e[i] -= d;
+    }
+    Some(HornerStep::Split {
+        var: i,
+        power: d,
+        rest: g,
+        cofactor: h,
+    })
+}
+
+/// The exponents and coefficients of the terms of `f`, sorted by exponents
+/// so that the program compiled from them does not depend on the order of
+/// the term map.
+pub(crate) fn exponent_terms<R, V, K, P, S>(
+    f: &Polynomial<'_, R, V, K, P, S>,
+) -> Vec<(Vec<usize>, K)>
+where
+    K: Clone,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut terms: Vec<(Vec<usize>, K)> = f
+        .terms
+        .iter()
+        .map(|(m, c)| {
+            let exponents = m.powers.iter().map(|p| p.to_usize().unwrap()).collect();
+            (exponents, c.clone())
+        })
+        .collect();
+    terms.sort_by(|(a, _), (b, _)| a.cmp(b));
+    terms
+}
+
+/// The instructions of one straight-line program computing all of
+/// `polynomials`, which share the registers of common subexpressions, and
+/// the output register of each, or `None` for zero.
+pub(crate) fn compile_all<R, V, K, P, S>(
+    polynomials: &[Polynomial<'_, R, V, K, P, S>],
+) -> (Vec<Instruction<K>>, Vec<Option<usize>>)
+where
+    K: Clone + One + PartialEq,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut compiler = Compiler {
+        instructions: Vec::new(),
+        emitted: HashMap::new(),
+    };
+    let outputs = polynomials
+        .iter()
+        .map(|f| compiler.compile(exponent_terms(f)))
+        .collect();
+    (compiler.instructions, outputs)
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: Clone + One + PartialEq,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// An [`Evaluator`] for `self`, following a multivariate Horner scheme
+    /// in which every power of a variable is computed once, by repeated
+    /// squaring.
+    ///
+    /// Evaluating term by term raises each variable to each exponent anew at
+    /// every point; the evaluator factors out common powers, so that
+    /// evaluating a dense polynomial of degree `d` in one variable takes `d`
+    /// multiplications.
+    pub fn compile_evaluator(&self) -> Evaluator<K> {
+        let (instructions, outputs) = compile_all(std::slice::from_ref(self));
+        Evaluator {
+            vars: self.elem_of.vars.len(),
+            instructions,
+            output: outputs[0],
+        }
+    }
+}
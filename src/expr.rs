@@ -0,0 +1,246 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+use num::{PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::evaluator::{HornerStep, exponent_terms, horner_step};
+use crate::{Monomial, Polynomial, PolynomialRing, Semiring, SemiringElement, TermHasher};
+
+/// An expression tree in the variables `x_i` of some polynomial ring, with
+/// constants of type `K`.
+///
+/// Expressions are converted to polynomials by expanding them with
+/// [`Expr::to_polynomial`], and polynomials to expressions in Horner form by
+/// [`Polynomial::to_expr`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<K> {
+    Constant(K),
+    /// The variable `x_i`.
+    Var(usize),
+    /// The sum of the expressions, which is zero if there are none.
+    Sum(Vec<Expr<K>>),
+    /// The product of the expressions, which is one if there are none.
+    Product(Vec<Expr<K>>),
+    Pow(Box<Expr<K>>, u32),
+}
+
+impl<K> Expr<K> {
+    pub fn pow(self, e: u32) -> Self {
+        Expr::Pow(Box::new(self), e)
+    }
+
+    /// `self` written with the names `vars` of the variables.
+    pub fn to_string_with<V: Display>(&self, vars: &[V]) -> String
+    where
+        K: Display,
+    {
+        let parenthesized = |e: &Expr<K>| match e {
+            Expr::Sum(terms) if terms.len() > 1 => format!("({})", e.to_string_with(vars)),
+            Expr::Product(factors) if factors.len() > 1 => {
+                format!("({})", e.to_string_with(vars))
+            }
+            Expr::Pow(_, _) => format!("({})", e.to_string_with(vars)),
+            _ => e.to_string_with(vars),
+        };
+        match self {
+            Expr::Constant(c) => c.to_string(),
+            Expr::Var(i) => vars[*i].to_string(),
+            Expr::Sum(terms) if terms.is_empty() => "0".to_string(),
+            Expr::Sum(terms) => terms
+                .iter()
+                .map(|t| t.to_string_with(vars))
+                .collect::<Vec<_>>()
+                .join(" + "),
+            Expr::Product(factors) if factors.is_empty() => "1".to_string(),
+            Expr::Product(factors) => factors
+                .iter()
+                .enumerate()
+                .map(|(i, f)| match f {
+                    Expr::Sum(_) => parenthesized(f),
+                    Expr::Constant(c) if i > 0 && c.to_string().starts_with('-') => {
+                        format!("({c})")
+                    }
+                    _ => f.to_string_with(vars),
+                })
+                .collect::<Vec<_>>()
+                .join("*"),
+            Expr::Pow(base, e) => format!("{}^{e}", parenthesized(base)),
+        }
+    }
+}
+
+impl<K: SemiringElement + Clone + PartialEq> Expr<K> {
+    /// `self` simplified: nested sums and products are flattened, constant
+    /// subexpressions are folded, zeros are dropped from sums and ones from
+    /// products, a product with a zero factor becomes zero, powers of
+    /// powers are combined, the powers `0` and `1` are removed, and sums and
+    /// products of one expression are replaced by it.
+    ///
+    /// Nothing is expanded, so the result is not a normal form: expressions
+    /// equal as polynomials may simplify differently.
+    pub fn simplify(self) -> Self {
+        match self {
+            Expr::Constant(_) | Expr::Var(_) => self,
+            Expr::Sum(terms) => {
+                let mut constant = K::zero();
+                let mut rest = Vec::new();
+                for term in terms.into_iter().map(Self::simplify) {
+                    let inner = match term {
+                        Expr::Sum(inner) => inner,
+                        term => vec![term],
+                    };
+                    for term in inner {
+                        match term {
+                            Expr::Constant(c) => constant += c,
+                            term => rest.push(term),
+                        }
+                    }
+                }
+                if !constant.is_zero() {
+                    rest.push(Expr::Constant(constant));
+                }
+                match rest.len() {
+                    0 => Expr::Constant(K::zero()),
+                    1 => rest.pop().unwrap(),
+                    _ => Expr::Sum(rest),
+                }
+            }
+            Expr::Product(factors) => {
+                let mut constant = K::one();
+                let mut rest = Vec::new();
+                for factor in factors.into_iter().map(Self::simplify) {
+                    let inner = match factor {
+                        Expr::Product(inner) => inner,
+                        factor => vec![factor],
+                    };
+                    for factor in inner {
+                        match factor {
+                            Expr::Constant(c) => constant *= c,
+                            factor => rest.push(factor),
+                        }
+                    }
+                }
+                if constant.is_zero() {
+                    return Expr::Constant(constant);
+                }
+                if !constant.is_one() {
+                    rest.insert(0, Expr::Constant(constant));
+                }
+                match rest.len() {
+                    0 => Expr::Constant(K::one()),
+                    1 => rest.pop().unwrap(),
+                    _ => Expr::Product(rest),
+                }
+            }
+            Expr::Pow(base, e) => match (base.simplify(), e) {
+                (_, 0) => Expr::Constant(K::one()),
+                (base, 1) => base,
+                (Expr::Constant(c), e) => Expr::Constant(num::pow(c, e as usize)),
+                (Expr::Pow(base, f), e) => Expr::Pow(base, e * f),
+                (base, e) => base.pow(e),
+            },
+        }
+    }
+}
+
+impl<K> Expr<K>
+where
+    K: SemiringElement + Clone,
+{
+    /// The expansion of `self` in `ring`, or an `InvalidInput` error if it
+    /// contains a variable `x_i` which `ring` does not have.
+    pub fn to_polynomial<'a, R, V, P>(
+        &self,
+        ring: &'a PolynomialRing<'a, R, V>,
+    ) -> Result<Polynomial<'a, R, V, K, P, TermHasher>, ChidogError>
+    where
+        R: Semiring<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        let n = ring.vars.len();
+        let one = || Monomial {
+            powers: smallvec![P::zero(); n],
+        };
+        let constant = |c: K| Polynomial::from_terms(ring, [(one(), c)]);
+        Ok(match self {
+            Expr::Constant(c) => constant(c.clone())?,
+            Expr::Var(i) => {
+                if *i >= n {
+                    return Err(ChidogError::InvalidInput(format!(
+                        "variable {i} in a ring of {n}"
+                    )));
+                }
+                let mut m = one();
+                m.powers[*i] = P::one();
+                Polynomial::from_terms(ring, [(m, K::one())])?
+            }
+            Expr::Sum(terms) => {
+                let mut sum = constant(K::zero())?;
+                for term in terms {
+                    sum += term.to_polynomial(ring)?;
+                }
+                sum
+            }
+            Expr::Product(factors) => {
+                let mut product = constant(K::one())?;
+                for factor in factors {
+                    product *= factor.to_polynomial(ring)?;
+                }
+                product
+            }
+            Expr::Pow(_, 0) => constant(K::one())?,
+            Expr::Pow(base, e) => num::pow(base.to_polynomial(ring)?, *e as usize),
+        })
+    }
+}
+
+/// The expression of the sum of the nonzero `terms` in the Horner form of
+/// [`horner_step`], or `None` if there are none.
+fn horner<K: SemiringElement + PartialEq>(terms: Vec<(Vec<usize>, K)>) -> Option<Expr<K>> {
+    let (var, power, rest, cofactor) = match horner_step(terms)? {
+        HornerStep::Constant(c) => return Some(Expr::Constant(c)),
+        HornerStep::Split {
+            var,
+            power,
+            rest,
+            cofactor,
+        } => (var, power, rest, cofactor),
+    };
+    let x = match power {
+        1 => Expr::Var(var),
+        _ => Expr::Var(var).pow(power as u32),
+    };
+    let product = match horner(cofactor)? {
+        Expr::Constant(c) if c.is_one() => x,
+        Expr::Constant(c) => Expr::Product(vec![Expr::Constant(c), x]),
+        Expr::Product(mut factors) => {
+            factors.insert(0, x);
+            Expr::Product(factors)
+        }
+        h => Expr::Product(vec![x, h]),
+    };
+    Some(match horner(rest) {
+        Some(Expr::Sum(mut terms)) => {
+            terms.push(product);
+            Expr::Sum(terms)
+        }
+        Some(g) => Expr::Sum(vec![g, product]),
+        None => product,
+    })
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: SemiringElement + Clone + PartialEq,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// `self` as an expression in the multivariate Horner form of
+    /// [`Polynomial::compile_evaluator`].
+    pub fn to_expr(&self) -> Expr<K> {
+        horner(exponent_terms(self)).unwrap_or(Expr::Sum(Vec::new()))
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use num::{One, Zero};
+
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// A variable `my_ring: ExteriorAlgebra<R, V>` represents the exterior
+/// (Grassmann) algebra over a base ring `R` on the variables `vars`. Its
+/// elements are of type `ExteriorForm<'_, R, V, K>`, with coefficients valued
+/// in `K`.
+///
+/// The variables anticommute, `x_i∧x_j = -x_j∧x_i`, and in particular square
+/// to zero. The algebra is graded by degree, and as a module it is free on
+/// the products `x_I = x_{i_1}∧...∧x_{i_k}` over increasing index sequences
+/// `I`. Since it is not commutative, it sets [`Semiring::COMMUTATIVE`] to
+/// `false`.
+pub struct ExteriorAlgebra<'a, R, V> {
+    pub vars: Vec<V>,
+    pub base: &'a R,
+}
+
+/// A basis element `x_{i_1}∧...∧x_{i_k}` of an exterior algebra, given by its
+/// strictly increasing indices. The empty blade is the unit.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Blade {
+    pub indices: Vec<usize>,
+}
+
+/// The empty blade, under which a detached constant is listed.
+static UNIT: Blade = Blade {
+    indices: Vec::new(),
+};
+
+impl Blade {
+    pub fn grade(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The blade `self∧rhs` together with whether its sign is negative, or
+    /// `None` if the blades share an index, so that the product vanishes.
+    ///
+    /// The sign is the parity of the number of pairs `(i, j)` with `i` in
+    /// `self`, `j` in `rhs`, and `i > j`, which is the number of
+    /// transpositions needed to sort the concatenation.
+    pub fn wedge(&self, rhs: &Blade) -> Option<(Blade, bool)> {
+        let mut indices = Vec::with_capacity(self.grade() + rhs.grade());
+        let mut negative = false;
+        let (mut a, mut b) = (0, 0);
+        while a < self.indices.len() && b < rhs.indices.len() {
+            let (i, j) = (self.indices[a], rhs.indices[b]);
+            if i == j {
+                return None;
+            }
+            if i < j {
+                indices.push(i);
+                a += 1;
+            } else {
+                // `j` moves past the remaining indices of `self`.
+                negative ^= (self.indices.len() - a) % 2 == 1;
+                indices.push(j);
+                b += 1;
+            }
+        }
+        indices.extend_from_slice(&self.indices[a..]);
+        indices.extend_from_slice(&rhs.indices[b..]);
+        Some((Blade { indices }, negative))
+    }
+}
+
+/// An element of an exterior algebra, as a hash map associating to each
+/// blade a nonzero coefficient.
+///
+/// Since `Zero::zero` and `One::one` are called without access to an
+/// algebra, constants in `K` may also be stored detached from any algebra. A
+/// detached constant `c` is attached as `c` times the empty blade to the
+/// algebra of the other operand when combined with a form.
+pub struct ExteriorForm<'a, R, V, K> {
+    repr: Repr<'a, R, V, K>,
+}
+
+enum Repr<'a, R, V, K> {
+    Constant(K),
+    Terms {
+        elem_of: &'a ExteriorAlgebra<'a, R, V>,
+        terms: HashMap<Blade, K>,
+    },
+}
+
+impl<R, V, K: Clone> Clone for ExteriorForm<'_, R, V, K> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Constant(c) => Repr::Constant(c.clone()),
+            Repr::Terms { elem_of, terms } => Repr::Terms {
+                elem_of,
+                terms: terms.clone(),
+            },
+        };
+        Self { repr }
+    }
+}
+
+impl<'a, R, V> ExteriorAlgebra<'a, R, V> {
+    pub fn new(vars: Vec<V>, base: &'a R) -> Self {
+        Self { vars, base }
+    }
+
+    pub fn zero<K>(&'a self) -> ExteriorForm<'a, R, V, K> {
+        self.with_terms(HashMap::new())
+    }
+
+    fn with_terms<K>(&'a self, terms: HashMap<Blade, K>) -> ExteriorForm<'a, R, V, K> {
+        ExteriorForm {
+            repr: Repr::Terms {
+                elem_of: self,
+                terms,
+            },
+        }
+    }
+
+    /// The product `c*x_{i_1}∧...∧x_{i_k}` for indices in any order, which is
+    /// zero if an index repeats.
+    pub fn term<K>(&'a self, indices: &[usize], c: K) -> ExteriorForm<'a, R, V, K>
+    where
+        R: Ring<K>,
+        K: RingElement + Clone,
+    {
+        let mut form = self.constant(c);
+        for &i in indices {
+            form *= self.var(i);
+        }
+        form
+    }
+
+    pub fn constant<K>(&'a self, c: K) -> ExteriorForm<'a, R, V, K>
+    where
+        R: Ring<K>,
+        K: RingElement,
+    {
+        let mut terms = HashMap::new();
+        if !self.base.is_zero(&c) {
+            terms.insert(Blade { indices: vec![] }, c);
+        }
+        self.with_terms(terms)
+    }
+
+    pub fn one<K>(&'a self) -> ExteriorForm<'a, R, V, K>
+    where
+        R: Ring<K>,
+        K: RingElement,
+    {
+        self.constant(K::one())
+    }
+
+    /// The `i`-th variable.
+    pub fn var<K>(&'a self, i: usize) -> ExteriorForm<'a, R, V, K>
+    where
+        R: Ring<K>,
+        K: RingElement,
+    {
+        assert!(i < self.vars.len(), "variable index out of range");
+        self.with_terms(HashMap::from([(Blade { indices: vec![i] }, K::one())]))
+    }
+
+    /// The form `∑ coeffs[i]*x_i` of degree one.
+    pub fn linear_form<K>(&'a self, coeffs: &[K]) -> ExteriorForm<'a, R, V, K>
+    where
+        R: Ring<K>,
+        K: RingElement + Clone,
+    {
+        let mut form = self.zero();
+        for (i, c) in coeffs.iter().enumerate() {
+            form += self.var(i) * self.constant(c.clone());
+        }
+        form
+    }
+}
+
+/// Adds `c*b` to `terms`, removing the term if it cancels in `base`.
+fn add_term<R, K>(base: &R, terms: &mut HashMap<Blade, K>, b: Blade, c: K)
+where
+    R: Ring<K>,
+    K: RingElement,
+{
+    match terms.entry(b) {
+        Entry::Occupied(mut entry) => {
+            if base.add_into(entry.get_mut(), c) {
+                entry.remove();
+            }
+        }
+        Entry::Vacant(entry) => {
+            if !base.is_zero(&c) {
+                entry.insert(c);
+            }
+        }
+    }
+}
+
+impl<'a, R, V, K> ExteriorForm<'a, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    /// The algebra of `self` and `other`, unless both are detached
+    /// constants.
+    fn algebra_of(&self, other: &Self) -> Option<&'a ExteriorAlgebra<'a, R, V>> {
+        match (&self.repr, &other.repr) {
+            (Repr::Terms { elem_of, .. }, _) | (_, Repr::Terms { elem_of, .. }) => Some(elem_of),
+            _ => None,
+        }
+    }
+
+    /// The terms of `self` in `algebra`.
+    fn into_terms(self, algebra: &'a ExteriorAlgebra<'a, R, V>) -> HashMap<Blade, K> {
+        match self.repr {
+            Repr::Constant(c) => algebra.constant(c).into_terms(algebra),
+            Repr::Terms { terms, .. } => terms,
+        }
+    }
+
+    /// The terms of `self`, as pairs of a blade and its nonzero coefficient.
+    pub fn terms(&self) -> impl Iterator<Item = (&Blade, &K)> {
+        let (constant, terms) = match &self.repr {
+            Repr::Constant(c) => (Some(c).filter(|c| !c.is_zero()), None),
+            Repr::Terms { terms, .. } => (None, Some(terms)),
+        };
+        let constant = constant.map(|c| (&UNIT, c));
+        constant.into_iter().chain(terms.into_iter().flatten())
+    }
+
+    /// The coefficient of the blade `b`.
+    pub fn coeff(&self, b: &Blade) -> K {
+        match &self.repr {
+            Repr::Constant(c) if b.indices.is_empty() => c.clone(),
+            Repr::Constant(_) => K::zero(),
+            Repr::Terms { terms, .. } => terms.get(b).cloned().unwrap_or_else(K::zero),
+        }
+    }
+
+    /// The homogeneous component of `self` of grade `k`.
+    pub fn grade_part(&self, k: usize) -> Self {
+        match &self.repr {
+            Repr::Constant(_) if k == 0 => self.clone(),
+            Repr::Constant(_) => Self::zero(),
+            Repr::Terms { elem_of, terms } => elem_of.with_terms(
+                terms
+                    .iter()
+                    .filter(|(b, _)| b.grade() == k)
+                    .map(|(b, c)| (b.clone(), c.clone()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The grade of `self` if it is nonzero and homogeneous.
+    pub fn grade(&self) -> Option<usize> {
+        let mut grades = self.terms().map(|(b, _)| b.grade());
+        let k = grades.next()?;
+        grades.all(|j| j == k).then_some(k)
+    }
+
+    /// The coefficient of `x_1∧...∧x_n`, the blade of top grade. For linear
+    /// forms `f_1, ..., f_n`, the top coefficient of `f_1∧...∧f_n` is the
+    /// determinant of their coefficient matrix.
+    ///
+    /// A detached constant has no algebra, so its top coefficient is taken
+    /// to be zero.
+    pub fn top_coeff(&self) -> K {
+        match &self.repr {
+            Repr::Constant(_) => K::zero(),
+            Repr::Terms { elem_of, .. } => {
+                let indices = (0..elem_of.vars.len()).collect();
+                self.coeff(&Blade { indices })
+            }
+        }
+    }
+}
+
+impl<R, V, K> Add for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a + b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (b, c) in rhs.into_terms(algebra) {
+            add_term(algebra.base, &mut terms, b, c);
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> AddAssign for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<R, V, K> Sub for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a - b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (b, c) in rhs.into_terms(algebra) {
+            add_term(algebra.base, &mut terms, b, K::zero() - c);
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> SubAssign for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+/// The wedge product.
+impl<R, V, K> Mul for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a * b),
+            };
+        };
+        let (lhs, rhs) = (self.into_terms(algebra), rhs.into_terms(algebra));
+        let mut terms = HashMap::new();
+        for (b1, c1) in lhs.iter() {
+            for (b2, c2) in rhs.iter() {
+                let Some((b, negative)) = b1.wedge(b2) else {
+                    continue;
+                };
+                let c = c1.clone() * c2.clone();
+                add_term(
+                    algebra.base,
+                    &mut terms,
+                    b,
+                    if negative { K::zero() - c } else { c },
+                );
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> MulAssign for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::one()) * rhs;
+    }
+}
+
+impl<R, V, K> Zero for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn zero() -> Self {
+        Self {
+            repr: Repr::Constant(K::zero()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match &self.repr {
+            Repr::Constant(c) => c.is_zero(),
+            Repr::Terms { terms, .. } => terms.is_empty(),
+        }
+    }
+}
+
+impl<R, V, K> One for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn one() -> Self {
+        Self {
+            repr: Repr::Constant(K::one()),
+        }
+    }
+}
+
+impl<R, V, K> Semiring<ExteriorForm<'_, R, V, K>> for ExteriorAlgebra<'_, R, V>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    const COMMUTATIVE: bool = false;
+}
+
+impl<R, V, K> Ring<ExteriorForm<'_, R, V, K>> for ExteriorAlgebra<'_, R, V>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+impl<R, V, K> SemiringElement for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+impl<R, V, K> RingElement for ExteriorForm<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+/// Displays the terms in order of increasing grade, as in
+/// `1+2*x+y-x∧y`, and a detached constant as itself.
+impl<R, V, K> Display for ExteriorForm<'_, R, V, K>
+where
+    V: Display,
+    K: Display + One + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (elem_of, terms) = match &self.repr {
+            Repr::Constant(c) => return Display::fmt(c, f),
+            Repr::Terms { elem_of, terms } => (elem_of, terms),
+        };
+        let mut terms: Vec<_> = terms.iter().collect();
+        terms.sort_by(|(b1, _), (b2, _)| (b1.grade(), b1).cmp(&(b2.grade(), b2)));
+        if terms.is_empty() {
+            write!(f, "0")?;
+        }
+        for (i, (b, c)) in terms.into_iter().enumerate() {
+            if b.indices.is_empty() || !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                if b.indices.is_empty() {
+                    continue;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            for (j, &k) in b.indices.iter().enumerate() {
+                if j > 0 {
+                    write!(f, "∧")?;
+                }
+                write!(f, "{}", elem_of.vars[k])?;
+            }
+        }
+        Ok(())
+    }
+}
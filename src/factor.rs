@@ -0,0 +1,453 @@
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::lifting::hensel_lift;
+use crate::matrix::Matrix;
+use crate::multimodular::{Modular, PrimeField};
+use crate::univariate::DenseUnivariate;
+use crate::univariate::fft::is_prime_u64;
+
+/// A factorization over ℤ, as a constant and the irreducible factors with
+/// their multiplicities.
+type Factorization<C> = (C, Vec<(DenseUnivariate<BigInt>, usize)>);
+
+/// The number of suitable primes among which [`factor_squarefree`] picks the
+/// one giving the fewest modular factors.
+const PRIMES_TRIED: usize = 5;
+
+/// `base^e` modulo `f`, by repeated squaring.
+fn pow_mod(
+    base: &DenseUnivariate<Modular>,
+    mut e: u64,
+    f: &DenseUnivariate<Modular>,
+) -> DenseUnivariate<Modular> {
+    let (mut power, mut base) = (DenseUnivariate::one(), base.div_rem(f).unwrap().1);
+    while e > 0 {
+        if e & 1 == 1 {
+            power = (power * base.clone()).div_rem(f).unwrap().1;
+        }
+        base = (base.clone() * base).div_rem(f).unwrap().1;
+        e >>= 1;
+    }
+    power
+}
+
+/// A basis of the Berlekamp subalgebra of the squarefree monic `f` over
+/// `field`, the polynomials `v` of degree below that of `f` with `v^p ≡ v`
+/// modulo `f`, whose dimension is the number of irreducible factors of `f`.
+fn berlekamp_basis(
+    f: &DenseUnivariate<Modular>,
+    field: &PrimeField,
+) -> Vec<DenseUnivariate<Modular>> {
+    let n = f.degree().unwrap();
+    // `v^p = ∑ v_i*x^(ip)` since the coefficients lie in the prime field, so
+    // `v` is in the subalgebra when `(Q^T - I)*v = 0`, where the row `i` of
+    // `Q` holds the coefficients of `x^(ip)` modulo `f`.
+    let frobenius = pow_mod(&DenseUnivariate::x(), field.p(), f);
+    let mut matrix = Matrix::zero(n, n);
+    let mut power = DenseUnivariate::constant(field.element(1));
+    for i in 0..n {
+        for j in 0..n {
+            matrix[(j, i)] = power.coeff(j);
+        }
+        matrix[(i, i)] -= field.element(1);
+        power = (power * frobenius.clone()).div_rem(f).unwrap().1;
+    }
+    matrix
+        .nullspace()
+        .into_iter()
+        .map(DenseUnivariate::new)
+        .collect()
+}
+
+/// The monic irreducible factors over `field` of the squarefree monic `f`,
+/// from the basis of its Berlekamp subalgebra: each `v` in it is congruent
+/// to a constant `s` modulo each irreducible factor, and the GCDs of the
+/// factors found so far with `v - s` for all `s` in the field separate any
+/// two irreducible factors on which `v` differs.
+fn berlekamp_split(
+    f: &DenseUnivariate<Modular>,
+    basis: &[DenseUnivariate<Modular>],
+    field: &PrimeField,
+) -> Vec<DenseUnivariate<Modular>> {
+    let mut factors = vec![f.clone()];
+    for v in basis.iter().filter(|v| !v.is_constant()) {
+        if factors.len() == basis.len() {
+            break;
+        }
+        let mut split = Vec::new();
+        for mut u in factors {
+            for s in 0..field.p() {
+                if u.degree() == Some(1) {
+                    break;
+                }
+                let g = u.gcd(&(v.clone() - DenseUnivariate::constant(field.element(s as i64))));
+                if g.degree().is_some_and(|d| d > 0 && Some(d) < u.degree()) {
+                    u = u.div_rem(&g).unwrap().0.monic();
+                    split.push(g);
+                }
+            }
+            split.push(u);
+        }
+        factors = split;
+    }
+    factors
+}
+
+/// `f` with each coefficient reduced into `(-m/2, m/2]`.
+fn symmetric(f: &DenseUnivariate<BigInt>, m: &BigInt) -> DenseUnivariate<BigInt> {
+    let half = m / 2;
+    f.map(|c| {
+        let c = c.mod_floor(m);
+        if c > half { c - m } else { c }
+    })
+}
+
+/// The primitive part of the nonzero `f` with positive leading coefficient.
+fn primitive(f: &DenseUnivariate<BigInt>) -> DenseUnivariate<BigInt> {
+    let mut content = f.coeffs().iter().fold(BigInt::zero(), |a, c| a.gcd(c));
+    if f.leading_coeff().unwrap().is_negative() {
+        content = -content;
+    }
+    f.map(|c| c / &content)
+}
+
+/// The quotient of `f` by the nonzero `g` over ℤ, or `None` if `g` does not
+/// divide `f`.
+fn divide_exact(
+    f: &DenseUnivariate<BigInt>,
+    g: &DenseUnivariate<BigInt>,
+) -> Option<DenseUnivariate<BigInt>> {
+    let n = g.degree().unwrap();
+    let lc = g.leading_coeff().unwrap();
+    let mut r = f.coeffs().to_vec();
+    if r.len() <= n {
+        return r.iter().all(Zero::is_zero).then(DenseUnivariate::zero);
+    }
+    let mut q = vec![BigInt::zero(); r.len() - n];
+    for i in (0..q.len()).rev() {
+        let (c, rem) = r[i + n].div_rem(lc);
+        if !rem.is_zero() {
+            return None;
+        }
+        for (j, d) in g.coeffs().iter().enumerate() {
+            r[i + j] -= &c * d;
+        }
+        q[i] = c;
+    }
+    r[..n]
+        .iter()
+        .all(Zero::is_zero)
+        .then(|| DenseUnivariate::new(q))
+}
+
+/// Advances `subset`, increasing indices below `n`, to the next subset of
+/// the same size in lexicographic order, returning whether there is one.
+fn next_subset(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    let Some(i) = (0..k).rev().find(|&i| subset[i] < n - k + i) else {
+        return false;
+    };
+    subset[i] += 1;
+    for j in i + 1..k {
+        subset[j] = subset[j - 1] + 1;
+    }
+    true
+}
+
+/// A prime `p` not dividing the leading coefficient of `f` modulo which `f`
+/// stays squarefree, with the image of `f` made monic and the basis of its
+/// Berlekamp subalgebra: of the first [`PRIMES_TRIED`] such primes, the one
+/// with the fewest factors.
+fn choose_prime(
+    f: &DenseUnivariate<BigInt>,
+) -> (
+    PrimeField,
+    DenseUnivariate<Modular>,
+    Vec<DenseUnivariate<Modular>>,
+) {
+    let mut best: Option<(PrimeField, DenseUnivariate<Modular>, Vec<_>)> = None;
+    let primes = (3..)
+        .step_by(2)
+        .filter(|&p| is_prime_u64(p))
+        .map(|p| PrimeField::new(p).unwrap());
+    let derivative = f.derivative();
+    let mut tried = 0;
+    for field in primes {
+        let image = f.map(|c| field.from_integer(c));
+        let image_derivative = derivative.map(|c| field.from_integer(c));
+        if image.degree() != f.degree() || image.gcd(&image_derivative).degree() != Some(0) {
+            continue;
+        }
+        let image = image.monic();
+        let basis = berlekamp_basis(&image, &field);
+        if best.as_ref().is_none_or(|(_, _, b)| basis.len() < b.len()) {
+            best = Some((field, image, basis));
+        }
+        tried += 1;
+        if tried == PRIMES_TRIED || best.as_ref().is_some_and(|(_, _, b)| b.len() == 1) {
+            break;
+        }
+    }
+    best.unwrap()
+}
+
+/// The irreducible factors over ℤ of the primitive squarefree `f` of
+/// positive degree and leading coefficient, by the algorithm of Zassenhaus.
+///
+/// The monic factors of `f` modulo a prime `p` are lifted modulo a power
+/// `p^k` beyond twice the bound of Mignotte on the coefficients of a factor
+/// of `f` times its leading coefficient, and each product of a subset of
+/// them, times the leading coefficient, is tried as a factor, smallest
+/// subsets first. `control` is consulted before each subset, with the number
+/// of subsets tried, the number of lifted factors left and the number of
+/// coefficients held.
+fn factor_squarefree(
+    f: &DenseUnivariate<BigInt>,
+    control: &ComputationControl,
+) -> Result<Vec<DenseUnivariate<BigInt>>, ChidogError> {
+    let n = f.degree().unwrap();
+    if n == 1 {
+        return Ok(vec![f.clone()]);
+    }
+    let (field, image, basis) = choose_prime(f);
+    if basis.len() == 1 {
+        return Ok(vec![f.clone()]);
+    }
+    let modular = berlekamp_split(&image, &basis, &field);
+    // A factor `g` of `f` has `|g|_∞ ≤ 2^deg(g)*|f|_2`.
+    let norm = f.coeffs().iter().map(|c| c * c).sum::<BigInt>().sqrt() + 1;
+    let bound = f.leading_coeff().unwrap().abs() * (BigInt::one() << n) * norm;
+    let p = BigInt::from(field.p());
+    let (mut k, mut m) = (1, p.clone());
+    while m <= &bound * 2 {
+        k += 1;
+        m *= &p;
+    }
+    let mut lifted = hensel_lift(f, &modular, &field, k).expect("a valid modular factorization");
+    let mut f = f.clone();
+    let mut factors = Vec::new();
+    let mut size = 1;
+    let mut tried = 0;
+    while 2 * size <= lifted.len() {
+        let mut subset: Vec<usize> = (0..size).collect();
+        let found = loop {
+            control.check(&Progress {
+                algorithm: "zassenhaus",
+                steps: tried,
+                remaining: Some(lifted.len()),
+                terms: lifted
+                    .iter()
+                    .chain(&factors)
+                    .chain([&f])
+                    .map(|g| g.coeffs().len())
+                    .sum(),
+            })?;
+            tried += 1;
+            let product = subset.iter().fold(
+                DenseUnivariate::constant(f.leading_coeff().unwrap().clone()),
+                |a, &i| a * lifted[i].clone(),
+            );
+            let g = primitive(&symmetric(&product, &m));
+            if let Some(q) = divide_exact(&f, &g) {
+                break Some((g, q));
+            }
+            if !next_subset(&mut subset, lifted.len()) {
+                break None;
+            }
+        };
+        match found {
+            Some((g, q)) => {
+                factors.push(g);
+                f = q;
+                for &i in subset.iter().rev() {
+                    lifted.remove(i);
+                }
+            }
+            None => size += 1,
+        }
+    }
+    factors.push(f);
+    Ok(factors)
+}
+
+/// The factorization of `f` over ℤ of [`DenseUnivariate::factor`], without
+/// delegating to FLINT: each squarefree part of
+/// [`DenseUnivariate::squarefree_controlled`] is factored by
+/// [`factor_squarefree`], both consulting `control`.
+pub(crate) fn factor_native(
+    f: &DenseUnivariate<BigInt>,
+    control: &ComputationControl,
+) -> Result<Factorization<BigInt>, ChidogError> {
+    let Some(lc) = f.leading_coeff() else {
+        return Ok((BigInt::zero(), Vec::new()));
+    };
+    let mut factors = Vec::new();
+    let rational = f.map(|c| BigRational::from_integer(c.clone()));
+    for (i, a) in rational.squarefree_controlled(control)?.iter().enumerate() {
+        if a.is_constant() {
+            continue;
+        }
+        let denominator = a
+            .coeffs()
+            .iter()
+            .fold(BigInt::one(), |d, c| d.lcm(c.denom()));
+        let a = primitive(&a.map(|c| (c * &denominator).to_integer()));
+        factors.extend(
+            factor_squarefree(&a, control)?
+                .into_iter()
+                .map(|g| (g, i + 1)),
+        );
+    }
+    sort_factors(&mut factors);
+    let leading = factors.iter().fold(BigInt::one(), |a, (g, e)| {
+        a * num::pow(g.leading_coeff().unwrap().clone(), *e)
+    });
+    Ok((lc / leading, factors))
+}
+
+/// Sorts `factors` by degree and then by coefficients.
+pub(crate) fn sort_factors(factors: &mut [(DenseUnivariate<BigInt>, usize)]) {
+    factors.sort_by(|(f, _), (g, _)| (f.degree(), f.coeffs()).cmp(&(g.degree(), g.coeffs())));
+}
+
+impl DenseUnivariate<BigInt> {
+    /// The factorization of `self` into irreducible polynomials over ℤ, as
+    /// its content `c`, with the sign of its leading coefficient, and the
+    /// distinct primitive irreducible factors `g_i` of positive degree, with
+    /// positive leading coefficients, with their multiplicities `e_i`, such
+    /// that `self = c * ∏ g_i^e_i`. The factors are sorted by degree and then
+    /// by coefficients. The zero polynomial has `c = 0` and no factors.
+    ///
+    /// Each squarefree part of [`DenseUnivariate::squarefree`] is factored
+    /// by the algorithm of Zassenhaus: Berlekamp's algorithm modulo a small
+    /// prime, Hensel lifting by [`hensel_lift`] and recombination of the
+    /// lifted factors. The recombination is exponential in the worst case,
+    /// for polynomials with many more factors modulo every prime than over
+    /// ℤ, such as the Swinnerton-Dyer polynomials. With the `flint` feature,
+    /// the factorization is delegated to FLINT instead.
+    pub fn factor(&self) -> (BigInt, Vec<(Self, usize)>) {
+        self.factor_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The factorization of [`DenseUnivariate::factor`], consulting `control`
+    /// before each squarefree part and each subset tried in the
+    /// recombination, and failing as it directs. With the `flint` feature,
+    /// `control` is only consulted before FLINT is called.
+    pub fn factor_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<(BigInt, Vec<(Self, usize)>), ChidogError> {
+        #[cfg(feature = "flint")]
+        if crate::flint::is_supported() {
+            control.check_interrupted()?;
+            let (c, mut factors) = self.flint_factor();
+            sort_factors(&mut factors);
+            return Ok((c, factors));
+        }
+        factor_native(self, control)
+    }
+}
+
+impl DenseUnivariate<BigRational> {
+    /// The factorization of `self` into irreducible polynomials over ℚ, as a
+    /// constant `c` and the factorization over ℤ of `self` times the common
+    /// denominator `d` of its coefficients, so that `c` is the content of
+    /// that over `d`. The zero polynomial has `c = 0` and no factors.
+    pub fn factor(&self) -> (BigRational, Vec<(DenseUnivariate<BigInt>, usize)>) {
+        self.factor_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The factorization of [`DenseUnivariate::factor`] over ℚ, consulting
+    /// `control` as the factorization over ℤ does.
+    pub fn factor_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<Factorization<BigRational>, ChidogError> {
+        let denominator = self
+            .coeffs()
+            .iter()
+            .fold(BigInt::one(), |d, c| d.lcm(c.denom()));
+        let (c, factors) = self
+            .map(|c| (c * &denominator).to_integer())
+            .factor_controlled(control)?;
+        Ok((BigRational::new(c, denominator), factors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(coeffs: &[i64]) -> DenseUnivariate<BigInt> {
+        DenseUnivariate::new(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+    }
+
+    fn expand(c: BigInt, factors: &[(DenseUnivariate<BigInt>, usize)]) -> DenseUnivariate<BigInt> {
+        factors
+            .iter()
+            .fold(DenseUnivariate::constant(c), |a, (g, e)| {
+                a * num::pow(g.clone(), *e)
+            })
+    }
+
+    #[test]
+    fn factors_over_the_integers() {
+        let (c, factors) = poly(&[-2, 0, 0, 0, 2]).factor();
+        assert_eq!(c, BigInt::from(2));
+        assert_eq!(
+            factors,
+            vec![
+                (poly(&[-1, 1]), 1),
+                (poly(&[1, 1]), 1),
+                (poly(&[1, 0, 1]), 1)
+            ]
+        );
+        let f = poly(&[1, 0, 1])
+            * poly(&[-2, 0, 1])
+            * num::pow(poly(&[1, 3]), 2)
+            * poly(&[-6, 0, 0, 1]);
+        let (c, factors) = f.factor();
+        assert_eq!(factors.len(), 4);
+        assert_eq!(factors[0], (poly(&[1, 3]), 2));
+        assert_eq!(expand(c, &factors), f);
+    }
+
+    #[test]
+    fn keeps_irreducible_polynomials_with_modular_factors() {
+        // Both split into linear or quadratic factors modulo every prime.
+        for f in [poly(&[1, 0, 0, 0, 1]), poly(&[1, 0, -10, 0, 1])] {
+            assert_eq!(f.factor(), (BigInt::one(), vec![(f.clone(), 1)]));
+        }
+    }
+
+    #[test]
+    fn controlled_factorization_reports_recombination() {
+        use std::sync::{Arc, Mutex};
+
+        // Irreducible, but with at least two factors modulo every prime.
+        let f = poly(&[1, 0, 0, 0, 1]);
+        let algorithms = Arc::new(Mutex::new(Vec::new()));
+        let seen = algorithms.clone();
+        let control = ComputationControl::new()
+            .with_progress(move |p| seen.lock().unwrap().push(p.algorithm));
+        assert_eq!(factor_native(&f, &control).unwrap(), f.factor());
+        assert!(algorithms.lock().unwrap().contains(&"zassenhaus"));
+        control.cancel();
+        let result = f.factor_controlled(&control);
+        assert!(matches!(result, Err(ChidogError::Cancelled)));
+    }
+
+    #[test]
+    fn factors_over_the_rationals() {
+        let f = poly(&[-1, 0, 4]).map(|c| BigRational::new(c.clone(), BigInt::from(8)));
+        let (c, factors) = f.factor();
+        assert_eq!(c, BigRational::new(BigInt::one(), BigInt::from(8)));
+        assert_eq!(factors, vec![(poly(&[-1, 2]), 1), (poly(&[1, 2]), 1)]);
+        let zero = DenseUnivariate::<BigRational>::new(vec![]);
+        assert_eq!(zero.factor(), (BigRational::zero(), vec![]));
+    }
+}
@@ -0,0 +1,326 @@
+use std::array;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::iter::zip;
+use std::ops::{Add, Mul, Sub};
+
+use num::{One, PrimInt, Unsigned};
+
+use crate::error::ChidogError;
+use crate::order::MonomialOrder;
+use crate::{
+    Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring, SemiringElement, TermHasher,
+};
+
+/// A monomial in exactly `N` variables, with its exponents in an array.
+///
+/// When the number of variables is known at compile time, the exponents
+/// need no stored length: products, divisibility and comparisons run over
+/// arrays of a fixed size, which the compiler unrolls without bounds checks,
+/// and the monomial is a plain `Copy` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedMonomial<P, const N: usize> {
+    pub powers: [P; N],
+}
+
+impl<P: PrimInt, const N: usize> FixedMonomial<P, N> {
+    /// The monomial `1`.
+    pub fn one() -> Self {
+        Self {
+            powers: [P::zero(); N],
+        }
+    }
+
+    /// The total degree.
+    pub fn degree(&self) -> P {
+        self.powers.iter().fold(P::zero(), |d, p| d + *p)
+    }
+
+    /// Whether `self` divides `other`.
+    pub fn divides(&self, other: &Self) -> bool {
+        zip(&self.powers, &other.powers).all(|(p, q)| p <= q)
+    }
+
+    /// The quotient `self/other`, or `None` if `other` does not divide
+    /// `self`.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.divides(self).then(|| Self {
+            powers: array::from_fn(|i| self.powers[i] - other.powers[i]),
+        })
+    }
+
+    /// The least common multiple, with the larger of each pair of exponents.
+    pub fn lcm(&self, other: &Self) -> Self {
+        Self {
+            powers: array::from_fn(|i| self.powers[i].max(other.powers[i])),
+        }
+    }
+
+    /// The greatest common divisor, with the smaller of each pair of
+    /// exponents.
+    pub fn gcd(&self, other: &Self) -> Self {
+        Self {
+            powers: array::from_fn(|i| self.powers[i].min(other.powers[i])),
+        }
+    }
+
+    /// `self` as a [`Monomial`].
+    pub fn to_monomial(&self) -> Monomial<P> {
+        Monomial {
+            powers: self.powers.iter().copied().collect(),
+        }
+    }
+}
+
+impl<P: PrimInt, const N: usize> Mul for FixedMonomial<P, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            powers: array::from_fn(|i| self.powers[i] + rhs.powers[i]),
+        }
+    }
+}
+
+/// Fails with an `InvalidInput` error unless `m` has exactly `N` exponents.
+impl<P: PrimInt, const N: usize> TryFrom<&Monomial<P>> for FixedMonomial<P, N> {
+    type Error = ChidogError;
+
+    fn try_from(m: &Monomial<P>) -> Result<Self, Self::Error> {
+        let powers = m.powers.as_slice().try_into().map_err(|_| {
+            ChidogError::InvalidInput(format!(
+                "monomial in {} variables is not in {N}",
+                m.powers.len()
+            ))
+        })?;
+        Ok(Self { powers })
+    }
+}
+
+/// A polynomial whose ring is known at compile time to have `N` variables,
+/// stored like a [`Polynomial`] but keyed by [`FixedMonomial`]s.
+///
+/// Its constructors check `N` against the variables of the ring once, after
+/// which no operation looks at the number of variables again.
+pub struct FixedPolynomial<'a, R, V, K, P, const N: usize, S = TermHasher> {
+    pub elem_of: &'a PolynomialRing<'a, R, V>,
+    pub terms: HashMap<FixedMonomial<P, N>, K, S>,
+}
+
+impl<R, V, K, P, const N: usize, S> Clone for FixedPolynomial<'_, R, V, K, P, N, S>
+where
+    K: Clone,
+    P: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+/// Fails with an `InvalidInput` error unless `ring` has exactly `n`
+/// variables.
+fn check_arity<R, V>(ring: &PolynomialRing<'_, R, V>, n: usize) -> Result<(), ChidogError> {
+    if ring.vars.len() != n {
+        return Err(ChidogError::InvalidInput(format!(
+            "ring in {} variables is not in {n}",
+            ring.vars.len()
+        )));
+    }
+    Ok(())
+}
+
+impl<'a, R, V, K, P, const N: usize, S> FixedPolynomial<'a, R, V, K, P, N, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Clone + Default,
+{
+    /// The zero polynomial of `ring`, or an `InvalidInput` error if `ring`
+    /// does not have `N` variables.
+    pub fn zero(ring: &'a PolynomialRing<'a, R, V>) -> Result<Self, ChidogError> {
+        check_arity(ring, N)?;
+        Ok(Self {
+            elem_of: ring,
+            terms: HashMap::default(),
+        })
+    }
+
+    /// The polynomial `f`, or an `InvalidInput` error if its ring does not
+    /// have `N` variables.
+    pub fn from_polynomial(f: &Polynomial<'a, R, V, K, P, S>) -> Result<Self, ChidogError> {
+        check_arity(f.elem_of, N)?;
+        let terms = f
+            .terms
+            .iter()
+            .map(|(m, c)| Ok((FixedMonomial::try_from(m)?, c.clone())))
+            .collect::<Result<_, ChidogError>>()?;
+        Ok(Self {
+            elem_of: f.elem_of,
+            terms,
+        })
+    }
+
+    /// `self` as a [`Polynomial`].
+    pub fn to_polynomial(&self) -> Polynomial<'a, R, V, K, P, S> {
+        Polynomial {
+            elem_of: self.elem_of,
+            terms: self
+                .terms
+                .iter()
+                .map(|(m, c)| (m.to_monomial(), c.clone()))
+                .collect(),
+        }
+    }
+
+    /// The leading term in `order`, or `None` for zero.
+    pub fn leading_term<O: MonomialOrder>(&self, order: &O) -> Option<(&FixedMonomial<P, N>, &K)> {
+        self.terms
+            .iter()
+            .max_by(|(a, _), (b, _)| order.cmp_fixed(*a, *b))
+    }
+
+    /// The value of `self` at `point`.
+    pub fn eval(&self, point: &[K; N]) -> K {
+        let mut value = K::zero();
+        for (m, c) in &self.terms {
+            let mut t = c.clone();
+            for (x, p) in zip(point, &m.powers) {
+                t *= num::pow(x.clone(), p.to_usize().unwrap());
+            }
+            value += t;
+        }
+        value
+    }
+}
+
+impl<R, V, K, P, const N: usize, S> Add for FixedPolynomial<'_, R, V, K, P, N, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut terms = self.terms;
+        for (m, c) in rhs.terms {
+            match terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if self.elem_of.base.add_into(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(c);
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, const N: usize, S> Sub for FixedPolynomial<'_, R, V, K, P, N, S>
+where
+    R: Ring<K>,
+    K: RingElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut terms = self.terms;
+        for (m, c) in rhs.terms {
+            match terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if self.elem_of.base.sub_from(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c);
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+/// Multiplies term by term, accumulating into the map of the product.
+impl<R, V, K, P, const N: usize, S> Mul for FixedPolynomial<'_, R, V, K, P, N, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let base = self.elem_of.base;
+        let mut terms =
+            HashMap::with_capacity_and_hasher(self.terms.len() + rhs.terms.len(), S::default());
+        for (m, c) in &self.terms {
+            for (n, d) in &rhs.terms {
+                let k = c.clone() * d.clone();
+                match terms.entry(*m * *n) {
+                    Entry::Occupied(mut entry) => {
+                        if base.add_into(entry.get_mut(), k) {
+                            entry.remove();
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        if !base.is_zero(&k) {
+                            entry.insert(k);
+                        }
+                    }
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, const N: usize, S> Display for FixedPolynomial<'_, R, V, K, P, N, S>
+where
+    K: Display + One + PartialEq,
+    P: Hash + Display + PrimInt,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (m, c)) in self.terms.iter().enumerate() {
+            if !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            self.elem_of.fmt_monomial(f, &m.to_monomial())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,574 @@
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+use std::sync::OnceLock;
+
+use num::bigint::Sign;
+use num::{BigInt, Zero};
+
+use crate::SemiringElement;
+use crate::multimodular::{Modular, PrimeField};
+use crate::univariate::DenseUnivariate;
+use crate::univariate::specialize::{downcast, upcast};
+
+/// The number of coefficients from which the arithmetic of
+/// [`DenseUnivariate`] over the integers and the prime fields is delegated
+/// to FLINT, below which converting the operands costs more than it saves.
+const THRESHOLD: usize = 32;
+
+#[allow(non_camel_case_types)]
+mod sys {
+    use std::ffi::{c_char, c_int};
+
+    /// The limb `mp_limb_t` of GMP, which is `unsigned long` except on
+    /// 64-bit Windows, where `long` has 32 bits.
+    #[cfg(not(all(windows, target_pointer_width = "64")))]
+    pub type ulong = std::ffi::c_ulong;
+    #[cfg(all(windows, target_pointer_width = "64"))]
+    pub type ulong = std::ffi::c_ulonglong;
+    /// The signed limb `mp_limb_signed_t` of GMP.
+    #[cfg(not(all(windows, target_pointer_width = "64")))]
+    pub type slong = std::ffi::c_long;
+    #[cfg(all(windows, target_pointer_width = "64"))]
+    pub type slong = std::ffi::c_longlong;
+    /// An integer, stored inline when small and as a tagged pointer to an
+    /// `mpz` otherwise.
+    pub type fmpz = slong;
+
+    #[repr(C)]
+    pub struct nmod_t {
+        pub n: ulong,
+        pub ninv: ulong,
+        pub norm: ulong,
+    }
+
+    #[repr(C)]
+    pub struct nmod_poly_struct {
+        pub coeffs: *mut ulong,
+        pub alloc: slong,
+        pub length: slong,
+        pub modulus: nmod_t,
+    }
+
+    #[repr(C)]
+    pub struct nmod_poly_factor_struct {
+        pub p: *mut nmod_poly_struct,
+        pub exp: *mut slong,
+        pub num: slong,
+        pub alloc: slong,
+    }
+
+    #[repr(C)]
+    pub struct fmpz_poly_struct {
+        pub coeffs: *mut fmpz,
+        pub alloc: slong,
+        pub length: slong,
+    }
+
+    #[repr(C)]
+    pub struct fmpz_poly_factor_struct {
+        pub c: fmpz,
+        pub p: *mut fmpz_poly_struct,
+        pub exp: *mut slong,
+        pub num: slong,
+        pub alloc: slong,
+    }
+
+    #[link(name = "flint")]
+    unsafe extern "C" {
+        /// The version of the library, such as `3.1.2`.
+        pub static flint_version: c_char;
+
+        pub fn fmpz_clear(f: *mut fmpz);
+        pub fn fmpz_sgn(f: *const fmpz) -> c_int;
+        pub fn fmpz_size(f: *const fmpz) -> slong;
+        pub fn fmpz_abs(f: *mut fmpz, g: *const fmpz);
+        pub fn fmpz_neg(f: *mut fmpz, g: *const fmpz);
+        pub fn fmpz_set_ui_array(out: *mut fmpz, input: *const ulong, n: slong);
+        pub fn fmpz_get_ui_array(out: *mut ulong, n: slong, input: *const fmpz);
+
+        pub fn nmod_poly_init(poly: *mut nmod_poly_struct, n: ulong);
+        pub fn nmod_poly_clear(poly: *mut nmod_poly_struct);
+        pub fn nmod_poly_set_coeff_ui(poly: *mut nmod_poly_struct, n: slong, c: ulong);
+        pub fn nmod_poly_mul(
+            res: *mut nmod_poly_struct,
+            a: *const nmod_poly_struct,
+            b: *const nmod_poly_struct,
+        );
+        pub fn nmod_poly_gcd(
+            g: *mut nmod_poly_struct,
+            a: *const nmod_poly_struct,
+            b: *const nmod_poly_struct,
+        );
+        pub fn nmod_poly_factor_init(fac: *mut nmod_poly_factor_struct);
+        pub fn nmod_poly_factor_clear(fac: *mut nmod_poly_factor_struct);
+        pub fn nmod_poly_factor(
+            fac: *mut nmod_poly_factor_struct,
+            poly: *const nmod_poly_struct,
+        ) -> ulong;
+
+        pub fn fmpz_poly_init(poly: *mut fmpz_poly_struct);
+        pub fn fmpz_poly_clear(poly: *mut fmpz_poly_struct);
+        pub fn fmpz_poly_set_coeff_fmpz(poly: *mut fmpz_poly_struct, n: slong, x: *const fmpz);
+        pub fn fmpz_poly_mul(
+            res: *mut fmpz_poly_struct,
+            a: *const fmpz_poly_struct,
+            b: *const fmpz_poly_struct,
+        );
+        pub fn fmpz_poly_gcd(
+            g: *mut fmpz_poly_struct,
+            a: *const fmpz_poly_struct,
+            b: *const fmpz_poly_struct,
+        );
+        pub fn fmpz_poly_factor_init(fac: *mut fmpz_poly_factor_struct);
+        pub fn fmpz_poly_factor_clear(fac: *mut fmpz_poly_factor_struct);
+        pub fn fmpz_poly_factor(fac: *mut fmpz_poly_factor_struct, poly: *const fmpz_poly_struct);
+    }
+}
+
+/// The number of 32-bit digits of a [`BigInt`] in a limb.
+const LIMB_DIGITS: usize = (sys::ulong::BITS / 32) as usize;
+
+/// The version of the linked FLINT, such as `3.1.2`.
+pub fn version() -> &'static str {
+    // SAFETY: `flint_version` is a constant NUL-terminated string.
+    let version = unsafe { CStr::from_ptr(&raw const sys::flint_version) };
+    version.to_str().unwrap_or("")
+}
+
+/// Whether the linked FLINT has major version 3, whose structures this
+/// module declares. Otherwise nothing is delegated to it, and the `flint_`
+/// methods panic.
+pub fn is_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| version().starts_with("3."))
+}
+
+/// Panics unless the linked FLINT [`is_supported`].
+fn require_supported() {
+    assert!(
+        is_supported(),
+        "FLINT 3 is required, but version {} is linked",
+        version()
+    );
+}
+
+/// Whether operations on polynomials with `len` coefficients are delegated
+/// to FLINT.
+pub(crate) fn delegates(len: usize) -> bool {
+    len >= THRESHOLD && is_supported()
+}
+
+/// The prime of `field` as a limb, or `None` if it does not fit in one, as
+/// on targets with 32-bit limbs.
+#[allow(clippy::useless_conversion)]
+fn limb(field: &PrimeField) -> Option<sys::ulong> {
+    sys::ulong::try_from(field.p()).ok()
+}
+
+/// The prime field of the residues among the coefficients of `a` and `b`,
+/// or `None` if they are all constants or the prime does not fit in a limb.
+fn common_field(a: &DenseUnivariate<Modular>, b: &DenseUnivariate<Modular>) -> Option<PrimeField> {
+    let p = a
+        .coeffs()
+        .iter()
+        .chain(b.coeffs())
+        .find_map(Modular::modulus)?;
+    let field = PrimeField::new(p).ok()?;
+    limb(&field).map(|_| field)
+}
+
+/// The product `a * b` by FLINT, or `None` unless the coefficients are
+/// integers or residues and each factor has [`THRESHOLD`] coefficients.
+pub(crate) fn mul<K: SemiringElement + Clone>(
+    a: &DenseUnivariate<K>,
+    b: &DenseUnivariate<K>,
+) -> Option<DenseUnivariate<K>> {
+    if !delegates(a.coeffs().len().min(b.coeffs().len())) {
+        return None;
+    }
+    if let (Some(a), Some(b)) = (downcast::<K, BigInt>(a), downcast::<K, BigInt>(b)) {
+        return Some(upcast(a.flint_mul(b)));
+    }
+    let (a, b) = (downcast::<K, Modular>(a)?, downcast::<K, Modular>(b)?);
+    let field = common_field(a, b)?;
+    Some(upcast(a.flint_mul(b, &field)))
+}
+
+/// The monic greatest common divisor of `a` and `b` by FLINT, or `None`
+/// unless the coefficients are residues and either has [`THRESHOLD`]
+/// coefficients.
+pub(crate) fn gcd<K: SemiringElement + Clone>(
+    a: &DenseUnivariate<K>,
+    b: &DenseUnivariate<K>,
+) -> Option<DenseUnivariate<K>> {
+    if !delegates(a.coeffs().len().max(b.coeffs().len())) {
+        return None;
+    }
+    let (a, b) = (downcast::<K, Modular>(a)?, downcast::<K, Modular>(b)?);
+    let field = common_field(a, b)?;
+    Some(upcast(a.flint_gcd(b, &field)))
+}
+
+/// An `fmpz`, cleared on drop.
+struct Fmpz(sys::fmpz);
+
+impl Fmpz {
+    fn new(n: &BigInt) -> Self {
+        // Zero is the small integer 0, which needs no initialization.
+        let mut f = Fmpz(0);
+        let (sign, digits) = n.to_u32_digits();
+        let limbs: Vec<sys::ulong> = digits
+            .chunks(LIMB_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |l, (k, &d)| l | (d as sys::ulong) << (32 * k))
+            })
+            .collect();
+        // SAFETY: `f` is an initialized `fmpz` and `limbs` has the given
+        // length.
+        unsafe {
+            sys::fmpz_set_ui_array(&mut f.0, limbs.as_ptr(), limbs.len() as sys::slong);
+            if sign == Sign::Minus {
+                sys::fmpz_neg(&mut f.0, &f.0);
+            }
+        }
+        f
+    }
+}
+
+impl Drop for Fmpz {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is an initialized `fmpz`.
+        unsafe { sys::fmpz_clear(&mut self.0) }
+    }
+}
+
+/// The integer `f`.
+///
+/// # Safety
+///
+/// `f` must point to an initialized `fmpz`.
+unsafe fn to_bigint(f: *const sys::fmpz) -> BigInt {
+    unsafe {
+        let sign = match sys::fmpz_sgn(f) {
+            0 => return BigInt::zero(),
+            s if s < 0 => Sign::Minus,
+            _ => Sign::Plus,
+        };
+        let mut abs = Fmpz(0);
+        sys::fmpz_abs(&mut abs.0, f);
+        let mut limbs: Vec<sys::ulong> = vec![0; sys::fmpz_size(&abs.0) as usize];
+        sys::fmpz_get_ui_array(limbs.as_mut_ptr(), limbs.len() as sys::slong, &abs.0);
+        let digits = limbs
+            .iter()
+            .flat_map(|&l| (0..LIMB_DIGITS).map(move |k| (l >> (32 * k)) as u32))
+            .collect();
+        BigInt::new(sign, digits)
+    }
+}
+
+/// The polynomial with the coefficients of `poly`, as elements of `field`.
+///
+/// # Safety
+///
+/// `poly` must be initialized.
+unsafe fn nmod_dense(poly: &sys::nmod_poly_struct, field: &PrimeField) -> DenseUnivariate<Modular> {
+    // SAFETY: the first `length` coefficients are initialized.
+    let coeffs = unsafe { std::slice::from_raw_parts(poly.coeffs, poly.length as usize) };
+    DenseUnivariate::new(coeffs.iter().map(|&c| field.element(c as i64)).collect())
+}
+
+/// The polynomial with the coefficients of `poly`.
+///
+/// # Safety
+///
+/// `poly` must be initialized.
+unsafe fn fmpz_dense(poly: &sys::fmpz_poly_struct) -> DenseUnivariate<BigInt> {
+    // SAFETY: the first `length` coefficients are initialized `fmpz`.
+    unsafe {
+        let coeffs = std::slice::from_raw_parts(poly.coeffs, poly.length as usize);
+        DenseUnivariate::new(coeffs.iter().map(|c| to_bigint(c)).collect())
+    }
+}
+
+/// An `nmod_poly`, cleared on drop.
+struct NmodPoly(sys::nmod_poly_struct);
+
+impl NmodPoly {
+    /// The zero polynomial over `field`.
+    ///
+    /// # Panics
+    ///
+    /// If FLINT is not [`is_supported`] or the prime does not fit in a limb.
+    fn new(field: &PrimeField) -> Self {
+        require_supported();
+        let p = limb(field).expect("the prime fits in a limb");
+        let mut poly = MaybeUninit::uninit();
+        // SAFETY: `nmod_poly_init` initializes `poly`.
+        unsafe {
+            sys::nmod_poly_init(poly.as_mut_ptr(), p);
+            NmodPoly(poly.assume_init())
+        }
+    }
+
+    fn from_dense(f: &DenseUnivariate<Modular>, field: &PrimeField) -> Self {
+        let mut poly = Self::new(field);
+        for (i, c) in f.coeffs().iter().enumerate() {
+            // Adding zero gives the constants `zero()` and `one()` the
+            // modulus.
+            let c = (field.element(0) + *c).value() as sys::ulong;
+            // SAFETY: `poly` is initialized.
+            unsafe { sys::nmod_poly_set_coeff_ui(&mut poly.0, i as sys::slong, c) }
+        }
+        poly
+    }
+
+    fn to_dense(&self, field: &PrimeField) -> DenseUnivariate<Modular> {
+        // SAFETY: `self.0` is initialized.
+        unsafe { nmod_dense(&self.0, field) }
+    }
+}
+
+impl Drop for NmodPoly {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is initialized.
+        unsafe { sys::nmod_poly_clear(&mut self.0) }
+    }
+}
+
+/// An `fmpz_poly`, cleared on drop.
+struct FmpzPoly(sys::fmpz_poly_struct);
+
+impl FmpzPoly {
+    /// The zero polynomial.
+    ///
+    /// # Panics
+    ///
+    /// If FLINT is not [`is_supported`].
+    fn new() -> Self {
+        require_supported();
+        let mut poly = MaybeUninit::uninit();
+        // SAFETY: `fmpz_poly_init` initializes `poly`.
+        unsafe {
+            sys::fmpz_poly_init(poly.as_mut_ptr());
+            FmpzPoly(poly.assume_init())
+        }
+    }
+
+    fn from_dense(f: &DenseUnivariate<BigInt>) -> Self {
+        let mut poly = Self::new();
+        for (i, c) in f.coeffs().iter().enumerate() {
+            let c = Fmpz::new(c);
+            // SAFETY: `poly` and `c` are initialized.
+            unsafe { sys::fmpz_poly_set_coeff_fmpz(&mut poly.0, i as sys::slong, &c.0) }
+        }
+        poly
+    }
+
+    fn to_dense(&self) -> DenseUnivariate<BigInt> {
+        // SAFETY: `self.0` is initialized.
+        unsafe { fmpz_dense(&self.0) }
+    }
+}
+
+impl Drop for FmpzPoly {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is initialized.
+        unsafe { sys::fmpz_poly_clear(&mut self.0) }
+    }
+}
+
+/// Polynomials over `field`, with arithmetic delegated to the `nmod_poly`
+/// of FLINT 3, which the `flint` feature links from the system. Operands are
+/// converted on each call. Multiplication and [`DenseUnivariate::gcd`]
+/// delegate to these for operands with enough coefficients.
+///
+/// These panic if FLINT is not [`is_supported`] or the prime of `field` does
+/// not fit in a limb.
+impl DenseUnivariate<Modular> {
+    /// The product `self * rhs` over `field`.
+    pub fn flint_mul(&self, rhs: &Self, field: &PrimeField) -> Self {
+        let (a, b) = (
+            NmodPoly::from_dense(self, field),
+            NmodPoly::from_dense(rhs, field),
+        );
+        let mut c = NmodPoly::new(field);
+        // SAFETY: all three polynomials are initialized with the same modulus.
+        unsafe { sys::nmod_poly_mul(&mut c.0, &a.0, &b.0) };
+        c.to_dense(field)
+    }
+
+    /// The monic greatest common divisor of `self` and `other` over `field`,
+    /// as [`DenseUnivariate::gcd`].
+    pub fn flint_gcd(&self, other: &Self, field: &PrimeField) -> Self {
+        let (a, b) = (
+            NmodPoly::from_dense(self, field),
+            NmodPoly::from_dense(other, field),
+        );
+        let mut g = NmodPoly::new(field);
+        // SAFETY: all three polynomials are initialized with the same modulus.
+        unsafe { sys::nmod_poly_gcd(&mut g.0, &a.0, &b.0) };
+        g.to_dense(field)
+    }
+
+    /// The factorization of the nonzero `self` over `field`: its leading
+    /// coefficient and its distinct monic irreducible factors with their
+    /// multiplicities.
+    pub fn flint_factor(&self, field: &PrimeField) -> (Modular, Vec<(Self, usize)>) {
+        let f = NmodPoly::from_dense(self, field);
+        let mut fac = MaybeUninit::uninit();
+        // SAFETY: `fac` is initialized before use and cleared after its
+        // factors are copied out.
+        unsafe {
+            sys::nmod_poly_factor_init(fac.as_mut_ptr());
+            let mut fac = fac.assume_init();
+            let lc = sys::nmod_poly_factor(&mut fac, &f.0);
+            let factors = (0..fac.num as usize)
+                .map(|i| (nmod_dense(&*fac.p.add(i), field), *fac.exp.add(i) as usize))
+                .collect();
+            sys::nmod_poly_factor_clear(&mut fac);
+            (field.element(lc as i64), factors)
+        }
+    }
+}
+
+/// Polynomials over `ℤ`, with arithmetic delegated to the `fmpz_poly` of
+/// FLINT 3, which the `flint` feature links from the system. Operands are
+/// converted on each call. Multiplication,
+/// [`DenseUnivariate::gcd_modular`] and [`DenseUnivariate::factor`]
+/// delegate to these for operands with enough coefficients.
+///
+/// These panic if FLINT is not [`is_supported`].
+impl DenseUnivariate<BigInt> {
+    /// The product `self * rhs`.
+    pub fn flint_mul(&self, rhs: &Self) -> Self {
+        let (a, b) = (FmpzPoly::from_dense(self), FmpzPoly::from_dense(rhs));
+        let mut c = FmpzPoly::new();
+        // SAFETY: all three polynomials are initialized.
+        unsafe { sys::fmpz_poly_mul(&mut c.0, &a.0, &b.0) };
+        c.to_dense()
+    }
+
+    /// The greatest common divisor of `self` and `other` in `ℤ[x]`, with a
+    /// positive leading coefficient, which is zero only if both are zero.
+    pub fn flint_gcd(&self, other: &Self) -> Self {
+        let (a, b) = (FmpzPoly::from_dense(self), FmpzPoly::from_dense(other));
+        let mut g = FmpzPoly::new();
+        // SAFETY: all three polynomials are initialized.
+        unsafe { sys::fmpz_poly_gcd(&mut g.0, &a.0, &b.0) };
+        g.to_dense()
+    }
+
+    /// The factorization of `self` in `ℤ[x]`: its content, with the sign of
+    /// its leading coefficient, and its distinct primitive irreducible
+    /// factors of positive degree, with positive leading coefficients, and
+    /// their multiplicities.
+    pub fn flint_factor(&self) -> (BigInt, Vec<(Self, usize)>) {
+        let f = FmpzPoly::from_dense(self);
+        let mut fac = MaybeUninit::uninit();
+        // SAFETY: `fac` is initialized before use and cleared after its
+        // content and factors are copied out.
+        unsafe {
+            sys::fmpz_poly_factor_init(fac.as_mut_ptr());
+            let mut fac = fac.assume_init();
+            sys::fmpz_poly_factor(&mut fac, &f.0);
+            let factors = (0..fac.num as usize)
+                .map(|i| (fmpz_dense(&*fac.p.add(i)), *fac.exp.add(i) as usize))
+                .collect();
+            let content = to_bigint(&fac.c);
+            sys::fmpz_poly_factor_clear(&mut fac);
+            (content, factors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigRational, One};
+
+    use super::*;
+    use crate::control::ComputationControl;
+
+    /// A polynomial with `n` pseudorandom coefficients of about 90 bits, of
+    /// either sign.
+    fn pseudorandom(n: usize, seed: u64) -> DenseUnivariate<BigInt> {
+        let mut state = seed;
+        DenseUnivariate::new(
+            (0..n)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    let c: BigInt = num::pow(BigInt::from(state >> 34), 3) + 1;
+                    if state & 1 == 1 { -c } else { c }
+                })
+                .collect(),
+        )
+    }
+
+    fn rational(f: &DenseUnivariate<BigInt>) -> DenseUnivariate<BigRational> {
+        f.map(|c| BigRational::from_integer(c.clone()))
+    }
+
+    #[test]
+    fn links_flint_3() {
+        assert!(is_supported(), "version {}", version());
+    }
+
+    #[test]
+    fn multiplication_matches_the_native_paths() {
+        let (a, b) = (pseudorandom(50, 1), pseudorandom(40, 2));
+        assert_eq!(a.clone() * b.clone(), a.mul_karatsuba(&b));
+        assert_eq!(a.flint_mul(&b), a.mul_ntt(&b));
+        let field = PrimeField::new(1_000_003).unwrap();
+        let (a, b) = (
+            a.map(|c| field.from_integer(c)),
+            b.map(|c| field.from_integer(c)),
+        );
+        assert_eq!(a.clone() * b.clone(), a.mul_karatsuba(&b));
+    }
+
+    #[test]
+    fn gcd_matches_the_native_paths() {
+        let mut g = pseudorandom(40, 3);
+        g += DenseUnivariate::monomial(BigInt::one(), 40);
+        let a = g.clone() * (DenseUnivariate::monomial(BigInt::one(), 5) + DenseUnivariate::one());
+        let b = g.clone()
+            * (DenseUnivariate::monomial(BigInt::one(), 5)
+                + DenseUnivariate::constant(BigInt::from(2)));
+        assert_eq!(a.gcd_modular(&b), g);
+        assert_eq!(rational(&a).gcd(&rational(&b)), rational(&g));
+        let field = PrimeField::new(1_000_003).unwrap();
+        let (a, b) = (
+            a.map(|c| field.from_integer(c)),
+            b.map(|c| field.from_integer(c)),
+        );
+        assert_eq!(a.gcd(&b), a.extended_gcd(&b).0);
+    }
+
+    #[test]
+    fn factorization_matches_the_native_paths() {
+        let poly = |coeffs: &[i64]| {
+            DenseUnivariate::new(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+        };
+        let f = poly(&[-2])
+            * poly(&[1, 0, 0, 0, 1])
+            * poly(&[1, 0, -10, 0, 1])
+            * num::pow(poly(&[1, 3]), 2)
+            * num::pow(pseudorandom(20, 4), 2);
+        let native = crate::factor::factor_native(&f, &ComputationControl::new());
+        assert_eq!(f.factor(), native.unwrap());
+        let field = PrimeField::new(101).unwrap();
+        let f = f.map(|c| field.from_integer(c));
+        let (lc, factors) = f.flint_factor(&field);
+        let product = factors
+            .iter()
+            .fold(DenseUnivariate::constant(lc), |a, (g, e)| {
+                assert!(g.is_irreducible(&field));
+                a * num::pow(g.clone(), *e)
+            });
+        assert_eq!(product, f);
+    }
+}
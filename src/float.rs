@@ -0,0 +1,167 @@
+use std::array;
+use std::hash::{BuildHasher, Hash};
+use std::iter::zip;
+
+use num::{PrimInt, ToPrimitive, Unsigned};
+
+use crate::{Field, Polynomial, Ring, Semiring};
+
+/// The real numbers, approximated by `f64` coefficients, with a
+/// tolerance-based notion of zero.
+///
+/// A value is treated as zero if its magnitude is at most `abs_tol`, or if it
+/// arises from a sum whose result is at most `rel_tol` times the magnitude of
+/// the larger summand. Polynomials over this ring therefore discard terms
+/// produced by catastrophic cancellation, rather than accumulating rounding
+/// noise such as `1e-17*x^2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatRing {
+    pub abs_tol: f64,
+    pub rel_tol: f64,
+}
+
+impl FloatRing {
+    /// A ring treating only exact zeros as zero.
+    pub fn exact() -> Self {
+        Self::with_tolerance(0.0, 0.0)
+    }
+
+    pub fn with_tolerance(abs_tol: f64, rel_tol: f64) -> Self {
+        Self { abs_tol, rel_tol }
+    }
+
+    fn is_negligible(&self, x: f64, scale: f64) -> bool {
+        x.abs() <= self.abs_tol || x.abs() <= self.rel_tol * scale
+    }
+}
+
+impl Default for FloatRing {
+    /// A relative tolerance of a few units in the last place, and no absolute
+    /// tolerance.
+    fn default() -> Self {
+        Self::with_tolerance(0.0, 4.0 * f64::EPSILON)
+    }
+}
+
+impl Semiring<f64> for FloatRing {
+    fn is_zero(&self, x: &f64) -> bool {
+        self.is_negligible(*x, 0.0)
+    }
+
+    fn add_into(&self, acc: &mut f64, x: f64) -> bool {
+        let scale = acc.abs().max(x.abs());
+        *acc += x;
+        self.is_negligible(*acc, scale)
+    }
+}
+
+impl Ring<f64> for FloatRing {
+    fn sub_from(&self, acc: &mut f64, x: f64) -> bool {
+        let scale = acc.abs().max(x.abs());
+        *acc -= x;
+        self.is_negligible(*acc, scale)
+    }
+}
+
+impl Field<f64> for FloatRing {}
+
+/// The number of points evaluated together by [`Polynomial::eval_many`].
+const CHUNK: usize = 64;
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: ToPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// The values of `self` at each of `points`, computed in `f64`.
+    ///
+    /// The terms are converted to `f64` and sorted once. The points are then
+    /// taken in chunks of 64: the powers of each coordinate up to the largest
+    /// exponent of its variable are tabulated once per chunk, and each term
+    /// is accumulated over the whole chunk in loops over contiguous arrays,
+    /// which the compiler vectorizes.
+    ///
+    /// Panics if `N` is not the number of variables of the ring, or if a
+    /// coefficient has no `f64` value.
+    pub fn eval_many<const N: usize>(&self, points: &[[f64; N]]) -> Vec<f64> {
+        assert_eq!(N, self.elem_of.vars.len(), "wrong number of variables");
+        let mut terms: Vec<([usize; N], f64)> = self
+            .terms
+            .iter()
+            .map(|(m, c)| {
+                let exponents = array::from_fn(|i| m.powers[i].to_usize().unwrap());
+                (exponents, c.to_f64().unwrap())
+            })
+            .collect();
+        terms.sort_unstable_by_key(|(e, _)| *e);
+        // `powers[i][k]` holds the `k`th powers of coordinate `i` of the
+        // points of the chunk.
+        let mut powers: Vec<Vec<[f64; CHUNK]>> = (0..N)
+            .map(|i| {
+                let max = terms.iter().map(|(e, _)| e[i]).max().unwrap_or(0);
+                vec![[1.0; CHUNK]; max + 1]
+            })
+            .collect();
+        let mut values = Vec::with_capacity(points.len());
+        for chunk in points.chunks(CHUNK) {
+            for (i, table) in powers.iter_mut().enumerate() {
+                for k in 1..table.len() {
+                    let (lower, upper) = table.split_at_mut(k);
+                    for (j, point) in chunk.iter().enumerate() {
+                        upper[0][j] = lower[k - 1][j] * point[i];
+                    }
+                }
+            }
+            let mut sum = [0.0; CHUNK];
+            for (exponents, c) in &terms {
+                let mut term = [*c; CHUNK];
+                for (table, &k) in zip(&powers, exponents) {
+                    if k > 0 {
+                        for (t, x) in zip(&mut term, &table[k]) {
+                            *t *= x;
+                        }
+                    }
+                }
+                for (s, t) in zip(&mut sum, &term) {
+                    *s += t;
+                }
+            }
+            values.extend_from_slice(&sum[..chunk.len()]);
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::smallvec;
+
+    use super::*;
+    use crate::{Monomial, PolynomialRing};
+
+    #[test]
+    fn products_drop_cancelled_terms() {
+        let base = FloatRing::default();
+        let ring = PolynomialRing::new(vec!["x".to_string(), "y".to_string()], &base);
+        let term = |x: u32, y: u32, c: f64| {
+            (
+                Monomial {
+                    powers: smallvec![x, y],
+                },
+                c,
+            )
+        };
+        let poly = |terms: Vec<(Monomial<u32>, f64)>| -> Polynomial<'_, _, _, _, _> {
+            Polynomial::from_terms(&ring, terms).unwrap()
+        };
+        // The coefficient of `x*y` is `0.1*3 - 0.3`, which rounds to about
+        // `5.6e-17` instead of zero.
+        let f = poly(vec![term(1, 0, 0.1), term(0, 1, 0.3)]);
+        let g = poly(vec![term(0, 1, 3.0), term(1, 0, -1.0)]);
+        let product = f.clone() * g.clone();
+        assert_eq!(product.terms.len(), 2);
+        assert!(!product.terms.contains_key(&term(1, 1, 0.0).0));
+        assert_eq!(product.terms, f.mul_trunc(&g, 2).terms);
+    }
+}
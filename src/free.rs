@@ -0,0 +1,525 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use num::{One, Zero};
+
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// A variable `my_ring: FreeAlgebra<R, V>` represents the free associative
+/// algebra `R<x_1, ..., x_n>` over a base ring `R`, in which the variables
+/// `vars` do not commute with each other. Its elements are of type
+/// `FreePolynomial<'_, R, V, K>`, with coefficients valued in `K`.
+///
+/// The coefficients are assumed to commute with the variables. The algebra
+/// is not commutative, which it advertises through [`Semiring::COMMUTATIVE`].
+pub struct FreeAlgebra<'a, R, V> {
+    pub vars: Vec<V>,
+    pub base: &'a R,
+}
+
+/// A word in the variables of a free algebra, given by the indices of its
+/// letters from left to right. The empty word is the unit.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word {
+    pub letters: Vec<usize>,
+}
+
+/// The empty word, under which a detached constant is listed.
+static EMPTY: Word = Word {
+    letters: Vec::new(),
+};
+
+impl Word {
+    pub fn degree(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// The concatenation of `self` followed by `rhs`.
+    pub fn concat(&self, rhs: &Word) -> Word {
+        let mut letters = self.letters.clone();
+        letters.extend_from_slice(&rhs.letters);
+        Word { letters }
+    }
+}
+
+/// A non-commutative polynomial, as a hash map associating to each word a
+/// coefficient. As for [`crate::Polynomial`], the map contains only nonzero
+/// coefficients.
+///
+/// Since `Zero::zero` and `One::one` are called without access to an
+/// algebra, constants in `K` may also be stored detached from any algebra. A
+/// detached constant `c` is attached as `c` times the empty word to the
+/// algebra of the other operand when combined with a polynomial.
+pub struct FreePolynomial<'a, R, V, K> {
+    repr: Repr<'a, R, V, K>,
+}
+
+enum Repr<'a, R, V, K> {
+    Constant(K),
+    Terms {
+        elem_of: &'a FreeAlgebra<'a, R, V>,
+        terms: HashMap<Word, K>,
+    },
+}
+
+impl<R, V, K: Clone> Clone for FreePolynomial<'_, R, V, K> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Constant(c) => Repr::Constant(c.clone()),
+            Repr::Terms { elem_of, terms } => Repr::Terms {
+                elem_of,
+                terms: terms.clone(),
+            },
+        };
+        Self { repr }
+    }
+}
+
+impl<'a, R, V> FreeAlgebra<'a, R, V> {
+    pub fn new(vars: Vec<V>, base: &'a R) -> Self {
+        Self { vars, base }
+    }
+
+    /// The term `c*w`, or zero if `c` is zero.
+    pub fn term<K>(&'a self, w: Word, c: K) -> FreePolynomial<'a, R, V, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        let mut terms = HashMap::new();
+        if !self.base.is_zero(&c) {
+            terms.insert(w, c);
+        }
+        self.with_terms(terms)
+    }
+
+    pub fn zero<K>(&'a self) -> FreePolynomial<'a, R, V, K> {
+        self.with_terms(HashMap::new())
+    }
+
+    fn with_terms<K>(&'a self, terms: HashMap<Word, K>) -> FreePolynomial<'a, R, V, K> {
+        FreePolynomial {
+            repr: Repr::Terms {
+                elem_of: self,
+                terms,
+            },
+        }
+    }
+
+    pub fn constant<K>(&'a self, c: K) -> FreePolynomial<'a, R, V, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        self.term(Word { letters: vec![] }, c)
+    }
+
+    pub fn one<K>(&'a self) -> FreePolynomial<'a, R, V, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        self.constant(K::one())
+    }
+
+    /// The `i`-th variable.
+    pub fn var<K>(&'a self, i: usize) -> FreePolynomial<'a, R, V, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        assert!(i < self.vars.len(), "variable index out of range");
+        self.term(Word { letters: vec![i] }, K::one())
+    }
+}
+
+/// Adds `c*w` to `terms`, removing the term if it cancels in `base`.
+fn add_term<R, K>(base: &R, terms: &mut HashMap<Word, K>, w: Word, c: K)
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+{
+    match terms.entry(w) {
+        Entry::Occupied(mut entry) => {
+            if base.add_into(entry.get_mut(), c) {
+                entry.remove();
+            }
+        }
+        Entry::Vacant(entry) => {
+            if !base.is_zero(&c) {
+                entry.insert(c);
+            }
+        }
+    }
+}
+
+impl<'a, R, V, K> FreePolynomial<'a, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    /// The algebra of `self` and `other`, unless both are detached
+    /// constants.
+    fn algebra_of(&self, other: &Self) -> Option<&'a FreeAlgebra<'a, R, V>> {
+        match (&self.repr, &other.repr) {
+            (Repr::Terms { elem_of, .. }, _) | (_, Repr::Terms { elem_of, .. }) => Some(elem_of),
+            _ => None,
+        }
+    }
+
+    /// The terms of `self` in `algebra`.
+    fn into_terms(self, algebra: &'a FreeAlgebra<'a, R, V>) -> HashMap<Word, K> {
+        match self.repr {
+            Repr::Constant(c) => algebra.constant(c).into_terms(algebra),
+            Repr::Terms { terms, .. } => terms,
+        }
+    }
+
+    /// The terms of `self`, as pairs of a word and its nonzero coefficient.
+    pub fn terms(&self) -> impl Iterator<Item = (&Word, &K)> {
+        let (constant, terms) = match &self.repr {
+            Repr::Constant(c) => (Some(c).filter(|c| !c.is_zero()), None),
+            Repr::Terms { terms, .. } => (None, Some(terms)),
+        };
+        let constant = constant.map(|c| (&EMPTY, c));
+        constant.into_iter().chain(terms.into_iter().flatten())
+    }
+
+    /// The coefficient of the word `w`.
+    pub fn coeff(&self, w: &Word) -> K {
+        match &self.repr {
+            Repr::Constant(c) if w.letters.is_empty() => c.clone(),
+            Repr::Constant(_) => K::zero(),
+            Repr::Terms { terms, .. } => terms.get(w).cloned().unwrap_or_else(K::zero),
+        }
+    }
+
+    /// The length of the longest word in `self`, or `None` for zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.terms().map(|(w, _)| w.degree()).max()
+    }
+
+    /// Evaluates `self` at `point`, which gives a value for each variable, in
+    /// any (possibly non-commutative) ring `T` into which the coefficients
+    /// embed.
+    pub fn eval<T>(&self, point: &[T]) -> T
+    where
+        T: SemiringElement + Clone + From<K>,
+    {
+        let mut value = T::zero();
+        for (w, c) in self.terms() {
+            let mut term = T::from(c.clone());
+            for &i in w.letters.iter() {
+                term *= point[i].clone();
+            }
+            value += term;
+        }
+        value
+    }
+}
+
+impl<R, V, K> FreePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    /// The commutator `self*rhs - rhs*self`.
+    pub fn commutator(&self, rhs: &Self) -> Self {
+        self.clone() * rhs.clone() - rhs.clone() * self.clone()
+    }
+}
+
+impl<R, V, K> Add for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a + b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (w, c) in rhs.into_terms(algebra) {
+            add_term(algebra.base, &mut terms, w, c);
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> AddAssign for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<R, V, K> Sub for FreePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a - b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (w, c) in rhs.into_terms(algebra) {
+            match terms.entry(w) {
+                Entry::Occupied(mut entry) => {
+                    if algebra.base.sub_from(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c);
+                }
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> SubAssign for FreePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+/// The product, extended bilinearly from the concatenation of words.
+impl<R, V, K> Mul for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a * b),
+            };
+        };
+        let (lhs, rhs) = (self.into_terms(algebra), rhs.into_terms(algebra));
+        let mut terms = HashMap::new();
+        for (w1, c1) in lhs.iter() {
+            for (w2, c2) in rhs.iter() {
+                add_term(
+                    algebra.base,
+                    &mut terms,
+                    w1.concat(w2),
+                    c1.clone() * c2.clone(),
+                );
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K> MulAssign for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::one()) * rhs;
+    }
+}
+
+impl<R, V, K> Zero for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn zero() -> Self {
+        Self {
+            repr: Repr::Constant(K::zero()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match &self.repr {
+            Repr::Constant(c) => c.is_zero(),
+            Repr::Terms { terms, .. } => terms.is_empty(),
+        }
+    }
+}
+
+impl<R, V, K> One for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn one() -> Self {
+        Self {
+            repr: Repr::Constant(K::one()),
+        }
+    }
+}
+
+impl<R, V, K> Semiring<FreePolynomial<'_, R, V, K>> for FreeAlgebra<'_, R, V>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    const COMMUTATIVE: bool = false;
+}
+
+impl<R, V, K> Ring<FreePolynomial<'_, R, V, K>> for FreeAlgebra<'_, R, V>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+impl<R, V, K> SemiringElement for FreePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+}
+
+impl<R, V, K> RingElement for FreePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+/// Displays the terms in order of decreasing degree, writing repeated
+/// letters as powers, as in `x*y^2*x`, and a detached constant as itself.
+impl<R, V, K> Display for FreePolynomial<'_, R, V, K>
+where
+    V: Display,
+    K: Display + One + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (elem_of, terms) = match &self.repr {
+            Repr::Constant(c) => return Display::fmt(c, f),
+            Repr::Terms { elem_of, terms } => (elem_of, terms),
+        };
+        let mut terms: Vec<_> = terms.iter().collect();
+        terms.sort_by(|(w1, _), (w2, _)| {
+            (w2.degree(), &w2.letters).cmp(&(w1.degree(), &w1.letters))
+        });
+        if terms.is_empty() {
+            write!(f, "0")?;
+        }
+        for (i, (w, c)) in terms.into_iter().enumerate() {
+            if w.letters.is_empty() || !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                if w.letters.is_empty() {
+                    continue;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            let mut letters = w.letters.iter().peekable();
+            let mut first = true;
+            while let Some(&x) = letters.next() {
+                let mut p = 1;
+                while letters.next_if_eq(&&x).is_some() {
+                    p += 1;
+                }
+                if !first {
+                    write!(f, "*")?;
+                }
+                write!(f, "{}", elem_of.vars[x])?;
+                if p > 1 {
+                    write!(f, "^{p}")?;
+                }
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use num::BigRational;
+    use smallvec::smallvec;
+
+    use super::*;
+    use crate::{AlreadyRing, Monomial, Polynomial, PolynomialRing};
+
+    type Free<'a> = FreePolynomial<'a, AlreadyRing<BigRational>, &'static str, BigRational>;
+
+    #[test]
+    fn generic_powers_keep_the_order_of_factors() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let algebra = FreeAlgebra::new(vec!["x", "y"], &base);
+        let (x, y): (Free, Free) = (algebra.var(0), algebra.var(1));
+        let square = num::pow(x.clone() + y.clone(), 2);
+        let expected = x.clone() * x.clone()
+            + x.clone() * y.clone()
+            + y.clone() * x.clone()
+            + y.clone() * y.clone();
+        assert!((square - expected).is_zero());
+        assert!(!x.commutator(&y).is_zero());
+    }
+
+    #[test]
+    fn polynomial_products_keep_the_order_of_coefficients() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let algebra = FreeAlgebra::new(vec!["x", "y"], &base);
+        let (x, y): (Free, Free) = (algebra.var(0), algebra.var(1));
+        let ring = PolynomialRing::new(vec!["t"], &algebra);
+        let t = |e: u32| Monomial {
+            powers: smallvec![e],
+        };
+        let f = Polynomial::<_, _, _, u32>::from_terms(
+            &ring,
+            [(t(2), x.clone()), (t(1), y.clone()), (t(0), x.clone())],
+        )
+        .unwrap();
+        let g = Polynomial::from_terms(&ring, [(t(1), y.clone())]).unwrap();
+        let product = f * g;
+        let xy = Word {
+            letters: vec![0, 1],
+        };
+        let yx = Word {
+            letters: vec![1, 0],
+        };
+        let c = product.coeff(&t(3)).unwrap();
+        assert_eq!((c.coeff(&xy), c.coeff(&yx)), (BigRational::one(), BigRational::zero()));
+        let c = product.coeff(&t(1)).unwrap();
+        assert_eq!((c.coeff(&xy), c.coeff(&yx)), (BigRational::one(), BigRational::zero()));
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::zip;
+
+use num::{PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::geobucket::Geobucket;
+use crate::order::Lex;
+use crate::{Field, FieldElement, Monomial, Polynomial, Powers};
+
+pub mod integer;
+
+/// Greatest common divisors and exact division of multivariate polynomials
+/// over a field.
+///
+/// Both operations refer to the lexicographic order on monomials, in which
+/// the first variable is the most significant. The GCD is computed
+/// recursively by the primitive polynomial remainder sequence: viewing
+/// polynomials as univariate in their last variable, with coefficients in the
+/// polynomial ring in the remaining variables, the GCD is the product of the
+/// GCD of the contents and the last nonzero element of the remainder sequence
+/// of the primitive parts.
+impl<'a, R, V, K, P> Polynomial<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn constant(&self, c: K) -> Self {
+        let powers = smallvec![P::zero(); self.elem_of.vars.len()];
+        self.term(Monomial { powers }, c)
+    }
+
+    fn term(&self, m: Monomial<P>, c: K) -> Self {
+        let mut terms = HashMap::default();
+        if !self.elem_of.base.is_zero(&c) {
+            terms.insert(m, c);
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+
+    /// The leading term of `self` in the lexicographic order.
+    fn lex_leading_term(&self) -> Option<(&Monomial<P>, &K)> {
+        self.terms
+            .iter()
+            .max_by(|(m1, _), (m2, _)| m1.powers.cmp(&m2.powers))
+    }
+
+    /// The degree of `self` in the `v`-th variable, or `None` for zero.
+    fn degree_in(&self, v: usize) -> Option<usize> {
+        self.terms
+            .keys()
+            .map(|m| m.powers[v].to_usize().unwrap())
+            .max()
+    }
+
+    /// The coefficient of `x_v^d` in `self`, viewed as a polynomial in `x_v`
+    /// over the polynomials in the other variables.
+    fn coeff_in(&self, v: usize, d: usize) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .filter(|(m, _)| m.powers[v].to_usize().unwrap() == d)
+            .map(|(m, c)| {
+                let mut m = m.clone();
+                m.powers[v] = P::zero();
+                (m, c.clone())
+            })
+            .collect();
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+
+    /// The content of `self` as a polynomial in `x_v`, that is, the GCD of
+    /// its coefficients.
+    fn content_in(&self, v: usize) -> Self {
+        let mut content = Self {
+            elem_of: self.elem_of,
+            terms: HashMap::default(),
+        };
+        for d in 0..=self.degree_in(v).unwrap_or(0) {
+            content = content.gcd(&self.coeff_in(v, d));
+        }
+        content
+    }
+
+    fn primitive_part_in(&self, v: usize) -> Self {
+        self.div_exact(&self.content_in(v)).unwrap()
+    }
+
+    /// The pseudo-remainder of `self` by `g`, as polynomials in `x_v`.
+    fn pseudo_rem_in(&self, g: &Self, v: usize) -> Self {
+        let dg = g.degree_in(v).unwrap();
+        let lc_g = g.coeff_in(v, dg);
+        let mut r = self.clone();
+        while let Some(dr) = r.degree_in(v).filter(|dr| *dr >= dg) {
+            let mut powers = smallvec![P::zero(); self.elem_of.vars.len()];
+            powers[v] = P::from(dr - dg).unwrap();
+            let shift = r.coeff_in(v, dr) * self.term(Monomial { powers }, K::one());
+            r = r * lc_g.clone() - shift * g.clone();
+        }
+        r
+    }
+
+    /// `self` divided by its leading coefficient in the lexicographic order,
+    /// or zero if `self` is zero.
+    pub fn monic(&self) -> Self {
+        match self.lex_leading_term() {
+            Some((_, c)) => self.clone() * self.constant(K::one() / c.clone()),
+            None => self.clone(),
+        }
+    }
+
+    /// The quotient `self / divisor`, or `None` if `divisor` does not divide
+    /// `self` (in particular, if `divisor` is zero).
+    pub fn div_exact(&self, divisor: &Self) -> Option<Self> {
+        let mut terms = divisor.sorted_terms(&Lex).into_iter();
+        let (gm, gc) = terms.next()?;
+        let tail: Vec<_> = terms.collect();
+        let mut r = Geobucket::new(self.elem_of, Lex);
+        r.add(self);
+        let mut q = Self {
+            elem_of: self.elem_of,
+            terms: HashMap::default(),
+        };
+        while let Some((rm, rc)) = r.pop_leading() {
+            if zip(rm.powers.iter(), gm.powers.iter()).any(|(a, b)| a < b) {
+                return None;
+            }
+            let powers: Powers<P> = zip(rm.powers.iter(), gm.powers.iter())
+                .map(|(a, b)| *a - *b)
+                .collect();
+            let t = rc / gc.clone();
+            // The leading term cancels by construction, so only the tail is
+            // subtracted.
+            let product = tail
+                .iter()
+                .map(|(m, c)| {
+                    let powers = zip(powers.iter(), m.powers.iter())
+                        .map(|(a, b)| *a + *b)
+                        .collect();
+                    (Monomial { powers }, t.clone() * (*c).clone())
+                })
+                .filter(|(_, c)| !self.elem_of.base.is_zero(c))
+                .collect();
+            r.sub_terms(product);
+            q.terms.insert(Monomial { powers }, t);
+        }
+        Some(q)
+    }
+
+    /// The monic greatest common divisor of `self` and `other`, which is zero
+    /// only if both are zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        if self.terms.is_empty() {
+            return other.monic();
+        }
+        if other.terms.is_empty() {
+            return self.monic();
+        }
+        let nvars = self.elem_of.vars.len();
+        let Some(v) = (0..nvars)
+            .rev()
+            .find(|&v| self.degree_in(v) > Some(0) || other.degree_in(v) > Some(0))
+        else {
+            // Both are nonzero constants.
+            return self.constant(K::one());
+        };
+        let (cf, cg) = (self.content_in(v), other.content_in(v));
+        let content = cf.gcd(&cg);
+        let mut a = self.div_exact(&cf).unwrap();
+        let mut b = other.div_exact(&cg).unwrap();
+        if a.degree_in(v) < b.degree_in(v) {
+            std::mem::swap(&mut a, &mut b);
+        }
+        while b.degree_in(v) > Some(0) {
+            let r = a.pseudo_rem_in(&b, v);
+            a = b;
+            b = if r.terms.is_empty() {
+                r
+            } else {
+                r.primitive_part_in(v)
+            };
+        }
+        if !b.terms.is_empty() {
+            // The remainder sequence reached a nonzero constant in `x_v`, so
+            // the primitive parts are coprime.
+            return content;
+        }
+        (content * a.primitive_part_in(v)).monic()
+    }
+}
@@ -0,0 +1,601 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::zip;
+use std::marker::PhantomData;
+
+use num::{BigInt, BigRational, Integer, One, PrimInt, Signed, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::geobucket::Geobucket;
+use crate::matrix::Matrix;
+use crate::multimodular::{Modular, PrimeField, word_primes};
+use crate::order::Lex;
+use crate::univariate::DenseUnivariate;
+use crate::univariate::fft::is_prime_u64;
+use crate::{AlreadyRing, Monomial, Polynomial, PolynomialRing, Powers, Ring, TermHasher};
+
+/// The number of primes after which [`Polynomial::gcd_modular`] falls back
+/// to a remainder sequence.
+const MAX_PRIMES: usize = 1024;
+
+/// A polynomial over ℤ/pℤ in the variables of a polynomial ring over ℤ.
+type Image<'b, P> = Polynomial<'b, PrimeField, usize, Modular, P>;
+
+/// The coefficients of `f` as a polynomial in the variables other than
+/// `x_k`, each a univariate polynomial in `x_k`.
+fn coefficients_in<P>(f: &Image<'_, P>, k: usize) -> HashMap<Monomial<P>, DenseUnivariate<Modular>>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut parts: HashMap<Monomial<P>, Vec<Modular>> = HashMap::new();
+    for (m, c) in &f.terms {
+        let d = m.powers[k].to_usize().unwrap();
+        let mut rest = m.clone();
+        rest.powers[k] = P::zero();
+        let coeffs = parts.entry(rest).or_default();
+        if coeffs.len() <= d {
+            coeffs.resize(d + 1, Modular::zero());
+        }
+        coeffs[d] = *c;
+    }
+    parts
+        .into_iter()
+        .map(|(m, c)| (m, DenseUnivariate::new(c)))
+        .collect()
+}
+
+/// The polynomial with the given coefficients in `x_k`, as in
+/// [`coefficients_in`].
+fn from_coefficients_in<'b, P>(
+    ring: &'b PolynomialRing<'b, PrimeField, usize>,
+    parts: impl IntoIterator<Item = (Monomial<P>, DenseUnivariate<Modular>)>,
+    k: usize,
+) -> Image<'b, P>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut terms = HashMap::default();
+    for (m, u) in parts {
+        for (d, c) in u.coeffs().iter().enumerate() {
+            if !c.is_zero() {
+                let mut m = m.clone();
+                m.powers[k] = P::from(d).unwrap();
+                terms.insert(m, *c);
+            }
+        }
+    }
+    Polynomial {
+        elem_of: ring,
+        terms,
+    }
+}
+
+/// The coefficient of the lexicographically greatest monomial in `parts`.
+fn leading_part<P: PrimInt>(
+    parts: &HashMap<Monomial<P>, DenseUnivariate<Modular>>,
+) -> &DenseUnivariate<Modular> {
+    parts
+        .iter()
+        .max_by(|(a, _), (b, _)| a.powers.cmp(&b.powers))
+        .unwrap()
+        .1
+}
+
+/// The GCD of the coefficients in `parts`.
+fn content_of<P>(
+    parts: &HashMap<Monomial<P>, DenseUnivariate<Modular>>,
+) -> DenseUnivariate<Modular> {
+    parts
+        .values()
+        .fold(DenseUnivariate::zero(), |c, u| c.gcd(u))
+}
+
+/// `f` with `x_k` replaced by `a`.
+fn substitute<'b, P>(f: &Image<'b, P>, k: usize, a: &Modular) -> Image<'b, P>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let terms = coefficients_in(f, k)
+        .into_iter()
+        .map(|(m, u)| (m, u.eval(a)))
+        .filter(|(_, c)| !c.is_zero())
+        .collect();
+    Polynomial {
+        elem_of: f.elem_of,
+        terms,
+    }
+}
+
+/// The monic GCD of `f` and `g` over ℤ/pℤ, in which only the variables
+/// `x_0, ..., x_k` occur, by the dense modular algorithm of Brown.
+///
+/// The variable `x_k` is eliminated by evaluation: after removing the
+/// contents in `x_k`, the GCDs of the images at points `x_k = a` are scaled
+/// to the GCD `γ` of the leading coefficients, which is a multiple of the
+/// leading coefficient of the GCD, and combined by Newton interpolation.
+/// Images whose leading monomial is too large come from unlucky points and
+/// are discarded. Once the interpolant stops changing, or enough points have
+/// been used to determine it, its primitive part is checked by trial
+/// division.
+///
+/// After the first image, the images at further points are computed by
+/// [`sparse_gcd`] from its support, which avoids the recursion when the
+/// support is right.
+fn brown_gcd<'b, P>(f: &Image<'b, P>, g: &Image<'b, P>, k: usize) -> Image<'b, P>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let ring = f.elem_of;
+    if f.terms.is_empty() {
+        return g.monic();
+    }
+    if g.terms.is_empty() {
+        return f.monic();
+    }
+    let (fk, gk) = (coefficients_in(f, k), coefficients_in(g, k));
+    if k == 0 {
+        let one = Monomial {
+            powers: smallvec![P::zero(); ring.vars.len()],
+        };
+        let h = fk[&one].gcd(&gk[&one]);
+        return from_coefficients_in(ring, [(one, h)], 0);
+    }
+    if f.degree_in(k) == Some(0) && g.degree_in(k) == Some(0) {
+        return brown_gcd(f, g, k - 1);
+    }
+    let (cf, cg) = (content_of(&fk), content_of(&gk));
+    let c = cf.gcd(&cg);
+    let primitive = |parts: HashMap<Monomial<P>, DenseUnivariate<Modular>>,
+                     c: &DenseUnivariate<Modular>| {
+        let parts: HashMap<_, _> = parts
+            .into_iter()
+            .map(|(m, u)| (m, u.div_rem(c).unwrap().0))
+            .collect();
+        let lead = leading_part(&parts).clone();
+        (from_coefficients_in(ring, parts, k), lead)
+    };
+    let (f, lf) = primitive(fk, &cf);
+    let (g, lg) = primitive(gk, &cg);
+    let gamma = lf.gcd(&lg);
+    let bound = gamma.degree().unwrap() + f.degree_in(k).unwrap().min(g.degree_in(k).unwrap());
+    let one = Monomial {
+        powers: smallvec![P::zero(); ring.vars.len()],
+    };
+    let content = from_coefficients_in(ring, [(one, c)], k);
+
+    let field = ring.base;
+    let mut sparse = k > 1;
+    let mut lead: Option<Monomial<P>> = None;
+    let mut form: Vec<Monomial<P>> = Vec::new();
+    let mut interpolant: HashMap<Monomial<P>, DenseUnivariate<Modular>> = HashMap::new();
+    let mut points = 0;
+    let mut q = DenseUnivariate::one();
+    for a in 1.. {
+        let a = field.element(a);
+        let gamma_a = gamma.eval(&a);
+        if gamma_a.is_zero() || lf.eval(&a).is_zero() || lg.eval(&a).is_zero() {
+            continue;
+        }
+        let (fa, ga) = (substitute(&f, k, &a), substitute(&g, k, &a));
+        let image = (sparse && lead.is_some())
+            .then(|| sparse_gcd(&fa, &ga, &form, k - 1))
+            .flatten()
+            .unwrap_or_else(|| brown_gcd(&fa, &ga, k - 1));
+        let lm = image.lex_leading_term().unwrap().0.clone();
+        match lead.as_ref().map(|l| lm.powers.cmp(&l.powers)) {
+            Some(Ordering::Greater) => continue,
+            Some(Ordering::Equal) => {}
+            _ => {
+                form = image.terms.keys().cloned().collect();
+                lead = Some(lm);
+                interpolant.clear();
+                points = 0;
+                q = DenseUnivariate::one();
+            }
+        }
+        let q_a = q.eval(&a);
+        let monomials: HashSet<_> = interpolant
+            .keys()
+            .chain(image.terms.keys())
+            .cloned()
+            .collect();
+        let mut changed = false;
+        for m in monomials {
+            let value = image
+                .terms
+                .get(&m)
+                .map_or(Modular::zero(), |c| *c * gamma_a);
+            let u = interpolant.entry(m).or_default();
+            let delta = (value - u.eval(&a)) / q_a;
+            if !delta.is_zero() {
+                changed = true;
+                *u += q.scale(&delta);
+            }
+        }
+        q *= DenseUnivariate::new(vec![-a, Modular::one()]);
+        points += 1;
+        if (points > 1 && !changed) || points > bound {
+            let content_h = content_of(&interpolant);
+            let h = interpolant
+                .iter()
+                .map(|(m, u)| (m.clone(), u.div_rem(&content_h).unwrap().0));
+            let h = from_coefficients_in(ring, h, k);
+            if f.div_exact(&h).is_some() && g.div_exact(&h).is_some() {
+                return (h * content.clone()).monic();
+            }
+            if points > bound {
+                sparse = false;
+                lead = None;
+            }
+        }
+    }
+    unreachable!("ran out of evaluation points")
+}
+
+/// The monic GCD of `f` and `g` over ℤ/pℤ, in which only the variables
+/// `x_0, ..., x_k` occur, assuming its support is `form`, by the sparse
+/// interpolation of Zippel, or `None` if that fails.
+///
+/// The variables `x_1, ..., x_k` are evaluated at points `(q_1^j, ...,
+/// q_k^j)` for the first primes `q_i`, and the univariate GCDs in `x_0` are
+/// computed. Each of them is the image of the GCD up to an unknown scalar,
+/// as the leading coefficient in `x_0` is a polynomial in the other
+/// variables; following de Kleine, Monagan and Wittkopf, the scalars join
+/// the coefficients as unknowns of one linear system, normalized by fixing
+/// the first scalar to 1.
+fn sparse_gcd<'b, P>(
+    f: &Image<'b, P>,
+    g: &Image<'b, P>,
+    form: &[Monomial<P>],
+    k: usize,
+) -> Option<Image<'b, P>>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let field = f.elem_of.base;
+    let degree_0 = |m: &Monomial<P>| m.powers[0].to_usize().unwrap();
+    let mut degrees: Vec<usize> = form.iter().map(degree_0).collect();
+    degrees.sort_unstable();
+    degrees.dedup();
+    if degrees.len() < 2 {
+        return None;
+    }
+    let widest = degrees
+        .iter()
+        .map(|&d| form.iter().filter(|m| degree_0(m) == d).count())
+        .max()
+        .unwrap();
+    let count = widest.max((form.len() - 1).div_ceil(degrees.len() - 1));
+    let unknowns = form.len() + count - 1;
+    let bases: Vec<_> = (2..)
+        .filter(|&q| is_prime_u64(q))
+        .take(k)
+        .map(|q| field.element(q as i64))
+        .collect();
+    let value = |m: &Monomial<P>, point: &[Modular]| {
+        zip(&m.powers[1..=k], point).fold(Modular::one(), |v, (e, b)| {
+            v * num::pow(*b, e.to_usize().unwrap())
+        })
+    };
+    let at = |h: &Image<'b, P>, point: &[Modular]| {
+        let mut coeffs = Vec::new();
+        for (m, c) in &h.terms {
+            let d = degree_0(m);
+            if coeffs.len() <= d {
+                coeffs.resize(d + 1, Modular::zero());
+            }
+            coeffs[d] += *c * value(m, point);
+        }
+        DenseUnivariate::new(coeffs)
+    };
+    let mut rows = Vec::new();
+    let mut rhs = Vec::new();
+    for j in 0..count {
+        let point: Vec<_> = bases.iter().map(|b| num::pow(*b, j + 1)).collect();
+        let (fj, gj) = (at(f, &point), at(g, &point));
+        if fj.degree() != f.degree_in(0) || gj.degree() != g.degree_in(0) {
+            return None;
+        }
+        let u = fj.gcd(&gj);
+        if u.degree() != degrees.last().copied()
+            || (0..u.coeffs().len()).any(|d| !u.coeff(d).is_zero() && !degrees.contains(&d))
+        {
+            return None;
+        }
+        for &d in &degrees {
+            let mut row = vec![Modular::zero(); unknowns];
+            for (t, m) in form.iter().enumerate() {
+                if degree_0(m) == d {
+                    row[t] = value(m, &point);
+                }
+            }
+            if j == 0 {
+                rhs.push(u.coeff(d));
+            } else {
+                row[form.len() + j - 1] = -u.coeff(d);
+                rhs.push(Modular::zero());
+            }
+            rows.push(row);
+        }
+    }
+    let system = Matrix::from_rows(rows).unwrap();
+    if system.rank() < unknowns {
+        return None;
+    }
+    let solution = system.solve(&rhs)?;
+    let terms = zip(form.iter().cloned(), solution)
+        .filter(|(_, c)| !c.is_zero())
+        .collect();
+    let h = Polynomial {
+        elem_of: f.elem_of,
+        terms,
+    };
+    (!h.terms.is_empty()).then(|| h.monic())
+}
+
+/// GCDs over ℤ by modular methods.
+impl<'a, R, V, P> Polynomial<'a, R, V, BigInt, P>
+where
+    R: Ring<BigInt>,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The lexicographically leading coefficient of `self`.
+    fn lex_leading_coeff(&self) -> &BigInt {
+        self.terms
+            .iter()
+            .max_by(|(a, _), (b, _)| a.powers.cmp(&b.powers))
+            .unwrap()
+            .1
+    }
+
+    /// The GCD of the coefficients of `self`, which is nonnegative.
+    fn integer_content(&self) -> BigInt {
+        self.terms.values().fold(BigInt::zero(), |c, a| c.gcd(a))
+    }
+
+    /// `self` with each coefficient multiplied by `c`.
+    fn scale_integer(&self, c: &BigInt) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self.terms.iter().map(|(m, a)| (m.clone(), a * c)).collect(),
+        }
+    }
+
+    /// `self` with each coefficient divided by its integer content, and the
+    /// lexicographically leading one made positive.
+    fn primitive_integer(&self) -> Self {
+        let mut content = self.integer_content();
+        if self.lex_leading_coeff().is_negative() {
+            content = -content;
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms: self
+                .terms
+                .iter()
+                .map(|(m, a)| (m.clone(), a / &content))
+                .collect(),
+        }
+    }
+
+    /// The quotient `self / divisor` over ℤ, or `None` if it does not exist.
+    fn div_exact_integer(&self, divisor: &Self) -> Option<Self> {
+        let mut terms = divisor.sorted_terms(&Lex).into_iter();
+        let (gm, gc) = terms.next()?;
+        let tail: Vec<_> = terms.collect();
+        let mut r = Geobucket::new(self.elem_of, Lex);
+        r.add(self);
+        let mut q = HashMap::default();
+        while let Some((rm, rc)) = r.pop_leading() {
+            if zip(&rm.powers, &gm.powers).any(|(a, b)| a < b) {
+                return None;
+            }
+            let (t, rest) = rc.div_rem(gc);
+            if !rest.is_zero() {
+                return None;
+            }
+            let powers: Powers<P> = zip(&rm.powers, &gm.powers).map(|(a, b)| *a - *b).collect();
+            let product = tail
+                .iter()
+                .map(|(m, c)| {
+                    let powers = zip(&powers, &m.powers).map(|(a, b)| *a + *b).collect();
+                    (Monomial { powers }, &t * *c)
+                })
+                .collect();
+            r.sub_terms(product);
+            q.insert(Monomial { powers }, t);
+        }
+        Some(Self {
+            elem_of: self.elem_of,
+            terms: q,
+        })
+    }
+
+    /// The image of `self` modulo the prime of `ring`.
+    fn image<'b>(&self, ring: &'b PolynomialRing<'b, PrimeField, usize>) -> Image<'b, P> {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(m, c)| (m.clone(), ring.base.from_integer(c)))
+            .filter(|(_, c)| !c.is_zero())
+            .collect();
+        Polynomial {
+            elem_of: ring,
+            terms,
+        }
+    }
+
+    /// The GCD of `self` and `other` over ℤ, with positive lexicographically
+    /// leading coefficient, computed modulo primes.
+    ///
+    /// After the integer contents are removed, the GCD modulo each prime is
+    /// computed by Brown's dense evaluation and interpolation in the
+    /// variables, with Zippel's sparse interpolation for all but the first
+    /// image at each level. It is scaled to the GCD `γ` of the leading
+    /// coefficients of the inputs, which is a multiple of the leading
+    /// coefficient of the GCD, and the images are combined by the Chinese
+    /// remainder theorem into the symmetric range. Primes dividing a leading
+    /// coefficient are skipped, and images with too large a leading monomial
+    /// come from unlucky primes and are discarded. Once the combination
+    /// stops changing, its primitive part is checked by trial division.
+    ///
+    /// In the unlikely event that this does not succeed within 1024 primes,
+    /// the GCD is computed by the subresultant remainder sequence for
+    /// univariate inputs, and over ℚ otherwise.
+    pub fn gcd_modular(&self, other: &Self) -> Self {
+        if self.terms.is_empty() {
+            return if other.terms.is_empty() {
+                other.clone()
+            } else {
+                other
+                    .primitive_integer()
+                    .scale_integer(&other.integer_content())
+            };
+        }
+        if other.terms.is_empty() {
+            return self
+                .primitive_integer()
+                .scale_integer(&self.integer_content());
+        }
+        let c = self.integer_content().gcd(&other.integer_content());
+        let f = self.primitive_integer();
+        let g = other.primitive_integer();
+        let (lf, lg) = (f.lex_leading_coeff(), g.lex_leading_coeff());
+        let gamma = lf.gcd(lg);
+        let n = self.elem_of.vars.len();
+        if n == 0 {
+            return f.scale_integer(&c);
+        }
+
+        let mut lead: Option<Monomial<P>> = None;
+        let mut modulus = BigInt::one();
+        let mut residues: HashMap<Monomial<P>, BigInt> = HashMap::new();
+        let mut previous: Option<HashMap<Monomial<P>, BigInt, TermHasher>> = None;
+        for p in word_primes().take(MAX_PRIMES) {
+            let prime = BigInt::from(p);
+            if (lf % &prime).is_zero() || (lg % &prime).is_zero() {
+                continue;
+            }
+            let field = PrimeField::new(p).unwrap();
+            let ring = PolynomialRing::new((0..n).collect(), &field);
+            let h = brown_gcd(&f.image(&ring), &g.image(&ring), n - 1);
+            let lm = h.lex_leading_term().unwrap().0.clone();
+            match lead.as_ref().map(|l| lm.powers.cmp(&l.powers)) {
+                Some(Ordering::Greater) => continue,
+                Some(Ordering::Equal) => {}
+                _ => {
+                    lead = Some(lm);
+                    modulus = BigInt::one();
+                    residues.clear();
+                    previous = None;
+                }
+            }
+            let gamma_p = field.from_integer(&gamma);
+            let inverse = modulus.mod_floor(&prime).modinv(&prime).unwrap();
+            let monomials: HashSet<_> = residues.keys().chain(h.terms.keys()).cloned().collect();
+            for m in monomials {
+                let s = h.terms.get(&m).map_or(0, |c| (*c * gamma_p).value());
+                let r = residues.entry(m).or_default();
+                let t = ((BigInt::from(s) - &*r) * &inverse).mod_floor(&prime);
+                *r += &modulus * t;
+            }
+            modulus *= prime;
+            let half = &modulus >> 1u32;
+            let symmetric: HashMap<_, _, TermHasher> = residues
+                .iter()
+                .filter(|(_, r)| !r.is_zero())
+                .map(|(m, r)| (m.clone(), if *r > half { r - &modulus } else { r.clone() }))
+                .collect();
+            if previous.as_ref() == Some(&symmetric) {
+                let h = Self {
+                    elem_of: self.elem_of,
+                    terms: symmetric.clone(),
+                }
+                .primitive_integer();
+                if f.div_exact_integer(&h).is_some() && g.div_exact_integer(&h).is_some() {
+                    return h.scale_integer(&c);
+                }
+            }
+            previous = Some(symmetric);
+        }
+        f.gcd_by_remainders(&g).scale_integer(&c)
+    }
+
+    /// The primitive GCD of the primitive polynomials `self` and `other`, by
+    /// the subresultant remainder sequence if at most one variable occurs,
+    /// and over ℚ otherwise.
+    fn gcd_by_remainders(&self, other: &Self) -> Self {
+        let n = self.elem_of.vars.len();
+        let occurs = |v: usize| {
+            self.terms
+                .keys()
+                .chain(other.terms.keys())
+                .any(|m| !m.powers[v].is_zero())
+        };
+        let vars: Vec<usize> = (0..n).filter(|&v| occurs(v)).collect();
+        if vars.len() <= 1 {
+            let v = vars.first().copied().unwrap_or(0);
+            let a = DenseUnivariate::from_polynomial(self, v).unwrap();
+            let b = DenseUnivariate::from_polynomial(other, v).unwrap();
+            let (a, b) = if a.degree() >= b.degree() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            let last = a.subresultant_prs(&b).1.pop().unwrap();
+            return last.to_polynomial(self.elem_of, v).primitive_integer();
+        }
+        let base = AlreadyRing::<BigRational> {
+            phantom: PhantomData,
+        };
+        let ring = PolynomialRing::new((0..n).collect::<Vec<_>>(), &base);
+        let rational = |f: &Self| Polynomial {
+            elem_of: &ring,
+            terms: f
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), BigRational::from_integer(c.clone())))
+                .collect(),
+        };
+        let h = rational(self).gcd(&rational(other));
+        let denominator = h
+            .terms
+            .values()
+            .fold(BigInt::one(), |d, c| d.lcm(c.denom()));
+        Self {
+            elem_of: self.elem_of,
+            terms: h
+                .terms
+                .into_iter()
+                .map(|(m, c)| {
+                    (
+                        m,
+                        (c * BigRational::from_integer(denominator.clone())).to_integer(),
+                    )
+                })
+                .collect(),
+        }
+        .primitive_integer()
+    }
+}
+
+impl DenseUnivariate<BigInt> {
+    /// The GCD of `self` and `other` over ℤ, with positive leading
+    /// coefficient, computed modulo primes as in
+    /// [`Polynomial::gcd_modular`], or by FLINT for large operands with the
+    /// `flint` feature.
+    pub fn gcd_modular(&self, other: &Self) -> Self {
+        #[cfg(feature = "flint")]
+        if crate::flint::delegates(self.coeffs().len().max(other.coeffs().len())) {
+            return self.flint_gcd(other);
+        }
+        let base = AlreadyRing::<BigInt> {
+            phantom: PhantomData,
+        };
+        let ring = PolynomialRing::new(vec![0], &base);
+        let f: Polynomial<'_, _, _, _, u32> = self.to_polynomial(&ring, 0);
+        let h = f.gcd_modular(&other.to_polynomial(&ring, 0));
+        DenseUnivariate::from_polynomial(&h, 0).unwrap()
+    }
+}
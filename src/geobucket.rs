@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::Vec as AllocVec;
+use num::{PrimInt, Unsigned};
+
+use crate::order::MonomialOrder;
+use crate::{Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring, SemiringElement};
+
+/// The number of terms allowed in bucket `i` is `BASE^(i + 1)`.
+const BASE: usize = 4;
+
+/// An accumulator for sums of many polynomials, after Yan's geobuckets.
+///
+/// Bucket `i` holds a polynomial with at most `4^(i + 1)` terms, sorted by a
+/// monomial order. A summand is merged into the bucket of its size, and a
+/// bucket that overflows is merged into the next one, so each term takes
+/// part in a logarithmic number of linear merges. Summing `k` polynomials
+/// of `n` terms costs about `k*n*log(k*n)` instead of the `k^2*n` of
+/// repeatedly adding to one growing polynomial.
+///
+/// The leading term of the sum, which division and reduction need after
+/// every step, is found among the leading terms of the buckets.
+///
+/// The buckets are allocated by `A`, the global allocator by default. Every
+/// merge allocates a new bucket and frees two, so a long reduction can
+/// instead allocate them in an [`Arena`](crate::arena::Arena) of its ring.
+pub struct Geobucket<'a, R, V, K, P, O, A: Allocator = Global> {
+    pub elem_of: &'a PolynomialRing<'a, R, V>,
+    order: O,
+    /// The terms of each bucket in increasing order, so that the leading
+    /// term is last.
+    buckets: Vec<AllocVec<(Monomial<P>, K), A>>,
+    alloc: A,
+}
+
+impl<'a, R, V, K, P, O> Geobucket<'a, R, V, K, P, O>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    /// The empty sum in `ring`, ordered by `order`.
+    pub fn new(ring: &'a PolynomialRing<'a, R, V>, order: O) -> Self {
+        Self::new_in(ring, order, Global)
+    }
+}
+
+impl<'a, R, V, K, P, O, A> Geobucket<'a, R, V, K, P, O, A>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+    A: Allocator + Clone,
+{
+    /// The empty sum in `ring`, ordered by `order`, with its buckets
+    /// allocated by `alloc`.
+    pub fn new_in(ring: &'a PolynomialRing<'a, R, V>, order: O, alloc: A) -> Self {
+        Self {
+            elem_of: ring,
+            order,
+            buckets: Vec::new(),
+            alloc,
+        }
+    }
+
+    /// The number of terms in the buckets, which may include several terms
+    /// with the same monomial.
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.iter().all(|bucket| bucket.is_empty())
+    }
+
+    /// Merges two lists of terms in increasing order.
+    fn merge(
+        &self,
+        a: impl ExactSizeIterator<Item = (Monomial<P>, K)>,
+        b: impl ExactSizeIterator<Item = (Monomial<P>, K)>,
+    ) -> AllocVec<(Monomial<P>, K), A> {
+        let mut merged = AllocVec::with_capacity_in(a.len() + b.len(), self.alloc.clone());
+        let mut a = a.peekable();
+        let mut b = b.peekable();
+        loop {
+            let ordering = match (a.peek(), b.peek()) {
+                (Some((m, _)), Some((n, _))) => self.order.cmp(m, n),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => merged.push(a.next().unwrap()),
+                Ordering::Greater => merged.push(b.next().unwrap()),
+                Ordering::Equal => {
+                    let (m, mut c) = a.next().unwrap();
+                    let (_, d) = b.next().unwrap();
+                    if !self.elem_of.base.add_into(&mut c, d) {
+                        merged.push((m, c));
+                    }
+                }
+            }
+        }
+        merged
+    }
+
+    /// Adds the sum of `terms`, which must be nonzero and in decreasing
+    /// order with distinct monomials, as returned by
+    /// [`Polynomial::mul_ordered`] for the same order.
+    pub fn add_terms(&mut self, terms: Vec<(Monomial<P>, K)>) {
+        let mut i = 0;
+        while BASE.pow(i as u32 + 1) < terms.len() {
+            i += 1;
+        }
+        let bucket = self.take_bucket(i);
+        let mut merged = self.merge(bucket.into_iter(), terms.into_iter().rev());
+        while merged.len() > BASE.pow(i as u32 + 1) {
+            i += 1;
+            let bucket = self.take_bucket(i);
+            merged = self.merge(bucket.into_iter(), merged.into_iter());
+        }
+        self.buckets[i] = merged;
+    }
+
+    /// Empties bucket `i`, creating it if needed, and returns its terms.
+    fn take_bucket(&mut self, i: usize) -> AllocVec<(Monomial<P>, K), A> {
+        if self.buckets.len() <= i {
+            self.buckets
+                .resize_with(i + 1, || AllocVec::new_in(self.alloc.clone()));
+        }
+        std::mem::replace(&mut self.buckets[i], AllocVec::new_in(self.alloc.clone()))
+    }
+
+    /// Adds `f` to the sum.
+    pub fn add(&mut self, f: &Polynomial<'_, R, V, K, P>) {
+        let terms = f
+            .sorted_terms(&self.order)
+            .into_iter()
+            .map(|(m, c)| (m.clone(), c.clone()))
+            .collect();
+        self.add_terms(terms);
+    }
+
+    /// Removes and returns the leading term of the sum, or `None` if the sum
+    /// is zero.
+    pub fn pop_leading(&mut self) -> Option<(Monomial<P>, K)> {
+        loop {
+            let mut lead: Option<usize> = None;
+            for (i, bucket) in self.buckets.iter().enumerate() {
+                if let Some((m, _)) = bucket.last()
+                    && lead.is_none_or(|j| {
+                        let n = &self.buckets[j].last().unwrap().0;
+                        self.order.cmp(m, n) == Ordering::Greater
+                    })
+                {
+                    lead = Some(i);
+                }
+            }
+            let (m, mut c) = self.buckets[lead?].pop().unwrap();
+            for bucket in self.buckets.iter_mut() {
+                if bucket.last().is_some_and(|(n, _)| *n == m) {
+                    let (_, d) = bucket.pop().unwrap();
+                    self.elem_of.base.add_into(&mut c, d);
+                }
+            }
+            if !self.elem_of.base.is_zero(&c) {
+                return Some((m, c));
+            }
+        }
+    }
+
+    /// The terms of the sum in decreasing order, moved out of the buckets
+    /// into memory of the global allocator.
+    pub fn into_terms(mut self) -> Vec<(Monomial<P>, K)> {
+        let mut terms = Vec::with_capacity(self.len());
+        while let Some(term) = self.pop_leading() {
+            terms.push(term);
+        }
+        terms
+    }
+
+    /// The sum, as a polynomial.
+    pub fn into_polynomial(self) -> Polynomial<'a, R, V, K, P> {
+        let terms = self.buckets.into_iter().flatten().collect::<Vec<_>>();
+        let mut sum = Polynomial {
+            elem_of: self.elem_of,
+            terms: HashMap::with_capacity_and_hasher(terms.len(), Default::default()),
+        };
+        for (m, c) in terms {
+            match sum.terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if self.elem_of.base.add_into(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(c);
+                }
+            }
+        }
+        sum
+    }
+}
+
+impl<R, V, K, P, O, A> Geobucket<'_, R, V, K, P, O, A>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+    A: Allocator + Clone,
+{
+    /// Subtracts the sum of `terms`, under the same conditions as
+    /// [`Geobucket::add_terms`].
+    pub fn sub_terms(&mut self, terms: Vec<(Monomial<P>, K)>) {
+        self.add_terms(terms.into_iter().map(|(m, c)| (m, K::zero() - c)).collect());
+    }
+}
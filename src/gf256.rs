@@ -0,0 +1,147 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{One, Zero};
+
+use crate::{Field, FieldElement, Ring, RingElement, Semiring, SemiringElement};
+
+/// The low byte of the modulus `x^8 + x^4 + x^3 + x^2 + 1` of [`Gf256`].
+const MODULUS: u8 = 0x1d;
+
+/// An element of the field `GF(2^8) = GF(2)[x]/(x^8 + x^4 + x^3 + x^2 + 1)`,
+/// as the byte whose bit `i` is the coefficient of `x^i`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf256(pub u8);
+
+/// The field with 256 elements, whose elements are values of type
+/// [`Gf256`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gf256Field;
+
+impl Semiring<Gf256> for Gf256Field {}
+impl Ring<Gf256> for Gf256Field {}
+impl Field<Gf256> for Gf256Field {}
+impl SemiringElement for Gf256 {}
+impl RingElement for Gf256 {}
+impl FieldElement for Gf256 {}
+
+impl Gf256 {
+    /// The multiplicative inverse of `self`, or `None` if it is zero.
+    pub fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // The multiplicative group has order 255, so a^-1 = a^254.
+        let mut result = Self::one();
+        let mut base = self;
+        let mut e = 254u8;
+        while e > 0 {
+            if e & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            e >>= 1;
+        }
+        Some(result)
+    }
+}
+
+impl Zero for Gf256 {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Gf256 {
+    fn one() -> Self {
+        Self(1)
+    }
+}
+
+impl Add for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Sub for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Mul for Gf256 {
+    type Output = Self;
+
+    /// Multiplies by shifting and adding, reducing by the modulus whenever
+    /// `x^8` appears.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (mut a, mut b, mut product) = (self.0, rhs.0, 0);
+        while b != 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+            let carry = a & 0x80 != 0;
+            a <<= 1;
+            if carry {
+                a ^= MODULUS;
+            }
+            b >>= 1;
+        }
+        Self(product)
+    }
+}
+
+impl Div for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inv().expect("division by zero")
+    }
+}
+
+impl Neg for Gf256 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl AddAssign for Gf256 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Gf256 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Gf256 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Display for Gf256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,604 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::iter::zip;
+use std::thread;
+
+use num::{PrimInt, Unsigned};
+
+use crate::control::{ComputationControl, Progress};
+use crate::division::Divisors;
+use crate::error::ChidogError;
+use crate::hilbert::HilbertSeries;
+use crate::order::MonomialOrder;
+use crate::{Field, FieldElement, Monomial, Polynomial};
+
+/// A critical pair of basis elements `i < j`, with the least common multiple
+/// of their leading monomials.
+struct Pair<P> {
+    i: usize,
+    j: usize,
+    lcm: Monomial<P>,
+}
+
+/// The state of Buchberger's algorithm: a basis of monic polynomials with
+/// their leading monomials, and the critical pairs still to be reduced.
+struct Buchberger<'a, 'o, R, V, K, P, O>
+where
+    P: Hash,
+{
+    order: &'o O,
+    basis: Vec<Polynomial<'a, R, V, K, P>>,
+    leads: Vec<Monomial<P>>,
+    /// Pending pairs, sorted so that the one with the least `lcm` is last.
+    pairs: Vec<Pair<P>>,
+    pending: HashSet<(usize, usize)>,
+}
+
+impl<'a, 'o, R, V, K, P, O> Buchberger<'a, 'o, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    fn new(generators: &[Polynomial<'a, R, V, K, P>], order: &'o O) -> Self {
+        let mut state = Self {
+            order,
+            basis: Vec::new(),
+            leads: Vec::new(),
+            pairs: Vec::new(),
+            pending: HashSet::new(),
+        };
+        for f in generators {
+            state.insert(f);
+        }
+        state
+    }
+
+    /// Adds `f`, divided by its leading coefficient, to the basis along with
+    /// its pairs with the previous elements. Zero is ignored.
+    fn insert(&mut self, f: &Polynomial<'a, R, V, K, P>) {
+        let Some((m, c)) = f.sorted_terms(self.order).first().copied() else {
+            return;
+        };
+        let (m, c) = (m.clone(), c.clone());
+        let terms = f
+            .terms
+            .iter()
+            .map(|(n, d)| (n.clone(), d.clone() / c.clone()))
+            .collect();
+        let j = self.basis.len();
+        for (i, lead) in self.leads.iter().enumerate() {
+            let lcm = lead.lcm(&m);
+            self.pairs.push(Pair { i, j, lcm });
+            self.pending.insert((i, j));
+        }
+        self.basis.push(Polynomial {
+            elem_of: f.elem_of,
+            terms,
+        });
+        self.leads.push(m);
+        let order = self.order;
+        self.pairs.sort_by(|a, b| order.cmp(&b.lcm, &a.lcm));
+    }
+
+    /// Removes up to `count` pairs with the least `lcm` (the normal
+    /// strategy), skipping pairs whose S-polynomials are known to reduce to
+    /// zero by Buchberger's criteria: the leading monomials are coprime, or
+    /// the leading monomial of a third element `k` divides their `lcm` and
+    /// the pairs with `k` have been treated.
+    fn select(&mut self, count: usize) -> Vec<(usize, usize)> {
+        let mut batch = Vec::new();
+        while batch.len() < count
+            && let Some(Pair { i, j, lcm }) = self.pairs.pop()
+        {
+            self.pending.remove(&(i, j));
+            if !self.useless(i, j, &lcm) {
+                batch.push((i, j));
+            }
+        }
+        batch
+    }
+
+    /// Whether the S-polynomial of the pair `i < j` with `lcm` is known to
+    /// reduce to zero by Buchberger's criteria.
+    fn useless(&self, i: usize, j: usize, lcm: &Monomial<P>) -> bool {
+        let coprime = zip(&self.leads[i].powers, &self.leads[j].powers)
+            .all(|(p, q)| p.is_zero() || q.is_zero());
+        let chain = (0..self.leads.len()).any(|k| {
+            k != i
+                && k != j
+                && self.leads[k].divides(lcm)
+                && !self.pending.contains(&(i.min(k), i.max(k)))
+                && !self.pending.contains(&(j.min(k), j.max(k)))
+        });
+        coprime || chain
+    }
+
+    /// `m*f` for a monomial `m`.
+    fn shifted(f: &Polynomial<'a, R, V, K, P>, m: &[P]) -> Polynomial<'a, R, V, K, P> {
+        let terms = f
+            .terms
+            .iter()
+            .map(|(n, c)| {
+                let powers = zip(m, &n.powers).map(|(p, q)| *p + *q).collect();
+                (Monomial { powers }, c.clone())
+            })
+            .collect();
+        Polynomial {
+            elem_of: f.elem_of,
+            terms,
+        }
+    }
+
+    /// The S-polynomial of the basis elements `i` and `j`, in which their
+    /// (monic) leading terms cancel.
+    fn s_polynomial(&self, i: usize, j: usize) -> Polynomial<'a, R, V, K, P> {
+        let lcm = self.leads[i].lcm(&self.leads[j]);
+        let cofactor = |k: usize| lcm.checked_div(&self.leads[k]).unwrap().powers;
+        Self::shifted(&self.basis[i], &cofactor(i)) - Self::shifted(&self.basis[j], &cofactor(j))
+    }
+
+    /// Runs the algorithm to completion, taking `batch` pairs at a time and
+    /// computing the remainders of their S-polynomials with `reduce`.
+    ///
+    /// The remainders of a batch are computed against the same basis, so
+    /// each nonzero one is reduced again by the elements added before it
+    /// from the same batch. `control` is consulted before each batch.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "buchberger",
+            skip_all,
+            fields(batch = batch, generators = self.basis.len())
+        )
+    )]
+    fn run(
+        &mut self,
+        batch: usize,
+        reduce: impl Fn(
+            &Self,
+            &[(usize, usize)],
+            &ComputationControl,
+        ) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>,
+        control: &ComputationControl,
+    ) -> Result<(), ChidogError> {
+        let mut steps = 0;
+        #[cfg(feature = "tracing")]
+        let mut zero = 0;
+        loop {
+            control.check(&Progress {
+                algorithm: "buchberger",
+                steps,
+                remaining: Some(self.pairs.len()),
+                terms: self.basis.iter().map(|g| g.terms.len()).sum(),
+            })?;
+            let pairs = self.select(batch);
+            if pairs.is_empty() {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(pairs = steps, zero, basis = self.basis.len(), "done");
+                return Ok(());
+            }
+            steps += pairs.len();
+            let size = self.basis.len();
+            for h in reduce(self, &pairs, control)? {
+                if h.terms.is_empty() {
+                    #[cfg(feature = "tracing")]
+                    {
+                        zero += 1;
+                    }
+                    continue;
+                }
+                let h = if self.basis.len() > size {
+                    h.rem(&self.basis[size..], self.order)
+                } else {
+                    h
+                };
+                self.insert(&h);
+            }
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                pairs = steps,
+                zero,
+                basis = self.basis.len(),
+                pending = self.pairs.len(),
+                "batch"
+            );
+        }
+    }
+
+    /// Runs the algorithm to completion on a homogeneous basis, degree by
+    /// degree, knowing that the quotient by the ideal has the Hilbert series
+    /// `hilbert`. Once the leading monomials found in a degree have the
+    /// dimension the series predicts there, the other pairs of that degree
+    /// can only reduce to zero, and are dropped. Fails if the series is not
+    /// that of the ideal.
+    fn run_hilbert(&mut self, hilbert: &HilbertSeries) -> Result<(), ChidogError> {
+        let n = hilbert.vars();
+        let degree = |m: &Monomial<P>| m.powers.iter().map(|p| p.to_usize().unwrap()).sum();
+        while let Some(d) = self.pairs.iter().map(|pair| degree(&pair.lcm)).min() {
+            let target = hilbert.function(d);
+            let mut current = HilbertSeries::of_monomial_ideal(&self.leads, n).function(d);
+            while let Some(at) = self.pairs.iter().rposition(|pair| degree(&pair.lcm) == d) {
+                if current < target {
+                    return Err(ChidogError::InvalidInput(format!(
+                        "the Hilbert function exceeds that of the ideal in degree {d}"
+                    )));
+                }
+                let Pair { i, j, lcm } = self.pairs.remove(at);
+                self.pending.remove(&(i, j));
+                if current == target || self.useless(i, j, &lcm) {
+                    continue;
+                }
+                let h = self.s_polynomial(i, j).rem(&self.basis, self.order);
+                if !h.terms.is_empty() {
+                    self.insert(&h);
+                    current = HilbertSeries::of_monomial_ideal(&self.leads, n).function(d);
+                }
+            }
+            if current != target {
+                return Err(ChidogError::InvalidInput(format!(
+                    "the Hilbert function is not that of the ideal in degree {d}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the algorithm on the pairs whose `lcm` has total degree at most
+    /// `degree`, dropping the others.
+    fn run_up_to_degree(&mut self, degree: usize) {
+        let total =
+            |m: &Monomial<P>| -> usize { m.powers.iter().map(|p| p.to_usize().unwrap()).sum() };
+        while let Some(Pair { i, j, lcm }) = self.pairs.pop() {
+            self.pending.remove(&(i, j));
+            if total(&lcm) > degree || self.useless(i, j, &lcm) {
+                continue;
+            }
+            let h = self.s_polynomial(i, j).rem(&self.basis, self.order);
+            self.insert(&h);
+        }
+    }
+
+    /// The remainders of the S-polynomials of `pairs`, one after another,
+    /// failing if `control` is cancelled or runs out of time meanwhile.
+    fn reduce_sequential(
+        &self,
+        pairs: &[(usize, usize)],
+        control: &ComputationControl,
+    ) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError> {
+        let divisors = Divisors::new(&self.basis, self.order);
+        pairs
+            .iter()
+            .map(|&(i, j)| {
+                self.s_polynomial(i, j)
+                    .reduce_controlled(&divisors, self.order, control)
+            })
+            .collect()
+    }
+
+    /// The reduced Gröbner basis spanned by the current basis: elements whose
+    /// leading monomial is divisible by that of another are dropped, and
+    /// every tail is reduced by the others. The result is sorted by
+    /// decreasing leading monomial.
+    fn into_reduced(self) -> Vec<Polynomial<'a, R, V, K, P>> {
+        let order = self.order;
+        let keep: Vec<usize> = (0..self.basis.len())
+            .filter(|&i| {
+                !(0..self.basis.len()).any(|k| {
+                    k != i
+                        && self.leads[k].divides(&self.leads[i])
+                        && (self.leads[k] != self.leads[i] || k < i)
+                })
+            })
+            .collect();
+        let mut basis: Vec<_> = keep.iter().map(|&i| self.basis[i].clone()).collect();
+        let leads: Vec<_> = keep.iter().map(|&i| self.leads[i].clone()).collect();
+        for (i, lead) in leads.iter().enumerate() {
+            let mut g = basis.remove(i);
+            let (lead, c) = g.terms.remove_entry(lead).unwrap();
+            let mut reduced = g.rem(&basis, order);
+            reduced.terms.insert(lead, c);
+            basis.insert(i, reduced);
+        }
+        let mut sorted: Vec<_> = zip(leads, basis).collect();
+        sorted.sort_by(|(a, _), (b, _)| order.cmp(b, a));
+        sorted.into_iter().map(|(_, g)| g).collect()
+    }
+}
+
+impl<'a, R, V, K, P, O> Buchberger<'a, '_, R, V, K, P, O>
+where
+    R: Field<K> + Sync,
+    V: Eq + Sync,
+    K: FieldElement + Clone + Send + Sync,
+    P: Hash + PrimInt + Unsigned + Send + Sync,
+    O: MonomialOrder + Clone + Sync,
+{
+    /// The remainders of the S-polynomials of `pairs`, each on its own
+    /// thread. The threads share the basis by reference, which is not
+    /// modified until all of them have finished, and share `control`, which
+    /// each checks as it reduces, so that all of them stop once it is
+    /// cancelled or runs out of time.
+    fn reduce_parallel(
+        &self,
+        pairs: &[(usize, usize)],
+        control: &ComputationControl,
+    ) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError> {
+        let divisors = Divisors::new(&self.basis, self.order);
+        thread::scope(|scope| {
+            let workers: Vec<_> = pairs
+                .iter()
+                .map(|&(i, j)| {
+                    let divisors = &divisors;
+                    scope.spawn(move || {
+                        self.s_polynomial(i, j)
+                            .reduce_controlled(divisors, self.order, control)
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("S-pair reduction panicked"))
+                .collect()
+        })
+    }
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators` in
+/// `order`, by Buchberger's algorithm.
+///
+/// The basis consists of monic polynomials, sorted by decreasing leading
+/// monomial; it is empty for the zero ideal and `[1]` for the whole ring.
+pub fn groebner_basis<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    groebner_basis_controlled(generators, order, &ComputationControl::new())
+        .expect("uncontrolled computations are not interrupted")
+}
+
+/// The reduced Gröbner basis of [`groebner_basis`], consulting `control`
+/// before each S-pair, with the number of pairs reduced and pending and the
+/// number of terms of the basis, and failing as it directs. The reduction of
+/// each S-pair also stops once `control` is cancelled or runs out of time.
+pub fn groebner_basis_controlled<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    control: &ComputationControl,
+) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let mut state = Buchberger::new(generators, order);
+    state.run(1, Buchberger::reduce_sequential, control)?;
+    Ok(state.into_reduced())
+}
+
+/// The reduced Gröbner basis of the ideal generated by `generators` in
+/// `order`, truncated at degree `degree`: Buchberger's algorithm restricted
+/// to the generators and S-pairs of total degree at most `degree`.
+///
+/// For homogeneous generators, the leading monomial of every element of the
+/// ideal of degree at most `degree` is divisible by that of an element of
+/// the result, so the result agrees with the full basis up to that degree,
+/// as does the Hilbert function of its leading ideal. For other generators
+/// the result is only part of a Gröbner basis, with no such guarantee.
+pub fn groebner_basis_up_to_degree<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    degree: usize,
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let low: Vec<_> = generators
+        .iter()
+        .filter(|f| {
+            f.terms.keys().all(|m| {
+                m.powers
+                    .iter()
+                    .map(|p| p.to_usize().unwrap())
+                    .sum::<usize>()
+                    <= degree
+            })
+        })
+        .cloned()
+        .collect();
+    let mut state = Buchberger::new(&low, order);
+    state.run_up_to_degree(degree);
+    state.into_reduced()
+}
+
+/// The reduced Gröbner basis of [`groebner_basis`] for homogeneous
+/// `generators`, using the known Hilbert series `hilbert` of the quotient by
+/// the ideal to skip S-pairs, or an `InvalidInput` error if the generators
+/// are not homogeneous or the series is not that of the ideal.
+///
+/// The pairs are treated by increasing degree. In each degree, the Hilbert
+/// function gives the number of monomials outside the leading ideal, so once
+/// the leading monomials found reach it, the remaining pairs of that degree
+/// are dropped without reduction. The series can come from a Gröbner basis
+/// of the same ideal in another order, typically an easier graded one, by
+/// [`HilbertSeries::of_leading_ideal`], or from a computation modulo a
+/// prime.
+pub fn groebner_basis_hilbert_driven<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    hilbert: &HilbertSeries,
+) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    for f in generators {
+        if f.elem_of.vars.len() != hilbert.vars() {
+            return Err(ChidogError::InvalidInput(format!(
+                "the Hilbert series is for {} variables, not {}",
+                hilbert.vars(),
+                f.elem_of.vars.len()
+            )));
+        }
+        let mut degrees = f
+            .terms
+            .keys()
+            .map(|m| m.powers.iter().fold(P::zero(), |d, p| d + *p));
+        let first = degrees.next();
+        if degrees.any(|d| Some(d) != first) {
+            return Err(ChidogError::InvalidInput(
+                "the generators are not homogeneous".to_string(),
+            ));
+        }
+    }
+    let mut state = Buchberger::new(generators, order);
+    state.run_hilbert(hilbert)?;
+    Ok(state.into_reduced())
+}
+
+/// The reduced Gröbner basis of [`groebner_basis`], reducing up to `threads`
+/// S-pairs concurrently.
+///
+/// The pairs are taken in rounds of the `threads` pairs with the least
+/// `lcm`, whose S-polynomials are reduced in parallel against the basis
+/// as it stood at the start of the round; the new elements are then added in
+/// order. A round may do work that the sequential algorithm would skip, when
+/// one of its pairs would have been reduced to zero by an element found in
+/// the same round, so this pays off once reductions are expensive. The
+/// result does not depend on the number of threads.
+pub fn groebner_basis_parallel<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    threads: usize,
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Field<K> + Sync,
+    V: Eq + Sync,
+    K: FieldElement + Clone + Send + Sync,
+    P: Hash + PrimInt + Unsigned + Send + Sync,
+    O: MonomialOrder + Clone + Sync,
+{
+    groebner_basis_parallel_controlled(generators, order, threads, &ComputationControl::new())
+        .expect("uncontrolled computations are not interrupted")
+}
+
+/// The reduced Gröbner basis of [`groebner_basis_parallel`], consulting
+/// `control` before each round of S-pairs as [`groebner_basis_controlled`]
+/// does before each pair, and failing as it directs. The threads of a round
+/// share `control` and check it as they reduce, so a round stops once it is
+/// cancelled or runs out of time, without waiting for its slowest pair.
+pub fn groebner_basis_parallel_controlled<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    threads: usize,
+    control: &ComputationControl,
+) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+where
+    R: Field<K> + Sync,
+    V: Eq + Sync,
+    K: FieldElement + Clone + Send + Sync,
+    P: Hash + PrimInt + Unsigned + Send + Sync,
+    O: MonomialOrder + Clone + Sync,
+{
+    let mut state = Buchberger::new(generators, order);
+    match threads {
+        0 | 1 => state.run(1, Buchberger::reduce_sequential, control)?,
+        _ => state.run(threads, Buchberger::reduce_parallel, control)?,
+    }
+    Ok(state.into_reduced())
+}
+
+/// Whether `basis` is a Gröbner basis in `order` of the ideal it generates,
+/// that is, whether every S-polynomial of two of its elements reduces to zero.
+pub fn is_groebner_basis<R, V, K, P, O>(basis: &[Polynomial<'_, R, V, K, P>], order: &O) -> bool
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let state = Buchberger::new(basis, order);
+    let divisors = Divisors::new(&state.basis, order);
+    state.pairs.iter().all(|Pair { i, j, .. }| {
+        state
+            .s_polynomial(*i, *j)
+            .reduce(&divisors, order, None)
+            .terms
+            .is_empty()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use num::BigRational;
+
+    use super::*;
+    use crate::order::GRevLex;
+    use crate::{AlreadyRing, PolynomialRing};
+
+    type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+    #[test]
+    fn parallel_controlled_matches_sequential() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(vec!["x".into(), "y".into(), "z".into()], &base);
+        let generators: Vec<_> = ["x + y + z", "x*y + y*z + z*x", "x*y*z - 1"]
+            .iter()
+            .map(|f| Poly::from_sympy(&ring, f).unwrap())
+            .collect();
+        let direct = groebner_basis(&generators, &GRevLex);
+        for threads in [1, 4] {
+            let basis = groebner_basis_parallel_controlled(
+                &generators,
+                &GRevLex,
+                threads,
+                &ComputationControl::new(),
+            )
+            .unwrap();
+            assert_eq!(basis.len(), direct.len());
+            for (f, g) in basis.iter().zip(&direct) {
+                assert_eq!(f.terms, g.terms);
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_controlled_stops_when_cancelled() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(vec!["x".into(), "y".into()], &base);
+        let generators: Vec<_> = ["x^2 - y", "y^2 - x"]
+            .iter()
+            .map(|f| Poly::from_sympy(&ring, f).unwrap())
+            .collect();
+        let control = ComputationControl::new();
+        control.cancel();
+        let result = groebner_basis_parallel_controlled(&generators, &GRevLex, 4, &control);
+        assert!(matches!(result, Err(ChidogError::Cancelled)));
+    }
+}
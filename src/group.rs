@@ -0,0 +1,614 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use num::{One, Zero};
+
+use crate::error::ChidogError;
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// A finite group, given by its multiplication table on the elements `0..n`.
+///
+/// Each element carries a name for display: `e` and `g1, g2, ...` for groups
+/// given by a table, and cycle notation for permutation groups.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiniteGroup {
+    table: Vec<Vec<usize>>,
+    identity: usize,
+    inverses: Vec<usize>,
+    names: Vec<String>,
+}
+
+impl FiniteGroup {
+    /// The group with multiplication `a*b = table[a][b]`, which must satisfy
+    /// the group axioms.
+    pub fn from_table(table: Vec<Vec<usize>>) -> Result<Self, ChidogError> {
+        let n = table.len();
+        let invalid = |msg: &str| Err(ChidogError::InvalidInput(msg.to_string()));
+        if n == 0 {
+            return invalid("a group has at least one element");
+        }
+        if table
+            .iter()
+            .any(|row| row.len() != n || row.iter().any(|&c| c >= n))
+        {
+            return invalid("the multiplication table is not square");
+        }
+        let Some(identity) = (0..n).find(|&e| (0..n).all(|a| table[e][a] == a && table[a][e] == a))
+        else {
+            return invalid("the multiplication table has no identity");
+        };
+        let mut inverses = Vec::with_capacity(n);
+        for (a, row) in table.iter().enumerate() {
+            match (0..n).find(|&b| row[b] == identity && table[b][a] == identity) {
+                Some(b) => inverses.push(b),
+                None => return invalid("some element has no inverse"),
+            }
+        }
+        for a in 0..n {
+            for b in 0..n {
+                for c in 0..n {
+                    if table[table[a][b]][c] != table[a][table[b][c]] {
+                        return invalid("the multiplication is not associative");
+                    }
+                }
+            }
+        }
+        let mut next = 1..;
+        let names = (0..n)
+            .map(|a| {
+                if a == identity {
+                    "e".to_string()
+                } else {
+                    format!("g{}", next.next().unwrap())
+                }
+            })
+            .collect();
+        Ok(Self {
+            table,
+            identity,
+            inverses,
+            names,
+        })
+    }
+
+    /// The group of permutations of `0..d` generated by `gens`, each given
+    /// by its list of images. The product `p*q` is the composition which
+    /// applies `q` first, so that `(p*q)[i] = p[q[i]]`.
+    pub fn from_permutations(d: usize, gens: &[Vec<usize>]) -> Result<Self, ChidogError> {
+        for g in gens {
+            let mut seen = vec![false; d];
+            if g.len() != d
+                || g.iter()
+                    .any(|&i| i >= d || std::mem::replace(&mut seen[i], true))
+            {
+                return Err(ChidogError::InvalidInput(format!(
+                    "{g:?} is not a permutation of 0..{d}"
+                )));
+            }
+        }
+        let compose = |p: &[usize], q: &[usize]| q.iter().map(|&i| p[i]).collect::<Vec<_>>();
+        let mut elements = vec![(0..d).collect::<Vec<_>>()];
+        let mut index = HashMap::from([(elements[0].clone(), 0)]);
+        let mut i = 0;
+        while i < elements.len() {
+            for g in gens {
+                let p = compose(g, &elements[i]);
+                if let Entry::Vacant(entry) = index.entry(p.clone()) {
+                    entry.insert(elements.len());
+                    elements.push(p);
+                }
+            }
+            i += 1;
+        }
+        let table = elements
+            .iter()
+            .map(|p| elements.iter().map(|q| index[&compose(p, q)]).collect())
+            .collect();
+        let inverses = elements
+            .iter()
+            .map(|p| {
+                let mut inv = vec![0; d];
+                for (i, &j) in p.iter().enumerate() {
+                    inv[j] = i;
+                }
+                index[&inv]
+            })
+            .collect();
+        let names = elements.iter().map(|p| cycle_notation(p)).collect();
+        Ok(Self {
+            table,
+            identity: 0,
+            inverses,
+            names,
+        })
+    }
+
+    /// The cyclic group of order `n`, with `a*b = a+b mod n`.
+    pub fn cyclic(n: usize) -> Self {
+        let table = (0..n)
+            .map(|a| (0..n).map(|b| (a + b) % n).collect())
+            .collect();
+        Self::from_table(table).unwrap()
+    }
+
+    pub fn order(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn identity(&self) -> usize {
+        self.identity
+    }
+
+    pub fn mul(&self, a: usize, b: usize) -> usize {
+        self.table[a][b]
+    }
+
+    pub fn inv(&self, a: usize) -> usize {
+        self.inverses[a]
+    }
+
+    pub fn name(&self, a: usize) -> &str {
+        &self.names[a]
+    }
+
+    pub fn is_abelian(&self) -> bool {
+        let n = self.order();
+        (0..n).all(|a| (0..a).all(|b| self.table[a][b] == self.table[b][a]))
+    }
+}
+
+/// The permutation `p` as a product of disjoint cycles, as in `(0 1 2)(3 4)`,
+/// or `e` for the identity.
+fn cycle_notation(p: &[usize]) -> String {
+    let mut seen = vec![false; p.len()];
+    let mut out = String::new();
+    for start in 0..p.len() {
+        if seen[start] || p[start] == start {
+            continue;
+        }
+        let mut cycle = vec![start.to_string()];
+        seen[start] = true;
+        let mut i = p[start];
+        while i != start {
+            cycle.push(i.to_string());
+            seen[i] = true;
+            i = p[i];
+        }
+        out += &format!("({})", cycle.join(" "));
+    }
+    if out.is_empty() {
+        out.push('e');
+    }
+    out
+}
+
+/// A variable `my_ring: GroupAlgebra<R>` represents the group algebra `R[G]`
+/// of a finite group `G` over a base ring `R`. Its elements are of type
+/// `GroupAlgebraElement<'_, R, K>`, with coefficients valued in `K`.
+///
+/// As for polynomials, an element is a formal linear combination of basis
+/// elements, here the group elements, with multiplication extended bilinearly
+/// from the group law. The algebra is commutative exactly when `G` is abelian;
+/// since this is not known statically, it sets [`Semiring::COMMUTATIVE`] to
+/// `false`.
+pub struct GroupAlgebra<'a, R> {
+    pub group: FiniteGroup,
+    pub base: &'a R,
+}
+
+/// An element `∑ c_g*g` of a group algebra, as a hash map associating to each
+/// group element a nonzero coefficient.
+///
+/// Since `Zero::zero` and `One::one` are called without access to an
+/// algebra, constants in `K` may also be stored detached from any algebra. A
+/// detached constant `c` is attached as `c*e` to the algebra of the other
+/// operand when combined with an element of an algebra.
+pub struct GroupAlgebraElement<'a, R, K> {
+    repr: Repr<'a, R, K>,
+}
+
+enum Repr<'a, R, K> {
+    Constant(K),
+    Terms {
+        elem_of: &'a GroupAlgebra<'a, R>,
+        terms: HashMap<usize, K>,
+    },
+}
+
+impl<R, K: Clone> Clone for GroupAlgebraElement<'_, R, K> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Constant(c) => Repr::Constant(c.clone()),
+            Repr::Terms { elem_of, terms } => Repr::Terms {
+                elem_of,
+                terms: terms.clone(),
+            },
+        };
+        Self { repr }
+    }
+}
+
+impl<'a, R> GroupAlgebra<'a, R> {
+    pub fn new(group: FiniteGroup, base: &'a R) -> Self {
+        Self { group, base }
+    }
+
+    pub fn zero<K>(&'a self) -> GroupAlgebraElement<'a, R, K> {
+        self.with_terms(HashMap::new())
+    }
+
+    fn with_terms<K>(&'a self, terms: HashMap<usize, K>) -> GroupAlgebraElement<'a, R, K> {
+        GroupAlgebraElement {
+            repr: Repr::Terms {
+                elem_of: self,
+                terms,
+            },
+        }
+    }
+
+    /// The element `c*g`.
+    pub fn term<K>(&'a self, g: usize, c: K) -> GroupAlgebraElement<'a, R, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        assert!(g < self.group.order(), "group element out of range");
+        let mut terms = HashMap::new();
+        if !self.base.is_zero(&c) {
+            terms.insert(g, c);
+        }
+        self.with_terms(terms)
+    }
+
+    /// The basis element `g`.
+    pub fn basis<K>(&'a self, g: usize) -> GroupAlgebraElement<'a, R, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        self.term(g, K::one())
+    }
+
+    pub fn constant<K>(&'a self, c: K) -> GroupAlgebraElement<'a, R, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        self.term(self.group.identity(), c)
+    }
+
+    pub fn one<K>(&'a self) -> GroupAlgebraElement<'a, R, K>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        self.constant(K::one())
+    }
+
+    /// The terms of `x` in this algebra, as pairs of a group element and its
+    /// nonzero coefficient, in the order of the group elements.
+    pub fn terms<K>(&'a self, x: &GroupAlgebraElement<'a, R, K>) -> Vec<(usize, K)>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+    {
+        let mut terms: Vec<_> = x.clone().into_terms(self).into_iter().collect();
+        terms.sort_by_key(|(g, _)| *g);
+        terms
+    }
+
+    /// The coefficient of the group element `g` in `x`.
+    pub fn coeff<K>(&'a self, x: &GroupAlgebraElement<'a, R, K>, g: usize) -> K
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+    {
+        match &x.repr {
+            Repr::Constant(c) if g == self.group.identity() => c.clone(),
+            Repr::Constant(_) => K::zero(),
+            Repr::Terms { terms, .. } => terms.get(&g).cloned().unwrap_or_else(K::zero),
+        }
+    }
+}
+
+/// Adds `c*g` to `terms`, removing the term if it cancels in `base`.
+fn add_term<R, K>(base: &R, terms: &mut HashMap<usize, K>, g: usize, c: K)
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+{
+    match terms.entry(g) {
+        Entry::Occupied(mut entry) => {
+            if base.add_into(entry.get_mut(), c) {
+                entry.remove();
+            }
+        }
+        Entry::Vacant(entry) => {
+            if !base.is_zero(&c) {
+                entry.insert(c);
+            }
+        }
+    }
+}
+
+impl<'a, R, K> GroupAlgebraElement<'a, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    /// The algebra of `self` and `other`, unless both are detached
+    /// constants.
+    fn algebra_of(&self, other: &Self) -> Option<&'a GroupAlgebra<'a, R>> {
+        match (&self.repr, &other.repr) {
+            (Repr::Terms { elem_of, .. }, _) | (_, Repr::Terms { elem_of, .. }) => Some(elem_of),
+            _ => None,
+        }
+    }
+
+    /// The terms of `self` in `algebra`.
+    fn into_terms(self, algebra: &'a GroupAlgebra<'a, R>) -> HashMap<usize, K> {
+        match self.repr {
+            Repr::Constant(c) => algebra.constant(c).into_terms(algebra),
+            Repr::Terms { terms, .. } => terms,
+        }
+    }
+
+    /// The value of `self`, if it is a constant.
+    pub fn as_constant(&self) -> Option<K> {
+        match &self.repr {
+            Repr::Constant(c) => Some(c.clone()),
+            Repr::Terms { elem_of, terms } => match terms.iter().next() {
+                None => Some(K::zero()),
+                Some((g, c)) if terms.len() == 1 && *g == elem_of.group.identity() => {
+                    Some(c.clone())
+                }
+                Some(_) => None,
+            },
+        }
+    }
+
+    /// The augmentation `∑ c_g`, the image of `self` under the ring
+    /// homomorphism sending every group element to 1.
+    pub fn augmentation(&self) -> K {
+        match &self.repr {
+            Repr::Constant(c) => c.clone(),
+            Repr::Terms { elem_of, terms } => {
+                let mut sum = K::zero();
+                for c in terms.values() {
+                    elem_of.base.add_into(&mut sum, c.clone());
+                }
+                sum
+            }
+        }
+    }
+
+    /// The image `∑ c_g*g^-1` of `self` under the anti-involution induced by
+    /// inversion in the group.
+    pub fn involution(&self) -> Self {
+        match &self.repr {
+            Repr::Constant(_) => self.clone(),
+            Repr::Terms { elem_of, terms } => elem_of.with_terms(
+                terms
+                    .iter()
+                    .map(|(g, c)| (elem_of.group.inv(*g), c.clone()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<R, K> Add for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a + b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (g, c) in rhs.into_terms(algebra) {
+            add_term(algebra.base, &mut terms, g, c);
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, K> AddAssign for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<R, K> Sub for GroupAlgebraElement<'_, R, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a - b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (g, c) in rhs.into_terms(algebra) {
+            match terms.entry(g) {
+                Entry::Occupied(mut entry) => {
+                    if algebra.base.sub_from(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c);
+                }
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, K> SubAssign for GroupAlgebraElement<'_, R, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+/// The convolution product `(∑ a_g*g)*(∑ b_h*h) = ∑ a_g*b_h*(g*h)`.
+impl<R, K> Mul for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a * b),
+            };
+        };
+        let group = &algebra.group;
+        let (lhs, rhs) = (self.into_terms(algebra), rhs.into_terms(algebra));
+        let mut terms = HashMap::new();
+        for (g, a) in lhs.iter() {
+            for (h, b) in rhs.iter() {
+                add_term(
+                    algebra.base,
+                    &mut terms,
+                    group.mul(*g, *h),
+                    a.clone() * b.clone(),
+                );
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, K> MulAssign for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::one()) * rhs;
+    }
+}
+
+impl<R, K> Zero for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn zero() -> Self {
+        Self {
+            repr: Repr::Constant(K::zero()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match &self.repr {
+            Repr::Constant(c) => c.is_zero(),
+            Repr::Terms { terms, .. } => terms.is_empty(),
+        }
+    }
+}
+
+impl<R, K> One for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn one() -> Self {
+        Self {
+            repr: Repr::Constant(K::one()),
+        }
+    }
+}
+
+impl<R, K> Semiring<GroupAlgebraElement<'_, R, K>> for GroupAlgebra<'_, R>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    const COMMUTATIVE: bool = false;
+}
+
+impl<R, K> Ring<GroupAlgebraElement<'_, R, K>> for GroupAlgebra<'_, R>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+impl<R, K> SemiringElement for GroupAlgebraElement<'_, R, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+}
+
+impl<R, K> RingElement for GroupAlgebraElement<'_, R, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+}
+
+/// Displays the terms in the order of the group elements, as in
+/// `2*e+(0 1 2)`, and a detached constant as itself.
+impl<R, K> Display for GroupAlgebraElement<'_, R, K>
+where
+    K: Display + One + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (elem_of, terms) = match &self.repr {
+            Repr::Constant(c) => return Display::fmt(c, f),
+            Repr::Terms { elem_of, terms } => (elem_of, terms),
+        };
+        let mut terms: Vec<_> = terms.iter().collect();
+        terms.sort_by_key(|(g, _)| **g);
+        if terms.is_empty() {
+            write!(f, "0")?;
+        }
+        for (i, (g, c)) in terms.into_iter().enumerate() {
+            if !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            write!(f, "{}", elem_of.group.name(*g))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use num::{BigRational, One};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::{AlreadyRing, Monomial, Polynomial, PolynomialRing};
+
+static BASE: AlreadyRing<BigRational> = AlreadyRing {
+    phantom: PhantomData,
+};
+
+type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+/// A polynomial ring over the rationals shared by the polynomials in it, for
+/// bindings to languages which cannot hold the borrows of [`Polynomial`].
+pub(crate) type SharedRing = Rc<PolynomialRing<'static, AlreadyRing<BigRational>, String>>;
+
+/// The shared ring in the variables `vars`.
+pub(crate) fn shared_ring(vars: Vec<String>) -> SharedRing {
+    Rc::new(PolynomialRing::new(vars, &BASE))
+}
+
+/// A polynomial of a [`SharedRing`], holding its terms, from which the
+/// [`Polynomial`] is rebuilt for each operation.
+#[derive(Clone)]
+pub(crate) struct OwnedPolynomial {
+    ring: SharedRing,
+    terms: Vec<(Monomial<u32>, BigRational)>,
+}
+
+impl OwnedPolynomial {
+    fn new(ring: &SharedRing, f: Poly<'_>) -> Self {
+        Self {
+            ring: ring.clone(),
+            terms: f.into_terms().collect(),
+        }
+    }
+
+    fn polynomial(&self) -> Poly<'_> {
+        Poly::from_terms(&self.ring, self.terms.iter().cloned()).unwrap()
+    }
+
+    /// The polynomial of `ring` written as `input`, such as
+    /// `x^2*y - 3/4*z + 1`.
+    pub(crate) fn parse(ring: &SharedRing, input: &str) -> Result<Self, ChidogError> {
+        Ok(Self::new(ring, Poly::from_sympy(ring, input)?))
+    }
+
+    /// The constant polynomial `c` of `ring`.
+    pub(crate) fn constant(ring: &SharedRing, c: BigRational) -> Self {
+        let powers = smallvec![0; ring.vars.len()];
+        Self::new(
+            ring,
+            Poly::from_terms(ring, [(Monomial { powers }, c)]).unwrap(),
+        )
+    }
+
+    /// `op` applied to `self` and `other`, or an `InvalidInput` error if they
+    /// are not of the same ring.
+    fn binary(
+        &self,
+        other: &Self,
+        op: impl for<'a> FnOnce(Poly<'a>, Poly<'a>) -> Poly<'a>,
+    ) -> Result<Self, ChidogError> {
+        if !Rc::ptr_eq(&self.ring, &other.ring) {
+            return Err(ChidogError::InvalidInput(
+                "polynomials of different rings".to_string(),
+            ));
+        }
+        Ok(Self::new(
+            &self.ring,
+            op(self.polynomial(), other.polynomial()),
+        ))
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> Result<Self, ChidogError> {
+        self.binary(other, |f, g| f + g)
+    }
+
+    pub(crate) fn sub(&self, other: &Self) -> Result<Self, ChidogError> {
+        self.binary(other, |f, g| f - g)
+    }
+
+    pub(crate) fn mul(&self, other: &Self) -> Result<Self, ChidogError> {
+        self.binary(other, |f, g| f * g)
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        let terms = self.terms.iter().map(|(m, c)| (m.clone(), -c)).collect();
+        Self {
+            ring: self.ring.clone(),
+            terms,
+        }
+    }
+
+    /// The power `self^n`, by repeated squaring.
+    pub(crate) fn pow(&self, mut n: u32) -> Self {
+        let one = Self::constant(&self.ring, BigRational::one());
+        let mut result = one.polynomial();
+        let mut base = self.polynomial();
+        while n > 0 {
+            if n & 1 == 1 {
+                result *= base.clone();
+            }
+            n >>= 1;
+            if n > 0 {
+                base *= base.clone();
+            }
+        }
+        Self::new(&self.ring, result)
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether `self` and `other` are equal polynomials of the same ring.
+    pub(crate) fn equals(&self, other: &Self) -> bool {
+        self.sub(other).is_ok_and(|d| d.is_zero())
+    }
+
+    /// The total degree, or `None` for zero.
+    pub(crate) fn degree(&self) -> Option<u64> {
+        self.terms
+            .iter()
+            .map(|(m, _)| m.powers.iter().map(|p| u64::from(*p)).sum())
+            .max()
+    }
+
+    /// `self` written as read by [`OwnedPolynomial::parse`], with its terms in
+    /// decreasing lexicographic order, such as `x^2*y-3/4*z+1`.
+    pub(crate) fn to_infix(&self) -> String {
+        self.polynomial().to_sage()
+    }
+}
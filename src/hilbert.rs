@@ -0,0 +1,194 @@
+use std::hash::Hash;
+
+use num::{BigInt, One, PrimInt, Unsigned, Zero};
+
+use crate::order::MonomialOrder;
+use crate::{Monomial, Polynomial, Semiring, SemiringElement};
+
+/// The Hilbert series `∑ dim (R/I)_d t^d` of the quotient of a polynomial
+/// ring `R` in `n` variables by a homogeneous ideal `I`, as the numerator
+/// `N(t)` of `N(t)/(1 - t)^n`.
+///
+/// A homogeneous ideal and its leading ideal in any order have the same
+/// Hilbert series, so it is computed from a monomial ideal, by
+/// [`HilbertSeries::of_monomial_ideal`] or
+/// [`HilbertSeries::of_leading_ideal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HilbertSeries {
+    numerator: Vec<BigInt>,
+    vars: usize,
+}
+
+/// `f - t^k*g` for polynomials in `t` given by their coefficients.
+fn sub_shifted(mut f: Vec<BigInt>, g: &[BigInt], k: usize) -> Vec<BigInt> {
+    if f.len() < g.len() + k {
+        f.resize(g.len() + k, BigInt::zero());
+    }
+    for (i, c) in g.iter().enumerate() {
+        f[i + k] -= c;
+    }
+    while f.last().is_some_and(Zero::is_zero) {
+        f.pop();
+    }
+    f
+}
+
+/// The generators of the monomial ideal generated by `monomials` that no
+/// other one divides, without repetitions.
+fn minimalize(mut monomials: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+    monomials.sort_unstable_by_key(|m| m.iter().sum::<u64>());
+    monomials.dedup();
+    let mut minimal: Vec<Vec<u64>> = Vec::new();
+    for m in monomials {
+        if !minimal
+            .iter()
+            .any(|g| g.iter().zip(&m).all(|(a, b)| a <= b))
+        {
+            minimal.push(m);
+        }
+    }
+    minimal
+}
+
+/// The numerator of the Hilbert series of the quotient by the ideal
+/// generated by the exponent vectors `monomials`.
+///
+/// If no variable appears in two generators, the generators form a regular
+/// sequence and the numerator is `∏ (1 - t^deg(m))`. Otherwise, for the
+/// variable `x` appearing in the most generators and its least positive
+/// exponent `e` in them, the numerator of `I` is that of `I + (x^e)` plus
+/// `t^e` times that of `I : x^e`, after Bigatti.
+fn numerator(monomials: Vec<Vec<u64>>) -> Vec<BigInt> {
+    let monomials = minimalize(monomials);
+    let n = monomials.first().map_or(0, Vec::len);
+    let counts: Vec<usize> = (0..n)
+        .map(|i| monomials.iter().filter(|m| m[i] > 0).count())
+        .collect();
+    let Some(x) = (0..n).filter(|&i| counts[i] > 1).max_by_key(|&i| counts[i]) else {
+        return monomials.iter().fold(vec![BigInt::one()], |f, m| {
+            let f2 = f.clone();
+            sub_shifted(f, &f2, m.iter().sum::<u64>() as usize)
+        });
+    };
+    let e = monomials
+        .iter()
+        .map(|m| m[x])
+        .filter(|&e| e > 0)
+        .min()
+        .unwrap();
+    let mut pivot = vec![0; n];
+    pivot[x] = e;
+    let mut sum = monomials.clone();
+    sum.push(pivot);
+    let quotient = monomials
+        .into_iter()
+        .map(|mut m| {
+            m[x] = m[x].saturating_sub(e);
+            m
+        })
+        .collect();
+    let negated: Vec<BigInt> = numerator(quotient).into_iter().map(|c| -c).collect();
+    sub_shifted(numerator(sum), &negated, e as usize)
+}
+
+/// The binomial coefficient `(n choose k)`.
+fn binomial(n: usize, k: usize) -> BigInt {
+    (0..k).fold(BigInt::one(), |b, i| b * (n - i) / (i + 1))
+}
+
+impl HilbertSeries {
+    /// The Hilbert series of the quotient of the ring in `vars` variables
+    /// by the ideal generated by `monomials`.
+    pub fn of_monomial_ideal<P: PrimInt>(monomials: &[Monomial<P>], vars: usize) -> Self {
+        let exponents = monomials
+            .iter()
+            .map(|m| m.powers.iter().map(|p| p.to_u64().unwrap()).collect())
+            .collect::<Vec<_>>();
+        let numerator = match exponents.is_empty() {
+            true => vec![BigInt::one()],
+            false => numerator(exponents),
+        };
+        Self { numerator, vars }
+    }
+
+    /// The Hilbert series of the quotient by the ideal generated by the
+    /// leading monomials of `basis` in `order`, which is that of the ideal
+    /// generated by `basis` if it is a Gröbner basis of a homogeneous ideal.
+    pub fn of_leading_ideal<R, V, K, P, O>(basis: &[Polynomial<'_, R, V, K, P>], order: &O) -> Self
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+        O: MonomialOrder,
+    {
+        let leads: Vec<_> = basis
+            .iter()
+            .filter_map(|g| g.leading_term(order))
+            .map(|(m, _)| m.clone())
+            .collect();
+        let vars = basis.first().map_or(0, |g| g.elem_of.vars.len());
+        Self::of_monomial_ideal(&leads, vars)
+    }
+
+    /// The coefficients of the numerator `N(t)`, in order of increasing
+    /// degree.
+    pub fn numerator(&self) -> &[BigInt] {
+        &self.numerator
+    }
+
+    /// The number `n` of variables, the exponent of the denominator.
+    pub fn vars(&self) -> usize {
+        self.vars
+    }
+
+    /// The value `dim (R/I)_d` of the Hilbert function, the coefficient of
+    /// `t^d` in the series, `∑ N_k*(d - k + n - 1 choose n - 1)`.
+    pub fn function(&self, d: usize) -> BigInt {
+        match self.vars {
+            0 => self.numerator.get(d).cloned().unwrap_or_default(),
+            n => (0..self.numerator.len().min(d + 1))
+                .map(|k| &self.numerator[k] * binomial(d - k + n - 1, n - 1))
+                .sum(),
+        }
+    }
+
+    /// The numerator with the factors `1 - t` it shares with the
+    /// denominator removed, and the number of them.
+    fn reduced(&self) -> (Vec<BigInt>, usize) {
+        let mut numerator = self.numerator.clone();
+        let mut removed = 0;
+        while removed < self.vars
+            && !numerator.is_empty()
+            && numerator.iter().sum::<BigInt>().is_zero()
+        {
+            // Synthetic division by `1 - t`: the quotient's coefficients are
+            // the partial sums.
+            let mut sum = BigInt::zero();
+            numerator = numerator[..numerator.len() - 1]
+                .iter()
+                .map(|c| {
+                    sum += c;
+                    sum.clone()
+                })
+                .collect();
+            removed += 1;
+        }
+        (numerator, removed)
+    }
+
+    /// The Krull dimension of `R/I`, the order of the pole of the series at
+    /// `t = 1`, which is `-1` for the whole ring.
+    pub fn dimension(&self) -> isize {
+        if self.numerator.is_empty() {
+            return -1;
+        }
+        (self.vars - self.reduced().1) as isize
+    }
+
+    /// The degree, or multiplicity, of `R/I`: the value at `t = 1` of the
+    /// numerator once the factors `1 - t` are removed, which is positive
+    /// unless `I` is the whole ring.
+    pub fn degree(&self) -> BigInt {
+        self.reduced().0.iter().sum()
+    }
+}
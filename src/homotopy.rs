@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::fmt::{Display, Write};
+use std::hash::{BuildHasher, Hash};
+
+use num::complex::Complex64;
+use num::{BigInt, BigRational, FromPrimitive, One, PrimInt, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::infix::to_infix;
+use crate::polytope::bkk_bound;
+use crate::{AlreadyRing, Monomial, Polynomial, PolynomialRing, Semiring, SemiringElement};
+
+/// A ring of polynomials with complex floating-point coefficients, in which
+/// start systems are built.
+pub type ComplexRing<'a, V> = PolynomialRing<'a, AlreadyRing<Complex64>, V>;
+
+/// A polynomial of a [`ComplexRing`].
+pub type ComplexPolynomial<'a, V, P, S> =
+    Polynomial<'a, AlreadyRing<Complex64>, V, Complex64, P, S>;
+
+/// A matrix of polynomials, indexed by row and then column.
+pub type PolynomialMatrix<'a, R, V, K, P, S> = Vec<Vec<Polynomial<'a, R, V, K, P, S>>>;
+
+/// The Jacobian matrix of `system`, whose entry `(i, j)` is the derivative
+/// of `system[i]` with respect to `x_j`.
+pub fn jacobian<'a, R, V, K, P, S>(
+    system: &[Polynomial<'a, R, V, K, P, S>],
+) -> PolynomialMatrix<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    system
+        .iter()
+        .map(|f| (0..f.elem_of.vars.len()).map(|j| f.derivative(j)).collect())
+        .collect()
+}
+
+/// Fails unless `system` has as many polynomials as variables, all nonzero.
+fn check_square<R, V, K, P, S>(system: &[Polynomial<'_, R, V, K, P, S>]) -> Result<(), ChidogError>
+where
+    P: Hash,
+{
+    if let Some(f) = system.iter().find(|f| f.elem_of.vars.len() != system.len()) {
+        return Err(ChidogError::InvalidInput(format!(
+            "a start system needs as many polynomials as variables, not {} in {}",
+            system.len(),
+            f.elem_of.vars.len()
+        )));
+    }
+    if system.iter().any(|f| f.terms.is_empty()) {
+        return Err(ChidogError::InvalidInput(
+            "a start system needs nonzero polynomials".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The total-degree start system `a_i*x_i^(d_i) - b_i` of a square system
+/// whose polynomials have total degrees `d_i`, with random coefficients
+/// `a_i` and `b_i`, as built by [`total_degree_start_system`].
+///
+/// Its `d_1*...*d_n` solutions, one for each path of the homotopy, are the
+/// points whose coordinates `x_i` are the `d_i`-th roots of `b_i/a_i`.
+pub struct TotalDegreeStart<'a, V, P: Hash, S> {
+    pub polynomials: Vec<ComplexPolynomial<'a, V, P, S>>,
+    pub degrees: Vec<usize>,
+    /// A `d_i`-th root of `b_i/a_i` for each variable.
+    roots: Vec<Complex64>,
+}
+
+impl<V, P: Hash, S> TotalDegreeStart<'_, V, P, S> {
+    /// The number of start solutions, which is the Bézout bound.
+    pub fn paths(&self) -> BigInt {
+        self.degrees.iter().map(|&d| BigInt::from(d)).product()
+    }
+
+    /// The start solutions, enumerated with the last coordinate varying
+    /// fastest.
+    pub fn solutions(&self) -> impl Iterator<Item = Vec<Complex64>> + '_ {
+        let mut index = vec![0; self.degrees.len()];
+        let mut done = self.degrees.contains(&0);
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let point = index
+                .iter()
+                .zip(&self.degrees)
+                .zip(&self.roots)
+                .map(|((&k, &d), r)| r * Complex64::from_polar(1.0, TAU * k as f64 / d as f64))
+                .collect();
+            // Advance the mixed-radix counter, finishing when it wraps.
+            done = true;
+            for (k, &d) in index.iter_mut().zip(&self.degrees).rev() {
+                *k += 1;
+                if *k < d {
+                    done = false;
+                    break;
+                }
+                *k = 0;
+            }
+            Some(point)
+        })
+    }
+}
+
+/// The total-degree start system of the square system `system` in `ring`,
+/// which must have the same number of variables, with the coefficients
+/// drawn by `random`, for instance uniformly from the unit circle.
+///
+/// Tracking the paths of `(1 - t)*system + t*γ*start` from `t = 1` to `t =
+/// 0`, for a random complex `γ`, reaches every isolated solution of
+/// `system`, at the cost of one path per start solution.
+pub fn total_degree_start_system<'b, R, V, K, P, S>(
+    system: &[Polynomial<'_, R, V, K, P, S>],
+    ring: &'b ComplexRing<'b, V>,
+    mut random: impl FnMut() -> Complex64,
+) -> Result<TotalDegreeStart<'b, V, P, S>, ChidogError>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    check_square(system)?;
+    let n = system.len();
+    let degrees: Vec<usize> = system
+        .iter()
+        .map(|f| {
+            f.terms
+                .keys()
+                .map(|m| m.powers.iter().map(|p| p.to_usize().unwrap()).sum())
+                .max()
+                .unwrap()
+        })
+        .collect();
+    let mut polynomials = Vec::with_capacity(n);
+    let mut roots = Vec::with_capacity(n);
+    for (i, &d) in degrees.iter().enumerate() {
+        let (a, b) = (random(), random());
+        let mut power: Monomial<P> = Monomial {
+            powers: smallvec![P::zero(); n],
+        };
+        power.powers[i] = P::from(d).unwrap();
+        let one = Monomial {
+            powers: smallvec![P::zero(); n],
+        };
+        let terms = [(power, a), (one, -b)]
+            .into_iter()
+            .filter(|(_, c)| !c.is_zero());
+        polynomials.push(Polynomial::from_terms(ring, terms)?);
+        roots.push(match d {
+            0 => Complex64::one(),
+            _ => (b / a).powf(1.0 / d as f64),
+        });
+    }
+    Ok(TotalDegreeStart {
+        polynomials,
+        degrees,
+        roots,
+    })
+}
+
+/// The polyhedral start system of a square system: the polynomials with
+/// the same supports and random coefficients, as built by
+/// [`polyhedral_start_system`].
+///
+/// Its solutions are not known in closed form; they are found by the
+/// polyhedral homotopy of Huber and Sturmfels from the mixed cells of the
+/// supports, which a path tracker computes.
+pub struct PolyhedralStart<'a, V, P: Hash, S> {
+    pub polynomials: Vec<ComplexPolynomial<'a, V, P, S>>,
+    /// The number of isolated solutions with nonzero coordinates, which is
+    /// the BKK bound, and so the number of paths.
+    pub paths: BigInt,
+}
+
+/// The polyhedral start system of the square system `system` in `ring`,
+/// with the coefficients drawn by `random`.
+///
+/// Tracking from it needs one path per solution with nonzero coordinates,
+/// which may be far fewer than for the total-degree start system.
+pub fn polyhedral_start_system<'b, R, V, K, P, S>(
+    system: &[Polynomial<'_, R, V, K, P, S>],
+    ring: &'b ComplexRing<'b, V>,
+    mut random: impl FnMut() -> Complex64,
+) -> Result<PolyhedralStart<'b, V, P, S>, ChidogError>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    check_square(system)?;
+    let paths = bkk_bound(system)?;
+    let polynomials = system
+        .iter()
+        .map(|f| {
+            let mut terms = HashMap::with_capacity_and_hasher(f.terms.len(), S::default());
+            terms.extend(f.terms.keys().map(|m| (m.clone(), random())));
+            Polynomial {
+                elem_of: ring,
+                terms,
+            }
+        })
+        .collect();
+    Ok(PolyhedralStart { polynomials, paths })
+}
+
+/// `c` as a Bertini constant, such as `(0.6-0.8*I)`.
+fn bertini_complex(c: &Complex64) -> String {
+    let sign = if c.im.is_sign_negative() { '-' } else { '+' };
+    format!("({:?}{sign}{:?}*I)", c.re, c.im.abs())
+}
+
+/// `f` in the infix notation of Bertini, with complex coefficients.
+fn bertini_polynomial<V: Display, P, S>(f: &ComplexPolynomial<'_, V, P, S>) -> String
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut terms: Vec<_> = f.terms.iter().collect();
+    terms.sort_unstable_by(|(a, _), (b, _)| b.powers.cmp(&a.powers));
+    let terms: Vec<String> = terms
+        .into_iter()
+        .map(|(m, c)| {
+            let mut factors = vec![bertini_complex(c)];
+            for (var, p) in f.elem_of.vars.iter().zip(&m.powers) {
+                match p.to_u64().unwrap() {
+                    0 => {}
+                    1 => factors.push(var.to_string()),
+                    p => factors.push(format!("{var}^{p}")),
+                }
+            }
+            factors.join("*")
+        })
+        .collect();
+    match terms.is_empty() {
+        true => "0".to_string(),
+        false => terms.join("+"),
+    }
+}
+
+/// The Bertini input file solving the square system `system` by Bertini's
+/// own homotopy, which differentiates the functions itself.
+pub fn bertini_input<R, V, K, P, S>(system: &[Polynomial<'_, R, V, K, P, S>]) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut input = String::from("INPUT\n");
+    if let Some(f) = system.first() {
+        let vars: Vec<String> = f.elem_of.vars.iter().map(|v| v.to_string()).collect();
+        writeln!(input, "variable_group {};", vars.join(", ")).unwrap();
+    }
+    let names: Vec<String> = (1..=system.len()).map(|i| format!("f{i}")).collect();
+    writeln!(input, "function {};", names.join(", ")).unwrap();
+    for (name, f) in names.iter().zip(system) {
+        writeln!(input, "{name} = {};", to_infix(f, "^")).unwrap();
+    }
+    input.push_str("END;\n");
+    input
+}
+
+/// The Bertini input file tracking the user-defined homotopy
+/// `(1 - s)*target + s*gamma*start` from `s = 1`, where the solutions of
+/// `start` are given in the file written by [`bertini_start`], to `s = 0`,
+/// where they are solutions of `target`.
+///
+/// The polynomials of `start` must be in variables of the same names as
+/// those of `target`.
+pub fn bertini_homotopy<R, V, K, P, S>(
+    target: &[Polynomial<'_, R, V, K, P, S>],
+    start: &[ComplexPolynomial<'_, V, P, S>],
+    gamma: Complex64,
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut input = String::from("CONFIG\nUserHomotopy: 1;\nEND;\nINPUT\n");
+    if let Some(f) = target.first() {
+        let vars: Vec<String> = f.elem_of.vars.iter().map(|v| v.to_string()).collect();
+        writeln!(input, "variable {};", vars.join(", ")).unwrap();
+    }
+    let names: Vec<String> = (1..=target.len()).map(|i| format!("f{i}")).collect();
+    writeln!(input, "function {};", names.join(", ")).unwrap();
+    input.push_str("pathvariable t;\nparameter s;\nconstant gamma;\n");
+    writeln!(input, "gamma = {};", bertini_complex(&gamma)).unwrap();
+    input.push_str("s = t;\n");
+    for ((name, f), g) in names.iter().zip(target).zip(start) {
+        writeln!(
+            input,
+            "{name} = (1-s)*({}) + s*gamma*({});",
+            to_infix(f, "^"),
+            bertini_polynomial(g)
+        )
+        .unwrap();
+    }
+    input.push_str("END;\n");
+    input
+}
+
+/// The Bertini `start` file listing `solutions`: their number, then each
+/// of them as the real and imaginary parts of its coordinates, one per
+/// line, after a blank line.
+pub fn bertini_start(solutions: impl IntoIterator<Item = Vec<Complex64>>) -> String {
+    let mut count = 0;
+    let mut body = String::new();
+    for point in solutions {
+        count += 1;
+        body.push('\n');
+        for x in point {
+            writeln!(body, "{:e} {:e}", x.re, x.im).unwrap();
+        }
+    }
+    format!("{count}\n{body}")
+}
@@ -0,0 +1,898 @@
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{BigRational, PrimInt, ToPrimitive, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::groebner::groebner_basis;
+use crate::hilbert::HilbertSeries;
+use crate::matrix::Matrix;
+use crate::order::{GRevLex, MonomialOrder};
+use crate::univariate::DenseUnivariate;
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing, Powers, SemiringElement};
+
+/// A reduced normal form of a power in [`Ideal::minimal_polynomial`], with
+/// its pivot monomial, which the other rows lack, and its expression in the
+/// powers.
+type Row<K, P> = (Monomial<P>, HashMap<Monomial<P>, K>, Vec<K>);
+
+/// The integer `n` as an element of `K`, by doubling and adding.
+fn integer<K: SemiringElement + Clone>(n: usize) -> K {
+    let mut value = K::zero();
+    for i in (0..usize::BITS - n.leading_zeros()).rev() {
+        value = value.clone() + value;
+        if n >> i & 1 == 1 {
+            value += K::one();
+        }
+    }
+    value
+}
+
+/// Whether `f` has no repeated factor, that is, whether it is coprime to its
+/// derivative.
+fn is_squarefree<K: FieldElement + Clone>(f: &DenseUnivariate<K>) -> bool {
+    let derivative = DenseUnivariate::new(
+        f.coeffs()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| integer::<K>(i) * c.clone())
+            .collect(),
+    );
+    f.gcd(&derivative).degree() == Some(0)
+}
+
+/// The ideal of a polynomial ring over a field generated by some
+/// polynomials.
+///
+/// The reduced Gröbner basis in the graded reverse lexicographic order,
+/// usually the cheapest to compute, is computed when first needed and kept,
+/// so that membership, containment and equality tests on the same ideal
+/// only reduce polynomials by it.
+#[derive(Clone)]
+pub struct Ideal<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    ring: &'a PolynomialRing<'a, R, V>,
+    generators: Vec<Polynomial<'a, R, V, K, P>>,
+    basis: OnceCell<Vec<Polynomial<'a, R, V, K, P>>>,
+}
+
+impl<'a, R, V, K, P> Ideal<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The ideal of `ring` generated by `generators`.
+    pub fn new(
+        ring: &'a PolynomialRing<'a, R, V>,
+        generators: Vec<Polynomial<'a, R, V, K, P>>,
+    ) -> Self {
+        Self {
+            ring,
+            generators,
+            basis: OnceCell::new(),
+        }
+    }
+
+    /// The ring of the ideal.
+    pub fn ring(&self) -> &'a PolynomialRing<'a, R, V> {
+        self.ring
+    }
+
+    /// The generators the ideal was created with.
+    pub fn generators(&self) -> &[Polynomial<'a, R, V, K, P>] {
+        &self.generators
+    }
+
+    /// The reduced Gröbner basis of the ideal in [`GRevLex`], computed on
+    /// first use.
+    pub fn groebner_basis(&self) -> &[Polynomial<'a, R, V, K, P>] {
+        self.basis
+            .get_or_init(|| groebner_basis(&self.generators, &GRevLex))
+    }
+
+    /// The reduced Gröbner basis of the ideal in `order`, which is not kept.
+    pub fn groebner_basis_in<O: MonomialOrder + Clone>(
+        &self,
+        order: &O,
+    ) -> Vec<Polynomial<'a, R, V, K, P>> {
+        groebner_basis(&self.generators, order)
+    }
+
+    /// The normal form of `f` modulo the ideal, its remainder by the cached
+    /// Gröbner basis, which is zero exactly when `f` is in the ideal.
+    pub fn normal_form(&self, f: &Polynomial<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P> {
+        f.rem(self.groebner_basis(), &GRevLex)
+    }
+
+    /// Whether `f` is in the ideal.
+    pub fn contains(&self, f: &Polynomial<'a, R, V, K, P>) -> bool {
+        self.normal_form(f).terms.is_empty()
+    }
+
+    /// Whether `other` is contained in the ideal, that is, whether each of
+    /// its generators is.
+    pub fn contains_ideal(&self, other: &Self) -> bool {
+        other.generators.iter().all(|f| self.contains(f))
+    }
+
+    /// Whether the ideal is zero.
+    pub fn is_zero(&self) -> bool {
+        self.groebner_basis().is_empty()
+    }
+
+    /// Whether the ideal is the whole ring, that is, whether its reduced
+    /// Gröbner basis is `[1]`.
+    pub fn is_unit(&self) -> bool {
+        self.groebner_basis()
+            .iter()
+            .any(|g| g.terms.keys().all(|m| m.powers.iter().all(|p| p.is_zero())))
+    }
+
+    /// The initial ideal of the ideal in `order`, the monomial ideal
+    /// generated by the leading monomials of its elements.
+    ///
+    /// An ideal generated by monomials is its own initial ideal in every
+    /// order, and is returned without computing a Gröbner basis.
+    pub fn initial_ideal<O: MonomialOrder + Clone>(&self, order: &O) -> MonomialIdeal<P> {
+        let vars = self.ring.vars.len();
+        if self.generators.iter().all(|f| f.terms.len() <= 1) {
+            let monomials = self.generators.iter().flat_map(|f| f.terms.keys().cloned());
+            return MonomialIdeal::new(monomials.collect(), vars);
+        }
+        let leads = self
+            .groebner_basis_in(order)
+            .iter()
+            .filter_map(|g| g.leading_term(order))
+            .map(|(m, _)| m.clone())
+            .collect();
+        MonomialIdeal::new(leads, vars)
+    }
+}
+
+impl<'a, R, V, K, P> Ideal<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The Hilbert series of the quotient by the leading ideal of the cached
+    /// Gröbner basis, which has the dimension of the quotient by the ideal,
+    /// and as degree the dimension of the quotient as a vector space when
+    /// it is finite.
+    fn leading_series(&self) -> HilbertSeries {
+        self.leading_ideal().hilbert_series()
+    }
+
+    /// The initial ideal in [`GRevLex`], from the cached Gröbner basis.
+    fn leading_ideal(&self) -> MonomialIdeal<P> {
+        let leads = self
+            .groebner_basis()
+            .iter()
+            .filter_map(|g| g.leading_term(&GRevLex))
+            .map(|(m, _)| m.clone())
+            .collect();
+        MonomialIdeal::new(leads, self.ring.vars.len())
+    }
+
+    /// The unit of the field of coefficients, taken from the leading
+    /// coefficient of a monic element of the basis rather than `K::one()`,
+    /// so that it carries the modulus of a [`Modular`] residue.
+    ///
+    /// [`Modular`]: crate::multimodular::Modular
+    fn one(&self) -> K {
+        self.groebner_basis()
+            .first()
+            .and_then(|g| g.leading_term(&GRevLex))
+            .map_or_else(K::one, |(_, c)| c.clone())
+    }
+
+    /// The polynomial `c*x_i`.
+    fn variable(&self, i: usize, c: K) -> Polynomial<'a, R, V, K, P> {
+        let mut powers = smallvec![P::zero(); self.ring.vars.len()];
+        powers[i] = P::one();
+        Polynomial::from_terms(self.ring, [(Monomial { powers }, c)])
+            .expect("the monomial has one exponent for each variable")
+    }
+
+    /// The Krull dimension of the quotient ring by the ideal, the dimension
+    /// of its variety, which is `-1` for the whole ring.
+    pub fn dimension(&self) -> isize {
+        self.leading_series().dimension()
+    }
+
+    /// Whether the quotient ring by the ideal is a nonzero finite-dimensional
+    /// vector space, that is, whether the ideal has finitely many zeros over
+    /// an algebraic closure, and at least one.
+    pub fn is_zero_dimensional(&self) -> bool {
+        self.dimension() == 0
+    }
+
+    /// The standard monomials, those outside the initial ideal in
+    /// [`GRevLex`], in increasing order, whose residue classes form a basis
+    /// of the quotient ring by the ideal, or an `InvalidInput` error if there
+    /// are infinitely many. They are the monomials in which normal forms
+    /// are written.
+    pub fn standard_monomials(&self) -> Result<Vec<Monomial<P>>, ChidogError> {
+        self.leading_ideal().standard_monomials().ok_or_else(|| {
+            ChidogError::InvalidInput(
+                "the quotient by the ideal is not finite-dimensional".to_string(),
+            )
+        })
+    }
+
+    /// The dimension of the quotient ring by the ideal as a vector space,
+    /// the number of its zeros over an algebraic closure counted with
+    /// multiplicity, or an `InvalidInput` error if it is infinite.
+    pub fn vector_space_dimension(&self) -> Result<usize, ChidogError> {
+        let series = self.leading_series();
+        match series.dimension() {
+            -1 => Ok(0),
+            0 => Ok(series.degree().to_usize().unwrap()),
+            _ => Err(ChidogError::InvalidInput(
+                "the quotient by the ideal is not finite-dimensional".to_string(),
+            )),
+        }
+    }
+
+    /// The matrix of multiplication by `f` on the quotient ring, in the basis
+    /// `basis` of standard monomials: its column `j` holds the coordinates of
+    /// the normal form of `f*o_j`.
+    fn multiplication_matrix_in(
+        &self,
+        basis: &[Monomial<P>],
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> Matrix<K> {
+        let position: HashMap<&Monomial<P>, usize> = basis.iter().zip(0..).collect();
+        let mut matrix = Matrix::zero(basis.len(), basis.len());
+        for (j, o) in basis.iter().enumerate() {
+            let product = self.term(o, self.one()) * f.clone();
+            for (t, c) in &self.normal_form(&product).terms {
+                matrix[(position[t], j)] = c.clone();
+            }
+        }
+        matrix
+    }
+
+    /// The matrix of multiplication by `f` on the quotient ring, in the basis
+    /// of [`Ideal::standard_monomials`]: its column `j` holds the coordinates
+    /// of the normal form of `f*o_j`. This is an `InvalidInput` error if the
+    /// quotient is not finite-dimensional.
+    ///
+    /// Its eigenvalues are the values of `f` at the zeros of the ideal, and
+    /// its characteristic polynomial vanishes at `f` modulo the ideal.
+    pub fn multiplication_matrix(
+        &self,
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> Result<Matrix<K>, ChidogError> {
+        Ok(self.multiplication_matrix_in(&self.standard_monomials()?, f))
+    }
+
+    /// The matrices of multiplication by each of the variables on the
+    /// quotient ring, in the basis of [`Ideal::standard_monomials`], or an
+    /// `InvalidInput` error if the quotient is not finite-dimensional. They
+    /// commute, and their joint eigenvectors give the zeros of the ideal.
+    pub fn multiplication_matrices(&self) -> Result<Vec<Matrix<K>>, ChidogError> {
+        let basis = self.standard_monomials()?;
+        Ok((0..self.ring.vars.len())
+            .map(|i| self.multiplication_matrix_in(&basis, &self.variable(i, self.one())))
+            .collect())
+    }
+
+    /// The polynomial `c*m`.
+    fn term(&self, m: &Monomial<P>, c: K) -> Polynomial<'a, R, V, K, P> {
+        Polynomial::from_terms(self.ring, [(m.clone(), c)])
+            .expect("the monomial has one exponent for each variable")
+    }
+
+    /// The traces of multiplication by the standard monomials of `basis`,
+    /// which give that of multiplication by any polynomial from its normal
+    /// form, as the trace is linear.
+    fn traces(&self, basis: &[Monomial<P>]) -> Vec<K> {
+        basis
+            .iter()
+            .map(|o| {
+                self.multiplication_matrix_in(basis, &self.term(o, self.one()))
+                    .trace()
+            })
+            .collect()
+    }
+
+    /// The Hermite form of `h` in the basis `basis` of standard monomials,
+    /// whose multiplications have the traces `traces`.
+    fn hermite_form_in(
+        &self,
+        basis: &[Monomial<P>],
+        traces: &[K],
+        h: &Polynomial<'a, R, V, K, P>,
+    ) -> Matrix<K> {
+        let position: HashMap<&Monomial<P>, usize> = basis.iter().zip(0..).collect();
+        let trace = |f: &Polynomial<'a, R, V, K, P>| {
+            let mut t = K::zero();
+            for (m, c) in &self.normal_form(f).terms {
+                t += c.clone() * traces[position[m]].clone();
+            }
+            t
+        };
+        let products: Vec<_> = basis
+            .iter()
+            .map(|o| self.normal_form(&(self.term(o, self.one()) * h.clone())))
+            .collect();
+        let mut matrix = Matrix::zero(basis.len(), basis.len());
+        for (i, p) in products.iter().enumerate() {
+            for (j, o) in basis.iter().enumerate().skip(i) {
+                let t = trace(&(p.clone() * self.term(o, self.one())));
+                matrix[(j, i)] = t.clone();
+                matrix[(i, j)] = t;
+            }
+        }
+        matrix
+    }
+
+    /// The Hermite quadratic form of `h`, `(f, g) ↦ Tr(M_(h*f*g))` for the
+    /// matrix `M` of multiplication on the quotient ring, in the basis of
+    /// [`Ideal::standard_monomials`], or an `InvalidInput` error if the
+    /// quotient is not finite-dimensional.
+    ///
+    /// Its rank is the number of distinct zeros of the ideal over an
+    /// algebraic closure at which `h` does not vanish, and over the real
+    /// numbers its signature is the number of real zeros at which `h` is
+    /// positive minus that at which it is negative, after Hermite and
+    /// Pedersen, Roy and Szpirglas.
+    pub fn hermite_form(&self, h: &Polynomial<'a, R, V, K, P>) -> Result<Matrix<K>, ChidogError> {
+        let basis = self.standard_monomials()?;
+        Ok(self.hermite_form_in(&basis, &self.traces(&basis), h))
+    }
+
+    /// The monic minimal polynomial of multiplication by `f` on the quotient
+    /// ring by the zero-dimensional ideal, the monic generator of the
+    /// polynomials `p` with `p(f)` in the ideal, or an `InvalidInput` error
+    /// if the ideal is not zero-dimensional.
+    ///
+    /// The normal forms of `1, f, f^2, ...` are reduced against one another
+    /// until one is a combination of the previous ones, which happens by the
+    /// dimension of the quotient. For `f` a variable, this is the generator of
+    /// the elimination ideal in that variable.
+    pub fn minimal_polynomial(
+        &self,
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> Result<DenseUnivariate<K>, ChidogError> {
+        if !self.is_zero_dimensional() {
+            return Err(ChidogError::InvalidInput(
+                "the ideal is not zero-dimensional".to_string(),
+            ));
+        }
+        let one = Monomial {
+            powers: smallvec![P::zero(); self.ring.vars.len()],
+        };
+        let mut power = self.normal_form(&Polynomial::from_terms(self.ring, [(one, self.one())])?);
+        let mut rows: Vec<Row<K, P>> = Vec::new();
+        loop {
+            let k = rows.len();
+            let mut vector: HashMap<_, _> = power
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), c.clone()))
+                .collect();
+            let mut combination = vec![K::zero(); k + 1];
+            combination[k] = self.one();
+            for (pivot, row, expression) in &rows {
+                let Some(c) = vector.get(pivot) else {
+                    continue;
+                };
+                let c = c.clone() / row[pivot].clone();
+                for (m, d) in row {
+                    let entry = vector.entry(m.clone()).or_insert_with(K::zero);
+                    if self.ring.base.sub_from(entry, c.clone() * d.clone()) {
+                        vector.remove(m);
+                    }
+                }
+                for (a, b) in combination.iter_mut().zip(expression) {
+                    *a -= c.clone() * b.clone();
+                }
+            }
+            let Some(pivot) = vector.keys().max_by(|a, b| GRevLex.cmp(a, b)).cloned() else {
+                return Ok(DenseUnivariate::new(combination));
+            };
+            rows.push((pivot, vector, combination));
+            power = self.normal_form(&(power * f.clone()));
+        }
+    }
+
+    /// Whether the ideal is its own radical, or an `InvalidInput` error if it
+    /// is neither zero-dimensional, zero nor the whole ring.
+    ///
+    /// By Seidenberg's lemma, over a perfect field such as `ℚ` or a prime
+    /// field, a zero-dimensional ideal is radical exactly when the minimal
+    /// polynomial of each variable is squarefree.
+    pub fn is_radical(&self) -> Result<bool, ChidogError> {
+        if self.is_zero() || self.is_unit() {
+            return Ok(true);
+        }
+        for i in 0..self.ring.vars.len() {
+            if !is_squarefree(&self.minimal_polynomial(&self.variable(i, self.one()))?) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Whether the ideal is prime, given a test `irreducible` of univariate
+    /// polynomials over the field of coefficients, or an `InvalidInput`
+    /// error if it is neither zero-dimensional nor zero. Over `ℚ`,
+    /// [`Ideal::is_prime`] supplies the test.
+    ///
+    /// A radical zero-dimensional ideal `I` has a linear form `l` whose
+    /// minimal polynomial `m` has degree the dimension of `R/I`, so that
+    /// `R/I` is `K[t]/(m)`, and `I` is prime exactly when `m` is
+    /// irreducible. The forms `x_0 + k*x_1 + k^2*x_2 + ...` are tried for
+    /// `k = 1, 2, ...`; only finitely many fail in characteristic zero, but
+    /// over a small prime field all of them may, which is an `InvalidInput`
+    /// error too.
+    pub fn is_prime_with(
+        &self,
+        irreducible: impl Fn(&DenseUnivariate<K>) -> bool,
+    ) -> Result<bool, ChidogError> {
+        if self.is_unit() {
+            return Ok(false);
+        }
+        if self.is_zero() && !self.ring.vars.is_empty() {
+            return Ok(true);
+        }
+        if !self.is_radical()? {
+            return Ok(false);
+        }
+        let n = self.ring.vars.len();
+        let degree = self.leading_series().degree().to_usize().unwrap();
+        let tries = n.max(1) * degree * degree + 1;
+        for k in 1..=tries {
+            let mut form = Polynomial {
+                elem_of: self.ring,
+                terms: HashMap::default(),
+            };
+            let mut coefficient = self.one();
+            for i in 0..n {
+                form += self.variable(i, coefficient.clone());
+                coefficient *= integer::<K>(k);
+            }
+            let minimal = self.minimal_polynomial(&form)?;
+            if minimal.degree() == Some(degree) {
+                return Ok(irreducible(&minimal));
+            }
+        }
+        Err(ChidogError::InvalidInput(
+            "no linear form separates the zeros of the ideal".to_string(),
+        ))
+    }
+
+    /// Whether the ideal is maximal, given a test `irreducible` of univariate
+    /// polynomials over the field of coefficients, or an `InvalidInput`
+    /// error as for [`Ideal::is_prime_with`].
+    ///
+    /// A maximal ideal is zero-dimensional, by the Nullstellensatz, and a
+    /// zero-dimensional ideal is maximal exactly when it is prime.
+    pub fn is_maximal_with(
+        &self,
+        irreducible: impl Fn(&DenseUnivariate<K>) -> bool,
+    ) -> Result<bool, ChidogError> {
+        match self.is_zero_dimensional() {
+            true => self.is_prime_with(irreducible),
+            false => Ok(false),
+        }
+    }
+}
+
+/// Whether `f` is irreducible over `ℚ`, that is, has a single factor of
+/// multiplicity one in [`DenseUnivariate::factor`].
+fn is_irreducible_rational(f: &DenseUnivariate<BigRational>) -> bool {
+    matches!(f.factor().1.as_slice(), [(_, 1)])
+}
+
+impl<'a, R, V, P> Ideal<'a, R, V, BigRational, P>
+where
+    R: Field<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// Whether the ideal is prime, for rational coefficients, or an
+    /// `InvalidInput` error as for [`Ideal::is_prime_with`], which it calls
+    /// with factorization over `ℚ` as the test of irreducibility.
+    pub fn is_prime(&self) -> Result<bool, ChidogError> {
+        self.is_prime_with(is_irreducible_rational)
+    }
+
+    /// Whether the ideal is maximal, for rational coefficients, or an
+    /// `InvalidInput` error as for [`Ideal::is_prime_with`].
+    pub fn is_maximal(&self) -> Result<bool, ChidogError> {
+        self.is_maximal_with(is_irreducible_rational)
+    }
+}
+
+impl<'a, R, V, K, P> Ideal<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone + PartialOrd,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The number of distinct real zeros of the ideal, for coefficients in an
+    /// ordered subfield of the real numbers, or an `InvalidInput` error if
+    /// the quotient ring is not finite-dimensional.
+    ///
+    /// This is the signature of the Hermite form of `1`, so no zero is
+    /// isolated or even approximated.
+    pub fn count_real_zeros(&self) -> Result<usize, ChidogError> {
+        let basis = self.standard_monomials()?;
+        let one = self.term(
+            &Monomial {
+                powers: smallvec![P::zero(); self.ring.vars.len()],
+            },
+            self.one(),
+        );
+        Ok(self
+            .hermite_form_in(&basis, &self.traces(&basis), &one)
+            .signature() as usize)
+    }
+
+    /// The number of distinct real zeros of the ideal at which `h` is
+    /// positive, for coefficients in an ordered subfield of the real
+    /// numbers, or an `InvalidInput` error if the quotient ring is not
+    /// finite-dimensional.
+    ///
+    /// The signatures of the Hermite forms of `h` and `h^2` are the numbers
+    /// of real zeros with `h` positive minus and plus those with `h`
+    /// negative, so this is half their sum.
+    pub fn count_real_zeros_where_positive(
+        &self,
+        h: &Polynomial<'a, R, V, K, P>,
+    ) -> Result<usize, ChidogError> {
+        let basis = self.standard_monomials()?;
+        let traces = self.traces(&basis);
+        let signature =
+            |h: &Polynomial<'a, R, V, K, P>| self.hermite_form_in(&basis, &traces, h).signature();
+        Ok(((signature(h) + signature(&(h.clone() * h.clone()))) / 2) as usize)
+    }
+}
+
+/// Ideals are equal when each contains the other, which is decided by
+/// reducing the generators of each by the cached Gröbner basis of the
+/// other.
+impl<'a, R, V, K, P> PartialEq for Ideal<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.contains_ideal(other) && other.contains_ideal(self)
+    }
+}
+
+/// The generators among `monomials` that no other one divides, without
+/// repetitions, in increasing lexicographic order of their exponents.
+fn minimalize<P: PrimInt>(mut monomials: Vec<Monomial<P>>) -> Vec<Monomial<P>> {
+    monomials.sort_unstable_by(|m, n| m.powers.cmp(&n.powers));
+    monomials.dedup();
+    let minimal: Vec<bool> = monomials
+        .iter()
+        .map(|m| !monomials.iter().any(|g| g != m && g.divides(m)))
+        .collect();
+    monomials
+        .into_iter()
+        .zip(minimal)
+        .filter_map(|(m, minimal)| minimal.then_some(m))
+        .collect()
+}
+
+/// The number of variables with a positive exponent in `m`.
+fn support<P: PrimInt>(m: &Monomial<P>) -> usize {
+    m.powers.iter().filter(|p| !p.is_zero()).count()
+}
+
+/// Pushes onto `components` the generators of irreducible monomial ideals
+/// with intersection the ideal generated by `generators`, by splitting a
+/// generator `x_i^e*m` with `m` coprime to `x_i` into the ideals with
+/// `x_i^e` and `m` added instead, until all generators are pure powers.
+fn split<P: PrimInt>(generators: Vec<Monomial<P>>, components: &mut Vec<Vec<Monomial<P>>>) {
+    let generators = minimalize(generators);
+    let Some(mixed) = generators.iter().find(|m| support(m) > 1) else {
+        components.push(generators);
+        return;
+    };
+    let i = mixed.powers.iter().position(|p| !p.is_zero()).unwrap();
+    let mut power = Monomial {
+        powers: smallvec![P::zero(); mixed.powers.len()],
+    };
+    power.powers[i] = mixed.powers[i];
+    let mut rest = mixed.clone();
+    rest.powers[i] = P::zero();
+    let mut with_power = generators.clone();
+    with_power.push(power);
+    split(with_power, components);
+    let mut with_rest = generators;
+    with_rest.push(rest);
+    split(with_rest, components);
+}
+
+/// A monomial ideal, an ideal of a polynomial ring generated by monomials,
+/// given by its minimal generators.
+///
+/// A polynomial is in a monomial ideal when each of its monomials is, and a
+/// monomial when one of the generators divides it, so its operations only
+/// compare exponents and need neither coefficients nor Gröbner bases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonomialIdeal<P> {
+    generators: Vec<Monomial<P>>,
+    vars: usize,
+}
+
+impl<P: PrimInt> MonomialIdeal<P> {
+    /// The ideal of the ring in `vars` variables generated by `monomials`,
+    /// which must each have `vars` exponents.
+    pub fn new(monomials: Vec<Monomial<P>>, vars: usize) -> Self {
+        Self {
+            generators: minimalize(monomials),
+            vars,
+        }
+    }
+
+    /// The minimal generators, the generators that no other one divides,
+    /// in increasing lexicographic order of their exponents.
+    pub fn generators(&self) -> &[Monomial<P>] {
+        &self.generators
+    }
+
+    /// The number of variables of the ring.
+    pub fn vars(&self) -> usize {
+        self.vars
+    }
+
+    /// Whether the ideal is zero.
+    pub fn is_zero(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// Whether the ideal is the whole ring, that is, whether it contains
+    /// `1`.
+    pub fn is_unit(&self) -> bool {
+        self.generators.iter().any(|m| support(m) == 0)
+    }
+
+    /// Whether the monomial `m` is in the ideal.
+    pub fn contains(&self, m: &Monomial<P>) -> bool {
+        self.generators.iter().any(|g| g.divides(m))
+    }
+
+    /// Whether `other` is contained in the ideal.
+    pub fn contains_ideal(&self, other: &Self) -> bool {
+        other.generators.iter().all(|m| self.contains(m))
+    }
+
+    /// The sum of the ideals.
+    pub fn sum(&self, other: &Self) -> Self {
+        let generators = self.generators.iter().chain(&other.generators).cloned();
+        Self::new(generators.collect(), self.vars)
+    }
+
+    /// The product of the ideals, or an `ExponentOverflow` error if an
+    /// exponent of a product of generators overflows `P`.
+    pub fn product(&self, other: &Self) -> Result<Self, ChidogError> {
+        let generators = self
+            .generators
+            .iter()
+            .flat_map(|m| other.generators.iter().map(|n| m.checked_mul(n)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(generators, self.vars))
+    }
+
+    /// The intersection of the ideals, generated by the least common
+    /// multiples of pairs of generators.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let generators = self
+            .generators
+            .iter()
+            .flat_map(|m| other.generators.iter().map(|n| m.lcm(n)))
+            .collect();
+        Self::new(generators, self.vars)
+    }
+
+    /// The ideal quotient `I : m` by the monomial `m`, generated by the
+    /// quotients of the generators by their greatest common divisors with
+    /// `m`.
+    pub fn quotient(&self, m: &Monomial<P>) -> Self {
+        let generators = self
+            .generators
+            .iter()
+            .map(|g| g.checked_div(&g.gcd(m)).unwrap())
+            .collect();
+        Self::new(generators, self.vars)
+    }
+
+    /// The radical, generated by the products of the variables of each
+    /// generator.
+    pub fn radical(&self) -> Self {
+        let generators = self
+            .generators
+            .iter()
+            .map(|m| Monomial {
+                powers: m
+                    .powers
+                    .iter()
+                    .map(|p| p.min(&P::one()).to_owned())
+                    .collect(),
+            })
+            .collect();
+        Self::new(generators, self.vars)
+    }
+
+    /// Whether the ideal is squarefree, generated by products of distinct
+    /// variables, which are the radical monomial ideals.
+    pub fn is_squarefree(&self) -> bool {
+        self.generators
+            .iter()
+            .all(|m| m.powers.iter().all(|p| *p <= P::one()))
+    }
+
+    /// Whether the ideal is irreducible, a proper ideal generated by powers
+    /// of variables.
+    pub fn is_irreducible(&self) -> bool {
+        self.generators.iter().all(|m| support(m) == 1)
+    }
+
+    /// The irredundant decomposition of the ideal as an intersection of
+    /// irreducible monomial ideals, which is unique, and empty for the whole
+    /// ring.
+    pub fn irreducible_decomposition(&self) -> Vec<Self> {
+        if self.is_unit() {
+            return Vec::new();
+        }
+        let mut components = Vec::new();
+        split(self.generators.clone(), &mut components);
+        let components: Vec<Self> = components
+            .into_iter()
+            .map(|generators| Self {
+                generators,
+                vars: self.vars,
+            })
+            .collect();
+        // An irreducible ideal containing an intersection contains one of
+        // its terms, so the redundant components are those containing
+        // another one, or repeating an earlier one.
+        let redundant: Vec<bool> = components
+            .iter()
+            .enumerate()
+            .map(|(i, j)| {
+                components
+                    .iter()
+                    .enumerate()
+                    .any(|(l, k)| j.contains_ideal(k) && (k != j || l < i))
+            })
+            .collect();
+        components
+            .into_iter()
+            .zip(redundant)
+            .filter_map(|(j, redundant)| (!redundant).then_some(j))
+            .collect()
+    }
+
+    /// A minimal primary decomposition of the ideal, with one primary
+    /// monomial ideal for each associated prime, the intersection of the
+    /// irreducible components with that radical.
+    pub fn primary_decomposition(&self) -> Vec<Self> {
+        let mut primaries: Vec<Self> = Vec::new();
+        for component in self.irreducible_decomposition() {
+            let radical = component.radical();
+            match primaries.iter_mut().find(|q| q.radical() == radical) {
+                Some(q) => *q = q.intersection(&component),
+                None => primaries.push(component),
+            }
+        }
+        primaries
+    }
+
+    /// The Alexander dual of the ideal with respect to the least common
+    /// multiple of its generators.
+    pub fn alexander_dual(&self) -> Self {
+        let a = self.generators.iter().fold(
+            Monomial {
+                powers: smallvec![P::zero(); self.vars],
+            },
+            |a, m| a.lcm(m),
+        );
+        self.alexander_dual_at(&a)
+            .expect("the least common multiple is divisible by the generators")
+    }
+
+    /// The Alexander dual `I^[a]` of the ideal with respect to `a`, generated
+    /// by the monomials with exponent `a_i + 1 - b_i` for each positive
+    /// `b_i`, for the irreducible components `(x_i^b_i : b_i > 0)` of the
+    /// ideal, after Miller and Sturmfels.
+    ///
+    /// This is an `InvalidInput` error unless each generator divides `a`,
+    /// and an `ExponentOverflow` error if `a_i + 1` overflows `P`. For a
+    /// squarefree ideal and `a = x_1*...*x_n`, the dual is the ideal
+    /// generated by the products of the variables of the minimal primes,
+    /// the Stanley-Reisner ideal of the dual simplicial complex.
+    pub fn alexander_dual_at(&self, a: &Monomial<P>) -> Result<Self, ChidogError> {
+        if a.powers.len() != self.vars || !self.generators.iter().all(|m| m.divides(a)) {
+            return Err(ChidogError::InvalidInput(
+                "the generators must divide the exponent of the Alexander dual".to_string(),
+            ));
+        }
+        let generators = self
+            .irreducible_decomposition()
+            .iter()
+            .map(|component| {
+                let mut powers: Powers<P> = smallvec![P::zero(); self.vars];
+                for (i, p) in component
+                    .generators
+                    .iter()
+                    .flat_map(|m| m.powers.iter().enumerate())
+                {
+                    if !p.is_zero() {
+                        let e = a.powers[i]
+                            .checked_add(&P::one())
+                            .ok_or(ChidogError::ExponentOverflow)?;
+                        powers[i] = e - *p;
+                    }
+                }
+                Ok(Monomial { powers })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(generators, self.vars))
+    }
+
+    /// The standard monomials, those outside the ideal, in increasing
+    /// [`GRevLex`] order, or `None` if there are infinitely many, that is,
+    /// unless the ideal contains a power of each variable.
+    ///
+    /// They are generated by increasing degree, each from its quotient by
+    /// its last variable, which is also standard.
+    pub fn standard_monomials(&self) -> Option<Vec<Monomial<P>>> {
+        if self.is_unit() {
+            return Some(Vec::new());
+        }
+        let finite = (0..self.vars).all(|i| {
+            self.generators
+                .iter()
+                .any(|m| support(m) == 1 && !m.powers[i].is_zero())
+        });
+        if !finite {
+            return None;
+        }
+        let mut standard = vec![Monomial {
+            powers: smallvec![P::zero(); self.vars],
+        }];
+        let mut level = standard.clone();
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for m in &level {
+                let last = m.powers.iter().rposition(|p| !p.is_zero()).unwrap_or(0);
+                for i in last..self.vars {
+                    let mut t = m.clone();
+                    t.powers[i] = t.powers[i] + P::one();
+                    if !self.contains(&t) {
+                        next.push(t);
+                    }
+                }
+            }
+            standard.extend(next.iter().cloned());
+            level = next;
+        }
+        standard.sort_unstable_by(|a, b| GRevLex.cmp(a, b));
+        Some(standard)
+    }
+
+    /// The Hilbert series of the quotient by the ideal.
+    pub fn hilbert_series(&self) -> HilbertSeries {
+        HilbertSeries::of_monomial_ideal(&self.generators, self.vars)
+    }
+}
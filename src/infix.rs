@@ -0,0 +1,298 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use num::{BigRational, One, PrimInt, Signed, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::expr::Expr;
+use crate::sexpr::number;
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// `f` in infix notation, such as `-1/2*x^2*y+3*z-1`, with its terms in
+/// decreasing lexicographic order and powers written with `power`.
+pub(crate) fn to_infix<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>, power: &str) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    to_infix_with(f, power, BigRational::to_string)
+}
+
+/// `f` in infix notation as by [`to_infix`], with the absolute values of
+/// coefficients written by `coefficient`.
+pub(crate) fn to_infix_with<R, V, K, P, S>(
+    f: &Polynomial<'_, R, V, K, P, S>,
+    power: &str,
+    coefficient: impl Fn(&BigRational) -> String,
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut terms: Vec<_> = f.terms.iter().collect();
+    terms.sort_unstable_by(|(a, _), (b, _)| b.powers.cmp(&a.powers));
+    let mut out = String::new();
+    for (i, (m, c)) in terms.into_iter().enumerate() {
+        let c: BigRational = c.clone().into();
+        if c.is_negative() {
+            out.push('-');
+        } else if i > 0 {
+            out.push('+');
+        }
+        let c = c.abs();
+        let mut factors = Vec::new();
+        if !c.is_one() || m.powers.iter().all(|p| p.is_zero()) {
+            factors.push(coefficient(&c));
+        }
+        for (var, p) in f.elem_of.vars.iter().zip(m.powers.iter()) {
+            if p.is_one() {
+                factors.push(var.to_string());
+            } else if !p.is_zero() {
+                factors.push(format!("{var}{power}{}", p.to_u64().unwrap()));
+            }
+        }
+        out.push_str(&factors.join("*"));
+    }
+    if out.is_empty() {
+        out.push('0');
+    }
+    out
+}
+
+/// `s` split at the commas outside brackets, each part trimmed.
+pub(crate) fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(BigRational),
+    /// A variable, with the exponent written right after it in short
+    /// notation, such as the `2` of `x2`.
+    Var(usize, u32),
+    Op(char),
+}
+
+/// The tokens of `input`, whose variables are the longest of `names`
+/// starting where they appear. With `short`, the digits right after a
+/// variable are its exponent, as in Singular's `x2y`.
+fn tokens(input: &str, names: &[String], short: bool) -> Result<Vec<Token>, ChidogError> {
+    let digits = |chars: &mut Peekable<CharIndices>, s: &mut String| {
+        while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            s.push(c);
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut s = String::new();
+            digits(&mut chars, &mut s);
+            if chars.next_if(|&(_, c)| c == '.').is_some() {
+                s.push('.');
+                digits(&mut chars, &mut s);
+            }
+            let n =
+                number(&s).ok_or_else(|| ChidogError::Parse(format!("invalid number `{s}`")))?;
+            tokens.push(Token::Number(n));
+        } else if input[start..].starts_with("**") {
+            chars.nth(1);
+            tokens.push(Token::Op('^'));
+        } else if "+-*/^()".contains(c) {
+            chars.next();
+            tokens.push(Token::Op(c));
+        } else {
+            let (i, name) = names
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| !name.is_empty() && input[start..].starts_with(name.as_str()))
+                .max_by_key(|(_, name)| name.len())
+                .ok_or_else(|| {
+                    let word: String = input[start..]
+                        .chars()
+                        .take_while(|c| c.is_alphanumeric() || *c == '_')
+                        .collect();
+                    ChidogError::InvalidInput(format!("unknown variable `{word}`"))
+                })?;
+            while chars.next_if(|&(j, _)| j < start + name.len()).is_some() {}
+            let mut exponent = String::new();
+            if short {
+                digits(&mut chars, &mut exponent);
+            }
+            let exponent = match exponent.as_str() {
+                "" => 1,
+                e => e.parse().map_err(|_| ChidogError::ExponentOverflow)?,
+            };
+            tokens.push(Token::Var(i, exponent));
+        }
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser of the sums of products of powers of
+/// [`tokens`], in which juxtaposition multiplies.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        let found = self.peek() == Some(&Token::Op(op));
+        self.pos += usize::from(found);
+        found
+    }
+
+    fn error(&self) -> ChidogError {
+        match self.peek() {
+            Some(t) => ChidogError::Parse(format!("unexpected {t:?}")),
+            None => ChidogError::Parse("unexpected end of input".to_string()),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expr<BigRational>, ChidogError> {
+        let mut terms = Vec::new();
+        let mut negative = self.eat('-');
+        if !negative {
+            self.eat('+');
+        }
+        loop {
+            let term = self.product()?;
+            terms.push(match negative {
+                true => negate(term),
+                false => term,
+            });
+            if self.eat('+') {
+                negative = false;
+            } else if self.eat('-') {
+                negative = true;
+            } else {
+                return Ok(Expr::Sum(terms));
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr<BigRational>, ChidogError> {
+        let mut factors = vec![self.power()?];
+        loop {
+            if self.eat('*') {
+                factors.push(self.power()?);
+            } else if self.eat('/') {
+                match self.power()?.simplify() {
+                    Expr::Constant(d) if !d.is_zero() => factors.push(Expr::Constant(d.recip())),
+                    _ => return Err(ChidogError::Parse("division by a non-constant".to_string())),
+                }
+            } else if matches!(
+                self.peek(),
+                Some(Token::Number(_) | Token::Var(..) | Token::Op('('))
+            ) {
+                factors.push(self.power()?);
+            } else {
+                return Ok(Expr::Product(factors));
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<Expr<BigRational>, ChidogError> {
+        if self.eat('-') {
+            return Ok(negate(self.power()?));
+        }
+        let token = self.peek().cloned();
+        self.pos += 1;
+        let base = match token {
+            Some(Token::Number(n)) => Expr::Constant(n),
+            Some(Token::Var(i, 1)) => Expr::Var(i),
+            Some(Token::Var(i, e)) => Expr::Var(i).pow(e),
+            Some(Token::Op('(')) => {
+                let e = self.sum()?;
+                if !self.eat(')') {
+                    return Err(self.error());
+                }
+                e
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error());
+            }
+        };
+        if !self.eat('^') {
+            return Ok(base);
+        }
+        let exponent = match self.peek() {
+            Some(Token::Number(n)) if n.is_integer() && !n.is_negative() => n
+                .to_integer()
+                .try_into()
+                .map_err(|_| ChidogError::ExponentOverflow)?,
+            _ => return Err(self.error()),
+        };
+        self.pos += 1;
+        Ok(base.pow(exponent))
+    }
+}
+
+fn negate(e: Expr<BigRational>) -> Expr<BigRational> {
+    Expr::Product(vec![Expr::Constant(-BigRational::one()), e])
+}
+
+/// The expression of the infix `input` in the variables `names`, made of
+/// numbers, which may be decimals, variables, `+`, `-`, `*`, `/` by
+/// constants, powers with `^` or `**` and parentheses, and in which
+/// juxtaposition multiplies. With `short`, the digits right after a
+/// variable are its exponent.
+pub(crate) fn parse_infix(
+    input: &str,
+    names: &[String],
+    short: bool,
+) -> Result<Expr<BigRational>, ChidogError> {
+    let mut parser = Parser {
+        tokens: tokens(input, names, short)?,
+        pos: 0,
+    };
+    let e = parser.sum()?;
+    match parser.peek() {
+        None => Ok(e),
+        Some(_) => Err(parser.error()),
+    }
+}
+
+/// The polynomial in `ring` of the infix `input`, as read by
+/// [`parse_infix`] with the names of the variables of `ring`.
+pub(crate) fn from_infix<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+    short: bool,
+) -> Result<Polynomial<'a, R, V, BigRational, P>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let names: Vec<String> = ring.vars.iter().map(|v| v.to_string()).collect();
+    parse_infix(input, &names, short)?.to_polynomial(ring)
+}
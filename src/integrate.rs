@@ -0,0 +1,284 @@
+use std::fmt::Display;
+
+use num::{FromPrimitive, One, Zero};
+
+use crate::FieldElement;
+use crate::error::ChidogError;
+use crate::univariate::{DenseUnivariate, DivExact};
+
+/// Polynomials in `x` whose coefficients are polynomials in a second variable
+/// `t`.
+type Bivariate<K> = DenseUnivariate<DenseUnivariate<K>>;
+
+/// The sum `∑ a*log(argument(a, x))` over the roots `a` of `roots_of`, in an
+/// algebraic closure of the coefficient field.
+///
+/// `roots_of` is monic and squarefree, and `argument` is a polynomial in `x`
+/// whose coefficients are polynomials in `a`, reduced modulo `roots_of`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogTerm<K> {
+    pub roots_of: DenseUnivariate<K>,
+    pub argument: Bivariate<K>,
+}
+
+/// The antiderivative of a rational function: a rational part `numer /
+/// denom` plus a sum of logarithmic terms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RationalIntegral<K> {
+    pub numer: DenseUnivariate<K>,
+    pub denom: DenseUnivariate<K>,
+    pub logs: Vec<LogTerm<K>>,
+}
+
+impl<K: FieldElement + Clone> LogTerm<K> {
+    /// If `roots_of` is linear, its unique root `a` and the polynomial
+    /// `argument(a, x)`, so that the term is `a*log(argument(a, x))`.
+    pub fn rational_root(&self) -> Option<(K, DenseUnivariate<K>)> {
+        if self.roots_of.degree() != Some(1) {
+            return None;
+        }
+        let a = K::zero() - self.roots_of.coeff(0);
+        let arg = self.argument.map(|c| c.eval(&a));
+        Some((a, arg))
+    }
+}
+
+fn negate<K: FieldElement + Clone>(c: K) -> K {
+    K::zero() - c
+}
+
+fn from_usize<K: FieldElement + FromPrimitive>(n: usize) -> K {
+    K::from_usize(n).unwrap()
+}
+
+/// Hermite reduction: given `a / d` with `deg(a) < deg(d)` and `d` monic,
+/// returns `(g_numer, g_denom, h_numer, h_denom)` such that `a/d = (g_numer /
+/// g_denom)' + h_numer / h_denom` and `h_denom` is squarefree.
+///
+/// This is the quadratic version of Bronstein, *Symbolic Integration I*,
+/// §2.2, which needs only the squarefree decomposition of `d` and extended
+/// Euclidean steps.
+#[allow(clippy::type_complexity)]
+fn hermite_reduce<K>(
+    a: &DenseUnivariate<K>,
+    d: &DenseUnivariate<K>,
+) -> (
+    DenseUnivariate<K>,
+    DenseUnivariate<K>,
+    DenseUnivariate<K>,
+    DenseUnivariate<K>,
+)
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    let (mut gn, mut gd) = (DenseUnivariate::zero(), DenseUnivariate::one());
+    let (mut a, mut d) = (a.clone(), d.clone());
+    for (i, v) in d.squarefree().into_iter().enumerate().skip(1) {
+        let i = i + 1;
+        if v.is_constant() {
+            continue;
+        }
+        let u = d.div_exact(&num::pow(v.clone(), i)).unwrap();
+        let uv = u.clone() * v.derivative();
+        for j in (1..i).rev() {
+            let rhs = a.scale(&negate(K::one() / from_usize(j)));
+            let (b, c) = uv
+                .solve_bezout(&v, &rhs)
+                .expect("squarefree factors are coprime to their derivatives");
+            let vj = num::pow(v.clone(), j);
+            gn = gn * vj.clone() + b.clone() * gd.clone();
+            gd *= vj;
+            a = c.scale(&negate(from_usize(j))) - u.clone() * b.derivative();
+        }
+        d = u * v;
+    }
+    (gn, gd, a, d)
+}
+
+/// The logarithmic part of the integral of `a / d`, where `deg(a) < deg(d)`,
+/// `d` is squarefree, and `gcd(a, d) = 1`.
+///
+/// This is the Rothstein–Trager method with the improvement of Lazard and
+/// Rioboo (Bronstein, *Symbolic Integration I*, §2.5): the coefficients of
+/// the logarithms are the roots of the resultant `res_x(d, a - t*d')`, and
+/// the argument of the logarithms for roots of multiplicity `i` is read off
+/// the subresultant of degree `i`, avoiding computations in algebraic
+/// extensions.
+fn log_part<K>(a: &DenseUnivariate<K>, d: &DenseUnivariate<K>) -> Vec<LogTerm<K>>
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    let lift = |f: &DenseUnivariate<K>| f.map(|c| DenseUnivariate::constant(c.clone()));
+    let db = lift(d);
+    let rhs = lift(a)
+        - d.derivative()
+            .map(|c| DenseUnivariate::monomial(c.clone(), 1));
+    let (res, prs) = db.subresultant_prs(&rhs);
+    let mut logs = Vec::new();
+    for (i, q) in res.squarefree().into_iter().enumerate() {
+        let i = i + 1;
+        if q.is_constant() {
+            continue;
+        }
+        let mut s = if Some(i) == d.degree() {
+            db.clone()
+        } else {
+            prs[1..]
+                .iter()
+                .find(|r| r.degree() == Some(i))
+                .expect("subresultant of each root multiplicity exists")
+                .clone()
+        };
+        let lc = s.leading_coeff().unwrap().clone();
+        for (j, aj) in lc.squarefree().into_iter().enumerate() {
+            let g = num::pow(aj.gcd(&q), j + 1);
+            s = s.map(|c| c.div_exact(&g).unwrap());
+        }
+        let q = q.monic();
+        let mut argument = s.map(|c| c.div_rem(&q).unwrap().1);
+        // Make the argument monic in `x`, multiplying by the inverse of its
+        // leading coefficient modulo `q`.
+        let (g, inv, _) = argument.leading_coeff().unwrap().extended_gcd(&q);
+        if g.degree() == Some(0) {
+            argument = argument.map(|c| (c.clone() * inv.clone()).div_rem(&q).unwrap().1);
+        }
+        logs.push(LogTerm {
+            roots_of: q,
+            argument,
+        });
+    }
+    logs
+}
+
+/// Integrates the rational function `numer / denom` over a field of
+/// characteristic zero.
+///
+/// The result consists of a rational part, computed by Hermite reduction,
+/// and logarithmic terms whose coefficients are algebraic over the field of
+/// coefficients, computed by the Rothstein–Trager method. The rational part
+/// is reduced, with monic denominator.
+pub fn integrate_rational<K>(
+    numer: &DenseUnivariate<K>,
+    denom: &DenseUnivariate<K>,
+) -> Result<RationalIntegral<K>, ChidogError>
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    let Some(lc) = denom.leading_coeff() else {
+        return Err(ChidogError::DivisionByZero);
+    };
+    let g = numer.gcd(denom);
+    let a = numer.div_exact(&g).unwrap().scale(&(K::one() / lc.clone()));
+    let d = denom.div_exact(&g).unwrap().monic();
+    let (p, a) = a.div_rem(&d)?;
+    let (gn, gd, a, d) = hermite_reduce(&a, &d);
+    let g = a.gcd(&d);
+    let (a, d) = (a.div_exact(&g).unwrap(), d.div_exact(&g).unwrap());
+    let (p2, a) = a.div_rem(&d)?;
+    let logs = if a.is_zero() {
+        Vec::new()
+    } else {
+        log_part(&a, &d)
+    };
+    let poly = (p + p2).integral();
+    let numer = poly * gd.clone() + gn;
+    let g = numer.gcd(&gd);
+    let denom = gd.div_exact(&g).unwrap();
+    let lc = denom.leading_coeff().unwrap().clone();
+    Ok(RationalIntegral {
+        numer: numer.div_exact(&g).unwrap().scale(&(K::one() / lc)),
+        denom: denom.monic(),
+        logs,
+    })
+}
+
+/// Displays `f` in the variable `x`, with coefficients displayed as
+/// polynomials in `a`.
+fn fmt_bivariate<K>(f: &Bivariate<K>, out: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+where
+    K: FieldElement + Clone + Display + PartialEq,
+{
+    let mut first = true;
+    for (i, c) in f.coeffs().iter().enumerate().rev() {
+        if c.is_zero() {
+            continue;
+        }
+        let plus = if first { "" } else { "+" };
+        if c.coeffs().iter().filter(|k| !k.is_zero()).count() > 1 {
+            write!(out, "{plus}({})", c.display("a"))?;
+            if i > 0 {
+                write!(out, "*")?;
+            }
+        } else if i > 0 && c.is_one() {
+            write!(out, "{plus}")?;
+        } else {
+            if first {
+                write!(out, "{}", c.display("a"))?;
+            } else {
+                write!(out, "{:+}", c.display("a"))?;
+            }
+            if i > 0 {
+                write!(out, "*")?;
+            }
+        }
+        match i {
+            0 => {}
+            1 => write!(out, "x")?,
+            _ => write!(out, "x^{i}")?,
+        }
+        first = false;
+    }
+    if first {
+        write!(out, "0")?;
+    }
+    Ok(())
+}
+
+/// Displays the term as `a*log(s)` when its coefficient `a` is rational, and
+/// as `∑_{a: q(a)=0} a*log(s(a, x))` otherwise.
+impl<K> Display for LogTerm<K>
+where
+    K: FieldElement + Clone + Display + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((a, arg)) = self.rational_root() {
+            let sign = if f.sign_plus() { "+" } else { "" };
+            return write!(f, "{sign}({a})*log({arg})");
+        }
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        write!(f, "∑_{{a: {}=0}} a*log(", self.roots_of.display("a"))?;
+        fmt_bivariate(&self.argument, f)?;
+        write!(f, ")")
+    }
+}
+
+impl<K> Display for RationalIntegral<K>
+where
+    K: FieldElement + Clone + Display + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        if !self.numer.is_zero() {
+            if self.denom.is_one() {
+                write!(f, "{}", self.numer)?;
+            } else {
+                write!(f, "({})/({})", self.numer, self.denom)?;
+            }
+            first = false;
+        }
+        for log in self.logs.iter() {
+            if first {
+                write!(f, "{log}")?;
+            } else {
+                write!(f, "{log:+}")?;
+            }
+            first = false;
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
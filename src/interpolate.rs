@@ -0,0 +1,133 @@
+use std::hash::Hash;
+
+use num::{FromPrimitive, PrimInt, Unsigned};
+
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::recurrence::berlekamp_massey;
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing};
+
+/// The first `n` primes.
+fn primes(n: usize) -> Vec<u64> {
+    let mut primes: Vec<u64> = Vec::with_capacity(n);
+    let mut candidate = 2;
+    while primes.len() < n {
+        if primes.iter().all(|p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// Reconstructs the polynomial computed by the black box `oracle`, which must
+/// have at most `max_terms` terms, each of total degree at most `max_degree`,
+/// by the algorithm of Ben-Or and Tiwari.
+///
+/// The black box is evaluated at the powers `(p_1^i, ..., p_n^i)` of the
+/// first `n` primes, for `i < 2*max_terms`. A term `c*m` contributes `c*m(p)^i`
+/// to the `i`th value, so Berlekamp–Massey finds the polynomial whose roots
+/// are the values `m(p)` of the monomials of the result. These determine the
+/// monomials by unique factorization, and the coefficients are the solution of
+/// a transposed Vandermonde system.
+///
+/// The number of evaluations depends only on the number of terms, not on the
+/// degree, but finding the roots tests every monomial of degree at most
+/// `max_degree`. The coefficient field must have characteristic zero, or
+/// large enough that the values of distinct monomials stay distinct.
+///
+/// Fails with `InvalidInput` if the values are inconsistent with the bounds,
+/// as checked by one further evaluation at the next `n` primes.
+pub fn interpolate_sparse<'a, R, V, K, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    oracle: impl Fn(&[K]) -> K,
+    max_terms: usize,
+    max_degree: usize,
+) -> Result<Polynomial<'a, R, V, K, P>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = ring.vars.len();
+    let invalid = || {
+        ChidogError::InvalidInput(format!(
+            "the black box is not a polynomial with at most {max_terms} terms \
+             of degree at most {max_degree}"
+        ))
+    };
+    let primes: Vec<K> = primes(2 * n)
+        .into_iter()
+        .map(|p| K::from_u64(p).unwrap())
+        .collect();
+    let (base, check) = primes.split_at(n);
+
+    let mut point: Vec<K> = vec![K::one(); n];
+    let mut values = Vec::with_capacity(2 * max_terms);
+    for _ in 0..2 * max_terms {
+        values.push(oracle(&point));
+        for (x, p) in point.iter_mut().zip(base) {
+            *x *= p.clone();
+        }
+    }
+    let recurrence = berlekamp_massey(&values);
+    let t = recurrence.length;
+    if t > max_terms || recurrence.connection.degree() != Some(t) {
+        return Err(invalid());
+    }
+    let lambda = recurrence.minimal_polynomial();
+
+    // The roots of `lambda` among the values of the monomials of degree at
+    // most `max_degree`, visited depth first by exponent of each variable.
+    let mut roots: Vec<(Vec<usize>, K)> = Vec::with_capacity(t);
+    let mut stack = vec![(Vec::with_capacity(n), K::one(), 0)];
+    while let Some((exponents, value, degree)) = stack.pop() {
+        if roots.len() == t {
+            break;
+        }
+        if exponents.len() == n {
+            if ring.base.is_zero(&lambda.eval(&value)) {
+                roots.push((exponents, value));
+            }
+            continue;
+        }
+        let p = &base[exponents.len()];
+        let mut power = value;
+        for e in 0..=max_degree - degree {
+            let mut next = exponents.clone();
+            next.push(e);
+            stack.push((next, power.clone(), degree + e));
+            power *= p.clone();
+        }
+    }
+    if roots.len() < t {
+        return Err(invalid());
+    }
+
+    let mut vandermonde = Matrix::zero(t, t);
+    for (k, (_, value)) in roots.iter().enumerate() {
+        let mut power = K::one();
+        for i in 0..t {
+            vandermonde[(i, k)] = power.clone();
+            power *= value.clone();
+        }
+    }
+    let coeffs = vandermonde.solve(&values[..t]).ok_or_else(invalid)?;
+    let terms = roots
+        .into_iter()
+        .zip(coeffs)
+        .filter(|(_, c)| !ring.base.is_zero(c))
+        .map(|((exponents, _), c)| {
+            let powers = exponents.into_iter().map(|e| P::from(e).unwrap()).collect();
+            (Monomial { powers }, c)
+        })
+        .collect();
+    let f = Polynomial {
+        elem_of: ring,
+        terms,
+    };
+    if !ring.base.is_zero(&(f.eval(check) - oracle(check))) {
+        return Err(invalid());
+    }
+    Ok(f)
+}
@@ -0,0 +1,287 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{BigRational, One, Zero};
+
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// A type usable as the endpoints of an [`Interval`].
+///
+/// Implementations must produce bounds which are guaranteed to enclose the
+/// exact result of each operation, rounding outward where the endpoint type
+/// cannot represent it, and which are never NaN.
+pub trait Endpoint: Clone + PartialOrd + Zero + One {
+    /// A lower and an upper bound for the exact value of `self + rhs`.
+    fn add_bounds(&self, rhs: &Self) -> (Self, Self);
+
+    /// A lower and an upper bound for the exact value of `self * rhs`.
+    fn mul_bounds(&self, rhs: &Self) -> (Self, Self);
+
+    /// The exact negation of `self`.
+    fn negate(&self) -> Self;
+}
+
+impl Endpoint for BigRational {
+    fn add_bounds(&self, rhs: &Self) -> (Self, Self) {
+        let sum = self + rhs;
+        (sum.clone(), sum)
+    }
+
+    fn mul_bounds(&self, rhs: &Self) -> (Self, Self) {
+        let product = self * rhs;
+        (product.clone(), product)
+    }
+
+    fn negate(&self) -> Self {
+        -self
+    }
+}
+
+/// The bounds of the whole line, which enclose the undefined results of
+/// `∞ - ∞` and `0 * ∞`.
+const WHOLE_LINE: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+
+impl Endpoint for f64 {
+    fn add_bounds(&self, rhs: &Self) -> (Self, Self) {
+        // Knuth's TwoSum recovers the exact rounding error of the sum.
+        let sum = self + rhs;
+        if sum.is_nan() {
+            return WHOLE_LINE;
+        }
+        let rhs_part = sum - self;
+        let error = (self - (sum - rhs_part)) + (rhs - rhs_part);
+        if error > 0.0 {
+            (sum, sum.next_up())
+        } else if error < 0.0 {
+            (sum.next_down(), sum)
+        } else if error == 0.0 {
+            (sum, sum)
+        } else {
+            (sum.next_down(), sum.next_up())
+        }
+    }
+
+    fn mul_bounds(&self, rhs: &Self) -> (Self, Self) {
+        let product = self * rhs;
+        if product.is_nan() {
+            return WHOLE_LINE;
+        }
+        // The fused multiply-add recovers the exact rounding error, except
+        // when the product underflows; there we widen in both directions.
+        let error = self.mul_add(*rhs, -product);
+        let exact = (product.is_normal() && error == 0.0) || *self == 0.0 || *rhs == 0.0;
+        if exact {
+            (product, product)
+        } else if product.is_normal() && error > 0.0 {
+            (product, product.next_up())
+        } else if product.is_normal() && error < 0.0 {
+            (product.next_down(), product)
+        } else {
+            (product.next_down(), product.next_up())
+        }
+    }
+
+    fn negate(&self) -> Self {
+        -self
+    }
+}
+
+/// A closed interval `[lo, hi]`, representing an unknown value known to lie
+/// within it.
+///
+/// Arithmetic on intervals produces an interval enclosing every possible
+/// result, so that a polynomial with interval coefficients rigorously
+/// encloses every polynomial whose coefficients lie in the respective
+/// intervals. Since an interval straddling zero may or may not represent zero,
+/// `Zero::is_zero` holds only for the degenerate interval `[0, 0]`, so that
+/// polynomial arithmetic never discards a possibly nonzero term; use
+/// [`Interval::zero_test`] to distinguish the undecided case.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interval<T> {
+    lo: T,
+    hi: T,
+}
+
+/// The outcome of testing whether an [`Interval`] represents zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZeroTest {
+    /// The interval is exactly `[0, 0]`.
+    Zero,
+    /// The interval does not contain zero.
+    NonZero,
+    /// The interval contains zero along with other values.
+    Unknown,
+}
+
+/// The ring of intervals with endpoints of type `T`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IntervalRing<T> {
+    phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Endpoint> Semiring<Interval<T>> for IntervalRing<T> {}
+impl<T: Endpoint> Ring<Interval<T>> for IntervalRing<T> {}
+impl<T: Endpoint> SemiringElement for Interval<T> {}
+impl<T: Endpoint> RingElement for Interval<T> {}
+
+impl<T: Endpoint> Interval<T> {
+    /// The interval `[lo, hi]`.
+    ///
+    /// Panics unless `lo <= hi` (in particular, if either endpoint is NaN).
+    pub fn new(lo: T, hi: T) -> Self {
+        assert!(lo <= hi, "interval endpoints out of order");
+        Self { lo, hi }
+    }
+
+    /// The degenerate interval `[x, x]`.
+    pub fn point(x: T) -> Self {
+        Self::new(x.clone(), x)
+    }
+
+    pub fn lo(&self) -> &T {
+        &self.lo
+    }
+
+    pub fn hi(&self) -> &T {
+        &self.hi
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.lo <= *x && *x <= self.hi
+    }
+
+    /// An upper bound for `hi - lo`.
+    pub fn width(&self) -> T {
+        self.hi.add_bounds(&self.lo.negate()).1
+    }
+
+    /// Decides, as far as possible, whether `self` represents zero.
+    pub fn zero_test(&self) -> ZeroTest {
+        if self.lo.is_zero() && self.hi.is_zero() {
+            ZeroTest::Zero
+        } else if self.contains(&T::zero()) {
+            ZeroTest::Unknown
+        } else {
+            ZeroTest::NonZero
+        }
+    }
+}
+
+impl<T: Endpoint> Add for Interval<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            lo: self.lo.add_bounds(&rhs.lo).0,
+            hi: self.hi.add_bounds(&rhs.hi).1,
+        }
+    }
+}
+
+impl<T: Endpoint> Neg for Interval<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            lo: self.hi.negate(),
+            hi: self.lo.negate(),
+        }
+    }
+}
+
+impl<T: Endpoint> Sub for Interval<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: Endpoint> Mul for Interval<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let bounds = [
+            self.lo.mul_bounds(&rhs.lo),
+            self.lo.mul_bounds(&rhs.hi),
+            self.hi.mul_bounds(&rhs.lo),
+            self.hi.mul_bounds(&rhs.hi),
+        ];
+        let mut lo = bounds[0].0.clone();
+        let mut hi = bounds[0].1.clone();
+        for (l, h) in bounds.into_iter().skip(1) {
+            if l < lo {
+                lo = l;
+            }
+            if h > hi {
+                hi = h;
+            }
+        }
+        Self { lo, hi }
+    }
+}
+
+impl<T: Endpoint> AddAssign for Interval<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl<T: Endpoint> SubAssign for Interval<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.clone() - rhs;
+    }
+}
+
+impl<T: Endpoint> MulAssign for Interval<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<T: Endpoint> Zero for Interval<T> {
+    fn zero() -> Self {
+        Self::point(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.zero_test() == ZeroTest::Zero
+    }
+}
+
+impl<T: Endpoint> One for Interval<T> {
+    fn one() -> Self {
+        Self::point(T::one())
+    }
+}
+
+/// Intervals are constructed only from ordered endpoints, and the undefined
+/// results of arithmetic are the whole line, so they never contain NaN,
+/// making equality reflexive.
+impl<T: Endpoint> Eq for Interval<T> {}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undefined_results_are_the_whole_line() {
+        let infinity = Interval::point(f64::INFINITY);
+        let whole = Interval::new(f64::NEG_INFINITY, f64::INFINITY);
+        let difference = infinity.clone() - infinity.clone();
+        assert_eq!(difference, whole);
+        assert_eq!(difference, difference);
+        let product = infinity * Interval::new(0.0, 1.0);
+        assert_eq!(product, whole);
+        assert!(whole.contains(&0.0));
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{FromPrimitive, PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::groebner::groebner_basis;
+use crate::matrix::Matrix;
+use crate::monomials::monomials_of_degree;
+use crate::order::GRevLex;
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing};
+
+/// A finite group of invertible `n × n` matrices, acting on polynomials in
+/// `n` variables by the linear substitution `(g·f)(x) = f(g*x)`, which maps
+/// `x_i` to `∑ g_ij*x_j`.
+///
+/// The group is stored as the list of its elements, the first of which is
+/// the identity. Its invariants are computed in characteristic zero, or at
+/// least in a characteristic which does not divide the order of the group,
+/// where the Reynolds operator is defined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixGroup<K> {
+    n: usize,
+    elements: Vec<Matrix<K>>,
+}
+
+impl<K: FieldElement + Clone + PartialEq> MatrixGroup<K> {
+    /// The group of `n × n` matrices generated by `generators`, which must
+    /// generate a finite group, since its elements are enumerated.
+    pub fn generated_by(n: usize, generators: &[Matrix<K>]) -> Result<Self, ChidogError> {
+        if let Some(g) = generators.iter().find(|g| g.nrows() != n || g.ncols() != n) {
+            return Err(ChidogError::InvalidInput(format!(
+                "a {}×{} matrix does not act on {n} variables",
+                g.nrows(),
+                g.ncols()
+            )));
+        }
+        if generators.iter().any(|g| g.inverse().is_none()) {
+            return Err(ChidogError::InvalidInput(
+                "the generators of a matrix group must be invertible".to_string(),
+            ));
+        }
+        let mut elements = vec![Matrix::identity(n)];
+        let mut i = 0;
+        while i < elements.len() {
+            for g in generators {
+                let h = g.clone() * elements[i].clone();
+                if !elements.contains(&h) {
+                    elements.push(h);
+                }
+            }
+            i += 1;
+        }
+        Ok(Self { n, elements })
+    }
+
+    /// The group of permutations of the `n` variables generated by
+    /// `generators`, each given by its list of images, so that the
+    /// permutation `p` maps `x_i` to `x_(p[i])`.
+    pub fn from_permutations(n: usize, generators: &[Vec<usize>]) -> Result<Self, ChidogError> {
+        let mut matrices = Vec::with_capacity(generators.len());
+        for p in generators {
+            let mut seen = vec![false; n];
+            if p.len() != n
+                || p.iter()
+                    .any(|&i| i >= n || std::mem::replace(&mut seen[i], true))
+            {
+                return Err(ChidogError::InvalidInput(format!(
+                    "{p:?} is not a permutation of 0..{n}"
+                )));
+            }
+            let mut m = Matrix::zero(n, n);
+            for (i, &j) in p.iter().enumerate() {
+                m[(i, j)] = K::one();
+            }
+            matrices.push(m);
+        }
+        Self::generated_by(n, &matrices)
+    }
+}
+
+impl<K> MatrixGroup<K> {
+    /// The number of variables acted on.
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// The elements of the group, starting with the identity.
+    pub fn elements(&self) -> &[Matrix<K>] {
+        &self.elements
+    }
+}
+
+/// The size of the largest set of variables containing the support of none
+/// of `monomials`, which is the Krull dimension of the quotient by the
+/// monomial ideal they generate.
+///
+/// All subsets are tried, which is fine for the few variables of invariant
+/// theory.
+fn dimension<P: PrimInt>(monomials: &[Monomial<P>], n: usize) -> usize {
+    let supports: Vec<u64> = monomials
+        .iter()
+        .map(|m| {
+            m.powers
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| !p.is_zero())
+                .fold(0, |s, (i, _)| s | 1 << i)
+        })
+        .collect();
+    (0..1u64 << n)
+        .filter(|set| supports.iter().all(|s| s & !set != 0))
+        .map(|set| set.count_ones() as usize)
+        .max()
+        .unwrap_or(0)
+}
+
+impl<K> MatrixGroup<K>
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    /// The polynomial `g·f` for the `g`th element of the group.
+    ///
+    /// Monomial matrices, such as permutations, map each term to a single
+    /// term; other matrices expand the products of linear forms.
+    pub fn act<'a, R, V, P>(
+        &self,
+        g: usize,
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Field<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        let g = &self.elements[g];
+        let n = self.n;
+        let ring = f.elem_of;
+        let monomial: Option<Vec<(usize, K)>> = (0..n)
+            .map(|i| {
+                let mut row = (0..n).filter(|&j| !g[(i, j)].is_zero());
+                match (row.next(), row.next()) {
+                    (Some(j), None) => Some((j, g[(i, j)].clone())),
+                    _ => None,
+                }
+            })
+            .collect();
+        if let Some(rows) = monomial {
+            let terms = f.terms.iter().map(|(m, c)| {
+                let mut powers = smallvec![P::zero(); n];
+                let mut c = c.clone();
+                for (p, (j, a)) in m.powers.iter().zip(&rows) {
+                    powers[*j] = powers[*j] + *p;
+                    for _ in 0..p.to_usize().unwrap() {
+                        c *= a.clone();
+                    }
+                }
+                (Monomial { powers }, c)
+            });
+            return Polynomial::from_terms(ring, terms)
+                .expect("the monomials have one exponent for each variable");
+        }
+        let forms: Vec<Polynomial<'a, R, V, K, P>> = (0..n)
+            .map(|i| {
+                let terms = (0..n).map(|j| {
+                    let mut powers = smallvec![P::zero(); n];
+                    powers[j] = P::one();
+                    (Monomial { powers }, g[(i, j)].clone())
+                });
+                Polynomial::from_terms(ring, terms)
+                    .expect("the monomials have one exponent for each variable")
+            })
+            .collect();
+        let mut image = Polynomial {
+            elem_of: ring,
+            terms: HashMap::default(),
+        };
+        for (m, c) in &f.terms {
+            let one = Monomial {
+                powers: smallvec![P::zero(); n],
+            };
+            let mut t = Polynomial::from_terms(ring, [(one, c.clone())])
+                .expect("the monomials have one exponent for each variable");
+            for (p, form) in m.powers.iter().zip(&forms) {
+                for _ in 0..p.to_usize().unwrap() {
+                    t *= form.clone();
+                }
+            }
+            image += t;
+        }
+        image
+    }
+
+    /// The Reynolds operator, averaging `f` over the group into the invariant
+    /// `(1/|G|)*∑ g·f`, which is `f` itself if `f` is invariant.
+    pub fn reynolds<'a, R, V, P>(
+        &self,
+        f: &Polynomial<'a, R, V, K, P>,
+    ) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Field<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        let mut sum = Polynomial {
+            elem_of: f.elem_of,
+            terms: HashMap::default(),
+        };
+        for g in 0..self.order() {
+            sum += self.act(g, f);
+        }
+        let order = K::one() / K::from_usize(self.order()).unwrap();
+        scale(&sum, &order)
+    }
+
+    /// Whether `g·f = f` for every element `g` of the group.
+    pub fn is_invariant<R, V, P>(&self, f: &Polynomial<'_, R, V, K, P>) -> bool
+    where
+        R: Field<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        (1..self.order()).all(|g| (self.act(g, f) - f.clone()).terms.is_empty())
+    }
+
+    /// Primary invariants of the group: homogeneous invariants `θ_1, ...,
+    /// θ_n` in the variables of `ring`, which form a system of parameters,
+    /// so that the invariant ring is a finitely generated free module over
+    /// `K[θ_1, ..., θ_n]`.
+    ///
+    /// The candidates are the images of the monomials under the Reynolds
+    /// operator, by increasing degree up to Noether's bound `|G|`, which
+    /// suffices in characteristic zero. Each is kept if it lowers the
+    /// dimension of the ideal of those kept before, as found from a Gröbner
+    /// basis, and otherwise the sum of all candidates of the degree with
+    /// distinct coefficients is tried. An `InvalidInput` error is returned if
+    /// this greedy search still ends short of `n` invariants.
+    pub fn primary_invariants<'a, R, V, P>(
+        &self,
+        ring: &'a PolynomialRing<'a, R, V>,
+    ) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+    where
+        R: Field<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        self.check_ring(ring)?;
+        let n = self.n;
+        let mut primary: Vec<Polynomial<'a, R, V, K, P>> = Vec::new();
+        let mut dim = n;
+        // The dimension of the ideal of the primary invariants and `f`.
+        let dimension_with = |primary: &[Polynomial<'a, R, V, K, P>],
+                              f: &Polynomial<'a, R, V, K, P>| {
+            let mut generators = primary.to_vec();
+            generators.push(f.clone());
+            let basis = groebner_basis(&generators, &GRevLex);
+            let leads: Vec<_> = basis
+                .iter()
+                .map(|g| g.leading_term(&GRevLex).unwrap().0.clone())
+                .collect();
+            dimension(&leads, n)
+        };
+        for d in 1..=self.order() {
+            if dim == 0 {
+                break;
+            }
+            let mut candidates: Vec<Polynomial<'a, R, V, K, P>> = Vec::new();
+            for m in monomials_of_degree(n, d) {
+                let one = Polynomial::from_terms(ring, [(m, K::one())])?;
+                let r = self.reynolds(&one);
+                if !r.terms.is_empty()
+                    && candidates
+                        .iter()
+                        .all(|c| !(c.clone() - r.clone()).terms.is_empty())
+                {
+                    candidates.push(r);
+                }
+            }
+            if candidates.is_empty() {
+                continue;
+            }
+            let mut combination = Polynomial {
+                elem_of: ring,
+                terms: HashMap::default(),
+            };
+            for (i, c) in candidates.iter().enumerate() {
+                combination += scale(c, &K::from_usize(i + 1).unwrap());
+            }
+            candidates.push(combination);
+            while dim > 0 {
+                let Some((f, e)) = candidates
+                    .iter()
+                    .map(|f| (f, dimension_with(&primary, f)))
+                    .find(|(_, e)| *e < dim)
+                else {
+                    break;
+                };
+                primary.push(f.clone());
+                dim = e;
+            }
+        }
+        if dim > 0 {
+            return Err(ChidogError::InvalidInput(format!(
+                "no system of parameters found among the invariants of degree at most {}",
+                self.order()
+            )));
+        }
+        Ok(primary)
+    }
+
+    /// Secondary invariants of the group for the primary invariants
+    /// `primary`, of degrees `d_1, ..., d_n`: homogeneous invariants `η_1,
+    /// ..., η_m`, with `η_1 = 1`, such that the invariant ring is the free
+    /// `K[θ_1, ..., θ_n]`-module with basis `η_1, ..., η_m`, where `m = d_1
+    /// *...*d_n/|G|`.
+    ///
+    /// The secondary invariants of each degree, up to `∑ (d_i - 1)`, are the
+    /// images under the Reynolds operator of the standard monomials of the
+    /// ideal of the primary invariants, chosen so that their normal forms
+    /// modulo that ideal are linearly independent. An `InvalidInput` error is
+    /// returned if `primary` are not invariants forming a system of
+    /// parameters.
+    pub fn secondary_invariants<'a, R, V, P>(
+        &self,
+        primary: &[Polynomial<'a, R, V, K, P>],
+    ) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+    where
+        R: Field<K>,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        let invalid = |msg: &str| Err(ChidogError::InvalidInput(msg.to_string()));
+        let Some(ring) = primary.first().map(|f| f.elem_of) else {
+            return invalid("there are no primary invariants");
+        };
+        self.check_ring(ring)?;
+        if primary.len() != self.n || !primary.iter().all(|f| self.is_invariant(f)) {
+            return invalid("the primary invariants are not n invariants");
+        }
+        let basis = groebner_basis(primary, &GRevLex);
+        let leads: Vec<_> = basis
+            .iter()
+            .map(|g| g.leading_term(&GRevLex).unwrap().0.clone())
+            .collect();
+        if dimension(&leads, self.n) > 0 {
+            return invalid("the primary invariants are not a system of parameters");
+        }
+        let degrees: Vec<usize> = primary.iter().map(total_degree).collect();
+        let product: usize = degrees.iter().product();
+        if !product.is_multiple_of(self.order()) {
+            return invalid("the primary invariants are not a system of parameters");
+        }
+        let count = product / self.order();
+        let mut secondary = Vec::with_capacity(count);
+        for d in 0..=degrees.iter().map(|d| d - 1).sum() {
+            // The normal forms of the secondary invariants of degree `d`, in
+            // echelon form, by leading monomial.
+            let mut echelon: HashMap<Monomial<P>, Polynomial<'a, R, V, K, P>> = HashMap::new();
+            for m in monomials_of_degree(self.n, d) {
+                if secondary.len() == count {
+                    return Ok(secondary);
+                }
+                if leads.iter().any(|l| l.divides(&m)) {
+                    continue;
+                }
+                let r = self.reynolds(&Polynomial::from_terms(ring, [(m, K::one())])?);
+                let mut normal = r.rem(&basis, &GRevLex);
+                while !normal.terms.is_empty() {
+                    let (lead, c) = normal.leading_term(&GRevLex).unwrap();
+                    let Some(row) = echelon.get(lead) else {
+                        break;
+                    };
+                    normal -= scale(row, c);
+                }
+                if normal.terms.is_empty() {
+                    continue;
+                }
+                let (lead, c) = normal.leading_term(&GRevLex).unwrap();
+                let (lead, c) = (lead.clone(), K::one() / c.clone());
+                echelon.insert(lead, scale(&normal, &c));
+                secondary.push(r);
+            }
+        }
+        match secondary.len() == count {
+            true => Ok(secondary),
+            false => invalid("the primary invariants are not a system of parameters"),
+        }
+    }
+
+    fn check_ring<R, V>(&self, ring: &PolynomialRing<'_, R, V>) -> Result<(), ChidogError> {
+        if ring.vars.len() != self.n {
+            return Err(ChidogError::InvalidInput(format!(
+                "a group acting on {} variables does not act on a ring of {}",
+                self.n,
+                ring.vars.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The product of `f` by the scalar `c`.
+fn scale<'a, R, V, K, P>(f: &Polynomial<'a, R, V, K, P>, c: &K) -> Polynomial<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    Polynomial::from_terms(
+        f.elem_of,
+        f.terms
+            .iter()
+            .map(|(m, a)| (m.clone(), a.clone() * c.clone())),
+    )
+    .expect("the monomials have one exponent for each variable")
+}
+
+/// The largest total degree of a term of `f`.
+fn total_degree<R, V, K, P: Hash + PrimInt>(f: &Polynomial<'_, R, V, K, P>) -> usize {
+    f.terms
+        .keys()
+        .map(|m| m.powers.iter().map(|p| p.to_usize().unwrap()).sum())
+        .max()
+        .unwrap_or(0)
+}
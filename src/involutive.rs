@@ -0,0 +1,464 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{PrimInt, Unsigned};
+
+use crate::control::{ComputationControl, Progress};
+use crate::division::Divisors;
+use crate::error::ChidogError;
+use crate::order::MonomialOrder;
+use crate::{Field, FieldElement, Monomial, Polynomial};
+
+/// An involutive division, which assigns to each monomial of a finite set
+/// the variables it may be multiplied by when dividing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvolutiveDivision {
+    /// Variable `x_i` is multiplicative for `u` if `u_i` is the largest
+    /// exponent of `x_i` among the monomials of the set that agree with `u`
+    /// in `x_0, ..., x_(i-1)`.
+    Janet,
+    /// Variables `x_c, ..., x_(n-1)` are multiplicative for `u`, where the
+    /// class `c` is the last variable dividing `u`, or zero for `u = 1`.
+    /// Unlike the Janet division, this does not depend on the set.
+    Pommaret,
+}
+
+/// A node of a [`JanetTree`] at the level of variable `x_i`: the children by
+/// increasing exponent of `x_i`, or the index of a monomial below the last
+/// level.
+#[derive(Clone, Debug)]
+enum Node {
+    Branch(Vec<(u64, Node)>),
+    Leaf(usize),
+}
+
+/// The Janet tree of a set of monomials, which sorts them by the exponent of
+/// `x_0`, then of `x_1`, and so on.
+///
+/// The path of a monomial `u` goes through the node of the monomials that
+/// agree with `u` in `x_0, ..., x_(i-1)` at level `i`, so `x_i` is
+/// multiplicative for `u` exactly when it takes the last child there. The
+/// Janet divisor of a monomial, if any, is found along one path.
+#[derive(Clone, Debug)]
+pub struct JanetTree {
+    root: Option<Node>,
+}
+
+/// The exponents of `m`.
+fn exponents<P: PrimInt>(m: &Monomial<P>) -> Vec<u64> {
+    m.powers.iter().map(|p| p.to_u64().unwrap()).collect()
+}
+
+impl JanetTree {
+    /// The Janet tree of `monomials`, whose leaves are their indices. Of
+    /// equal monomials only the first is kept.
+    pub fn new<P: PrimInt>(monomials: &[Monomial<P>]) -> Self {
+        let mut tree = Self { root: None };
+        for (k, m) in monomials.iter().enumerate() {
+            tree.insert(&exponents(m), k);
+        }
+        tree
+    }
+
+    fn insert(&mut self, u: &[u64], k: usize) {
+        let mut node = self.root.get_or_insert_with(|| match u.is_empty() {
+            true => Node::Leaf(k),
+            false => Node::Branch(Vec::new()),
+        });
+        for (i, &d) in u.iter().enumerate() {
+            let Node::Branch(children) = node else {
+                unreachable!("leaves are below the last level")
+            };
+            let at = match children.binary_search_by_key(&d, |(e, _)| *e) {
+                Ok(at) => at,
+                Err(at) => {
+                    let child = match i + 1 == u.len() {
+                        true => Node::Leaf(k),
+                        false => Node::Branch(Vec::new()),
+                    };
+                    children.insert(at, (d, child));
+                    at
+                }
+            };
+            node = &mut children[at].1;
+        }
+    }
+
+    /// Whether each variable is multiplicative for the monomial `u` of the
+    /// tree in the Janet division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u` is not in the tree.
+    pub fn multiplicative<P: PrimInt>(&self, u: &Monomial<P>) -> Vec<bool> {
+        let mut node = self.root.as_ref().expect("the monomial is in the tree");
+        exponents(u)
+            .into_iter()
+            .map(|d| {
+                let Node::Branch(children) = node else {
+                    unreachable!("leaves are below the last level")
+                };
+                let at = children
+                    .binary_search_by_key(&d, |(e, _)| *e)
+                    .expect("the monomial is in the tree");
+                node = &children[at].1;
+                at + 1 == children.len()
+            })
+            .collect()
+    }
+
+    /// The index of the Janet divisor of `t` in the tree, that is, the
+    /// monomial `u` dividing `t` such that `t/u` is a product of variables
+    /// multiplicative for `u`, if any. It is unique.
+    pub fn divisor<P: PrimInt>(&self, t: &Monomial<P>) -> Option<usize> {
+        let mut node = self.root.as_ref()?;
+        for d in exponents(t) {
+            let Node::Branch(children) = node else {
+                unreachable!("leaves are below the last level")
+            };
+            // The last child may be multiplied by `x_i`, the others not.
+            let (last, _) = children.last()?;
+            node = match d.cmp(last) {
+                Ordering::Less => {
+                    let at = children.binary_search_by_key(&d, |(e, _)| *e).ok()?;
+                    &children[at].1
+                }
+                _ => &children.last()?.1,
+            };
+        }
+        match node {
+            Node::Leaf(k) => Some(*k),
+            Node::Branch(_) => None,
+        }
+    }
+}
+
+/// The multiplicative variables of each of `leads` in `division`, with the
+/// Janet tree of `leads`.
+fn multiplicative_variables<P: PrimInt>(
+    leads: &[Monomial<P>],
+    division: InvolutiveDivision,
+) -> (Vec<Vec<bool>>, JanetTree) {
+    let tree = JanetTree::new(leads);
+    let multiplicative = leads
+        .iter()
+        .map(|u| match division {
+            InvolutiveDivision::Janet => tree.multiplicative(u),
+            InvolutiveDivision::Pommaret => {
+                let class = u.powers.iter().rposition(|p| !p.is_zero()).unwrap_or(0);
+                (0..u.powers.len()).map(|j| j >= class).collect()
+            }
+        })
+        .collect();
+    (multiplicative, tree)
+}
+
+/// An involutive basis of an ideal, as computed by [`involutive_basis`].
+///
+/// Every element of the ideal is a sum of multiples of the elements by
+/// products of their multiplicative variables, with leading monomials
+/// distinct, so that the leading monomials with their multiplicative
+/// variables split the leading ideal into disjoint cones. In particular the
+/// elements form a Gröbner basis, though not a reduced one, and their tails
+/// are reduced involutively.
+#[derive(Clone, Debug)]
+pub struct InvolutiveBasis<'a, R, V, K, P, O>
+where
+    P: Hash,
+{
+    polynomials: Vec<Polynomial<'a, R, V, K, P>>,
+    leads: Vec<Monomial<P>>,
+    multiplicative: Vec<Vec<bool>>,
+    tree: JanetTree,
+    division: InvolutiveDivision,
+    order: O,
+}
+
+impl<'a, R, V, K, P, O> InvolutiveBasis<'a, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    /// An empty basis.
+    fn empty(division: InvolutiveDivision, order: &O) -> Self {
+        Self {
+            polynomials: Vec::new(),
+            leads: Vec::new(),
+            multiplicative: Vec::new(),
+            tree: JanetTree { root: None },
+            division,
+            order: order.clone(),
+        }
+    }
+
+    /// The monic elements of the basis.
+    pub fn polynomials(&self) -> &[Polynomial<'a, R, V, K, P>] {
+        &self.polynomials
+    }
+
+    /// The division the basis is involutive for.
+    pub fn division(&self) -> InvolutiveDivision {
+        self.division
+    }
+
+    /// Whether each variable is multiplicative for element `k`.
+    pub fn multiplicative_variables(&self, k: usize) -> &[bool] {
+        &self.multiplicative[k]
+    }
+
+    /// The Janet tree of the leading monomials of the elements.
+    pub fn janet_tree(&self) -> &JanetTree {
+        &self.tree
+    }
+
+    /// The involutive normal form of `f`: while some term of `f` is the
+    /// leading monomial of an element times its multiplicative variables,
+    /// the term is cancelled by the multiple of that element. It is zero
+    /// exactly when `f` is in the ideal.
+    pub fn normal_form(&self, f: &Polynomial<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P> {
+        let divisors =
+            Divisors::involutive(&self.polynomials, &self.order, self.multiplicative.clone());
+        f.reduce(&divisors, &self.order, None)
+    }
+
+    /// Replaces the elements by `polynomials`, updating the leading
+    /// monomials and multiplicative variables.
+    fn set(&mut self, polynomials: Vec<Polynomial<'a, R, V, K, P>>) {
+        self.leads = polynomials
+            .iter()
+            .map(|g| g.leading_term(&self.order).unwrap().0.clone())
+            .collect();
+        (self.multiplicative, self.tree) = multiplicative_variables(&self.leads, self.division);
+        self.polynomials = polynomials;
+    }
+
+    /// Adds the monic `h`, whose leading monomial has no involutive divisor
+    /// among those of the elements, and removes and returns the elements
+    /// whose leading monomials are proper multiples of it, which may no
+    /// longer be involutively irreducible.
+    fn insert(&mut self, h: Polynomial<'a, R, V, K, P>) -> Vec<Polynomial<'a, R, V, K, P>> {
+        let lead = h.leading_term(&self.order).unwrap().0.clone();
+        let (mut kept, mut removed) = (Vec::new(), Vec::new());
+        for (g, m) in self.polynomials.iter().zip(&self.leads) {
+            match lead.divides(m) && lead != *m {
+                true => removed.push(g.clone()),
+                false => kept.push(g.clone()),
+            }
+        }
+        kept.push(h);
+        self.set(kept);
+        removed
+    }
+
+    /// Reduces the non-leading terms of each element involutively by the
+    /// others. The leading monomials lie in disjoint cones, so they are left
+    /// unchanged, and with them the multiplicative variables.
+    fn reduce_tails(&mut self) {
+        for k in 0..self.polynomials.len() {
+            let mut others = self.polynomials.clone();
+            let g = others.remove(k);
+            let mut multiplicative = self.multiplicative.clone();
+            multiplicative.remove(k);
+            let divisors = Divisors::involutive(&others, &self.order, multiplicative);
+            self.polynomials[k] = g.reduce(&divisors, &self.order, None);
+        }
+    }
+}
+
+/// `f` divided by its leading coefficient.
+fn monic<'a, R, V, K, P, O>(f: Polynomial<'a, R, V, K, P>, order: &O) -> Polynomial<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    let c = K::one() / f.leading_term(order).unwrap().1.clone();
+    Polynomial::from_terms(
+        f.elem_of,
+        f.terms.into_iter().map(|(m, a)| (m, a * c.clone())),
+    )
+    .expect("the monomials have one exponent for each variable")
+}
+
+/// The minimal involutive basis of the ideal generated by `generators` in
+/// `order` for `division`, by the completion algorithm of Gerdt and Blinkov.
+///
+/// A queue starts with the generators. The polynomial of the queue with the
+/// least leading monomial is taken out and reduced involutively by the
+/// basis, and if the result `h` is nonzero it is added to the basis, made
+/// monic. The elements whose leading monomials are proper multiples of that
+/// of `h` go back to the queue, since their multiplicative variables may
+/// have changed, and the products of the elements by the nonmultiplicative
+/// variables not yet tried are queued. Once the queue is empty, the tails of
+/// the elements are reduced. Reductions use the same engine as
+/// [`Polynomial::rem`], restricted to involutive divisors.
+///
+/// Taking the least leading monomial first and sending the multiples back
+/// make the leading monomials the minimal involutive basis of the leading
+/// ideal, so the unit ideal has the basis `{1}`.
+///
+/// The Janet division always gives a finite basis. The Pommaret division
+/// only does in δ-regular coordinates, which a generic linear change of
+/// coordinates provides; otherwise this does not terminate, and
+/// [`involutive_basis_controlled`] should be used.
+pub fn involutive_basis<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    division: InvolutiveDivision,
+) -> InvolutiveBasis<'a, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    involutive_basis_controlled(generators, order, division, &ComputationControl::new())
+        .expect("uncontrolled computations are not interrupted")
+}
+
+/// The involutive basis of [`involutive_basis`], consulting `control` before
+/// each reduction of a polynomial of the queue, with the number reduced, the
+/// length of the queue and the number of terms of the basis, and failing as
+/// it directs.
+pub fn involutive_basis_controlled<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    division: InvolutiveDivision,
+    control: &ComputationControl,
+) -> Result<InvolutiveBasis<'a, R, V, K, P, O>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let lead = |f: &Polynomial<'a, R, V, K, P>| f.leading_term(order).unwrap().0.clone();
+    let mut basis = InvolutiveBasis::empty(division, order);
+    let mut queue: Vec<_> = generators
+        .iter()
+        .filter(|f| !f.terms.is_empty())
+        .cloned()
+        .collect();
+    // The nonmultiplicative variables each element has been multiplied by,
+    // by leading monomial.
+    let mut prolonged: HashMap<Monomial<P>, Vec<bool>> = HashMap::new();
+    let mut steps = 0;
+    while !queue.is_empty() {
+        control.check(&Progress {
+            algorithm: "involutive",
+            steps,
+            remaining: Some(queue.len()),
+            terms: basis.polynomials.iter().map(|g| g.terms.len()).sum(),
+        })?;
+        steps += 1;
+        let least = (0..queue.len())
+            .min_by(|&a, &b| order.cmp(&lead(&queue[a]), &lead(&queue[b])))
+            .unwrap();
+        let h = basis.normal_form(&queue.swap_remove(least));
+        if h.terms.is_empty() {
+            continue;
+        }
+        for g in basis.insert(monic(h, order)) {
+            prolonged.remove(&lead(&g));
+            queue.push(g);
+        }
+        for (k, g) in basis.polynomials.iter().enumerate() {
+            let n = basis.leads[k].powers.len();
+            let done = prolonged
+                .entry(basis.leads[k].clone())
+                .or_insert_with(|| vec![false; n]);
+            for j in (0..n).filter(|&j| !basis.multiplicative[k][j]) {
+                if done[j] {
+                    continue;
+                }
+                done[j] = true;
+                let mut powers = basis.leads[k].powers.clone();
+                powers.iter_mut().for_each(|p| *p = P::zero());
+                powers[j] = P::one();
+                let x = Polynomial::from_terms(g.elem_of, [(Monomial { powers }, K::one())])?;
+                queue.push(x * g.clone());
+            }
+        }
+    }
+    basis.reduce_tails();
+    Ok(basis)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::marker::PhantomData;
+
+    use num::BigRational;
+
+    use super::*;
+    use crate::groebner::groebner_basis;
+    use crate::order::GRevLex;
+    use crate::{AlreadyRing, PolynomialRing};
+
+    type Ring<'a> = PolynomialRing<'a, AlreadyRing<BigRational>, String>;
+    type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+    fn parse<'a>(ring: &'a Ring<'a>, inputs: &[&str]) -> Vec<Poly<'a>> {
+        inputs
+            .iter()
+            .map(|f| Poly::from_sympy(ring, f).unwrap())
+            .collect()
+    }
+
+    /// The leading monomials of `polynomials`, by their exponents.
+    fn leads(polynomials: &[Poly<'_>]) -> HashSet<Vec<u32>> {
+        polynomials
+            .iter()
+            .map(|f| f.leading_term(&GRevLex).unwrap().0.powers.to_vec())
+            .collect()
+    }
+
+    /// Checks that the basis is a Gröbner basis of the ideal of
+    /// `generators` whose leading monomials are `expected`.
+    fn check(generators: &[Poly<'_>], division: InvolutiveDivision, expected: &[&[u32]]) {
+        let basis = involutive_basis(generators, &GRevLex, division);
+        let expected: HashSet<Vec<u32>> = expected.iter().map(|m| m.to_vec()).collect();
+        assert_eq!(basis.polynomials().len(), expected.len());
+        assert_eq!(leads(basis.polynomials()), expected);
+        let reduced = groebner_basis(basis.polynomials(), &GRevLex);
+        let direct = groebner_basis(generators, &GRevLex);
+        assert_eq!(reduced.len(), direct.len());
+        for (f, g) in reduced.iter().zip(&direct) {
+            assert_eq!(f.terms, g.terms);
+        }
+    }
+
+    #[test]
+    fn unit_ideal_has_basis_one() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(["x", "y", "z"].map(String::from).to_vec(), &base);
+        let generators = parse(&ring, &["x^2 + y*z - 1", "x*y - z", "z^2 - x"]);
+        for division in [InvolutiveDivision::Janet, InvolutiveDivision::Pommaret] {
+            check(&generators, division, &[&[0, 0, 0]]);
+        }
+    }
+
+    #[test]
+    fn quasi_regular_ideal_has_minimal_basis() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let ring = PolynomialRing::new(["x", "y"].map(String::from).to_vec(), &base);
+        // The leading ideal `(x^2, y^2)` has the minimal Janet and Pommaret
+        // basis `{x^2, x*y^2, y^2}`.
+        let generators = parse(&ring, &["x^2 - y", "y^2 - x"]);
+        for division in [InvolutiveDivision::Janet, InvolutiveDivision::Pommaret] {
+            check(&generators, division, &[&[2, 0], &[1, 2], &[0, 2]]);
+        }
+    }
+}
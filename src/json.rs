@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, PrimInt, Unsigned, Zero};
+use serde_json::{Map, Value, json};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::{Monomial, Polynomial, PolynomialRing, Powers, Semiring, SemiringElement};
+
+/// Coefficients with an encoding in the JSON format of
+/// [`Polynomial::to_json`].
+pub trait JsonCoefficient: Sized {
+    /// The name of the encoding, which is the `coefficients` field of the
+    /// JSON object.
+    const ENCODING: &'static str;
+
+    fn to_json(&self) -> Value;
+
+    /// The coefficient encoded by `value`, or `None` if it encodes none.
+    fn from_json(value: &Value) -> Option<Self>;
+}
+
+/// The integer written in decimal in the string `value`, or given by the
+/// JSON number `value`.
+fn integer(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Integers are encoded as `"integer"`, each a decimal string such as
+/// `"-12"`, so that they survive JSON parsers which read all numbers as
+/// doubles. JSON integers are also accepted when reading.
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl JsonCoefficient for $t {
+            const ENCODING: &'static str = "integer";
+
+            fn to_json(&self) -> Value {
+                Value::String(self.to_string())
+            }
+
+            fn from_json(value: &Value) -> Option<Self> {
+                integer(value)?.try_into().ok()
+            }
+        }
+    )*};
+}
+
+impl_integer!(i8, i16, i32, i64, u8, u16, u32, u64, BigInt);
+
+/// Rational numbers are encoded as `"rational"`, each a string `"n/d"` in
+/// lowest terms with `d > 1`, or `"n"` for an integer.
+impl JsonCoefficient for BigRational {
+    const ENCODING: &'static str = "rational";
+
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => match s.split_once('/') {
+                Some((n, d)) => {
+                    let (n, d): (BigInt, BigInt) = (n.parse().ok()?, d.parse().ok()?);
+                    (!d.is_zero()).then(|| BigRational::new(n, d))
+                }
+                None => Some(BigRational::from_integer(s.parse().ok()?)),
+            },
+            _ => Some(BigRational::from_integer(integer(value)?)),
+        }
+    }
+}
+
+/// Doubles are encoded as `"float"`, each a JSON number, or one of the
+/// strings `"NaN"`, `"Infinity"` and `"-Infinity"`, which JSON numbers
+/// cannot represent.
+impl JsonCoefficient for f64 {
+    const ENCODING: &'static str = "float";
+
+    fn to_json(&self) -> Value {
+        match serde_json::Number::from_f64(*self) {
+            Some(n) => Value::Number(n),
+            None if self.is_nan() => json!("NaN"),
+            None if *self > 0.0 => json!("Infinity"),
+            None => json!("-Infinity"),
+        }
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => match s.as_str() {
+                "NaN" => Some(f64::NAN),
+                "Infinity" => Some(f64::INFINITY),
+                "-Infinity" => Some(f64::NEG_INFINITY),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    V: Display,
+    K: JsonCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// `self` in the JSON interchange format of this crate, an object such as
+    ///
+    /// ```json
+    /// {
+    ///   "format": "chidog-polynomial",
+    ///   "version": 1,
+    ///   "vars": ["x", "y"],
+    ///   "coefficients": "rational",
+    ///   "terms": [
+    ///     {"exponents": [0, 0], "coefficient": "-1/2"},
+    ///     {"exponents": [2, 1], "coefficient": "3"}
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// with fields
+    ///
+    /// - `format` and `version`, identifying the schema, whose version only
+    ///   changes if the meaning of existing fields does;
+    /// - `vars`, the names of the variables of the ring, as displayed;
+    /// - `coefficients`, the name of the encoding of the coefficients, given
+    ///   by [`JsonCoefficient::ENCODING`]: `"integer"` or `"rational"`, with
+    ///   coefficients as decimal strings such as `"-12"` and `"3/4"`, or
+    ///   `"float"`, with coefficients as JSON numbers;
+    /// - `terms`, the nonzero terms, each with the exponents of the
+    ///   variables in the order of `vars` and the coefficient, sorted by
+    ///   exponents.
+    pub fn to_json(&self) -> String {
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_unstable_by(|(a, _), (b, _)| a.powers.cmp(&b.powers));
+        let terms: Vec<Value> = terms
+            .into_iter()
+            .map(|(m, c)| {
+                let exponents: Vec<u64> = m.powers.iter().map(|e| e.to_u64().unwrap()).collect();
+                json!({"exponents": exponents, "coefficient": c.to_json()})
+            })
+            .collect();
+        let vars: Vec<String> = self.elem_of.vars.iter().map(|v| v.to_string()).collect();
+        let object = json!({
+            "format": "chidog-polynomial",
+            "version": 1,
+            "vars": vars,
+            "coefficients": K::ENCODING,
+            "terms": terms,
+        });
+        object.to_string()
+    }
+}
+
+impl<'a, R, V, K, P, S> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    V: Display,
+    K: SemiringElement + JsonCoefficient,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    /// The polynomial in `ring` given by `json` in the format of
+    /// [`Polynomial::to_json`].
+    ///
+    /// The variables of `json` are matched to those of `ring` by name, so
+    /// they may come in any order and may omit variables of `ring`, whose
+    /// exponents are then zero. Repeated monomials are added.
+    ///
+    /// Fails with a `Parse` error if `json` does not follow the format, with
+    /// an `InvalidInput` error if it has a variable which `ring` does not
+    /// have or another coefficient encoding than that of `K`, and with an
+    /// `ExponentOverflow` error if an exponent does not fit in `P`.
+    pub fn from_json(ring: &'a PolynomialRing<'a, R, V>, json: &str) -> Result<Self, ChidogError> {
+        let object: Map<String, Value> =
+            serde_json::from_str(json).map_err(|e| parse_error(e.to_string()))?;
+        let field = |name: &str| {
+            object
+                .get(name)
+                .ok_or_else(|| parse_error(format!("missing field `{name}`")))
+        };
+        if field("format")? != "chidog-polynomial" {
+            return Err(parse_error("not a chidog polynomial"));
+        }
+        if field("version")? != 1 {
+            return Err(parse_error(format!(
+                "unsupported version {}",
+                field("version")?
+            )));
+        }
+        let encoding = field("coefficients")?;
+        if encoding != K::ENCODING {
+            return Err(ChidogError::InvalidInput(format!(
+                "coefficients encoded as {encoding}, expected \"{}\"",
+                K::ENCODING
+            )));
+        }
+        let names: HashMap<String, usize> = ring
+            .vars
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.to_string(), i))
+            .collect();
+        let indices = field("vars")?
+            .as_array()
+            .ok_or_else(|| parse_error("`vars` is not an array"))?
+            .iter()
+            .map(|v| {
+                let name = v
+                    .as_str()
+                    .ok_or_else(|| parse_error("variable is not a string"))?;
+                names
+                    .get(name)
+                    .copied()
+                    .ok_or_else(|| ChidogError::InvalidInput(format!("unknown variable `{name}`")))
+            })
+            .collect::<Result<Vec<usize>, ChidogError>>()?;
+        let terms = field("terms")?
+            .as_array()
+            .ok_or_else(|| parse_error("`terms` is not an array"))?;
+        let mut parsed = Vec::with_capacity(terms.len());
+        for term in terms {
+            let exponents = term
+                .get("exponents")
+                .and_then(Value::as_array)
+                .filter(|e| e.len() == indices.len())
+                .ok_or_else(|| parse_error("term without one exponent per variable"))?;
+            let mut powers: Powers<P> = smallvec![P::zero(); ring.vars.len()];
+            for (&i, e) in indices.iter().zip(exponents) {
+                let e = e
+                    .as_u64()
+                    .ok_or_else(|| parse_error(format!("invalid exponent {e}")))?;
+                let e = P::from(e).ok_or(ChidogError::ExponentOverflow)?;
+                powers[i] = powers[i]
+                    .checked_add(&e)
+                    .ok_or(ChidogError::ExponentOverflow)?;
+            }
+            let c = term
+                .get("coefficient")
+                .ok_or_else(|| parse_error("term without coefficient"))?;
+            let c = K::from_json(c)
+                .ok_or_else(|| parse_error(format!("invalid {} coefficient {c}", K::ENCODING)))?;
+            parsed.push((Monomial { powers }, c));
+        }
+        Polynomial::from_terms(ring, parsed)
+    }
+}
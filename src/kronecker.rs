@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::bigint::Sign;
+use num::{BigInt, BigUint, One, PrimInt, Signed, Unsigned, Zero};
+
+use crate::{Monomial, Polynomial, Ring};
+
+/// The Kronecker substitution `x_i -> y^(s_i)` for polynomials with degree
+/// less than `bounds[i]` in `x_i`, where `s_0 = 1` and `s_(i+1) = s_i *
+/// bounds[i]`, which maps distinct monomials to distinct powers of `y`.
+struct Substitution {
+    bounds: Vec<usize>,
+}
+
+impl Substitution {
+    fn pack<P: PrimInt>(&self, m: &Monomial<P>) -> usize {
+        let mut index = 0;
+        for (p, b) in m.powers.iter().zip(&self.bounds).rev() {
+            index = index * b + p.to_usize().unwrap();
+        }
+        index
+    }
+
+    fn unpack<P: PrimInt>(&self, mut index: usize) -> Monomial<P> {
+        let powers = self
+            .bounds
+            .iter()
+            .map(|b| {
+                let p = index % b;
+                index /= b;
+                P::from(p).unwrap()
+            })
+            .collect();
+        Monomial { powers }
+    }
+}
+
+/// The integer `∑ c_k * 2^(32*width*k)` for the coefficients `c_k` at the
+/// indices `k`, built from its positive and negative parts without carries.
+fn pack_integer<'c>(
+    coeffs: impl Iterator<Item = (usize, &'c BigInt)>,
+    len: usize,
+    width: usize,
+) -> BigInt {
+    let mut positive = vec![0u32; len * width];
+    let mut negative = vec![0u32; len * width];
+    for (k, c) in coeffs {
+        let digits = if c.is_negative() {
+            &mut negative
+        } else {
+            &mut positive
+        };
+        for (i, d) in c.magnitude().to_u32_digits().into_iter().enumerate() {
+            digits[k * width + i] = d;
+        }
+    }
+    BigInt::from(BigUint::new(positive)) - BigInt::from(BigUint::new(negative))
+}
+
+/// The digits `c_k` of `n` in base `2^(32*width)`, balanced so that `|c_k| <
+/// 2^(32*width - 1)`, in order of increasing `k`.
+fn unpack_integer(n: &BigInt, width: usize) -> Vec<BigInt> {
+    let (sign, digits) = n.to_u32_digits();
+    let base = BigInt::one() << (32 * width);
+    let half = BigInt::one() << (32 * width - 1);
+    let mut carry = BigInt::zero();
+    let mut coeffs = Vec::with_capacity(digits.len().div_ceil(width) + 1);
+    for chunk in digits.chunks(width) {
+        let mut c = BigInt::from(BigUint::from_slice(chunk)) + &carry;
+        carry = if c >= half {
+            c -= &base;
+            BigInt::one()
+        } else {
+            BigInt::zero()
+        };
+        coeffs.push(c);
+    }
+    if !carry.is_zero() {
+        coeffs.push(carry);
+    }
+    if sign == Sign::Minus {
+        coeffs.iter_mut().for_each(|c| *c = -&*c);
+    }
+    coeffs
+}
+
+impl<R, V, P> Polynomial<'_, R, V, BigInt, P>
+where
+    R: Ring<BigInt>,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The product of `self` and `rhs` by Kronecker substitution, delegating
+    /// to a single multiplication of big integers.
+    ///
+    /// Each variable is substituted by a power of a single variable `y`,
+    /// large enough that no two monomials of the product collide, and `y` in
+    /// turn by a power of two large enough to hold every coefficient of the
+    /// product. The product of the two resulting integers is unpacked into
+    /// balanced digits.
+    ///
+    /// The integers have about `∏ (deg_i(self) + deg_i(rhs) + 1)` digits, so
+    /// this pays off for dense or nearly dense operands.
+    pub fn mul_kronecker(&self, rhs: &Self) -> Self {
+        if self.terms.is_empty() || rhs.terms.is_empty() {
+            return Self {
+                elem_of: self.elem_of,
+                terms: HashMap::default(),
+            };
+        }
+        let degrees = |f: &Self, i: usize| {
+            f.terms
+                .keys()
+                .map(|m| m.powers[i].to_usize().unwrap())
+                .max()
+                .unwrap()
+        };
+        let n = self.elem_of.vars.len();
+        let substitution = Substitution {
+            bounds: (0..n)
+                .map(|i| degrees(self, i) + degrees(rhs, i) + 1)
+                .collect(),
+        };
+        let len: usize = substitution.bounds.iter().product();
+
+        let bits = |f: &Self| f.terms.values().map(|c| c.bits()).max().unwrap();
+        let terms = self.terms.len().min(rhs.terms.len());
+        let bound = bits(self) + bits(rhs) + u64::from(terms.ilog2()) + 2;
+        let width = bound.div_ceil(32) as usize;
+
+        let pack = |f: &Self| {
+            let coeffs = f.terms.iter().map(|(m, c)| (substitution.pack(m), c));
+            pack_integer(coeffs, len, width)
+        };
+        let product = pack(self) * pack(rhs);
+        let terms = unpack_integer(&product, width)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, c)| !self.elem_of.base.is_zero(c))
+            .map(|(k, c)| (substitution.unpack(k), c))
+            .collect();
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::zip;
+use std::ops::{Add, Mul, Sub};
+
+use num::{One, PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::{Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring, SemiringElement};
+
+/// A variable `my_ring: LaurentPolynomialRing<R, V>` represents the ring of
+/// Laurent polynomials over a polynomial ring `poly`, that is, its
+/// localization at the monomials, in which variables may appear with
+/// negative exponents. Its elements are of type `LaurentPolynomial<'_, R, V,
+/// K, P>`.
+pub struct LaurentPolynomialRing<'a, R, V> {
+    pub poly: &'a PolynomialRing<'a, R, V>,
+}
+
+/// A Laurent polynomial is stored as a fraction `numer / x^denom` of a
+/// polynomial by a monomial. We maintain the guarantee that the fraction is
+/// reduced: no variable divides both the numerator and the denominator, so
+/// that each Laurent polynomial has a unique representation.
+pub struct LaurentPolynomial<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    numer: Polynomial<'a, R, V, K, P>,
+    denom: Monomial<P>,
+}
+
+impl<R, V, K, P> Clone for LaurentPolynomial<'_, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            numer: self.numer.clone(),
+            denom: self.denom.clone(),
+        }
+    }
+}
+
+/// Multiplies `f` by the monomial `m`.
+fn shift<'a, R, V, K, P>(
+    f: Polynomial<'a, R, V, K, P>,
+    m: &Monomial<P>,
+) -> Polynomial<'a, R, V, K, P>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    if m.powers.iter().all(|p| p.is_zero()) {
+        return f;
+    }
+    Polynomial {
+        elem_of: f.elem_of,
+        terms: f
+            .terms
+            .into_iter()
+            .map(|(n, c)| {
+                let powers = zip(n.powers.iter(), m.powers.iter())
+                    .map(|(a, b)| *a + *b)
+                    .collect();
+                (Monomial { powers }, c)
+            })
+            .collect(),
+    }
+}
+
+impl<'a, R, V> LaurentPolynomialRing<'a, R, V> {
+    pub fn new(poly: &'a PolynomialRing<'a, R, V>) -> Self {
+        Self { poly }
+    }
+
+    /// The term `c * x^exponents`, where exponents may be negative.
+    pub fn monomial<K, P>(&self, exponents: &[i64], c: K) -> LaurentPolynomial<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        assert_eq!(
+            exponents.len(),
+            self.poly.vars.len(),
+            "wrong number of exponents"
+        );
+        let numer = exponents
+            .iter()
+            .map(|&e| P::from(e.max(0)).expect("exponent out of range"))
+            .collect();
+        let denom = exponents
+            .iter()
+            .map(|&e| P::from((-e).max(0)).expect("exponent out of range"))
+            .collect();
+        let mut terms = HashMap::default();
+        if !self.poly.base.is_zero(&c) {
+            terms.insert(Monomial { powers: numer }, c);
+        }
+        LaurentPolynomial::new(
+            Polynomial {
+                elem_of: self.poly,
+                terms,
+            },
+            Monomial { powers: denom },
+        )
+    }
+}
+
+impl<'a, R, V, K, P> LaurentPolynomial<'a, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The Laurent polynomial `numer / x^denom`.
+    pub fn new(numer: Polynomial<'a, R, V, K, P>, denom: Monomial<P>) -> Self {
+        let mut f = Self { numer, denom };
+        f.reduce();
+        f
+    }
+
+    pub fn from_polynomial(f: Polynomial<'a, R, V, K, P>) -> Self {
+        let denom = Monomial {
+            powers: smallvec![P::zero(); f.elem_of.vars.len()],
+        };
+        Self::new(f, denom)
+    }
+
+    /// Cancels the largest monomial dividing both numerator and denominator.
+    fn reduce(&mut self) {
+        let mut common = self.denom.powers.clone();
+        for m in self.numer.terms.keys() {
+            for (g, p) in zip(common.iter_mut(), m.powers.iter()) {
+                *g = (*g).min(*p);
+            }
+        }
+        if self.numer.terms.is_empty() {
+            // Zero has the denominator 1.
+            common.clone_from(&self.denom.powers);
+        }
+        if common.iter().all(|g| g.is_zero()) {
+            return;
+        }
+        let terms = std::mem::take(&mut self.numer.terms);
+        self.numer.terms = terms
+            .into_iter()
+            .map(|(m, c)| {
+                let powers = zip(m.powers.iter(), common.iter())
+                    .map(|(p, g)| *p - *g)
+                    .collect();
+                (Monomial { powers }, c)
+            })
+            .collect();
+        for (d, g) in zip(self.denom.powers.iter_mut(), common.iter()) {
+            *d = *d - *g;
+        }
+    }
+
+    /// Writes `self` as `numer / x^denom` with `numer` an ordinary
+    /// polynomial, returning `(numer, denom)`. The monomial `x^denom` is the
+    /// smallest by which `self` must be multiplied to clear denominators.
+    pub fn clear_denominators(&self) -> (Polynomial<'a, R, V, K, P>, Monomial<P>) {
+        (self.numer.clone(), self.denom.clone())
+    }
+
+    /// `self` as an ordinary polynomial, if it has no negative exponents.
+    pub fn to_polynomial(&self) -> Option<Polynomial<'a, R, V, K, P>> {
+        self.denom
+            .powers
+            .iter()
+            .all(|d| d.is_zero())
+            .then(|| self.numer.clone())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.terms.is_empty()
+    }
+
+    /// Iterates over the terms of `self`, with their signed exponents.
+    pub fn terms(&self) -> impl Iterator<Item = (Vec<i64>, &K)> + '_ {
+        self.numer.terms.iter().map(|(m, c)| {
+            let exponents = zip(m.powers.iter(), self.denom.powers.iter())
+                .map(|(p, d)| p.to_i64().unwrap() - d.to_i64().unwrap())
+                .collect();
+            (exponents, c)
+        })
+    }
+
+    /// Brings `self` and `rhs` to their least common denominator. The
+    /// results are equal to `self` and `rhs`, but need not be reduced.
+    fn common_denominator(self, rhs: Self) -> (Self, Self) {
+        let denom = Monomial {
+            powers: zip(self.denom.powers.iter(), rhs.denom.powers.iter())
+                .map(|(a, b)| (*a).max(*b))
+                .collect(),
+        };
+        let lift = |f: Self| {
+            let m = Monomial {
+                powers: zip(denom.powers.iter(), f.denom.powers.iter())
+                    .map(|(d, e)| *d - *e)
+                    .collect(),
+            };
+            Self {
+                numer: shift(f.numer, &m),
+                denom: denom.clone(),
+            }
+        };
+        (lift(self), lift(rhs))
+    }
+}
+
+impl<R, V, K, P> Add for LaurentPolynomial<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (f, g) = self.common_denominator(rhs);
+        Self::new(f.numer + g.numer, f.denom)
+    }
+}
+
+impl<R, V, K, P> Sub for LaurentPolynomial<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (f, g) = self.common_denominator(rhs);
+        Self::new(f.numer - g.numer, f.denom)
+    }
+}
+
+impl<R, V, K, P> Mul for LaurentPolynomial<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let denom = Monomial {
+            powers: zip(self.denom.powers.iter(), rhs.denom.powers.iter())
+                .map(|(a, b)| *a + *b)
+                .collect(),
+        };
+        Self::new(self.numer * rhs.numer, denom)
+    }
+}
+
+impl<R, V, K, P> Display for LaurentPolynomial<'_, R, V, K, P>
+where
+    K: Clone + Display + One + PartialEq,
+    P: Hash + PrimInt + Unsigned,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let vars = &self.numer.elem_of.vars;
+        for (i, (exponents, c)) in self.terms().enumerate() {
+            if !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            if exponents.iter().all(|e| *e == 0) {
+                write!(f, "1")?;
+                continue;
+            }
+            for (j, (var, e)) in zip(vars.iter(), exponents.iter())
+                .filter(|(_, e)| **e != 0)
+                .enumerate()
+            {
+                if j > 0 {
+                    write!(f, "*")?;
+                }
+                write!(f, "{var}")?;
+                if *e != 1 {
+                    write!(f, "^{e}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1022 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Debug, Display};
+use std::hash::{BuildHasher, Hash};
+use std::iter::zip;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+use num::rational::Ratio;
+use num::traits::NumAssign;
+use num::{FromPrimitive, Integer, Num, One, PrimInt, Unsigned, Zero};
+use smallvec::SmallVec;
+
+pub mod arena;
+pub mod binary;
+pub mod boolean;
+pub mod border;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod cas;
+pub mod codegen;
+pub mod control;
+pub mod division;
+pub mod dual;
+pub mod error;
+pub mod evaluator;
+pub mod expr;
+pub mod exterior;
+pub mod factor;
+pub mod fixed;
+#[cfg(feature = "flint")]
+pub mod flint;
+pub mod float;
+pub mod free;
+pub mod gcd;
+pub mod geobucket;
+pub mod gf256;
+pub mod groebner;
+pub mod group;
+#[cfg(any(feature = "capi", feature = "wasm"))]
+mod handle;
+pub mod hilbert;
+pub mod homotopy;
+pub mod ideal;
+mod infix;
+pub mod integrate;
+pub mod interpolate;
+pub mod interval;
+pub mod invariant;
+pub mod involutive;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod kronecker;
+pub mod laurent;
+pub mod lifting;
+pub mod macaulay2;
+pub mod matrix;
+pub mod modular;
+pub mod monomials;
+#[cfg(feature = "rug")]
+pub mod mpfr;
+pub mod mq;
+pub mod multimodular;
+pub mod openmath;
+pub mod order;
+pub mod ordered;
+pub mod ore;
+pub mod packed;
+pub mod padic;
+pub mod polytope;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod rational;
+pub mod recurrence;
+pub mod resultant;
+pub mod sagbi;
+pub mod scscp;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod series;
+pub mod sexpr;
+pub mod singular;
+pub mod smtlib;
+pub mod stats;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod strong;
+pub mod symmetric;
+pub mod sympy;
+pub mod tropical;
+pub mod univariate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod weyl;
+
+pub use error::ChidogError;
+
+use arena::ArenaPool;
+use order::{GRevLex, MonomialOrder};
+use stats::RingCounters;
+
+/// A trait for types whose values are semirings, that is, rings which need
+/// not have additive inverses.
+///
+/// If the type `A` implements `Semiring<B>`, then a value `a: A` denotes an
+/// instance of a semiring, whose elements are valued in `B`.
+///
+/// A semiring instance decides which of its elements are treated as zero, so
+/// that rings of approximate values can discard negligible results. By
+/// default, this is the exact test `Zero::is_zero`.
+///
+/// Multiplication is assumed to be commutative unless the semiring sets
+/// [`Semiring::COMMUTATIVE`] to `false`. Algorithms that reorder factors, such
+/// as GCDs and fractions, are only meaningful for commutative semirings.
+pub trait Semiring<T: SemiringElement> {
+    /// Whether `x * y == y * x` for all elements `x` and `y`.
+    const COMMUTATIVE: bool = true;
+
+    /// Whether `x` should be treated as zero in this semiring.
+    fn is_zero(&self, x: &T) -> bool {
+        x.is_zero()
+    }
+
+    /// Adds `x` to `acc`, returning whether the sum should be treated as zero.
+    fn add_into(&self, acc: &mut T, x: T) -> bool {
+        *acc += x;
+        self.is_zero(acc)
+    }
+}
+
+/// A trait for types whose values are rings.
+///
+/// If the type `A` implements `Ring<B>`, then a value `a: A` denotes an
+/// instance of a ring, whose ring elements are valued in `B`. Therefore, a ring
+/// operation in `a` might look like `b1 + b2 * b3`.
+pub trait Ring<T: RingElement>: Semiring<T> {
+    /// Subtracts `x` from `acc`, returning whether the difference should be
+    /// treated as zero.
+    fn sub_from(&self, acc: &mut T, x: T) -> bool {
+        *acc -= x;
+        self.is_zero(acc)
+    }
+}
+
+/// A trait for types whose values are fields, that is, rings in which every
+/// nonzero element has a multiplicative inverse.
+///
+/// Algorithms which divide by coefficients, such as polynomial GCD, require
+/// the base ring to implement `Field`.
+pub trait Field<T: FieldElement>: Ring<T> {}
+
+/// The semiring operations + and *, in-place versions, and additive and
+/// multiplicative units
+pub trait SemiringOps: Add + Mul + One + Zero + AddAssign + MulAssign {}
+impl<T> SemiringOps for T where T: Add + Mul + One + Zero + AddAssign + MulAssign {}
+
+/// The ring operations +, -, and *, in-place versions, and additive and
+/// multiplicative units
+pub trait RingOps: SemiringOps + Sub<Output = Self> + SubAssign {}
+impl<T> RingOps for T where T: SemiringOps + Sub<Output = Self> + SubAssign {}
+
+/// A type whose values are elements of a semiring.
+pub trait SemiringElement: Sized + SemiringOps {}
+
+/// A type whose values are elements of a ring.
+pub trait RingElement: SemiringElement + RingOps {}
+
+/// A type whose values are elements of a field, with division by nonzero
+/// elements.
+pub trait FieldElement: RingElement + Div<Output = Self> {}
+
+/// A variable `my_ring: PolynomialRing<R, V>` represents a polynomial ring over
+/// a base ring `R`. The elements of this polynomial ring will be of type
+/// `Polynomial<'_, R, V, K, P>`. The variable `my_ring` owns its variable names
+/// of type `V`, and maintains a reference to its base ring `r: R`.
+///
+/// Such a `my_ring: PolynomialRing<R, V>` also implements `Ring<Polynomial<'_,
+/// R, V, K, P>>`, meaning it has ring elements of the form `f: Polynomial<'_,
+/// R, V, K, P>`. Such `f` represents a polynomial belonging to `my_ring`. The
+/// coefficients of the polynomial are valued in `K`, where the base ring `R`
+/// implements `Ring<K>` (that is, values `k: K` are elements belonging to the
+/// base ring `r: R`)
+///
+/// The ring also owns the [`ArenaPool`] from which long computations in it
+/// allocate their intermediate terms, and the [`RingCounters`] of the
+/// products and divisions done in it.
+///
+/// The exponents of a polynomial are of the primitive integer type `P`, and
+/// arithmetic adds them with plain `+`, which panics on overflow in debug
+/// builds and wraps silently in release builds. With a small `P` such as
+/// `u8`, products should go through [`Polynomial::checked_mul`], which
+/// returns an `ExponentOverflow` error instead. A ring built by
+/// [`PolynomialRing::with_max_exponent`] checks once that `P` can hold the
+/// exponents a computation needs, and its checked operations keep every
+/// exponent within that bound.
+pub struct PolynomialRing<'a, R, V> {
+    pub vars: Vec<V>,
+    pub base: &'a R,
+    arenas: ArenaPool,
+    counters: RingCounters,
+    max_exponent: Option<u64>,
+}
+
+impl<'a, R, V> PolynomialRing<'a, R, V> {
+    /// The polynomial ring over `base` in the variables `vars`.
+    pub fn new(vars: Vec<V>, base: &'a R) -> Self {
+        Self {
+            vars,
+            base,
+            arenas: ArenaPool::default(),
+            counters: RingCounters::default(),
+            max_exponent: None,
+        }
+    }
+
+    /// The polynomial ring over `base` in the variables `vars` whose checked
+    /// operations allow exponents up to `max_exponent`, or an
+    /// `ExponentOverflow` error if that does not fit in `P`.
+    pub fn with_max_exponent<P: PrimInt>(
+        vars: Vec<V>,
+        base: &'a R,
+        max_exponent: u64,
+    ) -> Result<Self, ChidogError> {
+        if P::from(max_exponent).is_none() {
+            return Err(ChidogError::ExponentOverflow);
+        }
+        Ok(Self {
+            max_exponent: Some(max_exponent),
+            ..Self::new(vars, base)
+        })
+    }
+
+    /// The largest exponent allowed by checked operations, if the ring
+    /// bounds them beyond the exponent type.
+    pub fn max_exponent(&self) -> Option<u64> {
+        self.max_exponent
+    }
+
+    /// The arenas lent out to computations in this ring.
+    pub fn arenas(&self) -> &ArenaPool {
+        &self.arenas
+    }
+
+    /// The counters of the work done in this ring.
+    pub fn counters(&self) -> &RingCounters {
+        &self.counters
+    }
+}
+
+impl<R, V> PolynomialRing<'_, R, V>
+where
+    V: Display,
+{
+    fn fmt_monomial<P: Display + Zero + One + Eq>(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        m: &Monomial<P>,
+    ) -> std::fmt::Result {
+        if m.powers.iter().all(|p| p.is_zero()) {
+            write!(f, "1")?;
+        } else {
+            for (i, (var_idx, p)) in m
+                .powers
+                .iter()
+                .enumerate()
+                .filter(|(_j, p)| !p.is_zero())
+                .enumerate()
+            {
+                if i > 0 {
+                    write!(f, "*")?;
+                }
+                write!(f, "{}", self.vars[var_idx])?;
+                if !p.is_one() {
+                    write!(f, "^{p}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The hasher of the term maps of polynomials: FxHash with the `fxhash`
+/// feature, which is much faster on monomials than the default SipHash of
+/// the standard library but offers no protection against collisions chosen
+/// by an adversary.
+#[cfg(feature = "fxhash")]
+pub type TermHasher = rustc_hash::FxBuildHasher;
+
+/// The hasher of the term maps of polynomials: the default SipHash of the
+/// standard library, or FxHash with the `fxhash` feature.
+#[cfg(not(feature = "fxhash"))]
+pub type TermHasher = std::hash::RandomState;
+
+/// Polynomials are implemented as a hash map associating to each monomial a
+/// coefficient. We maintain a guarantee that the hash map contains only nonzero
+/// coefficients; any operation which would result in a zero coefficient simply
+/// deletes the corresponding entry from the map. Whether a coefficient is zero
+/// is decided by the base ring, see [`Semiring::is_zero`].
+///
+/// The map hashes monomials with `S`, which is [`TermHasher`] unless chosen
+/// otherwise.
+///
+/// The map is private to the crate, so that the guarantee cannot be broken
+/// from outside: polynomials are built from arbitrary terms by
+/// [`Polynomial::from_terms`], and read through [`Polynomial::terms`].
+pub struct Polynomial<'a, R, V, K, P, S = TermHasher>
+where
+    P: Hash,
+{
+    pub elem_of: &'a PolynomialRing<'a, R, V>,
+    pub(crate) terms: HashMap<Monomial<P>, K, S>,
+}
+
+impl<R, V, K, P, S> Clone for Polynomial<'_, R, V, K, P, S>
+where
+    K: Clone,
+    P: Hash + Clone,
+    S: BuildHasher + Clone + Default,
+{
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+impl<'a, R, V, K, P, S> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    /// The sum of `terms` in `ring`, or an `InvalidInput` error if a
+    /// monomial does not have one exponent for each variable of `ring`.
+    ///
+    /// Terms with the same monomial are added, and zero coefficients are
+    /// dropped.
+    pub fn from_terms(
+        ring: &'a PolynomialRing<'a, R, V>,
+        terms: impl IntoIterator<Item = (Monomial<P>, K)>,
+    ) -> Result<Self, ChidogError> {
+        let mut f = Self {
+            elem_of: ring,
+            terms: HashMap::default(),
+        };
+        for (m, c) in terms {
+            if m.powers.len() != ring.vars.len() {
+                return Err(ChidogError::InvalidInput(format!(
+                    "monomial in {} variables in a ring of {}",
+                    m.powers.len(),
+                    ring.vars.len()
+                )));
+            }
+            match f.terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if ring.base.add_into(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    if !ring.base.is_zero(&c) {
+                        entry.insert(c);
+                    }
+                }
+            }
+        }
+        Ok(f)
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    P: Hash + Eq,
+    S: BuildHasher,
+{
+    /// The terms, in no particular order.
+    pub fn terms(&self) -> impl Iterator<Item = (&Monomial<P>, &K)> {
+        self.terms.iter()
+    }
+
+    /// The terms, moved out of `self`, in no particular order.
+    pub fn into_terms(self) -> impl Iterator<Item = (Monomial<P>, K)> {
+        self.terms.into_iter()
+    }
+
+    /// The number of terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// The coefficient of `m`, or `None` if it is zero.
+    pub fn coeff(&self, m: &Monomial<P>) -> Option<&K> {
+        self.terms.get(m)
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: Clone,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Clone + Default,
+{
+    /// Evaluates the polynomial at `point`, which gives a value for each
+    /// variable of the ring, in any ring `T` into which the coefficients
+    /// embed.
+    pub fn eval<T>(&self, point: &[T]) -> T
+    where
+        T: SemiringElement + Clone + From<K>,
+    {
+        let mut value = T::zero();
+        for (m, c) in self.terms.iter() {
+            let mut term = T::from(c.clone());
+            for (x, p) in zip(point.iter(), m.powers.iter()) {
+                term *= num::pow(x.clone(), p.to_usize().unwrap());
+            }
+            value += term;
+        }
+        value
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    /// The partial derivative of `self` with respect to the variable `x_i`.
+    pub fn derivative(&self, i: usize) -> Self {
+        let base = self.elem_of.base;
+        let terms = self
+            .terms
+            .iter()
+            .filter(|(m, _)| !m.powers[i].is_zero())
+            .map(|(m, c)| {
+                let mut m = m.clone();
+                let p = m.powers[i];
+                m.powers[i] = p - P::one();
+                (m, K::from_usize(p.to_usize().unwrap()).unwrap() * c.clone())
+            })
+            .filter(|(_, c)| !base.is_zero(c))
+            .collect();
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, S> Add for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut terms = self.terms.clone();
+        for (m, c2) in rhs.terms.into_iter() {
+            match terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if self.elem_of.base.add_into(entry.get_mut(), c2) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert_entry(c2);
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, S> Sub for Polynomial<'_, R, V, K, P, S>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut terms = self.terms;
+        for (m, c2) in rhs.terms.into_iter() {
+            match terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if self.elem_of.base.sub_from(entry.get_mut(), c2) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c2);
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+/// The product of the `i`th term of the left factor and the `j`th term of
+/// the right factor in [`Polynomial::mul_ordered`], ordered by its monomial.
+struct HeapEntry<'o, P, O> {
+    monomial: Monomial<P>,
+    i: usize,
+    j: usize,
+    order: &'o O,
+}
+
+impl<P: PrimInt, O: MonomialOrder> PartialEq for HeapEntry<'_, P, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<P: PrimInt, O: MonomialOrder> Eq for HeapEntry<'_, P, O> {}
+
+impl<P: PrimInt, O: MonomialOrder> PartialOrd for HeapEntry<'_, P, O> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: PrimInt, O: MonomialOrder> Ord for HeapEntry<'_, P, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order.cmp(&self.monomial, &other.monomial)
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Clone + Default,
+{
+    /// The terms of `self` in decreasing order for `order`.
+    pub fn sorted_terms<O: MonomialOrder>(&self, order: &O) -> Vec<(&Monomial<P>, &K)> {
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_by(|(a, _), (b, _)| order.cmp(b, a));
+        terms
+    }
+
+    /// The leading term in `order`, or `None` for zero.
+    pub fn leading_term<O: MonomialOrder>(&self, order: &O) -> Option<(&Monomial<P>, &K)> {
+        self.terms.iter().max_by(|(a, _), (b, _)| order.cmp(a, b))
+    }
+
+    /// The terms of the product `self * rhs` in decreasing order for
+    /// `order`, by the heap merge of Johnson, as in [`heap_product`].
+    pub fn mul_ordered<O: MonomialOrder>(&self, rhs: &Self, order: &O) -> Vec<(Monomial<P>, K)> {
+        let f = self.sorted_terms(order);
+        let g = rhs.sorted_terms(order);
+        heap_product(self.elem_of.base, &f, &g, order)
+    }
+}
+
+/// The terms of the product of the polynomials over `base` with terms `f`
+/// and `g`, each in decreasing order for `order`, in decreasing order, by
+/// the heap merge of Johnson.
+///
+/// The products `f_i*g_j` for fixed `i` are decreasing in `j`, and a heap
+/// holds the next product from each of these sequences. Terms of the result
+/// come off the heap in order, each complete when the next monomial
+/// differs, so the heap never holds more than one entry per term of `f` and
+/// no intermediate map is needed.
+///
+/// Coefficients are multiplied as `f_i*g_j`, so the product is correct over
+/// a base ring which is not [`Semiring::COMMUTATIVE`]. Over a commutative
+/// one, the factors are exchanged when `g` is the shorter, to keep the heap
+/// small.
+pub(crate) fn heap_product<R, K, P, O>(
+    base: &R,
+    f: &[(&Monomial<P>, &K)],
+    g: &[(&Monomial<P>, &K)],
+    order: &O,
+) -> Vec<(Monomial<P>, K)>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    if f.is_empty() || g.is_empty() {
+        return Vec::new();
+    }
+    if R::COMMUTATIVE && g.len() < f.len() {
+        return heap_product(base, g, f, order);
+    }
+    // The product of generic polynomials has at least as many terms as the
+    // factors together; dense ones have up to their product.
+    let mut result = Vec::with_capacity(f.len() + g.len());
+    let entry = |i: usize, j: usize| HeapEntry {
+        monomial: Monomial {
+            powers: zip(f[i].0.powers.iter(), g[j].0.powers.iter())
+                .map(|(p, q)| *p + *q)
+                .collect(),
+        },
+        i,
+        j,
+        order,
+    };
+    let mut heap = BinaryHeap::with_capacity(f.len());
+    heap.push(entry(0, 0));
+    // The monomial being accumulated, with its sum so far, which is dropped
+    // when the base ring finds that it cancels to zero.
+    let mut current: Option<(Monomial<P>, Option<K>)> = None;
+    while let Some(HeapEntry { monomial, i, j, .. }) = heap.pop() {
+        if j == 0 && i + 1 < f.len() {
+            heap.push(entry(i + 1, 0));
+        }
+        if j + 1 < g.len() {
+            heap.push(entry(i, j + 1));
+        }
+        let c = f[i].1.clone() * g[j].1.clone();
+        match &mut current {
+            Some((m, acc)) if *m == monomial => match acc {
+                Some(sum) => {
+                    if base.add_into(sum, c) {
+                        *acc = None;
+                    }
+                }
+                None => *acc = Some(c),
+            },
+            _ => {
+                if let Some((m, Some(acc))) = current.replace((monomial, Some(c)))
+                    && !base.is_zero(&acc)
+                {
+                    result.push((m, acc));
+                }
+            }
+        }
+    }
+    if let Some((m, Some(acc))) = current
+        && !base.is_zero(&acc)
+    {
+        result.push((m, acc));
+    }
+    result
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    /// The largest exponent of each variable in a term of `self`.
+    fn max_exponents(&self) -> Vec<P> {
+        let mut max = vec![P::zero(); self.elem_of.vars.len()];
+        for m in self.terms.keys() {
+            for (e, p) in zip(&mut max, &m.powers) {
+                *e = (*e).max(*p);
+            }
+        }
+        max
+    }
+
+    /// The product `self * rhs`, or an `ExponentOverflow` error if an
+    /// exponent of the product would overflow `P` or exceed the
+    /// [`PolynomialRing::max_exponent`] of the ring.
+    ///
+    /// The largest exponents of the factors are checked up front, after
+    /// which the product is computed as by `*`.
+    pub fn checked_mul(&self, rhs: &Self) -> Result<Self, ChidogError> {
+        let bound = self.elem_of.max_exponent;
+        for (p, q) in zip(self.max_exponents(), rhs.max_exponents()) {
+            let e = p.checked_add(&q).ok_or(ChidogError::ExponentOverflow)?;
+            if bound.is_some_and(|bound| e.to_u64().is_none_or(|e| e > bound)) {
+                return Err(ChidogError::ExponentOverflow);
+            }
+        }
+        Ok(self.clone() * rhs.clone())
+    }
+
+    /// The sum of the terms of `self * rhs` of total degree at most `d`.
+    ///
+    /// The terms of `rhs` are sorted by degree, so that for each term of
+    /// `self` the products of degree above `d` are never formed.
+    pub fn mul_trunc(&self, rhs: &Self, d: usize) -> Self {
+        let degree =
+            |m: &Monomial<P>| -> usize { m.powers.iter().map(|p| p.to_usize().unwrap()).sum() };
+        let mut g: Vec<_> = rhs
+            .terms
+            .iter()
+            .map(|(n, k)| (degree(n), n, k))
+            .filter(|(e, _, _)| *e <= d)
+            .collect();
+        g.sort_unstable_by_key(|(e, _, _)| *e);
+        let base = self.elem_of.base;
+        let mut terms: HashMap<Monomial<P>, K, S> = HashMap::default();
+        for (m, c) in &self.terms {
+            let e = degree(m);
+            for (_, n, k) in g.iter().take_while(|(f, _, _)| e + f <= d) {
+                let powers = zip(&m.powers, &n.powers).map(|(p, q)| *p + *q).collect();
+                let t = c.clone() * (*k).clone();
+                match terms.entry(Monomial { powers }) {
+                    Entry::Occupied(mut entry) => {
+                        if base.add_into(entry.get_mut(), t) {
+                            entry.remove();
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        if !base.is_zero(&t) {
+                            entry.insert(t);
+                        }
+                    }
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+/// Multiplies by [`Polynomial::mul_ordered`] in the graded reverse
+/// lexicographic order.
+impl<R, V, K, P, S> Mul for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    type Output = Self;
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            name = "mul",
+            skip_all,
+            fields(lhs = self.terms.len(), rhs = rhs.terms.len())
+        )
+    )]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let product = self.mul_ordered(&rhs, &GRevLex);
+        self.elem_of.counters.record_product(product.len());
+        #[cfg(feature = "tracing")]
+        tracing::trace!(terms = product.len(), "product");
+        let mut terms = HashMap::with_capacity_and_hasher(product.len(), S::default());
+        terms.extend(product);
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, S> One for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn one() -> Self {
+        todo!()
+    }
+    fn is_one(&self) -> bool
+    where
+        Self: PartialEq,
+    {
+        *self == Self::one()
+    }
+}
+
+impl<R, V, K, P, S> Zero for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn zero() -> Self {
+        todo!()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl<R, V, K, P, S> AddAssign for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            terms: std::mem::take(&mut self.terms),
+        };
+        *self = lhs + rhs;
+    }
+}
+
+impl<R, V, K, P, S> SubAssign for Polynomial<'_, R, V, K, P, S>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            terms: std::mem::take(&mut self.terms),
+        };
+        *self = lhs - rhs;
+    }
+}
+
+impl<R, V, K, P, S> MulAssign for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned + Clone,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            terms: std::mem::take(&mut self.terms),
+        };
+        *self = lhs * rhs;
+    }
+}
+
+/// A monomial, given by the exponent of each variable of its ring.
+///
+/// The exponents of monomials in up to [`INLINE_VARS`] variables are stored
+/// inline, so that creating one does not allocate.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Monomial<P> {
+    pub powers: Powers<P>,
+}
+
+/// The number of variables up to which [`Monomial`] stores its exponents
+/// inline.
+pub const INLINE_VARS: usize = 8;
+
+/// The exponents of a [`Monomial`].
+pub type Powers<P> = SmallVec<[P; INLINE_VARS]>;
+
+impl<P: PrimInt> Monomial<P> {
+    /// The product `self * other`, or an `ExponentOverflow` error if an
+    /// exponent overflows `P`.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, ChidogError> {
+        let powers = zip(&self.powers, &other.powers)
+            .map(|(p, q)| p.checked_add(q).ok_or(ChidogError::ExponentOverflow))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { powers })
+    }
+
+    /// Whether `self` divides `other`.
+    pub fn divides(&self, other: &Self) -> bool {
+        zip(&self.powers, &other.powers).all(|(p, q)| p <= q)
+    }
+
+    /// The quotient `self/other`, or `None` if `other` does not divide
+    /// `self`.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.divides(self).then(|| Self {
+            powers: zip(&self.powers, &other.powers)
+                .map(|(p, q)| *p - *q)
+                .collect(),
+        })
+    }
+
+    /// The least common multiple, with the larger of each pair of exponents.
+    pub fn lcm(&self, other: &Self) -> Self {
+        Self {
+            powers: zip(&self.powers, &other.powers)
+                .map(|(p, q)| *p.max(q))
+                .collect(),
+        }
+    }
+
+    /// The greatest common divisor, with the smaller of each pair of
+    /// exponents.
+    pub fn gcd(&self, other: &Self) -> Self {
+        Self {
+            powers: zip(&self.powers, &other.powers)
+                .map(|(p, q)| *p.min(q))
+                .collect(),
+        }
+    }
+}
+
+impl<R, V, K, P, S> Semiring<Polynomial<'_, R, V, K, P, S>> for PolynomialRing<'_, R, V>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    const COMMUTATIVE: bool = R::COMMUTATIVE;
+}
+
+impl<R, V, K, P, S> Ring<Polynomial<'_, R, V, K, P, S>> for PolynomialRing<'_, R, V>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned, // TODO: Correct trait (see also impl RingElement for Polynomial)
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+}
+
+impl<R, V, K, P, S> SemiringElement for Polynomial<'_, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+}
+
+impl<R, V, K, P, S> RingElement for Polynomial<'_, R, V, K, P, S>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+}
+
+impl<R, V, K, P, S> Display for Polynomial<'_, R, V, K, P, S>
+where
+    K: Display + One + PartialEq,
+    P: Hash + Display + One + Zero + Eq,
+    V: Display,
+    S: BuildHasher + Clone + Default,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            write!(f, "0")?;
+        } else {
+            for (i, (m, c)) in self.terms.iter().enumerate() {
+                // TODO: Handle parenthesization of coefficients;
+                // probably decided trait DisplayAsCoefficient
+                if !c.is_one() {
+                    if i > 0 {
+                        write!(f, "{c:+}")?;
+                    } else {
+                        write!(f, "{c}")?;
+                    }
+                    write!(f, "*")?;
+                } else if i > 0 {
+                    write!(f, "+")?;
+                }
+                self.elem_of.fmt_monomial(f, m)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The terms of the polynomial, without its ring.
+impl<R, V, K, P, S> Debug for Polynomial<'_, R, V, K, P, S>
+where
+    K: Debug,
+    P: Hash + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polynomial")
+            .field("terms", &self.terms)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A dummy type with value representing the ring whose elements are of type
+/// `T`, used to encode the fact that a base or external numerical type should
+/// be treated as a type fo ring elements.
+#[derive(Clone)]
+pub struct AlreadyRing<T> {
+    pub phantom: PhantomData<T>,
+}
+impl<T> Semiring<T> for AlreadyRing<T> where T: Num + SemiringOps {}
+impl<T> Ring<T> for AlreadyRing<T> where T: Num + RingOps {}
+impl<T> Field<T> for AlreadyRing<T> where T: Num + RingOps + FieldElement {}
+impl<T> SemiringElement for T where T: Num + SemiringOps {}
+impl<T> RingElement for T where T: Num + RingOps {}
+impl<T> FieldElement for Ratio<T> where T: Clone + Integer + NumAssign {}
+impl FieldElement for f32 {}
+impl FieldElement for f64 {}
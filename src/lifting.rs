@@ -0,0 +1,649 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::iter::zip;
+
+use num::{BigInt, Integer, One, PrimInt, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::multimodular::{Modular, PrimeField};
+use crate::univariate::DenseUnivariate;
+use crate::{Field, FieldElement, Monomial, Polynomial, Semiring};
+
+/// `f` with each coefficient reduced into `[0, m)`.
+fn reduce(f: &DenseUnivariate<BigInt>, m: &BigInt) -> DenseUnivariate<BigInt> {
+    f.map(|c| c.mod_floor(m))
+}
+
+/// The residues of `u` as integers in `[0, p)`.
+fn residues(u: &DenseUnivariate<Modular>) -> DenseUnivariate<BigInt> {
+    u.map(|c| BigInt::from(c.value()))
+}
+
+/// The quotient and remainder of `f` by the monic `g`, modulo `m`.
+fn div_rem_monic(
+    f: &DenseUnivariate<BigInt>,
+    g: &DenseUnivariate<BigInt>,
+    m: &BigInt,
+) -> (DenseUnivariate<BigInt>, DenseUnivariate<BigInt>) {
+    let n = g.degree().unwrap();
+    let mut r = f.coeffs().to_vec();
+    if r.len() <= n {
+        return (DenseUnivariate::zero(), reduce(f, m));
+    }
+    let mut q = vec![BigInt::zero(); r.len() - n];
+    for i in (0..q.len()).rev() {
+        let c = r[i + n].mod_floor(m);
+        for (j, d) in g.coeffs().iter().enumerate() {
+            r[i + j] -= &c * d;
+        }
+        q[i] = c;
+    }
+    r.truncate(n);
+    (DenseUnivariate::new(q), reduce(&DenseUnivariate::new(r), m))
+}
+
+/// A factorization `f ≡ g*h` with `h` monic, and Bézout coefficients with
+/// `s*g + t*h ≡ 1`, `deg(s) < deg(h)` and `deg(t) < deg(g)`, modulo some
+/// modulus.
+struct Split {
+    g: DenseUnivariate<BigInt>,
+    h: DenseUnivariate<BigInt>,
+    s: DenseUnivariate<BigInt>,
+    t: DenseUnivariate<BigInt>,
+}
+
+impl Split {
+    /// The same relations for `f` modulo `m^2`, from those modulo `m`, by one
+    /// step of quadratic Hensel lifting (von zur Gathen and Gerhard,
+    /// Algorithm 15.10).
+    fn lift(self, f: &DenseUnivariate<BigInt>, m: &BigInt) -> Self {
+        let Self { g, h, s, t } = self;
+        let m = m * m;
+        let e = reduce(&(f.clone() - g.clone() * h.clone()), &m);
+        let (q, r) = div_rem_monic(&(s.clone() * e.clone()), &h, &m);
+        let g = reduce(&(g.clone() + t.clone() * e + q * g), &m);
+        let h = reduce(&(h + r), &m);
+        let b = reduce(
+            &(s.clone() * g.clone() + t.clone() * h.clone() - DenseUnivariate::one()),
+            &m,
+        );
+        let (c, d) = div_rem_monic(&(s.clone() * b.clone()), &h, &m);
+        let s = reduce(&(s - d), &m);
+        let t = reduce(&(t.clone() - t * b - c * g.clone()), &m);
+        Self { g, h, s, t }
+    }
+}
+
+/// The monic factors modulo `target` lifting `factors`, of a polynomial `f`
+/// known modulo `target` whose leading coefficient is a unit.
+///
+/// The factors are split into halves, the factorization of `f` into the
+/// two products is lifted, and then each product is factored in turn.
+fn lift_tree(
+    f: &DenseUnivariate<BigInt>,
+    factors: &[DenseUnivariate<Modular>],
+    field: &PrimeField,
+    target: &BigInt,
+) -> Result<Vec<DenseUnivariate<BigInt>>, ChidogError> {
+    let lc = f.leading_coeff().unwrap();
+    if factors.len() == 1 {
+        let lc_inv = lc.mod_floor(target).modinv(target).unwrap();
+        return Ok(vec![reduce(&f.scale(&lc_inv), target)]);
+    }
+    let (left, right) = factors.split_at(factors.len() / 2);
+    let product = |us: &[DenseUnivariate<Modular>]| {
+        us.iter().fold(DenseUnivariate::one(), |a, u| a * u.clone())
+    };
+    let g = product(left).scale(&field.from_integer(lc));
+    let h = product(right);
+    let (d, s, t) = g.extended_gcd(&h);
+    if d.degree() != Some(0) {
+        return Err(ChidogError::InvalidInput(
+            "factors are not coprime modulo p".to_string(),
+        ));
+    }
+    let mut m = BigInt::from(field.p());
+    let mut split = Split {
+        g: residues(&g),
+        h: residues(&h),
+        s: residues(&s),
+        t: residues(&t),
+    };
+    while m < *target {
+        split = split.lift(f, &m);
+        m = &m * &m;
+    }
+    let mut lifted = lift_tree(&reduce(&split.g, target), left, field, target)?;
+    lifted.extend(lift_tree(&reduce(&split.h, target), right, field, target)?);
+    Ok(lifted)
+}
+
+/// Lifts a factorization of `f` modulo `p` to one modulo `p^k`, by
+/// quadratic Hensel lifting along a balanced tree of the factors.
+///
+/// The `factors` are pairwise coprime monic polynomials over `field`, the
+/// field with `p` elements, whose product times the leading coefficient of
+/// `f` is `f` modulo `p`. The result consists of the unique monic
+/// polynomials `g_i` with coefficients in `[0, p^k)` such that `g_i ≡
+/// factors[i]` modulo `p` and `f ≡ lc(f) * ∏ g_i` modulo `p^k`. Fails if the
+/// factors are not as described, or if `p` divides the leading coefficient
+/// of `f`.
+pub fn hensel_lift(
+    f: &DenseUnivariate<BigInt>,
+    factors: &[DenseUnivariate<Modular>],
+    field: &PrimeField,
+    k: u32,
+) -> Result<Vec<DenseUnivariate<BigInt>>, ChidogError> {
+    let invalid = |reason: &str| Err(ChidogError::InvalidInput(reason.to_string()));
+    let Some(lc) = f.leading_coeff() else {
+        return invalid("cannot lift a factorization of zero");
+    };
+    if k == 0 || factors.is_empty() {
+        return invalid("nothing to lift");
+    }
+    if field.from_integer(lc).is_zero() {
+        return invalid("p divides the leading coefficient");
+    }
+    if factors
+        .iter()
+        .any(|u| u.is_constant() || !u.leading_coeff().unwrap().is_one())
+    {
+        return invalid("factors must be monic and nonconstant");
+    }
+    let product = factors
+        .iter()
+        .fold(DenseUnivariate::constant(field.from_integer(lc)), |a, u| {
+            a * u.clone()
+        });
+    if product != f.map(|c| field.from_integer(c)) {
+        return invalid("factors do not multiply to f modulo p");
+    }
+    let target = num::pow(BigInt::from(field.p()), k as usize);
+    lift_tree(&reduce(f, &target), factors, field, &target)
+}
+
+/// Lifts a simple root of `f` modulo `p` to the unique root modulo `p^k`
+/// congruent to it, by Newton's iteration, which doubles the precision at
+/// each step.
+///
+/// The result lies in `[0, p^k)`. Fails unless `root` is a root of `f` over
+/// `field` at which the derivative of `f` does not vanish.
+pub fn hensel_lift_root(
+    f: &DenseUnivariate<BigInt>,
+    root: Modular,
+    field: &PrimeField,
+    k: u32,
+) -> Result<BigInt, ChidogError> {
+    let df = f.derivative();
+    let mut r = BigInt::from(root.value());
+    if !field.from_integer(&f.eval(&r)).is_zero() {
+        return Err(ChidogError::InvalidInput(format!(
+            "{r} is not a root modulo {}",
+            field.p()
+        )));
+    }
+    if field.from_integer(&df.eval(&r)).is_zero() {
+        return Err(ChidogError::InvalidInput(format!(
+            "{r} is a multiple root modulo {}",
+            field.p()
+        )));
+    }
+    let target = num::pow(BigInt::from(field.p()), k as usize);
+    let mut m = BigInt::from(field.p());
+    while m < target {
+        m = &m * &m;
+        let slope = df.eval(&r).mod_floor(&m).modinv(&m).unwrap();
+        r = (&r - f.eval(&r) * slope).mod_floor(&m);
+    }
+    Ok(r.mod_floor(&target))
+}
+
+/// The derivative of `f` in `x_i` at `point`.
+fn partial_at<R, V, P>(f: &Polynomial<'_, R, V, BigInt, P>, i: usize, point: &[BigInt]) -> BigInt
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    f.terms
+        .iter()
+        .filter(|(m, _)| !m.powers[i].is_zero())
+        .map(|(m, c)| {
+            let e = m.powers[i].to_usize().unwrap();
+            let mut value = c * BigInt::from(e);
+            for (j, (x, p)) in zip(point, &m.powers).enumerate() {
+                let p = p.to_usize().unwrap() - usize::from(j == i);
+                value *= num::pow(x.clone(), p);
+            }
+            value
+        })
+        .sum()
+}
+
+/// Lifts a common root modulo `p` of the polynomials `system`, one for each
+/// variable, at which their Jacobian matrix is invertible, to the unique
+/// common root modulo `p^k` congruent to it.
+///
+/// Each step corrects the next `p`-adic digit of the root by Newton's
+/// iteration with the Jacobian matrix modulo `p`, which is inverted once.
+/// The coordinates of the result lie in `[0, p^k)`. Fails unless `root` is
+/// such a root over `field`.
+pub fn hensel_lift_roots<R, V, P>(
+    system: &[Polynomial<'_, R, V, BigInt, P>],
+    root: &[Modular],
+    field: &PrimeField,
+    k: u32,
+) -> Result<Vec<BigInt>, ChidogError>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = root.len();
+    if system.len() != n || system.iter().any(|f| f.elem_of.vars.len() != n) {
+        return Err(ChidogError::InvalidInput(
+            "need one polynomial and one coordinate for each variable".to_string(),
+        ));
+    }
+    let mut x: Vec<BigInt> = root.iter().map(|a| BigInt::from(a.value())).collect();
+    if system
+        .iter()
+        .any(|f| !field.from_integer(&f.eval(&x)).is_zero())
+    {
+        return Err(ChidogError::InvalidInput(format!(
+            "not a common root modulo {}",
+            field.p()
+        )));
+    }
+    let rows = system
+        .iter()
+        .map(|f| {
+            (0..n)
+                .map(|i| field.from_integer(&partial_at(f, i, &x)))
+                .collect()
+        })
+        .collect();
+    let Some(inverse) = Matrix::from_rows(rows)?.inverse() else {
+        return Err(ChidogError::InvalidInput(format!(
+            "singular Jacobian matrix modulo {}",
+            field.p()
+        )));
+    };
+    let p = BigInt::from(field.p());
+    let mut m = p.clone();
+    for _ in 1..k {
+        let values: Vec<_> = system
+            .iter()
+            .map(|f| field.from_integer(&(f.eval(&x) / &m)))
+            .collect();
+        for (i, xi) in x.iter_mut().enumerate() {
+            let step = zip(inverse.row(i), &values).fold(Modular::zero(), |s, (a, b)| s + *a * *b);
+            *xi -= &m * BigInt::from(step.value());
+        }
+        m *= &p;
+        for xi in &mut x {
+            *xi = xi.mod_floor(&m);
+        }
+    }
+    Ok(x)
+}
+
+/// The polynomial `f(x_0 + shift[0], ..., x_n + shift[n])`.
+fn translate<'a, R, V, K, P>(
+    f: &Polynomial<'a, R, V, K, P>,
+    shift: &[K],
+) -> Polynomial<'a, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let base = f.elem_of.base;
+    let mut terms = HashMap::default();
+    for (m, c) in &f.terms {
+        let mut expansion = vec![(m.clone(), c.clone())];
+        for (j, s) in shift.iter().enumerate() {
+            let e = m.powers[j].to_usize().unwrap();
+            if e == 0 || base.is_zero(s) {
+                continue;
+            }
+            let mut binomials = vec![K::one()];
+            for _ in 0..e {
+                let mut next = vec![K::one()];
+                next.extend(binomials.windows(2).map(|w| w[0].clone() + w[1].clone()));
+                next.push(K::one());
+                binomials = next;
+            }
+            let weights: Vec<K> = binomials
+                .into_iter()
+                .enumerate()
+                .map(|(i, b)| b * num::pow(s.clone(), e - i))
+                .collect();
+            expansion = expansion
+                .into_iter()
+                .flat_map(|(n, d)| {
+                    weights.iter().enumerate().map(move |(i, w)| {
+                        let mut n = n.clone();
+                        n.powers[j] = P::from(i).unwrap();
+                        (n, d.clone() * w.clone())
+                    })
+                })
+                .collect();
+        }
+        for (n, d) in expansion {
+            if base.is_zero(&d) {
+                continue;
+            }
+            match terms.entry(n) {
+                Entry::Occupied(mut entry) => {
+                    if base.add_into(entry.get_mut(), d) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(d);
+                }
+            }
+        }
+    }
+    Polynomial {
+        elem_of: f.elem_of,
+        terms,
+    }
+}
+
+/// The factors of a multivariate polynomial.
+type Factors<'a, R, V, K, P> = Vec<Polynomial<'a, R, V, K, P>>;
+
+/// The degree of `f` in `x_v`, or zero if `f` is zero.
+fn degree_in<R, V, K, P>(f: &Polynomial<'_, R, V, K, P>, v: usize) -> usize
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    f.terms
+        .keys()
+        .map(|m| m.powers[v].to_usize().unwrap())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The terms of `f` satisfying `keep`, with `x_v` set to the power `to`.
+fn select<'a, R, V, K, P>(
+    f: &Polynomial<'a, R, V, K, P>,
+    keep: impl Fn(&Monomial<P>) -> bool,
+    v: usize,
+    to: usize,
+) -> Polynomial<'a, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let terms = f
+        .terms
+        .iter()
+        .filter(|(m, _)| keep(m))
+        .map(|(m, c)| {
+            let mut m = m.clone();
+            m.powers[v] = P::from(to).unwrap();
+            (m, c.clone())
+        })
+        .collect();
+    Polynomial {
+        elem_of: f.elem_of,
+        terms,
+    }
+}
+
+/// The coefficient of `x_v^e` in `f`, as a polynomial in the other
+/// variables.
+fn coefficient<'a, R, V, K, P>(
+    f: &Polynomial<'a, R, V, K, P>,
+    v: usize,
+    e: usize,
+) -> Polynomial<'a, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    select(f, |m| m.powers[v].to_usize() == Some(e), v, 0)
+}
+
+/// `f` with the terms of total degree above `d` in `x_1, ..., x_v` removed.
+fn truncate<'a, R, V, K, P>(
+    f: Polynomial<'a, R, V, K, P>,
+    v: usize,
+    d: usize,
+) -> Polynomial<'a, R, V, K, P>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let terms = f
+        .terms
+        .into_iter()
+        .filter(|(m, _)| {
+            m.powers[1..=v]
+                .iter()
+                .map(|p| p.to_usize().unwrap())
+                .sum::<usize>()
+                <= d
+        })
+        .collect();
+    Polynomial {
+        elem_of: f.elem_of,
+        terms,
+    }
+}
+
+/// The products `∏_{j≠i} a_j`.
+fn cofactors<'a, R, V, K, P>(a: &[Polynomial<'a, R, V, K, P>]) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    let one = |f: &Polynomial<'a, R, V, K, P>| Polynomial {
+        elem_of: f.elem_of,
+        terms: HashMap::from_iter([(
+            Monomial {
+                powers: smallvec![P::zero(); f.elem_of.vars.len()],
+            },
+            K::one(),
+        )]),
+    };
+    (0..a.len())
+        .map(|i| {
+            a.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(one(&a[i]), |b, (_, f)| b * f.clone())
+        })
+        .collect()
+}
+
+/// Solves `∑ σ_i * ∏_{j≠i} a_j = c` modulo `(x_1, ..., x_v)^(d + 1)` for
+/// polynomials `σ_i` in `x_0, ..., x_v` with `deg_{x_0}(σ_i) <
+/// deg_{x_0}(a_i)` (Geddes, Czapor and Labahn, Algorithm 6.2).
+///
+/// The `a_i` are polynomials in `x_0, ..., x_v`, monic in `x_0`, whose
+/// values `u_i` at `x_1 = ... = x_v = 0` are pairwise coprime, and `bezout`
+/// holds polynomials `s_i` with `∑ s_i * ∏_{j≠i} u_j = 1`. The equation is
+/// solved at `x_v = 0` and then lifted one power of `x_v` at a time.
+fn diophantine<'a, R, V, K, P>(
+    a: &[Polynomial<'a, R, V, K, P>],
+    c: &Polynomial<'a, R, V, K, P>,
+    v: usize,
+    d: usize,
+    units: &[DenseUnivariate<K>],
+    bezout: &[DenseUnivariate<K>],
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    let ring = c.elem_of;
+    if v == 0 {
+        let c = DenseUnivariate::from_polynomial(c, 0).unwrap();
+        return zip(units, bezout)
+            .map(|(u, s)| {
+                let sigma = (c.clone() * s.clone()).div_rem(u).unwrap().1;
+                sigma.to_polynomial(ring, 0)
+            })
+            .collect();
+    }
+    let b = cofactors(a);
+    let at_zero = |f: &Polynomial<'a, R, V, K, P>| select(f, |m| m.powers[v].is_zero(), v, 0);
+    let a_next: Vec<_> = a.iter().map(at_zero).collect();
+    let mut sigma = diophantine(&a_next, &at_zero(c), v - 1, d, units, bezout);
+    let residual = |sigma: &[Polynomial<'a, R, V, K, P>]| {
+        let sum = zip(sigma, &b).fold(c.clone(), |e, (s, b)| e - s.clone() * b.clone());
+        truncate(sum, v, d)
+    };
+    let mut e = residual(&sigma);
+    for m in 1..=d {
+        if e.terms.is_empty() {
+            break;
+        }
+        let cm = coefficient(&e, v, m);
+        if cm.terms.is_empty() {
+            continue;
+        }
+        let delta = diophantine(&a_next, &cm, v - 1, d, units, bezout);
+        for (s, ds) in zip(&mut sigma, delta) {
+            *s = s.clone() + select(&ds, |_| true, v, m);
+        }
+        e = residual(&sigma);
+    }
+    sigma
+}
+
+/// Lifts a factorization of `f(x_0, a_1, ..., a_n)` to one of `f`, by
+/// multivariate Hensel lifting one variable at a time (Geddes, Czapor and
+/// Labahn, Algorithm 6.4).
+///
+/// Here `x_0` is the first variable of the ring of `f`, and `point` holds
+/// the values `a_1, ..., a_n` of the others. The leading coefficient `c` of
+/// `f` in `x_0` must be a nonzero constant, and the `factors` pairwise
+/// coprime monic polynomials with `c * ∏ factors[i] = f(x_0, a_1, ...,
+/// a_n)`. The result consists of the polynomials `g_i`, monic in `x_0`,
+/// with `g_i(x_0, a_1, ..., a_n) = factors[i]` and `f = c * ∏ g_i`. Such
+/// `g_i` are unique if they exist; if they do not, because the
+/// factorization at the point does not come from one of `f`, the result is
+/// `None`.
+pub fn hensel_lift_multivariate<'a, R, V, K, P>(
+    f: &Polynomial<'a, R, V, K, P>,
+    factors: &[DenseUnivariate<K>],
+    point: &[K],
+) -> Result<Option<Factors<'a, R, V, K, P>>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    let ring = f.elem_of;
+    let n = ring.vars.len() - 1;
+    let invalid = |reason: &str| Err(ChidogError::InvalidInput(reason.to_string()));
+    if point.len() != n {
+        return invalid("need a value for each variable but the first");
+    }
+    let degree = degree_in(f, 0);
+    let leading: Vec<_> = f
+        .terms
+        .iter()
+        .filter(|(m, _)| m.powers[0].to_usize() == Some(degree))
+        .collect();
+    let c = match leading[..] {
+        [(m, c)] if m.powers[1..].iter().all(|p| p.is_zero()) => c.clone(),
+        _ => return invalid("leading coefficient in the first variable is not a constant"),
+    };
+    if factors.iter().any(|u| {
+        u.is_constant()
+            || !ring
+                .base
+                .is_zero(&(u.leading_coeff().unwrap().clone() - K::one()))
+    }) {
+        return invalid("factors must be monic and nonconstant");
+    }
+    let mut bezout = Vec::with_capacity(factors.len());
+    for (i, u) in factors.iter().enumerate() {
+        let b = factors
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(DenseUnivariate::one(), |b, (_, w)| b * w.clone());
+        let (g, s, _) = b.extended_gcd(u);
+        if g.degree() != Some(0) {
+            return invalid("factors are not pairwise coprime");
+        }
+        bezout.push(s);
+    }
+
+    let mut shift = vec![K::zero()];
+    shift.extend(point.iter().cloned());
+    let f = translate(f, &shift);
+    let d = f
+        .terms
+        .keys()
+        .map(|m| m.powers[1..].iter().map(|p| p.to_usize().unwrap()).sum())
+        .max()
+        .unwrap_or(0);
+    let restrict = |v: usize| Polynomial {
+        elem_of: ring,
+        terms: f
+            .terms
+            .iter()
+            .filter(|(m, _)| m.powers[v + 1..].iter().all(|p| p.is_zero()))
+            .map(|(m, c)| (m.clone(), c.clone()))
+            .collect(),
+    };
+    let scaled = |g: &Polynomial<'a, R, V, K, P>, c: &K| Polynomial {
+        elem_of: ring,
+        terms: g
+            .terms
+            .iter()
+            .map(|(m, a)| (m.clone(), a.clone() * c.clone()))
+            .collect(),
+    };
+    let error = |target: &Polynomial<'a, R, V, K, P>, lifted: &[Polynomial<'a, R, V, K, P>]| {
+        let product = lifted.iter().fold(
+            scaled(&DenseUnivariate::one().to_polynomial(ring, 0), &c),
+            |p, g| p * g.clone(),
+        );
+        target.clone() - product
+    };
+
+    let mut lifted: Vec<_> = factors.iter().map(|u| u.to_polynomial(ring, 0)).collect();
+    if !error(&restrict(0), &lifted).terms.is_empty() {
+        return invalid("factors do not multiply to f at the point");
+    }
+    let c_inv = K::one() / c.clone();
+    for v in 1..=n {
+        let target = restrict(v);
+        let previous = lifted.clone();
+        let mut e = error(&target, &lifted);
+        let top = degree_in(&target, v);
+        for m in 1..=top {
+            if e.terms.is_empty() {
+                break;
+            }
+            let cm = scaled(&coefficient(&e, v, m), &c_inv);
+            if cm.terms.is_empty() {
+                continue;
+            }
+            let delta = diophantine(&previous, &cm, v - 1, d, factors, &bezout);
+            for (g, dg) in zip(&mut lifted, delta) {
+                *g = g.clone() + select(&dg, |_| true, v, m);
+            }
+            e = error(&target, &lifted);
+        }
+        if !e.terms.is_empty() {
+            return Ok(None);
+        }
+    }
+    let unshift: Vec<K> = shift.into_iter().map(|a| K::zero() - a).collect();
+    Ok(Some(
+        lifted.iter().map(|g| translate(g, &unshift)).collect(),
+    ))
+}
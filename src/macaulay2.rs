@@ -0,0 +1,308 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigRational, PrimInt, Unsigned};
+
+use crate::error::ChidogError;
+use crate::infix::{from_infix, split_top_level, to_infix};
+use crate::matrix::Matrix;
+use crate::singular::RingOrdering;
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// The coefficient field, variables and monomial order of a Macaulay2
+/// polynomial ring, such as `QQ[x,y,z, MonomialOrder => Lex]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingDescription {
+    /// The characteristic of the coefficient field, `0` for `QQ` or a prime
+    /// `p` for `ZZ/p`.
+    pub characteristic: u64,
+    pub vars: Vec<String>,
+    pub ordering: RingOrdering,
+}
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+fn coefficient_ring(characteristic: u64) -> String {
+    match characteristic {
+        0 => "QQ".to_string(),
+        p => format!("ZZ/{p}"),
+    }
+}
+
+/// `s` without one pair of enclosing `open` and `close` brackets, if it has
+/// them.
+fn unwrap(s: &str, open: char, close: char) -> Option<&str> {
+    Some(s.trim().strip_prefix(open)?.strip_suffix(close)?.trim())
+}
+
+/// The Macaulay2 ring of characteristic `characteristic` with the variables
+/// of `ring` and the monomial order `ordering`, such as
+/// `QQ[x,y,z, MonomialOrder => Lex]`.
+///
+/// The names of the variables must be Macaulay2 symbols, or indexed
+/// variables such as `x_1`.
+pub fn ring_to_m2<R, V: Display>(
+    ring: &PolynomialRing<'_, R, V>,
+    characteristic: u64,
+    ordering: RingOrdering,
+) -> String {
+    let vars: Vec<String> = ring.vars.iter().map(|v| v.to_string()).collect();
+    let order = match ordering {
+        RingOrdering::Lex => "Lex",
+        RingOrdering::GrLex => "GLex",
+        RingOrdering::GRevLex => "GRevLex",
+    };
+    format!(
+        "{}[{}, MonomialOrder => {order}]",
+        coefficient_ring(characteristic),
+        vars.join(",")
+    )
+}
+
+/// `f` in Macaulay2 syntax, such as `-1/2*x^2*y+3*z-1`, with its terms in
+/// decreasing lexicographic order.
+pub fn to_m2<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    to_infix(f, "^")
+}
+
+/// The Macaulay2 ideal generated by `generators`, such as
+/// `ideal(x^2+y, x*y-1)`. The ideal without generators is written
+/// `ideal(0)`, which Macaulay2 reads as an ideal of `ZZ` unless it is
+/// promoted, as by `sub(ideal(0), R)`.
+pub fn ideal_to_m2<R, V, K, P, S>(generators: &[Polynomial<'_, R, V, K, P, S>]) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let generators: Vec<String> = generators.iter().map(to_m2).collect();
+    match generators.is_empty() {
+        true => "ideal(0)".to_string(),
+        false => format!("ideal({})", generators.join(", ")),
+    }
+}
+
+/// The Macaulay2 matrix of the entries of `m`, such as
+/// `matrix{{x, y}, {y^2, x}}`.
+pub fn matrix_to_m2<R, V, K, P, S>(m: &Matrix<Polynomial<'_, R, V, K, P, S>>) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let rows: Vec<String> = (0..m.nrows())
+        .map(|i| {
+            let entries: Vec<String> = m.row(i).iter().map(to_m2).collect();
+            format!("{{{}}}", entries.join(", "))
+        })
+        .collect();
+    format!("matrix{{{}}}", rows.join(", "))
+}
+
+/// The variables of the entry `var` of a variable list, which is a symbol
+/// or a range such as `a..c` or `x_1..x_3`.
+fn expand_var(var: &str) -> Result<Vec<String>, ChidogError> {
+    let Some((low, high)) = var.split_once("..") else {
+        return Ok(vec![var.to_string()]);
+    };
+    let invalid = || parse_error(format!("unsupported range `{var}`"));
+    let (low, high) = (low.trim(), high.trim());
+    if let (Some((x, i)), Some((y, j))) = (low.split_once('_'), high.split_once('_'))
+        && x == y
+    {
+        let (i, j): (u64, u64) = (
+            i.parse().map_err(|_| invalid())?,
+            j.parse().map_err(|_| invalid())?,
+        );
+        return Ok((i..=j).map(|k| format!("{x}_{k}")).collect());
+    }
+    let letter = |s: &str| {
+        let mut chars = s.chars();
+        chars
+            .next()
+            .filter(|c| c.is_ascii_alphabetic() && chars.next().is_none())
+    };
+    match (letter(low), letter(high)) {
+        (Some(a), Some(b)) => Ok((a..=b).map(String::from).collect()),
+        _ => Err(invalid()),
+    }
+}
+
+/// The monomial order given by the value of a `MonomialOrder` option, such
+/// as `Lex` or `VerticalList{MonomialSize => 32, GRevLex => {3:1}, Position
+/// => Up}`.
+fn parse_order(value: &str) -> Result<RingOrdering, ChidogError> {
+    let mut orders = value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| {
+            !w.is_empty()
+                && !w.starts_with(|c: char| c.is_ascii_digit())
+                && !["VerticalList", "MonomialSize", "Position", "Up", "Down"].contains(w)
+        })
+        .map(|w| match w {
+            "Lex" => Some(RingOrdering::Lex),
+            "GLex" => Some(RingOrdering::GrLex),
+            "GRevLex" => Some(RingOrdering::GRevLex),
+            _ => None,
+        });
+    match (orders.next().flatten(), orders.next()) {
+        (Some(order), None) => Ok(order),
+        _ => Err(ChidogError::InvalidInput(format!(
+            "unsupported monomial order `{}`",
+            value.trim()
+        ))),
+    }
+}
+
+/// The ring written as `input` by Macaulay2's `toExternalString`, or in the
+/// syntax of [`ring_to_m2`], such as
+/// `QQ[x..z, Degrees => {3:1}, MonomialOrder => VerticalList{MonomialSize
+/// => 32, GRevLex => {3:1}, Position => Up}]`.
+///
+/// The coefficient field must be `QQ` or `ZZ/p`, and the monomial order
+/// `Lex`, `GLex` or the default `GRevLex`, or an `InvalidInput` error is
+/// returned. Options other than `MonomialOrder` are ignored.
+pub fn parse_ring(input: &str) -> Result<RingDescription, ChidogError> {
+    let input = input.trim();
+    let (field, rest) = input
+        .split_once('[')
+        .ok_or_else(|| parse_error("expected a polynomial ring"))?;
+    let field = field.trim();
+    let field = unwrap(field, '(', ')').unwrap_or(field);
+    let characteristic = match field.split_once('/') {
+        None if field == "QQ" => 0,
+        Some(("ZZ", p)) => p
+            .trim()
+            .parse()
+            .map_err(|_| ChidogError::InvalidInput(format!("unsupported field `{field}`")))?,
+        _ => {
+            return Err(ChidogError::InvalidInput(format!(
+                "unsupported field `{field}`"
+            )));
+        }
+    };
+    let rest = rest
+        .strip_suffix(']')
+        .ok_or_else(|| parse_error("unterminated `[`"))?;
+    let mut vars = Vec::new();
+    let mut ordering = RingOrdering::GRevLex;
+    for entry in split_top_level(rest) {
+        match entry.split_once("=>") {
+            Some((option, value)) if option.trim() == "MonomialOrder" => {
+                ordering = parse_order(value)?;
+            }
+            Some(_) => {}
+            None => vars.extend(expand_var(entry)?),
+        }
+    }
+    Ok(RingDescription {
+        characteristic,
+        vars,
+        ordering,
+    })
+}
+
+/// The polynomial in `ring` written as `input` in Macaulay2 syntax, such as
+/// the output of `toExternalString` or `toString`, expanded.
+pub fn from_m2<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Polynomial<'a, R, V, BigRational, P>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    from_infix(ring, input, false)
+}
+
+/// The rows of entries of the matrix `input`, written as `matrix{{...}}`,
+/// as a bare list of rows `{{...}}`, or as `map(target, source, {{...}})`
+/// by `toExternalString`.
+fn rows(input: &str) -> Result<Vec<Vec<&str>>, ChidogError> {
+    let input = input.trim();
+    let list = if let Some(args) = input.strip_prefix("map") {
+        let args = unwrap(args, '(', ')').ok_or_else(|| parse_error("invalid `map`"))?;
+        *split_top_level(args)
+            .last()
+            .ok_or_else(|| parse_error("invalid `map`"))?
+    } else {
+        input.strip_prefix("matrix").unwrap_or(input).trim()
+    };
+    let list = unwrap(list, '{', '}').ok_or_else(|| {
+        ChidogError::InvalidInput(format!("expected a list of rows, found `{list}`"))
+    })?;
+    if list.is_empty() {
+        return Ok(Vec::new());
+    }
+    split_top_level(list)
+        .into_iter()
+        .map(|row| {
+            let row =
+                unwrap(row, '{', '}').ok_or_else(|| parse_error(format!("invalid row `{row}`")))?;
+            Ok(match row.is_empty() {
+                true => Vec::new(),
+                false => split_top_level(row),
+            })
+        })
+        .collect()
+}
+
+/// The matrix in `ring` written as `input` by Macaulay2, as `matrix{{...}}`
+/// or as `map(target, source, {{...}})` by `toExternalString`.
+pub fn parse_matrix<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Matrix<Polynomial<'a, R, V, BigRational, P>>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let rows = rows(input)?
+        .into_iter()
+        .map(|row| row.into_iter().map(|e| from_m2(ring, e)).collect())
+        .collect::<Result<_, _>>()?;
+    Matrix::from_rows(rows)
+}
+
+/// The generators of the ideal in `ring` written as `input` by Macaulay2,
+/// as `ideal(...)`, `ideal{...}` or, by `toExternalString`, as the ideal
+/// `ideal map(...)` of the entries of a matrix with one row.
+pub fn parse_ideal<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Vec<Polynomial<'a, R, V, BigRational, P>>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let input = input
+        .trim()
+        .strip_prefix("ideal")
+        .ok_or_else(|| parse_error("expected an ideal"))?
+        .trim();
+    let generators = if input.starts_with("map") {
+        rows(input)?.into_iter().flatten().collect()
+    } else {
+        let list = unwrap(input, '(', ')')
+            .or_else(|| unwrap(input, '{', '}'))
+            .ok_or_else(|| parse_error(format!("invalid ideal `{input}`")))?;
+        match list.is_empty() {
+            true => Vec::new(),
+            false => split_top_level(list),
+        }
+    };
+    generators.into_iter().map(|g| from_m2(ring, g)).collect()
+}
@@ -0,0 +1,445 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use num::{PrimInt, Unsigned};
+
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::univariate::{DenseUnivariate, DivExact};
+use crate::{Polynomial, PolynomialRing, Ring, RingElement};
+
+pub mod hermite;
+pub mod linear;
+pub mod smith;
+
+/// A dense matrix with entries in a commutative ring, stored in row-major
+/// order.
+///
+/// Entries carry no reference to a ring, so they are tested for zero with
+/// `Zero::is_zero`. Matrices of univariate polynomials are matrices over
+/// [`DenseUnivariate`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// The `rows × cols` matrix with the given entries in row-major order.
+    pub fn new(rows: usize, cols: usize, entries: Vec<T>) -> Result<Self, ChidogError> {
+        if entries.len() != rows * cols {
+            return Err(ChidogError::InvalidInput(format!(
+                "{} entries do not fill a {rows}×{cols} matrix",
+                entries.len()
+            )));
+        }
+        Ok(Self {
+            rows,
+            cols,
+            entries,
+        })
+    }
+
+    /// The matrix with the given rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ChidogError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(ChidogError::InvalidInput(
+                "rows of a matrix must have equal lengths".to_string(),
+            ));
+        }
+        let n = rows.len();
+        Self::new(n, cols, rows.into_iter().flatten().collect())
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.entries[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        for k in 0..self.cols {
+            self.entries.swap(i * self.cols + k, j * self.cols + k);
+        }
+    }
+
+    pub fn swap_cols(&mut self, i: usize, j: usize) {
+        for k in 0..self.rows {
+            self.entries.swap(k * self.cols + i, k * self.cols + j);
+        }
+    }
+
+    /// The matrix with entries `f(a)` for the entries `a` of `self`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self.entries.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn transpose(&self) -> Self {
+        let entries = (0..self.cols)
+            .flat_map(|j| (0..self.rows).map(move |i| (i, j)))
+            .map(|(i, j)| self[(i, j)].clone())
+            .collect();
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            entries,
+        }
+    }
+
+    /// The submatrix with the given rows and columns, in the given order.
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> Self {
+        let entries = rows
+            .iter()
+            .flat_map(|&i| cols.iter().map(move |&j| (i, j)))
+            .map(|(i, j)| self[(i, j)].clone())
+            .collect();
+        Self {
+            rows: rows.len(),
+            cols: cols.len(),
+            entries,
+        }
+    }
+}
+
+impl<T: RingElement + Clone> Matrix<T> {
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            entries: vec![T::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::one();
+        }
+        m
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.entries.iter().all(T::is_zero)
+    }
+
+    /// The product `c*self`.
+    pub fn scale(&self, c: &T) -> Self {
+        self.map(|a| c.clone() * a.clone())
+    }
+
+    /// Adds `c` times row `src` to row `dst`, which must be different.
+    pub fn add_row_multiple(&mut self, dst: usize, src: usize, c: &T) {
+        assert_ne!(dst, src, "row operation on a single row");
+        for k in 0..self.cols {
+            let a = c.clone() * self[(src, k)].clone();
+            self[(dst, k)] += a;
+        }
+    }
+
+    /// Adds `c` times column `src` to column `dst`, which must be different.
+    pub fn add_col_multiple(&mut self, dst: usize, src: usize, c: &T) {
+        assert_ne!(dst, src, "column operation on a single column");
+        for k in 0..self.rows {
+            let a = c.clone() * self[(k, src)].clone();
+            self[(k, dst)] += a;
+        }
+    }
+
+    /// Multiplies row `i` by `c`.
+    pub fn scale_row(&mut self, i: usize, c: &T) {
+        for k in 0..self.cols {
+            self[(i, k)] = c.clone() * self[(i, k)].clone();
+        }
+    }
+
+    /// Multiplies column `j` by `c`.
+    pub fn scale_col(&mut self, j: usize, c: &T) {
+        for k in 0..self.rows {
+            self[(k, j)] = c.clone() * self[(k, j)].clone();
+        }
+    }
+
+    pub fn trace(&self) -> T {
+        assert!(self.is_square(), "trace of a non-square matrix");
+        let mut t = T::zero();
+        for i in 0..self.rows {
+            t += self[(i, i)].clone();
+        }
+        t
+    }
+
+    /// The characteristic polynomial `det(x*I - self)` as a dense polynomial,
+    /// computed without division by the algorithm of Berkowitz.
+    ///
+    /// Writing the leading principal submatrix of size `r` as `[[A, c], [s,
+    /// a]]`, the characteristic polynomial of size `r` is obtained from that
+    /// of `A` by multiplying with the lower triangular Toeplitz matrix with
+    /// first column `(1, -a, -s*c, -s*A*c, -s*A^2*c, ...)`.
+    pub fn char_poly_dense(&self) -> DenseUnivariate<T> {
+        self.char_poly_dense_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The characteristic polynomial of [`Matrix::char_poly_dense`],
+    /// consulting `control` before each leading principal submatrix, with
+    /// the size reached and the number of entries, and failing as it
+    /// directs.
+    pub fn char_poly_dense_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<DenseUnivariate<T>, ChidogError> {
+        assert!(
+            self.is_square(),
+            "characteristic polynomial of a non-square matrix"
+        );
+        // Coefficients in order of decreasing degree.
+        let mut p = vec![T::one()];
+        for r in 0..self.rows {
+            control.check(&Progress {
+                algorithm: "berkowitz",
+                steps: r,
+                remaining: Some(self.rows - r),
+                terms: self.entries.len(),
+            })?;
+            let mut toeplitz = vec![T::one(), T::zero() - self[(r, r)].clone()];
+            // The column `A^k*c` above the diagonal entry, for k = 0, 1, ...
+            let mut v: Vec<T> = (0..r).map(|i| self[(i, r)].clone()).collect();
+            for _ in 0..r {
+                let mut s = T::zero();
+                for (j, vj) in v.iter().enumerate() {
+                    s += self[(r, j)].clone() * vj.clone();
+                }
+                toeplitz.push(T::zero() - s);
+                v = (0..r)
+                    .map(|i| {
+                        let mut s = T::zero();
+                        for (j, vj) in v.iter().enumerate() {
+                            s += self[(i, j)].clone() * vj.clone();
+                        }
+                        s
+                    })
+                    .collect();
+            }
+            p = (0..=r + 1)
+                .map(|i| {
+                    let mut s = T::zero();
+                    for (j, pj) in p.iter().enumerate().take(i + 1) {
+                        s += toeplitz[i - j].clone() * pj.clone();
+                    }
+                    s
+                })
+                .collect();
+        }
+        p.reverse();
+        Ok(DenseUnivariate::new(p))
+    }
+
+    /// The characteristic polynomial `det(x*I - self)`, where `x` is the
+    /// variable with index `var` of `ring`.
+    pub fn char_poly<'a, R, V, P>(
+        &self,
+        ring: &'a PolynomialRing<'a, R, V>,
+        var: usize,
+    ) -> Polynomial<'a, R, V, T, P>
+    where
+        R: Ring<T>,
+        P: Hash + PrimInt + Unsigned,
+    {
+        self.char_poly_dense().to_polynomial(ring, var)
+    }
+}
+
+impl<T: DivExact + Clone> Matrix<T> {
+    /// The determinant of `self`, by fraction-free Gaussian elimination
+    /// (Bareiss): after eliminating `k` columns, each remaining entry is a
+    /// minor of size `k + 1`, and the division by the previous pivot is exact.
+    pub fn det(&self) -> T {
+        self.det_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The determinant of [`Matrix::det`], consulting `control` before each
+    /// column is eliminated, with the number eliminated and the number of
+    /// entries, and failing as it directs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(size = self.rows))
+    )]
+    pub fn det_controlled(&self, control: &ComputationControl) -> Result<T, ChidogError> {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let n = self.rows;
+        let mut m = self.clone();
+        let mut negative = false;
+        let mut prev = T::one();
+        for k in 0..n.saturating_sub(1) {
+            control.check(&Progress {
+                algorithm: "bareiss",
+                steps: k,
+                remaining: Some(n - 1 - k),
+                terms: m.entries.len(),
+            })?;
+            let Some(p) = (k..n).find(|&i| !m[(i, k)].is_zero()) else {
+                return Ok(T::zero());
+            };
+            if p != k {
+                m.swap_rows(p, k);
+                negative = !negative;
+            }
+            for i in k + 1..n {
+                for j in k + 1..n {
+                    let a = m[(i, j)].clone() * m[(k, k)].clone()
+                        - m[(i, k)].clone() * m[(k, j)].clone();
+                    m[(i, j)] = a.div_exact(&prev).expect("inexact division in Bareiss");
+                }
+            }
+            prev = m[(k, k)].clone();
+        }
+        let d = if n == 0 {
+            T::one()
+        } else {
+            m[(n - 1, n - 1)].clone()
+        };
+        Ok(if negative { T::zero() - d } else { d })
+    }
+}
+
+/// The number of sign changes in the sequence of the nonzero `coeffs`,
+/// with those of odd index negated if `alternate`.
+fn sign_changes<T: RingElement + PartialOrd>(coeffs: &[T], alternate: bool) -> usize {
+    let signs: Vec<bool> = coeffs
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|(i, c)| (*c > T::zero()) != (alternate && i % 2 == 1))
+        .collect();
+    signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+impl<T: RingElement + Clone + PartialOrd> Matrix<T> {
+    /// The numbers of positive and of negative eigenvalues of the symmetric
+    /// matrix `self`, counted with multiplicity.
+    ///
+    /// The characteristic polynomial of a real symmetric matrix has only real
+    /// roots, so the rule of signs of Descartes counts its positive and
+    /// negative roots exactly.
+    pub fn inertia(&self) -> (usize, usize) {
+        let p = self.char_poly_dense();
+        (
+            sign_changes(p.coeffs(), false),
+            sign_changes(p.coeffs(), true),
+        )
+    }
+
+    /// The signature of the symmetric matrix `self`, the number of its
+    /// positive eigenvalues minus that of its negative ones.
+    pub fn signature(&self) -> isize {
+        let (positive, negative) = self.inertia();
+        positive as isize - negative as isize
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols, "matrix index out of range");
+        &self.entries[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.rows && j < self.cols, "matrix index out of range");
+        &mut self.entries[i * self.cols + j]
+    }
+}
+
+impl<T: RingElement> Add for Matrix<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        assert!(
+            (self.rows, self.cols) == (rhs.rows, rhs.cols),
+            "sum of matrices of different shapes"
+        );
+        for (a, b) in self.entries.iter_mut().zip(rhs.entries) {
+            *a += b;
+        }
+        self
+    }
+}
+
+impl<T: RingElement> Sub for Matrix<T> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        assert!(
+            (self.rows, self.cols) == (rhs.rows, rhs.cols),
+            "difference of matrices of different shapes"
+        );
+        for (a, b) in self.entries.iter_mut().zip(rhs.entries) {
+            *a -= b;
+        }
+        self
+    }
+}
+
+impl<T: RingElement + Clone> Mul for Matrix<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "product of incompatible matrices");
+        let mut product = Self::zero(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for (k, a) in self.row(i).iter().enumerate() {
+                if a.is_zero() {
+                    continue;
+                }
+                for (j, b) in rhs.row(k).iter().enumerate() {
+                    product[(i, j)] += a.clone() * b.clone();
+                }
+            }
+        }
+        product
+    }
+}
+
+/// Displays the matrix as a list of rows, as in `[[1, 2], [3, 4]]`.
+impl<T: Display> Display for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for i in 0..self.rows {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "[")?;
+            for (j, a) in self.row(i).iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{a}")?;
+            }
+            write!(f, "]")?;
+        }
+        write!(f, "]")
+    }
+}
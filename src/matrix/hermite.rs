@@ -0,0 +1,193 @@
+use crate::FieldElement;
+use crate::matrix::Matrix;
+use crate::univariate::{DenseUnivariate, Euclidean};
+
+/// A normal form of a matrix `a` together with the unimodular transformation
+/// producing it: `form = transform * a` for row forms, obtained by row
+/// operations, and `form = a * transform` for column forms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NormalForm<T> {
+    pub form: Matrix<T>,
+    pub transform: Matrix<T>,
+}
+
+impl<T: Clone> NormalForm<T> {
+    /// The column form corresponding to the row form of the transpose.
+    fn transpose(self) -> Self {
+        Self {
+            form: self.form.transpose(),
+            transform: self.transform.transpose(),
+        }
+    }
+}
+
+impl<T: Euclidean + Clone> Matrix<T> {
+    /// The row Hermite normal form of `self`.
+    ///
+    /// This is the row echelon form in which each pivot is in normal form
+    /// (positive, or monic) and the entries above each pivot are reduced
+    /// modulo it (in `0..p` for an integer pivot `p`, of lower degree for a
+    /// polynomial pivot). It is unique, and its nonzero rows span the same
+    /// module as the rows of `self`.
+    pub fn row_hermite_form(&self) -> NormalForm<T> {
+        let (m, n) = (self.nrows(), self.ncols());
+        let mut h = self.clone();
+        let mut u = Matrix::identity(m);
+        let neg = |a: T| T::zero() - a;
+        let mut r = 0;
+        for c in 0..n {
+            if r == m {
+                break;
+            }
+            // Euclid's algorithm on the column, until a single nonzero entry
+            // remains below row `r`.
+            loop {
+                let mut pivot: Option<usize> = None;
+                for i in r..m {
+                    if !h[(i, c)].is_zero()
+                        && pivot.is_none_or(|p| h[(i, c)].smaller_than(&h[(p, c)]))
+                    {
+                        pivot = Some(i);
+                    }
+                }
+                let Some(p) = pivot else {
+                    break;
+                };
+                if p != r {
+                    h.swap_rows(p, r);
+                    u.swap_rows(p, r);
+                }
+                let mut reduced = true;
+                for i in r + 1..m {
+                    if h[(i, c)].is_zero() {
+                        continue;
+                    }
+                    let (q, rem) = h[(i, c)].div_rem_euclid(&h[(r, c)]);
+                    h.add_row_multiple(i, r, &neg(q.clone()));
+                    u.add_row_multiple(i, r, &neg(q));
+                    reduced &= rem.is_zero();
+                }
+                if reduced {
+                    break;
+                }
+            }
+            if h[(r, c)].is_zero() {
+                continue;
+            }
+            let unit = h[(r, c)].normal_unit();
+            h.scale_row(r, &unit);
+            u.scale_row(r, &unit);
+            for i in 0..r {
+                let (q, _) = h[(i, c)].div_rem_euclid(&h[(r, c)]);
+                if !q.is_zero() {
+                    h.add_row_multiple(i, r, &neg(q.clone()));
+                    u.add_row_multiple(i, r, &neg(q));
+                }
+            }
+            r += 1;
+        }
+        NormalForm {
+            form: h,
+            transform: u,
+        }
+    }
+
+    /// The column Hermite normal form of `self`, the transpose of the row
+    /// Hermite normal form of the transpose.
+    pub fn column_hermite_form(&self) -> NormalForm<T> {
+        self.transpose().row_hermite_form().transpose()
+    }
+}
+
+impl<K: FieldElement + Clone> Matrix<DenseUnivariate<K>> {
+    /// The pivot index of row `i`, the rightmost column whose entry has the
+    /// degree of the row, and that degree; `None` for a zero row.
+    fn row_pivot(&self, i: usize) -> Option<(usize, usize)> {
+        let mut pivot = None;
+        for (j, a) in self.row(i).iter().enumerate() {
+            if let Some(d) = a.degree()
+                && pivot.is_none_or(|(_, e)| d >= e)
+            {
+                pivot = Some((j, d));
+            }
+        }
+        pivot
+    }
+
+    /// The row Popov form of `self`.
+    ///
+    /// A matrix is in weak Popov form if the pivot indices of its nonzero
+    /// rows are distinct, and in Popov form if moreover its pivots are monic,
+    /// every other entry in the column of a pivot has lower degree, and the
+    /// rows are sorted by pivot index, with zero rows last. The Popov form is
+    /// unique and row reduced, so its row degrees are minimal.
+    ///
+    /// The weak Popov form is computed by the algorithm of Mulders and
+    /// Storjohann, cancelling the leading term of one of two rows with the
+    /// same pivot index. Its rows are a Gröbner basis of the row module for
+    /// the degree-then-position order, and reducing each row modulo the
+    /// others gives the Popov form.
+    pub fn row_popov_form(&self) -> NormalForm<DenseUnivariate<K>> {
+        let m = self.nrows();
+        let mut p = self.clone();
+        let mut u = Matrix::identity(m);
+        let neg = |a: DenseUnivariate<K>| -a;
+        loop {
+            let pivots: Vec<_> = (0..m).map(|i| p.row_pivot(i)).collect();
+            let conflict = (0..m)
+                .flat_map(|i| (0..m).map(move |k| (i, k)))
+                .find(|&(i, k)| match (pivots[i], pivots[k]) {
+                    (Some((ji, di)), Some((jk, dk))) => i != k && ji == jk && di <= dk,
+                    _ => false,
+                });
+            let Some((i, k)) = conflict else {
+                break;
+            };
+            let ((j, di), (_, dk)) = (pivots[i].unwrap(), pivots[k].unwrap());
+            let c = p[(k, j)].leading_coeff().unwrap().clone()
+                / p[(i, j)].leading_coeff().unwrap().clone();
+            let q = DenseUnivariate::monomial(c, dk - di);
+            p.add_row_multiple(k, i, &neg(q.clone()));
+            u.add_row_multiple(k, i, &neg(q));
+        }
+        loop {
+            let mut changed = false;
+            for i in 0..m {
+                let Some((j, d)) = p.row_pivot(i) else {
+                    continue;
+                };
+                for k in (0..m).filter(|&k| k != i) {
+                    if p[(k, j)].degree().is_some_and(|e| e >= d) {
+                        let (q, _) = p[(k, j)].div_rem_euclid(&p[(i, j)]);
+                        p.add_row_multiple(k, i, &neg(q.clone()));
+                        u.add_row_multiple(k, i, &neg(q));
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        let mut order: Vec<_> = (0..m).map(|i| (p.row_pivot(i), i)).collect();
+        order.sort_by_key(|(pivot, _)| pivot.map_or(usize::MAX, |(j, _)| j));
+        let rows: Vec<usize> = order.iter().map(|(_, i)| *i).collect();
+        let cols: Vec<usize> = (0..self.ncols()).collect();
+        let mut form = p.submatrix(&rows, &cols);
+        let mut transform = u.submatrix(&rows, &(0..m).collect::<Vec<_>>());
+        for (i, (pivot, _)) in order.into_iter().enumerate() {
+            if let Some((j, _)) = pivot {
+                let unit = form[(i, j)].normal_unit();
+                form.scale_row(i, &unit);
+                transform.scale_row(i, &unit);
+            }
+        }
+        NormalForm { form, transform }
+    }
+
+    /// The column Popov form of `self`, the transpose of the row Popov form of
+    /// the transpose.
+    pub fn column_popov_form(&self) -> NormalForm<DenseUnivariate<K>> {
+        self.transpose().row_popov_form().transpose()
+    }
+}
@@ -0,0 +1,215 @@
+use crate::FieldElement;
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::univariate::DivExact;
+
+/// Exact linear algebra over an integral domain, by fraction-free
+/// elimination.
+impl<T: DivExact + Clone> Matrix<T> {
+    /// A row echelon form of `self` and its pivot columns, by the
+    /// fraction-free elimination of Bareiss.
+    ///
+    /// The rows of the echelon form span the same space over the fraction
+    /// field as the rows of `self`. Every entry is a minor of `self`, so
+    /// entries grow no more than determinants do, and in particular the last
+    /// pivot is, up to sign, the determinant of a maximal nonsingular minor.
+    pub fn fraction_free_echelon(&self) -> (Matrix<T>, Vec<usize>) {
+        self.fraction_free_echelon_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The echelon form of [`Matrix::fraction_free_echelon`], consulting
+    /// `control` before each column, with the number of columns done and
+    /// the number of entries, and failing as it directs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(rows = self.nrows(), cols = self.ncols())
+        )
+    )]
+    pub fn fraction_free_echelon_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<(Matrix<T>, Vec<usize>), ChidogError> {
+        let (m, n) = (self.nrows(), self.ncols());
+        let mut e = self.clone();
+        let mut prev = T::one();
+        let mut pivots = Vec::new();
+        for c in 0..n {
+            let r = pivots.len();
+            if r == m {
+                break;
+            }
+            control.check(&Progress {
+                algorithm: "bareiss",
+                steps: c,
+                remaining: Some(n - c),
+                terms: m * n,
+            })?;
+            let Some(p) = (r..m).find(|&i| !e[(i, c)].is_zero()) else {
+                continue;
+            };
+            e.swap_rows(p, r);
+            for i in r + 1..m {
+                for j in c + 1..n {
+                    let a = e[(r, c)].clone() * e[(i, j)].clone()
+                        - e[(i, c)].clone() * e[(r, j)].clone();
+                    e[(i, j)] = a.div_exact(&prev).expect("inexact division in Bareiss");
+                }
+                e[(i, c)] = T::zero();
+            }
+            prev = e[(r, c)].clone();
+            pivots.push(c);
+        }
+        Ok((e, pivots))
+    }
+
+    /// The rank of `self` over the fraction field of `T`.
+    pub fn rank(&self) -> usize {
+        self.fraction_free_echelon().1.len()
+    }
+
+    /// Solves the echelon system `e*x = d*rhs` by back substitution, where
+    /// `x` is given on the free columns.
+    fn back_substitute(e: &Matrix<T>, pivots: &[usize], rhs: &[T], x: &mut [T]) {
+        for (i, &p) in pivots.iter().enumerate().rev() {
+            let mut s = rhs[i].clone();
+            for j in p + 1..e.ncols() {
+                s -= e[(i, j)].clone() * x[j].clone();
+            }
+            x[p] = s
+                .div_exact(&e[(i, p)])
+                .expect("inexact division in back substitution");
+        }
+    }
+
+    /// A basis of the right nullspace `{x : self*x = 0}` over the fraction
+    /// field of `T`, with one vector for each non-pivot column `f`.
+    ///
+    /// The vector for `f` has entry 1 at `f` when the other entries can be
+    /// made to lie in `T`, as over a field, and otherwise the determinant
+    /// `d` of a maximal nonsingular minor, which makes all entries lie in
+    /// `T` by Cramer's rule. It is zero at the other non-pivot columns.
+    pub fn nullspace(&self) -> Vec<Vec<T>> {
+        let (e, pivots) = self.fraction_free_echelon();
+        let d = pivots
+            .last()
+            .map_or(T::one(), |&p| e[(pivots.len() - 1, p)].clone());
+        let zeros = vec![T::zero(); pivots.len()];
+        let n = self.ncols();
+        let mut basis = Vec::new();
+        for f in (0..n).filter(|f| !pivots.contains(f)) {
+            let mut x = vec![T::zero(); n];
+            x[f] = d.clone();
+            Self::back_substitute(&e, &pivots, &zeros, &mut x);
+            if let Some(y) = x.iter().map(|a| a.div_exact(&d)).collect() {
+                x = y;
+            }
+            basis.push(x);
+        }
+        basis
+    }
+
+    /// A solution of `self*x = b` over the fraction field of `T`, as a
+    /// vector `x` and a nonzero denominator `d` with `self*x = d*b`, or
+    /// `None` if the system is inconsistent. The entries of `x` at non-pivot
+    /// columns are zero.
+    pub fn solve_fraction_free(&self, b: &[T]) -> Option<(Vec<T>, T)> {
+        let (m, n) = (self.nrows(), self.ncols());
+        assert_eq!(b.len(), m, "right-hand side of the wrong length");
+        let entries = (0..m)
+            .flat_map(|i| self.row(i).iter().chain([&b[i]]).cloned())
+            .collect();
+        let augmented = Matrix::new(m, n + 1, entries).unwrap();
+        let (e, pivots) = augmented.fraction_free_echelon();
+        if pivots.last() == Some(&n) {
+            return None;
+        }
+        let d = pivots
+            .last()
+            .map_or(T::one(), |&p| e[(pivots.len() - 1, p)].clone());
+        let rhs: Vec<T> = (0..pivots.len())
+            .map(|i| e[(i, n)].clone() * d.clone())
+            .collect();
+        // The last entry of `x` multiplies the column of `b` and stays zero.
+        let mut x = vec![T::zero(); n + 1];
+        Self::back_substitute(&e, &pivots, &rhs, &mut x);
+        x.truncate(n);
+        Some((x, d))
+    }
+}
+
+/// Linear algebra over a field, by direct Gauss–Jordan elimination.
+impl<T: FieldElement + Clone> Matrix<T> {
+    /// The reduced row echelon form of `self` and its pivot columns.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(rows = self.nrows(), cols = self.ncols())
+        )
+    )]
+    pub fn rref(&self) -> (Matrix<T>, Vec<usize>) {
+        let (m, n) = (self.nrows(), self.ncols());
+        let mut e = self.clone();
+        let mut pivots = Vec::new();
+        for c in 0..n {
+            let r = pivots.len();
+            if r == m {
+                break;
+            }
+            let Some(p) = (r..m).find(|&i| !e[(i, c)].is_zero()) else {
+                continue;
+            };
+            e.swap_rows(p, r);
+            let inv = T::one() / e[(r, c)].clone();
+            e.scale_row(r, &inv);
+            for i in (0..m).filter(|&i| i != r) {
+                if !e[(i, c)].is_zero() {
+                    let f = T::zero() - e[(i, c)].clone();
+                    e.add_row_multiple(i, r, &f);
+                }
+            }
+            pivots.push(c);
+        }
+        (e, pivots)
+    }
+
+    /// A solution of `self*x = b`, or `None` if the system is inconsistent.
+    /// The entries of `x` at non-pivot columns are zero.
+    pub fn solve(&self, b: &[T]) -> Option<Vec<T>> {
+        let (m, n) = (self.nrows(), self.ncols());
+        assert_eq!(b.len(), m, "right-hand side of the wrong length");
+        let entries = (0..m)
+            .flat_map(|i| self.row(i).iter().chain([&b[i]]).cloned())
+            .collect();
+        let (e, pivots) = Matrix::new(m, n + 1, entries).unwrap().rref();
+        if pivots.last() == Some(&n) {
+            return None;
+        }
+        let mut x = vec![T::zero(); n];
+        for (i, &p) in pivots.iter().enumerate() {
+            x[p] = e[(i, n)].clone();
+        }
+        Some(x)
+    }
+
+    /// The inverse of `self`, or `None` if it is singular.
+    pub fn inverse(&self) -> Option<Matrix<T>> {
+        assert!(self.is_square(), "inverse of a non-square matrix");
+        let n = self.nrows();
+        let id = Matrix::<T>::identity(n);
+        let entries = (0..n)
+            .flat_map(|i| self.row(i).iter().chain(id.row(i)).cloned())
+            .collect();
+        let (e, pivots) = Matrix::new(n, 2 * n, entries).unwrap().rref();
+        if pivots.len() < n || pivots[n - 1] >= n {
+            return None;
+        }
+        Some(e.submatrix(&(0..n).collect::<Vec<_>>(), &(n..2 * n).collect::<Vec<_>>()))
+    }
+}
@@ -0,0 +1,121 @@
+use crate::matrix::Matrix;
+use crate::univariate::Euclidean;
+
+/// The Smith normal form `s = u * a * v` of a matrix `a` over a Euclidean
+/// domain, where `u` and `v` are invertible.
+///
+/// The matrix `s` is diagonal, with diagonal entries `d_1 | d_2 | ... | d_r`
+/// in normal form followed by zeros. The nonzero `d_i` are the invariant
+/// factors of `a`, determined by `a` up to units, and `r` is its rank.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmithForm<T> {
+    pub s: Matrix<T>,
+    pub u: Matrix<T>,
+    pub v: Matrix<T>,
+}
+
+impl<T: Euclidean + Clone> SmithForm<T> {
+    /// The nonzero diagonal entries of `s`.
+    pub fn invariant_factors(&self) -> Vec<T> {
+        (0..self.s.nrows().min(self.s.ncols()))
+            .map(|i| self.s[(i, i)].clone())
+            .take_while(|d| !d.is_zero())
+            .collect()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.invariant_factors().len()
+    }
+}
+
+impl<T: Euclidean + Clone> Matrix<T> {
+    /// The position of a nonzero entry of least norm in the submatrix of rows
+    /// and columns from `t` on.
+    fn smallest_entry_from(&self, t: usize) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for i in t..self.nrows() {
+            for j in t..self.ncols() {
+                let a = &self[(i, j)];
+                if a.is_zero() {
+                    continue;
+                }
+                if best.is_none_or(|b| a.smaller_than(&self[b])) {
+                    best = Some((i, j));
+                }
+            }
+        }
+        best
+    }
+
+    /// The Smith normal form of `self`, with transformation matrices.
+    ///
+    /// At each step, the entry of least norm is moved to the pivot position
+    /// and the rest of its row and column are reduced by it. Nonzero
+    /// remainders give a smaller pivot, and so does adding a row with an
+    /// entry not divisible by the pivot, so the process terminates with a
+    /// pivot dividing all remaining entries.
+    pub fn smith_form(&self) -> SmithForm<T> {
+        let (m, n) = (self.nrows(), self.ncols());
+        let mut s = self.clone();
+        let mut u = Matrix::identity(m);
+        let mut v = Matrix::identity(n);
+        let neg = |a: T| T::zero() - a;
+        for t in 0..m.min(n) {
+            loop {
+                let Some((pi, pj)) = s.smallest_entry_from(t) else {
+                    return SmithForm { s, u, v };
+                };
+                if pi != t {
+                    s.swap_rows(t, pi);
+                    u.swap_rows(t, pi);
+                }
+                if pj != t {
+                    s.swap_cols(t, pj);
+                    v.swap_cols(t, pj);
+                }
+                let mut reduced = true;
+                for i in t + 1..m {
+                    if s[(i, t)].is_zero() {
+                        continue;
+                    }
+                    let (q, r) = s[(i, t)].div_rem_euclid(&s[(t, t)]);
+                    s.add_row_multiple(i, t, &neg(q.clone()));
+                    u.add_row_multiple(i, t, &neg(q));
+                    reduced &= r.is_zero();
+                }
+                for j in t + 1..n {
+                    if s[(t, j)].is_zero() {
+                        continue;
+                    }
+                    let (q, r) = s[(t, j)].div_rem_euclid(&s[(t, t)]);
+                    s.add_col_multiple(j, t, &neg(q.clone()));
+                    v.add_col_multiple(j, t, &neg(q));
+                    reduced &= r.is_zero();
+                }
+                if !reduced {
+                    continue;
+                }
+                let pivot = s[(t, t)].clone();
+                let indivisible =
+                    (t + 1..m).find(|&i| (t + 1..n).any(|j| s[(i, j)].div_exact(&pivot).is_none()));
+                match indivisible {
+                    Some(i) => {
+                        s.add_row_multiple(t, i, &T::one());
+                        u.add_row_multiple(t, i, &T::one());
+                    }
+                    None => break,
+                }
+            }
+            let unit = s[(t, t)].normal_unit();
+            s.scale_row(t, &unit);
+            u.scale_row(t, &unit);
+        }
+        SmithForm { s, u, v }
+    }
+
+    /// The invariant factors of `self`, the nonzero diagonal entries of its
+    /// Smith normal form.
+    pub fn invariant_factors(&self) -> Vec<T> {
+        self.smith_form().invariant_factors()
+    }
+}
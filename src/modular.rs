@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{BigInt, BigRational, Integer, One, PrimInt, Signed, Unsigned, Zero};
+
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// The rational number `r/s` with `r ≡ s*a (mod m)`, `|r| ≤ num_bound` and
+/// `0 < s ≤ den_bound`, in lowest terms, or `None` if there is none, by
+/// Wang's algorithm.
+///
+/// The extended Euclidean algorithm on `m` and `a` produces remainders `r_i`
+/// with cofactors `s_i` such that `r_i ≡ s_i*a (mod m)`; the candidate is the
+/// first remainder not exceeding `num_bound`. If `2*num_bound*den_bound < m`,
+/// there is at most one such fraction, and this finds it.
+pub fn rational_reconstruction_with_bounds(
+    a: &BigInt,
+    m: &BigInt,
+    num_bound: &BigInt,
+    den_bound: &BigInt,
+) -> Option<BigRational> {
+    let (mut r0, mut r1) = (m.clone(), a.mod_floor(m));
+    let (mut s0, mut s1) = (BigInt::zero(), BigInt::one());
+    while r1 > *num_bound {
+        let q = &r0 / &r1;
+        (r0, r1) = (r1.clone(), r0 - &q * r1);
+        (s0, s1) = (s1.clone(), s0 - q * s1);
+    }
+    if s1.is_zero() || s1.abs() > *den_bound || !r1.gcd(&s1).is_one() {
+        return None;
+    }
+    Some(BigRational::new(r1, s1))
+}
+
+/// The rational number `r/s` with `r ≡ s*a (mod m)` and `|r|, s ≤ √(m/2)`,
+/// which is unique if it exists, by Wang's algorithm.
+///
+/// This recovers a rational number from its residue modulo `m` once `m`
+/// exceeds twice the square of the larger of its numerator and denominator.
+pub fn rational_reconstruction(a: &BigInt, m: &BigInt) -> Option<BigRational> {
+    let bound = (m >> 1u32).sqrt();
+    rational_reconstruction_with_bounds(a, m, &bound, &bound)
+}
+
+/// The polynomial in `ring` whose coefficients are the rational
+/// reconstructions modulo `m` of those of `f`, or `None` if one of them has
+/// none.
+pub fn reconstruct_polynomial<'b, R, S, V, P>(
+    f: &Polynomial<'_, R, V, BigInt, P>,
+    m: &BigInt,
+    ring: &'b PolynomialRing<'b, S, V>,
+) -> Option<Polynomial<'b, S, V, BigRational, P>>
+where
+    S: Semiring<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut terms = HashMap::with_capacity_and_hasher(f.terms.len(), Default::default());
+    for (monomial, c) in &f.terms {
+        let q = rational_reconstruction(c, m)?;
+        if !ring.base.is_zero(&q) {
+            terms.insert(monomial.clone(), q);
+        }
+    }
+    Some(Polynomial {
+        elem_of: ring,
+        terms,
+    })
+}
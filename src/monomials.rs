@@ -0,0 +1,130 @@
+use std::iter::FusedIterator;
+
+use num::PrimInt;
+use smallvec::smallvec;
+
+use crate::order::MonomialOrder;
+use crate::{Monomial, Powers};
+
+/// The number of monomials in `n` variables of total degree exactly `d`,
+/// which is the binomial coefficient `(n + d - 1 choose n - 1)`, saturating
+/// at `usize::MAX`.
+pub fn count_monomials_of_degree(n: usize, d: usize) -> usize {
+    match n {
+        0 => usize::from(d == 0),
+        _ => count_monomials_up_to_degree(n - 1, d),
+    }
+}
+
+/// The number of monomials in `n` variables of total degree at most `d`,
+/// which is the binomial coefficient `(n + d choose n)`, saturating at
+/// `usize::MAX`.
+pub fn count_monomials_up_to_degree(n: usize, d: usize) -> usize {
+    let mut count: usize = 1;
+    for i in 1..=n {
+        // `count*(d + i)` is divisible by `i`, being `i` times a binomial
+        // coefficient.
+        match count.checked_mul(d + i) {
+            Some(c) => count = c / i,
+            None => return usize::MAX,
+        }
+    }
+    count
+}
+
+/// The monomials in `n` variables of total degree exactly `d`, in
+/// decreasing lexicographic order, from `x_0^d` down to `x_{n-1}^d`, as
+/// returned by [`monomials_of_degree`].
+#[derive(Clone, Debug)]
+pub struct MonomialsOfDegree<P> {
+    next: Option<Powers<P>>,
+    remaining: usize,
+}
+
+/// The monomials in `n` variables of total degree exactly `d`, which must
+/// fit in `P`, in decreasing lexicographic order.
+pub fn monomials_of_degree<P: PrimInt>(n: usize, d: usize) -> MonomialsOfDegree<P> {
+    let next = match n {
+        0 => (d == 0).then(Powers::new),
+        _ => {
+            let mut powers: Powers<P> = smallvec![P::zero(); n];
+            powers[0] = P::from(d).expect("degree does not fit in the exponent type");
+            Some(powers)
+        }
+    };
+    MonomialsOfDegree {
+        next,
+        remaining: count_monomials_of_degree(n, d),
+    }
+}
+
+impl<P: PrimInt> Iterator for MonomialsOfDegree<P> {
+    type Item = Monomial<P>;
+
+    fn next(&mut self) -> Option<Monomial<P>> {
+        let powers = self.next.take()?;
+        // The next monomial moves one from the last nonzero exponent `a_i`
+        // before the final one to `x_{i+1}`, which also takes all of the
+        // degree beyond it.
+        let n = powers.len();
+        if let Some(i) = (0..n.saturating_sub(1))
+            .rev()
+            .find(|&i| !powers[i].is_zero())
+        {
+            let mut next = powers.clone();
+            let tail = next[i + 1..].iter().fold(P::zero(), |s, p| s + *p);
+            next[i] = next[i] - P::one();
+            next[i + 1] = tail + P::one();
+            for p in &mut next[i + 2..] {
+                *p = P::zero();
+            }
+            self.next = Some(next);
+        }
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(Monomial { powers })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            None => (0, Some(0)),
+            Some(_) if self.remaining == usize::MAX => (usize::MAX, None),
+            Some(_) => (self.remaining, Some(self.remaining)),
+        }
+    }
+}
+
+impl<P: PrimInt> FusedIterator for MonomialsOfDegree<P> {}
+
+/// The monomials in `n` variables of total degree at most `d`, which must
+/// fit in `P`, by decreasing degree and then in decreasing lexicographic
+/// order, which is the decreasing order of [`GrLex`](crate::order::GrLex).
+pub fn monomials_up_to_degree<P: PrimInt>(
+    n: usize,
+    d: usize,
+) -> impl Iterator<Item = Monomial<P>> + Clone {
+    (0..=d).rev().flat_map(move |e| monomials_of_degree(n, e))
+}
+
+/// The monomials in `n` variables of total degree exactly `d`, in
+/// decreasing order for `order`.
+pub fn monomials_of_degree_in<P: PrimInt, O: MonomialOrder>(
+    n: usize,
+    d: usize,
+    order: &O,
+) -> Vec<Monomial<P>> {
+    let mut monomials: Vec<_> = monomials_of_degree(n, d).collect();
+    monomials.sort_by(|a, b| order.cmp(b, a));
+    monomials
+}
+
+/// The monomials in `n` variables of total degree at most `d`, in
+/// decreasing order for `order`.
+pub fn monomials_up_to_degree_in<P: PrimInt, O: MonomialOrder>(
+    n: usize,
+    d: usize,
+    order: &O,
+) -> Vec<Monomial<P>> {
+    let mut monomials: Vec<_> = monomials_up_to_degree(n, d).collect();
+    monomials.sort_by(|a, b| order.cmp(b, a));
+    monomials
+}
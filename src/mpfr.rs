@@ -0,0 +1,151 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{One, Zero};
+use rug::{Assign, Float};
+
+use crate::{Ring, RingElement, Semiring, SemiringElement};
+
+/// An arbitrary-precision binary floating-point number, backed by MPFR.
+///
+/// The result of an operation has the larger of the precisions of its
+/// operands, so that the constants `zero()` and `one()`, which are stored
+/// with minimal precision, adopt the precision of whatever they are combined
+/// with. Values are normally created through an [`MpfrRing`], which fixes the
+/// working precision.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct MpFloat(pub Float);
+
+/// The real numbers, approximated by [`MpFloat`] coefficients with `prec`
+/// bits of precision.
+///
+/// Sums accumulated in polynomial arithmetic over this ring are rounded to
+/// `prec` bits, regardless of the precision of their summands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MpfrRing {
+    pub prec: u32,
+}
+
+impl MpfrRing {
+    pub fn new(prec: u32) -> Self {
+        Self { prec }
+    }
+
+    /// The value `x`, rounded to the precision of this ring.
+    pub fn element<T>(&self, x: T) -> MpFloat
+    where
+        Float: Assign<T>,
+    {
+        MpFloat(Float::with_val(self.prec, x))
+    }
+}
+
+impl Semiring<MpFloat> for MpfrRing {
+    fn add_into(&self, acc: &mut MpFloat, x: MpFloat) -> bool {
+        acc.0.set_prec(self.prec);
+        acc.0 += x.0;
+        acc.0.is_zero()
+    }
+}
+
+impl Ring<MpFloat> for MpfrRing {
+    fn sub_from(&self, acc: &mut MpFloat, x: MpFloat) -> bool {
+        acc.0.set_prec(self.prec);
+        acc.0 -= x.0;
+        acc.0.is_zero()
+    }
+}
+
+impl SemiringElement for MpFloat {}
+impl RingElement for MpFloat {}
+
+impl MpFloat {
+    pub fn prec(&self) -> u32 {
+        self.0.prec()
+    }
+
+    /// Raises the precision of `self` to at least `prec` bits.
+    fn widen(&mut self, prec: u32) {
+        if self.0.prec() < prec {
+            self.0.set_prec(prec);
+        }
+    }
+}
+
+impl Add for MpFloat {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl Sub for MpFloat {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl Mul for MpFloat {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl Neg for MpFloat {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for MpFloat {
+    fn add_assign(&mut self, rhs: Self) {
+        self.widen(rhs.prec());
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for MpFloat {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.widen(rhs.prec());
+        self.0 -= rhs.0;
+    }
+}
+
+impl MulAssign for MpFloat {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.widen(rhs.prec());
+        self.0 *= rhs.0;
+    }
+}
+
+impl Zero for MpFloat {
+    fn zero() -> Self {
+        Self(Float::new(1))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl One for MpFloat {
+    fn one() -> Self {
+        Self(Float::with_val(1, 1))
+    }
+}
+
+impl Display for MpFloat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt::Write;
+use std::hash::Hash;
+
+use num::{PrimInt, ToPrimitive, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::gf256::{Gf256, Gf256Field};
+use crate::ideal::Ideal;
+use crate::multimodular::{Modular, PrimeField};
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing, Powers};
+
+/// The fields of the MQ challenge format: the prime fields, written as
+/// integers in `[0, p)`, and `GF(2^8)`, written as the integers of its
+/// bytes.
+pub trait MqField<K: FieldElement>: Field<K> {
+    /// The field as named in the `Galois Field` line of the header, such as
+    /// `GF(31)`.
+    fn mq_name(&self) -> String;
+
+    /// The element written as `n`, or `None` if `n` writes none.
+    fn mq_element(&self, n: u64) -> Option<K>;
+
+    /// The integer writing `c`.
+    fn mq_integer(&self, c: &K) -> u64;
+}
+
+impl MqField<Modular> for PrimeField {
+    fn mq_name(&self) -> String {
+        format!("GF({})", self.p())
+    }
+
+    fn mq_element(&self, n: u64) -> Option<Modular> {
+        (n < self.p()).then(|| self.element(n as i64))
+    }
+
+    fn mq_integer(&self, c: &Modular) -> u64 {
+        // Adding zero gives the constants `zero()` and `one()` the modulus.
+        (self.element(0) + *c).value()
+    }
+}
+
+impl MqField<Gf256> for Gf256Field {
+    fn mq_name(&self) -> String {
+        "GF(2)[x] / x^8 + x^4 + x^3 + x^2 + 1".to_string()
+    }
+
+    fn mq_element(&self, n: u64) -> Option<Gf256> {
+        n.to_u8().map(Gf256)
+    }
+
+    fn mq_integer(&self, c: &Gf256) -> u64 {
+        c.0.into()
+    }
+}
+
+/// The header of a system in the MQ challenge format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MqHeader {
+    /// The name of the field, such as `GF(31)`.
+    pub field: String,
+    /// The number of variables `n`.
+    pub vars: usize,
+    /// The number of polynomials `m`.
+    pub polys: usize,
+    /// The seed of the generator of the system, if any.
+    pub seed: Option<u64>,
+}
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+/// The exponents of the monomials of degree at most 2 in `n` variables, in
+/// the order of the MQ challenge format, decreasing in the graded reverse
+/// lexicographic order: `x_1^2, x_1 x_2, x_2^2, x_1 x_3, ..., x_n^2`, then
+/// `x_1, ..., x_n`, then `1`.
+fn monomials<P: PrimInt>(n: usize) -> Vec<Powers<P>> {
+    let mut monomials = Vec::with_capacity((n + 1) * (n + 2) / 2);
+    for j in 0..n {
+        for i in 0..=j {
+            let mut powers: Powers<P> = smallvec![P::zero(); n];
+            powers[i] = powers[i] + P::one();
+            powers[j] = powers[j] + P::one();
+            monomials.push(powers);
+        }
+    }
+    for i in 0..n {
+        let mut powers: Powers<P> = smallvec![P::zero(); n];
+        powers[i] = P::one();
+        monomials.push(powers);
+    }
+    monomials.push(smallvec![P::zero(); n]);
+    monomials
+}
+
+/// The header of the system in the MQ challenge format `input`, which is
+/// needed to make the ring in which to read it with [`parse_mq`].
+pub fn parse_header(input: &str) -> Result<MqHeader, ChidogError> {
+    let mut field = None;
+    let (mut vars, mut polys, mut seed) = (None, None, None);
+    for line in input.lines() {
+        if line.trim_start().starts_with('*') {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let number = || {
+            value
+                .parse::<usize>()
+                .map_err(|_| parse_error(format!("invalid `{key}`")))
+        };
+        if key == "Galois Field" {
+            field = Some(value.to_string());
+        } else if key.starts_with("Number of variables") {
+            vars = Some(number()?);
+        } else if key.starts_with("Number of polynomials") {
+            polys = Some(number()?);
+        } else if key == "Seed" {
+            seed = Some(number()? as u64);
+        }
+    }
+    let missing = |field: &str| parse_error(format!("missing `{field}` in header"));
+    Ok(MqHeader {
+        field: field.ok_or_else(|| missing("Galois Field"))?,
+        vars: vars.ok_or_else(|| missing("Number of variables"))?,
+        polys: polys.ok_or_else(|| missing("Number of polynomials"))?,
+        seed,
+    })
+}
+
+/// The ideal of `ring` generated by the quadratic polynomials of the system
+/// in the MQ challenge format `input`, in the order they are listed. The
+/// format is a header followed by a line of asterisks and one line per
+/// polynomial, listing the coefficients of the monomials of degree at most
+/// 2 in decreasing graded reverse lexicographic order and ending with `;`.
+///
+/// Fails with an `InvalidInput` error if the field of the header is not the
+/// base ring of `ring` or the number of variables is not that of `ring`, and
+/// with a `Parse` error if a line does not have one valid coefficient per
+/// monomial or the number of lines is not the number of polynomials.
+pub fn parse_mq<'a, R, V, K, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Ideal<'a, R, V, K, P>, ChidogError>
+where
+    R: MqField<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    let header = parse_header(input)?;
+    if header.field != ring.base.mq_name() {
+        return Err(ChidogError::InvalidInput(format!(
+            "system over {}, expected {}",
+            header.field,
+            ring.base.mq_name()
+        )));
+    }
+    if header.vars != ring.vars.len() {
+        return Err(ChidogError::InvalidInput(format!(
+            "system in {} variables, expected {}",
+            header.vars,
+            ring.vars.len()
+        )));
+    }
+    let monomials = monomials::<P>(header.vars);
+    let body = input
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with('*'))
+        .skip(1)
+        .filter(|line| !line.trim().is_empty());
+    let mut system = Vec::with_capacity(header.polys);
+    for line in body {
+        let coefficients = line
+            .trim()
+            .strip_suffix(';')
+            .ok_or_else(|| parse_error("polynomial without `;`"))?;
+        let coefficients: Vec<&str> = coefficients.split_whitespace().collect();
+        if coefficients.len() != monomials.len() {
+            return Err(parse_error(format!(
+                "{} coefficients, expected {}",
+                coefficients.len(),
+                monomials.len()
+            )));
+        }
+        let mut terms = Vec::with_capacity(monomials.len());
+        for (c, powers) in coefficients.into_iter().zip(&monomials) {
+            let c = c
+                .parse()
+                .ok()
+                .and_then(|n| ring.base.mq_element(n))
+                .ok_or_else(|| parse_error(format!("invalid coefficient `{c}`")))?;
+            let powers = powers.clone();
+            terms.push((Monomial { powers }, c));
+        }
+        system.push(Polynomial::from_terms(ring, terms)?);
+    }
+    if system.len() != header.polys {
+        return Err(parse_error(format!(
+            "{} polynomials, expected {}",
+            system.len(),
+            header.polys
+        )));
+    }
+    Ok(Ideal::new(ring, system))
+}
+
+/// `system` in the MQ challenge format, with the given seed in its header,
+/// such as
+///
+/// ```text
+/// Galois Field : GF(31)
+/// Number of variables (n) : 2
+/// Number of polynomials (m) : 1
+/// Seed : 0
+/// Order : graded reverse lex order
+///
+/// *********************
+/// 1 0 3 0 5 30 ;
+/// ```
+///
+/// for `x^2 + 3y^2 + 5y - 1`. Fails with an `InvalidInput` error if a
+/// polynomial has degree more than 2. An ideal read by [`parse_mq`] is
+/// written back from its [`Ideal::generators`].
+pub fn to_mq<R, V, K, P>(
+    ring: &PolynomialRing<'_, R, V>,
+    system: &[Polynomial<'_, R, V, K, P>],
+    seed: u64,
+) -> Result<String, ChidogError>
+where
+    R: MqField<K>,
+    K: FieldElement,
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = ring.vars.len();
+    let mut out = String::new();
+    writeln!(out, "Galois Field : {}", ring.base.mq_name()).unwrap();
+    writeln!(out, "Number of variables (n) : {n}").unwrap();
+    writeln!(out, "Number of polynomials (m) : {}", system.len()).unwrap();
+    writeln!(out, "Seed : {seed}").unwrap();
+    writeln!(out, "Order : graded reverse lex order").unwrap();
+    writeln!(out, "\n*********************").unwrap();
+    let monomials = monomials::<P>(n);
+    for f in system {
+        if let Some((m, _)) = f
+            .terms()
+            .find(|(m, _)| m.powers.iter().map(|p| p.to_u64().unwrap()).sum::<u64>() > 2)
+        {
+            return Err(ChidogError::InvalidInput(format!(
+                "monomial {:?} of degree more than 2",
+                m.powers
+                    .iter()
+                    .map(|p| p.to_u64().unwrap())
+                    .collect::<Vec<_>>()
+            )));
+        }
+        for powers in &monomials {
+            let c = f
+                .terms
+                .get(&Monomial {
+                    powers: powers.clone(),
+                })
+                .map_or(0, |c| ring.base.mq_integer(c));
+            write!(out, "{c} ").unwrap();
+        }
+        writeln!(out, ";").unwrap();
+    }
+    Ok(out)
+}
@@ -0,0 +1,610 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::zip;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{BigInt, BigRational, Integer, One, PrimInt, ToPrimitive, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::groebner::{groebner_basis, is_groebner_basis};
+use crate::matrix::Matrix;
+use crate::modular::rational_reconstruction;
+use crate::order::{Lex, MonomialOrder};
+use crate::univariate::DenseUnivariate;
+use crate::univariate::fft::is_prime_u64;
+use crate::{
+    Field, FieldElement, Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring,
+    SemiringElement,
+};
+
+/// The number of primes after which the computations over ℚ in this module
+/// give up, which bounds the size of their results to about 250000 bits.
+const MAX_PRIMES: usize = 4096;
+
+/// A residue modulo a prime below `2^63`, an element of a [`PrimeField`].
+///
+/// The constants `zero()` and `one()` carry no modulus, and adopt the modulus
+/// of whatever they are combined with; combined only with each other, they
+/// behave as integers. Combining residues modulo different primes panics.
+#[derive(Clone, Copy, Debug)]
+pub struct Modular {
+    /// The residue in `[0, modulus)`, or any integer if `modulus` is zero.
+    value: i64,
+    modulus: u64,
+}
+
+/// The field `ℤ/pℤ` for a prime `p` below `2^63`, whose elements are values
+/// of type [`Modular`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrimeField {
+    p: u64,
+}
+
+impl PrimeField {
+    /// The field with `p` elements, failing unless `p` is a prime below
+    /// `2^63`.
+    pub fn new(p: u64) -> Result<Self, ChidogError> {
+        if p >= 1 << 63 || !is_prime_u64(p) {
+            return Err(ChidogError::InvalidInput(format!(
+                "{p} is not a prime below 2^63"
+            )));
+        }
+        Ok(Self { p })
+    }
+
+    pub fn p(&self) -> u64 {
+        self.p
+    }
+
+    /// The residue of `n`.
+    pub fn element(&self, n: i64) -> Modular {
+        Modular {
+            value: n.rem_euclid(self.p as i64),
+            modulus: self.p,
+        }
+    }
+
+    /// The residue of `n`.
+    pub fn from_integer(&self, n: &BigInt) -> Modular {
+        let value = n.mod_floor(&BigInt::from(self.p)).to_i64().unwrap();
+        Modular {
+            value,
+            modulus: self.p,
+        }
+    }
+
+    /// The residue of `q`, or `None` if `p` divides its denominator.
+    pub fn from_rational(&self, q: &BigRational) -> Option<Modular> {
+        let denom = self.from_integer(q.denom()).inv()?;
+        Some(self.from_integer(q.numer()) * denom)
+    }
+}
+
+impl Semiring<Modular> for PrimeField {}
+impl Ring<Modular> for PrimeField {}
+impl Field<Modular> for PrimeField {}
+impl SemiringElement for Modular {}
+impl RingElement for Modular {}
+impl FieldElement for Modular {}
+
+impl Modular {
+    /// The residue in `[0, p)`, for an element of the field with `p`
+    /// elements.
+    pub fn value(&self) -> u64 {
+        self.reduced(self.modulus).value as u64
+    }
+
+    /// The prime `p` of the field containing `self`, or `None` for the
+    /// constants which carry no modulus.
+    pub fn modulus(&self) -> Option<u64> {
+        (self.modulus != 0).then_some(self.modulus)
+    }
+
+    /// The multiplicative inverse of `self`, or `None` if `self` is zero or
+    /// is an integer constant other than `±1`.
+    pub fn inv(&self) -> Option<Self> {
+        if self.modulus == 0 {
+            return (self.value.abs() == 1).then_some(*self);
+        }
+        let m = BigInt::from(self.modulus);
+        let inverse = BigInt::from(self.value).modinv(&m)?;
+        Some(Self {
+            value: inverse.to_i64().unwrap(),
+            modulus: self.modulus,
+        })
+    }
+
+    /// `self` as a residue modulo `m`, where `m` is zero or the modulus of
+    /// `self`.
+    fn reduced(&self, m: u64) -> Self {
+        let value = if m == 0 {
+            self.value
+        } else {
+            self.value.rem_euclid(m as i64)
+        };
+        Self { value, modulus: m }
+    }
+
+    /// The modulus for an operation on `self` and `rhs`.
+    fn common_modulus(&self, rhs: &Self) -> u64 {
+        match (self.modulus, rhs.modulus) {
+            (0, m) | (m, 0) => m,
+            (m, n) => {
+                assert_eq!(m, n, "residues modulo different primes");
+                m
+            }
+        }
+    }
+
+    /// Combines `self` and `rhs` by the integer operation `op`, reduced
+    /// modulo their common modulus.
+    fn combine(self, rhs: Self, op: fn(i128, i128) -> Option<i128>) -> Self {
+        let m = self.common_modulus(&rhs);
+        let (a, b) = (self.reduced(m).value, rhs.reduced(m).value);
+        let c = op(a.into(), b.into()).expect("integer overflow in a residue constant");
+        let value = if m == 0 {
+            c.try_into()
+                .expect("integer overflow in a residue constant")
+        } else {
+            c.rem_euclid(m.into()) as i64
+        };
+        Self { value, modulus: m }
+    }
+}
+
+impl PartialEq for Modular {
+    fn eq(&self, other: &Self) -> bool {
+        let m = self.common_modulus(other);
+        self.reduced(m).value == other.reduced(m).value
+    }
+}
+
+impl Eq for Modular {}
+
+impl Zero for Modular {
+    fn zero() -> Self {
+        Self {
+            value: 0,
+            modulus: 0,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.reduced(self.modulus).value == 0
+    }
+}
+
+impl One for Modular {
+    fn one() -> Self {
+        Self {
+            value: 1,
+            modulus: 0,
+        }
+    }
+}
+
+impl Add for Modular {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, i128::checked_add)
+    }
+}
+
+impl Sub for Modular {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, i128::checked_sub)
+    }
+}
+
+impl Mul for Modular {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, i128::checked_mul)
+    }
+}
+
+impl Div for Modular {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let m = self.common_modulus(&rhs);
+        let inverse = rhs.reduced(m).inv().expect("division by zero");
+        self.combine(inverse, i128::checked_mul)
+    }
+}
+
+impl Neg for Modular {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl AddAssign for Modular {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Modular {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Modular {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Display for Modular {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+impl DenseUnivariate<Modular> {
+    /// Whether `self` is irreducible over `field`, by Rabin's test: a
+    /// polynomial `f` of degree `n > 0` over the field with `p` elements is
+    /// irreducible exactly when `f` divides `x^(p^n) - x` and is coprime to
+    /// `x^(p^(n/q)) - x` for each prime `q` dividing `n`.
+    pub fn is_irreducible(&self, field: &PrimeField) -> bool {
+        let Some(n) = self.degree().filter(|&n| n > 0) else {
+            return false;
+        };
+        let f = self.monic();
+        let x = Self::monomial(field.element(1), 1);
+        // `h^p` modulo `f`, by repeated squaring.
+        let frobenius = |h: &Self| {
+            let (mut power, mut base) = (Self::constant(field.element(1)), h.clone());
+            let mut e = field.p();
+            while e > 0 {
+                if e & 1 == 1 {
+                    power = (power * base.clone()).div_rem(&f).unwrap().1;
+                }
+                base = (base.clone() * base).div_rem(&f).unwrap().1;
+                e >>= 1;
+            }
+            power
+        };
+        // `x^(p^k)` modulo `f` for `k = 0, ..., n`.
+        let mut powers = vec![x.div_rem(&f).unwrap().1];
+        for k in 0..n {
+            powers.push(frobenius(&powers[k]));
+        }
+        let primes =
+            (2..=n).filter(|&q| n.is_multiple_of(q) && (2..q).all(|d| !q.is_multiple_of(d)));
+        (powers[n].clone() - x.clone())
+            .div_rem(&f)
+            .unwrap()
+            .1
+            .is_zero()
+            && primes
+                .into_iter()
+                .all(|q| f.gcd(&(powers[n / q].clone() - x.clone())).degree() == Some(0))
+    }
+}
+
+/// The primes below `2^63`, in decreasing order.
+pub fn word_primes() -> impl Iterator<Item = u64> {
+    (1..1 << 61)
+        .map(|k| (1 << 63) - 2 * k + 1)
+        .filter(|&p| is_prime_u64(p))
+}
+
+/// The images of a computation modulo several primes that agree in shape,
+/// combined by the Chinese remainder theorem.
+struct Images<S> {
+    shape: S,
+    modulus: BigInt,
+    residues: Vec<BigInt>,
+    /// The rational reconstruction of `residues`, if every one of them has
+    /// one.
+    candidate: Option<Vec<BigRational>>,
+}
+
+impl<S> Images<S> {
+    fn new(shape: S, len: usize) -> Self {
+        Self {
+            shape,
+            modulus: BigInt::one(),
+            residues: vec![BigInt::zero(); len],
+            candidate: None,
+        }
+    }
+
+    /// Adds the image modulo `p` and updates the candidate.
+    fn combine(&mut self, p: u64, image: &[u64]) {
+        let prime = BigInt::from(p);
+        let inverse = self.modulus.mod_floor(&prime).modinv(&prime).unwrap();
+        for (r, s) in zip(&mut self.residues, image) {
+            let t = ((BigInt::from(*s) - &*r) * &inverse).mod_floor(&prime);
+            *r += &self.modulus * t;
+        }
+        self.modulus *= prime;
+        self.candidate = self
+            .residues
+            .iter()
+            .map(|r| rational_reconstruction(r, &self.modulus))
+            .collect();
+    }
+}
+
+/// Runs a computation over ℚ modulo word-sized primes and reconstructs its
+/// result from the images, by the Chinese remainder theorem and rational
+/// reconstruction.
+///
+/// For each prime `p` from [`word_primes`], at most `max_primes` of them,
+/// `compute` returns the result modulo `p` as a shape, such as the support
+/// of a polynomial, and a list of residues, or `None` if `p` is bad for the
+/// input (typically because it divides a denominator). Images are combined
+/// only with images of the same shape, so an unlucky prime whose image has
+/// the wrong shape (such as a GCD of too high a degree) does not spoil the
+/// others.
+///
+/// Once the rationals reconstructed from some images reduce to the image
+/// modulo a further prime, they are passed to `accept`, which may verify
+/// them over ℚ; the first accepted shape and rationals are returned. With
+/// `accept` always true, a wrong result requires a further prime to divide a
+/// nonzero difference, an event of probability about `2^-62` per residue.
+pub fn multimodular<S, F, A>(
+    max_primes: usize,
+    mut compute: F,
+    mut accept: A,
+) -> Result<(S, Vec<BigRational>), ChidogError>
+where
+    S: Eq,
+    F: FnMut(&PrimeField) -> Option<(S, Vec<Modular>)>,
+    A: FnMut(&S, &[BigRational]) -> bool,
+{
+    let mut groups: Vec<Images<S>> = Vec::new();
+    for p in word_primes().take(max_primes) {
+        let field = PrimeField { p };
+        let Some((shape, image)) = compute(&field) else {
+            continue;
+        };
+        let image: Vec<u64> = image.iter().map(|c| c.reduced(p).value as u64).collect();
+        let k = match groups
+            .iter()
+            .position(|g| g.shape == shape && g.residues.len() == image.len())
+        {
+            Some(k) => k,
+            None => {
+                groups.push(Images::new(shape, image.len()));
+                groups.len() - 1
+            }
+        };
+        let group = &mut groups[k];
+        if let Some(candidate) = group.candidate.take() {
+            let stable = zip(&candidate, &image)
+                .all(|(q, r)| field.from_rational(q).is_some_and(|c| c.value as u64 == *r));
+            if stable && accept(&group.shape, &candidate) {
+                return Ok((groups.swap_remove(k).shape, candidate));
+            }
+        }
+        group.combine(p, &image);
+    }
+    Err(ChidogError::ReconstructionFailed(max_primes))
+}
+
+/// The image of `f` in `ring`, or `None` if `p` divides a denominator of a
+/// coefficient of `f`.
+fn image<'b, R, V, P>(
+    f: &Polynomial<'_, R, V, BigRational, P>,
+    ring: &'b PolynomialRing<'b, PrimeField, usize>,
+) -> Option<Polynomial<'b, PrimeField, usize, Modular, P>>
+where
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut terms = HashMap::with_capacity_and_hasher(f.terms.len(), Default::default());
+    for (m, c) in &f.terms {
+        let c = ring.base.from_rational(c)?;
+        if !c.is_zero() {
+            terms.insert(m.clone(), c);
+        }
+    }
+    Some(Polynomial {
+        elem_of: ring,
+        terms,
+    })
+}
+
+/// The monomials of `f` in decreasing order, and its coefficients in the
+/// same order.
+fn flatten<R, V, P, O>(
+    f: &Polynomial<'_, R, V, Modular, P>,
+    order: &O,
+) -> (Vec<Monomial<P>>, Vec<Modular>)
+where
+    R: Semiring<Modular>,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    f.sorted_terms(order)
+        .into_iter()
+        .map(|(m, c)| (m.clone(), *c))
+        .unzip()
+}
+
+impl Matrix<BigRational> {
+    /// The determinant of `self`, computed modulo word-sized primes by
+    /// [`multimodular`].
+    pub fn det_multimodular(&self) -> Result<BigRational, ChidogError> {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let (_, det) = multimodular(
+            MAX_PRIMES,
+            |field| {
+                let entries = (0..self.nrows())
+                    .flat_map(|i| self.row(i))
+                    .map(|q| field.from_rational(q))
+                    .collect::<Option<_>>()?;
+                let m = Matrix::new(self.nrows(), self.ncols(), entries).unwrap();
+                Some(((), vec![m.det()]))
+            },
+            |_, _| true,
+        )?;
+        Ok(det[0].clone())
+    }
+}
+
+impl<'a, R, V, P> Polynomial<'a, R, V, BigRational, P>
+where
+    R: Field<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// The polynomial with the given monomials and coefficients.
+    fn assemble(&self, monomials: &[Monomial<P>], coeffs: &[BigRational]) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: zip(monomials.iter().cloned(), coeffs.iter().cloned()).collect(),
+        }
+    }
+
+    /// The monic GCD of [`Polynomial::gcd`], computed modulo word-sized
+    /// primes by [`multimodular`].
+    ///
+    /// Primes at which the leading monomial of either input vanishes are
+    /// skipped, and images whose support differs from that of the true GCD
+    /// are kept apart from it. The result is verified by trial division.
+    pub fn gcd_multimodular(&self, other: &Self) -> Result<Self, ChidogError> {
+        if self.terms.is_empty() || other.terms.is_empty() {
+            return Ok(self.gcd(other));
+        }
+        let n = self.elem_of.vars.len();
+        let lead = |f: &Self| f.sorted_terms(&Lex)[0].0.clone();
+        let (lead_f, lead_g) = (lead(self), lead(other));
+        let (monomials, coeffs) = multimodular(
+            MAX_PRIMES,
+            |field| {
+                let ring = PolynomialRing::new((0..n).collect(), field);
+                let f = image(self, &ring)?;
+                let g = image(other, &ring)?;
+                if !f.terms.contains_key(&lead_f) || !g.terms.contains_key(&lead_g) {
+                    return None;
+                }
+                Some(flatten(&f.gcd(&g), &Lex))
+            },
+            |monomials, coeffs| {
+                let h = self.assemble(monomials, coeffs);
+                self.div_exact(&h).is_some() && other.div_exact(&h).is_some()
+            },
+        )?;
+        Ok(self.assemble(&monomials, &coeffs))
+    }
+}
+
+/// How much of a Gröbner basis reconstructed from its images modulo primes
+/// is checked over ℚ before it is returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// Every S-pair of the basis and every generator reduces to zero by it,
+    /// so that it is a Gröbner basis of an ideal containing the input.
+    Full,
+    /// Every generator reduces to zero by the basis, which skips the
+    /// S-pairs, by far the costlier part of the check.
+    Generators,
+    /// Nothing is checked over ℚ; the basis is only known to agree with its
+    /// image modulo one more prime than it was reconstructed from.
+    Probabilistic,
+}
+
+/// The reduced Gröbner basis of [`groebner_basis`], computed modulo
+/// word-sized primes by [`multimodular`], with [`Verification::Full`].
+pub fn groebner_basis_multimodular<'a, R, V, P, O>(
+    generators: &[Polynomial<'a, R, V, BigRational, P>],
+    order: &O,
+) -> Result<Vec<Polynomial<'a, R, V, BigRational, P>>, ChidogError>
+where
+    R: Field<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    groebner_basis_multimodular_verified(generators, order, Verification::Full)
+}
+
+/// The reduced Gröbner basis of [`groebner_basis`], computed modulo
+/// word-sized primes by [`multimodular`] and checked over ℚ as `verification`
+/// directs.
+///
+/// Primes at which a leading coefficient of a generator vanishes are
+/// skipped, and images with different leading monomials are kept apart, so
+/// that a prime whose image has extra leading monomials does not spoil the
+/// reconstruction. Each image is computed with [`groebner_basis`] over
+/// [`PrimeField`], free of coefficient growth, and the coefficients over ℚ
+/// are reconstructed by the Chinese remainder theorem and rational
+/// reconstruction once they stabilize.
+pub fn groebner_basis_multimodular_verified<'a, R, V, P, O>(
+    generators: &[Polynomial<'a, R, V, BigRational, P>],
+    order: &O,
+    verification: Verification,
+) -> Result<Vec<Polynomial<'a, R, V, BigRational, P>>, ChidogError>
+where
+    R: Field<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let Some(first) = generators.first() else {
+        return Ok(Vec::new());
+    };
+    let n = first.elem_of.vars.len();
+    let leads: Vec<_> = generators
+        .iter()
+        .map(|f| f.sorted_terms(order).first().map(|(m, _)| (*m).clone()))
+        .collect();
+    let assemble = |shape: &Vec<Vec<Monomial<P>>>, coeffs: &[BigRational]| {
+        let mut coeffs = coeffs.iter();
+        shape
+            .iter()
+            .map(|monomials| {
+                let c: Vec<_> = coeffs.by_ref().take(monomials.len()).cloned().collect();
+                first.assemble(monomials, &c)
+            })
+            .collect::<Vec<_>>()
+    };
+    let (shape, coeffs) = multimodular(
+        MAX_PRIMES,
+        |field| {
+            let ring = PolynomialRing::new((0..n).collect(), field);
+            let mut images = Vec::with_capacity(generators.len());
+            for (f, lead) in zip(generators, &leads) {
+                let f = image(f, &ring)?;
+                if lead.as_ref().is_some_and(|m| !f.terms.contains_key(m)) {
+                    return None;
+                }
+                images.push(f);
+            }
+            let (shape, coeffs): (Vec<_>, Vec<_>) = groebner_basis(&images, order)
+                .iter()
+                .map(|g| flatten(g, order))
+                .unzip();
+            Some((shape, coeffs.concat()))
+        },
+        |shape, coeffs| {
+            let basis = assemble(shape, coeffs);
+            let reduces = || {
+                generators
+                    .iter()
+                    .all(|f| f.rem(&basis, order).terms.is_empty())
+            };
+            match verification {
+                Verification::Full => is_groebner_basis(&basis, order) && reduces(),
+                Verification::Generators => reduces(),
+                Verification::Probabilistic => true,
+            }
+        },
+    )?;
+    Ok(assemble(&shape, &coeffs))
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, One, PrimInt, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::expr::Expr;
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// An XML element, with the text it directly contains.
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attrs: HashMap<String, String>,
+    pub(crate) children: Vec<Element>,
+    pub(crate) text: String,
+}
+
+impl Element {
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(String::as_str)
+    }
+
+    /// Whether `self` is the OpenMath symbol `name` of the content
+    /// dictionary `cd`.
+    pub(crate) fn is_symbol(&self, cd: &str, name: &str) -> bool {
+        self.name == "OMS" && self.attr("cd") == Some(cd) && self.attr("name") == Some(name)
+    }
+
+    /// Appends `self` to `xml`, with its attributes in alphabetical order.
+    pub(crate) fn write_xml(&self, xml: &mut String) {
+        xml.push('<');
+        xml.push_str(&self.name);
+        let mut attrs: Vec<_> = self.attrs.iter().collect();
+        attrs.sort_unstable();
+        for (name, value) in attrs {
+            write!(xml, " {name}=\"{}\"", escape(value)).unwrap();
+        }
+        if self.children.is_empty() && self.text.is_empty() {
+            xml.push_str("/>");
+            return;
+        }
+        xml.push('>');
+        xml.push_str(&escape(&self.text));
+        for child in &self.children {
+            child.write_xml(xml);
+        }
+        write!(xml, "</{}>", self.name).unwrap();
+    }
+}
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The text of `input` up to `end`, and the rest after it.
+fn split_at_end<'s>(input: &'s str, end: &str) -> Result<(&'s str, &'s str), ChidogError> {
+    input
+        .split_once(end)
+        .ok_or_else(|| parse_error(format!("missing `{end}`")))
+}
+
+/// The name of an element or attribute, without its namespace prefix.
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+/// The element, and whether it closes itself, of the start tag `tag`,
+/// without its angle brackets.
+pub(crate) fn parse_start_tag(tag: &str) -> Result<(Element, bool), ChidogError> {
+    let (tag, closed) = match tag.strip_suffix('/') {
+        Some(tag) => (tag, true),
+        None => (tag, false),
+    };
+    let tag = tag.trim();
+    let (name, mut rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+    let mut element = Element {
+        name: local_name(name).to_string(),
+        ..Element::default()
+    };
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok((element, closed));
+        }
+        let (attr, value) = split_at_end(rest, "=")?;
+        let value = value.trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|&q| q == '"' || q == '\'')
+            .ok_or_else(|| parse_error("unquoted attribute value"))?;
+        let (value, after) = split_at_end(&value[1..], &quote.to_string())?;
+        element
+            .attrs
+            .insert(local_name(attr.trim()).to_string(), unescape(value));
+        rest = after;
+    }
+}
+
+/// The root element of the XML document `input`.
+///
+/// This reads the subset of XML used by OpenMath: elements, attributes,
+/// character data and the predefined entities, skipping declarations,
+/// processing instructions and comments.
+pub(crate) fn parse_xml(input: &str) -> Result<Element, ChidogError> {
+    let mut stack = vec![Element::default()];
+    let mut rest = input;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = split_at_end(after, "?>")?.1;
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = split_at_end(after, "-->")?.1;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = split_at_end(after, ">")?.1;
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let (name, after) = split_at_end(after, ">")?;
+            let element = stack
+                .pop()
+                .filter(|e| e.name == local_name(name.trim()) && !stack.is_empty())
+                .ok_or_else(|| parse_error(format!("unexpected `</{name}>`")))?;
+            stack.last_mut().unwrap().children.push(element);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('<') {
+            let (tag, after) = split_at_end(after, ">")?;
+            let (element, closed) = parse_start_tag(tag)?;
+            if closed {
+                stack.last_mut().unwrap().children.push(element);
+            } else {
+                stack.push(element);
+            }
+            rest = after;
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            stack
+                .last_mut()
+                .unwrap()
+                .text
+                .push_str(&unescape(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+    match (stack.pop(), stack.is_empty()) {
+        (Some(mut root), true) if root.children.len() == 1 => Ok(root.children.pop().unwrap()),
+        _ => Err(parse_error("expected one root element")),
+    }
+}
+
+/// The integer of an `OMI` element.
+fn integer(e: &Element) -> Result<BigInt, ChidogError> {
+    let text = e.text.trim();
+    if e.name != "OMI" {
+        return Err(parse_error(format!("expected `OMI`, found `{}`", e.name)));
+    }
+    text.parse()
+        .map_err(|_| parse_error(format!("invalid integer `{text}`")))
+}
+
+/// The expression of the OpenMath object `e`, with variables named by
+/// `names`.
+fn to_expr(e: &Element, names: &HashMap<String, usize>) -> Result<Expr<BigRational>, ChidogError> {
+    let unsupported = || parse_error(format!("unsupported OpenMath object `{}`", e.name));
+    match e.name.as_str() {
+        "OMI" => Ok(Expr::Constant(BigRational::from_integer(integer(e)?))),
+        "OMF" => {
+            let dec = e.attr("dec").ok_or_else(unsupported)?;
+            let x: f64 = dec
+                .parse()
+                .map_err(|_| parse_error(format!("invalid float `{dec}`")))?;
+            BigRational::from_float(x)
+                .map(Expr::Constant)
+                .ok_or_else(unsupported)
+        }
+        "OMV" => {
+            let name = e.attr("name").ok_or_else(unsupported)?;
+            names
+                .get(name)
+                .map(|&i| Expr::Var(i))
+                .ok_or_else(|| ChidogError::InvalidInput(format!("unknown variable `{name}`")))
+        }
+        "OMA" => {
+            let (head, args) = e.children.split_first().ok_or_else(unsupported)?;
+            if head.is_symbol("polyd1", "DMP") {
+                return dmp(args, names);
+            }
+            if head.is_symbol("nums1", "rational") {
+                let [n, d] = args else {
+                    return Err(unsupported());
+                };
+                let d = integer(d)?;
+                if d.is_zero() {
+                    return Err(parse_error("zero denominator"));
+                }
+                return Ok(Expr::Constant(BigRational::new(integer(n)?, d)));
+            }
+            if head.is_symbol("arith1", "power") {
+                let [base, exponent] = args else {
+                    return Err(unsupported());
+                };
+                let exponent = integer(exponent)?
+                    .try_into()
+                    .map_err(|_| parse_error("invalid exponent"))?;
+                return Ok(to_expr(base, names)?.pow(exponent));
+            }
+            let mut args = args
+                .iter()
+                .map(|a| to_expr(a, names))
+                .collect::<Result<Vec<_>, _>>()?;
+            let negate = |e| Expr::Product(vec![Expr::Constant(-BigRational::one()), e]);
+            let name = head.attr("name").unwrap_or_default();
+            match (head.attr("cd"), name, args.len()) {
+                (Some("arith1"), "plus", _) => Ok(Expr::Sum(args)),
+                (Some("arith1"), "times", _) => Ok(Expr::Product(args)),
+                (Some("arith1"), "unary_minus", 1) => Ok(negate(args.pop().unwrap())),
+                (Some("arith1"), "minus", 2) => {
+                    let b = negate(args.pop().unwrap());
+                    Ok(Expr::Sum(vec![args.pop().unwrap(), b]))
+                }
+                (Some("arith1"), "divide", 2) => match args.pop().unwrap() {
+                    Expr::Constant(d) if !d.is_zero() => Ok(Expr::Product(vec![
+                        args.pop().unwrap(),
+                        Expr::Constant(d.recip()),
+                    ])),
+                    _ => Err(unsupported()),
+                },
+                _ => Err(unsupported()),
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// The expression of the distributed polynomial `DMP(ring, SDMP(terms))`
+/// with arguments `args`, whose variables are matched to `names` by name in
+/// a `poly_ring_d_named`, or by position in a `poly_ring_d`.
+fn dmp(args: &[Element], names: &HashMap<String, usize>) -> Result<Expr<BigRational>, ChidogError> {
+    let unsupported = || parse_error("unsupported polyd1 polynomial");
+    let [ring, sdmp] = args else {
+        return Err(unsupported());
+    };
+    let (ring_head, ring_args) = ring.children.split_first().ok_or_else(unsupported)?;
+    let vars: Vec<usize> = if ring_head.is_symbol("polyd1", "poly_ring_d_named") {
+        let (_, named) = ring_args.split_first().ok_or_else(unsupported)?;
+        named
+            .iter()
+            .map(|v| match to_expr(v, names)? {
+                Expr::Var(i) => Ok(i),
+                _ => Err(unsupported()),
+            })
+            .collect::<Result<_, _>>()?
+    } else if ring_head.is_symbol("polyd1", "poly_ring_d") {
+        let [_, n] = ring_args else {
+            return Err(unsupported());
+        };
+        let n = integer(n)?;
+        if n != BigInt::from(names.len()) {
+            return Err(ChidogError::InvalidInput(format!(
+                "polynomial in {n} variables in a ring of {}",
+                names.len()
+            )));
+        }
+        (0..names.len()).collect()
+    } else {
+        return Err(unsupported());
+    };
+    let (sdmp_head, terms) = sdmp.children.split_first().ok_or_else(unsupported)?;
+    if !sdmp_head.is_symbol("polyd1", "SDMP") {
+        return Err(unsupported());
+    }
+    let terms = terms
+        .iter()
+        .map(|term| {
+            let (head, args) = term.children.split_first().ok_or_else(unsupported)?;
+            let (coeff, exponents) = args.split_first().ok_or_else(unsupported)?;
+            if !head.is_symbol("polyd1", "term") || exponents.len() != vars.len() {
+                return Err(unsupported());
+            }
+            let mut factors = vec![to_expr(coeff, names)?];
+            for (&i, e) in vars.iter().zip(exponents) {
+                let e = integer(e)?
+                    .try_into()
+                    .map_err(|_| parse_error("invalid exponent"))?;
+                factors.push(Expr::Var(i).pow(e));
+            }
+            Ok(Expr::Product(factors))
+        })
+        .collect::<Result<_, ChidogError>>()?;
+    Ok(Expr::Sum(terms))
+}
+
+/// The OpenMath element of the rational number `c`: an `OMI` for an
+/// integer, and a `rational` of the `nums1` content dictionary otherwise.
+fn number(c: &BigRational) -> String {
+    if c.is_integer() {
+        format!("<OMI>{}</OMI>", c.numer())
+    } else {
+        format!(
+            "<OMA><OMS cd=\"nums1\" name=\"rational\"/><OMI>{}</OMI><OMI>{}</OMI></OMA>",
+            c.numer(),
+            c.denom()
+        )
+    }
+}
+
+/// `f` as an OpenMath 2.0 object in the XML encoding: a distributed
+/// polynomial `DMP` of the `polyd1` content dictionary, in a
+/// `poly_ring_d_named` over the rationals `Q` whose variables are those of
+/// the ring of `f`, with its terms in decreasing lexicographic order.
+pub fn to_openmath<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut xml =
+        String::from("<OMOBJ xmlns=\"http://www.openmath.org/OpenMath\" version=\"2.0\">");
+    xml.push_str("<OMA><OMS cd=\"polyd1\" name=\"DMP\"/>");
+    xml.push_str(
+        "<OMA><OMS cd=\"polyd1\" name=\"poly_ring_d_named\"/><OMS cd=\"setname1\" name=\"Q\"/>",
+    );
+    for v in &f.elem_of.vars {
+        write!(xml, "<OMV name=\"{}\"/>", escape(&v.to_string())).unwrap();
+    }
+    xml.push_str("</OMA><OMA><OMS cd=\"polyd1\" name=\"SDMP\"/>");
+    let mut terms: Vec<_> = f.terms.iter().collect();
+    terms.sort_unstable_by(|(a, _), (b, _)| b.powers.cmp(&a.powers));
+    for (m, c) in terms {
+        xml.push_str("<OMA><OMS cd=\"polyd1\" name=\"term\"/>");
+        xml.push_str(&number(&c.clone().into()));
+        for p in &m.powers {
+            write!(xml, "<OMI>{}</OMI>", p.to_u64().unwrap()).unwrap();
+        }
+        xml.push_str("</OMA>");
+    }
+    xml.push_str("</OMA></OMA></OMOBJ>");
+    xml
+}
+
+/// The polynomial in `ring` of the OpenMath object `xml`, in the XML
+/// encoding, expanded.
+///
+/// The object is either a `DMP` of the `polyd1` content dictionary, as
+/// written by [`to_openmath`], or an expression built from integers
+/// (`OMI`), floats (`OMF`), rationals of `nums1` and variables (`OMV`) by
+/// the `plus`, `times`, `minus`, `unary_minus`, `power` and `divide` of
+/// `arith1`, dividing only by constants. Variables are matched to those of
+/// `ring` by name, except in a `DMP` over an unnamed `poly_ring_d`, whose
+/// variables are those of `ring` in order.
+pub fn from_openmath<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    xml: &str,
+) -> Result<Polynomial<'a, R, V, BigRational, P>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let names = ring
+        .vars
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.to_string(), i))
+        .collect();
+    let root = parse_xml(xml)?;
+    let object = match root.name.as_str() {
+        "OMOBJ" => match root.children.as_slice() {
+            [object] => object,
+            _ => return Err(parse_error("expected one object in `OMOBJ`")),
+        },
+        _ => &root,
+    };
+    to_expr(object, &names)?.to_polynomial(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use super::*;
+    use crate::AlreadyRing;
+
+    type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+    #[test]
+    fn round_trip_matches_display_parser() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let vars = ["x", "y", "z"].map(String::from).to_vec();
+        let ring = PolynomialRing::new(vars, &base);
+        for input in [
+            "x^2*y - 3/4*z + 1",
+            "-x*y^3 - 2*z^5 + 7",
+            "-1/3*x^4*y*z^2 + 5/2*y - z",
+            "x - y",
+            "-5/7",
+            "42",
+            "0",
+        ] {
+            let f = Poly::from_sympy(&ring, input).unwrap();
+            let expected = Poly::from_sympy(&ring, &f.to_string()).unwrap();
+            let g: Poly = from_openmath(&ring, &to_openmath(&f)).unwrap();
+            assert_eq!(g.terms, expected.terms, "{input}");
+            assert_eq!(g.terms, f.terms, "{input}");
+        }
+    }
+}
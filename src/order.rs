@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use num::{PrimInt, Unsigned};
+
+use crate::Monomial;
+use crate::fixed::FixedMonomial;
+use crate::packed::{PackedMonomial, Packing};
+
+/// A monomial order: a total order on monomials in which `1` is least and
+/// which is compatible with multiplication, so that `a < b` implies `a*c <
+/// b*c`. Variables are ranked by index, with `x_0 > x_1 > ...`.
+pub trait MonomialOrder {
+    fn cmp<P: PrimInt>(&self, a: &Monomial<P>, b: &Monomial<P>) -> Ordering;
+
+    /// Compares monomials packed by `packing`. By default they are unpacked
+    /// and compared by [`MonomialOrder::cmp`]; orders that can compare the
+    /// packed words directly should override this.
+    fn cmp_packed<W: PrimInt + Unsigned>(
+        &self,
+        packing: &Packing<W>,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Ordering {
+        self.cmp::<W>(&packing.unpack(a), &packing.unpack(b))
+    }
+
+    /// Compares monomials of fixed arity. By default they are converted and
+    /// compared by [`MonomialOrder::cmp`]; orders that can compare the
+    /// arrays directly should override this.
+    fn cmp_fixed<P: PrimInt, const N: usize>(
+        &self,
+        a: &FixedMonomial<P, N>,
+        b: &FixedMonomial<P, N>,
+    ) -> Ordering {
+        self.cmp(&a.to_monomial(), &b.to_monomial())
+    }
+}
+
+/// The lexicographic order, comparing exponents of `x_0` first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lex;
+
+/// The graded lexicographic order: total degree first, ties broken by
+/// [`Lex`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GrLex;
+
+/// The graded reverse lexicographic order: total degree first, then the
+/// monomial with the smaller exponent in the last variable where they differ
+/// is greater.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GRevLex;
+
+fn total_degree<P: PrimInt>(powers: &[P]) -> u128 {
+    powers.iter().map(|p| p.to_u128().unwrap()).sum()
+}
+
+fn cmp_grlex<P: PrimInt>(a: &[P], b: &[P]) -> Ordering {
+    total_degree(a).cmp(&total_degree(b)).then_with(|| a.cmp(b))
+}
+
+fn cmp_grevlex<P: PrimInt>(a: &[P], b: &[P]) -> Ordering {
+    total_degree(a).cmp(&total_degree(b)).then_with(|| {
+        a.iter()
+            .zip(b)
+            .rev()
+            .find(|(p, q)| p != q)
+            .map_or(Ordering::Equal, |(p, q)| q.cmp(p))
+    })
+}
+
+impl MonomialOrder for Lex {
+    fn cmp<P: PrimInt>(&self, a: &Monomial<P>, b: &Monomial<P>) -> Ordering {
+        a.powers.cmp(&b.powers)
+    }
+
+    fn cmp_packed<W: PrimInt + Unsigned>(
+        &self,
+        packing: &Packing<W>,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Ordering {
+        packing.cmp_lex(a, b)
+    }
+
+    fn cmp_fixed<P: PrimInt, const N: usize>(
+        &self,
+        a: &FixedMonomial<P, N>,
+        b: &FixedMonomial<P, N>,
+    ) -> Ordering {
+        a.powers.cmp(&b.powers)
+    }
+}
+
+impl MonomialOrder for GrLex {
+    fn cmp<P: PrimInt>(&self, a: &Monomial<P>, b: &Monomial<P>) -> Ordering {
+        cmp_grlex(&a.powers, &b.powers)
+    }
+
+    fn cmp_packed<W: PrimInt + Unsigned>(
+        &self,
+        packing: &Packing<W>,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Ordering {
+        packing.cmp_grlex(a, b)
+    }
+
+    fn cmp_fixed<P: PrimInt, const N: usize>(
+        &self,
+        a: &FixedMonomial<P, N>,
+        b: &FixedMonomial<P, N>,
+    ) -> Ordering {
+        cmp_grlex(&a.powers, &b.powers)
+    }
+}
+
+impl MonomialOrder for GRevLex {
+    fn cmp<P: PrimInt>(&self, a: &Monomial<P>, b: &Monomial<P>) -> Ordering {
+        cmp_grevlex(&a.powers, &b.powers)
+    }
+
+    fn cmp_packed<W: PrimInt + Unsigned>(
+        &self,
+        packing: &Packing<W>,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Ordering {
+        packing.cmp_grevlex(a, b)
+    }
+
+    fn cmp_fixed<P: PrimInt, const N: usize>(
+        &self,
+        a: &FixedMonomial<P, N>,
+        b: &FixedMonomial<P, N>,
+    ) -> Ordering {
+        cmp_grevlex(&a.powers, &b.powers)
+    }
+}
+
+impl<P: PrimInt> Monomial<P> {
+    /// Compares `self` with `other` in `order`.
+    pub fn cmp_with<O: MonomialOrder>(&self, other: &Self, order: &O) -> Ordering {
+        order.cmp(self, other)
+    }
+}
+
+/// A monomial `M` ordered by the monomial order `O`, so that monomials can
+/// key a `BTreeMap` or fill a `BinaryHeap` in an order chosen at the type
+/// level. `M` is a [`Monomial`] or a [`FixedMonomial`].
+///
+/// The order is held by value, as the orders of this module are zero-sized.
+#[derive(Clone, Copy, Debug)]
+pub struct Ordered<M, O> {
+    pub monomial: M,
+    pub order: O,
+}
+
+impl<M, O: Default> Ordered<M, O> {
+    /// `monomial` in the default value of `O`.
+    pub fn new(monomial: M) -> Self {
+        Self {
+            monomial,
+            order: O::default(),
+        }
+    }
+}
+
+impl<M, O> Ordered<M, O> {
+    /// The monomial, without its order.
+    pub fn into_inner(self) -> M {
+        self.monomial
+    }
+}
+
+impl<P: PrimInt, O: MonomialOrder> Ord for Ordered<Monomial<P>, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order.cmp(&self.monomial, &other.monomial)
+    }
+}
+
+impl<P: PrimInt, const N: usize, O: MonomialOrder> Ord for Ordered<FixedMonomial<P, N>, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order.cmp_fixed(&self.monomial, &other.monomial)
+    }
+}
+
+/// Equality agrees with [`Ord`], ignoring the order values.
+impl<M, O> PartialEq for Ordered<M, O>
+where
+    Self: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<M, O> Eq for Ordered<M, O> where Self: Ord {}
+
+impl<M, O> PartialOrd for Ordered<M, O>
+where
+    Self: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hashes the monomial alone, as equal monomials are equal in every order.
+impl<M: Hash, O> Hash for Ordered<M, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.monomial.hash(state);
+    }
+}
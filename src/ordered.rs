@@ -0,0 +1,360 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::zip;
+use std::ops::{Add, Mul, Sub};
+
+use num::{One, PrimInt, Unsigned, Zero};
+
+use crate::geobucket::Geobucket;
+use crate::order::MonomialOrder;
+use crate::{
+    Field, FieldElement, Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring,
+    SemiringElement, heap_product,
+};
+
+/// A variable `my_ring: OrderedPolynomialRing<R, V, O>` represents the
+/// polynomial ring `poly` with its elements stored as lists of terms sorted
+/// by the monomial order `order`. Its elements are of type
+/// `OrderedPolynomial<'_, R, V, K, P, O>`.
+///
+/// Where a [`Polynomial`] must sort its terms whenever an algorithm needs
+/// them in order, these keep them sorted: the leading term is found in
+/// constant time, a coefficient by binary search, and iteration in order
+/// needs no sort. Sums merge the sorted lists and products come out of the
+/// heap merge already sorted, so the order is maintained in linear time.
+pub struct OrderedPolynomialRing<'a, R, V, O> {
+    pub poly: &'a PolynomialRing<'a, R, V>,
+    pub order: O,
+}
+
+/// A polynomial stored as its nonzero terms sorted by the order of its ring.
+pub struct OrderedPolynomial<'a, R, V, K, P, O> {
+    pub elem_of: &'a OrderedPolynomialRing<'a, R, V, O>,
+    /// The terms in increasing order, so that the leading term is last.
+    terms: Vec<(Monomial<P>, K)>,
+}
+
+impl<R, V, K, P, O> Clone for OrderedPolynomial<'_, R, V, K, P, O>
+where
+    K: Clone,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+impl<'a, R, V, O: MonomialOrder> OrderedPolynomialRing<'a, R, V, O> {
+    pub fn new(poly: &'a PolynomialRing<'a, R, V>, order: O) -> Self {
+        Self { poly, order }
+    }
+
+    pub fn zero<K, P>(&'a self) -> OrderedPolynomial<'a, R, V, K, P, O> {
+        OrderedPolynomial {
+            elem_of: self,
+            terms: Vec::new(),
+        }
+    }
+
+    /// The term `c * m`.
+    pub fn term<K, P>(&'a self, m: Monomial<P>, c: K) -> OrderedPolynomial<'a, R, V, K, P, O>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+    {
+        let mut f = self.zero();
+        if !self.poly.base.is_zero(&c) {
+            f.terms.push((m, c));
+        }
+        f
+    }
+
+    /// The polynomial `f`, with its terms sorted.
+    pub fn from_polynomial<K, P>(
+        &'a self,
+        f: &Polynomial<'_, R, V, K, P>,
+    ) -> OrderedPolynomial<'a, R, V, K, P, O>
+    where
+        K: Clone,
+        P: Hash + PrimInt,
+    {
+        let mut terms: Vec<_> = f
+            .terms
+            .iter()
+            .map(|(m, c)| (m.clone(), c.clone()))
+            .collect();
+        terms.sort_by(|(a, _), (b, _)| self.order.cmp(a, b));
+        OrderedPolynomial {
+            elem_of: self,
+            terms,
+        }
+    }
+}
+
+impl<'a, R, V, K, P, O> OrderedPolynomial<'a, R, V, K, P, O>
+where
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    /// The number of terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The terms in decreasing order.
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (&Monomial<P>, &K)> {
+        self.terms.iter().rev().map(|(m, c)| (m, c))
+    }
+
+    /// The leading term, or `None` for zero.
+    pub fn leading_term(&self) -> Option<(&Monomial<P>, &K)> {
+        self.terms.last().map(|(m, c)| (m, c))
+    }
+
+    pub fn leading_monomial(&self) -> Option<&Monomial<P>> {
+        self.terms.last().map(|(m, _)| m)
+    }
+
+    pub fn leading_coeff(&self) -> Option<&K> {
+        self.terms.last().map(|(_, c)| c)
+    }
+
+    /// Removes and returns the leading term, or `None` for zero.
+    pub fn pop_leading(&mut self) -> Option<(Monomial<P>, K)> {
+        self.terms.pop()
+    }
+
+    /// The coefficient of `m`, or `None` if it is zero.
+    pub fn coeff(&self, m: &Monomial<P>) -> Option<&K> {
+        let order = &self.elem_of.order;
+        self.terms
+            .binary_search_by(|(n, _)| order.cmp(n, m))
+            .ok()
+            .map(|i| &self.terms[i].1)
+    }
+
+    /// `self` as a [`Polynomial`] in the underlying ring.
+    pub fn to_polynomial(&self) -> Polynomial<'a, R, V, K, P>
+    where
+        K: Clone,
+    {
+        Polynomial {
+            elem_of: self.elem_of.poly,
+            terms: self.terms.iter().cloned().collect(),
+        }
+    }
+
+    /// Merges the terms of `self` and `rhs`, combining the coefficients of a
+    /// monomial in both with `combine`, which returns whether the result is
+    /// zero, and mapping those only in `rhs` with `only_rhs`.
+    fn merge(
+        self,
+        rhs: Self,
+        combine: impl Fn(&mut K, K) -> bool,
+        only_rhs: impl Fn(K) -> K,
+    ) -> Self {
+        let order = &self.elem_of.order;
+        let mut terms = Vec::with_capacity(self.terms.len() + rhs.terms.len());
+        let mut a = self.terms.into_iter().peekable();
+        let mut b = rhs.terms.into_iter().peekable();
+        loop {
+            let ordering = match (a.peek(), b.peek()) {
+                (Some((m, _)), Some((n, _))) => order.cmp(m, n),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => terms.push(a.next().unwrap()),
+                Ordering::Greater => {
+                    let (n, d) = b.next().unwrap();
+                    terms.push((n, only_rhs(d)));
+                }
+                Ordering::Equal => {
+                    let (m, mut c) = a.next().unwrap();
+                    let (_, d) = b.next().unwrap();
+                    if !combine(&mut c, d) {
+                        terms.push((m, c));
+                    }
+                }
+            }
+        }
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, O> Add for OrderedPolynomial<'_, R, V, K, P, O>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let base = self.elem_of.poly.base;
+        self.merge(rhs, |c, d| base.add_into(c, d), |d| d)
+    }
+}
+
+impl<R, V, K, P, O> Sub for OrderedPolynomial<'_, R, V, K, P, O>
+where
+    R: Ring<K>,
+    K: RingElement,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let base = self.elem_of.poly.base;
+        self.merge(rhs, |c, d| base.sub_from(c, d), |d| K::zero() - d)
+    }
+}
+
+/// Multiplies by the heap merge of Johnson, whose output is already sorted.
+impl<R, V, K, P, O> Mul for OrderedPolynomial<'_, R, V, K, P, O>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let f: Vec<_> = self.terms().collect();
+        let g: Vec<_> = rhs.terms().collect();
+        let mut terms = heap_product(self.elem_of.poly.base, &f, &g, &self.elem_of.order);
+        terms.reverse();
+        Self {
+            elem_of: self.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P, O> OrderedPolynomial<'_, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder + Clone,
+{
+    /// `self` divided by its leading coefficient, or zero if `self` is zero.
+    pub fn monic(&self) -> Self {
+        let Some(lc) = self.leading_coeff() else {
+            return self.clone();
+        };
+        let lc_inv = K::one() / lc.clone();
+        Self {
+            elem_of: self.elem_of,
+            terms: self
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), c.clone() * lc_inv.clone()))
+                .collect(),
+        }
+    }
+
+    /// The quotients `q_i` and the remainder `r` of the multivariate division
+    /// of `self` by `divisors`, as in [`Polynomial::div_rem`] in the order of
+    /// the ring.
+    ///
+    /// The leading terms of the divisors are read off directly, and the
+    /// terms of the quotients and the remainder are found in decreasing
+    /// order, so none of them is ever sorted.
+    pub fn div_rem(&self, divisors: &[Self]) -> (Vec<Self>, Self) {
+        let ring = self.elem_of;
+        let mut dividend = Geobucket::new(ring.poly, ring.order.clone());
+        dividend.add_terms(self.terms().map(|(m, c)| (m.clone(), c.clone())).collect());
+        let mut quotients: Vec<Vec<(Monomial<P>, K)>> = vec![Vec::new(); divisors.len()];
+        let mut remainder = Vec::new();
+        while let Some((m, c)) = dividend.pop_leading() {
+            let found = divisors.iter().enumerate().find_map(|(i, g)| {
+                g.leading_term()
+                    .filter(|(lm, _)| lm.divides(&m))
+                    .map(|lead| (i, lead, g))
+            });
+            let Some((i, (lm, lc), g)) = found else {
+                remainder.push((m, c));
+                continue;
+            };
+            let shift: Vec<P> = zip(&m.powers, &lm.powers).map(|(p, q)| *p - *q).collect();
+            let t = c / lc.clone();
+            let product = g
+                .terms()
+                .skip(1)
+                .map(|(n, d)| {
+                    let powers = zip(&shift, &n.powers).map(|(p, q)| *p + *q).collect();
+                    (Monomial { powers }, t.clone() * d.clone())
+                })
+                .filter(|(_, d)| !ring.poly.base.is_zero(d))
+                .collect();
+            dividend.sub_terms(product);
+            quotients[i].push((
+                Monomial {
+                    powers: shift.into_iter().collect(),
+                },
+                t,
+            ));
+        }
+        let sorted = |mut terms: Vec<(Monomial<P>, K)>| {
+            terms.reverse();
+            Self {
+                elem_of: ring,
+                terms,
+            }
+        };
+        (
+            quotients.into_iter().map(sorted).collect(),
+            sorted(remainder),
+        )
+    }
+
+    /// The remainder of the multivariate division of `self` by `divisors`,
+    /// as in [`OrderedPolynomial::div_rem`].
+    pub fn rem(&self, divisors: &[Self]) -> Self {
+        self.div_rem(divisors).1
+    }
+}
+
+/// Displays the terms in decreasing order.
+impl<R, V, K, P, O> Display for OrderedPolynomial<'_, R, V, K, P, O>
+where
+    K: Display + One + PartialEq,
+    P: Hash + Display + One + Zero + Eq,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (m, c)) in self.terms.iter().rev().enumerate() {
+            if !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            self.elem_of.poly.fmt_monomial(f, m)?;
+        }
+        Ok(())
+    }
+}
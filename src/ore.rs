@@ -0,0 +1,399 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::error::ChidogError;
+use crate::{Field, FieldElement, Ring, RingElement, Semiring, SemiringElement};
+
+/// A map on the coefficients, used for the twist and the derivation of an Ore
+/// polynomial ring.
+type CoeffMap<'a, K> = Box<dyn Fn(&K) -> K + 'a>;
+
+/// A variable `my_ring: OrePolynomialRing<R, V, K>` represents the Ore
+/// polynomial ring `R[X; σ, δ]` in the variable `var` over a base ring `R`.
+/// Its elements are of type `OrePolynomial<'_, R, V, K>`, with coefficients
+/// valued in `K`.
+///
+/// As an additive group this is the polynomial ring `R[X]`, but the variable
+/// does not commute with the coefficients: instead `X*a = σ(a)*X + δ(a)` for
+/// every coefficient `a`, where the twist `σ` is a ring endomorphism of `R`
+/// and the derivation `δ` is a `σ`-derivation, that is, additive with
+/// `δ(a*b) = σ(a)*δ(b) + δ(a)*b`. With `σ` the identity and `δ = d/dt` on a
+/// field of functions of `t`, this is the ring of linear differential
+/// operators; with `σ` the shift `t -> t+1` and `δ = 0`, it is the ring of
+/// linear recurrence operators.
+///
+/// Operators are written with coefficients on the left, as `a_n*X^n + ... +
+/// a_0`, and the operations that divide refer to that normal form: division
+/// is on the right, so `gcrd` and `lclm` are the greatest common right divisor
+/// and the least common left multiple. These require the twist to be
+/// injective, as it is for automorphisms.
+pub struct OrePolynomialRing<'a, R, V, K> {
+    pub var: V,
+    pub base: &'a R,
+    twist: CoeffMap<'a, K>,
+    derivation: CoeffMap<'a, K>,
+}
+
+/// An Ore polynomial `a_n*X^n + ... + a_0`, stored densely with coefficients
+/// on the left. The leading coefficient is always nonzero.
+pub struct OrePolynomial<'a, R, V, K> {
+    pub elem_of: &'a OrePolynomialRing<'a, R, V, K>,
+    coeffs: Vec<K>,
+}
+
+impl<R, V, K: Clone> Clone for OrePolynomial<'_, R, V, K> {
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            coeffs: self.coeffs.clone(),
+        }
+    }
+}
+
+impl<'a, R, V, K> OrePolynomialRing<'a, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + 'a,
+{
+    pub fn new(
+        var: V,
+        base: &'a R,
+        twist: impl Fn(&K) -> K + 'a,
+        derivation: impl Fn(&K) -> K + 'a,
+    ) -> Self {
+        Self {
+            var,
+            base,
+            twist: Box::new(twist),
+            derivation: Box::new(derivation),
+        }
+    }
+
+    /// The ring of differential operators, with trivial twist: `X*a = a*X +
+    /// δ(a)`.
+    pub fn differential(var: V, base: &'a R, derivation: impl Fn(&K) -> K + 'a) -> Self {
+        Self::new(var, base, K::clone, derivation)
+    }
+
+    /// The ring of difference operators, with zero derivation: `X*a =
+    /// σ(a)*X`.
+    pub fn difference(var: V, base: &'a R, shift: impl Fn(&K) -> K + 'a) -> Self {
+        Self::new(var, base, shift, |_| K::zero())
+    }
+
+    pub fn twist(&self, a: &K) -> K {
+        (self.twist)(a)
+    }
+
+    pub fn derivation(&self, a: &K) -> K {
+        (self.derivation)(a)
+    }
+
+    /// The operator with the given coefficients, in order of increasing
+    /// degree.
+    pub fn from_coeffs(&'a self, coeffs: Vec<K>) -> OrePolynomial<'a, R, V, K> {
+        let mut f = OrePolynomial {
+            elem_of: self,
+            coeffs,
+        };
+        f.normalize();
+        f
+    }
+
+    pub fn zero(&'a self) -> OrePolynomial<'a, R, V, K> {
+        self.from_coeffs(Vec::new())
+    }
+
+    pub fn one(&'a self) -> OrePolynomial<'a, R, V, K> {
+        self.constant(K::one())
+    }
+
+    pub fn constant(&'a self, c: K) -> OrePolynomial<'a, R, V, K> {
+        self.from_coeffs(vec![c])
+    }
+
+    /// The monomial `c*X^d`.
+    pub fn monomial(&'a self, c: K, d: usize) -> OrePolynomial<'a, R, V, K> {
+        let mut coeffs = vec![K::zero(); d];
+        coeffs.push(c);
+        self.from_coeffs(coeffs)
+    }
+
+    pub fn variable(&'a self) -> OrePolynomial<'a, R, V, K> {
+        self.monomial(K::one(), 1)
+    }
+}
+
+impl<'a, R, V, K> OrePolynomial<'a, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    /// Drops the zero coefficients of highest degree.
+    fn normalize(&mut self) {
+        while self
+            .coeffs
+            .last()
+            .is_some_and(|c| self.elem_of.base.is_zero(c))
+        {
+            self.coeffs.pop();
+        }
+    }
+
+    /// The coefficients of `self`, in order of increasing degree, with no
+    /// trailing zeros.
+    pub fn coeffs(&self) -> &[K] {
+        &self.coeffs
+    }
+
+    /// The coefficient of `X^i`.
+    pub fn coeff(&self, i: usize) -> K {
+        self.coeffs.get(i).cloned().unwrap_or_else(K::zero)
+    }
+
+    /// The degree of `self`, or `None` for zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn leading_coeff(&self) -> Option<&K> {
+        self.coeffs.last()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The product `c*self`.
+    pub fn scale_left(&self, c: &K) -> Self {
+        let coeffs = self.coeffs.iter().map(|a| c.clone() * a.clone()).collect();
+        self.elem_of.from_coeffs(coeffs)
+    }
+
+    /// The product `X*self`, by the commutation rule `X*a = σ(a)*X + δ(a)`.
+    fn mul_var_left(&self) -> Self {
+        let ring = self.elem_of;
+        let mut coeffs = vec![K::zero(); self.coeffs.len() + 1];
+        for (k, c) in self.coeffs.iter().enumerate() {
+            ring.base.add_into(&mut coeffs[k + 1], ring.twist(c));
+            ring.base.add_into(&mut coeffs[k], ring.derivation(c));
+        }
+        ring.from_coeffs(coeffs)
+    }
+}
+
+impl<'a, R, V, K> OrePolynomial<'a, R, V, K>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+{
+    /// The right Euclidean division of `self` by `rhs`: the operators `(q,
+    /// r)` with `self = q*rhs + r` and `deg(r) < deg(rhs)`.
+    pub fn right_div_rem(&self, rhs: &Self) -> Result<(Self, Self), ChidogError> {
+        let ring = self.elem_of;
+        let (Some(dg), Some(lc)) = (rhs.degree(), rhs.leading_coeff()) else {
+            return Err(ChidogError::DivisionByZero);
+        };
+        let mut q = ring.zero();
+        let mut r = self.clone();
+        // The twists `σ^k(lc)`, since the leading coefficient of `X^k*rhs` is
+        // `σ^k(lc)`.
+        let mut twisted = vec![lc.clone()];
+        while let Some(dr) = r.degree().filter(|dr| *dr >= dg) {
+            let k = dr - dg;
+            while twisted.len() <= k {
+                twisted.push(ring.twist(twisted.last().unwrap()));
+            }
+            if ring.base.is_zero(&twisted[k]) {
+                return Err(ChidogError::DivisionByZero);
+            }
+            let c = r.coeffs[dr].clone() / twisted[k].clone();
+            let t = ring.monomial(c, k);
+            r -= t.clone() * rhs.clone();
+            // The leading terms cancel exactly in theory; discard any
+            // rounding residue so that the loop terminates.
+            r.coeffs.truncate(dr);
+            r.normalize();
+            q += t;
+        }
+        Ok((q, r))
+    }
+
+    /// Whether `rhs` is a right divisor of `self`, that is, `self = q*rhs`
+    /// for some `q`.
+    pub fn is_right_divisible_by(&self, rhs: &Self) -> bool {
+        self.right_div_rem(rhs).is_ok_and(|(_, r)| r.is_zero())
+    }
+
+    /// `self` left-multiplied by the inverse of its leading coefficient, or
+    /// zero if `self` is zero.
+    pub fn monic(&self) -> Self {
+        match self.leading_coeff() {
+            Some(lc) => self.scale_left(&(K::one() / lc.clone())),
+            None => self.clone(),
+        }
+    }
+
+    /// The monic greatest common right divisor of `self` and `other`, which
+    /// is zero only if both are zero.
+    pub fn gcrd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.right_div_rem(&b).unwrap();
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// The monic least common left multiple of `self` and `other`, which is
+    /// zero if either is zero.
+    ///
+    /// This runs the extended right Euclidean algorithm, tracking cofactors
+    /// `u_i` with `u_i*self + v_i*other = r_i`; when the remainder vanishes,
+    /// `u_i*self = -v_i*other` is the least common left multiple.
+    pub fn lclm(&self, other: &Self) -> Self {
+        let ring = self.elem_of;
+        if self.is_zero() || other.is_zero() {
+            return ring.zero();
+        }
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut u0, mut u1) = (ring.one(), ring.zero());
+        while !r1.is_zero() {
+            let (q, r) = r0.right_div_rem(&r1).unwrap();
+            let u = u0 - q * u1.clone();
+            (r0, r1) = (r1, r);
+            (u0, u1) = (u1, u);
+        }
+        (u1 * self.clone()).monic()
+    }
+}
+
+impl<R, V, K> Add for OrePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<R, V, K> AddAssign for OrePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize(rhs.coeffs.len(), K::zero());
+        }
+        for (a, b) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            self.elem_of.base.add_into(a, b);
+        }
+        self.normalize();
+    }
+}
+
+impl<R, V, K> Sub for OrePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<R, V, K> SubAssign for OrePolynomial<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize(rhs.coeffs.len(), K::zero());
+        }
+        for (a, b) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            self.elem_of.base.sub_from(a, b);
+        }
+        self.normalize();
+    }
+}
+
+/// The product `(∑ a_i*X^i)*g = ∑ a_i*(X^i*g)`, where each `X^i*g` is
+/// obtained from the previous one by the commutation rule.
+impl<R, V, K> Mul for OrePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut product = self.elem_of.zero();
+        let mut shifted = rhs;
+        for (i, a) in self.coeffs.iter().enumerate() {
+            if i > 0 {
+                shifted = shifted.mul_var_left();
+            }
+            product += shifted.scale_left(a);
+        }
+        product
+    }
+}
+
+impl<R, V, K> MulAssign for OrePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<R, V, K> Display for OrePolynomial<'_, R, V, K>
+where
+    R: Semiring<K>,
+    V: Display,
+    K: SemiringElement + Clone + Display + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let var = &self.elem_of.var;
+        let mut first = true;
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            if self.elem_of.base.is_zero(c) {
+                continue;
+            }
+            if i == 0 || !c.is_one() {
+                if first {
+                    write!(f, "{c}")?;
+                } else {
+                    write!(f, "{c:+}")?;
+                }
+                if i > 0 {
+                    write!(f, "*")?;
+                }
+            } else if !first {
+                write!(f, "+")?;
+            }
+            match i {
+                0 => {}
+                1 => write!(f, "{var}")?,
+                _ => write!(f, "{var}^{i}")?,
+            }
+            first = false;
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
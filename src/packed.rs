@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+use num::{PrimInt, Unsigned};
+
+use crate::error::ChidogError;
+use crate::order::MonomialOrder;
+use crate::{Monomial, Polynomial, Semiring, SemiringElement};
+
+/// A monomial whose exponents are packed into the bit fields of a single
+/// word of type `W`, laid out by a [`Packing`]: `u64` holds one machine
+/// word, and `u128` two.
+///
+/// Equality and hashing look at the word alone, and multiplication,
+/// divisibility and the comparisons of the usual orders take a few word
+/// operations, where a [`Monomial`] walks a heap-allocated vector.
+///
+/// Packed monomials are not a storage representation of polynomials, which
+/// always hold [`Monomial`]s. They are the working form of multiplication
+/// kernels such as [`Polynomial::mul_packed`], which packs the terms of its
+/// factors, multiplies on words and unpacks the product.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PackedMonomial<W = u64> {
+    word: W,
+}
+
+/// The layout of packed monomials in some number of variables: each
+/// exponent takes a field of `bits` bits, with `x_0` in the most significant
+/// field, and a graded packing stores the total degree in a further field
+/// above them.
+///
+/// The top bit of every field is a guard bit, which is clear in a valid
+/// monomial, so that exponents are below `2^(bits - 1)`. Adding two valid
+/// words then never carries from one field into the next, and an exponent
+/// overflows exactly when the sum sets a guard bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packing<W = u64> {
+    vars: usize,
+    bits: u32,
+    graded: bool,
+    /// The fields of the exponents, without the degree.
+    exponents: W,
+    /// The guard bits of all fields.
+    guards: W,
+}
+
+impl<W: PrimInt + Unsigned> Packing<W> {
+    /// The packing of monomials in `vars` variables with fields of `bits`
+    /// bits, storing the total degree if `graded`, or an `InvalidInput`
+    /// error if the fields do not fit in a word or have fewer than 2 bits.
+    pub fn new(vars: usize, bits: u32, graded: bool) -> Result<Self, ChidogError> {
+        let width = W::zero().count_zeros();
+        let fields = vars + usize::from(graded);
+        if bits < 2 || fields * bits as usize > width as usize {
+            return Err(ChidogError::InvalidInput(format!(
+                "{fields} fields of {bits} bits do not fit in {width} bits"
+            )));
+        }
+        let field = W::max_value() >> (width - bits) as usize;
+        let guard = W::one() << (bits - 1) as usize;
+        let (mut exponents, mut guards) = (W::zero(), W::zero());
+        for i in 0..fields {
+            let shift = i * bits as usize;
+            if i < vars {
+                exponents = exponents | field << shift;
+            }
+            guards = guards | guard << shift;
+        }
+        Ok(Self {
+            vars,
+            bits,
+            graded,
+            exponents,
+            guards,
+        })
+    }
+
+    /// The packing of monomials in `vars` variables with the widest fields
+    /// that fit in a word, storing the total degree if `graded`.
+    pub fn widest(vars: usize, graded: bool) -> Result<Self, ChidogError> {
+        let fields = (vars + usize::from(graded)).max(1);
+        let width = W::zero().count_zeros() as usize;
+        Self::new(vars, (width / fields) as u32, graded)
+    }
+
+    pub fn vars(&self) -> usize {
+        self.vars
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Whether the total degree is stored.
+    pub fn is_graded(&self) -> bool {
+        self.graded
+    }
+
+    /// The largest exponent, and total degree if stored, that fits in a
+    /// field.
+    pub fn max_exponent(&self) -> W {
+        (W::one() << (self.bits - 1) as usize) - W::one()
+    }
+
+    /// The shift of the field of `x_i`.
+    fn shift(&self, i: usize) -> usize {
+        (self.vars - 1 - i) * self.bits as usize
+    }
+
+    /// The monomial `1`.
+    pub fn one(&self) -> PackedMonomial<W> {
+        PackedMonomial { word: W::zero() }
+    }
+
+    /// The packed form of `m`, or `None` if an exponent, or the total degree
+    /// in a graded packing, exceeds [`Packing::max_exponent`].
+    pub fn pack<P: PrimInt>(&self, m: &Monomial<P>) -> Option<PackedMonomial<W>> {
+        assert_eq!(m.powers.len(), self.vars, "wrong number of variables");
+        let max = self.max_exponent();
+        let mut word = W::zero();
+        let mut degree = W::zero();
+        for (i, p) in m.powers.iter().enumerate() {
+            let e = W::from(*p).filter(|e| *e <= max)?;
+            degree = degree.checked_add(&e)?;
+            word = word | e << self.shift(i);
+        }
+        if self.graded {
+            if degree > max {
+                return None;
+            }
+            word = word | degree << (self.vars * self.bits as usize);
+        }
+        Some(PackedMonomial { word })
+    }
+
+    /// The monomial packed in `m`.
+    pub fn unpack<P: PrimInt>(&self, m: &PackedMonomial<W>) -> Monomial<P> {
+        let powers = (0..self.vars)
+            .map(|i| P::from(self.exponent(m, i)).unwrap())
+            .collect();
+        Monomial { powers }
+    }
+
+    /// The exponent of `x_i` in `m`.
+    pub fn exponent(&self, m: &PackedMonomial<W>, i: usize) -> W {
+        (m.word >> self.shift(i)) & self.max_exponent()
+    }
+
+    /// The total degree of `m`, which is stored in a graded packing and
+    /// summed otherwise.
+    pub fn degree(&self, m: &PackedMonomial<W>) -> W {
+        if self.graded {
+            m.word >> (self.vars * self.bits as usize)
+        } else {
+            (0..self.vars).fold(W::zero(), |d, i| d + self.exponent(m, i))
+        }
+    }
+
+    /// The product `a*b`, or `None` if an exponent, or the total degree in a
+    /// graded packing, overflows its field.
+    pub fn checked_mul(
+        &self,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Option<PackedMonomial<W>> {
+        let word = a.word + b.word;
+        (word & self.guards)
+            .is_zero()
+            .then_some(PackedMonomial { word })
+    }
+
+    /// Whether `a` divides `b`.
+    ///
+    /// Subtracting `a` from `b` with all guard bits set borrows from the
+    /// guard bit of exactly the fields in which `a` is larger.
+    pub fn divides(&self, a: &PackedMonomial<W>, b: &PackedMonomial<W>) -> bool {
+        (((b.word | self.guards) - a.word) & self.guards) == self.guards
+    }
+
+    /// The quotient `a/b`, or `None` if `b` does not divide `a`.
+    pub fn checked_div(
+        &self,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Option<PackedMonomial<W>> {
+        self.divides(b, a).then(|| PackedMonomial {
+            word: a.word - b.word,
+        })
+    }
+
+    /// Compares `a` and `b` in `order`.
+    pub fn cmp<O: MonomialOrder>(
+        &self,
+        order: &O,
+        a: &PackedMonomial<W>,
+        b: &PackedMonomial<W>,
+    ) -> Ordering {
+        order.cmp_packed(self, a, b)
+    }
+
+    /// Compares `a` and `b` in the lexicographic order, in which the word
+    /// without the degree field orders monomials.
+    pub(crate) fn cmp_lex(&self, a: &PackedMonomial<W>, b: &PackedMonomial<W>) -> Ordering {
+        (a.word & self.exponents).cmp(&(b.word & self.exponents))
+    }
+
+    /// Compares `a` and `b` by total degree, then in the lexicographic
+    /// order; for a graded packing, this is the order of the words.
+    pub(crate) fn cmp_grlex(&self, a: &PackedMonomial<W>, b: &PackedMonomial<W>) -> Ordering {
+        if self.graded {
+            a.word.cmp(&b.word)
+        } else {
+            self.degree(a)
+                .cmp(&self.degree(b))
+                .then_with(|| self.cmp_lex(a, b))
+        }
+    }
+
+    /// Compares `a` and `b` by total degree, then by the exponent of the
+    /// last variable in which they differ, which is in the lowest field in
+    /// which the words differ.
+    pub(crate) fn cmp_grevlex(&self, a: &PackedMonomial<W>, b: &PackedMonomial<W>) -> Ordering {
+        self.degree(a).cmp(&self.degree(b)).then_with(|| {
+            let diff = (a.word ^ b.word) & self.exponents;
+            if diff.is_zero() {
+                return Ordering::Equal;
+            }
+            let i = self.vars - 1 - diff.trailing_zeros() as usize / self.bits as usize;
+            self.exponent(b, i).cmp(&self.exponent(a, i))
+        })
+    }
+}
+
+impl<R, V, K, P> Polynomial<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The product `self * rhs`, computed on monomials packed by `packing`,
+    /// or `None` if a monomial of `self`, `rhs` or the product does not fit.
+    ///
+    /// The terms are packed once, after which every product of two terms
+    /// adds two words and accumulates into a map keyed by a single word. The
+    /// product is unpacked into an ordinary polynomial, so the packing only
+    /// lives for the duration of the call.
+    pub fn mul_packed<W>(&self, rhs: &Self, packing: &Packing<W>) -> Option<Self>
+    where
+        W: Hash + PrimInt + Unsigned,
+    {
+        let pack = |f: &Self| -> Option<Vec<(PackedMonomial<W>, K)>> {
+            f.terms
+                .iter()
+                .map(|(m, c)| Some((packing.pack(m)?, c.clone())))
+                .collect()
+        };
+        let (f, g) = (pack(self)?, pack(rhs)?);
+        let base = self.elem_of.base;
+        let mut product: HashMap<PackedMonomial<W>, K> = HashMap::new();
+        for (m, c) in &f {
+            for (n, d) in &g {
+                let k = c.clone() * d.clone();
+                match product.entry(packing.checked_mul(m, n)?) {
+                    Entry::Occupied(mut entry) => {
+                        if base.add_into(entry.get_mut(), k) {
+                            entry.remove();
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        if !base.is_zero(&k) {
+                            entry.insert(k);
+                        }
+                    }
+                }
+            }
+        }
+        let terms = product
+            .into_iter()
+            .map(|(m, c)| (packing.unpack(&m), c))
+            .collect();
+        Some(Self {
+            elem_of: self.elem_of,
+            terms,
+        })
+    }
+}
@@ -0,0 +1,388 @@
+use std::cmp::min;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{BigInt, BigRational, Integer, One, Zero};
+
+use crate::error::ChidogError;
+use crate::{Field, FieldElement, Ring, RingElement, Semiring, SemiringElement};
+
+/// Valuation used to mark an exact zero, whose absolute precision is infinite.
+const EXACT: i64 = i64::MAX;
+
+/// A truncated p-adic number, that is, an element of ℚ_p known only up to
+/// some finite precision.
+///
+/// Elements are stored in capped-relative form: the value is `p^valuation *
+/// unit`, where `unit` is a p-adic unit known modulo `p^rel_prec`, and
+/// `rel_prec` never exceeds `CAP`. The absolute precision of an element is
+/// `valuation + rel_prec`, so that it is determined only up to adding a
+/// multiple of `p^(valuation + rel_prec)`.
+///
+/// An element with no known significant digits (`rel_prec == 0`) is
+/// indistinguishable from zero; for such an element, `valuation` holds the
+/// absolute precision to which it is known to vanish. The element produced by
+/// `Zero::zero` is the only exact element, having infinite absolute precision.
+///
+/// Arithmetic propagates precision, so that precision lost to cancellation is
+/// visible through [`PAdic::absolute_precision`] and
+/// [`PAdic::relative_precision`], and operations which need more digits than
+/// are known report [`ChidogError::PrecisionLoss`].
+///
+/// The parameter `P` must be prime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PAdic<const P: u64, const CAP: u32> {
+    valuation: i64,
+    unit: BigInt,
+    rel_prec: u32,
+}
+
+/// The ring ℤ_p of p-adic integers, whose elements are the values
+/// `x: PAdic<P, CAP>` with `x.is_integral()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PAdicIntegers<const P: u64, const CAP: u32>;
+
+/// The field ℚ_p of p-adic numbers, whose elements are values of type
+/// `PAdic<P, CAP>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PAdicNumbers<const P: u64, const CAP: u32>;
+
+impl<const P: u64, const CAP: u32> Semiring<PAdic<P, CAP>> for PAdicIntegers<P, CAP> {}
+impl<const P: u64, const CAP: u32> Ring<PAdic<P, CAP>> for PAdicIntegers<P, CAP> {}
+impl<const P: u64, const CAP: u32> Semiring<PAdic<P, CAP>> for PAdicNumbers<P, CAP> {}
+impl<const P: u64, const CAP: u32> Ring<PAdic<P, CAP>> for PAdicNumbers<P, CAP> {}
+impl<const P: u64, const CAP: u32> Field<PAdic<P, CAP>> for PAdicNumbers<P, CAP> {}
+impl<const P: u64, const CAP: u32> SemiringElement for PAdic<P, CAP> {}
+impl<const P: u64, const CAP: u32> RingElement for PAdic<P, CAP> {}
+impl<const P: u64, const CAP: u32> FieldElement for PAdic<P, CAP> {}
+
+impl<const P: u64, const CAP: u32> PAdic<P, CAP> {
+    fn prime_power(k: i64) -> BigInt {
+        num::pow(BigInt::from(P), k as usize)
+    }
+
+    /// Splits a nonzero integer into its p-adic valuation and the remaining
+    /// cofactor, which is prime to `p`.
+    fn split_valuation(mut n: BigInt) -> (i64, BigInt) {
+        let p = BigInt::from(P);
+        let mut v = 0;
+        loop {
+            let (q, r) = n.div_rem(&p);
+            if !r.is_zero() {
+                return (v, n);
+            }
+            n = q;
+            v += 1;
+        }
+    }
+
+    /// Builds the element `p^base * s + O(p^abs_prec)`, where `s` is any
+    /// integer, and `abs_prec == EXACT` requests as much precision as the
+    /// relative cap allows.
+    fn normalize(base: i64, mut s: BigInt, abs_prec: i64) -> Self {
+        if abs_prec != EXACT {
+            if abs_prec <= base {
+                return Self::zero_with_precision(abs_prec);
+            }
+            s = s.mod_floor(&Self::prime_power(abs_prec - base));
+        }
+        if s.is_zero() {
+            return Self::zero_with_precision(abs_prec);
+        }
+        let (k, unit) = Self::split_valuation(s);
+        let valuation = base + k;
+        let rel_prec = if abs_prec == EXACT {
+            CAP
+        } else {
+            min(abs_prec - valuation, CAP as i64) as u32
+        };
+        Self {
+            valuation,
+            unit: unit.mod_floor(&Self::prime_power(rel_prec as i64)),
+            rel_prec,
+        }
+    }
+
+    /// The element `n`, known to the full relative precision `CAP`.
+    pub fn from_integer(n: impl Into<BigInt>) -> Self {
+        Self::normalize(0, n.into(), EXACT)
+    }
+
+    /// The image of the rational number `q` in ℚ_p, known to the full
+    /// relative precision `CAP`.
+    pub fn from_rational(q: &BigRational) -> Self {
+        if q.is_zero() {
+            return Self::zero();
+        }
+        let (v_numer, numer) = Self::split_valuation(q.numer().clone());
+        let (v_denom, denom) = Self::split_valuation(q.denom().clone());
+        let modulus = Self::prime_power(CAP as i64);
+        let denom_inv = denom
+            .mod_floor(&modulus)
+            .modinv(&modulus)
+            .expect("cofactor prime to p is invertible");
+        Self::normalize(
+            v_numer - v_denom,
+            numer * denom_inv,
+            v_numer - v_denom + CAP as i64,
+        )
+    }
+
+    /// An element known only to be divisible by `p^abs_prec`.
+    pub fn zero_with_precision(abs_prec: i64) -> Self {
+        Self {
+            valuation: abs_prec,
+            unit: BigInt::zero(),
+            rel_prec: 0,
+        }
+    }
+
+    /// Reduces the absolute precision of `self` to at most `abs_prec`.
+    pub fn with_precision(&self, abs_prec: i64) -> Self {
+        let abs_prec = min(abs_prec, self.absolute_precision().unwrap_or(EXACT));
+        if self.rel_prec == 0 {
+            return Self::zero_with_precision(abs_prec);
+        }
+        Self::normalize(self.valuation, self.unit.clone(), abs_prec)
+    }
+
+    /// The p-adic valuation of `self`, or `None` for the exact zero. For an
+    /// element indistinguishable from zero, this is only a lower bound, equal
+    /// to its absolute precision.
+    pub fn valuation(&self) -> Option<i64> {
+        (self.valuation != EXACT).then_some(self.valuation)
+    }
+
+    /// The exponent `N` such that `self` is known modulo `p^N`, or `None` for
+    /// the exact zero.
+    pub fn absolute_precision(&self) -> Option<i64> {
+        (self.valuation != EXACT).then(|| self.valuation + self.rel_prec as i64)
+    }
+
+    /// The number of known significant p-adic digits of `self`.
+    pub fn relative_precision(&self) -> u32 {
+        self.rel_prec
+    }
+
+    /// The unit `u` with `self = p^v * u`, known modulo `p^rel_prec`.
+    pub fn unit_part(&self) -> &BigInt {
+        &self.unit
+    }
+
+    /// Whether `self` lies in ℤ_p, as far as can be told from known digits.
+    pub fn is_integral(&self) -> bool {
+        self.valuation >= 0
+    }
+
+    /// The residue of `self` modulo `p^n`, as an integer in `[0, p^n)`.
+    ///
+    /// Fails if `self` is not integral, or if it is not known to absolute
+    /// precision at least `n`.
+    pub fn residue(&self, n: i64) -> Result<BigInt, ChidogError> {
+        if !self.is_integral() {
+            return Err(ChidogError::NotIntegral);
+        }
+        if let Some(available) = self.absolute_precision()
+            && available < n
+        {
+            return Err(ChidogError::PrecisionLoss {
+                required: n,
+                available,
+            });
+        }
+        if self.valuation >= n {
+            return Ok(BigInt::zero());
+        }
+        Ok((&self.unit * Self::prime_power(self.valuation)).mod_floor(&Self::prime_power(n)))
+    }
+
+    /// The rational number `p^v * u` represented by the known digits of
+    /// `self`.
+    pub fn to_rational(&self) -> BigRational {
+        if self.rel_prec == 0 {
+            return BigRational::zero();
+        }
+        let power = Self::prime_power(self.valuation.abs());
+        if self.valuation >= 0 {
+            BigRational::from_integer(&self.unit * power)
+        } else {
+            BigRational::new(self.unit.clone(), power)
+        }
+    }
+
+    /// The multiplicative inverse of `self`, failing if `self` is
+    /// indistinguishable from zero.
+    pub fn checked_inv(&self) -> Result<Self, ChidogError> {
+        Self::one().checked_div(self)
+    }
+
+    /// The quotient `self / rhs`, failing if `rhs` is indistinguishable from
+    /// zero.
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, ChidogError> {
+        if rhs.rel_prec == 0 {
+            return Err(ChidogError::DivisionByZero);
+        }
+        if self.valuation == EXACT {
+            return Ok(Self::zero());
+        }
+        let valuation = self.valuation - rhs.valuation;
+        let rel_prec = min(self.rel_prec, rhs.rel_prec);
+        if rel_prec == 0 {
+            return Ok(Self::zero_with_precision(valuation));
+        }
+        let modulus = Self::prime_power(rel_prec as i64);
+        let rhs_inv = rhs
+            .unit
+            .mod_floor(&modulus)
+            .modinv(&modulus)
+            .expect("p-adic unit is invertible");
+        Ok(Self {
+            valuation,
+            unit: (&self.unit * rhs_inv).mod_floor(&modulus),
+            rel_prec,
+        })
+    }
+
+    /// The integer `unit * p^(valuation - base)`, or zero if that term
+    /// vanishes modulo `p^(abs_prec - base)`.
+    fn shifted_unit(&self, base: i64, abs_prec: i64) -> BigInt {
+        if self.rel_prec == 0 || self.valuation >= abs_prec {
+            BigInt::zero()
+        } else {
+            &self.unit * Self::prime_power(self.valuation - base)
+        }
+    }
+}
+
+impl<const P: u64, const CAP: u32> Add for PAdic<P, CAP> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.valuation == EXACT {
+            return rhs;
+        }
+        if rhs.valuation == EXACT {
+            return self;
+        }
+        let abs_prec = min(
+            self.absolute_precision().unwrap(),
+            rhs.absolute_precision().unwrap(),
+        );
+        let base = min(self.valuation, rhs.valuation);
+        let sum = self.shifted_unit(base, abs_prec) + rhs.shifted_unit(base, abs_prec);
+        Self::normalize(base, sum, abs_prec)
+    }
+}
+
+impl<const P: u64, const CAP: u32> Neg for PAdic<P, CAP> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.rel_prec == 0 {
+            return self;
+        }
+        let modulus = Self::prime_power(self.rel_prec as i64);
+        Self {
+            unit: (-self.unit).mod_floor(&modulus),
+            ..self
+        }
+    }
+}
+
+impl<const P: u64, const CAP: u32> Sub for PAdic<P, CAP> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u64, const CAP: u32> Mul for PAdic<P, CAP> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.valuation == EXACT || rhs.valuation == EXACT {
+            return Self::zero();
+        }
+        let valuation = self.valuation + rhs.valuation;
+        let rel_prec = min(self.rel_prec, rhs.rel_prec);
+        if rel_prec == 0 {
+            return Self::zero_with_precision(valuation);
+        }
+        Self {
+            valuation,
+            unit: (self.unit * rhs.unit).mod_floor(&Self::prime_power(rel_prec as i64)),
+            rel_prec,
+        }
+    }
+}
+
+impl<const P: u64, const CAP: u32> Div for PAdic<P, CAP> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(&rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<const P: u64, const CAP: u32> AddAssign for PAdic<P, CAP> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<const P: u64, const CAP: u32> SubAssign for PAdic<P, CAP> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+impl<const P: u64, const CAP: u32> MulAssign for PAdic<P, CAP> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) * rhs;
+    }
+}
+
+impl<const P: u64, const CAP: u32> Zero for PAdic<P, CAP> {
+    fn zero() -> Self {
+        Self::zero_with_precision(EXACT)
+    }
+
+    /// Whether `self` has no known significant digits.
+    fn is_zero(&self) -> bool {
+        self.rel_prec == 0
+    }
+}
+
+impl<const P: u64, const CAP: u32> One for PAdic<P, CAP> {
+    fn one() -> Self {
+        Self::from_integer(1)
+    }
+}
+
+impl<const P: u64, const CAP: u32> Display for PAdic<P, CAP> {
+    /// Writes `self` as its p-adic expansion, e.g. `3 + 2*5 + 4*5^2 + O(5^4)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.sign_plus() {
+            write!(f, "+")?;
+        }
+        let Some(abs_prec) = self.absolute_precision() else {
+            return write!(f, "0");
+        };
+        let p = BigInt::from(P);
+        let mut rest = self.unit.clone();
+        for e in self.valuation..abs_prec {
+            let (q, digit) = rest.div_rem(&p);
+            rest = q;
+            if digit.is_zero() {
+                continue;
+            }
+            match e {
+                0 => write!(f, "{digit} + ")?,
+                1 => write!(f, "{digit}*{P} + ")?,
+                _ => write!(f, "{digit}*{P}^{e} + ")?,
+            }
+        }
+        write!(f, "O({P}^{abs_prec})")
+    }
+}
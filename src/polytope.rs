@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, Integer, PrimInt, Signed, ToPrimitive, Unsigned, Zero};
+
+use crate::Polynomial;
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+
+/// A lattice polytope: the convex hull of finitely many points of `ℤ^n`.
+///
+/// The polytope keeps the points it was given, without duplicates, rather
+/// than only its vertices; [`Polytope::boundary_points`] drops the points in
+/// its interior.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polytope {
+    dim: usize,
+    points: Vec<Vec<i64>>,
+}
+
+/// A facet of a full-dimensional [`Polytope`], the points of which satisfy
+/// `normal·x = offset`, while the others satisfy `normal·x < offset`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolytopeFacet {
+    /// The primitive outer normal.
+    pub normal: Vec<i64>,
+    pub offset: i64,
+    /// The spanning points of the polytope on the facet, sorted.
+    pub points: Vec<Vec<i64>>,
+}
+
+/// A simplex of a triangulated boundary: `dim` points spanning a hyperplane
+/// `normal·x = offset`, with `normal·x < offset` on the side of the hull.
+struct Facet {
+    vertices: Vec<usize>,
+    normal: Vec<BigInt>,
+    offset: BigInt,
+}
+
+/// The convex hull of a full-dimensional polytope, built by the
+/// beneath-beyond method: its boundary is triangulated into simplices, and
+/// each point beyond some of them is joined to the ridges on the horizon
+/// between the facets it sees and those it does not. The simplices joining
+/// the point to the facets it sees triangulate the added region, so the
+/// volume is accumulated along the way.
+struct Hull<'p> {
+    points: &'p [Vec<i64>],
+    facets: Vec<Facet>,
+    /// The sum of the vertices of the first simplex, `dim + 1` times a point
+    /// inside the hull.
+    inside: Vec<BigInt>,
+    /// `dim!` times the volume.
+    volume: BigInt,
+}
+
+/// `v·w`.
+fn dot(v: &[BigInt], w: &[i64]) -> BigInt {
+    v.iter().zip(w).map(|(a, b)| a * b).sum()
+}
+
+impl<'p> Hull<'p> {
+    /// The hull of `points` in `dim > 0` dimensions, or `None` if they lie
+    /// in a hyperplane.
+    fn new(dim: usize, points: &'p [Vec<i64>]) -> Option<Self> {
+        if dim == 0 {
+            return None;
+        }
+        let mut simplex = vec![0];
+        let mut rows: Vec<Vec<BigInt>> = Vec::new();
+        for (i, p) in points.iter().enumerate().skip(1) {
+            if simplex.len() == dim + 1 {
+                break;
+            }
+            let row: Vec<BigInt> = p
+                .iter()
+                .zip(&points[0])
+                .map(|(a, b)| BigInt::from(a - b))
+                .collect();
+            rows.push(row);
+            if Matrix::from_rows(rows.clone()).unwrap().rank() == rows.len() {
+                simplex.push(i);
+            } else {
+                rows.pop();
+            }
+        }
+        if points.is_empty() || simplex.len() < dim + 1 {
+            return None;
+        }
+        let inside = (0..dim)
+            .map(|j| simplex.iter().map(|&i| BigInt::from(points[i][j])).sum())
+            .collect();
+        let mut hull = Self {
+            points,
+            facets: Vec::new(),
+            inside,
+            volume: Matrix::from_rows(rows).unwrap().det().abs(),
+        };
+        for skip in 0..=dim {
+            let vertices = simplex
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != skip)
+                .map(|(_, &i)| i)
+                .collect();
+            hull.push_facet(vertices);
+        }
+        for i in 0..points.len() {
+            if !simplex.contains(&i) {
+                hull.add(i);
+            }
+        }
+        Some(hull)
+    }
+
+    /// Adds the facet through the points `vertices`, oriented away from the
+    /// inside.
+    fn push_facet(&mut self, vertices: Vec<usize>) {
+        let dim = self.inside.len();
+        let base = &self.points[vertices[0]];
+        let rows: Vec<Vec<BigInt>> = vertices[1..]
+            .iter()
+            .map(|&i| {
+                self.points[i]
+                    .iter()
+                    .zip(base)
+                    .map(|(a, b)| BigInt::from(a - b))
+                    .collect()
+            })
+            .collect();
+        // The normal is the generalized cross product of the edges, whose
+        // entries are the signed maximal minors.
+        let edges = Matrix::from_rows(rows).unwrap();
+        let all: Vec<usize> = (0..dim - 1).collect();
+        let mut normal: Vec<BigInt> = (0..dim)
+            .map(|j| {
+                let cols: Vec<usize> = (0..dim).filter(|&c| c != j).collect();
+                let minor = match dim {
+                    1 => BigInt::from(1),
+                    _ => edges.submatrix(&all, &cols).det(),
+                };
+                if j % 2 == 0 { minor } else { -minor }
+            })
+            .collect();
+        let mut offset = dot(&normal, base);
+        let inside: BigInt = normal.iter().zip(&self.inside).map(|(a, b)| a * b).sum();
+        if inside > &offset * BigInt::from(dim + 1) {
+            normal.iter_mut().for_each(|a| *a = -&*a);
+            offset = -offset;
+        }
+        self.facets.push(Facet {
+            vertices,
+            normal,
+            offset,
+        });
+    }
+
+    /// Adds the point `p` to the hull, if it lies beyond some facet.
+    fn add(&mut self, p: usize) {
+        let point = &self.points[p];
+        let (visible, hidden): (Vec<Facet>, Vec<Facet>) = std::mem::take(&mut self.facets)
+            .into_iter()
+            .partition(|f| dot(&f.normal, point) > f.offset);
+        self.facets = hidden;
+        let mut ridges: HashMap<Vec<usize>, usize> = HashMap::new();
+        for facet in &visible {
+            self.volume += dot(&facet.normal, point) - &facet.offset;
+            for skip in 0..facet.vertices.len() {
+                let mut ridge: Vec<usize> = facet.vertices.clone();
+                ridge.remove(skip);
+                ridge.sort_unstable();
+                *ridges.entry(ridge).or_default() += 1;
+            }
+        }
+        for (mut ridge, count) in ridges {
+            if count == 1 {
+                ridge.push(p);
+                self.push_facet(ridge);
+            }
+        }
+    }
+}
+
+impl Polytope {
+    /// The convex hull of `points` in `ℤ^dim`, which must all have `dim`
+    /// coordinates.
+    pub fn new(dim: usize, points: Vec<Vec<i64>>) -> Result<Self, ChidogError> {
+        if let Some(p) = points.iter().find(|p| p.len() != dim) {
+            return Err(ChidogError::InvalidInput(format!(
+                "point with {} coordinates in a polytope of dimension {dim}",
+                p.len()
+            )));
+        }
+        let mut points = points;
+        points.sort_unstable();
+        points.dedup();
+        Ok(Self { dim, points })
+    }
+
+    /// The Newton polytope of `f`: the convex hull of the exponents of its
+    /// terms.
+    pub fn newton<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>) -> Self
+    where
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher,
+    {
+        let points = f
+            .terms
+            .keys()
+            .map(|m| {
+                m.powers
+                    .iter()
+                    .map(|p| p.to_i64().expect("exponent overflows i64"))
+                    .collect()
+            })
+            .collect();
+        Self::new(f.elem_of.vars.len(), points).unwrap()
+    }
+
+    /// The dimension of the ambient space.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// The points spanning the polytope, sorted and without duplicates.
+    pub fn points(&self) -> &[Vec<i64>] {
+        &self.points
+    }
+
+    /// Whether the polytope is empty, as is the Newton polytope of zero.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The polytope together with the origin, as used for counting roots
+    /// with zero coordinates.
+    pub fn with_origin(&self) -> Self {
+        let mut points = self.points.clone();
+        points.push(vec![0; self.dim]);
+        Self::new(self.dim, points).unwrap()
+    }
+
+    /// The spanning points on the boundary, which include all vertices. If
+    /// the polytope is not full-dimensional, all of them are kept.
+    pub fn boundary_points(&self) -> Vec<Vec<i64>> {
+        match Hull::new(self.dim, &self.points) {
+            None => self.points.clone(),
+            Some(hull) => {
+                let mut on_boundary = vec![false; self.points.len()];
+                for facet in &hull.facets {
+                    for &i in &facet.vertices {
+                        on_boundary[i] = true;
+                    }
+                }
+                for (i, p) in self.points.iter().enumerate() {
+                    if !on_boundary[i] && hull.facets.iter().any(|f| dot(&f.normal, p) == f.offset)
+                    {
+                        on_boundary[i] = true;
+                    }
+                }
+                self.points
+                    .iter()
+                    .zip(on_boundary)
+                    .filter(|(_, b)| *b)
+                    .map(|(p, _)| p.clone())
+                    .collect()
+            }
+        }
+    }
+
+    /// The facets, or none if the polytope is not full-dimensional.
+    pub fn facets(&self) -> Vec<PolytopeFacet> {
+        let Some(hull) = Hull::new(self.dim, &self.points) else {
+            return Vec::new();
+        };
+        let mut facets: Vec<PolytopeFacet> = Vec::new();
+        for facet in &hull.facets {
+            let g = facet.normal.iter().fold(BigInt::zero(), |g, a| g.gcd(a));
+            let to_i64 = |a: &BigInt| (a / &g).to_i64().expect("facet normal overflows i64");
+            let normal: Vec<i64> = facet.normal.iter().map(to_i64).collect();
+            if facets.iter().any(|f| f.normal == normal) {
+                continue;
+            }
+            let offset = to_i64(&facet.offset);
+            let points = self
+                .points
+                .iter()
+                .filter(|p| p.iter().zip(&normal).map(|(x, a)| x * a).sum::<i64>() == offset)
+                .cloned()
+                .collect();
+            facets.push(PolytopeFacet {
+                normal,
+                offset,
+                points,
+            });
+        }
+        facets
+    }
+
+    /// The Minkowski sum `self + other`, the polytope of the sums of their
+    /// points, spanned by the sums of their boundary points.
+    pub fn minkowski_sum(&self, other: &Self) -> Self {
+        assert_eq!(
+            self.dim, other.dim,
+            "Minkowski sum of polytopes of different dimensions"
+        );
+        let b = other.boundary_points();
+        let points = self
+            .boundary_points()
+            .iter()
+            .flat_map(|p| {
+                b.iter()
+                    .map(move |q| p.iter().zip(q).map(|(x, y)| x + y).collect())
+            })
+            .collect();
+        let sum = Self::new(self.dim, points).unwrap();
+        Self::new(self.dim, sum.boundary_points()).unwrap()
+    }
+
+    /// The normalized volume, `dim!` times the Euclidean volume, which is an
+    /// integer for lattice polytopes. It is zero unless the polytope is
+    /// full-dimensional.
+    pub fn normalized_volume(&self) -> BigInt {
+        match self.dim {
+            0 => BigInt::from(usize::from(!self.is_empty())),
+            _ => Hull::new(self.dim, &self.points).map_or_else(BigInt::zero, |h| h.volume),
+        }
+    }
+
+    /// The Euclidean volume.
+    pub fn volume(&self) -> BigRational {
+        let factorial: BigInt = (1..=self.dim).map(BigInt::from).product();
+        BigRational::new(self.normalized_volume(), factorial)
+    }
+}
+
+/// The mixed volume of the `n` polytopes `polytopes` in `ℤ^n`, normalized so
+/// that the mixed volume of `n` copies of `P` is `n!` times the volume of
+/// `P`.
+///
+/// It is computed by inclusion and exclusion over the `2^n - 1` Minkowski
+/// sums of nonempty subsets `S`, as the sum of `(-1)^(n - |S|)` times their
+/// volumes, so it is practical for small `n` only.
+pub fn mixed_volume(polytopes: &[Polytope]) -> Result<BigInt, ChidogError> {
+    let n = polytopes.len();
+    if let Some(p) = polytopes.iter().find(|p| p.dim != n || p.is_empty()) {
+        return Err(ChidogError::InvalidInput(format!(
+            "mixed volume of {n} polytopes needs nonempty polytopes of dimension {n}, not {}",
+            p.dim
+        )));
+    }
+    // The sums over the subsets of `polytopes[..k]` are extended one
+    // polytope at a time, so that each sum is formed once.
+    let mut sums: Vec<(Option<Polytope>, usize)> = vec![(None, 0)];
+    for p in polytopes {
+        let extended: Vec<_> = sums
+            .iter()
+            .map(|(sum, size)| {
+                let sum = match sum {
+                    None => p.clone(),
+                    Some(sum) => sum.minkowski_sum(p),
+                };
+                (Some(sum), size + 1)
+            })
+            .collect();
+        sums.extend(extended);
+    }
+    let mut total = BigInt::zero();
+    for (sum, size) in sums.into_iter().skip(1) {
+        let volume = sum.unwrap().normalized_volume();
+        match (n - size) % 2 {
+            0 => total += volume,
+            _ => total -= volume,
+        }
+    }
+    // The normalized volumes are `n!` times the volumes.
+    let factorial: BigInt = (1..=n).map(BigInt::from).product();
+    Ok(total / factorial)
+}
+
+/// The BKK bound of the square system `system`: the mixed volume of the
+/// Newton polytopes of its polynomials, which by Bernstein's theorem bounds
+/// the number of isolated roots with no zero coordinate, and equals it for
+/// generic coefficients.
+///
+/// Fails with an `InvalidInput` error unless there are as many polynomials
+/// as variables, all nonzero.
+pub fn bkk_bound<R, V, K, P, S>(
+    system: &[Polynomial<'_, R, V, K, P, S>],
+) -> Result<BigInt, ChidogError>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let polytopes: Vec<_> = system.iter().map(Polytope::newton).collect();
+    mixed_volume(&polytopes)
+}
+
+/// The bound of [`bkk_bound`] on the isolated roots of `system` in the
+/// whole affine space, including those with zero coordinates: the mixed
+/// volume of the Newton polytopes with the origin added, by the theorem of
+/// Li and Wang.
+pub fn affine_bkk_bound<R, V, K, P, S>(
+    system: &[Polynomial<'_, R, V, K, P, S>],
+) -> Result<BigInt, ChidogError>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let polytopes: Vec<_> = system
+        .iter()
+        .map(|f| Polytope::newton(f).with_origin())
+        .collect();
+    mixed_volume(&polytopes)
+}
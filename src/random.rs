@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hash};
+
+use num::{PrimInt, Unsigned};
+use rand::Rng;
+use rand::seq::index;
+use smallvec::smallvec;
+
+use crate::monomials::{count_monomials_up_to_degree, monomials_up_to_degree};
+use crate::{Monomial, Polynomial, PolynomialRing, Powers, Semiring, SemiringElement};
+
+/// A monomial in `n` variables of total degree at most `d`, uniformly
+/// distributed among all of them.
+///
+/// The monomials correspond to the ways of placing `n` bars among `d + n`
+/// slots: the exponent of `x_i` is the number of empty slots between bars `i`
+/// and `i + 1`.
+fn random_monomial<P: PrimInt, G: Rng + ?Sized>(n: usize, d: usize, rng: &mut G) -> Monomial<P> {
+    let mut bars = index::sample(rng, d + n, n).into_vec();
+    bars.sort_unstable();
+    let mut previous = 0;
+    let powers = bars
+        .into_iter()
+        .map(|bar| {
+            let p = P::from(bar - previous).unwrap();
+            previous = bar + 1;
+            p
+        })
+        .collect();
+    Monomial { powers }
+}
+
+impl<'a, R, V> PolynomialRing<'a, R, V> {
+    /// A random polynomial of total degree at most `degree` with at most
+    /// `num_terms` terms, whose distinct monomials are drawn uniformly from
+    /// those of total degree at most `degree`, with coefficients drawn by
+    /// `coeff`.
+    ///
+    /// There are fewer terms if there are fewer than `num_terms` such
+    /// monomials, or if `coeff` draws zeros. The same seed of `rng` always
+    /// gives the same polynomial.
+    pub fn random<K, P, S, G>(
+        &'a self,
+        degree: usize,
+        num_terms: usize,
+        mut coeff: impl FnMut(&mut G) -> K,
+        rng: &mut G,
+    ) -> Polynomial<'a, R, V, K, P, S>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher + Default,
+        G: Rng + ?Sized,
+    {
+        let n = self.vars.len();
+        let num_terms = num_terms.min(count_monomials_up_to_degree(n, degree));
+        let mut monomials = HashSet::with_capacity(num_terms);
+        let mut terms = Vec::with_capacity(num_terms);
+        while terms.len() < num_terms {
+            let m: Monomial<P> = random_monomial(n, degree, rng);
+            if monomials.insert(m.clone()) {
+                terms.push((m, coeff(rng)));
+            }
+        }
+        Polynomial::from_terms(self, terms).unwrap()
+    }
+
+    /// A random dense polynomial of total degree at most `degree`, with
+    /// every monomial of total degree at most `degree` and coefficients drawn
+    /// by `coeff`.
+    pub fn random_dense<K, P, S, G>(
+        &'a self,
+        degree: usize,
+        mut coeff: impl FnMut(&mut G) -> K,
+        rng: &mut G,
+    ) -> Polynomial<'a, R, V, K, P, S>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher + Default,
+        G: Rng + ?Sized,
+    {
+        let terms: Vec<_> = monomials_up_to_degree(self.vars.len(), degree)
+            .map(|m| (m, coeff(rng)))
+            .collect();
+        Polynomial::from_terms(self, terms).unwrap()
+    }
+
+    /// `count` random polynomials as drawn by [`PolynomialRing::random`].
+    pub fn random_system<K, P, S, G>(
+        &'a self,
+        count: usize,
+        degree: usize,
+        num_terms: usize,
+        mut coeff: impl FnMut(&mut G) -> K,
+        rng: &mut G,
+    ) -> Vec<Polynomial<'a, R, V, K, P, S>>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher + Default,
+        G: Rng + ?Sized,
+    {
+        (0..count)
+            .map(|_| self.random(degree, num_terms, &mut coeff, rng))
+            .collect()
+    }
+
+    /// `count` random dense polynomials as drawn by
+    /// [`PolynomialRing::random_dense`].
+    pub fn random_dense_system<K, P, S, G>(
+        &'a self,
+        count: usize,
+        degree: usize,
+        mut coeff: impl FnMut(&mut G) -> K,
+        rng: &mut G,
+    ) -> Vec<Polynomial<'a, R, V, K, P, S>>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher + Default,
+        G: Rng + ?Sized,
+    {
+        (0..count)
+            .map(|_| self.random_dense(degree, &mut coeff, rng))
+            .collect()
+    }
+
+    /// The generators of a random zero-dimensional ideal: for each variable
+    /// `x_i`, the polynomial `x_i^degree + g_i`, where `g_i` is drawn by
+    /// [`PolynomialRing::random`] with total degree less than `degree`, for
+    /// `degree > 0`.
+    ///
+    /// Under any degree-compatible order the leading monomials of the
+    /// generators are the pure powers `x_i^degree`, so the ideal has
+    /// finitely many zeros, at most `degree^n` of them counted with
+    /// multiplicity. Such ideals are typical inputs of Gröbner basis and
+    /// system solving benchmarks.
+    pub fn random_zero_dimensional_ideal<K, P, S, G>(
+        &'a self,
+        degree: usize,
+        num_terms: usize,
+        mut coeff: impl FnMut(&mut G) -> K,
+        rng: &mut G,
+    ) -> Vec<Polynomial<'a, R, V, K, P, S>>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+        S: BuildHasher + Default,
+        G: Rng + ?Sized,
+    {
+        assert!(degree > 0, "zero-dimensional ideal of degree 0");
+        let n = self.vars.len();
+        (0..n)
+            .map(|i| {
+                let g: Polynomial<'a, R, V, K, P, S> =
+                    self.random(degree - 1, num_terms, &mut coeff, rng);
+                let mut powers: Powers<P> = smallvec![P::zero(); n];
+                powers[i] = P::from(degree).unwrap();
+                let leading = (Monomial { powers }, K::one());
+                Polynomial::from_terms(self, g.into_terms().chain([leading])).unwrap()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+use num::{One, PrimInt, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::{
+    Field, FieldElement, Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring,
+    SemiringElement,
+};
+
+/// A variable `my_field: FractionField<R, V>` represents the field of
+/// rational functions in the variables of the polynomial ring `poly`, whose
+/// base ring must be a field. Its elements are of type `RationalFunction<'_,
+/// R, V, K, P>`.
+///
+/// `my_field` implements `Field<RationalFunction<'_, R, V, K, P>>`, so it can
+/// itself serve as the base ring of a `PolynomialRing`, giving polynomials
+/// with rational-function coefficients.
+pub struct FractionField<'a, R, V> {
+    pub poly: &'a PolynomialRing<'a, R, V>,
+}
+
+/// A rational function `numer / denom`.
+///
+/// We maintain the guarantee that the fraction is reduced, with
+/// `gcd(numer, denom) = 1` and `denom` monic in the lexicographic order, so
+/// that each rational function has a unique representation.
+///
+/// Since `Zero::zero` and `One::one` are called without access to a field,
+/// constants in `K` may also be stored detached from any field. A detached
+/// constant is attached to the field of the other operand when combined with
+/// a rational function.
+pub struct RationalFunction<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    repr: Repr<'a, R, V, K, P>,
+}
+
+enum Repr<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    Constant(K),
+    Fraction {
+        elem_of: &'a FractionField<'a, R, V>,
+        numer: Polynomial<'a, R, V, K, P>,
+        denom: Polynomial<'a, R, V, K, P>,
+    },
+}
+
+/// A numerator and denominator.
+type Parts<'a, R, V, K, P> = (Polynomial<'a, R, V, K, P>, Polynomial<'a, R, V, K, P>);
+
+impl<R, V, K, P> Clone for RationalFunction<'_, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Constant(c) => Repr::Constant(c.clone()),
+            Repr::Fraction {
+                elem_of,
+                numer,
+                denom,
+            } => Repr::Fraction {
+                elem_of,
+                numer: numer.clone(),
+                denom: denom.clone(),
+            },
+        };
+        Self { repr }
+    }
+}
+
+impl<'a, R, V> FractionField<'a, R, V> {
+    pub fn new(poly: &'a PolynomialRing<'a, R, V>) -> Self {
+        Self { poly }
+    }
+
+    /// The constant polynomial `c`.
+    fn constant<K, P>(&self, c: K) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut terms = HashMap::default();
+        if !self.poly.base.is_zero(&c) {
+            let powers = smallvec![P::zero(); self.poly.vars.len()];
+            terms.insert(Monomial { powers }, c);
+        }
+        Polynomial {
+            elem_of: self.poly,
+            terms,
+        }
+    }
+
+    /// The rational function `numer / denom`, which must have a nonzero
+    /// denominator.
+    pub fn fraction<K, P>(
+        &'a self,
+        numer: Polynomial<'a, R, V, K, P>,
+        denom: Polynomial<'a, R, V, K, P>,
+    ) -> Result<RationalFunction<'a, R, V, K, P>, ChidogError>
+    where
+        R: Field<K>,
+        K: FieldElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        if denom.terms.is_empty() {
+            return Err(ChidogError::DivisionByZero);
+        }
+        Ok(RationalFunction::reduced(self, numer, denom))
+    }
+
+    pub fn from_polynomial<K, P>(
+        &'a self,
+        f: Polynomial<'a, R, V, K, P>,
+    ) -> RationalFunction<'a, R, V, K, P>
+    where
+        R: Field<K>,
+        K: FieldElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+        V: Eq,
+    {
+        let denom = self.constant(K::one());
+        RationalFunction {
+            repr: Repr::Fraction {
+                elem_of: self,
+                numer: f,
+                denom,
+            },
+        }
+    }
+
+    /// The numerator of `f` in lowest terms.
+    pub fn numer<K, P>(&self, f: &RationalFunction<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Field<K>,
+        K: FieldElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        match &f.repr {
+            Repr::Constant(c) => self.constant(c.clone()),
+            Repr::Fraction { numer, .. } => numer.clone(),
+        }
+    }
+
+    /// The monic denominator of `f` in lowest terms.
+    pub fn denom<K, P>(&self, f: &RationalFunction<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Field<K>,
+        K: FieldElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        match &f.repr {
+            Repr::Constant(_) => self.constant(K::one()),
+            Repr::Fraction { denom, .. } => denom.clone(),
+        }
+    }
+}
+
+impl<'a, R, V, K, P> RationalFunction<'a, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    /// Reduces `numer / denom` to lowest terms. The denominator must be
+    /// nonzero.
+    fn reduced(
+        elem_of: &'a FractionField<'a, R, V>,
+        numer: Polynomial<'a, R, V, K, P>,
+        denom: Polynomial<'a, R, V, K, P>,
+    ) -> Self {
+        let (numer, denom) = if numer.terms.is_empty() {
+            (numer, elem_of.constant(K::one()))
+        } else {
+            let g = numer.gcd(&denom);
+            let numer = numer.div_exact(&g).unwrap();
+            let denom = denom.div_exact(&g).unwrap();
+            let monic = denom.monic();
+            let scale = denom.div_exact(&monic).unwrap();
+            (numer.div_exact(&scale).unwrap(), monic)
+        };
+        Self {
+            repr: Repr::Fraction {
+                elem_of,
+                numer,
+                denom,
+            },
+        }
+    }
+
+    /// The field of `self` and `other`, unless both are detached constants.
+    fn field_of(&self, other: &Self) -> Option<&'a FractionField<'a, R, V>> {
+        match (&self.repr, &other.repr) {
+            (Repr::Fraction { elem_of, .. }, _) | (_, Repr::Fraction { elem_of, .. }) => {
+                Some(elem_of)
+            }
+            _ => None,
+        }
+    }
+
+    /// The numerator and denominator of `self` in `field`.
+    fn into_parts(self, field: &'a FractionField<'a, R, V>) -> Parts<'a, R, V, K, P> {
+        match self.repr {
+            Repr::Constant(c) => (field.constant(c), field.constant(K::one())),
+            Repr::Fraction { numer, denom, .. } => (numer, denom),
+        }
+    }
+
+    /// The value of `self`, if it is a constant.
+    pub fn as_constant(&self) -> Option<K> {
+        match &self.repr {
+            Repr::Constant(c) => Some(c.clone()),
+            Repr::Fraction { numer, denom, .. } => {
+                let is_const = |f: &Polynomial<'a, R, V, K, P>| {
+                    f.terms.keys().all(|m| m.powers.iter().all(|p| p.is_zero()))
+                };
+                if !is_const(numer) || !is_const(denom) {
+                    return None;
+                }
+                Some(numer.terms.values().next().cloned().unwrap_or_else(K::zero))
+            }
+        }
+    }
+
+    /// The multiplicative inverse of `self`, which must be nonzero.
+    pub fn inv(&self) -> Result<Self, ChidogError> {
+        if self.is_zero() {
+            return Err(ChidogError::DivisionByZero);
+        }
+        Ok(match &self.repr {
+            Repr::Constant(c) => Self {
+                repr: Repr::Constant(K::one() / c.clone()),
+            },
+            Repr::Fraction {
+                elem_of,
+                numer,
+                denom,
+            } => Self::reduced(elem_of, denom.clone(), numer.clone()),
+        })
+    }
+
+    /// The quotient `self / rhs`, where `rhs` must be nonzero.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, ChidogError> {
+        Ok(self * rhs.inv()?)
+    }
+}
+
+impl<R, V, K, P> Add for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let Some(field) = self.field_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a + b),
+            };
+        };
+        let (n1, d1) = self.into_parts(field);
+        let (n2, d2) = rhs.into_parts(field);
+        Self::reduced(field, n1 * d2.clone() + n2 * d1.clone(), d1 * d2)
+    }
+}
+
+impl<R, V, K, P> Sub for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let Some(field) = self.field_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a - b),
+            };
+        };
+        let (n1, d1) = self.into_parts(field);
+        let (n2, d2) = rhs.into_parts(field);
+        Self::reduced(field, n1 * d2.clone() - n2 * d1.clone(), d1 * d2)
+    }
+}
+
+impl<R, V, K, P> Mul for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Some(field) = self.field_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a * b),
+            };
+        };
+        let (n1, d1) = self.into_parts(field);
+        let (n2, d2) = rhs.into_parts(field);
+        Self::reduced(field, n1 * n2, d1 * d2)
+    }
+}
+
+/// Panics if `rhs` is zero; see [`RationalFunction::checked_div`].
+impl<R, V, K, P> Div for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("division by zero")
+    }
+}
+
+impl<R, V, K, P> AddAssign for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<R, V, K, P> SubAssign for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+impl<R, V, K, P> MulAssign for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) * rhs;
+    }
+}
+
+impl<R, V, K, P> Zero for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn zero() -> Self {
+        Self {
+            repr: Repr::Constant(K::zero()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match &self.repr {
+            Repr::Constant(c) => c.is_zero(),
+            Repr::Fraction { numer, .. } => numer.terms.is_empty(),
+        }
+    }
+}
+
+impl<R, V, K, P> One for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn one() -> Self {
+        Self {
+            repr: Repr::Constant(K::one()),
+        }
+    }
+}
+
+impl<R, V, K, P> PartialEq for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone + PartialEq,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.repr, &other.repr) {
+            (
+                Repr::Fraction {
+                    numer: n1,
+                    denom: d1,
+                    ..
+                },
+                Repr::Fraction {
+                    numer: n2,
+                    denom: d2,
+                    ..
+                },
+            ) => n1.terms == n2.terms && d1.terms == d2.terms,
+            _ => match (self.as_constant(), other.as_constant()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl<R, V, K, P> Semiring<RationalFunction<'_, R, V, K, P>> for FractionField<'_, R, V>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+impl<R, V, K, P> Ring<RationalFunction<'_, R, V, K, P>> for FractionField<'_, R, V>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+impl<R, V, K, P> Field<RationalFunction<'_, R, V, K, P>> for FractionField<'_, R, V>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+impl<R, V, K, P> SemiringElement for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+impl<R, V, K, P> RingElement for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+impl<R, V, K, P> FieldElement for RationalFunction<'_, R, V, K, P>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+{
+}
+
+/// Displays `numer/denom`, parenthesizing numerator and denominator when they
+/// have more than one term, or only `numer` when `denom` is 1.
+impl<R, V, K, P> Display for RationalFunction<'_, R, V, K, P>
+where
+    K: Display + One + PartialEq,
+    P: Hash + PrimInt + Unsigned + Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (numer, denom) = match &self.repr {
+            Repr::Constant(c) => return Display::fmt(c, f),
+            Repr::Fraction { numer, denom, .. } => (numer, denom),
+        };
+        let denom_is_one = denom.terms.len() == 1
+            && denom
+                .terms
+                .iter()
+                .all(|(m, c)| c.is_one() && m.powers.iter().all(|p| p.is_zero()));
+        let parenthesize = |g: &Polynomial<'_, R, V, K, P>| {
+            if g.terms.len() > 1 && !denom_is_one {
+                format!("({g})")
+            } else {
+                g.to_string()
+            }
+        };
+        let numer = parenthesize(numer);
+        if f.sign_plus() && !numer.starts_with('-') {
+            write!(f, "+")?;
+        }
+        write!(f, "{numer}")?;
+        if !denom_is_one {
+            write!(f, "/{}", parenthesize(denom))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use num::One;
+
+use crate::FieldElement;
+use crate::matrix::Matrix;
+use crate::univariate::{DenseUnivariate, DivExact};
+
+/// A linear recurrence `s_n + c_1*s_(n-1) + ... + c_L*s_(n-L) = 0` for `n >=
+/// L`, given by its connection polynomial `1 + c_1*x + ... + c_L*x^L` and its
+/// length `L`.
+///
+/// The degree of the connection polynomial may be less than `L`, when the
+/// first terms of a sequence do not follow the recurrence of the later ones,
+/// as for `1, 0, 0, ...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearRecurrence<K> {
+    pub connection: DenseUnivariate<K>,
+    pub length: usize,
+}
+
+impl<K: FieldElement + Clone> LinearRecurrence<K> {
+    /// The minimal polynomial `x^L + c_1*x^(L-1) + ... + c_L` of the
+    /// recurrence, the reversal of the connection polynomial at length `L`.
+    pub fn minimal_polynomial(&self) -> DenseUnivariate<K> {
+        let mut coeffs = vec![K::zero(); self.length + 1];
+        for (i, c) in self.connection.coeffs().iter().enumerate() {
+            coeffs[self.length - i] = c.clone();
+        }
+        DenseUnivariate::new(coeffs)
+    }
+
+    /// The `count` terms of the sequence following `seq`, whose last `L`
+    /// terms determine them.
+    pub fn next_terms(&self, seq: &[K], count: usize) -> Vec<K> {
+        assert!(seq.len() >= self.length, "too few initial terms");
+        let mut s = seq.to_vec();
+        for _ in 0..count {
+            let n = s.len();
+            let mut t = K::zero();
+            for (i, c) in self.connection.coeffs().iter().enumerate().skip(1) {
+                t -= c.clone() * s[n - i].clone();
+            }
+            s.push(t);
+        }
+        s.split_off(seq.len())
+    }
+}
+
+/// The shortest linear recurrence satisfied by `seq`, by the algorithm of
+/// Berlekamp and Massey.
+///
+/// If `seq` satisfies a recurrence of length `L` and has at least `2L`
+/// terms, this is the unique minimal recurrence of the whole infinite
+/// sequence. Fewer terms only give a recurrence consistent with `seq`.
+pub fn berlekamp_massey<K: FieldElement + Clone>(seq: &[K]) -> LinearRecurrence<K> {
+    // The current connection polynomial `c` of length `l`, and the one `b`
+    // from before the last change of length, when its discrepancy was `d_b`,
+    // `shift` terms ago.
+    let mut c = vec![K::one()];
+    let mut b = vec![K::one()];
+    let mut l = 0;
+    let mut shift = 1;
+    let mut d_b = K::one();
+    for n in 0..seq.len() {
+        let mut d = seq[n].clone();
+        for i in 1..=l.min(c.len() - 1) {
+            d += c[i].clone() * seq[n - i].clone();
+        }
+        if d.is_zero() {
+            shift += 1;
+            continue;
+        }
+        let f = d.clone() / d_b.clone();
+        let previous = c.clone();
+        if c.len() < b.len() + shift {
+            c.resize(b.len() + shift, K::zero());
+        }
+        for (i, bi) in b.iter().enumerate() {
+            c[i + shift] -= f.clone() * bi.clone();
+        }
+        if 2 * l <= n {
+            l = n + 1 - l;
+            b = previous;
+            d_b = d;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    LinearRecurrence {
+        connection: DenseUnivariate::new(c),
+        length: l,
+    }
+}
+
+/// The least common multiple of two monic polynomials.
+fn lcm<K: FieldElement + Clone>(
+    a: &DenseUnivariate<K>,
+    b: &DenseUnivariate<K>,
+) -> DenseUnivariate<K> {
+    (a.clone() * b.clone()).div_exact(&a.gcd(b)).unwrap()
+}
+
+/// The minimal polynomial of a sequence of vectors, the monic polynomial
+/// `x^L + c_1*x^(L-1) + ... + c_L` of least degree such that
+/// `v_n + c_1*v_(n-1) + ... + c_L*v_(n-L) = 0` for all `n >= L`.
+///
+/// It is the least common multiple of the minimal polynomials of the
+/// coordinate sequences, each given by [`berlekamp_massey`]; as there, the
+/// result is that of the infinite sequence once `seq` has at least `2L` terms.
+pub fn vector_minimal_polynomial<K: FieldElement + Clone>(seq: &[Vec<K>]) -> DenseUnivariate<K> {
+    let dim = seq.first().map_or(0, Vec::len);
+    let mut p = DenseUnivariate::one();
+    for j in 0..dim {
+        let coordinate: Vec<K> = seq.iter().map(|v| v[j].clone()).collect();
+        p = lcm(&p, &berlekamp_massey(&coordinate).minimal_polynomial());
+    }
+    p
+}
+
+impl<K: FieldElement + Clone> Matrix<K> {
+    /// The minimal polynomial of the projected sequence `u^T*self^i*v`, by
+    /// Berlekamp–Massey on its first `2n` terms for an `n×n` matrix.
+    ///
+    /// It divides the minimal polynomial of `self`, and equals it for generic
+    /// `u` and `v`. This is the scalar sequence of Wiedemann's algorithm and of
+    /// the sparse variant of FGLM, which only needs products of `self` with
+    /// vectors.
+    pub fn projected_minimal_polynomial(&self, u: &[K], v: &[K]) -> DenseUnivariate<K> {
+        assert!(
+            self.is_square(),
+            "minimal polynomial of a non-square matrix"
+        );
+        let n = self.nrows();
+        let mut w = v.to_vec();
+        let mut seq = Vec::with_capacity(2 * n);
+        for _ in 0..2 * n {
+            let mut s = K::zero();
+            for (a, b) in u.iter().zip(&w) {
+                s += a.clone() * b.clone();
+            }
+            seq.push(s);
+            w = self.mul_vector(&w);
+        }
+        berlekamp_massey(&seq).minimal_polynomial()
+    }
+
+    /// The minimal polynomial of `self`, the monic polynomial `p` of least
+    /// degree with `p(self) = 0`, as the minimal polynomial of the Krylov
+    /// sequences `self^i*e_j` of the unit vectors.
+    pub fn minimal_polynomial(&self) -> DenseUnivariate<K> {
+        assert!(
+            self.is_square(),
+            "minimal polynomial of a non-square matrix"
+        );
+        let n = self.nrows();
+        let mut p = DenseUnivariate::one();
+        for j in 0..n {
+            let mut w: Vec<K> = (0..n)
+                .map(|i| if i == j { K::one() } else { K::zero() })
+                .collect();
+            let mut seq = Vec::with_capacity(2 * n);
+            for _ in 0..2 * n {
+                let next = self.mul_vector(&w);
+                seq.push(std::mem::replace(&mut w, next));
+            }
+            p = lcm(&p, &vector_minimal_polynomial(&seq));
+        }
+        p
+    }
+
+    /// The product of `self` with the column vector `v`.
+    fn mul_vector(&self, v: &[K]) -> Vec<K> {
+        (0..self.nrows())
+            .map(|i| {
+                let mut s = K::zero();
+                for (a, b) in self.row(i).iter().zip(v) {
+                    s += a.clone() * b.clone();
+                }
+                s
+            })
+            .collect()
+    }
+}
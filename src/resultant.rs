@@ -0,0 +1,1236 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::{BigInt, BigRational, FromPrimitive, PrimInt, Signed, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::monomials::monomials_of_degree;
+use crate::univariate::{DenseUnivariate, DivExact};
+use crate::{Field, FieldElement, Monomial, Polynomial, Powers, RingElement, Semiring};
+
+/// A homogeneous form, as its terms and its degree.
+type Form<T, P> = (Vec<(Monomial<P>, T)>, usize);
+
+/// The Macaulay matrix of `n + 1` homogeneous forms `f_0, ..., f_n` in
+/// `n + 1` variables, as built by [`macaulay_matrix`].
+///
+/// For `D = ∑ (d_i - 1) + 1`, its rows and columns are indexed by the
+/// monomials `x^α` of degree `D`: the row of `x^α` holds the coefficients of
+/// `x^α/x_i^d_i*f_i`, for the first `i` with `x_i^d_i` dividing `x^α`. Its
+/// determinant is the resultant of the forms times the extraneous minor,
+/// the one on the monomials divisible by `x_i^d_i` for at least two `i`,
+/// which does not involve the coefficients of `f_n`.
+#[derive(Clone, Debug)]
+pub struct MacaulayMatrix<T, P> {
+    matrix: Matrix<T>,
+    monomials: Vec<Monomial<P>>,
+    forms: Vec<usize>,
+    extraneous: Vec<usize>,
+}
+
+impl<T: RingElement + Clone, P> MacaulayMatrix<T, P> {
+    /// The matrix.
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    /// The monomials of degree `D` indexing both the rows and the columns,
+    /// in decreasing lexicographic order.
+    pub fn monomials(&self) -> &[Monomial<P>] {
+        &self.monomials
+    }
+
+    /// The index `i` of the form `f_i` whose multiple makes up each row.
+    pub fn forms(&self) -> &[usize] {
+        &self.forms
+    }
+
+    /// The extraneous minor, the submatrix on the rows and columns of the
+    /// monomials divisible by `x_i^d_i` for at least two `i`.
+    pub fn extraneous_minor(&self) -> Matrix<T> {
+        self.matrix.submatrix(&self.extraneous, &self.extraneous)
+    }
+}
+
+impl<T: DivExact + Clone, P> MacaulayMatrix<T, P> {
+    /// The resultant of the forms, the quotient of the determinant by the
+    /// extraneous minor.
+    ///
+    /// If the extraneous minor vanishes, as can happen for special
+    /// coefficients, the resultant is instead the value at `s = 0` of the
+    /// generalized characteristic polynomial of Canny, the resultant of the
+    /// forms `f_i + s*x_i^d_i`, which is the quotient of `det(M + s*I)` by
+    /// the same for the extraneous minor: each row gains `s` on the
+    /// diagonal, in the column of its own monomial.
+    pub fn resultant(&self) -> T {
+        self.resultant_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The resultant of [`MacaulayMatrix::resultant`], consulting `control`
+    /// during each determinant or characteristic polynomial, and failing as
+    /// it directs.
+    pub fn resultant_controlled(&self, control: &ComputationControl) -> Result<T, ChidogError> {
+        let minor = self.extraneous_minor();
+        let minor_det = minor.det_controlled(control)?;
+        if !minor_det.is_zero() {
+            return Ok(self
+                .matrix
+                .det_controlled(control)?
+                .div_exact(&minor_det)
+                .expect("the extraneous minor divides the determinant"));
+        }
+        let shifted = |m: &Matrix<T>| {
+            m.map(|a| T::zero() - a.clone())
+                .char_poly_dense_controlled(control)
+        };
+        let (numerator, denominator) = (shifted(&self.matrix)?, shifted(&minor)?);
+        // The lowest coefficients of `numerator = C(s)*denominator` are
+        // zero up to that of the first nonzero one of `denominator`, where
+        // it is `C(0)` times that.
+        let k = denominator
+            .coeffs()
+            .iter()
+            .position(|c| !c.is_zero())
+            .expect("characteristic polynomials are monic");
+        Ok(numerator
+            .coeff(k)
+            .div_exact(&denominator.coeffs()[k])
+            .expect("the generalized characteristic polynomial is a polynomial"))
+    }
+}
+
+/// The total degree of `m`.
+fn degree<P: PrimInt>(m: &Monomial<P>) -> usize {
+    m.powers.iter().map(|p| p.to_usize().unwrap()).sum()
+}
+
+/// The Macaulay matrix of the nonconstant homogeneous `forms` in `vars`
+/// variables, of which there must be `vars`.
+fn macaulay<T, P>(forms: Vec<Form<T, P>>, vars: usize) -> Result<MacaulayMatrix<T, P>, ChidogError>
+where
+    T: RingElement + Clone,
+    P: Hash + PrimInt,
+{
+    if forms.len() != vars || vars == 0 {
+        return Err(ChidogError::InvalidInput(format!(
+            "a Macaulay matrix needs as many forms as variables, not {} forms in {vars}",
+            forms.len()
+        )));
+    }
+    if forms.iter().any(|(_, d)| *d == 0) {
+        return Err(ChidogError::InvalidInput(
+            "the forms of a Macaulay matrix must not be constant".to_string(),
+        ));
+    }
+    let top = forms.iter().map(|(_, d)| d - 1).sum::<usize>() + 1;
+    P::from(top).ok_or(ChidogError::ExponentOverflow)?;
+    let monomials: Vec<Monomial<P>> = monomials_of_degree(vars, top).collect();
+    let position: HashMap<&Monomial<P>, usize> = monomials.iter().zip(0..).collect();
+    let n = monomials.len();
+    let mut matrix = Matrix::zero(n, n);
+    let mut row_forms = Vec::with_capacity(n);
+    let mut extraneous = Vec::new();
+    for (r, m) in monomials.iter().enumerate() {
+        let mut reduced = (0..vars).filter(|&i| m.powers[i].to_usize().unwrap() >= forms[i].1);
+        let i = reduced
+            .next()
+            .expect("some power x_i^d_i divides each monomial");
+        if reduced.next().is_some() {
+            extraneous.push(r);
+        }
+        let mut shift = m.clone();
+        shift.powers[i] = shift.powers[i] - P::from(forms[i].1).unwrap();
+        for (t, c) in &forms[i].0 {
+            let column = position[&shift.checked_mul(t)?];
+            matrix[(r, column)] = c.clone();
+        }
+        row_forms.push(i);
+    }
+    Ok(MacaulayMatrix {
+        matrix,
+        monomials,
+        forms: row_forms,
+        extraneous,
+    })
+}
+
+/// The Macaulay matrix of the nonconstant homogeneous polynomials `forms`,
+/// of which there must be as many as variables, or an `InvalidInput` error
+/// if they are not.
+pub fn macaulay_matrix<R, V, K, P>(
+    forms: &[Polynomial<'_, R, V, K, P>],
+) -> Result<MacaulayMatrix<K, P>, ChidogError>
+where
+    R: Semiring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let vars = forms.first().map_or(0, |f| f.elem_of.vars.len());
+    let forms = forms
+        .iter()
+        .map(|f| {
+            let d = f.terms.keys().map(degree).max().unwrap_or(0);
+            if f.terms.keys().any(|m| degree(m) != d) {
+                return Err(ChidogError::InvalidInput(
+                    "the forms of a Macaulay matrix must be homogeneous".to_string(),
+                ));
+            }
+            let terms = f
+                .terms
+                .iter()
+                .map(|(m, c)| (m.clone(), c.clone()))
+                .collect();
+            Ok((terms, d))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    macaulay(forms, vars)
+}
+
+/// The Macaulay resultant of the nonconstant homogeneous polynomials
+/// `forms` over a field, of which there must be as many as variables, which
+/// vanishes exactly when they have a common nontrivial zero over an
+/// algebraic closure.
+///
+/// This is an `InvalidInput` error if the forms are not as required.
+pub fn macaulay_resultant<R, V, K, P>(
+    forms: &[Polynomial<'_, R, V, K, P>],
+) -> Result<K, ChidogError>
+where
+    R: Semiring<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    macaulay_resultant_controlled(forms, &ComputationControl::new())
+}
+
+/// The Macaulay resultant of [`macaulay_resultant`], consulting `control`
+/// as [`MacaulayMatrix::resultant_controlled`] does.
+pub fn macaulay_resultant_controlled<R, V, K, P>(
+    forms: &[Polynomial<'_, R, V, K, P>],
+    control: &ComputationControl,
+) -> Result<K, ChidogError>
+where
+    R: Semiring<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    macaulay_matrix(forms)?.resultant_controlled(control)
+}
+
+/// The forms in the variables other than `hidden`, followed by a
+/// homogenizing variable, with coefficients the polynomials in `hidden`, of
+/// the polynomials of `system`, with their degrees in those variables.
+fn hide<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: Option<usize>,
+) -> Vec<Form<DenseUnivariate<K>, P>>
+where
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut forms = Vec::with_capacity(system.len());
+    for f in system {
+        let visible = |m: &Monomial<P>| {
+            let mut powers: Powers<P> = m
+                .powers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| Some(*i) != hidden)
+                .map(|(_, p)| *p)
+                .collect();
+            powers.push(P::zero());
+            Monomial { powers }
+        };
+        let d = f
+            .terms
+            .keys()
+            .map(|m| degree(&visible(m)))
+            .max()
+            .unwrap_or(0);
+        let mut terms: HashMap<Monomial<P>, DenseUnivariate<K>> = HashMap::new();
+        for (m, c) in &f.terms {
+            let mut key = visible(m);
+            let last = key.powers.len() - 1;
+            key.powers[last] = P::from(d - degree(&key)).unwrap();
+            let power = hidden.map_or(0, |h| m.powers[h].to_usize().unwrap());
+            let coefficient = terms.entry(key).or_default();
+            *coefficient += DenseUnivariate::monomial(c.clone(), power);
+        }
+        forms.push((terms.into_iter().collect(), d));
+    }
+    forms
+}
+
+/// The hidden-variable resultant of the `n` polynomials `system` in `n`
+/// variables, eliminating all variables but the one with index `hidden`: the
+/// Macaulay resultant of the system, homogenized in the other variables,
+/// with coefficients polynomials in the hidden one.
+///
+/// It vanishes at the value of the hidden variable at each common zero, and
+/// also where the homogenized system has a common zero at infinity. This is
+/// an `InvalidInput` error if the number of polynomials is wrong or if one
+/// involves only the hidden variable.
+pub fn hidden_variable_resultant<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    hidden_variable_resultant_controlled(system, hidden, &ComputationControl::new())
+}
+
+/// The hidden-variable resultant of [`hidden_variable_resultant`],
+/// consulting `control` as [`MacaulayMatrix::resultant_controlled`] does.
+pub fn hidden_variable_resultant_controlled<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+    control: &ComputationControl,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let vars = system.first().map_or(0, |f| f.elem_of.vars.len());
+    if hidden >= vars {
+        return Err(ChidogError::InvalidInput(format!(
+            "no variable with index {hidden} to hide"
+        )));
+    }
+    macaulay(hide(system, Some(hidden)), vars)?.resultant_controlled(control)
+}
+
+/// The Macaulay matrix of the homogenized `system` followed by the linear
+/// form `t*x_n - ∑ c_j*x_j - s*x_i`, where `s` is `t^e` for `(i, e)` in
+/// `perturbation`, with the number of its rows from that form.
+fn u_matrix<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    c: &[K],
+    perturbation: Option<(usize, usize)>,
+) -> Result<(MacaulayMatrix<DenseUnivariate<K>, P>, usize), ChidogError>
+where
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = c.len();
+    let mut forms = hide(system, None);
+    let unit = |i: usize| {
+        let mut powers = smallvec![P::zero(); n + 1];
+        powers[i] = P::one();
+        Monomial { powers }
+    };
+    let mut linear = vec![(unit(n), DenseUnivariate::x())];
+    for (j, c) in c.iter().enumerate() {
+        let mut u = DenseUnivariate::constant(K::zero() - c.clone());
+        if let Some((i, e)) = perturbation
+            && i == j
+        {
+            u -= DenseUnivariate::monomial(K::one(), e);
+        }
+        linear.push((unit(j), u));
+    }
+    forms.push((linear, 1));
+    let matrix = macaulay(forms, n + 1)?;
+    let rows = matrix.forms.iter().filter(|&&i| i == n).count();
+    Ok((matrix, rows))
+}
+
+/// The u-resultant of the `n` polynomials `system` in `n` variables with
+/// `u_0 = t` and `u_j = -c_j`: the Macaulay resultant of the homogenized
+/// system and the linear form `u_0*x_n + ∑ u_j*x_j`, as a polynomial in `t`.
+///
+/// It is, up to a constant factor, the product of `t - ∑ c_j*ξ_j` over the
+/// common zeros `ξ` counted with multiplicity, so for `c` separating the
+/// zeros its roots are the values of that linear form at them. Zeros of the
+/// homogenized system at infinity only contribute constant factors, which
+/// vanish if the linear form does at one. This is an `InvalidInput` error if
+/// `c` does not have one entry for each variable, or if the number of
+/// polynomials is wrong or one of them is constant.
+pub fn u_resultant<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    c: &[K],
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    check_u_system(system, c)?;
+    Ok(u_matrix(system, c, None)?.0.resultant())
+}
+
+/// An `InvalidInput` error unless `c` has one entry for each of the
+/// variables of `system`.
+fn check_u_system<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    c: &[K],
+) -> Result<(), ChidogError>
+where
+    P: Hash,
+{
+    if system.iter().any(|f| f.elem_of.vars.len() != c.len()) {
+        return Err(ChidogError::InvalidInput(
+            "the linear form needs one coefficient for each variable".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The common zeros of the `n` polynomials `system` in `n` variables read
+/// off their u-resultant, as a squarefree monic polynomial `r` and, for
+/// each variable, a polynomial `q_i` of smaller degree: the zeros are the
+/// points `(q_0(θ), ..., q_{n-1}(θ))` for the roots `θ` of `r`, which are
+/// the values of `∑ c_j*x_j` at them.
+///
+/// Writing `R(t, s)` for the u-resultant with `u_0 = t` and
+/// `u = -(c + s*e_i)`, each zero `ξ` has `ξ_i = -∂_s R(θ, 0)/∂_t R(θ, 0)`.
+/// Both derivatives come from a single determinant, with `s = t^e` for `e`
+/// larger than the degree in `t`. This is an `InvalidInput` error under the
+/// conditions of [`u_resultant`], and if the u-resultant vanishes or has a
+/// repeated root, as happens when `c` does not separate the zeros or some
+/// zero is not simple.
+pub fn u_resultant_zeros<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    c: &[K],
+) -> Result<(DenseUnivariate<K>, Vec<DenseUnivariate<K>>), ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    check_u_system(system, c)?;
+    let (_, rows) = u_matrix(system, c, None)?;
+    let e = rows + 1;
+    let mut eliminant = None;
+    let mut derivatives = Vec::with_capacity(c.len());
+    for i in 0..c.len() {
+        let resultant = u_matrix(system, c, Some((i, e)))?.0.resultant();
+        let coeffs = resultant.coeffs();
+        let part =
+            |k: usize| DenseUnivariate::new(coeffs.iter().skip(k * e).take(e).cloned().collect());
+        eliminant.get_or_insert_with(|| part(0));
+        derivatives.push(part(1));
+    }
+    let eliminant = eliminant.unwrap_or_else(|| DenseUnivariate::x());
+    let (g, inverse, _) = eliminant.derivative().extended_gcd(&eliminant);
+    if eliminant.degree().is_none() || g.degree() != Some(0) {
+        return Err(ChidogError::InvalidInput(
+            "the u-resultant vanishes or has a repeated root".to_string(),
+        ));
+    }
+    let coordinates = derivatives
+        .into_iter()
+        .map(|d| (-(d * inverse.clone())).div_rem(&eliminant).unwrap().1)
+        .collect();
+    Ok((eliminant.monic(), coordinates))
+}
+
+/// A sparse polynomial in the variables `x_1, ..., x_n, α_1, ..., α_n` of a
+/// Dixon polynomial, as a map from exponents to coefficients.
+type Sparse<T> = HashMap<Vec<usize>, T>;
+
+/// The product `a*b`.
+fn sparse_mul<T: RingElement + Clone>(a: &Sparse<T>, b: &Sparse<T>) -> Sparse<T> {
+    let mut product: Sparse<T> = HashMap::new();
+    for (m, c) in a {
+        for (n, d) in b {
+            let e: Vec<usize> = m.iter().zip(n).map(|(i, j)| i + j).collect();
+            *product.entry(e).or_insert_with(T::zero) += c.clone() * d.clone();
+        }
+    }
+    product.retain(|_, c| !c.is_zero());
+    product
+}
+
+/// The determinant of the square matrix of sparse polynomials in `vars`
+/// variables on the rows from `row` down and the columns `columns`, by
+/// expansion along the first of those rows, consulting `control` before
+/// each minor is expanded.
+fn sparse_det<T: RingElement + Clone>(
+    rows: &[Vec<Sparse<T>>],
+    vars: usize,
+    row: usize,
+    columns: &[usize],
+    control: &ComputationControl,
+) -> Result<Sparse<T>, ChidogError> {
+    let Some(first) = rows.get(row) else {
+        return Ok(HashMap::from([(vec![0; vars], T::one())]));
+    };
+    let mut det: Sparse<T> = HashMap::new();
+    for (k, &j) in columns.iter().enumerate() {
+        if first[j].is_empty() {
+            continue;
+        }
+        control.check(&Progress {
+            algorithm: "dixon",
+            steps: row,
+            remaining: Some(rows.len() - row),
+            terms: det.len(),
+        })?;
+        let rest: Vec<usize> = columns.iter().copied().filter(|&i| i != j).collect();
+        let minor = sparse_det(rows, vars, row + 1, &rest, control)?;
+        for (m, c) in sparse_mul(&first[j], &minor) {
+            let entry = det.entry(m).or_insert_with(T::zero);
+            if k % 2 == 0 {
+                *entry += c;
+            } else {
+                *entry -= c;
+            }
+        }
+    }
+    det.retain(|_, c| !c.is_zero());
+    Ok(det)
+}
+
+/// The Dixon matrix of `n + 1` polynomials `f_0, ..., f_n` in `n`
+/// variables, as built by [`dixon_matrix`].
+///
+/// The Dixon polynomial of the system is the determinant of the matrix with
+/// rows `f_j(α_1, ..., α_k, x_(k+1), ..., x_n)` for `k = 0, ..., n`, divided
+/// by `∏ (x_k - α_k)`; it vanishes at every common zero `x` of the system,
+/// whatever the new variables `α`. Its coefficients, with rows indexed by
+/// the monomials in `α` and columns by those in `x`, form the Dixon matrix,
+/// which annihilates the vector of monomials in `x` at each common zero.
+///
+/// The Dixon polynomial is the multivariate Bezoutian of Cayley: for one
+/// variable it is `(f_0(x)*f_1(α) - f_0(α)*f_1(x))/(x - α)` up to sign, and
+/// the Dixon matrix the Bezout matrix of
+/// [`DenseUnivariate::bezout_matrix`].
+#[derive(Clone, Debug)]
+pub struct DixonMatrix<T, P> {
+    matrix: Matrix<T>,
+    rows: Vec<Monomial<P>>,
+    columns: Vec<Monomial<P>>,
+}
+
+impl<T, P> DixonMatrix<T, P> {
+    /// The matrix.
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    /// The monomials in `α` indexing the rows, by increasing exponents.
+    pub fn row_monomials(&self) -> &[Monomial<P>] {
+        &self.rows
+    }
+
+    /// The monomials in `x` indexing the columns, by increasing exponents.
+    pub fn column_monomials(&self) -> &[Monomial<P>] {
+        &self.columns
+    }
+}
+
+impl<T: DivExact + Clone, P> DixonMatrix<T, P> {
+    /// The projection operator of the system, the determinant of a maximal
+    /// nonsingular submatrix of the Dixon matrix, or zero if the matrix is.
+    ///
+    /// This is the determinant itself when the matrix is square and
+    /// nonsingular, and in any case it is up to sign the last pivot of
+    /// [`Matrix::fraction_free_echelon`]. It is meant for coefficients
+    /// involving parameters, such as polynomials in a hidden variable: by
+    /// the rank submatrix method of Kapur, Saxena and Yang it is then a
+    /// multiple of the resultant, which vanishes for the values of the
+    /// parameters where the system has a common zero, provided some column
+    /// is not a combination of the others. For numeric coefficients the rank
+    /// is that of the specialized matrix, so a common zero shows as a drop
+    /// in rank rather than as a vanishing operator.
+    pub fn projection_operator(&self) -> T {
+        self.projection_operator_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The projection operator of [`DixonMatrix::projection_operator`],
+    /// consulting `control` during the elimination, and failing as it
+    /// directs.
+    pub fn projection_operator_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<T, ChidogError> {
+        let (echelon, pivots) = self.matrix.fraction_free_echelon_controlled(control)?;
+        Ok(match pivots.last() {
+            Some(&c) => echelon[(pivots.len() - 1, c)].clone(),
+            None => T::zero(),
+        })
+    }
+}
+
+/// The Dixon matrix of the `n + 1` polynomials with terms `system`, given by
+/// their exponents, in `n` variables, consulting `control` during the
+/// expansion of the Dixon determinant.
+fn dixon<T, P>(
+    system: &[Vec<(Vec<usize>, T)>],
+    n: usize,
+    control: &ComputationControl,
+) -> Result<DixonMatrix<T, P>, ChidogError>
+where
+    T: RingElement + Clone,
+    P: PrimInt,
+{
+    if system.len() != n + 1 {
+        return Err(ChidogError::InvalidInput(format!(
+            "a Dixon matrix needs one more polynomial than variables, not {} in {n}",
+            system.len()
+        )));
+    }
+    // The first row is the system itself, and the row of `k` the divided
+    // difference in `x_k` of the rows `k - 1` and `k` of the Dixon
+    // determinant, which leaves the determinant unchanged up to sign: a term
+    // `c*x_k^e` of `f_j(α_1, ..., α_(k-1), x_k, ...)` gives
+    // `c*∑ x_k^a*α_k^b` over `a + b = e - 1`.
+    let mut rows: Vec<Vec<Sparse<T>>> = Vec::with_capacity(n + 1);
+    rows.push(
+        system
+            .iter()
+            .map(|f| {
+                f.iter()
+                    .map(|(m, c)| {
+                        let mut e = m.clone();
+                        e.resize(2 * n, 0);
+                        (e, c.clone())
+                    })
+                    .collect()
+            })
+            .collect(),
+    );
+    for k in 0..n {
+        let row = system
+            .iter()
+            .map(|f| {
+                let mut difference: Sparse<T> = HashMap::new();
+                for (m, c) in f {
+                    for a in 0..m[k] {
+                        let mut e = vec![0; 2 * n];
+                        for (i, &p) in m.iter().enumerate() {
+                            match i.cmp(&k) {
+                                std::cmp::Ordering::Less => e[n + i] = p,
+                                std::cmp::Ordering::Equal => {
+                                    e[i] = a;
+                                    e[n + i] = p - 1 - a;
+                                }
+                                std::cmp::Ordering::Greater => e[i] = p,
+                            }
+                        }
+                        *difference.entry(e).or_insert_with(T::zero) += c.clone();
+                    }
+                }
+                difference.retain(|_, c| !c.is_zero());
+                difference
+            })
+            .collect();
+        rows.push(row);
+    }
+    let columns: Vec<usize> = (0..=n).collect();
+    let polynomial = sparse_det(&rows, 2 * n, 0, &columns, control)?;
+    let monomial = |e: &[usize]| -> Result<Monomial<P>, ChidogError> {
+        let powers = e
+            .iter()
+            .map(|&p| P::from(p).ok_or(ChidogError::ExponentOverflow))
+            .collect::<Result<_, _>>()?;
+        Ok(Monomial { powers })
+    };
+    let mut row_exponents: Vec<&[usize]> = polynomial.keys().map(|e| &e[n..]).collect();
+    let mut column_exponents: Vec<&[usize]> = polynomial.keys().map(|e| &e[..n]).collect();
+    for exponents in [&mut row_exponents, &mut column_exponents] {
+        exponents.sort_unstable();
+        exponents.dedup();
+    }
+    let row_of: HashMap<&[usize], usize> = row_exponents.iter().copied().zip(0..).collect();
+    let column_of: HashMap<&[usize], usize> = column_exponents.iter().copied().zip(0..).collect();
+    let mut matrix = Matrix::zero(row_exponents.len(), column_exponents.len());
+    for (e, c) in &polynomial {
+        matrix[(row_of[&e[n..]], column_of[&e[..n]])] = c.clone();
+    }
+    Ok(DixonMatrix {
+        matrix,
+        rows: row_exponents
+            .iter()
+            .map(|e| monomial(e))
+            .collect::<Result<_, _>>()?,
+        columns: column_exponents
+            .iter()
+            .map(|e| monomial(e))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// The Dixon matrix of the `n + 1` polynomials `system` in `n` variables,
+/// or an `InvalidInput` error if their number is wrong.
+pub fn dixon_matrix<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+) -> Result<DixonMatrix<K, P>, ChidogError>
+where
+    R: Semiring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    dixon_matrix_controlled(system, &ComputationControl::new())
+}
+
+/// The Dixon matrix of [`dixon_matrix`], consulting `control` before each
+/// minor in the expansion of the Dixon determinant, and failing as it
+/// directs.
+fn dixon_matrix_controlled<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    control: &ComputationControl,
+) -> Result<DixonMatrix<K, P>, ChidogError>
+where
+    R: Semiring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = system.first().map_or(0, |f| f.elem_of.vars.len());
+    let system: Vec<Vec<_>> = system
+        .iter()
+        .map(|f| {
+            f.terms
+                .iter()
+                .map(|(m, c)| {
+                    (
+                        m.powers.iter().map(|p| p.to_usize().unwrap()).collect(),
+                        c.clone(),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    dixon(&system, n, control)
+}
+
+/// The Dixon resultant of the `n + 1` polynomials `system` in `n`
+/// variables, the [`DixonMatrix::projection_operator`] of their Dixon
+/// matrix, or an `InvalidInput` error if their number is wrong.
+///
+/// The coefficients are typically polynomials in the parameters of the
+/// system, say over [`DenseUnivariateRing`], and the result the condition
+/// on the parameters for a common zero.
+///
+/// [`DenseUnivariateRing`]: crate::univariate::DenseUnivariateRing
+pub fn dixon_resultant<R, V, K, P>(system: &[Polynomial<'_, R, V, K, P>]) -> Result<K, ChidogError>
+where
+    R: Semiring<K>,
+    K: DivExact + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    dixon_resultant_controlled(system, &ComputationControl::new())
+}
+
+/// The Dixon resultant of [`dixon_resultant`], consulting `control` during
+/// the expansion of the Dixon determinant and the elimination, and failing
+/// as it directs.
+pub fn dixon_resultant_controlled<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    control: &ComputationControl,
+) -> Result<K, ChidogError>
+where
+    R: Semiring<K>,
+    K: DivExact + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    dixon_matrix_controlled(system, control)?.projection_operator_controlled(control)
+}
+
+/// The hidden-variable resultant of the `n` polynomials `system` in `n`
+/// variables by the Dixon method, eliminating all variables but the one
+/// with index `hidden`: the projection operator of the Dixon matrix of the
+/// system in the other variables, with coefficients polynomials in the
+/// hidden one.
+///
+/// It vanishes at the value of the hidden variable at each common zero, and
+/// is usually much smaller to compute than [`hidden_variable_resultant`].
+/// This is an `InvalidInput` error if the number of polynomials is wrong.
+pub fn hidden_variable_dixon_resultant<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    hidden_variable_dixon_resultant_controlled(system, hidden, &ComputationControl::new())
+}
+
+/// The hidden-variable resultant of [`hidden_variable_dixon_resultant`],
+/// consulting `control` as [`dixon_resultant_controlled`] does.
+pub fn hidden_variable_dixon_resultant_controlled<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+    control: &ComputationControl,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let vars = system.first().map_or(0, |f| f.elem_of.vars.len());
+    if hidden >= vars {
+        return Err(ChidogError::InvalidInput(format!(
+            "no variable with index {hidden} to hide"
+        )));
+    }
+    let system: Vec<Vec<_>> = hide(system, Some(hidden))
+        .into_iter()
+        .map(|(terms, _)| {
+            terms
+                .into_iter()
+                .map(|(m, c)| {
+                    let mut e: Vec<usize> =
+                        m.powers.iter().map(|p| p.to_usize().unwrap()).collect();
+                    e.pop();
+                    (e, c)
+                })
+                .collect()
+        })
+        .collect();
+    dixon::<_, P>(&system, vars - 1, control)?.projection_operator_controlled(control)
+}
+
+/// Pivots the simplex tableau `t` on the entry in row `r` and column `c`,
+/// making its variable basic in that row.
+fn pivot(t: &mut [Vec<BigRational>], basis: &mut [usize], r: usize, c: usize) {
+    let inverse = t[r][c].recip();
+    for a in &mut t[r] {
+        *a *= &inverse;
+    }
+    let pivot_row = t[r].clone();
+    for (i, row) in t.iter_mut().enumerate() {
+        if i == r || row[c].is_zero() {
+            continue;
+        }
+        let factor = row[c].clone();
+        for (a, p) in row.iter_mut().zip(&pivot_row) {
+            *a -= &factor * p;
+        }
+    }
+    basis[r] = c;
+}
+
+/// Runs the simplex method on the tableau `t`, whose last column is the
+/// right-hand side, minimizing `cost` over the columns before `allowed`,
+/// with the rule of Bland against cycling. The feasible region must be
+/// bounded.
+fn run_simplex(
+    t: &mut [Vec<BigRational>],
+    basis: &mut [usize],
+    cost: &[BigRational],
+    allowed: usize,
+) {
+    loop {
+        let reduced = |j: usize| {
+            let mut r = cost[j].clone();
+            for (row, &b) in t.iter().zip(basis.iter()) {
+                r -= &cost[b] * &row[j];
+            }
+            r
+        };
+        let Some(c) = (0..allowed).find(|&j| !basis.contains(&j) && reduced(j).is_negative())
+        else {
+            return;
+        };
+        let rhs = t[0].len() - 1;
+        let r = (0..t.len())
+            .filter(|&i| t[i][c].is_positive())
+            .min_by(|&i, &k| {
+                (&t[i][rhs] / &t[i][c])
+                    .cmp(&(&t[k][rhs] / &t[k][c]))
+                    .then(basis[i].cmp(&basis[k]))
+            })
+            .expect("the feasible region is bounded");
+        pivot(t, basis, r, c);
+    }
+}
+
+/// A minimizer `x` of `cost·x` subject to `a*x = b` and `x ≥ 0`, by the two
+/// phases of the simplex method, or `None` if there is none. The feasible
+/// region must be bounded.
+fn minimize(
+    a: &[Vec<BigRational>],
+    b: &[BigRational],
+    cost: &[BigRational],
+) -> Option<Vec<BigRational>> {
+    let (m, n) = (a.len(), cost.len());
+    // The first phase minimizes the sum of artificial variables, one for
+    // each row, which start as the basis.
+    let mut t: Vec<Vec<BigRational>> = a
+        .iter()
+        .zip(b)
+        .enumerate()
+        .map(|(i, (row, rhs))| {
+            let mut row: Vec<BigRational> = row.clone();
+            row.extend((0..m).map(|k| BigRational::from_integer(BigInt::from(u8::from(k == i)))));
+            row.push(rhs.clone());
+            if rhs.is_negative() {
+                for x in &mut row[..n] {
+                    *x = -x.clone();
+                }
+                row[n + m] = -rhs.clone();
+            }
+            row
+        })
+        .collect();
+    let mut basis: Vec<usize> = (n..n + m).collect();
+    let artificial: Vec<BigRational> = (0..n + m)
+        .map(|j| BigRational::from_integer(BigInt::from(u8::from(j >= n))))
+        .collect();
+    run_simplex(&mut t, &mut basis, &artificial, n + m);
+    if t.iter()
+        .zip(&basis)
+        .any(|(row, &j)| j >= n && !row[n + m].is_zero())
+    {
+        return None;
+    }
+    // Artificial variables left in the basis at zero are replaced by
+    // original ones where the row allows it; otherwise the row is redundant.
+    for r in 0..m {
+        if basis[r] >= n
+            && let Some(c) = (0..n).find(|&j| !t[r][j].is_zero())
+        {
+            pivot(&mut t, &mut basis, r, c);
+        }
+    }
+    let mut cost = cost.to_vec();
+    cost.resize(n + m, BigRational::zero());
+    run_simplex(&mut t, &mut basis, &cost, n);
+    let mut x = vec![BigRational::zero(); n];
+    for (row, &j) in t.iter().zip(&basis) {
+        if j < n {
+            x[j] = row[n + m].clone();
+        }
+    }
+    Some(x)
+}
+
+/// A polynomial of a system given by the exponents and coefficients of its
+/// terms.
+type Support<T> = Vec<(Vec<i64>, T)>;
+
+/// The next value of the linear congruential generator with state `state`,
+/// of 31 bits.
+fn next_random(state: &mut u64) -> i64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    (*state >> 33) as i64
+}
+
+/// The sparse resultant matrix of `n + 1` polynomials `f_0, ..., f_n` in
+/// `n` variables, as built by [`sparse_resultant_matrix`] after Canny and
+/// Emiris.
+///
+/// The supports `A_i` of the polynomials are lifted to `ℤ^(n+1)` by generic
+/// heights, and the lower hull of the Minkowski sum of the lifted supports
+/// projects to a mixed subdivision of the Minkowski sum `Q` of their Newton
+/// polytopes, whose cells are sums `F_0 + ... + F_n` with `F_i ⊆ A_i`. The
+/// rows and columns are indexed by the lattice points `p` of `Q + δ`, for a
+/// small generic displacement `δ`: for the largest `i` such that `F_i` is a
+/// single point `a` in the cell containing `p - δ`, the row of `p` holds the
+/// coefficients of `x^(p-a)*f_i`, all of whose monomials are lattice points
+/// of `Q + δ` again.
+///
+/// Only the sparsity of the system matters, so the matrix is usually much
+/// smaller than the Macaulay matrix of the homogenized system, and its
+/// determinant is a nonzero multiple of the sparse resultant for generic
+/// coefficients, of the same degree in those of `f_0`.
+#[derive(Clone, Debug)]
+pub struct SparseResultantMatrix<T, P> {
+    matrix: Matrix<T>,
+    monomials: Vec<Monomial<P>>,
+    forms: Vec<usize>,
+}
+
+impl<T, P> SparseResultantMatrix<T, P> {
+    /// The matrix.
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    /// The lattice points of `Q + δ` indexing both the rows and the
+    /// columns, as monomials, by increasing exponents.
+    pub fn monomials(&self) -> &[Monomial<P>] {
+        &self.monomials
+    }
+
+    /// The index `i` of the polynomial `f_i` whose multiple makes up each
+    /// row.
+    pub fn forms(&self) -> &[usize] {
+        &self.forms
+    }
+}
+
+impl<T: DivExact + Clone, P> SparseResultantMatrix<T, P> {
+    /// The determinant, a multiple of the sparse resultant by a factor not
+    /// involving the coefficients of `f_0`, which is nonzero for generic
+    /// coefficients.
+    pub fn determinant(&self) -> T {
+        self.matrix.det()
+    }
+
+    /// The determinant of [`SparseResultantMatrix::determinant`],
+    /// consulting `control` as [`Matrix::det_controlled`] does.
+    pub fn determinant_controlled(&self, control: &ComputationControl) -> Result<T, ChidogError> {
+        self.matrix.det_controlled(control)
+    }
+}
+
+/// The sparse resultant matrix of the `n + 1` nonzero polynomials `system`
+/// in `n` variables, with liftings and displacement drawn from `seed`,
+/// consulting `control` before each lattice point is placed in its cell.
+fn sparse<T, P>(
+    system: &[Support<T>],
+    n: usize,
+    seed: u64,
+    control: &ComputationControl,
+) -> Result<SparseResultantMatrix<T, P>, ChidogError>
+where
+    T: RingElement + Clone,
+    P: Hash + PrimInt,
+{
+    if system.len() != n + 1 || system.iter().any(Vec::is_empty) {
+        return Err(ChidogError::InvalidInput(format!(
+            "a sparse resultant needs one more nonzero polynomial than variables, not {} in {n}",
+            system.len()
+        )));
+    }
+    let degenerate = || {
+        ChidogError::InvalidInput(format!(
+            "the lifting drawn from seed {seed} is not generic for this system"
+        ))
+    };
+    let rational = |i: i64| BigRational::from_integer(BigInt::from(i));
+    let mut state = seed;
+    let delta: Vec<BigRational> = (0..n)
+        .map(|_| {
+            BigRational::new(
+                BigInt::from(next_random(&mut state) + 1),
+                BigInt::from(1i64 << 36),
+            )
+        })
+        .collect();
+    let heights: Vec<Vec<BigRational>> = system
+        .iter()
+        .map(|f| {
+            f.iter()
+                .map(|_| rational(next_random(&mut state)))
+                .collect()
+        })
+        .collect();
+    let cost: Vec<BigRational> = heights.concat();
+    // The constraints `∑ λ_(i,a)*a = p - δ` and `∑_a λ_(i,a) = 1` for each
+    // `i`, on the variables `λ_(i,a) ≥ 0`, whose optimum for the heights
+    // is supported on the cell containing `p - δ`.
+    let mut a: Vec<Vec<BigRational>> = (0..n)
+        .map(|j| {
+            system
+                .iter()
+                .flat_map(|f| f.iter().map(move |(e, _)| rational(e[j])))
+                .collect()
+        })
+        .collect();
+    for i in 0..=n {
+        a.push(
+            system
+                .iter()
+                .enumerate()
+                .flat_map(|(k, f)| f.iter().map(move |_| rational(i64::from(k == i))))
+                .collect(),
+        );
+    }
+    let low: Vec<i64> = (0..n)
+        .map(|j| {
+            system
+                .iter()
+                .map(|f| f.iter().map(|(e, _)| e[j]).min().unwrap())
+                .sum()
+        })
+        .collect();
+    let high: Vec<i64> = (0..n)
+        .map(|j| {
+            system
+                .iter()
+                .map(|f| f.iter().map(|(e, _)| e[j]).max().unwrap())
+                .sum()
+        })
+        .collect();
+    let mut points = Vec::new();
+    let mut p = low.clone();
+    'points: loop {
+        control.check(&Progress {
+            algorithm: "sparse resultant",
+            steps: points.len(),
+            remaining: None,
+            terms: points.len(),
+        })?;
+        let mut b: Vec<BigRational> = (0..n).map(|j| rational(p[j]) - &delta[j]).collect();
+        b.extend((0..=n).map(|_| rational(1)));
+        if let Some(lambda) = minimize(&a, &b, &cost) {
+            let mut cell = Vec::with_capacity(n + 1);
+            let mut offset = 0;
+            for f in system {
+                let support: Vec<usize> = (0..f.len())
+                    .filter(|&k| !lambda[offset + k].is_zero())
+                    .collect();
+                cell.push(support);
+                offset += f.len();
+            }
+            if cell.iter().map(|s| s.len() - 1).sum::<usize>() != n {
+                return Err(degenerate());
+            }
+            let (i, point) = cell
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, s)| s.len() == 1)
+                .map(|(i, s)| (i, s[0]))
+                .unwrap();
+            points.push((p.clone(), i, point));
+        }
+        for j in 0..n {
+            if p[j] < high[j] + 1 {
+                p[j] += 1;
+                continue 'points;
+            }
+            p[j] = low[j];
+        }
+        break;
+    }
+    points.sort_unstable_by(|x, y| x.0.cmp(&y.0));
+    let position: HashMap<&[i64], usize> = points
+        .iter()
+        .map(|(p, _, _)| p.as_slice())
+        .zip(0..)
+        .collect();
+    let size = points.len();
+    let mut matrix = Matrix::zero(size, size);
+    for (r, (p, i, point)) in points.iter().enumerate() {
+        let shift: Vec<i64> = p
+            .iter()
+            .zip(&system[*i][*point].0)
+            .map(|(x, y)| x - y)
+            .collect();
+        for (e, c) in &system[*i] {
+            let column: Vec<i64> = shift.iter().zip(e).map(|(x, y)| x + y).collect();
+            let &column = position.get(column.as_slice()).ok_or_else(degenerate)?;
+            matrix[(r, column)] = c.clone();
+        }
+    }
+    let monomials = points
+        .iter()
+        .map(|(p, _, _)| {
+            let powers = p
+                .iter()
+                .map(|&x| P::from(x).ok_or(ChidogError::ExponentOverflow))
+                .collect::<Result<_, _>>()?;
+            Ok(Monomial { powers })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(SparseResultantMatrix {
+        matrix,
+        monomials,
+        forms: points.iter().map(|(_, i, _)| *i).collect(),
+    })
+}
+
+/// The exponents of the terms of `f` as integers.
+fn support<R, V, K, P>(f: &Polynomial<'_, R, V, K, P>) -> Support<K>
+where
+    K: Clone,
+    P: Hash + PrimInt,
+{
+    f.terms
+        .iter()
+        .map(|(m, c)| {
+            (
+                m.powers.iter().map(|p| p.to_i64().unwrap()).collect(),
+                c.clone(),
+            )
+        })
+        .collect()
+}
+
+/// The sparse resultant matrix of the `n + 1` nonzero polynomials `system`
+/// in `n` variables, with the heights lifting their supports and the
+/// displacement drawn from `seed` by a linear congruential generator.
+///
+/// Each lattice point of the Minkowski sum takes a small exact linear
+/// program to place in its cell, so this suits systems with few terms. This
+/// is an `InvalidInput` error if the number of polynomials is wrong or one
+/// is zero, or if the heights from `seed` turn out not to be generic, in
+/// which case another seed will do.
+pub fn sparse_resultant_matrix<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    seed: u64,
+) -> Result<SparseResultantMatrix<K, P>, ChidogError>
+where
+    R: Semiring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let n = system.first().map_or(0, |f| f.elem_of.vars.len());
+    let system: Vec<_> = system.iter().map(support).collect();
+    sparse(&system, n, seed, &ComputationControl::new())
+}
+
+/// The hidden-variable resultant of the `n` polynomials `system` in `n`
+/// variables from their sparse resultant matrix in the variables other
+/// than the one with index `hidden`, with coefficients polynomials in the
+/// hidden one: the determinant of [`SparseResultantMatrix`], which vanishes
+/// at the value of the hidden variable at each common zero with no zero
+/// coordinate.
+///
+/// This is an `InvalidInput` error under the conditions of
+/// [`sparse_resultant_matrix`].
+pub fn hidden_variable_sparse_resultant<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+    seed: u64,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    hidden_variable_sparse_resultant_controlled(system, hidden, seed, &ComputationControl::new())
+}
+
+/// The hidden-variable resultant of [`hidden_variable_sparse_resultant`],
+/// consulting `control` before each lattice point is placed in its cell and
+/// during the determinant, and failing as it directs.
+pub fn hidden_variable_sparse_resultant_controlled<R, V, K, P>(
+    system: &[Polynomial<'_, R, V, K, P>],
+    hidden: usize,
+    seed: u64,
+    control: &ComputationControl,
+) -> Result<DenseUnivariate<K>, ChidogError>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    let vars = system.first().map_or(0, |f| f.elem_of.vars.len());
+    if hidden >= vars {
+        return Err(ChidogError::InvalidInput(format!(
+            "no variable with index {hidden} to hide"
+        )));
+    }
+    let system: Vec<Support<DenseUnivariate<K>>> = hide(system, Some(hidden))
+        .into_iter()
+        .map(|(terms, _)| {
+            terms
+                .into_iter()
+                .map(|(m, c)| {
+                    let mut e: Vec<i64> = m.powers.iter().map(|p| p.to_i64().unwrap()).collect();
+                    e.pop();
+                    (e, c)
+                })
+                .collect()
+        })
+        .collect();
+    sparse::<_, P>(&system, vars - 1, seed, control)?.determinant_controlled(control)
+}
@@ -0,0 +1,243 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use num::{PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::order::MonomialOrder;
+use crate::{Field, FieldElement, Monomial, Polynomial, PolynomialRing};
+
+/// A SAGBI basis of the subalgebra generated by some polynomials, truncated
+/// at a degree, as computed by [`sagbi_basis`].
+///
+/// The elements are monic and nonconstant, and the leading monomial of
+/// every element of the subalgebra of degree up to the truncation degree is
+/// a product of their leading monomials. Unlike Gröbner bases of ideals,
+/// SAGBI bases may be infinite, which is why they are truncated.
+#[derive(Clone, Debug)]
+pub struct SagbiBasis<'a, R, V, K, P, O>
+where
+    P: Hash,
+{
+    elements: Vec<Polynomial<'a, R, V, K, P>>,
+    leads: Vec<Vec<u64>>,
+    order: O,
+    degree: usize,
+}
+
+/// The total degree of the monomial with exponents `exponents`.
+fn total_degree(exponents: &[u64]) -> usize {
+    exponents.iter().sum::<u64>() as usize
+}
+
+/// Exponents `a_i`, if any, such that `target` is the sum of the `a_i*leads[i]`
+/// for `i` from `from` onwards, found by depth-first search.
+fn factor(target: &[u64], leads: &[Vec<u64>], from: usize) -> Option<Vec<usize>> {
+    if target.iter().all(|&t| t == 0) {
+        return Some(vec![0; leads.len()]);
+    }
+    let lead = leads.get(from)?;
+    let most = (0..target.len())
+        .filter(|&j| lead[j] > 0)
+        .map(|j| target[j] / lead[j])
+        .min()
+        .unwrap_or(0);
+    let mut rest = target.to_vec();
+    for k in (0..=most).rev() {
+        for (r, (t, l)) in rest.iter_mut().zip(target.iter().zip(lead)) {
+            *r = t - k * l;
+        }
+        if let Some(mut a) = factor(&rest, leads, from + 1) {
+            a[from] = k as usize;
+            return Some(a);
+        }
+    }
+    None
+}
+
+impl<'a, R, V, K, P, O> SagbiBasis<'a, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder,
+{
+    /// The elements of the basis, in the order they were found.
+    pub fn elements(&self) -> &[Polynomial<'a, R, V, K, P>] {
+        &self.elements
+    }
+
+    /// The degree up to which the basis is complete.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// The product `c*∏ elements[i]^a[i]`.
+    fn product(
+        &self,
+        ring: &'a PolynomialRing<'a, R, V>,
+        a: &[usize],
+        c: K,
+    ) -> Polynomial<'a, R, V, K, P> {
+        let one = Monomial {
+            powers: smallvec![P::zero(); ring.vars.len()],
+        };
+        let mut product = Polynomial::from_terms(ring, [(one, c)])
+            .expect("the monomials have one exponent for each variable");
+        for (s, &k) in self.elements.iter().zip(a) {
+            for _ in 0..k {
+                product *= s.clone();
+            }
+        }
+        product
+    }
+
+    /// The subduction of `f` by the basis: while the leading term `c*x^a` of
+    /// `f` is such that `x^a` is a product of leading monomials of the
+    /// elements, `f` is replaced by `f` minus `c` times the product of those
+    /// elements. The result is zero or has a leading monomial which is not
+    /// such a product.
+    pub fn subduce(&self, f: &Polynomial<'a, R, V, K, P>) -> Polynomial<'a, R, V, K, P> {
+        let mut f = f.clone();
+        while let Some((m, c)) = f.leading_term(&self.order) {
+            let target: Vec<u64> = m.powers.iter().map(|p| p.to_u64().unwrap()).collect();
+            let Some(a) = factor(&target, &self.leads, 0) else {
+                break;
+            };
+            let product = self.product(f.elem_of, &a, c.clone());
+            f -= product;
+        }
+        f
+    }
+
+    /// Whether `f` belongs to the subalgebra, that is, whether it subduces to
+    /// zero.
+    ///
+    /// The answer is exact for `f` of degree up to [`SagbiBasis::degree`]
+    /// when the generators are homogeneous and the order is graded;
+    /// otherwise a positive answer is still exact, but a negative one may
+    /// come from the truncation.
+    pub fn contains(&self, f: &Polynomial<'a, R, V, K, P>) -> bool {
+        self.subduce(f).terms.is_empty()
+    }
+
+    /// Subduces `f` and adds it to the basis, made monic, if it is not a
+    /// constant and its leading monomial has degree up to the truncation
+    /// degree. Returns whether it was added.
+    fn insert(&mut self, f: &Polynomial<'a, R, V, K, P>) -> bool {
+        let f = self.subduce(f);
+        let Some((m, c)) = f.leading_term(&self.order) else {
+            return false;
+        };
+        let lead: Vec<u64> = m.powers.iter().map(|p| p.to_u64().unwrap()).collect();
+        if total_degree(&lead) == 0 || total_degree(&lead) > self.degree {
+            return false;
+        }
+        let c = K::one() / c.clone();
+        let monic = Polynomial::from_terms(
+            f.elem_of,
+            f.terms
+                .iter()
+                .map(|(m, a)| (m.clone(), a.clone() * c.clone())),
+        )
+        .expect("the monomials have one exponent for each variable");
+        self.elements.push(monic);
+        self.leads.push(lead);
+        true
+    }
+
+    /// The exponent vectors `a` with `∑ a_i*deg(leads[i])` at most the
+    /// truncation degree, grouped by the product of leading monomials they
+    /// give.
+    fn products(&self) -> HashMap<Vec<u64>, Vec<Vec<usize>>> {
+        let n = self.leads.first().map_or(0, Vec::len);
+        let mut classes: HashMap<Vec<u64>, Vec<Vec<usize>>> = HashMap::new();
+        let mut stack = vec![(vec![0; self.leads.len()], vec![0; n], 0)];
+        while let Some((a, monomial, from)) = stack.pop() {
+            for i in from..self.leads.len() {
+                let next: Vec<u64> = monomial
+                    .iter()
+                    .zip(&self.leads[i])
+                    .map(|(m, l)| m + l)
+                    .collect();
+                if total_degree(&next) <= self.degree {
+                    let mut b = a.clone();
+                    b[i] += 1;
+                    classes.entry(next.clone()).or_default().push(b.clone());
+                    stack.push((b, next, i));
+                }
+            }
+        }
+        classes
+    }
+}
+
+/// The SAGBI basis of the subalgebra generated by `generators` in `order`,
+/// truncated at degree `degree`, by the algorithm of Robbiano and Sweedler.
+///
+/// The generators are subduced into an initial basis. Then, for every two
+/// products of elements whose leading monomials agree, of degree up to
+/// `degree` and without common factor, the difference of the products,
+/// which cancels the leading terms, is subduced, and added to the basis if
+/// it does not vanish. This repeats until no difference adds anything.
+pub fn sagbi_basis<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    degree: usize,
+) -> SagbiBasis<'a, R, V, K, P, O>
+where
+    R: Field<K>,
+    K: FieldElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    O: MonomialOrder + Clone,
+{
+    let mut basis = SagbiBasis {
+        elements: Vec::new(),
+        leads: Vec::new(),
+        order: order.clone(),
+        degree,
+    };
+    for f in generators {
+        basis.insert(f);
+    }
+    // The pairs of exponent vectors already considered, without their
+    // trailing zeros, which stay valid as the basis grows.
+    let mut done: HashSet<(Vec<usize>, Vec<usize>)> = HashSet::new();
+    let trim = |a: &[usize]| {
+        let end = a.iter().rposition(|&k| k > 0).map_or(0, |i| i + 1);
+        a[..end].to_vec()
+    };
+    loop {
+        let mut added = false;
+        // The pairs by increasing degree, in a fixed order, so that the basis
+        // does not depend on the iteration order of the classes.
+        let mut pairs: Vec<_> = basis
+            .products()
+            .into_iter()
+            .flat_map(|(monomial, class)| {
+                let mut pairs = Vec::new();
+                for (i, a) in class.iter().enumerate() {
+                    for b in &class[i + 1..] {
+                        if a.iter().zip(b).all(|(&x, &y)| x == 0 || y == 0) {
+                            pairs.push((total_degree(&monomial), trim(a), trim(b)));
+                        }
+                    }
+                }
+                pairs
+            })
+            .filter(|(_, a, b)| !done.contains(&(a.clone(), b.clone())))
+            .collect();
+        pairs.sort_unstable();
+        for (_, a, b) in pairs {
+            let ring = basis.elements[0].elem_of;
+            let difference = basis.product(ring, &a, K::one()) - basis.product(ring, &b, K::one());
+            added |= basis.insert(&difference);
+            done.insert((a, b));
+        }
+        if !added {
+            return basis;
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::error::ChidogError;
+use crate::openmath::{Element, escape, parse_start_tag, parse_xml};
+
+/// The version of the SCSCP protocol spoken by [`Client`] and [`serve`].
+pub const VERSION: &str = "1.3";
+
+const OPENMATH_NS: &str = "http://www.openmath.org/OpenMath";
+
+fn protocol_error(message: impl Into<String>) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        ChidogError::Parse(message.into()),
+    )
+}
+
+/// The attributes of the SCSCP processing instruction `line`, such as
+/// `<?scscp version="1.3" ?>`, or `None` if it is not one.
+fn instruction(line: &str) -> Option<HashMap<String, String>> {
+    let content = line.trim().strip_prefix("<?")?.strip_suffix("?>")?;
+    let (element, _) = parse_start_tag(content).ok()?;
+    (element.name == "scscp").then_some(element.attrs)
+}
+
+fn write_instruction<W: Write + ?Sized>(w: &mut W, attrs: &[(&str, &str)]) -> io::Result<()> {
+    let mut line = String::from("<?scscp");
+    for (name, value) in attrs {
+        write!(line, " {name}=\"{}\"", escape(value)).unwrap();
+    }
+    line.push_str(" ?>\n");
+    w.write_all(line.as_bytes())?;
+    w.flush()
+}
+
+/// Reads processing instructions from `r` until one with attributes,
+/// skipping `info` messages, or `None` at the end of the stream.
+fn read_instruction<R: BufRead + ?Sized>(r: &mut R) -> io::Result<Option<HashMap<String, String>>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if r.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if let Some(attrs) = instruction(&line)
+            && !attrs.contains_key("info")
+        {
+            return Ok(Some(attrs));
+        }
+    }
+}
+
+/// A message read from an SCSCP stream.
+enum Message {
+    /// An OpenMath object sent between `start` and `end` instructions.
+    Object(Element),
+    /// The `quit` instruction, or the end of the stream.
+    Quit,
+}
+
+/// Reads the next message from `r`, skipping cancelled ones.
+fn read_message<R: BufRead + ?Sized>(r: &mut R) -> io::Result<Message> {
+    let mut line = String::new();
+    let mut object: Option<String> = None;
+    loop {
+        line.clear();
+        if r.read_line(&mut line)? == 0 {
+            return Ok(Message::Quit);
+        }
+        let Some(trimmed) = line.trim().strip_prefix("<?scscp") else {
+            if let Some(object) = &mut object {
+                object.push_str(&line);
+            }
+            continue;
+        };
+        match trimmed.trim_end_matches("?>").trim() {
+            "start" => object = Some(String::new()),
+            "cancel" => object = None,
+            "end" => {
+                let xml = object
+                    .take()
+                    .ok_or_else(|| protocol_error("`end` without `start`"))?;
+                let root =
+                    parse_xml(&xml).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                return Ok(Message::Object(root));
+            }
+            t if t.starts_with("quit") => return Ok(Message::Quit),
+            _ => {}
+        }
+    }
+}
+
+fn write_message<W: Write + ?Sized>(w: &mut W, object: &Element) -> io::Result<()> {
+    let mut message = String::from("<?scscp start ?>\n");
+    object.write_xml(&mut message);
+    message.push_str("\n<?scscp end ?>\n");
+    w.write_all(message.as_bytes())?;
+    w.flush()
+}
+
+fn element(name: &str, attrs: &[(&str, &str)], children: Vec<Element>) -> Element {
+    Element {
+        name: name.to_string(),
+        attrs: attrs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        children,
+        text: String::new(),
+    }
+}
+
+fn symbol(cd: &str, name: &str) -> Element {
+    element("OMS", &[("cd", cd), ("name", name)], Vec::new())
+}
+
+fn string(s: &str) -> Element {
+    Element {
+        text: s.to_string(),
+        ..element("OMSTR", &[], Vec::new())
+    }
+}
+
+/// The object `<OMOBJ>e</OMOBJ>`.
+fn omobj(e: Element) -> Element {
+    element("OMOBJ", &[("xmlns", OPENMATH_NS)], vec![e])
+}
+
+/// The object of the OpenMath document `xml`, unwrapped from its `OMOBJ`.
+fn object(xml: &str) -> io::Result<Element> {
+    let root = parse_xml(xml).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    match root.name.as_str() {
+        "OMOBJ" => {
+            let mut root = root;
+            match root.children.len() {
+                1 => Ok(root.children.pop().unwrap()),
+                _ => Err(protocol_error("expected one object in `OMOBJ`")),
+            }
+        }
+        _ => Ok(root),
+    }
+}
+
+/// The document `<OMOBJ>e</OMOBJ>` of the object `e`.
+fn document(e: Element) -> String {
+    let mut xml = String::new();
+    omobj(e).write_xml(&mut xml);
+    xml
+}
+
+/// The SCSCP message `e` attributed with `attrs`, pairs of `scscp1`
+/// symbols and strings, such as its `call_id`.
+fn attributed(attrs: &[(&str, &str)], e: Element) -> Element {
+    let pairs = attrs
+        .iter()
+        .flat_map(|(name, value)| [symbol("scscp1", name), string(value)])
+        .collect();
+    omobj(element(
+        "OMATTR",
+        &[],
+        vec![element("OMATP", &[], pairs), e],
+    ))
+}
+
+/// The attributes of the message `root`, by the names of their `scscp1`
+/// symbols, and the attributed object.
+fn unattributed(mut root: Element) -> io::Result<(HashMap<String, String>, Element)> {
+    let malformed = || protocol_error("malformed SCSCP message");
+    let root = match root.name.as_str() {
+        "OMOBJ" if root.children.len() == 1 => root.children.pop().unwrap(),
+        _ => return Err(malformed()),
+    };
+    if root.name != "OMATTR" {
+        return Ok((HashMap::new(), root));
+    }
+    let mut root = root;
+    let (Some(e), Some(atp), None) = (
+        root.children.pop(),
+        root.children.pop(),
+        root.children.pop(),
+    ) else {
+        return Err(malformed());
+    };
+    let mut attrs = HashMap::new();
+    for pair in atp.children.chunks(2) {
+        if let [key, value] = pair
+            && let Some(name) = key.attr("name")
+        {
+            attrs.insert(name.to_string(), value.text.clone());
+        }
+    }
+    Ok((attrs, e))
+}
+
+/// A client of an SCSCP server, such as GAP or Singular, which calls its
+/// procedures on OpenMath objects, such as the polynomials written by
+/// [`to_openmath`](crate::openmath::to_openmath).
+pub struct Client<S> {
+    stream: BufReader<S>,
+    service_name: String,
+    next_call: u64,
+}
+
+impl Client<TcpStream> {
+    /// Connects to the SCSCP server at `addr`.
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::new(TcpStream::connect(addr)?)
+    }
+}
+
+impl<S: Read + Write> Client<S> {
+    /// Starts an SCSCP session on `stream`, negotiating version
+    /// [`VERSION`] of the protocol.
+    pub fn new(stream: S) -> io::Result<Self> {
+        let mut stream = BufReader::new(stream);
+        let greeting = read_instruction(&mut stream)?
+            .ok_or_else(|| protocol_error("connection closed before greeting"))?;
+        let versions = greeting.get("scscp_versions").map_or("", String::as_str);
+        if !versions.split_whitespace().any(|v| v == VERSION) {
+            write_instruction(
+                stream.get_mut(),
+                &[("quit", ""), ("reason", "unsupported version")],
+            )?;
+            return Err(protocol_error(format!(
+                "server supports only versions {versions}"
+            )));
+        }
+        write_instruction(stream.get_mut(), &[("version", VERSION)])?;
+        let reply = read_instruction(&mut stream)?
+            .ok_or_else(|| protocol_error("connection closed during version negotiation"))?;
+        if reply.get("version").map(String::as_str) != Some(VERSION) {
+            return Err(protocol_error("server rejected the version"));
+        }
+        Ok(Self {
+            stream,
+            service_name: greeting.get("service_name").cloned().unwrap_or_default(),
+            next_call: 0,
+        })
+    }
+
+    /// The name the server gave for its service.
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// The OpenMath document of the result of calling the procedure `name`
+    /// of the content dictionary `cd`, usually `scscp_transient_1`, on the
+    /// OpenMath documents `args`, or the message of the error the server
+    /// reported as an `Err` inside the `Ok`.
+    ///
+    /// Fails with an I/O error if the stream does, or if the server does not
+    /// follow the protocol.
+    pub fn call(
+        &mut self,
+        cd: &str,
+        name: &str,
+        args: &[&str],
+    ) -> io::Result<Result<String, String>> {
+        self.next_call += 1;
+        let call_id = format!("chidog-{}", self.next_call);
+        let mut procedure = vec![symbol(cd, name)];
+        for arg in args {
+            procedure.push(object(arg)?);
+        }
+        let call = element(
+            "OMA",
+            &[],
+            vec![
+                symbol("scscp1", "procedure_call"),
+                element("OMA", &[], procedure),
+            ],
+        );
+        let message = attributed(&[("call_id", &call_id), ("option_return_object", "")], call);
+        write_message(self.stream.get_mut(), &message)?;
+        let root = match read_message(&mut self.stream)? {
+            Message::Object(root) => root,
+            Message::Quit => return Err(protocol_error("server quit")),
+        };
+        let (attrs, response) = unattributed(root)?;
+        if attrs.get("call_id") != Some(&call_id) {
+            return Err(protocol_error("response to another call"));
+        }
+        let mut children = response.children.into_iter();
+        let (Some(head), Some(result)) = (children.next(), children.next()) else {
+            return Err(protocol_error("malformed response"));
+        };
+        if head.is_symbol("scscp1", "procedure_completed") {
+            Ok(Ok(document(result)))
+        } else if head.is_symbol("scscp1", "procedure_terminated") {
+            let message = result
+                .children
+                .iter()
+                .find(|e| e.name == "OMSTR")
+                .map_or_else(String::new, |e| e.text.clone());
+            Ok(Err(message))
+        } else {
+            Err(protocol_error("malformed response"))
+        }
+    }
+
+    /// Ends the session.
+    pub fn quit(mut self) -> io::Result<()> {
+        write_instruction(self.stream.get_mut(), &[("quit", "")])
+    }
+}
+
+/// Serves SCSCP procedure calls on `stream` until the client quits, as the
+/// service `service_name`.
+///
+/// Each call of a procedure `name`, from any content dictionary, on the
+/// OpenMath documents `args` is answered with the document returned by
+/// `handler(name, args)`, or with the message of its error, which the
+/// client receives as a `procedure_terminated` with an
+/// `error_system_specific`. Serving many clients, for example from a
+/// [`TcpListener`](std::net::TcpListener), takes one call of `serve` per
+/// connection.
+pub fn serve<S, F>(stream: S, service_name: &str, mut handler: F) -> io::Result<()>
+where
+    S: Read + Write,
+    F: FnMut(&str, &[String]) -> Result<String, String>,
+{
+    let mut stream = BufReader::new(stream);
+    let service_id = std::process::id().to_string();
+    write_instruction(
+        stream.get_mut(),
+        &[
+            ("service_name", service_name),
+            ("service_version", env!("CARGO_PKG_VERSION")),
+            ("service_id", &service_id),
+            ("scscp_versions", VERSION),
+        ],
+    )?;
+    let Some(request) = read_instruction(&mut stream)? else {
+        return Ok(());
+    };
+    if request.get("version").map(String::as_str) != Some(VERSION) {
+        return write_instruction(
+            stream.get_mut(),
+            &[("quit", ""), ("reason", "unsupported version")],
+        );
+    }
+    write_instruction(stream.get_mut(), &[("version", VERSION)])?;
+    loop {
+        let root = match read_message(&mut stream)? {
+            Message::Object(root) => root,
+            Message::Quit => return Ok(()),
+        };
+        let (attrs, call) = unattributed(root)?;
+        let call_id = attrs.get("call_id").cloned().unwrap_or_default();
+        let result = match call.children.as_slice() {
+            [head, procedure] if head.is_symbol("scscp1", "procedure_call") => {
+                match procedure.children.split_first() {
+                    Some((name, args)) if name.name == "OMS" => {
+                        let name = name.attr("name").unwrap_or_default();
+                        let args: Vec<String> = args
+                            .iter()
+                            .map(|arg| {
+                                let mut xml = String::new();
+                                arg.write_xml(&mut xml);
+                                format!("<OMOBJ xmlns=\"{OPENMATH_NS}\">{xml}</OMOBJ>")
+                            })
+                            .collect();
+                        handler(name, &args)
+                    }
+                    _ => Err("malformed procedure call".to_string()),
+                }
+            }
+            _ => Err("not a procedure call".to_string()),
+        };
+        let response = match result.map(|xml| object(&xml)) {
+            Ok(Ok(result)) => element(
+                "OMA",
+                &[],
+                vec![symbol("scscp1", "procedure_completed"), result],
+            ),
+            Ok(Err(e)) => terminated(&e.to_string()),
+            Err(message) => terminated(&message),
+        };
+        write_message(
+            stream.get_mut(),
+            &attributed(&[("call_id", &call_id)], response),
+        )?;
+    }
+}
+
+/// The `procedure_terminated` response reporting the error `message`.
+fn terminated(message: &str) -> Element {
+    let error = element(
+        "OME",
+        &[],
+        vec![symbol("scscp1", "error_system_specific"), string(message)],
+    );
+    element(
+        "OMA",
+        &[],
+        vec![symbol("scscp1", "procedure_terminated"), error],
+    )
+}
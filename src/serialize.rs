@@ -0,0 +1,130 @@
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use num::{PrimInt, Unsigned};
+use serde::de::{DeserializeSeed, Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Monomial, Polynomial, PolynomialRing, Semiring, SemiringElement};
+
+/// The variables, of type `T`, and exponent bound of a ring, which is
+/// serialized without its base ring.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "PolynomialRing")]
+struct RingData<T> {
+    vars: T,
+    max_exponent: Option<u64>,
+}
+
+/// A ring is serialized as its variables and the exponent bound of its
+/// checked operations. The base ring is not serialized, and is supplied
+/// again by [`PolynomialRing::deserialize_over`].
+impl<R, V: Serialize> Serialize for PolynomialRing<'_, R, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RingData {
+            vars: &self.vars,
+            max_exponent: self.max_exponent,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'a, R, V> PolynomialRing<'a, R, V> {
+    /// The ring over `base` serialized by `deserializer`.
+    pub fn deserialize_over<'de, D>(base: &'a R, deserializer: D) -> Result<Self, D::Error>
+    where
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let data = RingData::<Vec<V>>::deserialize(deserializer)?;
+        Ok(Self {
+            max_exponent: data.max_exponent,
+            ..Self::new(data.vars, base)
+        })
+    }
+
+    /// The seed deserializing a `T` made of polynomials in `self`, such as a
+    /// [`Polynomial`] or a `Vec` of them, which is named explicitly, as in
+    /// `ring.seed::<Vec<Polynomial<_, _, K, P>>>()`.
+    pub fn seed<T>(&'a self) -> Seed<'a, R, V, T> {
+        Seed {
+            ring: self,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// A polynomial is serialized as the sequence of its terms, each a pair of
+/// the exponents of its monomial and its coefficient, sorted by exponents so
+/// that equal polynomials serialize identically. The ring is not serialized:
+/// a polynomial is deserialized in a ring given by the caller, through
+/// [`PolynomialRing::seed`].
+impl<R, V, K, P, S> Serialize for Polynomial<'_, R, V, K, P, S>
+where
+    K: Serialize,
+    P: Hash + PrimInt + Serialize,
+    S: BuildHasher,
+{
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_unstable_by(|(a, _), (b, _)| a.powers.cmp(&b.powers));
+        serializer.collect_seq(terms)
+    }
+}
+
+/// The [`DeserializeSeed`] of a `T` made of polynomials in `ring`, made by
+/// [`PolynomialRing::seed`].
+///
+/// Deserialization fails if a monomial does not have one exponent for each
+/// variable of the ring, but otherwise cannot tell whether the polynomials
+/// were serialized in the same ring.
+pub struct Seed<'a, R, V, T> {
+    ring: &'a PolynomialRing<'a, R, V>,
+    phantom: PhantomData<T>,
+}
+
+impl<'de, 'a, R, V, K, P, S> DeserializeSeed<'de> for Seed<'a, R, V, Polynomial<'a, R, V, K, P, S>>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Deserialize<'de>,
+    P: Hash + PrimInt + Unsigned + Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    type Value = Polynomial<'a, R, V, K, P, S>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let terms = Vec::<(Monomial<P>, K)>::deserialize(deserializer)?;
+        Polynomial::from_terms(self.ring, terms).map_err(D::Error::custom)
+    }
+}
+
+impl<'de, 'a, R, V, T> DeserializeSeed<'de> for Seed<'a, R, V, Vec<T>>
+where
+    Seed<'a, R, V, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = Vec<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<T>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'a, R, V, T> Visitor<'de> for Seed<'a, R, V, Vec<T>>
+where
+    Seed<'a, R, V, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of polynomials")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<T>, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element_seed(self.ring.seed::<T>())? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
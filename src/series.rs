@@ -0,0 +1,476 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+use num::FromPrimitive;
+
+use crate::error::ChidogError;
+use crate::univariate::{DenseUnivariate, multiply};
+use crate::{FieldElement, Ring, RingElement, Semiring, SemiringElement};
+
+pub mod multivariate;
+
+/// A variable `my_ring: PowerSeriesRing<R, V>` represents the ring of formal
+/// power series `R[[x]]` in the variable `var` over a base ring `R`,
+/// truncated at the precision `prec`. Its elements are of type
+/// `PowerSeries<'_, R, V, K>`, with coefficients valued in `K`.
+///
+/// Every series carries its own precision, which is at most `prec`: the
+/// series `f + O(x^n)` is known only modulo `x^n`, and arithmetic tracks how
+/// many coefficients of a result are determined by the known coefficients of
+/// its operands.
+pub struct PowerSeriesRing<'a, R, V> {
+    pub var: V,
+    pub base: &'a R,
+    pub prec: usize,
+}
+
+/// A truncated power series `c_0 + c_1*x + ... + O(x^n)`, stored densely: the
+/// coefficient vector always has length equal to the precision `n`.
+pub struct PowerSeries<'a, R, V, K> {
+    pub elem_of: &'a PowerSeriesRing<'a, R, V>,
+    coeffs: Vec<K>,
+}
+
+impl<R, V, K: Clone> Clone for PowerSeries<'_, R, V, K> {
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            coeffs: self.coeffs.clone(),
+        }
+    }
+}
+
+/// The product of `a` and `b` modulo `x^n`, by a fast full product for
+/// long enough factors over the integers, the rationals, the prime fields
+/// and the floating-point numbers.
+pub(crate) fn mul_trunc<K: SemiringElement + Clone>(a: &[K], b: &[K], n: usize) -> Vec<K> {
+    if let Some(c) = multiply::mul_trunc_specialized(a, b, n) {
+        return c;
+    }
+    let mut c = vec![K::zero(); n];
+    for (i, x) in a.iter().enumerate().take(n) {
+        for (j, y) in b.iter().enumerate().take(n - i) {
+            c[i + j] += x.clone() * y.clone();
+        }
+    }
+    c
+}
+
+/// The inverse of `f` modulo `x^n` by Newton iteration `g <- g*(2 - f*g)`,
+/// which doubles the number of correct coefficients at each step.
+pub(crate) fn inv_trunc<K>(f: &[K], n: usize) -> Vec<K>
+where
+    K: RingElement + Clone + Div<Output = K>,
+{
+    let mut g = vec![K::one() / f[0].clone()];
+    let mut m = 1;
+    while m < n {
+        m = (2 * m).min(n);
+        let mut e = mul_trunc(f, &g, m);
+        for c in e.iter_mut() {
+            *c = K::zero() - c.clone();
+        }
+        e[0] += K::one() + K::one();
+        g = mul_trunc(&g, &e, m);
+    }
+    g.truncate(n);
+    g
+}
+
+fn derivative<K: SemiringElement + Clone + FromPrimitive>(f: &[K]) -> Vec<K> {
+    f.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| K::from_usize(i).unwrap() * c.clone())
+        .collect()
+}
+
+fn integral<K>(f: &[K]) -> Vec<K>
+where
+    K: SemiringElement + Clone + Div<Output = K> + FromPrimitive,
+{
+    std::iter::once(K::zero())
+        .chain(
+            f.iter()
+                .enumerate()
+                .map(|(i, c)| c.clone() / K::from_usize(i + 1).unwrap()),
+        )
+        .collect()
+}
+
+/// The logarithm of `f` modulo `x^n`, as the integral of `f'/f`. The constant
+/// term of `f` must be 1.
+fn log_trunc<K>(f: &[K], n: usize) -> Vec<K>
+where
+    K: RingElement + Clone + Div<Output = K> + FromPrimitive,
+{
+    if n == 0 {
+        return Vec::new();
+    }
+    let df = derivative(&f[..n]);
+    integral(&mul_trunc(&df, &inv_trunc(f, n - 1), n - 1))
+}
+
+/// The exponential of `f` modulo `x^n` by Newton iteration `g <- g*(1 + f -
+/// log g)`. The constant term of `f` must be zero.
+fn exp_trunc<K>(f: &[K], n: usize) -> Vec<K>
+where
+    K: RingElement + Clone + Div<Output = K> + FromPrimitive,
+{
+    let mut g = vec![K::one()];
+    let mut m = 1;
+    while m < n {
+        m = (2 * m).min(n);
+        g.resize(m, K::zero());
+        let mut e = log_trunc(&g, m);
+        for (c, a) in e.iter_mut().zip(f.iter()) {
+            *c = a.clone() - c.clone();
+        }
+        e[0] += K::one();
+        g = mul_trunc(&g, &e, m);
+    }
+    g.truncate(n);
+    g
+}
+
+impl<'a, R, V> PowerSeriesRing<'a, R, V> {
+    pub fn new(var: V, base: &'a R, prec: usize) -> Self {
+        Self { var, base, prec }
+    }
+
+    /// The series with the given coefficients, known to the precision of the
+    /// ring. Coefficients beyond the precision are discarded.
+    pub fn from_coeffs<K>(&'a self, mut coeffs: Vec<K>) -> PowerSeries<'a, R, V, K>
+    where
+        K: SemiringElement + Clone,
+    {
+        coeffs.resize(self.prec, K::zero());
+        PowerSeries {
+            elem_of: self,
+            coeffs,
+        }
+    }
+
+    pub fn zero<K: SemiringElement + Clone>(&'a self) -> PowerSeries<'a, R, V, K> {
+        self.from_coeffs(Vec::new())
+    }
+
+    pub fn one<K: SemiringElement + Clone>(&'a self) -> PowerSeries<'a, R, V, K> {
+        self.from_coeffs(vec![K::one()])
+    }
+
+    /// The variable `x` of the ring.
+    pub fn variable<K: SemiringElement + Clone>(&'a self) -> PowerSeries<'a, R, V, K> {
+        self.from_coeffs(vec![K::zero(), K::one()])
+    }
+}
+
+impl<'a, R, V, K> PowerSeries<'a, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    /// The number `n` of known coefficients, so that `self` is known modulo
+    /// `x^n`.
+    pub fn precision(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// The known coefficients of `self`, of which there are `precision()`.
+    pub fn coeffs(&self) -> &[K] {
+        &self.coeffs
+    }
+
+    /// The coefficient of `x^i`, or `None` if it is not known.
+    pub fn coeff(&self, i: usize) -> Option<&K> {
+        self.coeffs.get(i)
+    }
+
+    /// The exponent of the first nonzero coefficient, or `None` if every
+    /// known coefficient is zero.
+    pub fn valuation(&self) -> Option<usize> {
+        self.coeffs
+            .iter()
+            .position(|c| !self.elem_of.base.is_zero(c))
+    }
+
+    /// Whether `self` is zero to its precision.
+    pub fn is_zero(&self) -> bool {
+        self.valuation().is_none()
+    }
+
+    /// Lowers the precision of `self` to at most `n`.
+    pub fn truncate(mut self, n: usize) -> Self {
+        self.coeffs.truncate(n);
+        self
+    }
+
+    /// The series `f(g)`, where `f` is `self`.
+    ///
+    /// The constant term of `g` must be zero. If `g` has valuation `v`, the
+    /// result is known to precision `min(v * precision(f), precision(g))`.
+    pub fn compose(&self, g: &Self) -> Result<Self, ChidogError> {
+        let v = match g.valuation() {
+            Some(0) => {
+                return Err(ChidogError::Domain(
+                    "compose (the inner series must have zero constant term)".to_string(),
+                ));
+            }
+            Some(v) => v,
+            None => g.precision(),
+        };
+        let n = (v * self.precision()).min(g.precision());
+        let mut coeffs = vec![K::zero(); n];
+        for c in self.coeffs.iter().rev() {
+            coeffs = mul_trunc(&coeffs, &g.coeffs, n);
+            if n > 0 {
+                coeffs[0] += c.clone();
+            }
+        }
+        Ok(Self {
+            elem_of: self.elem_of,
+            coeffs,
+        })
+    }
+}
+
+impl<R, V, K> PowerSeries<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + Div<Output = K>,
+{
+    /// The multiplicative inverse of `self`, computed by Newton iteration. The
+    /// constant term must be invertible.
+    pub fn inv(&self) -> Result<Self, ChidogError> {
+        match self.coeffs.first() {
+            Some(c) if !self.elem_of.base.is_zero(c) => Ok(Self {
+                elem_of: self.elem_of,
+                coeffs: inv_trunc(&self.coeffs, self.precision()),
+            }),
+            _ => Err(ChidogError::DivisionByZero),
+        }
+    }
+
+    /// The quotient `self / rhs`, where the constant term of `rhs` must be
+    /// invertible.
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, ChidogError> {
+        Ok(self.clone() * rhs.inv()?)
+    }
+}
+
+impl<R, V, K> PowerSeries<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + Div<Output = K> + FromPrimitive,
+{
+    /// The formal derivative of `self`, which loses one coefficient of
+    /// precision.
+    pub fn derivative(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            coeffs: derivative(&self.coeffs),
+        }
+    }
+
+    /// The formal antiderivative of `self` with zero constant term, which
+    /// gains one coefficient of precision, up to the precision of the ring.
+    pub fn integral(&self) -> Self {
+        let mut coeffs = integral(&self.coeffs);
+        coeffs.truncate(self.elem_of.prec);
+        Self {
+            elem_of: self.elem_of,
+            coeffs,
+        }
+    }
+
+    /// The exponential of `self`, whose constant term must be zero.
+    pub fn exp(&self) -> Result<Self, ChidogError> {
+        if !self
+            .coeffs
+            .first()
+            .is_none_or(|c| self.elem_of.base.is_zero(c))
+        {
+            return Err(ChidogError::Domain(
+                "exp (the constant term must be zero)".to_string(),
+            ));
+        }
+        Ok(Self {
+            elem_of: self.elem_of,
+            coeffs: exp_trunc(&self.coeffs, self.precision()),
+        })
+    }
+
+    /// The logarithm of `self`, whose constant term must be one.
+    pub fn log(&self) -> Result<Self, ChidogError> {
+        if !self
+            .coeffs
+            .first()
+            .is_none_or(|c| self.elem_of.base.is_zero(&(c.clone() - K::one())))
+        {
+            return Err(ChidogError::Domain(
+                "log (the constant term must be one)".to_string(),
+            ));
+        }
+        Ok(Self {
+            elem_of: self.elem_of,
+            coeffs: log_trunc(&self.coeffs, self.precision()),
+        })
+    }
+}
+
+impl<R, V, K> PowerSeries<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: FieldElement + Clone,
+{
+    /// The Padé approximant of type `[m/n]` of `self`, a pair of polynomials
+    /// `(p, q)` with `deg(p) <= m`, `deg(q) <= n` and `q(0) = 1` such that
+    /// `p/q` agrees with `self` modulo `x^(m + n + 1)`.
+    ///
+    /// Fails if fewer than `m + n + 1` coefficients are known, or if no such
+    /// pair exists. See [`DenseUnivariate::pade`].
+    pub fn pade(
+        &self,
+        m: usize,
+        n: usize,
+    ) -> Result<(DenseUnivariate<K>, DenseUnivariate<K>), ChidogError> {
+        if self.precision() < m + n + 1 {
+            return Err(ChidogError::PrecisionLoss {
+                required: (m + n + 1) as i64,
+                available: self.precision() as i64,
+            });
+        }
+        DenseUnivariate::new(self.coeffs.clone()).pade(m, n)
+    }
+}
+
+impl<R, V, K> Add for PowerSeries<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<R, V, K> AddAssign for PowerSeries<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.coeffs.truncate(rhs.precision());
+        for (a, b) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            *a += b;
+        }
+    }
+}
+
+impl<R, V, K> Sub for PowerSeries<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<R, V, K> SubAssign for PowerSeries<'_, R, V, K>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.coeffs.truncate(rhs.precision());
+        for (a, b) in self.coeffs.iter_mut().zip(rhs.coeffs) {
+            *a -= b;
+        }
+    }
+}
+
+impl<R, V, K> Mul for PowerSeries<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    type Output = Self;
+
+    /// The product of `f + O(x^n)` and `g + O(x^m)` is known modulo
+    /// `x^min(n + v(g), m + v(f))`, where `v` is the valuation.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let v1 = self.valuation().unwrap_or(self.precision());
+        let v2 = rhs.valuation().unwrap_or(rhs.precision());
+        let n = (self.precision() + v2)
+            .min(rhs.precision() + v1)
+            .min(self.elem_of.prec);
+        Self {
+            elem_of: self.elem_of,
+            coeffs: mul_trunc(&self.coeffs, &rhs.coeffs, n),
+        }
+    }
+}
+
+impl<R, V, K> MulAssign for PowerSeries<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            coeffs: std::mem::take(&mut self.coeffs),
+        };
+        *self = lhs * rhs;
+    }
+}
+
+impl<R, V, K> Display for PowerSeries<'_, R, V, K>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + Display + PartialEq,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let var = &self.elem_of.var;
+        let mut first = true;
+        for (i, c) in self.coeffs.iter().enumerate() {
+            if self.elem_of.base.is_zero(c) {
+                continue;
+            }
+            if i == 0 || !c.is_one() {
+                if first {
+                    write!(f, "{c}")?;
+                } else {
+                    write!(f, "{c:+}")?;
+                }
+                if i > 0 {
+                    write!(f, "*")?;
+                }
+            } else if !first {
+                write!(f, "+")?;
+            }
+            match i {
+                0 => {}
+                1 => write!(f, "{var}")?,
+                _ => write!(f, "{var}^{i}")?,
+            }
+            first = false;
+        }
+        if !first {
+            write!(f, "+")?;
+        }
+        match self.precision() {
+            0 => write!(f, "O(1)"),
+            1 => write!(f, "O({var})"),
+            n => write!(f, "O({var}^{n})"),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::zip;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+use num::{PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::{
+    Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring, SemiringElement, TermHasher,
+};
+
+/// A variable `my_ring: MultivariateSeriesRing<R, V>` represents the ring of
+/// power series in the variables of the polynomial ring `poly`, truncated at
+/// total degree `degree`: that is, the quotient of `poly` by the ideal
+/// generated by all monomials of degree `degree + 1`. Its elements are of
+/// type `MultivariateSeries<'_, R, V, K, P>`.
+///
+/// An element of this ring is the jet of order `degree` of a function at the
+/// origin, and [`MultivariateSeriesRing::taylor`] computes the jet of a
+/// polynomial at any point.
+pub struct MultivariateSeriesRing<'a, R, V> {
+    pub poly: &'a PolynomialRing<'a, R, V>,
+    pub degree: usize,
+}
+
+/// A truncated multivariate power series, stored as a hash map associating
+/// to each monomial of degree at most the cutoff its nonzero coefficient, as
+/// for [`Polynomial`].
+pub struct MultivariateSeries<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    pub elem_of: &'a MultivariateSeriesRing<'a, R, V>,
+    terms: HashMap<Monomial<P>, K, TermHasher>,
+}
+
+impl<R, V, K, P> Clone for MultivariateSeries<'_, R, V, K, P>
+where
+    K: Clone,
+    P: Hash + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+fn degree<P: PrimInt>(m: &Monomial<P>) -> usize {
+    m.powers.iter().map(|p| p.to_usize().unwrap()).sum()
+}
+
+impl<'a, R, V> MultivariateSeriesRing<'a, R, V> {
+    pub fn new(poly: &'a PolynomialRing<'a, R, V>, degree: usize) -> Self {
+        Self { poly, degree }
+    }
+
+    pub fn zero<K, P>(&'a self) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        P: Hash,
+    {
+        MultivariateSeries {
+            elem_of: self,
+            terms: HashMap::default(),
+        }
+    }
+
+    /// The term `c * m`, or zero if `m` has degree above the cutoff.
+    pub fn monomial<K, P>(&'a self, m: Monomial<P>, c: K) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut f = self.zero();
+        f.add_term(m, c);
+        f
+    }
+
+    pub fn one<K, P>(&'a self) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        self.scalar(K::one())
+    }
+
+    /// The `i`-th variable of the ring.
+    pub fn var<K, P>(&'a self, i: usize) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut powers = smallvec![P::zero(); self.poly.vars.len()];
+        powers[i] = P::one();
+        self.monomial(Monomial { powers }, K::one())
+    }
+
+    /// The polynomial `f`, with its terms above the cutoff discarded.
+    pub fn from_polynomial<K, P>(
+        &'a self,
+        f: &Polynomial<'_, R, V, K, P>,
+    ) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        K: Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        MultivariateSeries {
+            elem_of: self,
+            terms: f
+                .terms
+                .iter()
+                .filter(|(m, _)| degree(m) <= self.degree)
+                .map(|(m, c)| (m.clone(), c.clone()))
+                .collect(),
+        }
+    }
+
+    /// The Taylor expansion of `f` at `point`, truncated at the cutoff: the
+    /// series `g` in the variables `y` such that `f(point + y) = g(y)` up to
+    /// terms of higher degree.
+    pub fn taylor<K, P>(
+        &'a self,
+        f: &Polynomial<'_, R, V, K, P>,
+        point: &[K],
+    ) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let shifted: Vec<_> = point
+            .iter()
+            .enumerate()
+            .map(|(i, a)| self.scalar(a.clone()) + self.var(i))
+            .collect();
+        let mut g = self.zero();
+        for (m, c) in f.terms.iter() {
+            let mut term = self.scalar(c.clone());
+            for (x, p) in zip(shifted.iter(), m.powers.iter()) {
+                for _ in 0..p.to_usize().unwrap() {
+                    term *= x.clone();
+                }
+            }
+            g += term;
+        }
+        g
+    }
+
+    fn scalar<K, P>(&'a self, c: K) -> MultivariateSeries<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let powers = smallvec![P::zero(); self.poly.vars.len()];
+        self.monomial(Monomial { powers }, c)
+    }
+}
+
+impl<'a, R, V, K, P> MultivariateSeries<'a, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// Adds `c * m` to `self`, unless `m` lies above the cutoff.
+    fn add_term(&mut self, m: Monomial<P>, c: K) {
+        if degree(&m) > self.elem_of.degree {
+            return;
+        }
+        let base = self.elem_of.poly.base;
+        match self.terms.entry(m) {
+            Entry::Occupied(mut entry) => {
+                if base.add_into(entry.get_mut(), c) {
+                    entry.remove();
+                }
+            }
+            Entry::Vacant(entry) => {
+                if !base.is_zero(&c) {
+                    entry.insert(c);
+                }
+            }
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The coefficient of `m`, or `None` if it is zero.
+    pub fn coeff(&self, m: &Monomial<P>) -> Option<&K> {
+        self.terms.get(m)
+    }
+
+    pub fn terms(&self) -> impl Iterator<Item = (&Monomial<P>, &K)> + '_ {
+        self.terms.iter()
+    }
+
+    /// The least degree of a term of `self`, or `None` for zero.
+    pub fn order(&self) -> Option<usize> {
+        self.terms.keys().map(degree).min()
+    }
+
+    /// The sum of the terms of `self` of degree exactly `d`.
+    pub fn homogeneous_part(&self, d: usize) -> Self {
+        Self {
+            elem_of: self.elem_of,
+            terms: self
+                .terms
+                .iter()
+                .filter(|(m, _)| degree(m) == d)
+                .map(|(m, c)| (m.clone(), c.clone()))
+                .collect(),
+        }
+    }
+
+    /// `self` as a polynomial of degree at most the cutoff.
+    pub fn to_polynomial(&self) -> Polynomial<'a, R, V, K, P> {
+        Polynomial {
+            elem_of: self.elem_of.poly,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+impl<R, V, K, P> MultivariateSeries<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + Div<Output = K>,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The multiplicative inverse of `self`, whose constant term must be
+    /// invertible.
+    ///
+    /// Writing `self = c*(1 - h)` with `h` of order at least 1, the inverse is
+    /// `c^-1 * (1 + h + h^2 + ... + h^d)`, since `h^(d+1)` lies above the
+    /// cutoff `d`.
+    pub fn inv(&self) -> Result<Self, ChidogError> {
+        let ring = self.elem_of;
+        let powers = smallvec![P::zero(); ring.poly.vars.len()];
+        let c = self
+            .terms
+            .get(&Monomial { powers })
+            .ok_or(ChidogError::DivisionByZero)?;
+        let c_inv = K::one() / c.clone();
+        let h = ring.one() - self.clone() * ring.scalar(c_inv.clone());
+        let mut sum = ring.one();
+        for _ in 0..ring.degree {
+            sum = ring.one() + h.clone() * sum;
+        }
+        Ok(sum * ring.scalar(c_inv))
+    }
+}
+
+impl<R, V, K, P> Add for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<R, V, K, P> AddAssign for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        for (m, c) in rhs.terms {
+            self.add_term(m, c);
+        }
+    }
+}
+
+impl<R, V, K, P> Sub for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<R, V, K, P> SubAssign for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        let base = self.elem_of.poly.base;
+        for (m, c) in rhs.terms {
+            match self.terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if base.sub_from(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c);
+                }
+            }
+        }
+    }
+}
+
+impl<R, V, K, P> Mul for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    /// The product, computing only the terms at or below the cutoff.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let cutoff = self.elem_of.degree;
+        let rhs_terms: Vec<_> = rhs.terms.iter().map(|(m, c)| (m, c, degree(m))).collect();
+        let mut product = self.elem_of.zero();
+        for (m1, c1) in self.terms.iter() {
+            let d1 = degree(m1);
+            for (m2, c2, d2) in rhs_terms.iter() {
+                if d1 + d2 > cutoff {
+                    continue;
+                }
+                let powers = zip(m1.powers.iter(), m2.powers.iter())
+                    .map(|(a, b)| *a + *b)
+                    .collect();
+                product.add_term(Monomial { powers }, c1.clone() * (*c2).clone());
+            }
+        }
+        product
+    }
+}
+
+impl<R, V, K, P> MulAssign for MultivariateSeries<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = Self {
+            elem_of: self.elem_of,
+            terms: std::mem::take(&mut self.terms),
+        };
+        *self = lhs * rhs;
+    }
+}
+
+/// Displays the terms in order of increasing degree, followed by the
+/// truncation `O(x, y, ...)^(d+1)`.
+impl<R, V, K, P> Display for MultivariateSeries<'_, R, V, K, P>
+where
+    K: Display + SemiringElement + PartialEq,
+    P: Hash + PrimInt + Unsigned + Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ring = self.elem_of.poly;
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_by_key(|(m, _)| (degree(m), std::cmp::Reverse(m.powers.clone())));
+        for (i, (m, c)) in terms.into_iter().enumerate() {
+            if !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            ring.fmt_monomial(f, m)?;
+        }
+        if !self.terms.is_empty() {
+            write!(f, "+")?;
+        }
+        write!(f, "O(")?;
+        for (i, v) in ring.vars.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{v}")?;
+        }
+        write!(f, ")^{}", self.elem_of.degree + 1)
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, One, PrimInt, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::expr::Expr;
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// Writes `name` as an SMT-LIB symbol, quoting it unless it is a simple
+/// symbol.
+pub(crate) fn symbol(name: &str) -> String {
+    let simple = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c));
+    if simple {
+        name.to_string()
+    } else {
+        format!("|{name}|")
+    }
+}
+
+/// An S-expression, as read from solver output or written by
+/// [`to_sexpr`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+/// The S-expressions of `input`, in which atoms may be quoted as `|...|`
+/// and `;` starts a comment.
+pub(crate) fn parse_sexprs(input: &str) -> Result<Vec<SExpr>, ChidogError> {
+    let mut stack: Vec<Vec<SExpr>> = vec![Vec::new()];
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => stack.push(Vec::new()),
+            ')' => {
+                let list = stack
+                    .pop()
+                    .filter(|_| !stack.is_empty())
+                    .ok_or_else(|| ChidogError::Parse("unbalanced `)`".to_string()))?;
+                stack.last_mut().unwrap().push(SExpr::List(list));
+            }
+            ';' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '|' => {
+                let mut atom = String::new();
+                loop {
+                    match chars.next() {
+                        Some('|') => break,
+                        Some(c) => atom.push(c),
+                        None => return Err(ChidogError::Parse("unterminated `|`".to_string())),
+                    }
+                }
+                stack.last_mut().unwrap().push(SExpr::Atom(atom));
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut atom = String::from(c);
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && !"()|;".contains(c)) {
+                    atom.push(c);
+                }
+                stack.last_mut().unwrap().push(SExpr::Atom(atom));
+            }
+        }
+    }
+    match stack.len() {
+        1 => Ok(stack.pop().unwrap()),
+        _ => Err(ChidogError::Parse("unbalanced `(`".to_string())),
+    }
+}
+
+/// The rational number written as an integer `n`, a fraction `n/d` or a
+/// decimal `n.f`, each possibly negative.
+pub(crate) fn number(a: &str) -> Option<BigRational> {
+    if let Some((n, d)) = a.split_once('/') {
+        let d: BigInt = d.parse().ok()?;
+        return (!d.is_zero()).then_some(BigRational::new(n.parse().ok()?, d));
+    }
+    let (int, frac) = a.split_once('.').unwrap_or((a, ""));
+    if !frac.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let numer: BigInt = format!("{int}{frac}").parse().ok()?;
+    Some(BigRational::new(
+        numer,
+        num::pow(BigInt::from(10), frac.len()),
+    ))
+}
+
+/// The expression of `e`, whose atoms are the variables `names` or numbers.
+fn to_expr(e: &SExpr, names: &HashMap<String, usize>) -> Result<Expr<BigRational>, ChidogError> {
+    let unsupported = || ChidogError::Parse(format!("unsupported expression `{e:?}`"));
+    match e {
+        SExpr::Atom(a) => match (names.get(a), number(a)) {
+            (Some(&i), _) => Ok(Expr::Var(i)),
+            (None, Some(c)) => Ok(Expr::Constant(c)),
+            (None, None) => Err(ChidogError::InvalidInput(format!("unknown variable `{a}`"))),
+        },
+        SExpr::List(items) => {
+            let (SExpr::Atom(op), args) = items.split_first().ok_or_else(unsupported)? else {
+                return Err(unsupported());
+            };
+            let negate = |e| Expr::Product(vec![Expr::Constant(-BigRational::one()), e]);
+            if op == "^" {
+                let [base, SExpr::Atom(e)] = args else {
+                    return Err(unsupported());
+                };
+                let e = e.parse().map_err(|_| unsupported())?;
+                return Ok(to_expr(base, names)?.pow(e));
+            }
+            let mut args = args
+                .iter()
+                .map(|a| to_expr(a, names))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (op.as_str(), args.len()) {
+                ("+", _) => Ok(Expr::Sum(args)),
+                ("*", _) => Ok(Expr::Product(args)),
+                ("-", 1) => Ok(negate(args.pop().unwrap())),
+                ("-", 2..) => {
+                    let rest = args.split_off(1).into_iter().map(negate);
+                    Ok(Expr::Sum(args.into_iter().chain(rest).collect()))
+                }
+                ("/", 2) => match args.pop().unwrap() {
+                    Expr::Constant(d) if !d.is_zero() => Ok(Expr::Product(vec![
+                        args.pop().unwrap(),
+                        Expr::Constant(d.recip()),
+                    ])),
+                    _ => Err(unsupported()),
+                },
+                _ => Err(unsupported()),
+            }
+        }
+    }
+}
+
+/// `f` as an S-expression in prefix form, such as
+/// `(+ (* -1/2 (^ x 2) y) (* 3 z) 1)`, with its terms in decreasing
+/// lexicographic order.
+///
+/// Variables are written by name, quoted as `|x y|` unless they are simple
+/// symbols, and coefficients as integers or fractions `n/d`.
+pub fn to_sexpr<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let mut terms: Vec<_> = f.terms.iter().collect();
+    terms.sort_unstable_by(|(a, _), (b, _)| b.powers.cmp(&a.powers));
+    let mut terms: Vec<String> = terms
+        .into_iter()
+        .map(|(m, c)| {
+            let c: BigRational = c.clone().into();
+            let mut factors = Vec::new();
+            if !c.is_one() || m.powers.iter().all(|p| p.is_zero()) {
+                factors.push(c.to_string());
+            }
+            for (var, p) in f.elem_of.vars.iter().zip(m.powers.iter()) {
+                let var = symbol(&var.to_string());
+                if p.is_one() {
+                    factors.push(var);
+                } else if !p.is_zero() {
+                    factors.push(format!("(^ {var} {p})", p = p.to_u64().unwrap()));
+                }
+            }
+            match factors.len() {
+                1 => factors.pop().unwrap(),
+                _ => format!("(* {})", factors.join(" ")),
+            }
+        })
+        .collect();
+    match terms.len() {
+        0 => "0".to_string(),
+        1 => terms.pop().unwrap(),
+        _ => format!("(+ {})", terms.join(" ")),
+    }
+}
+
+/// The polynomial in `ring` of the S-expression `input`, as written by
+/// [`to_sexpr`], expanded.
+///
+/// Besides the `+`, `*` and `^` of [`to_sexpr`], `input` may negate and
+/// subtract with `-` and divide by constants with `/`, and its coefficients
+/// may be decimals. An atom is read as a variable of `ring` if it names one,
+/// and as a number otherwise.
+pub fn from_sexpr<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Polynomial<'a, R, V, BigRational, P>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let names = ring
+        .vars
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.to_string(), i))
+        .collect();
+    match parse_sexprs(input)?.as_slice() {
+        [e] => to_expr(e, &names)?.to_polynomial(ring),
+        _ => Err(ChidogError::Parse("expected one S-expression".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use super::*;
+    use crate::AlreadyRing;
+
+    type Poly<'a> = Polynomial<'a, AlreadyRing<BigRational>, String, BigRational, u32>;
+
+    #[test]
+    fn round_trip_matches_display_parser() {
+        let base = AlreadyRing {
+            phantom: PhantomData::<BigRational>,
+        };
+        let vars = ["x", "y", "z"].map(String::from).to_vec();
+        let ring = PolynomialRing::new(vars, &base);
+        for input in [
+            "x^2*y - 3/4*z + 1",
+            "-x*y^3 - 2*z^5 + 7",
+            "-1/3*x^4*y*z^2 + 5/2*y - z",
+            "x - y",
+            "-5/7",
+            "42",
+            "0",
+        ] {
+            let f = Poly::from_sympy(&ring, input).unwrap();
+            let expected = Poly::from_sympy(&ring, &f.to_string()).unwrap();
+            let g: Poly = from_sexpr(&ring, &to_sexpr(&f)).unwrap();
+            assert_eq!(g.terms, expected.terms, "{input}");
+            assert_eq!(g.terms, f.terms, "{input}");
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt::{self, Display};
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigRational, PrimInt, Unsigned};
+
+use crate::error::ChidogError;
+use crate::infix::{from_infix, split_top_level, to_infix};
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+/// The global monomial orderings of Singular, which are those of
+/// [`order`](crate::order).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RingOrdering {
+    /// `lp`, the order [`Lex`](crate::order::Lex).
+    Lex,
+    /// `Dp`, the order [`GrLex`](crate::order::GrLex).
+    GrLex,
+    /// `dp`, the order [`GRevLex`](crate::order::GRevLex).
+    GRevLex,
+}
+
+impl RingOrdering {
+    /// The name of the ordering in Singular.
+    pub fn name(self) -> &'static str {
+        match self {
+            RingOrdering::Lex => "lp",
+            RingOrdering::GrLex => "Dp",
+            RingOrdering::GRevLex => "dp",
+        }
+    }
+}
+
+/// A Singular ring declaration, such as `ring r = 0,(x,y,z),dp;`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingDeclaration {
+    pub name: String,
+    /// The characteristic of the coefficient field, `0` for the rationals
+    /// or a prime.
+    pub characteristic: u64,
+    pub vars: Vec<String>,
+    pub ordering: RingOrdering,
+}
+
+impl Display for RingDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ring {} = {},({}),{};",
+            self.name,
+            self.characteristic,
+            self.vars.join(","),
+            self.ordering.name()
+        )
+    }
+}
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+/// The statements of the Singular script `input`, without comments, each
+/// trimmed and without its `;`.
+fn statements(input: &str) -> Vec<String> {
+    let mut code = String::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |i| &after[i..]);
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map_or("", |i| &after[i + 2..]);
+        } else {
+            let c = rest.chars().next().unwrap();
+            code.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    code.split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// `s` without one pair of enclosing parentheses, if it has them.
+fn unparenthesize(s: &str) -> &str {
+    s.strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(s)
+        .trim()
+}
+
+/// The statement `keyword name = value`, split, if `statement` is one.
+fn definition<'s>(statement: &'s str, keyword: &str) -> Option<(&'s str, &'s str)> {
+    let rest = statement.strip_prefix(keyword)?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (name, value) = rest.split_once('=')?;
+    Some((name.trim(), value.trim()))
+}
+
+/// The variables declared by `var`, which is a name or a range `x(1..3)`
+/// of indexed names `x(1)`, `x(2)`, `x(3)`.
+fn expand_var(var: &str) -> Result<Vec<String>, ChidogError> {
+    let range = var
+        .strip_suffix(')')
+        .and_then(|v| v.split_once('('))
+        .and_then(|(name, range)| Some((name, range.split_once("..")?)));
+    let Some((name, (low, high))) = range else {
+        return Ok(vec![var.to_string()]);
+    };
+    let bound = |b: &str| {
+        b.trim()
+            .parse::<u64>()
+            .map_err(|_| parse_error(format!("invalid range `{var}`")))
+    };
+    Ok((bound(low)?..=bound(high)?)
+        .map(|i| format!("{name}({i})"))
+        .collect())
+}
+
+fn parse_ordering(ordering: &str, vars: usize) -> Result<RingOrdering, ChidogError> {
+    let blocks: Vec<&str> = split_top_level(unparenthesize(ordering))
+        .into_iter()
+        .filter(|b| *b != "c" && *b != "C")
+        .collect();
+    let unsupported = || ChidogError::InvalidInput(format!("unsupported ordering `{ordering}`"));
+    let [block] = blocks.as_slice() else {
+        return Err(unsupported());
+    };
+    let (name, size) = match block.split_once('(') {
+        Some((name, size)) => (name, size.strip_suffix(')').map(str::trim)),
+        None => (*block, None),
+    };
+    if size.is_some_and(|size| size != vars.to_string()) {
+        return Err(unsupported());
+    }
+    match name {
+        "lp" => Ok(RingOrdering::Lex),
+        "Dp" => Ok(RingOrdering::GrLex),
+        "dp" => Ok(RingOrdering::GRevLex),
+        _ => Err(unsupported()),
+    }
+}
+
+/// The declaration of the first ring of the Singular script `input`, such as
+/// `ring r = 0,(x,y,z),dp;`.
+///
+/// The coefficient field is given by its characteristic, `0` or `QQ` for
+/// the rationals and a prime `p` for the field of `p` elements, and the
+/// variables may be ranges such as `x(1..3)`. Orderings other than `lp`,
+/// `Dp` and `dp`, possibly with a module component `c` or `C`, and fields
+/// with parameters are not supported, and give an `InvalidInput` error.
+pub fn parse_ring(input: &str) -> Result<RingDeclaration, ChidogError> {
+    let statements = statements(input);
+    let (name, value) = statements
+        .iter()
+        .find_map(|s| definition(s, "ring"))
+        .ok_or_else(|| parse_error("no ring declaration"))?;
+    let [field, vars, ordering] = split_top_level(value)[..] else {
+        return Err(parse_error(format!("invalid ring declaration `{value}`")));
+    };
+    let characteristic = match field {
+        "QQ" => 0,
+        _ => field
+            .parse()
+            .map_err(|_| ChidogError::InvalidInput(format!("unsupported field `{field}`")))?,
+    };
+    let mut expanded = Vec::new();
+    for var in split_top_level(unparenthesize(vars)) {
+        expanded.extend(expand_var(var)?);
+    }
+    Ok(RingDeclaration {
+        name: name.to_string(),
+        characteristic,
+        ordering: parse_ordering(ordering, expanded.len())?,
+        vars: expanded,
+    })
+}
+
+/// `f` in Singular syntax, such as `-1/2*x^2*y+3*z-1`, with its terms in
+/// decreasing lexicographic order.
+pub fn to_singular<R, V, K, P, S>(f: &Polynomial<'_, R, V, K, P, S>) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    to_infix(f, "^")
+}
+
+/// The Singular statement defining the ideal `name` generated by
+/// `generators`, such as `ideal I = x^2+y,x*y-1;`.
+pub fn ideal_to_singular<R, V, K, P, S>(
+    name: &str,
+    generators: &[Polynomial<'_, R, V, K, P, S>],
+) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    let generators: Vec<String> = generators.iter().map(to_singular).collect();
+    match generators.is_empty() {
+        true => format!("ideal {name} = 0;"),
+        false => format!("ideal {name} = {};", generators.join(",")),
+    }
+}
+
+/// The polynomial in `ring` written as `input` in Singular syntax, expanded.
+///
+/// `input` is read both in the long notation of [`to_singular`] and in the
+/// short notation of Singular's output, such as `3/4x2y-z3`, in which the
+/// digits after a variable are its exponent and juxtaposition multiplies.
+/// Coefficients are read as rationals, even in rings of positive
+/// characteristic.
+pub fn from_singular<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Polynomial<'a, R, V, BigRational, P>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    from_infix(ring, input, true)
+}
+
+/// A named list of polynomials in a ring.
+type Named<'a, R, V, P> = (String, Vec<Polynomial<'a, R, V, BigRational, P>>);
+
+/// The ideals defined by the `ideal` statements of the Singular script
+/// `input`, by name and in order, with their generators in `ring`, which is
+/// usually made from the variables of [`parse_ring`]. The polynomial of a
+/// `poly` statement is read as the ideal it generates.
+///
+/// Other statements are ignored.
+pub fn parse_ideals<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    input: &str,
+) -> Result<Vec<Named<'a, R, V, P>>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut ideals = Vec::new();
+    for statement in statements(input) {
+        let Some((name, value)) =
+            definition(&statement, "ideal").or_else(|| definition(&statement, "poly"))
+        else {
+            continue;
+        };
+        let generators = split_top_level(value)
+            .into_iter()
+            .map(|g| from_singular(ring, g))
+            .collect::<Result<_, _>>()?;
+        ideals.push((name.to_string(), generators));
+    }
+    Ok(ideals)
+}
+
+/// The polynomials printed by Singular in `output`, in `ring`.
+///
+/// Ideals are printed one generator per line, as `I[1]=x2+y`, and the
+/// generators of all such lines are returned in order. If there are none,
+/// `output` is read as a single printed polynomial. Lines starting with
+/// `//` are ignored.
+pub fn parse_output<'a, R, V, P>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    output: &str,
+) -> Result<Vec<Polynomial<'a, R, V, BigRational, P>>, ChidogError>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("//"))
+        .collect();
+    let generators: Vec<&str> = lines
+        .iter()
+        .filter_map(|line| {
+            let (lhs, rhs) = line.split_once('=')?;
+            let index = lhs.strip_suffix(']')?.split_once('[')?.1;
+            index.parse::<usize>().is_ok().then_some(rhs)
+        })
+        .collect();
+    match (generators.is_empty(), lines.as_slice()) {
+        (false, _) => generators
+            .into_iter()
+            .map(|g| from_singular(ring, g))
+            .collect(),
+        (true, []) => Err(parse_error("no output")),
+        (true, lines) => Ok(vec![from_singular(ring, &lines.join(" "))?]),
+    }
+}
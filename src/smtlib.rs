@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+
+use num::{BigInt, BigRational, Integer, One, PrimInt, Signed, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::sexpr::{SExpr, parse_sexprs, symbol};
+use crate::{Monomial, Polynomial};
+
+/// The relation of a [`Constraint`] `lhs ~ 0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// The sort of the variables of an SMT-LIB script: integers, giving the
+/// logic `QF_NIA`, or reals, giving `QF_NRA`. Problems over ℚ are exported
+/// over the reals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sort {
+    Int,
+    Real,
+}
+
+/// A polynomial constraint `lhs ~ 0`.
+pub struct Constraint<'a, R, V, K, P>
+where
+    P: Hash,
+{
+    pub lhs: Polynomial<'a, R, V, K, P>,
+    pub relation: Relation,
+}
+
+impl Relation {
+    fn symbol(self) -> &'static str {
+        match self {
+            Relation::Eq => "=",
+            Relation::Ne => "distinct",
+            Relation::Lt => "<",
+            Relation::Le => "<=",
+            Relation::Gt => ">",
+            Relation::Ge => ">=",
+        }
+    }
+}
+
+impl Sort {
+    fn name(self) -> &'static str {
+        match self {
+            Sort::Int => "Int",
+            Sort::Real => "Real",
+        }
+    }
+
+    fn logic(self) -> &'static str {
+        match self {
+            Sort::Int => "QF_NIA",
+            Sort::Real => "QF_NRA",
+        }
+    }
+
+    fn numeral(self, n: &BigInt) -> String {
+        let digits = match self {
+            Sort::Int => n.abs().to_string(),
+            Sort::Real => format!("{}.0", n.abs()),
+        };
+        if n.is_negative() {
+            format!("(- {digits})")
+        } else {
+            digits
+        }
+    }
+}
+
+/// Serializes `constraints` as an SMT-LIB 2 script asserting all of them and
+/// requesting a model.
+///
+/// Constraints over the integers are first multiplied by the least common
+/// multiple of the denominators of their coefficients, which is positive and
+/// so preserves the relation.
+pub fn to_smtlib<R, V, K, P>(constraints: &[Constraint<'_, R, V, K, P>], sort: Sort) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+{
+    let mut script = String::new();
+    writeln!(script, "(set-logic {})", sort.logic()).unwrap();
+    if let Some(c) = constraints.first() {
+        for v in c.lhs.elem_of.vars.iter() {
+            writeln!(
+                script,
+                "(declare-fun {} () {})",
+                symbol(&v.to_string()),
+                sort.name()
+            )
+            .unwrap();
+        }
+    }
+    for c in constraints {
+        let lhs = polynomial_term(&c.lhs, sort);
+        let zero = sort.numeral(&BigInt::zero());
+        writeln!(script, "(assert ({} {lhs} {zero}))", c.relation.symbol()).unwrap();
+    }
+    writeln!(script, "(check-sat)").unwrap();
+    writeln!(script, "(get-model)").unwrap();
+    script
+}
+
+fn polynomial_term<R, V, K, P>(f: &Polynomial<'_, R, V, K, P>, sort: Sort) -> String
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+{
+    let coeffs: Vec<(&Monomial<P>, BigRational)> =
+        f.terms.iter().map(|(m, c)| (m, c.clone().into())).collect();
+    let scale = match sort {
+        Sort::Int => coeffs
+            .iter()
+            .fold(BigInt::one(), |l, (_, c)| l.lcm(c.denom())),
+        Sort::Real => BigInt::one(),
+    };
+    let terms: Vec<String> = coeffs
+        .into_iter()
+        .map(|(m, c)| {
+            let mut factors = Vec::new();
+            let c = c * BigRational::from_integer(scale.clone());
+            if !c.is_one() || m.powers.iter().all(|p| p.is_zero()) {
+                factors.push(if c.is_integer() {
+                    sort.numeral(c.numer())
+                } else {
+                    format!(
+                        "(/ {} {})",
+                        sort.numeral(c.numer()),
+                        sort.numeral(c.denom())
+                    )
+                });
+            }
+            for (var, p) in f.elem_of.vars.iter().zip(m.powers.iter()) {
+                for _ in 0..p.to_usize().unwrap() {
+                    factors.push(symbol(&var.to_string()));
+                }
+            }
+            if factors.len() == 1 {
+                factors.pop().unwrap()
+            } else {
+                format!("(* {})", factors.join(" "))
+            }
+        })
+        .collect();
+    match terms.len() {
+        0 => sort.numeral(&BigInt::zero()),
+        1 => terms.into_iter().next().unwrap(),
+        _ => format!("(+ {})", terms.join(" ")),
+    }
+}
+
+/// Evaluates a constant SMT-LIB numeric term: a numeral, a decimal, or a
+/// negation, sum, product, or quotient of such terms.
+fn eval_value(e: &SExpr) -> Result<BigRational, ChidogError> {
+    let unsupported = || ChidogError::Parse(format!("unsupported model value `{e:?}`"));
+    match e {
+        SExpr::Atom(a) => parse_decimal(a).ok_or_else(unsupported),
+        SExpr::List(items) => {
+            let (SExpr::Atom(op), args) = items.split_first().ok_or_else(unsupported)? else {
+                return Err(unsupported());
+            };
+            let args = args.iter().map(eval_value).collect::<Result<Vec<_>, _>>()?;
+            match (op.as_str(), args.as_slice()) {
+                ("-", [x]) => Ok(-x),
+                ("-", [x, rest @ ..]) => Ok(rest.iter().fold(x.clone(), |a, b| a - b)),
+                ("+", _) => Ok(args.iter().sum()),
+                ("*", _) => Ok(args.iter().product()),
+                ("/", [x, y]) if !y.is_zero() => Ok(x / y),
+                _ => Err(unsupported()),
+            }
+        }
+    }
+}
+
+fn parse_decimal(a: &str) -> Option<BigRational> {
+    let (int, frac) = a.split_once('.').unwrap_or((a, ""));
+    if int.is_empty() || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let numer: BigInt = format!("{int}{frac}").parse().ok()?;
+    Some(BigRational::new(
+        numer,
+        num::pow(BigInt::from(10), frac.len()),
+    ))
+}
+
+/// Reads the values of variables from a model printed by an SMT solver in
+/// response to `(get-model)`, as in `(model (define-fun x () Real (/ 1 2)))`
+/// or the same without the `model` keyword.
+///
+/// Only rational values are supported; algebraic numbers such as Z3's
+/// `root-obj` are reported as parse errors.
+pub fn parse_model(output: &str) -> Result<HashMap<String, BigRational>, ChidogError> {
+    let mut values = HashMap::new();
+    for top in parse_sexprs(output)? {
+        let SExpr::List(items) = top else {
+            // Skip status lines such as `sat`.
+            continue;
+        };
+        for item in items {
+            let SExpr::List(def) = item else {
+                continue;
+            };
+            match def.as_slice() {
+                [
+                    SExpr::Atom(kw),
+                    SExpr::Atom(name),
+                    SExpr::List(args),
+                    _sort,
+                    value,
+                ] if kw == "define-fun" && args.is_empty() => {
+                    values.insert(name.clone(), eval_value(value)?);
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(values)
+}
@@ -0,0 +1,239 @@
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use num::{BigInt, BigRational, PrimInt, Unsigned};
+
+use crate::gf256::Gf256;
+use crate::multimodular::Modular;
+use crate::{Monomial, Polynomial};
+
+/// Coefficients whose size can be measured.
+pub trait CoefficientSize {
+    /// The number of bits of the magnitude, or of the numerator and
+    /// denominator together for a fraction.
+    fn bits(&self) -> u64;
+
+    /// The bytes held on the heap, beyond the value itself.
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($t:ty),*) => {$(
+        impl CoefficientSize for $t {
+            fn bits(&self) -> u64 {
+                u64::from(<$t>::BITS - self.unsigned_abs().leading_zeros())
+            }
+        }
+    )*};
+}
+
+impl_primitive!(i8, i16, i32, i64);
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl CoefficientSize for $t {
+            fn bits(&self) -> u64 {
+                u64::from(<$t>::BITS - self.leading_zeros())
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64);
+
+/// A float always takes its 64 bits.
+impl CoefficientSize for f64 {
+    fn bits(&self) -> u64 {
+        64
+    }
+}
+
+/// The magnitude is held on the heap in 64-bit digits.
+impl CoefficientSize for BigInt {
+    fn bits(&self) -> u64 {
+        BigInt::bits(self)
+    }
+
+    fn heap_bytes(&self) -> usize {
+        BigInt::bits(self).div_ceil(64) as usize * 8
+    }
+}
+
+impl CoefficientSize for BigRational {
+    fn bits(&self) -> u64 {
+        self.numer().bits() + self.denom().bits()
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.numer().heap_bytes() + self.denom().heap_bytes()
+    }
+}
+
+/// The bits of the residue, which are bounded by those of the modulus.
+impl CoefficientSize for Modular {
+    fn bits(&self) -> u64 {
+        self.value().bits()
+    }
+}
+
+impl CoefficientSize for Gf256 {
+    fn bits(&self) -> u64 {
+        self.0.bits()
+    }
+}
+
+/// The size of a polynomial or of a system of polynomials, as reported by
+/// [`Polynomial::stats`] and [`system_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of polynomials.
+    pub polynomials: usize,
+    /// The number of terms.
+    pub terms: usize,
+    /// The most terms of one polynomial.
+    pub max_terms: usize,
+    /// The largest total degree of a term, or `None` if there are no terms.
+    pub max_degree: Option<u64>,
+    /// The most bits of one coefficient.
+    pub max_coefficient_bits: u64,
+    /// The bits of all coefficients.
+    pub coefficient_bits: u64,
+    /// An estimate of the bytes held on the heap: the term maps at their
+    /// capacity, the exponent vectors too long to be stored inline, and
+    /// the heap memory of the coefficients.
+    pub heap_bytes: usize,
+}
+
+impl Stats {
+    /// The statistics of the polynomials of `self` and `other` together.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            polynomials: self.polynomials + other.polynomials,
+            terms: self.terms + other.terms,
+            max_terms: self.max_terms.max(other.max_terms),
+            max_degree: self.max_degree.max(other.max_degree),
+            max_coefficient_bits: self.max_coefficient_bits.max(other.max_coefficient_bits),
+            coefficient_bits: self.coefficient_bits + other.coefficient_bits,
+            heap_bytes: self.heap_bytes + other.heap_bytes,
+        }
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    K: CoefficientSize,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// The number of terms of `self`, its degree, the sizes of its
+    /// coefficients and an estimate of its heap memory.
+    pub fn stats(&self) -> Stats {
+        // A hash map stores one control byte per bucket besides the entry.
+        let entry = size_of::<(Monomial<P>, K)>() + 1;
+        let mut stats = Stats {
+            polynomials: 1,
+            terms: self.terms.len(),
+            max_terms: self.terms.len(),
+            heap_bytes: self.terms.capacity() * entry,
+            ..Stats::default()
+        };
+        for (m, c) in &self.terms {
+            let degree = m.powers.iter().map(|p| p.to_u64().unwrap()).sum();
+            stats.max_degree = stats.max_degree.max(Some(degree));
+            let bits = c.bits();
+            stats.max_coefficient_bits = stats.max_coefficient_bits.max(bits);
+            stats.coefficient_bits += bits;
+            if m.powers.spilled() {
+                stats.heap_bytes += m.powers.capacity() * size_of::<P>();
+            }
+            stats.heap_bytes += c.heap_bytes();
+        }
+        stats
+    }
+}
+
+/// The statistics of the polynomials of `system`, such as the generators or
+/// a Gröbner basis of an ideal.
+pub fn system_stats<R, V, K, P, S>(system: &[Polynomial<'_, R, V, K, P, S>]) -> Stats
+where
+    K: CoefficientSize,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    system
+        .iter()
+        .map(Polynomial::stats)
+        .fold(Stats::default(), Stats::merge)
+}
+
+/// Counters of the work done in a polynomial ring, shared by all its
+/// computations and updated as they go, for monitoring long jobs from
+/// another thread through [`PolynomialRing::counters`].
+///
+/// [`PolynomialRing::counters`]: crate::PolynomialRing::counters
+#[derive(Debug, Default)]
+pub struct RingCounters {
+    multiplications: AtomicU64,
+    product_terms: AtomicU64,
+    largest_product: AtomicU64,
+    reductions: AtomicU64,
+    reduction_steps: AtomicU64,
+}
+
+/// The values of the [`RingCounters`] at one time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CounterSnapshot {
+    /// The number of polynomial products.
+    pub multiplications: u64,
+    /// The number of terms of all products.
+    pub product_terms: u64,
+    /// The most terms of one product.
+    pub largest_product: u64,
+    /// The number of multivariate divisions.
+    pub reductions: u64,
+    /// The number of steps of all divisions, each cancelling one term of
+    /// the dividend.
+    pub reduction_steps: u64,
+}
+
+impl RingCounters {
+    pub(crate) fn record_product(&self, terms: usize) {
+        self.multiplications.fetch_add(1, Ordering::Relaxed);
+        self.product_terms
+            .fetch_add(terms as u64, Ordering::Relaxed);
+        self.largest_product
+            .fetch_max(terms as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_reduction(&self, steps: usize) {
+        self.reductions.fetch_add(1, Ordering::Relaxed);
+        self.reduction_steps
+            .fetch_add(steps as u64, Ordering::Relaxed);
+    }
+
+    /// The current values.
+    pub fn snapshot(&self) -> CounterSnapshot {
+        CounterSnapshot {
+            multiplications: self.multiplications.load(Ordering::Relaxed),
+            product_terms: self.product_terms.load(Ordering::Relaxed),
+            largest_product: self.largest_product.load(Ordering::Relaxed),
+            reductions: self.reductions.load(Ordering::Relaxed),
+            reduction_steps: self.reduction_steps.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets all counters back to zero.
+    pub fn reset(&self) {
+        for counter in [
+            &self.multiplications,
+            &self.product_terms,
+            &self.largest_product,
+            &self.reductions,
+            &self.reduction_steps,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+use num::{BigInt, BigRational, PrimInt, Unsigned};
+use proptest::collection::vec;
+use proptest::strategy::Strategy;
+
+use crate::multimodular::{Modular, PrimeField};
+use crate::{Monomial, Polynomial, PolynomialRing, Semiring, SemiringElement};
+
+/// Monomials in `n` variables of total degree at most `max_degree`, which
+/// must fit in `P`, shrinking towards `1`.
+///
+/// The exponents are drawn independently and each is then cut down so that
+/// the total degree stays within `max_degree`.
+pub fn monomials<P>(n: usize, max_degree: usize) -> impl Strategy<Value = Monomial<P>>
+where
+    P: PrimInt + Debug,
+{
+    vec(0..=max_degree, n).prop_map(move |exponents| {
+        let mut degree = 0;
+        let powers = exponents
+            .into_iter()
+            .map(|e| {
+                let e = e.min(max_degree - degree);
+                degree += e;
+                P::from(e).unwrap()
+            })
+            .collect();
+        Monomial { powers }
+    })
+}
+
+/// Polynomials in `ring` with at most `max_terms` terms, with monomials drawn
+/// by [`monomials`] and coefficients by `coeffs`, shrinking by dropping terms
+/// and by shrinking their monomials and coefficients.
+///
+/// Terms with the same monomial are added, so a polynomial may have fewer
+/// terms than were drawn.
+pub fn polynomials<'a, R, V, K, P, S>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    coeffs: impl Strategy<Value = K>,
+    max_degree: usize,
+    max_terms: usize,
+) -> impl Strategy<Value = Polynomial<'a, R, V, K, P, S>>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Debug,
+    P: Hash + PrimInt + Unsigned + Debug,
+    S: BuildHasher + Default,
+{
+    let terms = (monomials(ring.vars.len(), max_degree), coeffs);
+    vec(terms, 0..=max_terms).prop_map(move |terms| Polynomial::from_terms(ring, terms).unwrap())
+}
+
+/// Integers in `[-bound, bound]`, shrinking towards zero.
+pub fn integers(bound: i64) -> impl Strategy<Value = BigInt> {
+    (-bound..=bound).prop_map(BigInt::from)
+}
+
+/// Rational numbers with numerator in `[-numer_bound, numer_bound]` and
+/// denominator in `[1, denom_bound]`, shrinking towards zero and towards
+/// integers.
+pub fn rationals(numer_bound: i64, denom_bound: i64) -> impl Strategy<Value = BigRational> {
+    (-numer_bound..=numer_bound, 1..=denom_bound)
+        .prop_map(|(n, d)| BigRational::new(n.into(), d.into()))
+}
+
+/// The elements of `field`, shrinking towards zero.
+pub fn prime_field_elements(field: PrimeField) -> impl Strategy<Value = Modular> {
+    (0..field.p()).prop_map(move |n| field.element(n as i64))
+}
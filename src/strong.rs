@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::zip;
+
+use num::{PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::geobucket::Geobucket;
+use crate::order::MonomialOrder;
+use crate::univariate::Euclidean;
+use crate::{Monomial, Polynomial, Powers, Ring};
+
+/// The greatest common divisor `g` of `a` and `b` in normal form, with
+/// `g = s*a + t*b`.
+fn extended_gcd<K: Euclidean + Clone>(a: &K, b: &K) -> (K, K, K) {
+    let (mut r0, mut r1) = (a.clone(), b.clone());
+    let (mut s0, mut s1) = (K::one(), K::zero());
+    let (mut t0, mut t1) = (K::zero(), K::one());
+    while !r1.is_zero() {
+        let (q, r) = r0.div_rem_euclid(&r1);
+        let s = s0 - q.clone() * s1.clone();
+        let t = t0 - q * t1.clone();
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+    let unit = r0.normal_unit();
+    (r0 * unit.clone(), s0 * unit.clone(), t0 * unit)
+}
+
+impl<'a, R, V, K, P> Polynomial<'a, R, V, K, P>
+where
+    R: Ring<K>,
+    K: Euclidean + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The nonzero terms of `c*m*self` for a coefficient `c` and a monomial
+    /// `m`, in decreasing order for `order`.
+    fn scaled<O: MonomialOrder>(&self, c: &K, m: &[P], order: &O) -> Vec<(Monomial<P>, K)> {
+        self.sorted_terms(order)
+            .into_iter()
+            .map(|(n, d)| {
+                let powers = zip(m, &n.powers).map(|(p, q)| *p + *q).collect();
+                (Monomial { powers }, c.clone() * d.clone())
+            })
+            .filter(|(_, d)| !self.elem_of.base.is_zero(d))
+            .collect()
+    }
+
+    /// The remainder of the strong reduction of `self` by `divisors` in
+    /// `order`, with coefficients in a Euclidean ring.
+    ///
+    /// Each step takes the leading term `c*x^a` of the running dividend. If
+    /// some divisor `g` has a leading term `d*x^b` with `x^b` dividing `x^a`
+    /// and a nonzero Euclidean quotient `q` of `c` by `d`, then
+    /// `q*x^(a-b)*g` is subtracted, which leaves the remainder of `c` by `d`
+    /// in its place; otherwise the term moves to the remainder. Over `ℤ`,
+    /// with positive leading coefficients, the coefficients of the remainder
+    /// are thus reduced into `0..d` wherever possible, and the remainder by
+    /// a strong Gröbner basis is a normal form, zero exactly on the ideal.
+    pub fn strong_rem<O: MonomialOrder + Clone>(&self, divisors: &[Self], order: &O) -> Self {
+        let leads: Vec<_> = divisors
+            .iter()
+            .map(|g| g.leading_term(order).map(|(m, c)| (m.clone(), c.clone())))
+            .collect();
+        let mut dividend = Geobucket::new(self.elem_of, order.clone());
+        dividend.add(self);
+        let mut remainder = HashMap::default();
+        let mut steps = 0;
+        while let Some((m, mut c)) = dividend.pop_leading() {
+            loop {
+                let found = zip(divisors, &leads).find_map(|(g, lead)| {
+                    let (lm, lc) = lead.as_ref()?;
+                    let shift = m.checked_div(lm)?;
+                    let (q, r) = c.div_rem_euclid(lc);
+                    (!q.is_zero()).then_some((g, shift, q, r))
+                });
+                let Some((g, shift, q, r)) = found else {
+                    remainder.insert(m, c);
+                    break;
+                };
+                let mut product = g.scaled(&q, &shift.powers, order);
+                product.retain(|(n, _)| n != &m);
+                dividend.sub_terms(product);
+                steps += 1;
+                if self.elem_of.base.is_zero(&r) {
+                    break;
+                }
+                c = r;
+            }
+        }
+        self.elem_of.counters().record_reduction(steps);
+        Polynomial {
+            elem_of: self.elem_of,
+            terms: remainder,
+        }
+    }
+}
+
+/// The state of the strong Gröbner basis algorithm: a basis whose leading
+/// coefficients are in normal form, with their leading terms, and the pairs
+/// still to be treated, sorted so that the one with the least `lcm` is last.
+struct Strong<'a, 'o, R, V, K, P, O>
+where
+    P: Hash,
+{
+    order: &'o O,
+    basis: Vec<Polynomial<'a, R, V, K, P>>,
+    leads: Vec<(Monomial<P>, K)>,
+    pairs: Vec<(usize, usize, Monomial<P>)>,
+}
+
+impl<'a, 'o, R, V, K, P, O> Strong<'a, 'o, R, V, K, P, O>
+where
+    R: Ring<K>,
+    K: Euclidean + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder + Clone,
+{
+    /// Adds `f`, times the unit normalizing its leading coefficient, to the
+    /// basis along with its pairs with the previous elements. Zero is
+    /// ignored.
+    fn insert(&mut self, f: &Polynomial<'a, R, V, K, P>) {
+        let Some((m, c)) = f.leading_term(self.order) else {
+            return;
+        };
+        let one: Powers<P> = smallvec![P::zero(); m.powers.len()];
+        let unit = c.normal_unit();
+        let lead = (m.clone(), c.clone() * unit.clone());
+        let g = Polynomial {
+            elem_of: f.elem_of,
+            terms: f.scaled(&unit, &one, self.order).into_iter().collect(),
+        };
+        let j = self.basis.len();
+        for (i, (lm, _)) in self.leads.iter().enumerate() {
+            self.pairs.push((i, j, lm.lcm(&lead.0)));
+        }
+        self.basis.push(g);
+        self.leads.push(lead);
+        let order = self.order;
+        self.pairs.sort_by(|(_, _, a), (_, _, b)| order.cmp(b, a));
+    }
+
+    /// The S-polynomial of elements `i` and `j`, `(l/a)*(x^c/x^a)*g_i -
+    /// (l/b)*(x^c/x^b)*g_j`, where `a*x^a` and `b*x^b` are their leading
+    /// terms, `l` the least common multiple of `a` and `b` and `x^c` of the
+    /// monomials, and their G-polynomial `s*(x^c/x^a)*g_i + t*(x^c/x^b)*g_j`,
+    /// where `s*a + t*b` is the greatest common divisor of `a` and `b`. The
+    /// G-polynomial is omitted when `a` divides `b` or `b` divides `a`, as it
+    /// is then a multiple of an element.
+    fn pair_polynomials(&self, i: usize, j: usize) -> Vec<Polynomial<'a, R, V, K, P>> {
+        let ((mi, a), (mj, b)) = (&self.leads[i], &self.leads[j]);
+        let lcm = mi.lcm(mj);
+        let (ui, uj) = (lcm.checked_div(mi).unwrap(), lcm.checked_div(mj).unwrap());
+        let (g, s, t) = extended_gcd(a, b);
+        let ring = self.basis[i].elem_of;
+        let combine = |x: &K, y: &K| {
+            let mut sum = Geobucket::new(ring, self.order.clone());
+            sum.add_terms(self.basis[i].scaled(x, &ui.powers, self.order));
+            sum.add_terms(self.basis[j].scaled(y, &uj.powers, self.order));
+            sum.into_polynomial()
+        };
+        let l = a.div_exact(&g).unwrap() * b.clone();
+        let neg = |x: K| K::zero() - x;
+        let mut polynomials = vec![combine(
+            &l.div_exact(a).unwrap(),
+            &neg(l.div_exact(b).unwrap()),
+        )];
+        if a.div_exact(b).is_none() && b.div_exact(a).is_none() {
+            polynomials.push(combine(&s, &t));
+        }
+        polynomials
+    }
+
+    /// Runs the algorithm to completion, consulting `control` before each
+    /// pair.
+    fn run(&mut self, control: &ComputationControl) -> Result<(), ChidogError> {
+        let mut steps = 0;
+        loop {
+            control.check(&Progress {
+                algorithm: "strong",
+                steps,
+                remaining: Some(self.pairs.len()),
+                terms: self.basis.iter().map(|g| g.terms.len()).sum(),
+            })?;
+            let Some((i, j, _)) = self.pairs.pop() else {
+                return Ok(());
+            };
+            steps += 1;
+            for h in self.pair_polynomials(i, j) {
+                let h = h.strong_rem(&self.basis, self.order);
+                self.insert(&h);
+            }
+        }
+    }
+
+    /// The reduced strong Gröbner basis spanned by the current basis:
+    /// elements whose leading term is divisible by that of another are
+    /// dropped, and every tail is reduced by the others. The result is
+    /// sorted by decreasing leading monomial.
+    fn into_reduced(self) -> Vec<Polynomial<'a, R, V, K, P>> {
+        let order = self.order;
+        let divides = |k: usize, i: usize| {
+            let ((mk, ck), (mi, ci)) = (&self.leads[k], &self.leads[i]);
+            mk.divides(mi) && ci.div_exact(ck).is_some()
+        };
+        let keep: Vec<usize> = (0..self.basis.len())
+            .filter(|&i| {
+                !(0..self.basis.len()).any(|k| k != i && divides(k, i) && (!divides(i, k) || k < i))
+            })
+            .collect();
+        let mut basis: Vec<_> = keep.iter().map(|&i| self.basis[i].clone()).collect();
+        let leads: Vec<_> = keep.iter().map(|&i| self.leads[i].0.clone()).collect();
+        for (i, lead) in leads.iter().enumerate() {
+            let mut g = basis.remove(i);
+            let (lead, c) = g.terms.remove_entry(lead).unwrap();
+            let mut reduced = g.strong_rem(&basis, order);
+            reduced.terms.insert(lead, c);
+            basis.insert(i, reduced);
+        }
+        let mut sorted: Vec<_> = zip(leads, basis).collect();
+        sorted.sort_by(|(a, _), (b, _)| order.cmp(b, a));
+        sorted.into_iter().map(|(_, g)| g).collect()
+    }
+}
+
+/// The reduced strong Gröbner basis of the ideal generated by `generators`
+/// in `order`, over a Euclidean ring of coefficients such as `ℤ`.
+///
+/// A strong Gröbner basis is one whose leading terms, with coefficients,
+/// divide the leading term of every element of the ideal; membership is
+/// decided by [`Polynomial::strong_rem`]. It is computed by the algorithm of
+/// Kandri-Rody and Kapur, which treats each pair of elements like
+/// Buchberger's algorithm, but adds the remainder of its G-polynomial as
+/// well as of its S-polynomial, to account for the greatest common divisors
+/// of the leading coefficients.
+///
+/// The leading coefficients are in normal form (positive over `ℤ`), and no
+/// leading term divides another.
+pub fn strong_groebner_basis<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Ring<K>,
+    K: Euclidean + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder + Clone,
+{
+    strong_groebner_basis_controlled(generators, order, &ComputationControl::new())
+        .expect("uncontrolled computations are not interrupted")
+}
+
+/// The reduced strong Gröbner basis of [`strong_groebner_basis`], consulting
+/// `control` before each pair, with the number of pairs treated and pending
+/// and the number of terms of the basis, and failing as it directs.
+pub fn strong_groebner_basis_controlled<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    order: &O,
+    control: &ComputationControl,
+) -> Result<Vec<Polynomial<'a, R, V, K, P>>, ChidogError>
+where
+    R: Ring<K>,
+    K: Euclidean + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder + Clone,
+{
+    let mut state = Strong {
+        order,
+        basis: Vec::new(),
+        leads: Vec::new(),
+        pairs: Vec::new(),
+    };
+    for f in generators {
+        let h = f.strong_rem(&state.basis, order);
+        state.insert(&h);
+    }
+    state.run(control)?;
+    Ok(state.into_reduced())
+}
+
+/// The reduced strong Gröbner basis of the ideal generated by `generators`
+/// over the quotient ring `K/(modulus)`, such as `ℤ/n`, with coefficients
+/// represented by their remainders by `modulus`.
+///
+/// This is the strong Gröbner basis over `K` of the ideal generated by
+/// `generators` and `modulus`, whose image modulo `modulus` is a strong
+/// Gröbner basis of the image of the ideal. The constant `modulus` itself,
+/// which is zero in the quotient, is left out; a smaller constant is kept.
+pub fn strong_groebner_basis_modulo<'a, R, V, K, P, O>(
+    generators: &[Polynomial<'a, R, V, K, P>],
+    modulus: &K,
+    order: &O,
+) -> Vec<Polynomial<'a, R, V, K, P>>
+where
+    R: Ring<K>,
+    K: Euclidean + Clone,
+    P: Hash + PrimInt + Unsigned,
+    O: MonomialOrder + Clone,
+{
+    let Some(f) = generators.first() else {
+        return Vec::new();
+    };
+    let one = Monomial {
+        powers: smallvec![P::zero(); f.elem_of.vars.len()],
+    };
+    let modulus = modulus.clone() * modulus.normal_unit();
+    let constant = Polynomial {
+        elem_of: f.elem_of,
+        terms: [(one.clone(), modulus.clone())].into_iter().collect(),
+    };
+    let mut generators = generators.to_vec();
+    generators.push(constant);
+    let mut basis = strong_groebner_basis(&generators, order);
+    basis.retain(|g| {
+        !(g.terms.len() == 1
+            && g.terms
+                .get(&one)
+                .is_some_and(|c| (c.clone() - modulus.clone()).is_zero()))
+    });
+    basis
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use num::{FromPrimitive, PrimInt, Unsigned};
+use smallvec::smallvec;
+
+use crate::error::ChidogError;
+use crate::monomials::monomials_of_degree;
+use crate::order::{Lex, MonomialOrder};
+use crate::univariate::DenseUnivariate;
+use crate::{
+    FieldElement, Monomial, Polynomial, PolynomialRing, Powers, Ring, RingElement, Semiring,
+    SemiringElement,
+};
+
+/// The sum of `monomials` with coefficient one in `ring`.
+fn sum_of<'a, R, V, K, P, S>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    monomials: impl IntoIterator<Item = Monomial<P>>,
+) -> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    Polynomial::from_terms(ring, monomials.into_iter().map(|m| (m, K::one())))
+        .expect("the monomials have one exponent for each variable")
+}
+
+/// The elementary symmetric polynomial `e_k` in the variables of `ring`: the
+/// sum of the products of `k` distinct variables, which is zero if `k`
+/// exceeds the number of variables.
+pub fn elementary_symmetric<'a, R, V, K, P, S>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    k: usize,
+) -> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    let n = ring.vars.len();
+    let mut monomials = Vec::new();
+    if k <= n {
+        // The subsets of `k` variables, as increasing index vectors in
+        // lexicographic order.
+        let mut subset: Vec<usize> = (0..k).collect();
+        loop {
+            let mut powers = smallvec![P::zero(); n];
+            for &i in &subset {
+                powers[i] = P::one();
+            }
+            monomials.push(Monomial { powers });
+            let Some(j) = (0..k).rev().find(|&j| subset[j] < n - k + j) else {
+                break;
+            };
+            subset[j] += 1;
+            for l in j + 1..k {
+                subset[l] = subset[l - 1] + 1;
+            }
+        }
+    }
+    sum_of(ring, monomials)
+}
+
+/// The complete homogeneous symmetric polynomial `h_k` in the variables of
+/// `ring`: the sum of all monomials of total degree `k`.
+pub fn complete_homogeneous<'a, R, V, K, P, S>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    k: usize,
+) -> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    sum_of(ring, monomials_of_degree(ring.vars.len(), k))
+}
+
+/// The power sum `p_k = x_1^k + ... + x_n^k` in the variables of `ring`,
+/// which is the number of variables if `k` is zero.
+pub fn power_sum<'a, R, V, K, P, S>(
+    ring: &'a PolynomialRing<'a, R, V>,
+    k: usize,
+) -> Polynomial<'a, R, V, K, P, S>
+where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    let n = ring.vars.len();
+    let k = P::from(k).expect("degree does not fit in the exponent type");
+    sum_of(
+        ring,
+        (0..n).map(|i| {
+            let mut powers = smallvec![P::zero(); n];
+            powers[i] = k;
+            Monomial { powers }
+        }),
+    )
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+    V: Eq,
+    S: BuildHasher + Clone + Default,
+{
+    /// The symmetric polynomial `self` in `n` variables written as a
+    /// polynomial in the elementary symmetric polynomials `e_1, ..., e_n`,
+    /// which are the `n` variables of `ring`, or an `InvalidInput` error if
+    /// `self` is not symmetric or `ring` has another number of variables.
+    ///
+    /// The leading term `c*x^a` of `self` in the lexicographic order is
+    /// removed by subtracting `c*e_1^(a_1 - a_2)*...*e_n^(a_n)` until
+    /// nothing is left; `self` is not symmetric if the exponents `a_i` ever
+    /// fail to decrease.
+    pub fn decompose_symmetric<'b, W>(
+        &self,
+        ring: &'b PolynomialRing<'b, R, W>,
+    ) -> Result<Polynomial<'b, R, W, K, P, S>, ChidogError> {
+        let n = self.elem_of.vars.len();
+        if ring.vars.len() != n {
+            return Err(ChidogError::InvalidInput(format!(
+                "decomposing a polynomial in {n} variables needs a ring of {n} \
+                 elementary symmetric polynomials, not {}",
+                ring.vars.len()
+            )));
+        }
+        let elementary: Vec<Self> = (1..=n)
+            .map(|k| elementary_symmetric(self.elem_of, k))
+            .collect();
+        let mut rest = self.clone();
+        let mut terms = Vec::new();
+        while let Some((lead, c)) = rest.terms.iter().max_by(|(a, _), (b, _)| Lex.cmp(a, b)) {
+            let a = &lead.powers;
+            if (1..n).any(|i| a[i - 1] < a[i]) {
+                return Err(ChidogError::InvalidInput(
+                    "the polynomial is not symmetric".to_string(),
+                ));
+            }
+            let powers: Powers<P> = (0..n)
+                .map(|i| match i + 1 < n {
+                    true => a[i] - a[i + 1],
+                    false => a[i],
+                })
+                .collect();
+            let one = Monomial {
+                powers: smallvec![P::zero(); n],
+            };
+            let mut product = Self::from_terms(self.elem_of, [(one, c.clone())])?;
+            for (e, p) in elementary.iter().zip(&powers) {
+                for _ in 0..p.to_usize().unwrap() {
+                    product *= e.clone();
+                }
+            }
+            terms.push((Monomial { powers }, c.clone()));
+            rest -= product;
+        }
+        let mut decomposed = HashMap::with_capacity_and_hasher(terms.len(), S::default());
+        decomposed.extend(terms);
+        Ok(Polynomial {
+            elem_of: ring,
+            terms: decomposed,
+        })
+    }
+}
+
+/// The power sums `p_1, ..., p_m` of `n` values from their elementary
+/// symmetric polynomials `e_1, ..., e_n`, by Newton's identities
+/// `p_k = e_1*p_(k-1) - e_2*p_(k-2) + ... + (-1)^(k-1)*k*e_k`, where `e_k`
+/// is zero for `k > n`.
+pub fn power_sums_from_elementary<K>(elementary: &[K], m: usize) -> Vec<K>
+where
+    K: RingElement + Clone + FromPrimitive,
+{
+    let mut power_sums: Vec<K> = Vec::with_capacity(m);
+    for k in 1..=m {
+        let mut p = match elementary.get(k - 1) {
+            Some(e) => K::from_usize(k).unwrap() * e.clone(),
+            None => K::zero(),
+        };
+        for i in (1..k).rev() {
+            // Alternate the sign, from `e_(k-1)*p_1` down to `e_1*p_(k-1)`.
+            p = K::zero() - p;
+            if let Some(e) = elementary.get(i - 1) {
+                p += e.clone() * power_sums[k - i - 1].clone();
+            }
+        }
+        power_sums.push(p);
+    }
+    power_sums
+}
+
+/// The elementary symmetric polynomials `e_1, ..., e_n` of `n` values from
+/// their power sums `p_1, ..., p_n`, by Newton's identities
+/// `k*e_k = e_(k-1)*p_1 - e_(k-2)*p_2 + ... + (-1)^(k-1)*p_k`, which divide
+/// by `k` and so only hold in characteristic zero.
+pub fn elementary_from_power_sums<K>(power_sums: &[K]) -> Vec<K>
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    let mut elementary = vec![K::one()];
+    for k in 1..=power_sums.len() {
+        let mut e = K::zero();
+        for i in (1..=k).rev() {
+            e = K::zero() - e;
+            e += elementary[k - i].clone() * power_sums[i - 1].clone();
+        }
+        elementary.push(e / K::from_usize(k).unwrap());
+    }
+    elementary.remove(0);
+    elementary
+}
+
+/// The elementary symmetric polynomials `e_1, ..., e_n` of the roots of the
+/// monic polynomial `f` of degree `n`, which are its coefficients
+/// `e_k = (-1)^k*a_(n-k)`, or an `InvalidInput` error if `f` is not monic.
+pub fn elementary_from_coefficients<K>(f: &DenseUnivariate<K>) -> Result<Vec<K>, ChidogError>
+where
+    K: RingElement + Clone + PartialEq,
+{
+    if !f.leading_coeff().is_some_and(|c| c.is_one()) {
+        return Err(ChidogError::InvalidInput(
+            "the polynomial is not monic".to_string(),
+        ));
+    }
+    let coeffs = f.coeffs();
+    Ok(coeffs
+        .iter()
+        .rev()
+        .skip(1)
+        .enumerate()
+        .map(|(k, a)| match k % 2 {
+            0 => K::zero() - a.clone(),
+            _ => a.clone(),
+        })
+        .collect())
+}
+
+/// The monic polynomial `(x - r_1)*...*(x - r_n)` whose roots have the
+/// elementary symmetric polynomials `e_1, ..., e_n`.
+pub fn coefficients_from_elementary<K>(elementary: &[K]) -> DenseUnivariate<K>
+where
+    K: RingElement + Clone,
+{
+    let n = elementary.len();
+    let mut coeffs = vec![K::zero(); n + 1];
+    coeffs[n] = K::one();
+    for (k, e) in elementary.iter().enumerate() {
+        coeffs[n - k - 1] = match k % 2 {
+            0 => K::zero() - e.clone(),
+            _ => e.clone(),
+        };
+    }
+    DenseUnivariate::new(coeffs)
+}
+
+/// The power sums `p_1, ..., p_m` of the roots of the monic polynomial `f`,
+/// counted with multiplicity, or an `InvalidInput` error if `f` is not
+/// monic.
+pub fn power_sums_from_coefficients<K>(
+    f: &DenseUnivariate<K>,
+    m: usize,
+) -> Result<Vec<K>, ChidogError>
+where
+    K: RingElement + Clone + PartialEq + FromPrimitive,
+{
+    Ok(power_sums_from_elementary(
+        &elementary_from_coefficients(f)?,
+        m,
+    ))
+}
+
+/// The monic polynomial of degree `n` whose roots have the power sums
+/// `p_1, ..., p_n`, in characteristic zero.
+pub fn coefficients_from_power_sums<K>(power_sums: &[K]) -> DenseUnivariate<K>
+where
+    K: FieldElement + Clone + FromPrimitive,
+{
+    coefficients_from_elementary(&elementary_from_power_sums(power_sums))
+}
@@ -0,0 +1,340 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use num::{BigInt, BigRational, One, PrimInt, Signed, ToPrimitive, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::expr::Expr;
+use crate::infix::{from_infix, to_infix};
+use crate::sexpr::number;
+use crate::singular::RingOrdering;
+use crate::{Polynomial, PolynomialRing, Semiring};
+
+fn parse_error(message: impl Into<String>) -> ChidogError {
+    ChidogError::Parse(message.into())
+}
+
+/// `s` as a Python string literal.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn constant(c: &BigRational) -> String {
+    match c.is_integer() {
+        true => format!("Integer({c})"),
+        false => format!("Rational({}, {})", c.numer(), c.denom()),
+    }
+}
+
+/// A node of the tree written by `srepr`: a call such as `Symbol('x')`,
+/// with its positional arguments, an integer or a string.
+#[derive(Debug)]
+enum Node {
+    Call(String, Vec<Node>),
+    Int(BigInt),
+    Str(String),
+}
+
+/// A parser of the Python expressions written by `srepr`, which are calls
+/// of constructors on integers, strings and calls. Keyword arguments, such
+/// as the `real=True` of a symbol, are ignored.
+struct Parser<'s> {
+    chars: Peekable<Chars<'s>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|&c| c.is_alphanumeric() || "_.+-".contains(c))
+        {
+            word.push(c);
+        }
+        word
+    }
+
+    fn string(&mut self, delimiter: char) -> Result<String, ChidogError> {
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => s.extend(self.chars.next()),
+                Some(c) if c == delimiter => return Ok(s),
+                Some(c) => s.push(c),
+                None => return Err(parse_error("unterminated string")),
+            }
+        }
+    }
+
+    /// The next node, or `None` for a keyword argument.
+    fn node(&mut self) -> Result<Option<Node>, ChidogError> {
+        self.skip_whitespace();
+        if let Some(delimiter) = self.chars.next_if(|&c| c == '\'' || c == '"') {
+            return Ok(Some(Node::Str(self.string(delimiter)?)));
+        }
+        let word = self.word();
+        if word.is_empty() {
+            return Err(match self.chars.peek() {
+                Some(c) => parse_error(format!("unexpected `{c}`")),
+                None => parse_error("unexpected end of input"),
+            });
+        }
+        if let Ok(n) = word.parse() {
+            return Ok(Some(Node::Int(n)));
+        }
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'=').is_some() {
+            self.node()?;
+            return Ok(None);
+        }
+        if self.chars.peek() != Some(&'(') {
+            return Ok(Some(Node::Call(word, Vec::new())));
+        }
+        self.chars.next();
+        let mut args = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&')').is_some() {
+            return Ok(Some(Node::Call(word, args)));
+        }
+        loop {
+            args.extend(self.node()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => {}
+                Some(')') => return Ok(Some(Node::Call(word, args))),
+                _ => return Err(parse_error(format!("unterminated call of `{word}`"))),
+            }
+        }
+    }
+}
+
+/// The number written by SymPy's `Float`, such as `'1.5e-3'`.
+fn float(s: &str) -> Option<BigRational> {
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(m) => (true, m),
+        None => (false, mantissa),
+    };
+    let scale = num::pow(
+        BigRational::from_integer(10.into()),
+        exponent.unsigned_abs() as usize,
+    );
+    let m = number(mantissa)?;
+    let m = if exponent < 0 { m / scale } else { m * scale };
+    Some(if negative { -m } else { m })
+}
+
+fn to_expr(node: &Node, names: &[String]) -> Result<Expr<BigRational>, ChidogError> {
+    let unsupported = || parse_error(format!("unsupported expression `{node:?}`"));
+    let Node::Call(head, args) = node else {
+        return Err(unsupported());
+    };
+    let integer = |n: &Node| match n {
+        Node::Int(n) => Ok(n.clone()),
+        Node::Call(head, args) => match (head.as_str(), args.as_slice()) {
+            ("Integer", [Node::Int(n)]) => Ok(n.clone()),
+            ("Zero", []) => Ok(BigInt::zero()),
+            ("One", []) => Ok(BigInt::one()),
+            ("NegativeOne", []) => Ok(-BigInt::one()),
+            _ => Err(unsupported()),
+        },
+        _ => Err(unsupported()),
+    };
+    let c = |c: BigRational| Ok(Expr::Constant(c));
+    match (head.as_str(), args.as_slice()) {
+        ("Symbol" | "Dummy", [Node::Str(name), ..]) => names
+            .iter()
+            .position(|n| n == name)
+            .map(Expr::Var)
+            .ok_or_else(|| ChidogError::InvalidInput(format!("unknown variable `{name}`"))),
+        ("Integer", [n]) => c(BigRational::from_integer(integer(n)?)),
+        ("Rational", [n, d]) => {
+            let d = integer(d)?;
+            match d.is_zero() {
+                true => Err(ChidogError::DivisionByZero),
+                false => c(BigRational::new(integer(n)?, d)),
+            }
+        }
+        ("Float", [Node::Str(s), ..]) => {
+            c(float(s).ok_or_else(|| parse_error(format!("invalid float `{s}`")))?)
+        }
+        ("Zero", []) => c(BigRational::zero()),
+        ("One", []) => c(BigRational::one()),
+        ("NegativeOne", []) => c(-BigRational::one()),
+        ("Half", []) => c(BigRational::new(1.into(), 2.into())),
+        ("Add", _) | ("Mul", _) => {
+            let args = args
+                .iter()
+                .map(|a| to_expr(a, names))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(match head.as_str() {
+                "Add" => Expr::Sum(args),
+                _ => Expr::Product(args),
+            })
+        }
+        ("Pow", [base, exponent]) => {
+            let base = to_expr(base, names)?;
+            let exponent = integer(exponent)?;
+            let e = exponent
+                .abs()
+                .to_u32()
+                .ok_or(ChidogError::ExponentOverflow)?;
+            if !exponent.is_negative() {
+                return Ok(base.pow(e));
+            }
+            match base.simplify() {
+                Expr::Constant(b) if !b.is_zero() => c(num::pow(b.recip(), e as usize)),
+                _ => Err(parse_error("negative power of a non-constant")),
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    V: Display,
+    K: Clone + Into<BigRational>,
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// `self` as written by SymPy's `srepr`, such as
+    /// `Add(Mul(Rational(3, 4), Symbol('y')), Pow(Symbol('x'), Integer(2)))`,
+    /// which `eval` in a Python session with `from sympy import *` turns
+    /// back into the polynomial. Variables are written by name.
+    pub fn to_sympy_srepr(&self) -> String {
+        let names: Vec<String> = self.elem_of.vars.iter().map(|v| v.to_string()).collect();
+        self.to_sympy_srepr_with(&names)
+    }
+
+    /// `self` as written by SymPy's `srepr`, with the variable `x_i` written
+    /// as the symbol `names[i]`.
+    pub fn to_sympy_srepr_with(&self, names: &[String]) -> String {
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_unstable_by(|(a, _), (b, _)| b.powers.cmp(&a.powers));
+        let mut terms: Vec<String> = terms
+            .into_iter()
+            .map(|(m, c)| {
+                let c: BigRational = c.clone().into();
+                let mut factors = Vec::new();
+                if !c.is_one() || m.powers.iter().all(|p| p.is_zero()) {
+                    factors.push(constant(&c));
+                }
+                for (name, p) in names.iter().zip(m.powers.iter()) {
+                    let symbol = format!("Symbol({})", quote(name));
+                    if p.is_one() {
+                        factors.push(symbol);
+                    } else if !p.is_zero() {
+                        factors.push(format!("Pow({symbol}, Integer({}))", p.to_u64().unwrap()));
+                    }
+                }
+                match factors.len() {
+                    1 => factors.pop().unwrap(),
+                    _ => format!("Mul({})", factors.join(", ")),
+                }
+            })
+            .collect();
+        match terms.len() {
+            0 => "Integer(0)".to_string(),
+            1 => terms.pop().unwrap(),
+            _ => format!("Add({})", terms.join(", ")),
+        }
+    }
+
+    /// `self` as a string which SymPy's `sympify` reads, such as
+    /// `x**2+3/4*y-1`, with its terms in decreasing lexicographic order.
+    pub fn to_sympy(&self) -> String {
+        to_infix(self, "**")
+    }
+
+    /// `self` as a string which Sage reads in a polynomial ring with the same
+    /// variables, such as `x^2+3/4*y-1`, with its terms in decreasing
+    /// lexicographic order.
+    pub fn to_sage(&self) -> String {
+        to_infix(self, "^")
+    }
+}
+
+impl<'a, R, V, P> Polynomial<'a, R, V, BigRational, P>
+where
+    R: Semiring<BigRational>,
+    V: Display + Eq,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The polynomial in `ring` written as `input` by SymPy's `srepr`,
+    /// expanded, with symbols matched to the variables of `ring` by name.
+    ///
+    /// `input` may be built from `Symbol`, `Integer`, `Rational`, `Float`,
+    /// `Add`, `Mul` and `Pow` with integer exponents, which must be
+    /// nonnegative unless the base is constant.
+    pub fn from_sympy_srepr(
+        ring: &'a PolynomialRing<'a, R, V>,
+        input: &str,
+    ) -> Result<Self, ChidogError> {
+        let names: Vec<String> = ring.vars.iter().map(|v| v.to_string()).collect();
+        Self::from_sympy_srepr_with(ring, input, &names)
+    }
+
+    /// The polynomial in `ring` written as `input` by SymPy's `srepr`, with
+    /// the symbol `names[i]` read as the variable `x_i`.
+    pub fn from_sympy_srepr_with(
+        ring: &'a PolynomialRing<'a, R, V>,
+        input: &str,
+        names: &[String],
+    ) -> Result<Self, ChidogError> {
+        let mut parser = Parser {
+            chars: input.chars().peekable(),
+        };
+        let node = parser
+            .node()?
+            .ok_or_else(|| parse_error("expected an expression"))?;
+        parser.skip_whitespace();
+        if parser.chars.peek().is_some() {
+            return Err(parse_error("trailing input"));
+        }
+        to_expr(&node, names)?.to_polynomial(ring)
+    }
+
+    /// The polynomial in `ring` written as `input` in the string forms of
+    /// SymPy or Sage, such as `x**2 + 3*y/4 - 1` or `x^2 + 3/4*y - 1`,
+    /// expanded.
+    pub fn from_sympy(
+        ring: &'a PolynomialRing<'a, R, V>,
+        input: &str,
+    ) -> Result<Self, ChidogError> {
+        from_infix(ring, input, false)
+    }
+}
+
+/// The Sage constructor of the polynomial ring of characteristic
+/// `characteristic` with the variables of `ring` and the monomial order
+/// `ordering`, such as `PolynomialRing(QQ, 'x,y,z', order='degrevlex')`.
+pub fn sage_ring<R, V: Display>(
+    ring: &PolynomialRing<'_, R, V>,
+    characteristic: u64,
+    ordering: RingOrdering,
+) -> String {
+    let vars: Vec<String> = ring.vars.iter().map(|v| v.to_string()).collect();
+    let field = match characteristic {
+        0 => "QQ".to_string(),
+        p => format!("GF({p})"),
+    };
+    let order = match ordering {
+        RingOrdering::Lex => "lex",
+        RingOrdering::GrLex => "deglex",
+        RingOrdering::GRevLex => "degrevlex",
+    };
+    format!(
+        "PolynomialRing({field}, {}, order='{order}')",
+        quote(&vars.join(","))
+    )
+}
@@ -0,0 +1,362 @@
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+
+use num::{BigInt, BigRational, Integer, One, PrimInt, Unsigned, Zero};
+
+use crate::error::ChidogError;
+use crate::polytope::Polytope;
+use crate::{Polynomial, PolynomialRing, Semiring, SemiringElement};
+
+/// The choice of tropical addition: either the minimum or the maximum.
+pub trait Convention {
+    /// Whether `a` is preferred over `b` by tropical addition.
+    fn prefers<T: PartialOrd>(a: &T, b: &T) -> bool;
+
+    /// The symbol for the tropical additive identity.
+    const INFINITY: &'static str;
+}
+
+/// The min-plus convention, in which tropical addition is the minimum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Min;
+
+/// The max-plus convention, in which tropical addition is the maximum.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Max;
+
+impl Convention for Min {
+    fn prefers<T: PartialOrd>(a: &T, b: &T) -> bool {
+        a < b
+    }
+
+    const INFINITY: &'static str = "∞";
+}
+
+impl Convention for Max {
+    fn prefers<T: PartialOrd>(a: &T, b: &T) -> bool {
+        a > b
+    }
+
+    const INFINITY: &'static str = "-∞";
+}
+
+/// An element of a tropical semiring over the ordered group `T`.
+///
+/// Tropical addition `a + b` is the minimum or maximum of `a` and `b`,
+/// depending on the convention `C`, and tropical multiplication `a * b` is the
+/// ordinary sum of `a` and `b`. The tropical zero is the infinite element,
+/// represented by `None`, and the tropical one is `Some(0)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tropical<T, C> {
+    pub value: Option<T>,
+    convention: PhantomData<C>,
+}
+
+pub type MinPlus<T> = Tropical<T, Min>;
+pub type MaxPlus<T> = Tropical<T, Max>;
+
+/// The tropical semiring over `T` with the convention `C`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TropicalSemiring<T, C> {
+    phantom: PhantomData<(T, C)>,
+}
+
+/// The ordered additive groups over which tropical semirings are formed.
+pub trait TropicalValue: Clone + PartialOrd + Add<Output = Self> + Zero {}
+impl<T> TropicalValue for T where T: Clone + PartialOrd + Add<Output = Self> + Zero {}
+
+impl<T: TropicalValue, C: Convention> Semiring<Tropical<T, C>> for TropicalSemiring<T, C> {}
+impl<T: TropicalValue, C: Convention> SemiringElement for Tropical<T, C> {}
+
+impl<T, C> Tropical<T, C> {
+    /// The finite tropical number `x`.
+    pub fn new(x: T) -> Self {
+        Self {
+            value: Some(x),
+            convention: PhantomData,
+        }
+    }
+
+    /// The infinite tropical number, which is the tropical zero.
+    pub fn infinity() -> Self {
+        Self {
+            value: None,
+            convention: PhantomData,
+        }
+    }
+}
+
+impl<T: TropicalValue, C: Convention> Add for Tropical<T, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match (&self.value, &rhs.value) {
+            (None, _) => rhs,
+            (_, None) => self,
+            (Some(a), Some(b)) => {
+                if C::prefers(b, a) {
+                    rhs
+                } else {
+                    self
+                }
+            }
+        }
+    }
+}
+
+impl<T: TropicalValue, C: Convention> Mul for Tropical<T, C> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        match (self.value, rhs.value) {
+            (Some(a), Some(b)) => Self::new(a + b),
+            _ => Self::infinity(),
+        }
+    }
+}
+
+impl<T: TropicalValue, C: Convention> AddAssign for Tropical<T, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::infinity()) + rhs;
+    }
+}
+
+impl<T: TropicalValue, C: Convention> MulAssign for Tropical<T, C> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::infinity()) * rhs;
+    }
+}
+
+impl<T: TropicalValue, C: Convention> Zero for Tropical<T, C> {
+    fn zero() -> Self {
+        Self::infinity()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+impl<T: TropicalValue, C: Convention> One for Tropical<T, C> {
+    fn one() -> Self {
+        Self::new(T::zero())
+    }
+}
+
+impl<T: Display, C: Convention> Display for Tropical<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(x) => Display::fmt(x, f),
+            None if f.sign_plus() && !C::INFINITY.starts_with('-') => {
+                write!(f, "+{}", C::INFINITY)
+            }
+            None => write!(f, "{}", C::INFINITY),
+        }
+    }
+}
+
+impl<R, V, K, P, S> Polynomial<'_, R, V, K, P, S>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher + Default,
+{
+    /// The tropicalization of `self` in `ring`, a min-plus polynomial in the
+    /// same variables, in which each term `c*x^a` becomes `v(c) ⊙ x^a` for
+    /// the valuation `v = valuation`.
+    ///
+    /// With the trivial valuation `|_| 0`, the result depends only on the
+    /// support of `self`.
+    pub fn tropicalize<'b, T: TropicalValue>(
+        &self,
+        ring: &'b PolynomialRing<'b, TropicalSemiring<T, Min>, V>,
+        mut valuation: impl FnMut(&K) -> T,
+    ) -> Polynomial<'b, TropicalSemiring<T, Min>, V, MinPlus<T>, P, S> {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(m, c)| (m.clone(), MinPlus::new(valuation(c))))
+            .collect();
+        Polynomial {
+            elem_of: ring,
+            terms,
+        }
+    }
+}
+
+/// The `p`-adic valuation of the nonzero rational `q`: the exponent of `p`
+/// in its factorization.
+pub fn p_adic_valuation(q: &BigRational, p: &BigInt) -> i64 {
+    let count = |mut n: BigInt| {
+        let mut v = 0;
+        while !n.is_zero() && (&n % p).is_zero() {
+            n /= p;
+            v += 1;
+        }
+        v
+    };
+    count(q.numer().clone()) - count(q.denom().clone())
+}
+
+/// A maximal cell of the regular subdivision of the Newton polytope of a
+/// tropical polynomial induced by its coefficients, dual to a vertex of its
+/// tropical hypersurface.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubdivisionCell {
+    /// The exponents of the terms in the cell, sorted.
+    pub exponents: Vec<Vec<i64>>,
+    /// The dual vertex, the point at which exactly the terms of the cell
+    /// attain the minimum.
+    pub vertex: Vec<BigRational>,
+}
+
+/// Where an edge of a tropical hypersurface ends.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EdgeEnd {
+    /// At the vertex dual to the cell with this index.
+    Vertex(usize),
+    /// Nowhere: the edge is a ray in this primitive direction.
+    Ray(Vec<i64>),
+}
+
+/// An edge of a tropical hypersurface, dual to a face of codimension one
+/// of the subdivision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TropicalEdge {
+    /// The index of the cell dual to the vertex the edge starts from.
+    pub start: usize,
+    pub end: EdgeEnd,
+    /// The exponents of the terms on the dual face, which attain the
+    /// minimum along the edge.
+    pub dual: Vec<Vec<i64>>,
+    /// For a curve, in two variables, the lattice length of the dual edge,
+    /// which is the multiplicity of the edge.
+    pub weight: Option<u64>,
+}
+
+/// The vertices and edges of a tropical hypersurface, with the dual
+/// subdivision. In two variables the edges make up the whole tropical
+/// curve; in more they are its one-dimensional skeleton.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TropicalHypersurface {
+    /// The cells of the dual subdivision, dual to the vertices.
+    pub cells: Vec<SubdivisionCell>,
+    pub edges: Vec<TropicalEdge>,
+}
+
+impl<V, P, S> Polynomial<'_, TropicalSemiring<i64, Min>, V, MinPlus<i64>, P, S>
+where
+    P: Hash + PrimInt + Unsigned,
+    S: BuildHasher,
+{
+    /// The regular subdivision of the Newton polytope of `self` induced by
+    /// lifting each exponent to the height of its coefficient, as the
+    /// projections of the lower facets of the lifted points.
+    ///
+    /// Fails with an `InvalidInput` error unless the Newton polytope is
+    /// full-dimensional.
+    pub fn dual_subdivision(&self) -> Result<Vec<SubdivisionCell>, ChidogError> {
+        let n = self.elem_of.vars.len();
+        let mut lifted: Vec<Vec<i64>> = self
+            .terms
+            .iter()
+            .filter_map(|(m, c)| {
+                let mut point: Vec<i64> = m.powers.iter().map(|p| p.to_i64().unwrap()).collect();
+                point.push(c.value?);
+                Some(point)
+            })
+            .collect();
+        if Polytope::new(n, lifted.iter().map(|p| p[..n].to_vec()).collect())?
+            .facets()
+            .is_empty()
+        {
+            return Err(ChidogError::InvalidInput(
+                "the Newton polytope is not full-dimensional".to_string(),
+            ));
+        }
+        // A point above all others adds only upper facets, and makes the
+        // lifted points span the whole space even if they lie in a
+        // hyperplane.
+        let top = lifted.iter().map(|p| p[n]).max().unwrap();
+        let mut above = lifted[0].clone();
+        above[n] = top + 1;
+        lifted.push(above);
+        let cells = Polytope::new(n + 1, lifted)?
+            .facets()
+            .into_iter()
+            .filter(|f| f.normal[n] < 0)
+            .map(|f| {
+                // On the facet, `normal[..n]·a + normal[n]*c = offset`, so the
+                // terms `c + w·a` with `w = -normal[..n]/u` for `u =
+                // -normal[n]` attain their minimum `-offset/u` there.
+                let u = BigInt::from(-f.normal[n]);
+                let vertex = f.normal[..n]
+                    .iter()
+                    .map(|a| BigRational::new(BigInt::from(-a), u.clone()))
+                    .collect();
+                let exponents = f.points.into_iter().map(|mut p| {
+                    p.pop();
+                    p
+                });
+                SubdivisionCell {
+                    exponents: exponents.collect(),
+                    vertex,
+                }
+            })
+            .collect();
+        Ok(cells)
+    }
+
+    /// The tropical hypersurface of `self`, the points at which the minimum
+    /// of its terms is attained at least twice, as the vertices dual to the
+    /// cells of [`Polynomial::dual_subdivision`] and the edges dual to
+    /// their facets: a facet shared by two cells joins their vertices, and
+    /// a facet on the boundary of the Newton polytope gives a ray in the
+    /// direction of its inner normal.
+    pub fn tropical_hypersurface(&self) -> Result<TropicalHypersurface, ChidogError> {
+        let n = self.elem_of.vars.len();
+        let cells = self.dual_subdivision()?;
+        // The facets of the cells, keyed by the exponents on them, with the
+        // cells they bound and the inner normal in the first of them.
+        type Face = (Vec<Vec<i64>>, Vec<usize>, Vec<i64>);
+        let mut faces: Vec<Face> = Vec::new();
+        for (i, cell) in cells.iter().enumerate() {
+            for facet in Polytope::new(n, cell.exponents.clone())?.facets() {
+                match faces
+                    .iter_mut()
+                    .find(|(points, _, _)| *points == facet.points)
+                {
+                    Some((_, bounded, _)) => bounded.push(i),
+                    None => {
+                        let inner = facet.normal.iter().map(|a| -a).collect();
+                        faces.push((facet.points, vec![i], inner));
+                    }
+                }
+            }
+        }
+        let edges = faces
+            .into_iter()
+            .map(|(dual, bounded, inner)| {
+                let end = match bounded[..] {
+                    [_, j] => EdgeEnd::Vertex(j),
+                    _ => EdgeEnd::Ray(inner),
+                };
+                let weight = (n == 2).then(|| {
+                    let (first, last) = (&dual[0], &dual[dual.len() - 1]);
+                    (last[0] - first[0])
+                        .unsigned_abs()
+                        .gcd(&(last[1] - first[1]).unsigned_abs())
+                });
+                TropicalEdge {
+                    start: bounded[0],
+                    end,
+                    dual,
+                    weight,
+                }
+            })
+            .collect();
+        Ok(TropicalHypersurface { cells, edges })
+    }
+}
@@ -0,0 +1,752 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{BigInt, FromPrimitive, Integer, One, PrimInt, Signed, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::control::{ComputationControl, Progress};
+use crate::error::ChidogError;
+use crate::{
+    FieldElement, Monomial, Polynomial, PolynomialRing, Ring, RingElement, Semiring,
+    SemiringElement,
+};
+
+pub mod bezout;
+pub mod bounds;
+pub mod compose;
+pub mod fft;
+pub mod multiply;
+pub mod shift;
+pub(crate) mod specialize;
+pub mod subproduct;
+
+/// A univariate polynomial stored densely, as the vector of its coefficients
+/// in order of increasing degree.
+///
+/// Unlike [`crate::Polynomial`], a dense polynomial carries no reference to a
+/// ring: coefficients are tested for zero with `Zero::is_zero`, and we
+/// maintain the guarantee that the leading coefficient is nonzero. In
+/// exchange, `DenseUnivariate<K>` is itself a [`RingElement`], so dense
+/// polynomials nest, as in `DenseUnivariate<DenseUnivariate<K>>` for `K[t][x]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DenseUnivariate<K> {
+    coeffs: Vec<K>,
+}
+
+/// The ring `K[x]` of dense univariate polynomials over the ring with
+/// elements in `K`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DenseUnivariateRing<K> {
+    phantom: PhantomData<K>,
+}
+
+impl<K: SemiringElement + Clone> Semiring<DenseUnivariate<K>> for DenseUnivariateRing<K> {}
+impl<K: RingElement + Clone> Ring<DenseUnivariate<K>> for DenseUnivariateRing<K> {}
+impl<K: SemiringElement + Clone> SemiringElement for DenseUnivariate<K> {}
+impl<K: RingElement + Clone> RingElement for DenseUnivariate<K> {}
+
+/// Ring elements supporting exact division, as in an integral domain.
+pub trait DivExact: RingElement {
+    /// The quotient `q` with `q * rhs == self`, or `None` if there is none.
+    fn div_exact(&self, rhs: &Self) -> Option<Self>;
+}
+
+impl<T: FieldElement + Clone> DivExact for T {
+    fn div_exact(&self, rhs: &Self) -> Option<Self> {
+        (!rhs.is_zero()).then(|| self.clone() / rhs.clone())
+    }
+}
+
+impl DivExact for BigInt {
+    fn div_exact(&self, rhs: &Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let (q, r) = self.div_rem(rhs);
+        r.is_zero().then_some(q)
+    }
+}
+
+impl<K: FieldElement + Clone> DivExact for DenseUnivariate<K> {
+    fn div_exact(&self, rhs: &Self) -> Option<Self> {
+        let (q, r) = self.div_rem(rhs).ok()?;
+        r.is_zero().then_some(q)
+    }
+}
+
+/// Euclidean domains, in which division with remainder makes the remainder
+/// smaller than the divisor, and every element has a normal form up to units.
+pub trait Euclidean: DivExact {
+    /// The quotient and remainder `(q, r)` with `self = q * rhs + r`, where
+    /// `r` is zero or smaller than `rhs`. The divisor must be nonzero.
+    fn div_rem_euclid(&self, rhs: &Self) -> (Self, Self);
+
+    /// Whether `self` is smaller than `other` in the Euclidean norm. Zero is
+    /// smaller than every nonzero element.
+    fn smaller_than(&self, other: &Self) -> bool;
+
+    /// A unit `u` such that `u * self` is the normal form of `self`: the
+    /// absolute value of an integer, or the monic associate of a polynomial.
+    fn normal_unit(&self) -> Self;
+}
+
+/// The remainder of integer division has the sign of the divisor, so that
+/// remainders by a positive divisor `d` lie in `0..d`.
+impl Euclidean for BigInt {
+    fn div_rem_euclid(&self, rhs: &Self) -> (Self, Self) {
+        self.div_mod_floor(rhs)
+    }
+
+    fn smaller_than(&self, other: &Self) -> bool {
+        self.magnitude() < other.magnitude()
+    }
+
+    fn normal_unit(&self) -> Self {
+        if self.is_negative() {
+            -BigInt::one()
+        } else {
+            BigInt::one()
+        }
+    }
+}
+
+impl<K: FieldElement + Clone> Euclidean for DenseUnivariate<K> {
+    fn div_rem_euclid(&self, rhs: &Self) -> (Self, Self) {
+        self.div_rem(rhs).expect("division by zero")
+    }
+
+    fn smaller_than(&self, other: &Self) -> bool {
+        self.degree() < other.degree()
+    }
+
+    fn normal_unit(&self) -> Self {
+        match self.leading_coeff() {
+            Some(lc) => Self::constant(K::one() / lc.clone()),
+            None => Self::one(),
+        }
+    }
+}
+
+impl<K: SemiringElement + Clone> DenseUnivariate<K> {
+    /// The polynomial with the given coefficients, in order of increasing
+    /// degree. Trailing zeros are discarded.
+    pub fn new(mut coeffs: Vec<K>) -> Self {
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    pub fn constant(c: K) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// The monomial `c * x^d`.
+    pub fn monomial(c: K, d: usize) -> Self {
+        let mut coeffs = vec![K::zero(); d];
+        coeffs.push(c);
+        Self::new(coeffs)
+    }
+
+    /// The variable `x`.
+    pub fn x() -> Self {
+        Self::monomial(K::one(), 1)
+    }
+
+    /// The coefficients of `self`, in order of increasing degree, with a
+    /// nonzero last entry.
+    pub fn coeffs(&self) -> &[K] {
+        &self.coeffs
+    }
+
+    /// The coefficient of `x^i`.
+    pub fn coeff(&self, i: usize) -> K {
+        self.coeffs.get(i).cloned().unwrap_or_else(K::zero)
+    }
+
+    /// The degree of `self`, or `None` for zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn leading_coeff(&self) -> Option<&K> {
+        self.coeffs.last()
+    }
+
+    /// Whether `self` is a constant, including zero.
+    pub fn is_constant(&self) -> bool {
+        self.coeffs.len() <= 1
+    }
+
+    /// Evaluates `self` at `x` by Horner's rule.
+    pub fn eval(&self, x: &K) -> K {
+        let mut value = K::zero();
+        for c in self.coeffs.iter().rev() {
+            value *= x.clone();
+            value += c.clone();
+        }
+        value
+    }
+
+    /// Applies `f` to each coefficient.
+    pub fn map<L: SemiringElement + Clone>(&self, f: impl FnMut(&K) -> L) -> DenseUnivariate<L> {
+        DenseUnivariate::new(self.coeffs.iter().map(f).collect())
+    }
+
+    /// Multiplies each coefficient by `c`.
+    pub fn scale(&self, c: &K) -> Self {
+        self.map(|a| a.clone() * c.clone())
+    }
+}
+
+impl<K: SemiringElement + Clone> DenseUnivariate<K> {
+    /// The dense form of a polynomial `f` in which only the variable with
+    /// index `var` occurs, or an `InvalidInput` error if another one does.
+    pub fn from_polynomial<R, V, P>(
+        f: &Polynomial<'_, R, V, K, P>,
+        var: usize,
+    ) -> Result<Self, ChidogError>
+    where
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut coeffs = Vec::new();
+        for (m, c) in &f.terms {
+            if m.powers
+                .iter()
+                .enumerate()
+                .any(|(i, p)| i != var && !p.is_zero())
+            {
+                return Err(ChidogError::InvalidInput(format!(
+                    "polynomial involves variables other than the one with index {var}"
+                )));
+            }
+            let d = m.powers[var].to_usize().unwrap();
+            if coeffs.len() <= d {
+                coeffs.resize(d + 1, K::zero());
+            }
+            coeffs[d] = c.clone();
+        }
+        Ok(Self::new(coeffs))
+    }
+
+    /// The sparse form of `self` in `ring`, as a polynomial in the variable
+    /// with index `var`.
+    pub fn to_polynomial<'a, R, V, P>(
+        &self,
+        ring: &'a PolynomialRing<'a, R, V>,
+        var: usize,
+    ) -> Polynomial<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let terms = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !ring.base.is_zero(c))
+            .map(|(d, c)| {
+                let mut powers = smallvec![P::zero(); ring.vars.len()];
+                powers[var] = P::from(d).unwrap();
+                (Monomial { powers }, c.clone())
+            })
+            .collect();
+        Polynomial {
+            elem_of: ring,
+            terms,
+        }
+    }
+}
+
+impl<K: SemiringElement + Clone + FromPrimitive> DenseUnivariate<K> {
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| K::from_usize(i).unwrap() * c.clone())
+                .collect(),
+        )
+    }
+}
+
+impl<K: RingElement + Clone> DenseUnivariate<K> {
+    /// The pseudo-remainder `lc(rhs)^(deg(self) - deg(rhs) + 1) * self mod
+    /// rhs`, which exists over any ring.
+    pub fn pseudo_rem(&self, rhs: &Self) -> Result<Self, ChidogError> {
+        let (db, lc) = match (rhs.degree(), rhs.leading_coeff()) {
+            (Some(db), Some(lc)) => (db, lc.clone()),
+            _ => return Err(ChidogError::DivisionByZero),
+        };
+        let Some(da) = self.degree().filter(|da| *da >= db) else {
+            return Ok(self.clone());
+        };
+        let mut r = self.clone();
+        let mut steps = 0;
+        while let Some(dr) = r.degree().filter(|dr| *dr >= db) {
+            let shift = Self::monomial(r.coeffs[dr].clone(), dr - db) * rhs.clone();
+            r = r.scale(&lc) - shift;
+            steps += 1;
+        }
+        Ok(r.scale(&num::pow(lc, da - db + 1 - steps)))
+    }
+}
+
+impl<K: FieldElement + Clone> DenseUnivariate<K> {
+    /// The quotient and remainder of `self` by `rhs`.
+    pub fn div_rem(&self, rhs: &Self) -> Result<(Self, Self), ChidogError> {
+        let (m, lc) = match (rhs.degree(), rhs.leading_coeff()) {
+            (Some(m), Some(lc)) => (m, lc.clone()),
+            _ => return Err(ChidogError::DivisionByZero),
+        };
+        let Some(n) = self.degree().filter(|n| *n >= m) else {
+            return Ok((Self::zero(), self.clone()));
+        };
+        let lc_inv = K::one() / lc;
+        let mut r = self.coeffs.clone();
+        let mut q = vec![K::zero(); n - m + 1];
+        for i in (0..=n - m).rev() {
+            let c = r[i + m].clone() * lc_inv.clone();
+            for (j, d) in rhs.coeffs.iter().enumerate() {
+                r[i + j] -= c.clone() * d.clone();
+            }
+            q[i] = c;
+        }
+        r.truncate(m);
+        Ok((Self::new(q), Self::new(r)))
+    }
+
+    /// `self` divided by its leading coefficient, or zero if `self` is zero.
+    pub fn monic(&self) -> Self {
+        match self.leading_coeff() {
+            Some(lc) => self.scale(&(K::one() / lc.clone())),
+            None => self.clone(),
+        }
+    }
+
+    /// The monic greatest common divisor of `self` and `other`, which is zero
+    /// only if both are zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        #[cfg(feature = "flint")]
+        if let Some(g) = crate::flint::gcd(self, other) {
+            return g;
+        }
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).unwrap().1;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// The monic GCD `g` of `self` and `other` together with Bézout
+    /// coefficients `(s, t)` such that `s*self + t*other = g`.
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1).unwrap();
+            r0 = std::mem::replace(&mut r1, r);
+            let s = s0 - q.clone() * s1.clone();
+            s0 = std::mem::replace(&mut s1, s);
+            let t = t0 - q * t1.clone();
+            t0 = std::mem::replace(&mut t1, t);
+        }
+        match r0.leading_coeff() {
+            Some(lc) => {
+                let lc_inv = K::one() / lc.clone();
+                (r0.scale(&lc_inv), s0.scale(&lc_inv), t0.scale(&lc_inv))
+            }
+            None => (r0, s0, t0),
+        }
+    }
+
+    /// Solves `s*self + t*other = c` with `s = 0` or `deg(s) < deg(other)`,
+    /// if a solution exists, that is, if `gcd(self, other)` divides `c`.
+    pub fn solve_bezout(&self, other: &Self, c: &Self) -> Option<(Self, Self)> {
+        let (g, s, _) = self.extended_gcd(other);
+        let c = c.div_exact(&g)?;
+        let s = if other.is_constant() {
+            Self::zero()
+        } else {
+            (s * c.clone()).div_rem(other).unwrap().1
+        };
+        let t = (c * g - s.clone() * self.clone()).div_exact(other)?;
+        Some((s, t))
+    }
+
+    /// The Padé approximant of type `[m/n]` of `self`, read as a power series
+    /// modulo `x^(m + n + 1)`: polynomials `p` and `q` with `deg(p) <= m`,
+    /// `deg(q) <= n` and `q(0) = 1` such that `q*self = p` modulo
+    /// `x^(m + n + 1)`.
+    ///
+    /// The pair is a row of the extended Euclidean algorithm on `x^(m + n +
+    /// 1)` and `self`, the first whose remainder has degree at most `m`. The
+    /// approximant does not exist, and a `Domain` error is returned, if `x`
+    /// divides the cofactor `q` of that row.
+    pub fn pade(&self, m: usize, n: usize) -> Result<(Self, Self), ChidogError> {
+        let len = m + n + 1;
+        let (mut r0, mut r1) = (
+            Self::monomial(K::one(), len),
+            Self::new(self.coeffs.iter().take(len).cloned().collect()),
+        );
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+        while r1.degree().is_some_and(|d| d > m) {
+            let (q, r) = r0.div_rem(&r1).unwrap();
+            r0 = std::mem::replace(&mut r1, r);
+            let t = t0 - q * t1.clone();
+            t0 = std::mem::replace(&mut t1, t);
+        }
+        let c = t1.coeff(0);
+        if c.is_zero() {
+            return Err(ChidogError::Domain(format!(
+                "Padé approximant of type [{m}/{n}] (the denominator vanishes at 0)"
+            )));
+        }
+        let c_inv = K::one() / c;
+        Ok((r1.scale(&c_inv), t1.scale(&c_inv)))
+    }
+}
+
+impl<K: FieldElement + Clone + FromPrimitive> DenseUnivariate<K> {
+    /// The antiderivative of `self` with zero constant term.
+    pub fn integral(&self) -> Self {
+        let mut coeffs = vec![K::zero()];
+        for (i, c) in self.coeffs.iter().enumerate() {
+            coeffs.push(c.clone() / K::from_usize(i + 1).unwrap());
+        }
+        Self::new(coeffs)
+    }
+
+    /// The squarefree decomposition of `self` by Yun's algorithm, valid in
+    /// characteristic zero: monic, pairwise coprime, squarefree polynomials
+    /// `a_1, ..., a_m` such that `self = lc * a_1 * a_2^2 * ... * a_m^m`.
+    /// Some of the `a_i` may be 1; `a_m` is not, unless `self` is constant.
+    pub fn squarefree(&self) -> Vec<Self> {
+        self.squarefree_controlled(&ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The squarefree decomposition of [`DenseUnivariate::squarefree`],
+    /// consulting `control` before each factor, with the number of factors
+    /// found, the degree left to split and the number of coefficients held,
+    /// and failing as it directs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(degree = self.degree()))
+    )]
+    pub fn squarefree_controlled(
+        &self,
+        control: &ComputationControl,
+    ) -> Result<Vec<Self>, ChidogError> {
+        if self.is_constant() {
+            return Ok(Vec::new());
+        }
+        let df = self.derivative();
+        let c = self.gcd(&df);
+        let mut w = self.div_exact(&c).unwrap();
+        let mut y = df.div_exact(&c).unwrap();
+        let mut z = y - w.derivative();
+        let mut factors: Vec<Self> = Vec::new();
+        while !w.is_constant() {
+            control.check(&Progress {
+                algorithm: "yun",
+                steps: factors.len(),
+                remaining: w.degree(),
+                terms: factors.iter().chain([&w, &z]).map(|f| f.coeffs.len()).sum(),
+            })?;
+            let g = w.gcd(&z);
+            w = w.div_exact(&g).unwrap();
+            y = z.div_exact(&g).unwrap();
+            z = y - w.derivative();
+            factors.push(g);
+        }
+        Ok(factors)
+    }
+}
+
+impl<D: DivExact + Clone> DenseUnivariate<D> {
+    /// Divides each coefficient by `c`, which must divide all of them.
+    fn div_exact_coeffs(&self, c: &D) -> Self {
+        self.map(|a| {
+            a.div_exact(c)
+                .expect("inexact division in subresultant PRS")
+        })
+    }
+
+    /// The resultant of `self` and `other`, and the subresultant polynomial
+    /// remainder sequence `R_0 = self, R_1 = other, ..., R_k` ending with its
+    /// last nonzero element, computed over the integral domain `D` as in
+    /// Bronstein, *Symbolic Integration I*, §1.5.
+    ///
+    /// Requires `deg(self) >= deg(other)` and `other != 0`.
+    pub fn subresultant_prs(&self, other: &Self) -> (D, Vec<Self>) {
+        self.subresultant_prs_controlled(other, &ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The resultant and sequence of [`DenseUnivariate::subresultant_prs`],
+    /// consulting `control` before each pseudo-remainder, with the number
+    /// computed, the degree of the last and the number of coefficients
+    /// held, and failing as it directs.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(degrees = ?(self.degree(), other.degree()))
+        )
+    )]
+    pub fn subresultant_prs_controlled(
+        &self,
+        other: &Self,
+        control: &ComputationControl,
+    ) -> Result<(D, Vec<Self>), ChidogError> {
+        let deg = |f: &Self| f.degree().unwrap();
+        let lc = |f: &Self| f.leading_coeff().unwrap().clone();
+        let neg = |d: D| D::zero() - d;
+        let minus_one = neg(D::one());
+        let mut r = vec![self.clone(), other.clone()];
+        // Index 0 of the following is unused, following the 1-based notation.
+        let mut lcs = vec![D::zero()];
+        let mut delta = vec![0, deg(self) - deg(other)];
+        let mut gamma = vec![D::zero(), minus_one.clone()];
+        let mut beta = vec![D::zero(), num::pow(minus_one, delta[1] + 1)];
+        let mut i = 1;
+        while !r[i].is_zero() {
+            control.check(&Progress {
+                algorithm: "subresultant",
+                steps: i - 1,
+                remaining: r[i].degree(),
+                terms: r.iter().map(|f| f.coeffs.len()).sum(),
+            })?;
+            lcs.push(lc(&r[i]));
+            let prem = r[i - 1].pseudo_rem(&r[i]).unwrap();
+            r.push(prem.div_exact_coeffs(&beta[i]));
+            i += 1;
+            let g = neg(lcs[i - 1].clone());
+            let g = match delta[i - 1] {
+                0 => gamma[i - 1].clone(),
+                d => num::pow(g, d)
+                    .div_exact(&num::pow(gamma[i - 1].clone(), d - 1))
+                    .unwrap(),
+            };
+            gamma.push(g);
+            if r[i].is_zero() {
+                break;
+            }
+            delta.push(deg(&r[i - 1]) - deg(&r[i]));
+            beta.push(neg(lcs[i - 1].clone()) * num::pow(gamma[i].clone(), delta[i]));
+        }
+        r.pop();
+        let k = i - 1;
+        if deg(&r[k]) > 0 {
+            return Ok((D::zero(), r));
+        }
+        if deg(&r[k - 1]) == 1 {
+            return Ok((lc(&r[k]), r));
+        }
+        let mut odd = false;
+        let (mut numer, mut denom) = (D::one(), D::one());
+        for j in 1..k {
+            let (d0, d1) = (deg(&r[j - 1]), deg(&r[j]));
+            if d0 % 2 == 1 && d1 % 2 == 1 {
+                odd = !odd;
+            }
+            let d2 = r.get(j + 1).map_or(0, deg);
+            numer *= num::pow(beta[j].clone(), d1) * num::pow(lcs[j].clone(), d0 - d2);
+            denom *= num::pow(lcs[j].clone(), (1 + delta[j]) * d1);
+        }
+        let res = (numer * num::pow(lc(&r[k]), deg(&r[k - 1])))
+            .div_exact(&denom)
+            .unwrap();
+        Ok((if odd { neg(res) } else { res }, r))
+    }
+
+    /// The resultant of `self` and `other`, which is zero if either is zero.
+    pub fn resultant(&self, other: &Self) -> D {
+        self.resultant_controlled(other, &ComputationControl::new())
+            .expect("uncontrolled computations are not interrupted")
+    }
+
+    /// The resultant of [`DenseUnivariate::resultant`], consulting `control`
+    /// as [`DenseUnivariate::subresultant_prs_controlled`] does.
+    pub fn resultant_controlled(
+        &self,
+        other: &Self,
+        control: &ComputationControl,
+    ) -> Result<D, ChidogError> {
+        Ok(match (self.degree(), other.degree()) {
+            (None, _) | (_, None) => D::zero(),
+            (Some(m), Some(n)) if m < n => {
+                let res = other.subresultant_prs_controlled(self, control)?.0;
+                if m % 2 == 1 && n % 2 == 1 {
+                    D::zero() - res
+                } else {
+                    res
+                }
+            }
+            _ => self.subresultant_prs_controlled(other, control)?.0,
+        })
+    }
+}
+
+impl<K: SemiringElement + Clone> Add for DenseUnivariate<K> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<K: SemiringElement + Clone> AddAssign for DenseUnivariate<K> {
+    fn add_assign(&mut self, rhs: Self) {
+        let mut coeffs = std::mem::take(&mut self.coeffs);
+        if coeffs.len() < rhs.coeffs.len() {
+            coeffs.resize(rhs.coeffs.len(), K::zero());
+        }
+        for (a, b) in coeffs.iter_mut().zip(rhs.coeffs) {
+            *a += b;
+        }
+        *self = Self::new(coeffs);
+    }
+}
+
+impl<K: RingElement + Clone> Sub for DenseUnivariate<K> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K: RingElement + Clone> SubAssign for DenseUnivariate<K> {
+    fn sub_assign(&mut self, rhs: Self) {
+        let mut coeffs = std::mem::take(&mut self.coeffs);
+        if coeffs.len() < rhs.coeffs.len() {
+            coeffs.resize(rhs.coeffs.len(), K::zero());
+        }
+        for (a, b) in coeffs.iter_mut().zip(rhs.coeffs) {
+            *a -= b;
+        }
+        *self = Self::new(coeffs);
+    }
+}
+
+impl<K: RingElement + Clone> Neg for DenseUnivariate<K> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl<K: SemiringElement + Clone> Mul for DenseUnivariate<K> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return Self::zero();
+        }
+        #[cfg(feature = "flint")]
+        if let Some(product) = crate::flint::mul(&self, &rhs) {
+            return product;
+        }
+        multiply::mul_specialized(&self, &rhs)
+            .unwrap_or_else(|| Self::new(multiply::schoolbook(&self.coeffs, &rhs.coeffs)))
+    }
+}
+
+impl<K: SemiringElement + Clone> MulAssign for DenseUnivariate<K> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::take(self) * rhs;
+    }
+}
+
+impl<K> Default for DenseUnivariate<K> {
+    fn default() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+}
+
+impl<K: SemiringElement + Clone> Zero for DenseUnivariate<K> {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+}
+
+impl<K: SemiringElement + Clone> One for DenseUnivariate<K> {
+    fn one() -> Self {
+        Self::constant(K::one())
+    }
+}
+
+/// A dense polynomial displayed in a given variable; see
+/// [`DenseUnivariate::display`].
+pub struct DisplayIn<'a, K> {
+    f: &'a DenseUnivariate<K>,
+    var: &'a str,
+}
+
+impl<K> DenseUnivariate<K> {
+    /// Displays `self` as a polynomial in the variable `var`, rather than the
+    /// default `x`.
+    pub fn display<'a>(&'a self, var: &'a str) -> DisplayIn<'a, K> {
+        DisplayIn { f: self, var }
+    }
+}
+
+impl<K: Display + Zero + One + PartialEq> Display for DisplayIn<'_, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let var = self.var;
+        let terms = self.f.coeffs.iter().enumerate().rev();
+        let mut first = true;
+        for (i, c) in terms.filter(|(_, c)| !c.is_zero()) {
+            let sign_plus = !first || f.sign_plus();
+            if i == 0 || !c.is_one() {
+                if sign_plus {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                if i > 0 {
+                    write!(f, "*")?;
+                }
+            } else if sign_plus {
+                write!(f, "+")?;
+            }
+            match i {
+                0 => {}
+                1 => write!(f, "{var}")?,
+                _ => write!(f, "{var}^{i}")?,
+            }
+            first = false;
+        }
+        if first {
+            let zero = K::zero();
+            if f.sign_plus() {
+                write!(f, "{zero:+}")?;
+            } else {
+                write!(f, "{zero}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K: Display + Zero + One + PartialEq> Display for DenseUnivariate<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.display("x"), f)
+    }
+}
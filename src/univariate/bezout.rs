@@ -0,0 +1,111 @@
+use num::FromPrimitive;
+
+use crate::matrix::Matrix;
+use crate::univariate::{DenseUnivariate, DivExact};
+use crate::{FieldElement, RingElement};
+
+impl<K: RingElement + Clone> DenseUnivariate<K> {
+    /// The Bezout matrix `B` of `f = self` and `g = other`, of size `n`, the
+    /// larger of their degrees, whose entries are the coefficients of the
+    /// Bezoutian `(f(x)*g(y) - f(y)*g(x))/(x - y) = ∑ B_(i,j)*x^i*y^j`.
+    ///
+    /// It is symmetric, and its determinant is the resultant up to sign and
+    /// a power of a leading coefficient, as computed by
+    /// [`DenseUnivariate::bezout_resultant`], while its size is only the
+    /// larger degree rather than the sum of the degrees of the Sylvester
+    /// matrix. Up to sign and the zero rows and columns it leaves out, the
+    /// Dixon matrix of `f` and `g` in one variable, as built by
+    /// [`dixon_matrix`], is this matrix, and it is the Bezoutian of several
+    /// polynomials in several variables.
+    ///
+    /// [`dixon_matrix`]: crate::resultant::dixon_matrix
+    pub fn bezout_matrix(&self, other: &Self) -> Matrix<K> {
+        let n = self.coeffs.len().max(other.coeffs.len()).saturating_sub(1);
+        let (f, g) = (|i| self.coeff(i), |i| other.coeff(i));
+        // The numerator as a polynomial in `x` with coefficients
+        // `N_a(y) = ∑ (f_a*g_b - f_b*g_a)*y^b`, divided synthetically by
+        // `x - y`: the quotient has `Q_(n-1) = N_n` and
+        // `Q_(a-1) = N_a + y*Q_a`.
+        let numerator = |a: usize| {
+            (0..=n)
+                .map(move |b| f(a) * g(b) - f(b) * g(a))
+                .collect::<Vec<K>>()
+        };
+        let mut matrix = Matrix::zero(n, n);
+        let mut quotient = vec![K::zero(); n + 1];
+        for a in (1..=n).rev() {
+            let mut next = numerator(a);
+            for b in 1..=n {
+                next[b] += quotient[b - 1].clone();
+            }
+            for (b, c) in next.iter().take(n).enumerate() {
+                matrix[(a - 1, b)] = c.clone();
+            }
+            quotient = next;
+        }
+        matrix
+    }
+}
+
+impl<D: DivExact + Clone> DenseUnivariate<D> {
+    /// The resultant of `f = self` and `g = other` from the determinant of
+    /// their Bezout matrix, which is `(-1)^(n(n-1)/2)*lc(f)^(n-m)` times the
+    /// resultant for `n = deg(f) ≥ m = deg(g)`, and zero if either is zero.
+    pub fn bezout_resultant(&self, other: &Self) -> D {
+        let (Some(n), Some(m)) = (self.degree(), other.degree()) else {
+            return D::zero();
+        };
+        if n < m {
+            let res = other.bezout_resultant(self);
+            return if n % 2 == 1 && m % 2 == 1 {
+                D::zero() - res
+            } else {
+                res
+            };
+        }
+        if n == 0 {
+            return D::one();
+        }
+        let det = self.bezout_matrix(other).det();
+        let det = match (n * (n - 1) / 2) % 2 {
+            0 => det,
+            _ => D::zero() - det,
+        };
+        det.div_exact(&num::pow(self.leading_coeff().unwrap().clone(), n - m))
+            .expect("the leading coefficient divides the Bezout determinant")
+    }
+}
+
+impl<K: RingElement + Clone + FromPrimitive + PartialOrd> DenseUnivariate<K> {
+    /// The number of distinct real roots of `self`, for coefficients in an
+    /// ordered subring of the real numbers, by the theorem of Hermite: the
+    /// signature of the Bezout matrix of `f` and `f'`.
+    pub fn count_real_roots(&self) -> usize {
+        if self.degree().unwrap_or(0) == 0 {
+            return 0;
+        }
+        self.bezout_matrix(&self.derivative()).signature() as usize
+    }
+}
+
+impl<K: FieldElement + Clone + FromPrimitive + PartialOrd> DenseUnivariate<K> {
+    /// The number of distinct real roots of `self` at which `g` is
+    /// positive, for coefficients in an ordered subfield of the real
+    /// numbers.
+    ///
+    /// The signature of the Bezout matrix of `f` and `f'*h mod f` is the
+    /// Cauchy index counting the real roots of `f` with `h` positive minus
+    /// those with `h` negative, so this is half the sum of those for `h = g`
+    /// and `h = g^2`.
+    pub fn count_real_roots_where_positive(&self, g: &Self) -> usize {
+        if self.degree().unwrap_or(0) == 0 {
+            return 0;
+        }
+        let derivative = self.derivative();
+        let signature = |h: Self| {
+            let h = (derivative.clone() * h).div_rem(self).unwrap().1;
+            self.bezout_matrix(&h).signature()
+        };
+        ((signature(g.clone()) + signature(g.clone() * g.clone())) / 2) as usize
+    }
+}
@@ -0,0 +1,147 @@
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+use crate::univariate::DenseUnivariate;
+
+/// The number of fractional bits kept by [`root_ceiling`].
+const PRECISION: usize = 32;
+
+/// A rational upper bound on `r^(1/k)` for `r ≥ 0` and `k > 0`, exact when
+/// the root is a rational with denominator dividing that of `r` times
+/// `2^PRECISION`, and otherwise above it by at most that reciprocal.
+fn root_ceiling(r: &BigRational, k: usize) -> BigRational {
+    if k == 1 {
+        return r.clone();
+    }
+    // `r^(1/k) = (p*q^(k-1)*2^(k*s))^(1/k)/(q*2^s)` for `r = p/q`.
+    let scale = BigInt::one() << PRECISION;
+    let radicand = r.numer() * num::pow(r.denom().clone(), k - 1) * num::pow(scale.clone(), k);
+    let mut root = radicand.nth_root(k as u32);
+    if num::pow(root.clone(), k) != radicand {
+        root += 1;
+    }
+    BigRational::new(root, r.denom() * scale)
+}
+
+impl DenseUnivariate<BigRational> {
+    /// The ratios `|a_i/a_n|` of the coefficients below the leading one to
+    /// it, by increasing `i`, or `None` for the zero polynomial.
+    fn ratios(&self) -> Option<Vec<BigRational>> {
+        let n = self.degree()?;
+        let lc = self.coeffs[n].clone();
+        Some(self.coeffs[..n].iter().map(|a| (a / &lc).abs()).collect())
+    }
+
+    /// The bound `1 + max |a_i/a_n|` of Cauchy on the magnitudes of the
+    /// complex roots of `self = ∑ a_i*x^i` of degree `n`, or `None` for the
+    /// zero polynomial.
+    pub fn cauchy_bound(&self) -> Option<BigRational> {
+        let ratios = self.ratios()?;
+        Some(BigRational::one() + ratios.into_iter().max().unwrap_or_else(BigRational::zero))
+    }
+
+    /// The bound `max(1, ∑ |a_i/a_n|)` of Lagrange on the magnitudes of the
+    /// complex roots of `self = ∑ a_i*x^i` of degree `n`, or `None` for the
+    /// zero polynomial.
+    pub fn lagrange_bound(&self) -> Option<BigRational> {
+        let sum: BigRational = self.ratios()?.into_iter().sum();
+        Some(sum.max(BigRational::one()))
+    }
+
+    /// The bound of Fujiwara on the magnitudes of the complex roots of
+    /// `self = ∑ a_i*x^i` of degree `n`,
+    /// `2*max(|a_(n-1)/a_n|, |a_(n-2)/a_n|^(1/2), ..., |a_0/(2*a_n)|^(1/n))`,
+    /// or `None` for the zero polynomial.
+    ///
+    /// It is within a factor `2n` of the largest magnitude, unlike the
+    /// bounds of Cauchy and Lagrange, which are never below one. The
+    /// radicals are rounded up to rationals, so the bound may exceed the
+    /// exact one slightly.
+    pub fn fujiwara_bound(&self) -> Option<BigRational> {
+        let mut ratios = self.ratios()?;
+        let n = ratios.len();
+        if let Some(a0) = ratios.first_mut() {
+            *a0 /= BigInt::from(2);
+        }
+        let max = ratios
+            .iter()
+            .enumerate()
+            .map(|(i, r)| root_ceiling(r, n - i))
+            .max()
+            .unwrap_or_else(BigRational::zero);
+        Some(max * BigInt::from(2))
+    }
+
+    /// For each negative coefficient `a_i` of `self` made to have a
+    /// positive leading coefficient `a_n`, the ratio `|a_i|/a_n` and the
+    /// gap `n - i`, or `None` for the zero polynomial.
+    fn negative_ratios(&self) -> Option<Vec<(BigRational, usize)>> {
+        let n = self.degree()?;
+        let lc = self.coeffs[n].clone();
+        Some(
+            self.coeffs[..n]
+                .iter()
+                .enumerate()
+                .map(|(i, a)| (a / &lc, n - i))
+                .filter(|(r, _)| r.is_negative())
+                .map(|(r, gap)| (-r, gap))
+                .collect(),
+        )
+    }
+
+    /// The bound of Cauchy on the positive real roots of `self`,
+    /// `max (λ*|a_i|/a_n)^(1/(n-i))` over the `λ` coefficients `a_i` of sign
+    /// opposite to the leading one `a_n`, or `None` for the zero
+    /// polynomial.
+    ///
+    /// It is zero if there are no such coefficients, when by the rule of
+    /// signs of Descartes there are no positive roots. The radicals are
+    /// rounded up to rationals.
+    pub fn cauchy_positive_root_bound(&self) -> Option<BigRational> {
+        let ratios = self.negative_ratios()?;
+        let count = BigRational::from_integer(ratios.len().into());
+        Some(
+            ratios
+                .iter()
+                .map(|(r, gap)| root_ceiling(&(r * &count), *gap))
+                .max()
+                .unwrap_or_else(BigRational::zero),
+        )
+    }
+
+    /// The bound of Kioustelidis on the positive real roots of `self`,
+    /// `2*max (|a_i|/a_n)^(1/(n-i))` over the coefficients `a_i` of sign
+    /// opposite to the leading one `a_n`, or `None` for the zero
+    /// polynomial.
+    ///
+    /// It is zero if there are no such coefficients, and the radicals are
+    /// rounded up to rationals, as for
+    /// [`DenseUnivariate::cauchy_positive_root_bound`].
+    pub fn kioustelidis_positive_root_bound(&self) -> Option<BigRational> {
+        let max = self
+            .negative_ratios()?
+            .iter()
+            .map(|(r, gap)| root_ceiling(r, *gap))
+            .max()
+            .unwrap_or_else(BigRational::zero);
+        Some(max * BigInt::from(2))
+    }
+
+    /// The least of the bounds of Cauchy and Kioustelidis on the positive
+    /// real roots of `self`, or `None` for the zero polynomial.
+    pub fn positive_root_bound(&self) -> Option<BigRational> {
+        self.cauchy_positive_root_bound()
+            .min(self.kioustelidis_positive_root_bound())
+    }
+
+    /// A positive lower bound on the positive real roots of `self`, the
+    /// reciprocal of [`DenseUnivariate::positive_root_bound`] of the
+    /// reversed polynomial `x^n*f(1/x)` once the factors `x` of `f` are
+    /// removed, or `None` if the coefficients show by the rule of signs
+    /// that there are no positive roots, as for the zero polynomial.
+    pub fn positive_root_lower_bound(&self) -> Option<BigRational> {
+        let low = self.coeffs.iter().position(|c| !c.is_zero())?;
+        let reversed = DenseUnivariate::new(self.coeffs[low..].iter().rev().cloned().collect());
+        let bound = reversed.positive_root_bound()?;
+        (!bound.is_zero()).then(|| bound.recip())
+    }
+}
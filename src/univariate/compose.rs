@@ -0,0 +1,54 @@
+use num::{One, Zero};
+
+use crate::FieldElement;
+use crate::error::ChidogError;
+use crate::matrix::Matrix;
+use crate::univariate::DenseUnivariate;
+use crate::univariate::subproduct::{NewtonDivisor, rem_newton};
+
+impl<K: FieldElement + Clone> DenseUnivariate<K> {
+    /// The modular composition `f(g) mod h` of `f = self`, by the
+    /// baby-step/giant-step algorithm of Brent and Kung.
+    ///
+    /// With `m` about the square root of the number of coefficients of `f`,
+    /// write `f = ∑ f_i*x^(m*i)` with each `f_i` of degree less than `m`. The
+    /// baby steps are the powers `g^j mod h` for `j < m`, and the values
+    /// `f_i(g) mod h` are the rows of one matrix product with them. The giant
+    /// steps evaluate `∑ f_i(g)*(g^m)^i mod h` by Horner's rule, for `O(√n)`
+    /// modular multiplications in all instead of `O(n)`. Each of those is a
+    /// fast product followed by a remainder by Newton iteration, with the
+    /// inverse it needs computed once for `h`.
+    pub fn compose_mod(&self, g: &Self, h: &Self) -> Result<Self, ChidogError> {
+        let n = h.degree().ok_or(ChidogError::DivisionByZero)?;
+        if n == 0 || self.is_zero() {
+            return Ok(Self::zero());
+        }
+        // Every product below is of two polynomials of degree less than `n`.
+        let divisor = NewtonDivisor::new(h, n - 1);
+        let g = rem_newton(g, h);
+        let m = (self.coeffs.len() as f64).sqrt().ceil() as usize;
+        let mut baby = vec![Self::one()];
+        for j in 1..=m {
+            baby.push(divisor.rem(&baby[j - 1].mul_fast(&g)));
+        }
+        let giant = baby.pop().unwrap();
+
+        let blocks = self.coeffs.len().div_ceil(m);
+        let a = Matrix::new(blocks, m, (0..blocks * m).map(|k| self.coeff(k)).collect()).unwrap();
+        let b = Matrix::new(
+            m,
+            n,
+            baby.iter()
+                .flat_map(|p| (0..n).map(move |k| p.coeff(k)))
+                .collect(),
+        )
+        .unwrap();
+        let values = a * b;
+
+        let mut result = Self::zero();
+        for i in (0..blocks).rev() {
+            result = divisor.rem(&result.mul_fast(&giant)) + Self::new(values.row(i).to_vec());
+        }
+        Ok(result)
+    }
+}
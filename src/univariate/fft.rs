@@ -0,0 +1,371 @@
+use std::f64::consts::PI;
+use std::sync::Mutex;
+
+use num::complex::Complex64;
+use num::{BigInt, Integer, One, ToPrimitive, Zero};
+
+use crate::multimodular::{Modular, PrimeField};
+use crate::univariate::DenseUnivariate;
+
+/// Below this many coefficients in the smaller factor, Toom–Cook and
+/// Karatsuba multiplication are faster than a transform.
+const TRANSFORM_CUTOFF: usize = 32;
+
+/// The 2-adic valuation of `p - 1` for the primes of [`ntt_primes`], so that
+/// transforms of length up to `2^32` exist.
+const TWO_ADICITY: u32 = 32;
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, p: u64) -> u64 {
+    let mut r = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = mul_mod(r, a, p);
+        }
+        a = mul_mod(a, a, p);
+        e >>= 1;
+    }
+    r
+}
+
+/// Whether `n` is prime, by the Miller–Rabin test with the first twelve
+/// primes as bases, which is deterministic below `3.3 * 10^24`.
+pub(crate) fn is_prime_u64(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&b) = BASES.iter().find(|&&b| n.is_multiple_of(b)) {
+        return n == b;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A prime `p = c*2^32 + 1` below `2^62`, with a generator of the
+/// multiplicative group modulo `p`.
+#[derive(Clone, Copy, Debug)]
+struct NttPrime {
+    p: u64,
+    generator: u64,
+}
+
+impl NttPrime {
+    /// The prime `c*2^32 + 1` with a generator, if it is prime.
+    fn new(c: u64) -> Option<Self> {
+        let p = (c << TWO_ADICITY) + 1;
+        if !is_prime_u64(p) {
+            return None;
+        }
+        let mut factors = vec![2];
+        let mut rest = c;
+        let mut q = 2;
+        while q * q <= rest {
+            if rest.is_multiple_of(q) {
+                factors.push(q);
+                while rest.is_multiple_of(q) {
+                    rest /= q;
+                }
+            }
+            q += 1;
+        }
+        if rest > 1 {
+            factors.push(rest);
+        }
+        let generator = (2..p)
+            .find(|&g| factors.iter().all(|q| pow_mod(g, (p - 1) / q, p) != 1))
+            .unwrap();
+        Some(Self { p, generator })
+    }
+
+    /// A primitive `n`th root of unity, for `n` a power of two.
+    fn root_of_unity(&self, n: usize) -> u64 {
+        pow_mod(self.generator, (self.p - 1) / n as u64, self.p)
+    }
+
+    /// The cyclic convolution of `a` and `b` modulo `p`, of length `n`, a
+    /// power of two.
+    fn convolve(&self, a: &[u64], b: &[u64], n: usize) -> Vec<u64> {
+        let w = self.root_of_unity(n);
+        let mut fa = a.to_vec();
+        let mut fb = b.to_vec();
+        fa.resize(n, 0);
+        fb.resize(n, 0);
+        ntt(&mut fa, w, self.p);
+        ntt(&mut fb, w, self.p);
+        for (x, y) in fa.iter_mut().zip(&fb) {
+            *x = mul_mod(*x, *y, self.p);
+        }
+        ntt(&mut fa, pow_mod(w, self.p - 2, self.p), self.p);
+        let n_inv = pow_mod(n as u64, self.p - 2, self.p);
+        for x in fa.iter_mut() {
+            *x = mul_mod(*x, n_inv, self.p);
+        }
+        fa
+    }
+}
+
+/// The first `count` primes `c*2^32 + 1` below `2^62`, each above `2^61`,
+/// in decreasing order.
+///
+/// Finding them takes a primality test for each candidate `c`, so the
+/// primes found so far are kept for later calls.
+fn ntt_primes(count: usize) -> Vec<NttPrime> {
+    static FOUND: Mutex<Vec<NttPrime>> = Mutex::new(Vec::new());
+    let mut found = FOUND.lock().unwrap();
+    if found.len() < count {
+        let next = found.last().map_or(1 << 30, |q| q.p >> TWO_ADICITY);
+        let more: Vec<_> = (1u64 << 29..next)
+            .rev()
+            .filter_map(NttPrime::new)
+            .take(count - found.len())
+            .collect();
+        found.extend(more);
+    }
+    found[..count].to_vec()
+}
+
+/// Permutes `a` into bit-reversed order.
+fn bit_reverse<T>(a: &mut [T]) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+}
+
+/// The number-theoretic transform of `a` in place, the values at the powers
+/// of `w`, a primitive root of unity of order `a.len()` modulo `p`.
+fn ntt(a: &mut [u64], w: u64, p: u64) {
+    let n = a.len();
+    bit_reverse(a);
+    let mut len = 2;
+    while len <= n {
+        let step = pow_mod(w, (n / len) as u64, p);
+        for chunk in a.chunks_mut(len) {
+            let mut t = 1;
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                let u = *x;
+                let v = mul_mod(*y, t, p);
+                *x = if u + v >= p { u + v - p } else { u + v };
+                *y = if u >= v { u - v } else { u + p - v };
+                t = mul_mod(t, step, p);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// The discrete Fourier transform of `a` in place, with `inverse` selecting
+/// the conjugate roots of unity (without the division by `a.len()`).
+fn fft(a: &mut [Complex64], inverse: bool) {
+    let n = a.len();
+    bit_reverse(a);
+    let sign = if inverse { 1.0 } else { -1.0 };
+    // The powers of a primitive `n`th root of unity, each computed directly
+    // rather than by repeated multiplication, which would accumulate error.
+    let roots: Vec<Complex64> = (0..n / 2)
+        .map(|k| Complex64::from_polar(1.0, sign * 2.0 * PI * k as f64 / n as f64))
+        .collect();
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (k, (x, y)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let t = roots[k * stride] * *y;
+                let u = *x;
+                *x = u + t;
+                *y = u - t;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+impl DenseUnivariate<BigInt> {
+    /// The product of `self` and `rhs` by number-theoretic transforms.
+    ///
+    /// The coefficients are reduced modulo enough primes `p = c*2^32 + 1`
+    /// that the product of the primes exceeds twice the largest possible
+    /// coefficient of the result, the residues are multiplied by cyclic
+    /// convolution modulo each prime, and the coefficients are recovered in
+    /// the symmetric range by the Chinese remainder theorem.
+    pub fn mul_ntt(&self, rhs: &Self) -> Self {
+        if self.coeffs.len().min(rhs.coeffs.len()) < TRANSFORM_CUTOFF {
+            return self.mul_toom3(rhs);
+        }
+        let len = self.coeffs.len() + rhs.coeffs.len() - 1;
+        let n = len.next_power_of_two();
+        let bits = |f: &Self| f.coeffs.iter().map(|c| c.bits()).max().unwrap_or(0);
+        let bound =
+            bits(self) + bits(rhs) + self.coeffs.len().min(rhs.coeffs.len()).ilog2() as u64 + 2;
+        let primes = ntt_primes(bound.div_ceil(61) as usize);
+
+        let residues = |f: &Self, p: u64| -> Vec<u64> {
+            let p = BigInt::from(p);
+            f.coeffs
+                .iter()
+                .map(|c| c.mod_floor(&p).to_u64().unwrap())
+                .collect()
+        };
+        let products: Vec<Vec<u64>> = primes
+            .iter()
+            .map(|q| q.convolve(&residues(self, q.p), &residues(rhs, q.p), n))
+            .collect();
+
+        // Garner's algorithm, with the partial products of the primes and
+        // their inverses modulo the next prime computed once.
+        let mut moduli = vec![BigInt::one()];
+        let mut inverses = Vec::with_capacity(primes.len());
+        for (i, q) in primes.iter().enumerate() {
+            let m = (&moduli[i] % q.p).to_u64().unwrap();
+            inverses.push(pow_mod(m, q.p - 2, q.p));
+            moduli.push(&moduli[i] * q.p);
+        }
+        let modulus = moduli.last().unwrap();
+        let half = modulus >> 1;
+        let coeffs = (0..len)
+            .map(|k| {
+                let mut x = BigInt::zero();
+                for (i, q) in primes.iter().enumerate() {
+                    let r = (&x % q.p).to_u64().unwrap();
+                    let d = (products[i][k] + q.p - r) % q.p;
+                    x += &moduli[i] * mul_mod(d, inverses[i], q.p);
+                }
+                if x > half { x - modulus } else { x }
+            })
+            .collect();
+        Self::new(coeffs)
+    }
+}
+
+impl DenseUnivariate<Modular> {
+    /// The product of `self` and `rhs` by number-theoretic transforms, for
+    /// residues modulo a prime `p`.
+    ///
+    /// Taking the residues in `[0, p)`, the coefficients of the product over
+    /// the integers are below `n*p^2` for `n` the length of the shorter
+    /// factor, which is less than the product of three primes `c*2^32 + 1`.
+    /// The residues are multiplied by cyclic convolution modulo each of
+    /// those, and each coefficient is recovered modulo `p` by Garner's
+    /// algorithm in machine words.
+    pub fn mul_ntt(&self, rhs: &Self) -> Self {
+        let modulus = self
+            .coeffs
+            .iter()
+            .chain(&rhs.coeffs)
+            .find_map(Modular::modulus);
+        let Some(field) = modulus.and_then(|p| PrimeField::new(p).ok()) else {
+            return self.mul_karatsuba(rhs);
+        };
+        if self.coeffs.len().min(rhs.coeffs.len()) < TRANSFORM_CUTOFF {
+            return self.mul_karatsuba(rhs);
+        }
+        let p = field.p();
+        let len = self.coeffs.len() + rhs.coeffs.len() - 1;
+        let n = len.next_power_of_two();
+        let one = field.element(1);
+        let residues = |f: &Self, q: u64| -> Vec<u64> {
+            f.coeffs.iter().map(|&c| (c * one).value() % q).collect()
+        };
+        let primes = ntt_primes(3);
+        let products: Vec<Vec<u64>> = primes
+            .iter()
+            .map(|q| q.convolve(&residues(self, q.p), &residues(rhs, q.p), n))
+            .collect();
+
+        // Garner's algorithm with the mixed-radix digits kept modulo each
+        // prime: `partial[i][j]` is the product of the first `j` primes
+        // modulo the `i`th, and the last row is modulo `p`.
+        let moduli: Vec<u64> = primes.iter().map(|q| q.p).chain([p]).collect();
+        let partial: Vec<Vec<u64>> = moduli
+            .iter()
+            .map(|&m| {
+                let mut row = vec![1 % m];
+                for q in &primes {
+                    row.push(mul_mod(*row.last().unwrap(), q.p % m, m));
+                }
+                row
+            })
+            .collect();
+        let inverses: Vec<u64> = primes
+            .iter()
+            .enumerate()
+            .map(|(i, q)| pow_mod(partial[i][i], q.p - 2, q.p))
+            .collect();
+        let coeffs = (0..len)
+            .map(|k| {
+                let mut digits = Vec::with_capacity(primes.len());
+                for (i, q) in primes.iter().enumerate() {
+                    let r = digits
+                        .iter()
+                        .enumerate()
+                        .fold(0, |r, (j, &d)| (r + mul_mod(d, partial[i][j], q.p)) % q.p);
+                    let d = (products[i][k] + q.p - r) % q.p;
+                    digits.push(mul_mod(d, inverses[i], q.p));
+                }
+                let x = digits.iter().enumerate().fold(0, |x, (j, &d)| {
+                    (x + mul_mod(d, partial[primes.len()][j], p)) % p
+                });
+                field.element(x as i64)
+            })
+            .collect();
+        Self::new(coeffs)
+    }
+}
+
+impl DenseUnivariate<f64> {
+    /// The product of `self` and `rhs` by a floating-point fast Fourier
+    /// transform, for approximate work.
+    ///
+    /// The error in each coefficient is of the order of the machine epsilon
+    /// times the largest coefficients of the factors, times the logarithm of
+    /// the length, so small coefficients of the result may be lost in it.
+    /// The product `self * rhs` uses this once both factors have a thousand
+    /// coefficients or so, where it is much faster than the alternatives.
+    pub fn mul_fft(&self, rhs: &Self) -> Self {
+        if self.coeffs.len().min(rhs.coeffs.len()) < TRANSFORM_CUTOFF {
+            return self.mul_karatsuba(rhs);
+        }
+        let len = self.coeffs.len() + rhs.coeffs.len() - 1;
+        let n = len.next_power_of_two();
+        let transform = |f: &Self| {
+            let mut a: Vec<Complex64> = f.coeffs.iter().map(|&c| Complex64::new(c, 0.0)).collect();
+            a.resize(n, Complex64::zero());
+            fft(&mut a, false);
+            a
+        };
+        let mut a = transform(self);
+        for (x, y) in a.iter_mut().zip(transform(rhs)) {
+            *x *= y;
+        }
+        fft(&mut a, true);
+        Self::new(a[..len].iter().map(|z| z.re / n as f64).collect())
+    }
+}
@@ -0,0 +1,411 @@
+use std::any::TypeId;
+
+use num::{BigInt, BigRational, FromPrimitive, Integer, One};
+
+use crate::multimodular::Modular;
+use crate::univariate::specialize::{downcast, type_id, upcast};
+use crate::univariate::{DenseUnivariate, DivExact};
+use crate::{RingElement, SemiringElement};
+
+/// Below this many coefficients in the smaller factor, Karatsuba
+/// multiplication falls back to the schoolbook method.
+const KARATSUBA_CUTOFF: usize = 16;
+
+/// Below this many coefficients in the smaller factor, Toom-3 multiplication
+/// falls back to Karatsuba's method.
+const TOOM3_CUTOFF: usize = 64;
+
+/// From this many coefficients in the smaller factor, integer polynomials
+/// with coefficients of at most twice as many bits are multiplied by
+/// number-theoretic transforms. Larger coefficients make each transform
+/// modulo more primes, and move the crossover with Toom-3 up.
+const INTEGER_NTT_CUTOFF: usize = 64;
+
+/// From this many coefficients in the smaller factor, polynomials over a
+/// prime field are multiplied by number-theoretic transforms rather than by
+/// Karatsuba's method.
+const MODULAR_NTT_CUTOFF: usize = 128;
+
+/// From this many coefficients in the smaller factor, floating-point
+/// polynomials are multiplied by a fast Fourier transform. Below it, the
+/// schoolbook method is faster than both the transform and Karatsuba's
+/// method, whose extra additions cost as much as the multiplications saved.
+const FFT_CUTOFF: usize = 1024;
+
+/// Below this many coefficients in the smaller factor, the truncated
+/// schoolbook product, which does half the work of a full one, is faster
+/// than a full product by Karatsuba's method or a transform over the
+/// integers and the prime fields.
+const TRUNCATED_CUTOFF: usize = 128;
+
+pub(crate) fn schoolbook<K: SemiringElement + Clone>(a: &[K], b: &[K]) -> Vec<K> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut c = vec![K::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            c[i + j] += x.clone() * y.clone();
+        }
+    }
+    c
+}
+
+/// Adds `src*x^shift` to `dst`, extending `dst` as needed.
+fn add_shifted<K: SemiringElement + Clone>(dst: &mut Vec<K>, src: &[K], shift: usize) {
+    if dst.len() < src.len() + shift {
+        dst.resize(src.len() + shift, K::zero());
+    }
+    for (d, s) in dst[shift..].iter_mut().zip(src) {
+        *d += s.clone();
+    }
+}
+
+/// The coefficientwise sum `a + b`.
+fn add<K: SemiringElement + Clone>(a: &[K], b: &[K]) -> Vec<K> {
+    let mut c = a.to_vec();
+    add_shifted(&mut c, b, 0);
+    c
+}
+
+/// Subtracts `src*x^shift` from `dst`, extending `dst` as needed.
+fn sub_shifted<K: RingElement + Clone>(dst: &mut Vec<K>, src: &[K], shift: usize) {
+    if dst.len() < src.len() + shift {
+        dst.resize(src.len() + shift, K::zero());
+    }
+    for (d, s) in dst[shift..].iter_mut().zip(src) {
+        *d -= s.clone();
+    }
+}
+
+/// The product of `a` and `b` computed in pieces of the length of the
+/// shorter factor, by `mul` on each piece. The pieces keep their side of the
+/// product, so that coefficients need not commute.
+fn unbalanced<K: SemiringElement + Clone>(
+    a: &[K],
+    b: &[K],
+    mul: fn(&[K], &[K]) -> Vec<K>,
+) -> Vec<K> {
+    let n = a.len().min(b.len());
+    let mut c = Vec::new();
+    if a.len() >= b.len() {
+        for (i, piece) in a.chunks(n).enumerate() {
+            add_shifted(&mut c, &mul(piece, b), i * n);
+        }
+    } else {
+        for (i, piece) in b.chunks(n).enumerate() {
+            add_shifted(&mut c, &mul(a, piece), i * n);
+        }
+    }
+    c
+}
+
+/// Karatsuba's method: with `a = a_0 + x^m*a_1` and `b = b_0 + x^m*b_1`, the
+/// middle product `a_0*b_1 + a_1*b_0` is `(a_0 + a_1)*(b_0 + b_1) - a_0*b_0
+/// - a_1*b_1`, for three half-size products instead of four.
+fn karatsuba<K: RingElement + Clone>(a: &[K], b: &[K]) -> Vec<K> {
+    let n = a.len().min(b.len());
+    if n < KARATSUBA_CUTOFF {
+        return schoolbook(a, b);
+    }
+    let m = a.len().max(b.len()).div_ceil(2);
+    if n <= m {
+        return unbalanced(a, b, karatsuba);
+    }
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let mut z1 = karatsuba(&add(a0, a1), &add(b0, b1));
+    sub_shifted(&mut z1, &z0, 0);
+    sub_shifted(&mut z1, &z2, 0);
+    let mut c = z0;
+    add_shifted(&mut c, &z1, m);
+    add_shifted(&mut c, &z2, 2 * m);
+    c
+}
+
+/// Toom–Cook multiplication in three pieces: `a` and `b` are split as
+/// polynomials of degree 2 in `x^k`, their product of degree 4 is
+/// interpolated from its values at `0, 1, -1, -2, ∞`, for five third-size
+/// products instead of nine. The interpolation follows Bodrato, with exact
+/// divisions by 2 and 3.
+fn toom3<K: DivExact + Clone + FromPrimitive>(a: &[K], b: &[K]) -> Vec<K> {
+    let n = a.len().min(b.len());
+    if n < TOOM3_CUTOFF {
+        return karatsuba(a, b);
+    }
+    let k = a.len().max(b.len()).div_ceil(3);
+    if n <= 2 * k {
+        return unbalanced(a, b, toom3);
+    }
+    let two = K::from_u8(2).unwrap();
+    let three = K::from_u8(3).unwrap();
+    let scaled = |c: &K, v: &[K]| -> Vec<K> { v.iter().map(|x| c.clone() * x.clone()).collect() };
+    let neg = |v: &[K]| -> Vec<K> { v.iter().map(|x| K::zero() - x.clone()).collect() };
+    let div = |v: &[K], d: &K| -> Vec<K> {
+        v.iter()
+            .map(|x| x.div_exact(d).expect("inexact division in Toom-3"))
+            .collect()
+    };
+    // The values of `f_0 + f_1*y + f_2*y^2` at `0, 1, -1, -2` and `∞`.
+    let evaluate = |f: &[K]| {
+        let f0 = &f[..k];
+        let f1 = &f[k..2 * k];
+        let f2 = &f[2 * k..];
+        let f02 = add(f0, f2);
+        let p1 = add(&f02, f1);
+        let pm1 = add(&f02, &neg(f1));
+        let mut pm2 = add(f0, &scaled(&(two.clone() * two.clone()), f2));
+        sub_shifted(&mut pm2, &scaled(&two, f1), 0);
+        [f0.to_vec(), p1, pm1, pm2, f2.to_vec()]
+    };
+    let [p0, p1, pm1, pm2, pinf] = evaluate(a);
+    let [q0, q1, qm1, qm2, qinf] = evaluate(b);
+    let r0 = toom3(&p0, &q0);
+    let r1 = toom3(&p1, &q1);
+    let rm1 = toom3(&pm1, &qm1);
+    let rm2 = toom3(&pm2, &qm2);
+    let r4 = toom3(&pinf, &qinf);
+
+    let mut t = rm2;
+    sub_shifted(&mut t, &r1, 0);
+    let mut c3 = div(&t, &three);
+    let mut t = r1;
+    sub_shifted(&mut t, &rm1, 0);
+    let mut c1 = div(&t, &two);
+    let mut c2 = rm1;
+    sub_shifted(&mut c2, &r0, 0);
+    let mut t = c2.clone();
+    sub_shifted(&mut t, &c3, 0);
+    c3 = div(&t, &two);
+    add_shifted(&mut c3, &scaled(&two, &r4), 0);
+    add_shifted(&mut c2, &c1, 0);
+    sub_shifted(&mut c2, &r4, 0);
+    sub_shifted(&mut c1, &c3, 0);
+
+    let mut c = r0;
+    add_shifted(&mut c, &c1, k);
+    add_shifted(&mut c, &c2, 2 * k);
+    add_shifted(&mut c, &c3, 3 * k);
+    add_shifted(&mut c, &r4, 4 * k);
+    c.truncate(a.len() + b.len() - 1);
+    c
+}
+
+/// The product of integer polynomials by the fastest of the methods above
+/// for the size of the factors.
+fn mul_integer(
+    a: &DenseUnivariate<BigInt>,
+    b: &DenseUnivariate<BigInt>,
+) -> DenseUnivariate<BigInt> {
+    let n = a.coeffs.len().min(b.coeffs.len());
+    let bits = a.coeffs.iter().chain(&b.coeffs).map(BigInt::bits).max();
+    if n >= INTEGER_NTT_CUTOFF && n as u64 >= bits.unwrap_or(0) / 2 {
+        a.mul_ntt(b)
+    } else {
+        a.mul_toom3(b)
+    }
+}
+
+/// The product of rational polynomials, as the product of their integer
+/// multiples by the common denominators of their coefficients. At any size,
+/// this is faster than reducing each product of coefficients to lowest
+/// terms as the schoolbook method does.
+fn mul_rational(
+    a: &DenseUnivariate<BigRational>,
+    b: &DenseUnivariate<BigRational>,
+) -> DenseUnivariate<BigRational> {
+    let clear = |f: &DenseUnivariate<BigRational>| {
+        let d = f.coeffs.iter().fold(BigInt::one(), |d, c| d.lcm(c.denom()));
+        let f = f.map(|c| c.numer() * (&d / c.denom()));
+        (f, d)
+    };
+    let ((a, da), (b, db)) = (clear(a), clear(b));
+    let d = da * db;
+    mul_integer(&a, &b).map(|c| BigRational::new(c.clone(), d.clone()))
+}
+
+/// The product `a * b` by the fastest method for the size of the factors,
+/// or `None` unless the coefficients are integers, rationals, residues or
+/// floating-point numbers.
+pub(crate) fn mul_specialized<K: SemiringElement + Clone>(
+    a: &DenseUnivariate<K>,
+    b: &DenseUnivariate<K>,
+) -> Option<DenseUnivariate<K>> {
+    let n = a.coeffs.len().min(b.coeffs.len());
+    if let (Some(a), Some(b)) = (downcast::<K, BigInt>(a), downcast::<K, BigInt>(b)) {
+        return Some(upcast(mul_integer(a, b)));
+    }
+    if let (Some(a), Some(b)) = (downcast::<K, BigRational>(a), downcast::<K, BigRational>(b)) {
+        return Some(upcast(mul_rational(a, b)));
+    }
+    if let (Some(a), Some(b)) = (downcast::<K, Modular>(a), downcast::<K, Modular>(b)) {
+        return Some(upcast(if n >= MODULAR_NTT_CUTOFF {
+            a.mul_ntt(b)
+        } else {
+            a.mul_karatsuba(b)
+        }));
+    }
+    let (a, b) = (downcast::<K, f64>(a)?, downcast::<K, f64>(b)?);
+    Some(upcast(if n >= FFT_CUTOFF {
+        a.mul_fft(b)
+    } else {
+        DenseUnivariate::new(schoolbook(&a.coeffs, &b.coeffs))
+    }))
+}
+
+/// The product of `a` and `b` modulo `x^n` by [`mul_specialized`], or
+/// `None` if the truncated schoolbook product is faster.
+pub(crate) fn mul_trunc_specialized<K: SemiringElement + Clone>(
+    a: &[K],
+    b: &[K],
+    n: usize,
+) -> Option<Vec<K>> {
+    let (a, b) = (&a[..a.len().min(n)], &b[..b.len().min(n)]);
+    let len = a.len().min(b.len());
+    let cutoff = match type_id::<K>() {
+        k if k == TypeId::of::<BigRational>() => 0,
+        k if k == TypeId::of::<f64>() => FFT_CUTOFF,
+        _ => TRUNCATED_CUTOFF,
+    };
+    if len < cutoff {
+        return None;
+    }
+    let a = DenseUnivariate::new(a.to_vec());
+    let b = DenseUnivariate::new(b.to_vec());
+    let mut c = mul_specialized(&a, &b)?.coeffs;
+    c.resize(n, K::zero());
+    Some(c)
+}
+
+impl<K: RingElement + Clone> DenseUnivariate<K> {
+    /// The product of `self` and `rhs` by the fastest method we have for
+    /// their coefficients and size.
+    ///
+    /// Over the integers, the rationals, the prime fields and the
+    /// floating-point numbers this is `self * rhs`, which picks a method by
+    /// the size of the factors. Other coefficients are multiplied by
+    /// Karatsuba's method, which `*` cannot use as it only needs a semiring.
+    pub fn mul_fast(&self, rhs: &Self) -> Self {
+        #[cfg(feature = "flint")]
+        if let Some(product) = crate::flint::mul(self, rhs) {
+            return product;
+        }
+        mul_specialized(self, rhs).unwrap_or_else(|| self.mul_karatsuba(rhs))
+    }
+
+    /// The product of `self` and `rhs` by Karatsuba's method, which falls
+    /// back to the schoolbook method for small factors.
+    pub fn mul_karatsuba(&self, rhs: &Self) -> Self {
+        Self::new(karatsuba(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+impl<K: DivExact + Clone + FromPrimitive> DenseUnivariate<K> {
+    /// The product of `self` and `rhs` by Toom–Cook multiplication in three
+    /// pieces, which falls back to Karatsuba's method and then to the
+    /// schoolbook method as the factors get smaller.
+    ///
+    /// This divides by 2 and 3, so the coefficients must not have
+    /// characteristic 2 or 3.
+    pub fn mul_toom3(&self, rhs: &Self) -> Self {
+        Self::new(toom3(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigRational, Signed, ToPrimitive};
+
+    use super::*;
+    use crate::multimodular::PrimeField;
+
+    /// `n` pseudorandom integers below `2^bits` in absolute value, of either
+    /// sign.
+    fn pseudorandom(n: usize, bits: u32, seed: u64) -> Vec<BigInt> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                let mut c = BigInt::from(0);
+                for _ in 0..bits.div_ceil(32) {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    c = (c << 32) + (state >> 32);
+                }
+                c >>= bits.div_ceil(32) * 32 - bits;
+                if state & 1 == 1 { -c } else { c }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fast_integer_products_match_the_schoolbook_method() {
+        for (n, m, bits) in [(20, 30, 40), (70, 90, 20), (70, 90, 300), (200, 150, 8)] {
+            let a = pseudorandom(n, bits, 1);
+            let b = pseudorandom(m, bits, 2);
+            let expected = DenseUnivariate::new(schoolbook(&a, &b));
+            let (a, b) = (DenseUnivariate::new(a), DenseUnivariate::new(b));
+            assert_eq!(a.clone() * b.clone(), expected);
+            assert_eq!(a.mul_fast(&b), expected);
+            assert_eq!(a.mul_karatsuba(&b), expected);
+            assert_eq!(a.mul_toom3(&b), expected);
+            assert_eq!(a.mul_ntt(&b), expected);
+        }
+    }
+
+    #[test]
+    fn fast_modular_products_match_the_schoolbook_method() {
+        let field = PrimeField::new(4611686018427388039).unwrap();
+        for (n, m) in [(20, 30), (130, 200), (300, 5)] {
+            let a: Vec<_> = pseudorandom(n, 62, 3)
+                .iter()
+                .map(|c| field.from_integer(c))
+                .collect();
+            let b: Vec<_> = pseudorandom(m, 62, 4)
+                .iter()
+                .map(|c| field.from_integer(c))
+                .collect();
+            let expected = DenseUnivariate::new(schoolbook(&a, &b));
+            let (a, b) = (DenseUnivariate::new(a), DenseUnivariate::new(b));
+            assert_eq!(a.clone() * b.clone(), expected);
+            assert_eq!(a.mul_karatsuba(&b), expected);
+            assert_eq!(a.mul_ntt(&b), expected);
+        }
+    }
+
+    #[test]
+    fn fast_rational_products_match_the_schoolbook_method() {
+        let rationals = |n, seed| -> Vec<BigRational> {
+            let numer = pseudorandom(n, 30, seed);
+            let denom = pseudorandom(n, 20, seed + 1);
+            numer
+                .into_iter()
+                .zip(denom)
+                .map(|(p, q)| BigRational::new(p, q.abs() + 1))
+                .collect()
+        };
+        let (a, b) = (rationals(25, 5), rationals(40, 7));
+        let expected = DenseUnivariate::new(schoolbook(&a, &b));
+        let (a, b) = (DenseUnivariate::new(a), DenseUnivariate::new(b));
+        assert_eq!(a.clone() * b.clone(), expected);
+        assert_eq!(a.mul_karatsuba(&b), expected);
+    }
+
+    #[test]
+    fn fast_floating_point_products_are_close_to_the_schoolbook_method() {
+        let floats = |n, seed| -> Vec<f64> {
+            pseudorandom(n, 20, seed)
+                .iter()
+                .map(|c| c.to_f64().unwrap() / 1e6)
+                .collect()
+        };
+        let (a, b) = (floats(1500, 8), floats(1100, 9));
+        let expected = schoolbook(&a, &b);
+        let product = DenseUnivariate::new(a) * DenseUnivariate::new(b);
+        for (x, y) in product.coeffs().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9, "{x} != {y}");
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use num::FromPrimitive;
+
+use crate::univariate::DenseUnivariate;
+use crate::{FieldElement, RingElement};
+
+/// Below this many coefficients, the classical shift is faster than divide
+/// and conquer.
+const SHIFT_CUTOFF: usize = 64;
+
+/// Replaces `c` by the coefficients of `f(x + a)`, where `f` has coefficients
+/// `c`, by repeated synthetic division by `x - a`.
+fn shift_in_place<K: RingElement + Clone>(c: &mut [K], a: &K) {
+    let n = c.len();
+    for i in 0..n {
+        for j in (i..n - 1).rev() {
+            let t = a.clone() * c[j + 1].clone();
+            c[j] += t;
+        }
+    }
+}
+
+impl<K: RingElement + Clone> DenseUnivariate<K> {
+    /// The Taylor shift `f(x + a)` of `f = self`, by the classical method for
+    /// small degrees and by divide and conquer for large ones.
+    pub fn shift(&self, a: &K) -> Self {
+        if self.coeffs.len() <= SHIFT_CUTOFF {
+            self.shift_classical(a)
+        } else {
+            self.shift_divide_and_conquer(a)
+        }
+    }
+
+    /// The Taylor shift `f(x + a)` by Horner's rule with synthetic division,
+    /// in a quadratic number of additions and multiplications by `a`.
+    pub fn shift_classical(&self, a: &K) -> Self {
+        let mut c = self.coeffs.clone();
+        shift_in_place(&mut c, a);
+        Self::new(c)
+    }
+
+    /// The Taylor shift `f(x + a)` by splitting `f = f_0 + x^m*f_1` with `m`
+    /// a power of two, so that `f(x + a) = f_0(x + a) + (x + a)^m*f_1(x + a)`,
+    /// with the powers `(x + a)^(2^k)` computed once by repeated squaring.
+    ///
+    /// This uses one multiplication of each size per level, by
+    /// [`Self::mul_fast`], so is softly linear when multiplication is fast.
+    /// It works over any ring.
+    pub fn shift_divide_and_conquer(&self, a: &K) -> Self {
+        let n = self.coeffs.len();
+        let mut powers = vec![Self::new(vec![a.clone(), K::one()])];
+        while 1 << powers.len() < n {
+            let p = powers.last().unwrap();
+            powers.push(p.mul_fast(p));
+        }
+        Self::shift_rec(&self.coeffs, a, &powers)
+    }
+
+    /// The shift of the polynomial with coefficients `c`, where `c` has at
+    /// most `2^(k+1)` entries for `k = powers.len() - 1`.
+    fn shift_rec(c: &[K], a: &K, powers: &[Self]) -> Self {
+        if c.len() <= SHIFT_CUTOFF || powers.is_empty() {
+            let mut c = c.to_vec();
+            shift_in_place(&mut c, a);
+            return Self::new(c);
+        }
+        let (last, lower) = powers.split_last().unwrap();
+        let m = 1 << lower.len();
+        if c.len() <= m {
+            return Self::shift_rec(c, a, lower);
+        }
+        let (lo, hi) = c.split_at(m);
+        Self::shift_rec(lo, a, lower) + last.mul_fast(&Self::shift_rec(hi, a, lower))
+    }
+}
+
+impl<K: FieldElement + Clone + FromPrimitive> DenseUnivariate<K> {
+    /// The Taylor shift `f(x + a)` as a single convolution: the coefficient
+    /// of `x^i` in the result is `1/i! * ∑ (j!*f_j) * a^(j-i)/(j-i)!`.
+    ///
+    /// This needs `n!` to be invertible for the degree `n` of `self`, as in
+    /// characteristic zero.
+    pub fn shift_convolution(&self, a: &K) -> Self {
+        let n = self.coeffs.len();
+        let mut factorials = vec![K::one()];
+        for i in 1..n {
+            let f = factorials[i - 1].clone() * K::from_usize(i).unwrap();
+            factorials.push(f);
+        }
+        let g = Self::new(
+            self.coeffs
+                .iter()
+                .zip(&factorials)
+                .rev()
+                .map(|(c, f)| c.clone() * f.clone())
+                .collect(),
+        );
+        let mut h = Vec::with_capacity(n);
+        let mut power = K::one();
+        for f in &factorials {
+            h.push(power.clone() / f.clone());
+            power *= a.clone();
+        }
+        let w = g.mul_fast(&Self::new(h));
+        Self::new(
+            (0..n)
+                .map(|i| w.coeff(n - 1 - i) / factorials[i].clone())
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use std::any::TypeId;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+
+use crate::univariate::DenseUnivariate;
+
+/// The `TypeId` of `T` with its lifetimes erased, which unlike
+/// [`TypeId::of`] does not require `T: 'static`. It tells whether `T` is a
+/// given type without lifetimes, such as [`num::BigInt`] or
+/// [`crate::multimodular::Modular`].
+pub(crate) fn type_id<T: ?Sized>() -> TypeId {
+    trait NonStatic {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+    impl<T: ?Sized> NonStatic for PhantomData<T> {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+    let phantom = PhantomData::<T>;
+    // SAFETY: the lifetimes of `T` affect neither the empty `PhantomData`
+    // nor the vtable, whose `type_id` only names `T`.
+    let phantom: &(dyn NonStatic + 'static) =
+        unsafe { std::mem::transmute(&phantom as &dyn NonStatic) };
+    phantom.type_id()
+}
+
+/// `f` as a polynomial over `L`, if `K` is `L`.
+pub(crate) fn downcast<K, L: 'static>(f: &DenseUnivariate<K>) -> Option<&DenseUnivariate<L>> {
+    // SAFETY: `K` is `L`, as `L` has no lifetimes to erase.
+    (type_id::<K>() == TypeId::of::<L>())
+        .then(|| unsafe { &*(f as *const DenseUnivariate<K>).cast::<DenseUnivariate<L>>() })
+}
+
+/// `f` as a polynomial over `K`, which must be `L`.
+pub(crate) fn upcast<K, L: 'static>(f: DenseUnivariate<L>) -> DenseUnivariate<K> {
+    assert!(type_id::<K>() == TypeId::of::<L>());
+    // SAFETY: `K` is `L`, and the original `f` is not dropped.
+    unsafe { std::mem::transmute_copy(&ManuallyDrop::new(f)) }
+}
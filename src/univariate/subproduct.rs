@@ -0,0 +1,305 @@
+use std::any::TypeId;
+
+use num::{BigRational, FromPrimitive, One, Zero};
+
+use crate::FieldElement;
+use crate::error::ChidogError;
+use crate::series::{inv_trunc, mul_trunc};
+use crate::univariate::DenseUnivariate;
+use crate::univariate::specialize::type_id;
+
+/// Below this many points, or this many coefficients of the polynomial,
+/// evaluating at each point by Horner's rule is faster than reducing down a
+/// subproduct tree.
+const MULTIPOINT_POINTS: usize = 32;
+const MULTIPOINT_COEFFS: usize = 512;
+
+/// The level of the subproduct tree, with nodes of `2^HORNER_LEVEL` points,
+/// below which evaluating the remainders by Horner's rule is faster than
+/// reducing them further.
+const HORNER_LEVEL: usize = 5;
+
+/// Division with remainder by a fixed polynomial `b` of degree `m`, with
+/// the quotient computed as a reversed power series quotient. The inverse
+/// of the reversal of `b` modulo `x^k` is computed once, so that each
+/// remainder of a polynomial of degree below `m + k` takes two
+/// multiplications.
+pub(crate) struct NewtonDivisor<K> {
+    divisor: DenseUnivariate<K>,
+    inverse: Vec<K>,
+}
+
+impl<K: FieldElement + Clone> NewtonDivisor<K> {
+    /// The divisor `b`, which must be nonzero, for dividends of degree below
+    /// `deg b + k`.
+    pub(crate) fn new(b: &DenseUnivariate<K>, k: usize) -> Self {
+        let rev_b: Vec<K> = b.coeffs().iter().rev().cloned().collect();
+        Self {
+            divisor: b.clone(),
+            inverse: inv_trunc(&rev_b, k.max(1)),
+        }
+    }
+
+    /// The remainder of `a` by the divisor.
+    pub(crate) fn rem(&self, a: &DenseUnivariate<K>) -> DenseUnivariate<K> {
+        let (Some(n), Some(m)) = (a.degree(), self.divisor.degree()) else {
+            return a.clone();
+        };
+        if n < m {
+            return a.clone();
+        }
+        let k = n - m + 1;
+        assert!(
+            k <= self.inverse.len(),
+            "dividend of degree {n} is too large"
+        );
+        let rev_a: Vec<K> = a.coeffs().iter().rev().cloned().collect();
+        let mut q = mul_trunc(&rev_a, &self.inverse, k);
+        q.reverse();
+        a.clone() - DenseUnivariate::new(q).mul_fast(&self.divisor)
+    }
+}
+
+/// The remainder of `a` by `b`, with the quotient computed as a reversed
+/// power series quotient, so in the time of a constant number of
+/// multiplications.
+///
+/// A quotient longer than `b` is found in blocks of `deg b` coefficients
+/// from the top, as by Horner's rule in `x^(deg b)`, so that the time is
+/// linear in the degree of `a` for a fixed `b`.
+pub(crate) fn rem_newton<K: FieldElement + Clone>(
+    a: &DenseUnivariate<K>,
+    b: &DenseUnivariate<K>,
+) -> DenseUnivariate<K> {
+    let (Some(n), Some(m)) = (a.degree(), b.degree()) else {
+        return a.clone();
+    };
+    if n < m {
+        return a.clone();
+    }
+    if m == 0 {
+        return DenseUnivariate::zero();
+    }
+    if n - m < m {
+        return NewtonDivisor::new(b, n - m + 1).rem(a);
+    }
+    let divisor = NewtonDivisor::new(b, m);
+    a.coeffs()
+        .chunks(m)
+        .rev()
+        .fold(DenseUnivariate::zero(), |r, block| {
+            let mut c = block.to_vec();
+            c.resize(m, K::zero());
+            c.extend_from_slice(r.coeffs());
+            divisor.rem(&DenseUnivariate::new(c))
+        })
+}
+
+/// The subproduct tree of points `a_0, ..., a_(n-1)`: the leaves are the
+/// linear polynomials `x - a_i`, and each inner node is the product of its
+/// two children, so that the root is `∏ (x - a_i)`.
+///
+/// Levels are stored from the leaves up. A level of odd length carries its
+/// last node unchanged to the next level.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubproductTree<K> {
+    levels: Vec<Vec<DenseUnivariate<K>>>,
+}
+
+impl<K: FieldElement + Clone> SubproductTree<K> {
+    pub fn new(points: &[K]) -> Self {
+        let leaves: Vec<_> = points
+            .iter()
+            .map(|a| DenseUnivariate::new(vec![K::zero() - a.clone(), K::one()]))
+            .collect();
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [l, r] => l.mul_fast(r),
+                    [l] => l.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    /// The product `∏ (x - a_i)` over all points, which is 1 for no points.
+    pub fn root(&self) -> DenseUnivariate<K> {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .cloned()
+            .unwrap_or_else(DenseUnivariate::one)
+    }
+
+    /// The values `f(a_i)` at the points of the tree, by reducing `f` modulo
+    /// the nodes from the root down to the nodes of `2^HORNER_LEVEL` points,
+    /// and evaluating the remainders there by Horner's rule.
+    pub fn eval(&self, f: &DenseUnivariate<K>) -> Vec<K> {
+        let Some((top, lower)) = self.levels.split_last() else {
+            return Vec::new();
+        };
+        let bottom = HORNER_LEVEL.min(lower.len());
+        let mut rems: Vec<_> = top.iter().map(|m| rem_newton(f, m)).collect();
+        for level in lower[bottom..].iter().rev() {
+            rems = level
+                .iter()
+                .enumerate()
+                .map(|(j, m)| rem_newton(&rems[j / 2], m))
+                .collect();
+        }
+        // The node `j` at level `bottom` is the product of the leaves
+        // `x - a_i` for `i` from `j*2^bottom` up to `(j + 1)*2^bottom`.
+        self.levels[0]
+            .chunks(1 << bottom)
+            .zip(&rems)
+            .flat_map(|(leaves, r)| leaves.iter().map(|l| r.eval(&(K::zero() - l.coeff(0)))))
+            .collect()
+    }
+
+    /// The linear combination `∑ c_i * root / (x - a_i)`, built up the tree
+    /// as `l*M_r + r*M_l` at each inner node with children `M_l` and `M_r`.
+    fn combine(&self, weights: &[K]) -> DenseUnivariate<K> {
+        let mut sums: Vec<_> = weights
+            .iter()
+            .map(|c| DenseUnivariate::constant(c.clone()))
+            .collect();
+        for level in &self.levels[..self.levels.len().saturating_sub(1)] {
+            sums = sums
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(s, m)| match (s, m) {
+                    ([sl, sr], [ml, mr]) => sl.mul_fast(mr) + sr.mul_fast(ml),
+                    ([s], _) => s.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        sums.pop().unwrap_or_else(DenseUnivariate::zero)
+    }
+}
+
+impl<K: FieldElement + Clone> DenseUnivariate<K> {
+    /// The values of `self` at all of `points`.
+    ///
+    /// This reduces `self` down a subproduct tree, which takes a constant
+    /// number of polynomial multiplications of each size per level of the
+    /// tree, and so softly linear time when multiplication is fast. Points
+    /// beyond the number of coefficients of `self` go in separate trees.
+    ///
+    /// Horner's rule at each point is faster for fewer than a few dozen
+    /// points or a few hundred coefficients. It is used at every size over
+    /// the rationals, where the coefficients of the remainders grow much
+    /// larger than the values, and over the floating-point numbers, where
+    /// the power series inverses behind the remainders grow like the powers
+    /// of the reciprocals of the points, and overflow for points near zero.
+    pub fn eval_multipoint(&self, points: &[K]) -> Vec<K> {
+        let horner = [
+            TypeId::of::<f32>(),
+            TypeId::of::<f64>(),
+            TypeId::of::<BigRational>(),
+        ];
+        if points.len() < MULTIPOINT_POINTS
+            || self.coeffs.len() < MULTIPOINT_COEFFS
+            || horner.contains(&type_id::<K>())
+        {
+            return points.iter().map(|a| self.eval(a)).collect();
+        }
+        points
+            .chunks(self.coeffs.len())
+            .flat_map(|batch| SubproductTree::new(batch).eval(self))
+            .collect()
+    }
+}
+
+impl<K: FieldElement + Clone + FromPrimitive> DenseUnivariate<K> {
+    /// The polynomial of degree less than `points.len()` taking the given
+    /// values at the given points, which must be distinct.
+    ///
+    /// Writing `m` for the product of the `x - a_i`, this is the Lagrange
+    /// formula `∑ v_i/m'(a_i) * m/(x - a_i)`, where the `m'(a_i)` are found
+    /// by multipoint evaluation and the sum is built up the subproduct tree.
+    pub fn interpolate(points: &[K], values: &[K]) -> Result<Self, ChidogError> {
+        if points.len() != values.len() {
+            return Err(ChidogError::InvalidInput(format!(
+                "{} points for {} values",
+                points.len(),
+                values.len()
+            )));
+        }
+        let tree = SubproductTree::new(points);
+        let derivatives = tree.eval(&tree.root().derivative());
+        if derivatives.iter().any(K::is_zero) {
+            return Err(ChidogError::InvalidInput(
+                "interpolation points must be distinct".to_string(),
+            ));
+        }
+        let weights: Vec<K> = values
+            .iter()
+            .zip(derivatives)
+            .map(|(v, d)| v.clone() / d)
+            .collect();
+        Ok(tree.combine(&weights))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multimodular::{Modular, PrimeField};
+
+    /// `n` pseudorandom residues modulo the prime of `field`.
+    fn pseudorandom(field: &PrimeField, n: usize, seed: u64) -> Vec<Modular> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                field.element((state >> 2) as i64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn multipoint_evaluation_matches_horner() {
+        let field = PrimeField::new(1_000_003).unwrap();
+        for (n, m) in [(10, 5), (600, 70), (1500, 40), (600, 1300)] {
+            let f = DenseUnivariate::new(pseudorandom(&field, n, 1));
+            let points = pseudorandom(&field, m, 2);
+            let expected: Vec<_> = points.iter().map(|a| f.eval(a)).collect();
+            assert_eq!(f.eval_multipoint(&points), expected);
+            assert_eq!(SubproductTree::new(&points).eval(&f), expected);
+        }
+    }
+
+    #[test]
+    fn floating_point_multipoint_evaluation_stays_finite() {
+        let f = DenseUnivariate::new((0..2000).map(|i| ((i * 37) % 101) as f64 / 100.0).collect());
+        let points: Vec<f64> = (0..100).map(|i| i as f64 / 100.0).collect();
+        for (v, a) in f.eval_multipoint(&points).iter().zip(&points) {
+            assert!(v.is_finite());
+            assert!((v - f.eval(a)).abs() <= 1e-9 * f.eval(a).abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn interpolation_inverts_multipoint_evaluation() {
+        let f = DenseUnivariate::new(
+            (0..40)
+                .map(|i| BigRational::from_integer(((i * 37) % 101 - 50).into()))
+                .collect(),
+        );
+        let points: Vec<_> = (0..40)
+            .map(|i| BigRational::from_integer(i.into()))
+            .collect();
+        let values = f.eval_multipoint(&points);
+        assert_eq!(DenseUnivariate::interpolate(&points, &values).unwrap(), f);
+    }
+}
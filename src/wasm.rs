@@ -0,0 +1,98 @@
+use num::BigRational;
+use wasm_bindgen::prelude::*;
+
+use crate::handle::{OwnedPolynomial, SharedRing, shared_ring};
+
+/// A polynomial ring over the rationals, shared by the polynomials in it, as
+/// JavaScript cannot hold the borrows of [`Polynomial`](crate::Polynomial).
+#[wasm_bindgen(js_name = Ring)]
+pub struct RingHandle {
+    ring: SharedRing,
+}
+
+/// A polynomial of a [`RingHandle`].
+#[wasm_bindgen(js_name = Polynomial)]
+#[derive(Clone)]
+pub struct PolynomialHandle(OwnedPolynomial);
+
+#[wasm_bindgen(js_class = Ring)]
+impl RingHandle {
+    /// The ring in the variables `vars`, in decreasing order of
+    /// significance.
+    #[wasm_bindgen(constructor)]
+    pub fn new(vars: Vec<String>) -> Self {
+        Self {
+            ring: shared_ring(vars),
+        }
+    }
+
+    /// The names of the variables.
+    #[wasm_bindgen(getter)]
+    pub fn vars(&self) -> Vec<String> {
+        self.ring.vars.clone()
+    }
+
+    /// The polynomial written as `input`, such as `x^2*y - 3/4*z + 1`.
+    pub fn parse(&self, input: &str) -> Result<PolynomialHandle, JsError> {
+        Ok(PolynomialHandle(OwnedPolynomial::parse(&self.ring, input)?))
+    }
+
+    /// The constant polynomial `c`, written as an integer or a fraction.
+    pub fn constant(&self, c: &str) -> Result<PolynomialHandle, JsError> {
+        let c: BigRational = c
+            .parse()
+            .map_err(|_| JsError::new(&format!("invalid number `{c}`")))?;
+        Ok(PolynomialHandle(OwnedPolynomial::constant(&self.ring, c)))
+    }
+}
+
+#[wasm_bindgen(js_class = Polynomial)]
+impl PolynomialHandle {
+    /// The sum `self + other`.
+    pub fn add(&self, other: &Self) -> Result<Self, JsError> {
+        Ok(Self(self.0.add(&other.0)?))
+    }
+
+    /// The difference `self - other`.
+    pub fn sub(&self, other: &Self) -> Result<Self, JsError> {
+        Ok(Self(self.0.sub(&other.0)?))
+    }
+
+    /// The product `self * other`.
+    pub fn mul(&self, other: &Self) -> Result<Self, JsError> {
+        Ok(Self(self.0.mul(&other.0)?))
+    }
+
+    /// The negation `-self`.
+    pub fn neg(&self) -> Self {
+        Self(self.0.neg())
+    }
+
+    /// The power `self^n`.
+    pub fn pow(&self, n: u32) -> Self {
+        Self(self.0.pow(n))
+    }
+
+    /// Whether `self` is zero.
+    #[wasm_bindgen(js_name = isZero)]
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Whether `self` and `other` are equal polynomials of the same ring.
+    pub fn equals(&self, other: &Self) -> bool {
+        self.0.equals(&other.0)
+    }
+
+    /// The total degree, or -1 for zero.
+    pub fn degree(&self) -> i64 {
+        self.0.degree().map_or(-1, |d| d as i64)
+    }
+
+    /// `self` written as for [`RingHandle::parse`], with its terms in
+    /// decreasing lexicographic order, such as `x^2*y-3/4*z+1`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_infix()
+    }
+}
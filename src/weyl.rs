@@ -0,0 +1,630 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use num::{FromPrimitive, One, PrimInt, Unsigned, Zero};
+use smallvec::smallvec;
+
+use crate::free::FreePolynomial;
+use crate::{
+    Monomial, Polynomial, Powers, Ring, RingElement, Semiring, SemiringElement, TermHasher,
+};
+
+/// A variable `my_ring: WeylAlgebra<R, V>` represents the Weyl algebra over a
+/// base ring `R` in the variables `vars`, that is, the algebra of linear
+/// differential operators with polynomial coefficients. Its elements are of
+/// type `WeylElement<'_, R, V, K, P>`, with coefficients valued in `K`.
+///
+/// The algebra is generated by the variables `x_i` and the derivations `∂_i`,
+/// subject to the relations `∂_i*x_i = x_i*∂_i + 1`, with all other pairs of
+/// generators commuting. Every element has a unique normal form in which all
+/// variables stand to the left of all derivations. Since the algebra is not
+/// commutative, it sets [`Semiring::COMMUTATIVE`] to `false`.
+pub struct WeylAlgebra<'a, R, V> {
+    pub vars: Vec<V>,
+    pub base: &'a R,
+}
+
+/// An element of a Weyl algebra in normal form, as a hash map associating to
+/// each normally ordered monomial `x^a*∂^b` a nonzero coefficient.
+///
+/// The monomial `x^a*∂^b` is stored as a [`Monomial`] with `2n` powers: the
+/// exponents `a` of the variables followed by the exponents `b` of the
+/// derivations.
+///
+/// Since `Zero::zero` and `One::one` are called without access to an
+/// algebra, constants in `K` may also be stored detached from any algebra. A
+/// detached constant `c` is attached as `c*x^0*∂^0` to the algebra of the
+/// other operand when combined with an element of an algebra.
+pub struct WeylElement<'a, R, V, K, P> {
+    repr: Repr<'a, R, V, K, P>,
+}
+
+enum Repr<'a, R, V, K, P> {
+    Constant(K),
+    Terms {
+        elem_of: &'a WeylAlgebra<'a, R, V>,
+        terms: HashMap<Monomial<P>, K, TermHasher>,
+    },
+}
+
+impl<R, V, K, P> Clone for WeylElement<'_, R, V, K, P>
+where
+    K: Clone,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Constant(c) => Repr::Constant(c.clone()),
+            Repr::Terms { elem_of, terms } => Repr::Terms {
+                elem_of,
+                terms: terms.clone(),
+            },
+        };
+        Self { repr }
+    }
+}
+
+impl<'a, R, V> WeylAlgebra<'a, R, V> {
+    pub fn new(vars: Vec<V>, base: &'a R) -> Self {
+        Self { vars, base }
+    }
+
+    /// The term `c*x^a*∂^b`, where `m` holds the exponents `a` followed by
+    /// `b`, or zero if `c` is zero.
+    pub fn term<K, P>(&'a self, m: Monomial<P>, c: K) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        assert_eq!(
+            m.powers.len(),
+            2 * self.vars.len(),
+            "wrong number of exponents"
+        );
+        let mut terms = HashMap::default();
+        if !self.base.is_zero(&c) {
+            terms.insert(m, c);
+        }
+        self.with_terms(terms)
+    }
+
+    pub fn zero<K, P>(&'a self) -> WeylElement<'a, R, V, K, P> {
+        self.with_terms(HashMap::default())
+    }
+
+    fn with_terms<K, P>(
+        &'a self,
+        terms: HashMap<Monomial<P>, K, TermHasher>,
+    ) -> WeylElement<'a, R, V, K, P> {
+        WeylElement {
+            repr: Repr::Terms {
+                elem_of: self,
+                terms,
+            },
+        }
+    }
+
+    pub fn constant<K, P>(&'a self, c: K) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let powers = smallvec![P::zero(); 2 * self.vars.len()];
+        self.term(Monomial { powers }, c)
+    }
+
+    pub fn one<K, P>(&'a self) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        self.constant(K::one())
+    }
+
+    /// The generator with index `j` among `x_1, ..., x_n, ∂_1, ..., ∂_n`.
+    fn generator<K, P>(&'a self, j: usize) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut powers = smallvec![P::zero(); 2 * self.vars.len()];
+        powers[j] = P::one();
+        self.term(Monomial { powers }, K::one())
+    }
+
+    /// The `i`-th variable `x_i`.
+    pub fn x<K, P>(&'a self, i: usize) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        assert!(i < self.vars.len(), "variable index out of range");
+        self.generator(i)
+    }
+
+    /// The derivation `∂_i` with respect to the `i`-th variable.
+    pub fn d<K, P>(&'a self, i: usize) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement,
+        P: Hash + PrimInt + Unsigned,
+    {
+        assert!(i < self.vars.len(), "variable index out of range");
+        self.generator(self.vars.len() + i)
+    }
+
+    /// The normal form of a non-commutative polynomial in the generators,
+    /// whose letters `0..n` stand for the variables `x_i` and whose letters
+    /// `n..2n` stand for the derivations `∂_i`.
+    pub fn normal_form<W, K, P>(
+        &'a self,
+        f: &FreePolynomial<'_, R, W, K>,
+    ) -> WeylElement<'a, R, V, K, P>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone + FromPrimitive,
+        P: Hash + PrimInt + Unsigned,
+    {
+        let mut result = self.zero();
+        for (w, c) in f.terms() {
+            let mut term = self.constant(c.clone());
+            for &j in w.letters.iter() {
+                assert!(j < 2 * self.vars.len(), "letter is not a generator");
+                term *= self.generator(j);
+            }
+            result += term;
+        }
+        result
+    }
+
+    /// The terms of `x` in this algebra, as pairs of a monomial `x^a*∂^b`
+    /// and its nonzero coefficient.
+    pub fn terms<K, P>(&'a self, x: &WeylElement<'a, R, V, K, P>) -> Vec<(Monomial<P>, K)>
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        x.clone().into_terms(self).into_iter().collect()
+    }
+
+    /// The coefficient of the normally ordered monomial `m` in `x`.
+    pub fn coeff<K, P>(&'a self, x: &WeylElement<'a, R, V, K, P>, m: &Monomial<P>) -> K
+    where
+        R: Semiring<K>,
+        K: SemiringElement + Clone,
+        P: Hash + PrimInt + Unsigned,
+    {
+        match &x.repr {
+            Repr::Constant(c) if m.powers.iter().all(P::is_zero) => c.clone(),
+            Repr::Constant(_) => K::zero(),
+            Repr::Terms { terms, .. } => terms.get(m).cloned().unwrap_or_else(K::zero),
+        }
+    }
+}
+
+/// The coefficient `k!*C(b, k)*C(c, k)` of `x^(c-k)*∂^(b-k)` in the normal
+/// form of `∂^b*x^c`.
+fn reorder_coeff(b: usize, c: usize, k: usize) -> usize {
+    let mut t: usize = 1;
+    for j in 1..=k {
+        t = t
+            .checked_mul(b - j + 1)
+            .and_then(|t| (t / j).checked_mul(c - j + 1))
+            .expect("Weyl algebra coefficient overflow");
+    }
+    t
+}
+
+/// Adds `c*m` to `terms`, removing the term if it cancels in `base`.
+fn add_term<R, K, P>(
+    base: &R,
+    terms: &mut HashMap<Monomial<P>, K, TermHasher>,
+    m: Monomial<P>,
+    c: K,
+) where
+    R: Semiring<K>,
+    K: SemiringElement,
+    P: Hash + PrimInt + Unsigned,
+{
+    match terms.entry(m) {
+        Entry::Occupied(mut entry) => {
+            if base.add_into(entry.get_mut(), c) {
+                entry.remove();
+            }
+        }
+        Entry::Vacant(entry) => {
+            if !base.is_zero(&c) {
+                entry.insert(c);
+            }
+        }
+    }
+}
+
+impl<'a, R, V, K, P> WeylElement<'a, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The algebra of `self` and `other`, unless both are detached
+    /// constants.
+    fn algebra_of(&self, other: &Self) -> Option<&'a WeylAlgebra<'a, R, V>> {
+        match (&self.repr, &other.repr) {
+            (Repr::Terms { elem_of, .. }, _) | (_, Repr::Terms { elem_of, .. }) => Some(elem_of),
+            _ => None,
+        }
+    }
+
+    /// The terms of `self` in `algebra`.
+    fn into_terms(self, algebra: &'a WeylAlgebra<'a, R, V>) -> HashMap<Monomial<P>, K, TermHasher> {
+        match self.repr {
+            Repr::Constant(c) => algebra.constant(c).into_terms(algebra),
+            Repr::Terms { terms, .. } => terms,
+        }
+    }
+
+    /// The order of `self` as a differential operator, that is, its total
+    /// degree in the derivations, or `None` for zero.
+    pub fn order(&self) -> Option<usize> {
+        match &self.repr {
+            Repr::Constant(c) => (!c.is_zero()).then_some(0),
+            Repr::Terms { elem_of, terms } => {
+                let n = elem_of.vars.len();
+                terms
+                    .keys()
+                    .map(|m| m.powers[n..].iter().map(|p| p.to_usize().unwrap()).sum())
+                    .max()
+            }
+        }
+    }
+}
+
+impl<'a, R, V, K, P> WeylElement<'a, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    /// The result of applying `self` as a differential operator to the
+    /// polynomial `f`, whose ring has the same variables.
+    pub fn apply<'b, W>(&self, f: &Polynomial<'b, R, W, K, P>) -> Polynomial<'b, R, W, K, P> {
+        let base = f.elem_of.base;
+        let (n, operator) = match &self.repr {
+            Repr::Constant(c) => {
+                let terms = f
+                    .terms
+                    .iter()
+                    .map(|(m, k)| (m.clone(), c.clone() * k.clone()))
+                    .filter(|(_, k)| !base.is_zero(k))
+                    .collect();
+                return Polynomial {
+                    elem_of: f.elem_of,
+                    terms,
+                };
+            }
+            Repr::Terms { elem_of, terms } => (elem_of.vars.len(), terms),
+        };
+        let mut terms = HashMap::default();
+        for (m, c) in operator.iter() {
+            for (fm, fc) in f.terms.iter() {
+                // `x^a*∂^b` maps `x^e` to `e!/(e-b)!*x^(e-b+a)`, or zero if
+                // some `b_i > e_i`.
+                let mut coeff = c.clone() * fc.clone();
+                let mut powers = Powers::with_capacity(n);
+                for i in 0..n {
+                    let (a, b, e) = (m.powers[i], m.powers[n + i], fm.powers[i]);
+                    if b > e {
+                        coeff = K::zero();
+                        break;
+                    }
+                    for j in 0..b.to_usize().unwrap() {
+                        coeff *= K::from_usize(e.to_usize().unwrap() - j).unwrap();
+                    }
+                    powers.push(e - b + a);
+                }
+                if base.is_zero(&coeff) {
+                    continue;
+                }
+                match terms.entry(Monomial { powers }) {
+                    Entry::Occupied(mut entry) => {
+                        if base.add_into(entry.get_mut(), coeff) {
+                            entry.remove();
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(coeff);
+                    }
+                }
+            }
+        }
+        Polynomial {
+            elem_of: f.elem_of,
+            terms,
+        }
+    }
+}
+
+impl<R, V, K, P> Add for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a + b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (m, c) in rhs.into_terms(algebra) {
+            add_term(algebra.base, &mut terms, m, c);
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K, P> AddAssign for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) + rhs;
+    }
+}
+
+impl<R, V, K, P> Sub for WeylElement<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a - b),
+            };
+        };
+        let mut terms = self.into_terms(algebra);
+        for (m, c) in rhs.into_terms(algebra) {
+            match terms.entry(m) {
+                Entry::Occupied(mut entry) => {
+                    if algebra.base.sub_from(entry.get_mut(), c) {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(K::zero() - c);
+                }
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K, P> SubAssign for WeylElement<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::zero()) - rhs;
+    }
+}
+
+/// The product of normally ordered monomials, by the Leibniz rule
+/// `∂^b*x^c = ∑_k k!*C(b, k)*C(c, k)*x^(c-k)*∂^(b-k)` in each variable.
+impl<R, V, K, P> Mul for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Some(algebra) = self.algebra_of(&rhs) else {
+            let (Repr::Constant(a), Repr::Constant(b)) = (self.repr, rhs.repr) else {
+                unreachable!()
+            };
+            return Self {
+                repr: Repr::Constant(a * b),
+            };
+        };
+        let n = algebra.vars.len();
+        let (lhs, rhs) = (self.into_terms(algebra), rhs.into_terms(algebra));
+        let mut terms = HashMap::default();
+        for (m1, c1) in lhs.iter() {
+            for (m2, c2) in rhs.iter() {
+                let powers: Powers<P> = m1
+                    .powers
+                    .iter()
+                    .zip(m2.powers.iter())
+                    .map(|(p, q)| *p + *q)
+                    .collect();
+                // Expand the reorderings one variable at a time, starting
+                // from the term with no contractions.
+                let mut expansion = vec![(powers, c1.clone() * c2.clone())];
+                for i in 0..n {
+                    let b = m1.powers[n + i].to_usize().unwrap();
+                    let c = m2.powers[i].to_usize().unwrap();
+                    if b == 0 || c == 0 {
+                        continue;
+                    }
+                    let mut next = Vec::new();
+                    for (p, coeff) in expansion.into_iter() {
+                        for k in 0..=b.min(c) {
+                            let mut p = p.clone();
+                            p[i] = p[i] - P::from(k).unwrap();
+                            p[n + i] = p[n + i] - P::from(k).unwrap();
+                            let t = K::from_usize(reorder_coeff(b, c, k)).unwrap();
+                            next.push((p, coeff.clone() * t));
+                        }
+                    }
+                    expansion = next;
+                }
+                for (powers, coeff) in expansion {
+                    add_term(algebra.base, &mut terms, Monomial { powers }, coeff);
+                }
+            }
+        }
+        algebra.with_terms(terms)
+    }
+}
+
+impl<R, V, K, P> MulAssign for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::one()) * rhs;
+    }
+}
+
+impl<R, V, K, P> Zero for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn zero() -> Self {
+        Self {
+            repr: Repr::Constant(K::zero()),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        match &self.repr {
+            Repr::Constant(c) => c.is_zero(),
+            Repr::Terms { terms, .. } => terms.is_empty(),
+        }
+    }
+}
+
+impl<R, V, K, P> One for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    fn one() -> Self {
+        Self {
+            repr: Repr::Constant(K::one()),
+        }
+    }
+}
+
+impl<R, V, K, P> Semiring<WeylElement<'_, R, V, K, P>> for WeylAlgebra<'_, R, V>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+    const COMMUTATIVE: bool = false;
+}
+
+impl<R, V, K, P> Ring<WeylElement<'_, R, V, K, P>> for WeylAlgebra<'_, R, V>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+}
+
+impl<R, V, K, P> SemiringElement for WeylElement<'_, R, V, K, P>
+where
+    R: Semiring<K>,
+    K: SemiringElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+}
+
+impl<R, V, K, P> RingElement for WeylElement<'_, R, V, K, P>
+where
+    R: Ring<K>,
+    K: RingElement + Clone + FromPrimitive,
+    P: Hash + PrimInt + Unsigned,
+{
+}
+
+/// Displays the terms in order of decreasing order and degree, as in
+/// `x^2*∂x+2*x`, and a detached constant as itself.
+impl<R, V, K, P> Display for WeylElement<'_, R, V, K, P>
+where
+    V: Display,
+    K: Display + One + PartialEq,
+    P: Hash + PrimInt + Unsigned + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (elem_of, terms) = match &self.repr {
+            Repr::Constant(c) => return Display::fmt(c, f),
+            Repr::Terms { elem_of, terms } => (elem_of, terms),
+        };
+        let n = elem_of.vars.len();
+        let key = |m: &Monomial<P>| {
+            let d: P = m.powers[n..].iter().fold(P::zero(), |s, p| s + *p);
+            let x: P = m.powers[..n].iter().fold(P::zero(), |s, p| s + *p);
+            (d, x, m.powers.clone())
+        };
+        let mut terms: Vec<_> = terms.iter().collect();
+        terms.sort_by_key(|(m, _)| std::cmp::Reverse(key(m)));
+        if terms.is_empty() {
+            write!(f, "0")?;
+        }
+        for (i, (m, c)) in terms.into_iter().enumerate() {
+            let constant = m.powers.iter().all(|p| p.is_zero());
+            if constant || !c.is_one() {
+                if i > 0 {
+                    write!(f, "{c:+}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+                if constant {
+                    continue;
+                }
+                write!(f, "*")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            let factors = m.powers.iter().enumerate().filter(|(_, p)| !p.is_zero());
+            for (j, (k, p)) in factors.enumerate() {
+                if j > 0 {
+                    write!(f, "*")?;
+                }
+                if k < n {
+                    write!(f, "{}", elem_of.vars[k])?;
+                } else {
+                    write!(f, "∂{}", elem_of.vars[k - n])?;
+                }
+                if !p.is_one() {
+                    write!(f, "^{p}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}